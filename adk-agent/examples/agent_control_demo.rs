@@ -13,12 +13,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Agent Control Features Demo ===\n");
 
     // Example 1: Default behavior (full conversation history)
-    println!("1. Testing IncludeContents::Default (normal agent with memory)");
+    println!("1. Testing IncludeContents::All (normal agent with memory)");
     let _agent_with_memory = LlmAgentBuilder::new("memory_agent")
         .description("Agent that remembers conversation history")
         .model(Arc::new(GeminiModel::new(&api_key, "gemini-2.5-flash")?))
         .instruction("You are a helpful assistant. Remember what the user tells you.")
-        .include_contents(IncludeContents::Default) // Full history
+        .include_contents(IncludeContents::All) // Full history
         .build()?;
 
     println!("   → This agent WILL remember conversation history\n");
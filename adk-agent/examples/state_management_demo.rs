@@ -1,8 +1,8 @@
 #![allow(clippy::result_large_err)]
 use adk_agent::{CustomAgentBuilder, LlmAgentBuilder, SequentialAgent};
 use adk_core::{
-    Agent, CallbackContext, Content, Event, InvocationContext, Memory, Part, ReadonlyContext,
-    Result, RunConfig, Session, State,
+    Agent, CallbackContext, Content, InvocationContext, Part, ReadonlyContext, Result, RunConfig,
+    Session, State,
 };
 use adk_model::gemini::GeminiModel;
 use async_trait::async_trait;
@@ -98,10 +98,6 @@ impl InvocationContext for TestContext {
         unimplemented!()
     }
 
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -225,23 +221,16 @@ async fn main() -> Result<()> {
 
     let state_reader = CustomAgentBuilder::new("state_reader")
         .description("Reads and displays state")
-        .handler(move |_ctx| {
+        .run(move |ctx| {
             let state = all_state.clone();
             async move {
-                let mut event = Event::new("demo-invocation");
-                event.author = "state_reader".to_string();
-
-                let mut text = String::from("State summary:\n");
+                ctx.emit_text("State summary:");
                 for (key, value) in &state {
-                    text.push_str(&format!("- {}: {}\n", key, value));
+                    ctx.emit_text(format!("- {}: {}", key, value));
                 }
-
-                event.llm_response.content = Some(Content {
-                    role: "assistant".to_string(),
-                    parts: vec![Part::Text { text }],
-                });
-
-                Ok(Box::pin(futures::stream::iter(vec![Ok(event)])) as adk_core::EventStream)
+                ctx.set_state("summarized_keys", &state.len())?;
+                ctx.finish();
+                Ok(())
             }
         })
         .build()?;
@@ -258,6 +247,9 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        for (key, value) in &event.actions.state_delta {
+            println!("State Delta: {} = {:?}", key, value);
+        }
     }
 
     println!("\n=== Demo Complete ===");
@@ -29,9 +29,9 @@ use adk_core::{
     AdkError, AfterAgentCallback, AfterModelCallback, AfterToolCallback, AfterToolCallbackFull,
     Agent, Artifacts, BeforeAgentCallback, BeforeModelCallback, BeforeModelResult,
     BeforeToolCallback, CallbackContext, Content, ErrorCategory, ErrorComponent, Event,
-    EventActions, EventStream, GenerateContentConfig, GlobalInstructionProvider, IncludeContents,
-    InstructionProvider, InvocationContext, Llm, LlmRequest, LlmResponse, MemoryEntry,
-    OnToolErrorCallback, Part, ReadonlyContext, RetryBudget, SharedState, Tool,
+    EventActions, EventStream, GenerateContentConfig, GlobalInstruction, GlobalInstructionProvider,
+    IncludeContents, InstructionProvider, InvocationContext, Llm, LlmRequest, LlmResponse,
+    MemoryEntry, OnToolErrorCallback, Part, ReadonlyContext, RetryBudget, SharedState, Tool,
     ToolCallbackContext, ToolConfirmationDecision, ToolConfirmationHandler, ToolConfirmationPolicy,
     ToolConfirmationRequest, ToolContext, ToolOutcome, Toolset,
 };
@@ -927,7 +927,13 @@ async fn run_tool(
                 value = Some(v);
                 break;
             }
-            Ok(Ok(Err(err))) => last_error = tool_error_message(&err),
+            Ok(Ok(Err(err))) => {
+                last_error = tool_error_message(&err);
+                // Typed failures other than upstream outages are not retried.
+                if err.tool_error().is_some_and(|e| !e.is_retryable()) {
+                    break;
+                }
+            }
             Ok(Err(_)) => {
                 last_error = format!("tool timed out after {}s", policy.tool_timeout.as_secs())
             }
@@ -1537,20 +1543,24 @@ impl CodeActAgent {
     }
 
     /// Resolve the global instruction for this invocation (dynamic provider or
-    /// static `{state.key}` template), or `None` when unset/empty.
+    /// static `{state.key}` template), or `None` when unset/empty. Under a
+    /// runner, the root agent's global instruction replaces this agent's own.
     async fn resolve_global_instruction(
         &self,
         ctx: &Arc<dyn InvocationContext>,
     ) -> adk_core::Result<Option<String>> {
-        if let Some(provider) = &self.global_instruction_provider {
-            let text = provider(ctx.clone() as Arc<dyn ReadonlyContext>).await?;
-            Ok((!text.is_empty()).then_some(text))
-        } else if let Some(template) = &self.global_instruction {
-            let text = adk_core::inject_session_state(ctx.as_ref(), template).await?;
-            Ok((!text.is_empty()).then_some(text))
-        } else {
-            Ok(None)
-        }
+        let text =
+            match ctx.run_config().global_instruction.clone().or_else(|| self.global_instruction())
+            {
+                Some(GlobalInstruction::Provider(provider)) => {
+                    provider(ctx.clone() as Arc<dyn ReadonlyContext>).await?
+                }
+                Some(GlobalInstruction::Template(template)) => {
+                    adk_core::inject_session_state(ctx.as_ref(), &template).await?
+                }
+                None => return Ok(None),
+            };
+        Ok((!text.is_empty()).then_some(text))
     }
 
     /// Resolve the agent instruction for this invocation.
@@ -1646,23 +1656,28 @@ impl CodeActAgent {
     /// current user turn.
     fn resolve_conversation(&self, ctx: &Arc<dyn InvocationContext>) -> Vec<Content> {
         let current = ctx.user_content().clone();
-        match self.include_contents {
-            IncludeContents::None => vec![current],
-            IncludeContents::Default => {
-                let mut history = if ctx.run_config().transfer_targets.is_empty() {
-                    ctx.session().conversation_history()
-                } else {
-                    ctx.session().conversation_history_for_agent(&self.name)
-                };
-                // Session history already contains the current user message;
-                // ensure the latest user turn reflects the current content.
-                if let Some(idx) = history.iter().rposition(|c| c.role == "user") {
-                    history[idx] = current;
-                } else {
-                    history.push(current);
-                }
-                history
+        let transferred = !ctx.run_config().transfer_targets.is_empty();
+        let mut history = match &self.include_contents {
+            IncludeContents::None => return vec![current],
+            IncludeContents::Filtered(keep) => {
+                ctx.session().conversation_history_filtered(&|event| {
+                    (!transferred || event.author == "user" || event.author == self.name)
+                        && keep.matches(event)
+                })
             }
+            _ if transferred => ctx.session().conversation_history_for_agent(&self.name),
+            _ => ctx.session().conversation_history(),
+        };
+        // Session history already contains the current user message;
+        // ensure the latest user turn reflects the current content.
+        if let Some(idx) = history.iter().rposition(|c| c.role == "user") {
+            history[idx] = current;
+        } else {
+            history.push(current);
+        }
+        match self.include_contents {
+            IncludeContents::LastN(n) => adk_core::last_n_turns(history, n),
+            _ => history,
         }
     }
 }
@@ -1691,6 +1706,22 @@ impl Agent for CodeActAgent {
         &self.sub_agents
     }
 
+    fn global_instruction(&self) -> Option<GlobalInstruction> {
+        match (&self.global_instruction_provider, &self.global_instruction) {
+            (Some(provider), _) => Some(GlobalInstruction::Provider(provider.clone())),
+            (None, Some(template)) => Some(GlobalInstruction::Template(template.clone())),
+            (None, None) => None,
+        }
+    }
+
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_model(self.model.name())
+            .with_tools(&self.tools)
+            .with_sub_agents(&self.sub_agents)
+            .with_capability("code_execution")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> adk_core::Result<EventStream> {
         // Input guardrails run first; a block aborts the run, a transform
         // (e.g. PII redaction) rewrites the user content downstream.
@@ -2448,7 +2479,7 @@ impl CodeActAgentBuilder {
             instruction_provider: self.instruction_provider,
             global_instruction: self.global_instruction,
             global_instruction_provider: self.global_instruction_provider,
-            include_contents: self.include_contents,
+            include_contents: self.include_contents.clone(),
             max_iterations: self.max_iterations,
             max_error_chars: self.max_error_chars,
             supports_suspension: capabilities.supports_suspension,
@@ -2571,9 +2602,15 @@ impl CallbackContext for CodeToolContext {
     fn artifacts(&self) -> Option<Arc<dyn Artifacts>> {
         self.inner.artifacts()
     }
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.inner.memory()
+    }
     fn shared_state(&self) -> Option<Arc<SharedState>> {
         self.inner.shared_state()
     }
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.inner.blackboard()
+    }
 }
 
 #[async_trait]
@@ -2599,6 +2636,21 @@ impl ToolContext for CodeToolContext {
     async fn get_secret(&self, name: &str) -> adk_core::Result<Option<String>> {
         self.inner.get_secret(name).await
     }
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+    fn deadline(&self) -> Option<std::time::Instant> {
+        self.inner.run_config().deadline_at
+    }
+    fn get_state(&self, key: &str) -> Option<Value> {
+        self.actions
+            .lock()
+            .unwrap()
+            .state_delta
+            .get(key)
+            .cloned()
+            .or_else(|| self.inner.session().state().get(key))
+    }
 }
 
 /// Wraps a [`CallbackContext`] to expose a [`ToolOutcome`] to after-tool
@@ -2645,9 +2697,15 @@ impl CallbackContext for ToolOutcomeContext {
     fn artifacts(&self) -> Option<Arc<dyn Artifacts>> {
         self.inner.artifacts()
     }
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.inner.memory()
+    }
     fn shared_state(&self) -> Option<Arc<SharedState>> {
         self.inner.shared_state()
     }
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.inner.blackboard()
+    }
     fn tool_outcome(&self) -> Option<ToolOutcome> {
         Some(self.outcome.clone())
     }
@@ -6,7 +6,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 use adk_core::{
-    Agent, Artifacts, CallbackContext, Content, Event, EventStream, InvocationContext, Memory,
+    Agent, Artifacts, CallbackContext, Content, Event, EventStream, InvocationContext,
     ReadonlyContext, RunConfig, Session, State, Tool, ToolContext, Toolset,
 };
 use async_trait::async_trait;
@@ -242,9 +242,6 @@ impl InvocationContext for MockInvocationContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!("MockInvocationContext::agent is not used by CodeActAgent")
     }
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -1,13 +1,17 @@
 use adk_core::{
-    AfterAgentCallback, Agent, BeforeAgentCallback, CallbackContext, Event, EventStream,
-    InvocationContext, Result,
+    AfterAgentCallback, Agent, BeforeAgentCallback, CallbackContext, Content, Event, EventActions,
+    EventStream, InvocationContext, Part, Result, State,
 };
 use async_stream::stream;
 use async_trait::async_trait;
 use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
 
 type RunHandler = Box<
     dyn Fn(Arc<dyn InvocationContext>) -> Pin<Box<dyn Future<Output = Result<EventStream>> + Send>>
@@ -20,6 +24,22 @@ type RunHandler = Box<
 /// `CustomAgent` allows you to implement arbitrary agent logic without
 /// conforming to the LLM request/response loop. Use the builder to configure
 /// the handler, sub-agents, and lifecycle callbacks.
+///
+/// Most agents are easiest to write with [`CustomAgentBuilder::run`], whose
+/// [`CustomAgentContext`] builds the events; [`CustomAgentBuilder::handler`]
+/// returns a hand-built [`EventStream`] instead.
+///
+/// ```rust,ignore
+/// let agent = CustomAgent::builder("counter")
+///     .run(|ctx| async move {
+///         let count = ctx.get_state("count").and_then(|v| v.as_u64()).unwrap_or(0) + 1;
+///         ctx.set_state("count", &count)?;
+///         ctx.emit_text(format!("Visit number {count}."));
+///         ctx.finish();
+///         Ok(())
+///     })
+///     .build()?;
+/// ```
 pub struct CustomAgent {
     name: String,
     description: String,
@@ -179,6 +199,45 @@ impl CustomAgentBuilder {
         self
     }
 
+    /// Set an async function that implements the agent's logic through a
+    /// [`CustomAgentContext`], replacing any [`handler`](Self::handler).
+    ///
+    /// Events the function emits are streamed as they are produced. State and
+    /// artifact changes ride on the next emitted event; whatever is still
+    /// pending when the context is finished or dropped goes out in a closing
+    /// event. An error returned by the function ends the stream after the
+    /// events emitted so far.
+    pub fn run<F, Fut>(mut self, run: F) -> Self
+    where
+        F: Fn(CustomAgentContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let author = self.name.clone();
+        self.handler = Some(Box::new(move |ctx| {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let mut run = Box::pin(run(CustomAgentContext::new(ctx, author.clone(), tx)));
+            let s = stream! {
+                let result = loop {
+                    tokio::select! {
+                        biased;
+                        Some(event) = rx.recv() => yield Ok(event),
+                        result = &mut run => break result,
+                    }
+                };
+                // The context has been finished or dropped by now, closing the
+                // channel once its last events are delivered.
+                while let Some(event) = rx.recv().await {
+                    yield Ok(event);
+                }
+                if let Err(e) = result {
+                    yield Err(e);
+                }
+            };
+            Box::pin(async move { Ok(Box::pin(s) as EventStream) })
+        }));
+        self
+    }
+
     /// Build the [`CustomAgent`], returning an error if no handler was set.
     pub fn build(self) -> Result<CustomAgent> {
         let handler = self
@@ -206,3 +265,133 @@ impl CustomAgentBuilder {
         })
     }
 }
+
+/// The execution context of a [`CustomAgentBuilder::run`] function.
+///
+/// Each helper builds a well-formed event authored by the agent, so the
+/// function never constructs [`Event`] or [`EventActions`] by hand. Text is
+/// emitted right away; state and artifact changes are collected and attached
+/// to the next emitted event. [`finish`](Self::finish) sends whatever is still
+/// pending in a closing event, and dropping the context does the same.
+pub struct CustomAgentContext {
+    ctx: Arc<dyn InvocationContext>,
+    author: String,
+    events: mpsc::UnboundedSender<Event>,
+    pending: Mutex<EventActions>,
+}
+
+impl CustomAgentContext {
+    fn new(
+        ctx: Arc<dyn InvocationContext>,
+        author: String,
+        events: mpsc::UnboundedSender<Event>,
+    ) -> Self {
+        Self { ctx, author, events, pending: Mutex::new(EventActions::default()) }
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, EventActions> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The invocation this agent runs in, for the session, user content,
+    /// artifacts, memory and run config.
+    pub fn invocation(&self) -> &Arc<dyn InvocationContext> {
+        &self.ctx
+    }
+
+    /// Returns the value of `key`, including changes made with
+    /// [`set_state`](Self::set_state) that have not been emitted yet.
+    pub fn get_state(&self, key: &str) -> Option<Value> {
+        let pending = self.pending().state_delta.get(key).cloned();
+        pending.or_else(|| self.ctx.session().state().get(key))
+    }
+
+    /// Serializes `value` and records it under `key`, to be applied to the
+    /// session with the next emitted event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` fails [`validate_state_key`](adk_core::validate_state_key)
+    /// or `value` cannot be serialized.
+    pub fn set_state<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<()> {
+        let mut pending = self.pending();
+        (&mut PendingState(&mut pending.state_delta) as &mut dyn State).set_as(key, value)
+    }
+
+    /// Saves `data` as a new version of artifact `name` and returns the
+    /// version, which is reported with the next emitted event.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an `Unsupported` error when the run has no artifact service,
+    /// or with the artifact service's error.
+    pub async fn save_artifact(
+        &self,
+        name: &str,
+        data: impl Into<Vec<u8>>,
+        mime_type: impl Into<String>,
+    ) -> Result<i64> {
+        let part = Part::InlineData { mime_type: mime_type.into(), data: data.into() };
+        let callback_ctx: &dyn CallbackContext = self.ctx.as_ref();
+        let version = callback_ctx.save_artifact(name, &part).await?;
+        self.pending().artifact_delta.insert(name.to_string(), version);
+        Ok(version)
+    }
+
+    /// Emits a model text response.
+    pub fn emit_text(&self, text: impl Into<String>) {
+        self.emit(Content::new("model").with_text(text));
+    }
+
+    /// Emits `content` as a response of this agent.
+    pub fn emit(&self, content: Content) {
+        let mut event = self.event();
+        event.llm_response.content = Some(content);
+        let _ = self.events.send(event);
+    }
+
+    /// Ends the run, sending the state and artifact changes not yet emitted in
+    /// a closing event.
+    pub fn finish(self) {
+        // Dropping sends the closing event.
+    }
+
+    /// A new event carrying the pending changes.
+    fn event(&self) -> Event {
+        let mut event = Event::new(self.ctx.invocation_id());
+        event.author = self.author.clone();
+        event.branch = self.ctx.branch().to_string();
+        event.actions = std::mem::take(&mut *self.pending());
+        event
+    }
+}
+
+impl Drop for CustomAgentContext {
+    fn drop(&mut self) {
+        let pending = self.pending();
+        let has_changes = !pending.state_delta.is_empty() || !pending.artifact_delta.is_empty();
+        drop(pending);
+        if has_changes {
+            let mut event = self.event();
+            event.llm_response.turn_complete = true;
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+/// Lets pending state changes reuse the typed [`State`] helpers.
+struct PendingState<'a>(&'a mut HashMap<String, Value>);
+
+impl State for PendingState<'_> {
+    fn get(&self, key: &str) -> Option<Value> {
+        self.0.get(key).cloned()
+    }
+
+    fn set(&mut self, key: String, value: Value) {
+        self.0.insert(key, value);
+    }
+
+    fn all(&self) -> HashMap<String, Value> {
+        self.0.clone()
+    }
+}
@@ -0,0 +1,63 @@
+//! Few-shot examples shown to the model ahead of the conversation.
+//!
+//! Examples added with [`LlmAgentBuilder::example`](crate::LlmAgentBuilder::example)
+//! or [`examples`](crate::LlmAgentBuilder::examples) are sent on every model
+//! call as alternating user and model turns, after the instructions and before
+//! the session history. They belong to the agent's configuration: they are
+//! never written to the session, and history limits such as
+//! [`IncludeContents::LastN`](adk_core::IncludeContents::LastN) or history
+//! compression only apply to the history after them, so an example is always
+//! sent whole.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_agent::LlmAgentBuilder;
+//! use adk_core::Content;
+//!
+//! let agent = LlmAgentBuilder::new("classifier")
+//!     .model(model)
+//!     .instruction("Classify the sentiment of the message.")
+//!     .example(
+//!         Content::new("user").with_text("I love it!"),
+//!         Content::new("model").with_text("positive"),
+//!     )
+//!     .example(
+//!         Content::new("user").with_text("It broke after a day."),
+//!         Content::new("model").with_text("negative"),
+//!     )
+//!     .build()?;
+//! ```
+
+use adk_core::Content;
+use serde::{Deserialize, Serialize};
+
+/// One example exchange: what the user says and how the model should answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Example {
+    /// The example user turn.
+    pub input: Content,
+    /// The answer the model should give to it.
+    pub output: Content,
+}
+
+impl Example {
+    /// Creates an example.
+    pub fn new(input: Content, output: Content) -> Self {
+        Self { input, output }
+    }
+}
+
+/// The turns sent for `examples`, in order. Inputs are sent as `user` turns
+/// and outputs as `model` turns, whatever roles the contents were given.
+pub(crate) fn example_turns(examples: &[Example]) -> Vec<Content> {
+    examples
+        .iter()
+        .flat_map(|example| {
+            [
+                Content { role: "user".to_string(), ..example.input.clone() },
+                Content { role: "model".to_string(), ..example.output.clone() },
+            ]
+        })
+        .collect()
+}
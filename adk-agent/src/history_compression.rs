@@ -0,0 +1,231 @@
+//! Summarizing old turns before a request outgrows the context window.
+//!
+//! With a [`CompressionConfig`] attached through
+//! [`LlmAgentBuilder::history_compression`](crate::LlmAgentBuilder::history_compression),
+//! every request is measured with [`Llm::count_tokens`] before it is sent.
+//! When it exceeds the threshold, the oldest turns are summarized by the
+//! agent's model, or a cheaper one set with [`CompressionConfig::model`], and
+//! replaced by a single "conversation summary" message. The most recent turns
+//! and the tool calls of the current turn are always sent as they are.
+//!
+//! The summary is stored in session state together with a fingerprint of the
+//! turns it covers, so later turns reuse it and only summarize again once the
+//! history has outgrown the threshold a second time.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_agent::{CompressionConfig, LlmAgentBuilder};
+//!
+//! let agent = LlmAgentBuilder::new("assistant")
+//!     .model(model)
+//!     .history_compression(
+//!         CompressionConfig::new(100_000).keep_recent_turns(4).model(cheap_model),
+//!     )
+//!     .build()?;
+//! ```
+
+use adk_core::{Content, Llm, LlmRequest, Part, Result, content_fingerprint, last_n_turns};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Session state key the summary is stored under by default.
+pub const DEFAULT_SUMMARY_STATE_KEY: &str = "adk_history_summary";
+
+/// Number of most recent turns never summarized by default.
+pub const DEFAULT_KEEP_RECENT_TURNS: usize = 3;
+
+const SUMMARY_PROMPT: &str = "\
+Summarize the following conversation between a user and an AI agent. \
+Keep the facts, decisions, tool results and open questions the agent needs to \
+continue the conversation. Reply with the summary only.";
+
+const SUMMARY_HEADER: &str = "Conversation summary of earlier turns:";
+
+/// When and how an agent compresses its conversation history.
+#[derive(Clone)]
+pub struct CompressionConfig {
+    pub(crate) token_threshold: usize,
+    pub(crate) keep_recent_turns: usize,
+    pub(crate) model: Option<Arc<dyn Llm>>,
+    pub(crate) state_key: String,
+}
+
+impl CompressionConfig {
+    /// Compress requests whose input exceeds `token_threshold` tokens.
+    pub fn new(token_threshold: usize) -> Self {
+        Self {
+            token_threshold,
+            keep_recent_turns: DEFAULT_KEEP_RECENT_TURNS,
+            model: None,
+            state_key: DEFAULT_SUMMARY_STATE_KEY.to_string(),
+        }
+    }
+
+    /// Number of most recent user turns, with everything that followed them,
+    /// that are never summarized. Default is 3; at least 1 is kept.
+    pub fn keep_recent_turns(mut self, turns: usize) -> Self {
+        self.keep_recent_turns = turns.max(1);
+        self
+    }
+
+    /// Summarize with `model` instead of the agent's own model.
+    pub fn model(mut self, model: Arc<dyn Llm>) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Session state key the summary is cached under. Default is
+    /// [`DEFAULT_SUMMARY_STATE_KEY`].
+    pub fn state_key(mut self, key: impl Into<String>) -> Self {
+        self.state_key = key.into();
+        self
+    }
+}
+
+impl std::fmt::Debug for CompressionConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressionConfig")
+            .field("token_threshold", &self.token_threshold)
+            .field("keep_recent_turns", &self.keep_recent_turns)
+            .field("model", &self.model.as_ref().map(|model| model.name().to_string()))
+            .field("state_key", &self.state_key)
+            .finish()
+    }
+}
+
+/// A summary of the first `covered` history contents, as cached in state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HistorySummary {
+    pub(crate) covered: usize,
+    pub(crate) fingerprint: String,
+    pub(crate) summary: String,
+}
+
+impl HistorySummary {
+    /// Whether this summary was made from the start of `history`.
+    fn covers(&self, history: &[Content]) -> bool {
+        self.covered <= history.len()
+            && content_fingerprint(&history[..self.covered]) == self.fingerprint
+    }
+
+    fn content(&self) -> Content {
+        Content::new("user").with_text(format!("{SUMMARY_HEADER}\n{}", self.summary))
+    }
+}
+
+/// Replaces the oldest history in `request` with a summary when the request
+/// exceeds the configured threshold.
+///
+/// The first `preamble_len` contents are instructions and are never touched.
+/// Returns `true` when a new summary was written to `cached`.
+pub(crate) async fn compress_history(
+    config: &CompressionConfig,
+    agent_model: &Arc<dyn Llm>,
+    request: &mut LlmRequest,
+    preamble_len: usize,
+    cached: &mut Option<HistorySummary>,
+) -> Result<bool> {
+    if agent_model.count_tokens(request).await? <= config.token_threshold {
+        return Ok(false);
+    }
+
+    let preamble_len = preamble_len.min(request.contents.len());
+    let history = request.contents.split_off(preamble_len);
+    let keep_from = history.len() - last_n_turns(history.clone(), config.keep_recent_turns).len();
+    let usable = cached.as_ref().filter(|summary| {
+        summary.covered > 0 && summary.covered <= keep_from && summary.covers(&history)
+    });
+
+    // A cached summary that brings the request under the threshold is reused.
+    if let Some(summary) = usable {
+        let mut candidate = request.clone();
+        candidate.contents.push(summary.content());
+        candidate.contents.extend_from_slice(&history[summary.covered..]);
+        if summary.covered == keep_from
+            || agent_model.count_tokens(&candidate).await? <= config.token_threshold
+        {
+            *request = candidate;
+            return Ok(false);
+        }
+    }
+
+    if keep_from == 0 {
+        request.contents.extend(history);
+        return Ok(false);
+    }
+
+    // Fold the turns after the cached summary into a new one.
+    let (previous, start) = match usable {
+        Some(summary) => (Some(summary.summary.as_str()), summary.covered),
+        None => (None, 0),
+    };
+    let model = config.model.as_ref().unwrap_or(agent_model);
+    let text = summarize(model.as_ref(), previous, &history[start..keep_from]).await?;
+    if text.is_empty() {
+        tracing::warn!("history compression produced an empty summary; sending full history");
+        request.contents.extend(history);
+        return Ok(false);
+    }
+
+    let summary = HistorySummary {
+        covered: keep_from,
+        fingerprint: content_fingerprint(&history[..keep_from]),
+        summary: text,
+    };
+    tracing::debug!(
+        summarized_contents = keep_from,
+        kept_contents = history.len() - keep_from,
+        "compressed conversation history"
+    );
+    request.contents.push(summary.content());
+    request.contents.extend_from_slice(&history[keep_from..]);
+    *cached = Some(summary);
+    Ok(true)
+}
+
+/// Asks `model` for a summary of `contents`, continuing `previous`.
+async fn summarize(
+    model: &dyn Llm,
+    previous: Option<&str>,
+    contents: &[Content],
+) -> Result<String> {
+    let mut prompt = SUMMARY_PROMPT.to_string();
+    if let Some(previous) = previous {
+        prompt.push_str("\n\nSummary of the conversation before these turns:\n");
+        prompt.push_str(previous);
+    }
+    prompt.push_str("\n\nConversation:\n");
+    prompt.push_str(&format_contents(contents));
+
+    let request = LlmRequest::new(model.name(), vec![Content::new("user").with_text(prompt)]);
+    let mut stream = model.generate_content(request, false).await?;
+    let mut summary = String::new();
+    while let Some(response) = stream.next().await {
+        if let Some(content) = response?.content {
+            summary.extend(content.parts.iter().filter_map(Part::text));
+        }
+    }
+    Ok(summary.trim().to_string())
+}
+
+fn format_contents(contents: &[Content]) -> String {
+    let mut lines = Vec::new();
+    for content in contents {
+        for part in &content.parts {
+            match part {
+                Part::Text { text } => lines.push(format!("{}: {}", content.role, text)),
+                Part::FunctionCall { name, args, .. } => {
+                    lines.push(format!("{}: called {name}({args})", content.role));
+                }
+                Part::FunctionResponse { function_response, .. } => lines.push(format!(
+                    "{} returned: {}",
+                    function_response.name, function_response.response
+                )),
+                _ => {}
+            }
+        }
+    }
+    lines.join("\n")
+}
@@ -80,25 +80,38 @@ pub mod codeact;
 
 pub mod compaction;
 mod custom_agent;
+pub mod few_shot;
 pub mod guardrails;
+pub mod history_compression;
 mod llm_agent;
+mod loop_guard;
+pub mod planner;
 mod skill_shim;
 pub mod tool_call_markup;
+pub mod tool_selection;
+pub mod turn_retry;
 mod workflow;
 
 pub use adk_core::AfterToolCallbackFull;
 pub use adk_core::Agent;
 pub use adk_core::OnToolErrorCallback;
 pub use compaction::LlmEventSummarizer;
-pub use custom_agent::{CustomAgent, CustomAgentBuilder};
+pub use custom_agent::{CustomAgent, CustomAgentBuilder, CustomAgentContext};
+pub use few_shot::Example;
 pub use guardrails::GuardrailSet;
+pub use history_compression::CompressionConfig;
 pub use llm_agent::{
-    DEFAULT_MAX_ITERATIONS, DEFAULT_TOOL_TIMEOUT, LlmAgent, LlmAgentBuilder, extract_typed,
+    DEFAULT_MAX_ITERATIONS, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_TOOL_TIMEOUT, LlmAgent,
+    LlmAgentBuilder, extract_typed,
 };
+pub use planner::{PlanReActPlanner, PlanStep, Planner, ToolFailure};
 pub use tool_call_markup::{normalize_content, normalize_option_content};
+pub use tool_selection::{KeywordToolRanker, ToolRanker, ToolSelector};
+pub use turn_retry::{TurnValidator, TurnVerdict};
 pub use workflow::{
-    ConditionalAgent, DEFAULT_LOOP_MAX_ITERATIONS, LlmConditionalAgent, LlmConditionalAgentBuilder,
-    LoopAgent, ParallelAgent, SequentialAgent,
+    AsyncCondition, ConditionalAgent, ConditionalAgentBuilder, DEFAULT_LOOP_MAX_ITERATIONS,
+    LlmConditionalAgent, LlmConditionalAgentBuilder, LoopAgent, LoopCondition,
+    PARALLEL_OUTPUT_PREFIX, ParallelAgent, SequentialAgent,
 };
 
 #[cfg(feature = "ambient")]
@@ -1,14 +1,17 @@
 use adk_core::{
     AfterAgentCallback, AfterModelCallback, AfterToolCallback, AfterToolCallbackFull, Agent,
     BeforeAgentCallback, BeforeModelCallback, BeforeModelResult, BeforeToolCallback,
-    CallbackContext, Content, Event, EventActions, FunctionResponseData, GlobalInstructionProvider,
-    InstructionProvider, InvocationContext, Llm, LlmRequest, LlmResponse, MemoryEntry,
-    OnToolErrorCallback, Part, ReadonlyContext, Result, RetryBudget, Tool, ToolCallbackContext,
+    CallbackContext, Citation, Content, Event, EventActions, FunctionResponseData,
+    GlobalInstruction, GlobalInstructionProvider, InstructionProvider, InvocationContext,
+    LifecycleEvent, Llm, LlmRequest, LlmResponse, MemoryEntry, OnToolErrorCallback, Part,
+    ReadonlyContext, Result, RetryBudget, Tool, ToolCallFailure, ToolCallbackContext,
     ToolConfirmationDecision, ToolConfirmationPolicy, ToolConfirmationRequest, ToolContext,
-    ToolExecutionStrategy, ToolOutcome, Toolset,
+    ToolError, ToolErrorDecision, ToolErrorPolicy, ToolExecutionStrategy, ToolFailureResolution,
+    ToolOutcome, Toolset,
 };
 use async_stream::stream;
 use async_trait::async_trait;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
 use tracing::Instrument;
 
@@ -20,18 +23,166 @@ use adk_plugin::{
 #[cfg(feature = "skills")]
 use crate::skill_shim::load_skill_index;
 use crate::{
+    few_shot::{Example, example_turns},
     guardrails::{GuardrailSet, enforce_guardrails},
+    history_compression::{CompressionConfig, HistorySummary, compress_history},
+    loop_guard::{LoopCheck, ToolLoopGuard},
+    planner::{PlanStep, Planner, ToolFailure},
     skill_shim::{SelectionPolicy, SkillIndex, select_skill_prompt_block},
     tool_call_markup::normalize_option_content,
+    tool_selection::ToolSelector,
+    turn_retry::{TurnRetryPolicy, TurnVerdict},
     workflow::with_user_content_override,
 };
 
 /// Default maximum number of LLM round-trips (iterations) before the agent stops.
 pub const DEFAULT_MAX_ITERATIONS: u32 = 100;
 
+/// Default maximum number of tool-call rounds in one invocation.
+pub const DEFAULT_MAX_TOOL_ITERATIONS: u32 = 10;
+
 /// Default tool execution timeout (5 minutes).
 pub const DEFAULT_TOOL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
 
+/// Prompt sent to resume a response cut off by `FinishReason::MaxTokens`.
+const CONTINUATION_PROMPT: &str = "Continue exactly where your previous response was cut off. Do not repeat any text you already wrote.";
+
+/// Decides per invocation whether a toolset-provided tool is offered.
+type ToolFilter = Arc<dyn Fn(&dyn InvocationContext, &dyn Tool) -> bool + Send + Sync>;
+
+/// Record on a continued response's event that it started out truncated by the token limit.
+fn annotate_continuation(event: &mut Event, continuations: u32) {
+    if continuations == 0 {
+        return;
+    }
+    event
+        .provider_metadata
+        .insert("adk.original_finish_reason".to_string(), "MaxTokens".to_string());
+    event
+        .provider_metadata
+        .insert("adk.max_token_continuations".to_string(), continuations.to_string());
+}
+
+/// Whether a response ending with `finish_reason` was cut off by the token
+/// limit and will be resumed by another model call.
+fn continues_after_max_tokens(
+    finish_reason: Option<adk_core::FinishReason>,
+    content: Option<&Content>,
+    continuations: u32,
+    max_continuations: u32,
+) -> bool {
+    finish_reason == Some(adk_core::FinishReason::MaxTokens)
+        && continuations < max_continuations
+        && !content.is_some_and(Content::has_function_calls)
+}
+
+/// Prefix a continued response's event with the text of the segments before it,
+/// so the final event holds the whole answer.
+fn stitch_continued_text(event: &mut Event, continued_text: &str) {
+    if continued_text.is_empty() {
+        return;
+    }
+    if let Some(content) = event.llm_response.content.as_mut() {
+        content.parts.insert(0, Part::Text { text: continued_text.to_string() });
+    }
+}
+
+/// Note on a model response that it was requested without tools because the
+/// agent reached its tool iteration limit.
+fn annotate_tool_iteration_limit(event: &mut Event, truncated_at: Option<u32>) {
+    if let Some(limit) = truncated_at {
+        event
+            .provider_metadata
+            .insert("adk.tool_iterations_truncated".to_string(), limit.to_string());
+    }
+}
+
+/// Add `new` to the sources cited so far in an invocation, skipping duplicates.
+fn merge_citations(citations: &mut Vec<Citation>, new: &[Citation]) {
+    for citation in new {
+        if !citations.contains(citation) {
+            citations.push(citation.clone());
+        }
+    }
+}
+
+/// Sources to attach to a model response event: everything cited during the
+/// invocation when `content` is the final answer, otherwise the response's own.
+fn event_citations(
+    content: Option<&Content>,
+    invocation: &[Citation],
+    own: &[Citation],
+) -> Vec<Citation> {
+    let calls_tools = content.is_some_and(|content| {
+        content.parts.iter().any(|part| matches!(part, Part::FunctionCall { .. }))
+    });
+    if calls_tools { own.to_vec() } else { invocation.to_vec() }
+}
+
+/// Gives function responses without an id the id of the call they answer,
+/// e.g. responses built by a before-tool callback.
+fn link_function_responses(content: &mut Content, call_id: &str) {
+    for part in &mut content.parts {
+        if let Part::FunctionResponse { id: id @ None, .. } = part {
+            *id = Some(call_id.to_string());
+        }
+    }
+}
+
+/// Tool calls in `batch` whose response reports an `error`.
+fn tool_failures(batch: &[Content]) -> Vec<ToolFailure> {
+    batch
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            Part::FunctionResponse { function_response, .. } => {
+                let error = function_response.response.get("error")?;
+                let error = error.as_str().map_or_else(|| error.to_string(), str::to_string);
+                Some(ToolFailure { name: function_response.name.clone(), error })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Awaits `future` unless the turn's deadline passes first, in which case the
+/// future is dropped and `None` is returned.
+async fn before_deadline<F: std::future::Future>(
+    deadline: Option<std::time::Instant>,
+    future: F,
+) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.into(), future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+/// Renders an instruction from its provider or static template, with session
+/// state placeholders substituted. Returns `None` for an empty instruction.
+async fn render_instruction(
+    ctx: &Arc<dyn InvocationContext>,
+    provider: Option<&Arc<InstructionProvider>>,
+    template: Option<&str>,
+    options: adk_core::TemplateOptions,
+    describe_provider: impl FnOnce() -> String,
+) -> Result<Option<Content>> {
+    let text = match (provider, template) {
+        (Some(provider), _) => {
+            let text =
+                provider(ctx.clone() as Arc<dyn ReadonlyContext>).await.map_err(|mut e| {
+                    e.message = format!("{} failed: {}", describe_provider(), e.message);
+                    e
+                })?;
+            adk_core::inject_session_state_with(ctx.as_ref(), &text, options).await?
+        }
+        (None, Some(template)) => {
+            adk_core::inject_session_state_with(ctx.as_ref(), template, options).await?
+        }
+        (None, None) => return Ok(None),
+    };
+    Ok((!text.is_empty()).then(|| Content::new("user").with_text(text)))
+}
+
 fn trace_json_payload<T: serde::Serialize>(
     value: &T,
     record_payloads: bool,
@@ -64,30 +215,44 @@ fn trace_json_payload<T: serde::Serialize>(
 pub struct LlmAgent {
     name: String,
     description: String,
+    version: Option<String>,
     model: Arc<dyn Llm>,
     instruction: Option<String>,
     instruction_provider: Option<Arc<InstructionProvider>>,
     global_instruction: Option<String>,
     global_instruction_provider: Option<Arc<GlobalInstructionProvider>>,
+    /// How static instruction templates render missing state keys.
+    template_options: adk_core::TemplateOptions,
     skills_index: Option<Arc<SkillIndex>>,
     skill_policy: SelectionPolicy,
     max_skill_chars: usize,
-    #[allow(dead_code)] // Part of public API via builder
     input_schema: Option<serde_json::Value>,
     output_schema: Option<serde_json::Value>,
+    /// Checks that schema-valid output also deserializes into the type set
+    /// with [`LlmAgentBuilder::output_type`].
+    output_type_check: Option<OutputTypeCheck>,
     /// Maximum retry attempts for output schema validation (default: 3).
     output_max_retries: usize,
     disallow_transfer_to_parent: bool,
     disallow_transfer_to_peers: bool,
     include_contents: adk_core::IncludeContents,
+    shared_history: adk_core::SharedHistory,
+    /// Summarizes old turns once a request exceeds a token threshold.
+    history_compression: Option<CompressionConfig>,
+    /// Few-shot exchanges sent ahead of the session history.
+    examples: Vec<Example>,
     tools: Vec<Arc<dyn Tool>>,
     toolsets: Vec<Arc<dyn Toolset>>,
     sub_agents: Vec<Arc<dyn Agent>>,
+    /// Structured tasks sent with transfers, keyed by sub-agent name.
+    handoffs: std::collections::BTreeMap<String, adk_core::Handoff>,
     output_key: Option<String>,
     /// Default generation config (temperature, top_p, etc.) applied to every LLM request.
     generate_content_config: Option<adk_core::GenerateContentConfig>,
     /// Maximum number of LLM round-trips before stopping
     max_iterations: u32,
+    /// Maximum number of tool-call rounds before the model must answer without tools
+    max_tool_iterations: u32,
     /// Timeout for individual tool executions
     tool_timeout: std::time::Duration,
     before_callbacks: Arc<Vec<BeforeAgentCallback>>,
@@ -97,6 +262,9 @@ pub struct LlmAgent {
     before_tool_callbacks: Arc<Vec<BeforeToolCallback>>,
     after_tool_callbacks: Arc<Vec<AfterToolCallback>>,
     on_tool_error_callbacks: Arc<Vec<OnToolErrorCallback>>,
+    /// What happens to a tool call that fails when no `on_tool_error`
+    /// callback substitutes a result.
+    tool_error_policy: ToolErrorPolicy,
     /// Rich after-tool callbacks that receive tool, args, and response.
     after_tool_callbacks_full: Arc<Vec<AfterToolCallbackFull>>,
     /// Default retry budget applied to all tools without a per-tool override.
@@ -107,9 +275,21 @@ pub struct LlmAgent {
     /// after this many consecutive failures within a single invocation.
     circuit_breaker_threshold: Option<u32>,
     tool_confirmation_policy: ToolConfirmationPolicy,
-    /// Per-agent tool execution strategy override. When `Some`, overrides the
-    /// `RunConfig` strategy for this agent's dispatch loop.
+    /// Per-agent tool execution strategy. When `None`, calls from one model
+    /// turn run concurrently.
     tool_execution_strategy: Option<ToolExecutionStrategy>,
+    /// Maximum number of calls from one model turn executing at once.
+    tool_concurrency: Option<usize>,
+    /// Narrows the declared tools per invocation when there are too many.
+    tool_selector: Option<ToolSelector>,
+    /// Withholds toolset-provided tools per invocation.
+    tool_filter: Option<ToolFilter>,
+    /// Whether sub-agents run by tools stream their events through this agent.
+    stream_sub_agent_output: bool,
+    /// Validates the final answer and retries the turn when it is rejected.
+    turn_retry: Option<TurnRetryPolicy>,
+    /// Plans before acting and re-plans after failed steps.
+    planner: Option<Arc<dyn Planner>>,
     input_guardrails: Arc<GuardrailSet>,
     output_guardrails: Arc<GuardrailSet>,
     /// Enhanced plugin manager for fine-grained tool/model call interception.
@@ -128,6 +308,7 @@ impl std::fmt::Debug for LlmAgent {
         f.debug_struct("LlmAgent")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("version", &self.version)
             .field("model", &self.model.name())
             .field("instruction", &self.instruction)
             .field("tools_count", &self.tools.len())
@@ -201,21 +382,46 @@ impl LlmAgent {
     }
 }
 
+/// Checks that a schema-valid output value deserializes into the agent's output type.
+type OutputTypeCheck =
+    Arc<dyn Fn(&serde_json::Value) -> std::result::Result<(), String> + Send + Sync>;
+
+/// Returns the JSON inside a response wrapped in a Markdown code fence, such as
+/// `` ```json ... ``` ``, or the trimmed text when it is not fenced.
+fn strip_code_fences(text: &str) -> &str {
+    let trimmed = text.trim();
+    let Some(inner) = trimmed.strip_prefix("```").and_then(|rest| rest.strip_suffix("```")) else {
+        return trimmed;
+    };
+    // Drop the info string (`json`) on the opening line.
+    match inner.split_once('\n') {
+        Some((_, body)) => body.trim(),
+        None => inner.trim(),
+    }
+}
+
 /// Validate a JSON string against an output schema.
 ///
 /// Returns `Ok(valid_json)` if the text parses as valid JSON and passes schema
 /// validation. Returns `Err(error_message)` describing the validation failure.
+/// A Markdown code fence around the JSON is stripped first.
 fn validate_output_against_schema(
     text: &str,
     schema: &serde_json::Value,
 ) -> std::result::Result<serde_json::Value, String> {
-    let parsed: serde_json::Value =
-        serde_json::from_str(text).map_err(|e| format!("Response is not valid JSON: {e}"))?;
+    let parsed: serde_json::Value = serde_json::from_str(strip_code_fences(text))
+        .map_err(|e| format!("Response is not valid JSON: {e}"))?;
 
     let validator =
         jsonschema::validator_for(schema).map_err(|e| format!("Invalid schema: {e}"))?;
 
-    let errors: Vec<String> = validator.iter_errors(&parsed).map(|e| e.to_string()).collect();
+    let errors: Vec<String> = validator
+        .iter_errors(&parsed)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            if path.is_empty() { e.to_string() } else { format!("{path}: {e}") }
+        })
+        .collect();
 
     if errors.is_empty() { Ok(parsed) } else { Err(errors.join("; ")) }
 }
@@ -270,7 +476,7 @@ pub fn extract_typed<T: serde::de::DeserializeOwned>(events: &[Event]) -> Result
         adk_core::AdkError::agent("no text content found in events for typed extraction")
     })?;
 
-    serde_json::from_str(&text)
+    serde_json::from_str(strip_code_fences(&text))
         .map_err(|e| adk_core::AdkError::agent(format!("output deserialization failed: {e}")))
 }
 
@@ -278,26 +484,34 @@ pub fn extract_typed<T: serde::de::DeserializeOwned>(events: &[Event]) -> Result
 pub struct LlmAgentBuilder {
     name: String,
     description: Option<String>,
+    version: Option<String>,
     model: Option<Arc<dyn Llm>>,
     instruction: Option<String>,
     instruction_provider: Option<Arc<InstructionProvider>>,
     global_instruction: Option<String>,
     global_instruction_provider: Option<Arc<GlobalInstructionProvider>>,
+    template_options: adk_core::TemplateOptions,
     skills_index: Option<Arc<SkillIndex>>,
     skill_policy: SelectionPolicy,
     max_skill_chars: usize,
     input_schema: Option<serde_json::Value>,
     output_schema: Option<serde_json::Value>,
+    output_type_check: Option<OutputTypeCheck>,
     output_max_retries: usize,
     disallow_transfer_to_parent: bool,
     disallow_transfer_to_peers: bool,
     include_contents: adk_core::IncludeContents,
+    shared_history: adk_core::SharedHistory,
+    history_compression: Option<CompressionConfig>,
+    examples: Vec<Example>,
     tools: Vec<Arc<dyn Tool>>,
     toolsets: Vec<Arc<dyn Toolset>>,
     sub_agents: Vec<Arc<dyn Agent>>,
+    handoffs: std::collections::BTreeMap<String, adk_core::Handoff>,
     output_key: Option<String>,
     generate_content_config: Option<adk_core::GenerateContentConfig>,
     max_iterations: u32,
+    max_tool_iterations: u32,
     tool_timeout: std::time::Duration,
     before_callbacks: Vec<BeforeAgentCallback>,
     after_callbacks: Vec<AfterAgentCallback>,
@@ -306,12 +520,19 @@ pub struct LlmAgentBuilder {
     before_tool_callbacks: Vec<BeforeToolCallback>,
     after_tool_callbacks: Vec<AfterToolCallback>,
     on_tool_error_callbacks: Vec<OnToolErrorCallback>,
+    tool_error_policy: ToolErrorPolicy,
     after_tool_callbacks_full: Vec<AfterToolCallbackFull>,
     default_retry_budget: Option<RetryBudget>,
     tool_retry_budgets: std::collections::HashMap<String, RetryBudget>,
     circuit_breaker_threshold: Option<u32>,
     tool_confirmation_policy: ToolConfirmationPolicy,
     tool_execution_strategy: Option<ToolExecutionStrategy>,
+    tool_concurrency: Option<usize>,
+    tool_selector: Option<ToolSelector>,
+    tool_filter: Option<ToolFilter>,
+    stream_sub_agent_output: bool,
+    turn_retry: Option<TurnRetryPolicy>,
+    planner: Option<Arc<dyn Planner>>,
     input_guardrails: GuardrailSet,
     output_guardrails: GuardrailSet,
     /// Enhanced plugins to register on the built agent.
@@ -328,26 +549,34 @@ impl LlmAgentBuilder {
         Self {
             name: name.into(),
             description: None,
+            version: None,
             model: None,
             instruction: None,
             instruction_provider: None,
             global_instruction: None,
             global_instruction_provider: None,
+            template_options: adk_core::TemplateOptions::default(),
             skills_index: None,
             skill_policy: SelectionPolicy::default(),
             max_skill_chars: 2000,
             input_schema: None,
             output_schema: None,
+            output_type_check: None,
             output_max_retries: 3,
             disallow_transfer_to_parent: false,
             disallow_transfer_to_peers: false,
-            include_contents: adk_core::IncludeContents::Default,
+            include_contents: adk_core::IncludeContents::All,
+            shared_history: adk_core::SharedHistory::Off,
+            history_compression: None,
+            examples: Vec::new(),
             tools: Vec::new(),
             toolsets: Vec::new(),
             sub_agents: Vec::new(),
+            handoffs: std::collections::BTreeMap::new(),
             output_key: None,
             generate_content_config: None,
             max_iterations: DEFAULT_MAX_ITERATIONS,
+            max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
             tool_timeout: DEFAULT_TOOL_TIMEOUT,
             before_callbacks: Vec::new(),
             after_callbacks: Vec::new(),
@@ -356,12 +585,19 @@ impl LlmAgentBuilder {
             before_tool_callbacks: Vec::new(),
             after_tool_callbacks: Vec::new(),
             on_tool_error_callbacks: Vec::new(),
+            tool_error_policy: ToolErrorPolicy::default(),
             after_tool_callbacks_full: Vec::new(),
             default_retry_budget: None,
             tool_retry_budgets: std::collections::HashMap::new(),
             circuit_breaker_threshold: None,
             tool_confirmation_policy: ToolConfirmationPolicy::Never,
             tool_execution_strategy: None,
+            tool_concurrency: None,
+            tool_selector: None,
+            tool_filter: None,
+            stream_sub_agent_output: true,
+            turn_retry: None,
+            planner: None,
             input_guardrails: GuardrailSet::new(),
             output_guardrails: GuardrailSet::new(),
             #[cfg(feature = "enhanced-plugins")]
@@ -377,6 +613,13 @@ impl LlmAgentBuilder {
         self
     }
 
+    /// Set a version label, included in [`Agent::config_hash`] and reported
+    /// by [`Agent::version`].
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
     /// Set the LLM model for this agent.
     pub fn model(mut self, model: Arc<dyn Llm>) -> Self {
         self.model = Some(model);
@@ -389,24 +632,76 @@ impl LlmAgentBuilder {
         self
     }
 
-    /// Set a dynamic instruction provider evaluated per invocation.
+    /// Set a dynamic instruction provider evaluated before every model call.
+    ///
+    /// The provider runs again for each model call, including tool
+    /// round-trips, so it can read fresh session state, memory or the clock;
+    /// nothing is cached between calls. Its output is then rendered like a
+    /// static [`instruction`](Self::instruction), so `{key}` placeholders are
+    /// filled from session state. A provider error fails the call.
     pub fn instruction_provider(mut self, provider: InstructionProvider) -> Self {
         self.instruction_provider = Some(Arc::new(provider));
         self
     }
 
+    /// Set a dynamic instruction from an async closure.
+    ///
+    /// Convenience over [`instruction_provider`](Self::instruction_provider) for
+    /// instructions that pull live data (dates, feature flags, database values):
+    ///
+    /// ```rust,ignore
+    /// let agent = LlmAgentBuilder::new("assistant")
+    ///     .model(model)
+    ///     .instruction_fn(|ctx| async move {
+    ///         Ok(format!("Today is {}. You are helping {}.", chrono::Utc::now().date_naive(), ctx.user_id()))
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn instruction_fn<F, Fut>(self, provider: F) -> Self
+    where
+        F: Fn(Arc<dyn ReadonlyContext>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        self.instruction_provider(Box::new(move |ctx| Box::pin(provider(ctx))))
+    }
+
     /// Set a global instruction prepended to all requests.
+    ///
+    /// On the root agent of a [`Runner`](https://docs.rs/adk-runner), it is
+    /// applied to every LLM agent in the tree, before that agent's own
+    /// instruction. On a sub-agent it is ignored; the runner logs a warning.
     pub fn global_instruction(mut self, instruction: impl Into<String>) -> Self {
         self.global_instruction = Some(instruction.into());
         self
     }
 
-    /// Set a dynamic global instruction provider evaluated per invocation.
+    /// Set a dynamic global instruction provider evaluated before every model
+    /// call. See [`instruction_provider`](Self::instruction_provider).
     pub fn global_instruction_provider(mut self, provider: GlobalInstructionProvider) -> Self {
         self.global_instruction_provider = Some(Arc::new(provider));
         self
     }
 
+    /// Control how instruction and global instruction templates, static or
+    /// provided, render. By default a `{key}` placeholder whose state key is missing
+    /// fails the turn; [`adk_core::MissingKeyPolicy::Empty`] renders it as an
+    /// empty string instead.
+    pub fn instruction_template_options(mut self, options: adk_core::TemplateOptions) -> Self {
+        self.template_options = options;
+        self
+    }
+
+    /// Set a dynamic global instruction from an async closure.
+    ///
+    /// See [`instruction_fn`](Self::instruction_fn).
+    pub fn global_instruction_fn<F, Fut>(self, provider: F) -> Self
+    where
+        F: Fn(Arc<dyn ReadonlyContext>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        self.global_instruction_provider(Box::new(move |ctx| Box::pin(provider(ctx))))
+    }
+
     /// Set a preloaded skills index for this agent.
     #[cfg(feature = "skills")]
     pub fn with_skills(mut self, index: SkillIndex) -> Self {
@@ -451,13 +746,20 @@ impl LlmAgentBuilder {
     /// Set a JSON schema for structured output from the LLM.
     pub fn output_schema(mut self, schema: serde_json::Value) -> Self {
         self.output_schema = Some(schema);
+        self.output_type_check = None;
         self
     }
 
     /// Derive the output schema from a Rust type using `schemars`.
     ///
-    /// This is a convenience method that generates a JSON Schema from `T`'s
-    /// `JsonSchema` implementation and sets it as the output schema.
+    /// Generates a JSON Schema from `T`'s `JsonSchema` implementation and sets
+    /// it as the output schema. The final response must also deserialize into
+    /// `T`; a response that does not is retried like any other schema failure
+    /// (see [`output_max_retries`](Self::output_max_retries)). Markdown code
+    /// fences around the JSON are ignored.
+    ///
+    /// With [`output_key`](Self::output_key) set, the parsed JSON value is
+    /// stored in state, so it can be read back with the state's `get_as::<T>`.
     ///
     /// # Example
     ///
@@ -476,10 +778,15 @@ impl LlmAgentBuilder {
     ///     .output_type::<MyOutput>()
     ///     .build()?;
     /// ```
-    pub fn output_type<T: schemars::JsonSchema>(mut self) -> Self {
+    pub fn output_type<T: schemars::JsonSchema + serde::de::DeserializeOwned>(mut self) -> Self {
         let schema = schemars::schema_for!(T);
         self.output_schema =
             Some(serde_json::to_value(schema).expect("schema serialization cannot fail"));
+        self.output_type_check = Some(Arc::new(|value: &serde_json::Value| {
+            T::deserialize(value).map(drop).map_err(|e| {
+                format!("Response does not deserialize into {}: {e}", std::any::type_name::<T>())
+            })
+        }));
         self
     }
 
@@ -505,22 +812,76 @@ impl LlmAgentBuilder {
     }
 
     /// Control which conversation history contents are included in LLM requests.
+    ///
+    /// [`IncludeContents::LastN`](adk_core::IncludeContents::LastN) and
+    /// [`IncludeContents::Filtered`](adk_core::IncludeContents::Filtered) trim
+    /// the history without separating a function call from its response.
+    /// `Filtered` applies to session events and is ignored when the agent
+    /// reads [`shared_history`](Self::shared_history).
     pub fn include_contents(mut self, include: adk_core::IncludeContents) -> Self {
         self.include_contents = include;
         self
     }
 
+    /// Build this agent's history from the run-wide
+    /// [`ConversationBuffer`](adk_core::ConversationBuffer) instead of its own
+    /// view of the session.
+    ///
+    /// Useful for sub-agents that must know what the user told the coordinator
+    /// or another specialist earlier in the conversation. Other agents' turns
+    /// are presented as user-role context messages. Defaults to
+    /// [`SharedHistory::Off`](adk_core::SharedHistory::Off).
+    pub fn shared_history(mut self, mode: adk_core::SharedHistory) -> Self {
+        self.shared_history = mode;
+        self
+    }
+
+    /// Summarize the oldest turns once a request exceeds the configured
+    /// token threshold.
+    ///
+    /// Requests are measured with the model's
+    /// [`count_tokens`](Llm::count_tokens). The summary replaces the old turns
+    /// with one "conversation summary" message and is cached in session state,
+    /// so it is reused on later turns. Recent turns and the current turn's
+    /// function calls and responses are never summarized. See
+    /// [`history_compression`](crate::history_compression).
+    pub fn history_compression(mut self, config: CompressionConfig) -> Self {
+        self.history_compression = Some(config);
+        self
+    }
+
+    /// Add a few-shot example: a user turn and the answer the model should
+    /// give. Repeatable; examples are sent in the order they were added.
+    ///
+    /// Examples are sent on every model call as user and model turns after
+    /// the instructions and before the session history, and are never stored
+    /// in the session. See [`few_shot`](crate::few_shot).
+    pub fn example(mut self, input: Content, output: Content) -> Self {
+        self.examples.push(Example::new(input, output));
+        self
+    }
+
+    /// Add several few-shot examples. See [`example`](Self::example).
+    pub fn examples(mut self, examples: impl IntoIterator<Item = Example>) -> Self {
+        self.examples.extend(examples);
+        self
+    }
+
     /// Set a state key where the agent's final output will be stored.
     pub fn output_key(mut self, key: impl Into<String>) -> Self {
         self.output_key = Some(key.into());
         self
     }
 
-    /// Set default generation parameters (temperature, top_p, top_k, max_output_tokens)
-    /// applied to every LLM request made by this agent.
+    /// Set default generation parameters (temperature, top_p, top_k, max_output_tokens,
+    /// stop_sequences) applied to every LLM request made by this agent, including the
+    /// follow-up calls after tool execution.
     ///
     /// These defaults are merged with any per-request config. If `output_schema` is also
-    /// set, the schema is preserved alongside these generation parameters.
+    /// set, the schema is preserved alongside these generation parameters. Because the
+    /// config travels on the request, agents sharing one model can sample differently;
+    /// request values take precedence over the model client's own defaults, and fields a
+    /// provider does not support are skipped with a debug log.
     ///
     /// # Example
     ///
@@ -581,6 +942,18 @@ impl LlmAgentBuilder {
         self
     }
 
+    /// Set the maximum number of model→tool→model rounds in one invocation.
+    /// Default is 10.
+    ///
+    /// Once the limit is reached the model is called one last time with no
+    /// tools, so it must answer from what it has. The events of that call carry
+    /// `adk.tool_iterations_truncated` in their metadata, and each `call_llm`
+    /// span records `adk.tool_iteration` and `adk.tool_iterations_truncated`.
+    pub fn max_tool_iterations(mut self, max: u32) -> Self {
+        self.max_tool_iterations = max;
+        self
+    }
+
     /// Set the timeout for individual tool executions.
     /// Default is 5 minutes. Tools that exceed this timeout will return an error.
     pub fn tool_timeout(mut self, timeout: std::time::Duration) -> Self {
@@ -610,6 +983,20 @@ impl LlmAgentBuilder {
         self
     }
 
+    /// Add a sub-agent that receives `handoff` whenever this agent transfers
+    /// to it.
+    ///
+    /// The sub-agent reads it through
+    /// [`InvocationContext::handoff`](adk_core::InvocationContext::handoff).
+    /// An `LlmAgent` adds the task, context, and constraints to its prompt
+    /// and, when the handoff has a return schema and the agent has no output
+    /// schema of its own, validates its answer against it.
+    pub fn handoff_to(mut self, agent: Arc<dyn Agent>, handoff: adk_core::Handoff) -> Self {
+        self.handoffs.insert(agent.name().to_string(), handoff);
+        self.sub_agents.push(agent);
+        self
+    }
+
     /// Add a before-agent callback.
     pub fn before_callback(mut self, callback: BeforeAgentCallback) -> Self {
         self.before_callbacks.push(callback);
@@ -671,12 +1058,27 @@ impl LlmAgentBuilder {
     /// If the callback returns `Ok(Some(value))`, the value is used as a
     /// fallback function response to the LLM. If it returns `Ok(None)`,
     /// the next callback in the chain is tried. If no callback provides a
-    /// fallback, the original error is reported to the LLM.
+    /// fallback, the [`tool_error_policy`](Self::tool_error_policy) decides.
     pub fn on_tool_error(mut self, callback: OnToolErrorCallback) -> Self {
         self.on_tool_error_callbacks.push(callback);
         self
     }
 
+    /// Choose what happens to a tool call that still fails after its retries
+    /// when no `on_tool_error` callback substitutes a result.
+    ///
+    /// The default, [`ToolErrorPolicy::ReturnToModel`], sends
+    /// `{"error": "...", "tool": "..."}` to the model as the function
+    /// response. [`ToolErrorPolicy::Abort`] fails the invocation instead, and
+    /// [`ToolErrorPolicy::Custom`] decides per call. Every failure is also
+    /// reported as an [`Event::tool_failed`] event, even when the agent
+    /// recovers. An abort discards the results of the other calls in the same
+    /// model response.
+    pub fn tool_error_policy(mut self, policy: ToolErrorPolicy) -> Self {
+        self.tool_error_policy = policy;
+        self
+    }
+
     /// Set a default retry budget applied to all tools that do not have
     /// a per-tool override.
     ///
@@ -728,14 +1130,113 @@ impl LlmAgentBuilder {
 
     /// Set the tool execution strategy for this agent.
     ///
-    /// When set, this overrides the `RunConfig`'s `tool_execution_strategy`
-    /// for this agent's dispatch loop. When `None` (the default), the
-    /// `RunConfig` value is used.
+    /// By default, the function calls in one model response run concurrently,
+    /// as with [`ToolExecutionStrategy::Parallel`]. Whatever the strategy, the
+    /// results go back to the model in call order, each linked to its call id.
     pub fn tool_execution_strategy(mut self, strategy: ToolExecutionStrategy) -> Self {
         self.tool_execution_strategy = Some(strategy);
         self
     }
 
+    /// Limit how many function calls from one model response execute at once.
+    ///
+    /// `tool_concurrency(1)` runs them one at a time, for tools with side
+    /// effects that must not overlap. Unlimited by default; per-tool limits in
+    /// `RunConfig::tool_concurrency` still apply.
+    ///
+    /// Concurrent calls each record their own state delta, and the deltas are
+    /// applied in call order. When two calls in the same response write the
+    /// same key, the later call in the response wins, whichever finished last.
+    pub fn tool_concurrency(mut self, max_concurrent: usize) -> Self {
+        self.tool_concurrency = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Narrow the tools declared to the model on each invocation.
+    ///
+    /// The selector ranks the agent's tools, including toolset-provided ones,
+    /// against the current user message and keeps the most relevant. Without
+    /// a selector every tool is declared, and a run whose tools exceed the
+    /// model's [`max_tools`](Llm::max_tools) fails before the model is called.
+    pub fn tool_selection(mut self, selector: ToolSelector) -> Self {
+        self.tool_selector = Some(selector);
+        self
+    }
+
+    /// Decide per invocation which toolset-provided tools are offered.
+    ///
+    /// `filter` sees the invocation, including its session state, and each
+    /// tool resolved from the agent's toolsets and the run's runtime toolsets;
+    /// tools it rejects are neither declared to the model nor callable. Tools
+    /// added with [`tool`](Self::tool) are always offered. The filter runs once
+    /// when the toolsets are resolved at the start of the invocation, so a tool
+    /// the model was shown can still be called later in the turn even if state
+    /// changes meanwhile.
+    ///
+    /// ```rust,ignore
+    /// let agent = LlmAgentBuilder::new("support")
+    ///     .model(model)
+    ///     .toolset(Arc::new(billing_tools))
+    ///     .tool_filter(|ctx, tool| {
+    ///         tool.name() != "refund_tool"
+    ///             || ctx.session().state().get("user:tier") == Some(json!("premium"))
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn tool_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&dyn InvocationContext, &dyn Tool) -> bool + Send + Sync + 'static,
+    {
+        self.tool_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Whether sub-agents called as tools stream their output through this
+    /// agent's event stream (default: `true`).
+    ///
+    /// While a tool such as `AgentTool` runs a sub-agent, the sub-agent's
+    /// events, including partial chunks in streaming mode, are yielded as they
+    /// happen with the sub-agent's name as `author`, so a UI can show which
+    /// agent is speaking. They are tagged with the tool call's id
+    /// ([`Event::sub_agent_call_id`]) and not written to the session; the
+    /// tool's result is. Set `false` to consume the sub-agent's output
+    /// silently and only emit this agent's own synthesis.
+    ///
+    /// Agents reached by `transfer_to_agent` always stream their own events.
+    pub fn stream_sub_agent_output(mut self, stream: bool) -> Self {
+        self.stream_sub_agent_output = stream;
+        self
+    }
+
+    /// Retry the whole turn, up to `max_retries` times, when `validator`
+    /// rejects the final answer.
+    ///
+    /// The validator sees the content the agent would end its turn with. On
+    /// [`TurnVerdict::Retry`] the feedback is sent to the model as a user
+    /// message and recorded as an [`Event::turn_retried`] event. When the
+    /// retries run out, the run fails with an error carrying the last feedback.
+    /// See [`turn_retry`](crate::turn_retry).
+    pub fn turn_retry(
+        mut self,
+        max_retries: u32,
+        validator: impl Fn(&Content) -> TurnVerdict + Send + Sync + 'static,
+    ) -> Self {
+        self.turn_retry = Some(TurnRetryPolicy { max_retries, validator: Arc::new(validator) });
+        self
+    }
+
+    /// Plan before acting, using `planner`.
+    ///
+    /// The planner's instruction is added to every model call. Plans found in
+    /// responses (after `after_model` callbacks) are reported as
+    /// [`Event::planned`] events, tool failures prompt a revised plan, and a
+    /// response that signals completion ends the turn. See
+    /// [`planner`](crate::planner).
+    pub fn planner(mut self, planner: Arc<dyn Planner>) -> Self {
+        self.planner = Some(planner);
+        self
+    }
+
     /// Set input guardrails to validate user input before processing.
     ///
     /// Input guardrails run before the agent processes the request and can:
@@ -900,26 +1401,34 @@ impl LlmAgentBuilder {
         Ok(LlmAgent {
             name: self.name,
             description: self.description.unwrap_or_default(),
+            version: self.version,
             model,
             instruction: self.instruction,
             instruction_provider: self.instruction_provider,
             global_instruction: self.global_instruction,
             global_instruction_provider: self.global_instruction_provider,
+            template_options: self.template_options,
             skills_index: self.skills_index,
             skill_policy: self.skill_policy,
             max_skill_chars: self.max_skill_chars,
             input_schema: self.input_schema,
             output_schema: self.output_schema,
+            output_type_check: self.output_type_check,
             output_max_retries: self.output_max_retries,
             disallow_transfer_to_parent: self.disallow_transfer_to_parent,
             disallow_transfer_to_peers: self.disallow_transfer_to_peers,
             include_contents: self.include_contents,
+            shared_history: self.shared_history,
+            history_compression: self.history_compression,
+            examples: self.examples,
             tools: self.tools,
             toolsets: self.toolsets,
             sub_agents: self.sub_agents,
+            handoffs: self.handoffs,
             output_key: self.output_key,
             generate_content_config: self.generate_content_config,
             max_iterations: self.max_iterations,
+            max_tool_iterations: self.max_tool_iterations,
             tool_timeout: self.tool_timeout,
             before_callbacks: Arc::new(self.before_callbacks),
             after_callbacks: Arc::new(self.after_callbacks),
@@ -928,12 +1437,19 @@ impl LlmAgentBuilder {
             before_tool_callbacks: Arc::new(self.before_tool_callbacks),
             after_tool_callbacks: Arc::new(self.after_tool_callbacks),
             on_tool_error_callbacks: Arc::new(self.on_tool_error_callbacks),
+            tool_error_policy: self.tool_error_policy,
             after_tool_callbacks_full: Arc::new(self.after_tool_callbacks_full),
             default_retry_budget: self.default_retry_budget,
             tool_retry_budgets: self.tool_retry_budgets,
             circuit_breaker_threshold: self.circuit_breaker_threshold,
             tool_confirmation_policy: self.tool_confirmation_policy,
             tool_execution_strategy: self.tool_execution_strategy,
+            tool_concurrency: self.tool_concurrency,
+            tool_selector: self.tool_selector,
+            tool_filter: self.tool_filter,
+            stream_sub_agent_output: self.stream_sub_agent_output,
+            turn_retry: self.turn_retry,
+            planner: self.planner,
             input_guardrails: Arc::new(self.input_guardrails),
             output_guardrails: Arc::new(self.output_guardrails),
             #[cfg(feature = "enhanced-plugins")]
@@ -951,6 +1467,8 @@ struct AgentToolContext {
     function_call_id: String,
     actions: Mutex<EventActions>,
     progress_tx: Option<tokio::sync::mpsc::UnboundedSender<Event>>,
+    /// Whether sub-agent events reach `progress_tx`.
+    forward_sub_agent_events: bool,
 }
 
 impl AgentToolContext {
@@ -960,6 +1478,7 @@ impl AgentToolContext {
             function_call_id,
             actions: Mutex::new(EventActions::default()),
             progress_tx: None,
+            forward_sub_agent_events: false,
         }
     }
 
@@ -970,6 +1489,12 @@ impl AgentToolContext {
         self
     }
 
+    /// Also forward events of sub-agents run by the tool onto the progress sink.
+    fn with_sub_agent_events(mut self, forward: bool) -> Self {
+        self.forward_sub_agent_events = forward;
+        self
+    }
+
     fn actions_guard(&self) -> std::sync::MutexGuard<'_, EventActions> {
         self.actions.lock().unwrap_or_else(|e| e.into_inner())
     }
@@ -1016,9 +1541,17 @@ impl CallbackContext for AgentToolContext {
         self.parent_ctx.artifacts()
     }
 
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.parent_ctx.memory()
+    }
+
     fn shared_state(&self) -> Option<Arc<adk_core::SharedState>> {
         self.parent_ctx.shared_state()
     }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.parent_ctx.blackboard()
+    }
 }
 
 #[async_trait]
@@ -1052,6 +1585,19 @@ impl ToolContext for AgentToolContext {
         self.parent_ctx.get_secret(name).await
     }
 
+    fn is_cancelled(&self) -> bool {
+        self.parent_ctx.is_cancelled()
+    }
+
+    fn deadline(&self) -> Option<std::time::Instant> {
+        self.parent_ctx.run_config().deadline_at
+    }
+
+    fn get_state(&self, key: &str) -> Option<serde_json::Value> {
+        let pending = self.actions_guard().state_delta.get(key).cloned();
+        pending.or_else(|| self.parent_ctx.session().state().get(key))
+    }
+
     async fn emit_progress(&self, stream: &str, chunk: &str) {
         // Primary path: forward as a partial Event on the agent's EventStream so
         // UIs consume tool progress through the same channel as everything else.
@@ -1074,6 +1620,26 @@ impl ToolContext for AgentToolContext {
             "{chunk}",
         );
     }
+
+    fn sub_agent_streaming_mode(&self) -> Option<adk_core::StreamingMode> {
+        (self.forward_sub_agent_events && self.progress_tx.is_some())
+            .then(|| self.parent_ctx.run_config().streaming_mode)
+    }
+
+    async fn forward_sub_agent_event(&self, mut event: Event) {
+        if !self.forward_sub_agent_events {
+            return;
+        }
+        if let Some(tx) = &self.progress_tx {
+            // Nested agent tools already tagged their own call; keep the
+            // innermost id, whose function call is forwarded too.
+            event
+                .provider_metadata
+                .entry(adk_core::SUB_AGENT_CALL_ID_KEY.to_string())
+                .or_insert_with(|| self.function_call_id.clone());
+            let _ = tx.send(event);
+        }
+    }
 }
 
 /// Wrapper that adds ToolOutcome to an existing CallbackContext.
@@ -1121,9 +1687,17 @@ impl CallbackContext for ToolOutcomeCallbackContext {
         self.inner.artifacts()
     }
 
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.inner.memory()
+    }
+
     fn tool_outcome(&self) -> Option<ToolOutcome> {
         Some(self.outcome.clone())
     }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.inner.blackboard()
+    }
 }
 
 /// Per-invocation circuit breaker state.
@@ -1162,6 +1736,43 @@ impl CircuitBreakerState {
     }
 }
 
+/// Minimal context handed to toolsets while warming up, before any session exists.
+struct WarmupContext {
+    agent_name: String,
+    user_content: Content,
+}
+
+#[async_trait]
+impl ReadonlyContext for WarmupContext {
+    fn invocation_id(&self) -> &str {
+        "warmup"
+    }
+
+    fn agent_name(&self) -> &str {
+        &self.agent_name
+    }
+
+    fn user_id(&self) -> &str {
+        "warmup"
+    }
+
+    fn app_name(&self) -> &str {
+        "warmup"
+    }
+
+    fn session_id(&self) -> &str {
+        "warmup"
+    }
+
+    fn branch(&self) -> &str {
+        ""
+    }
+
+    fn user_content(&self) -> &Content {
+        &self.user_content
+    }
+}
+
 #[async_trait]
 impl Agent for LlmAgent {
     fn name(&self) -> &str {
@@ -1176,6 +1787,153 @@ impl Agent for LlmAgent {
         &self.sub_agents
     }
 
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn global_instruction(&self) -> Option<GlobalInstruction> {
+        match (&self.global_instruction_provider, &self.global_instruction) {
+            (Some(provider), _) => Some(GlobalInstruction::Provider(provider.clone())),
+            (None, Some(template)) => Some(GlobalInstruction::Template(template.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// Hashes what shapes the model calls: the instructions, the model id, the
+    /// generation config, the input and output schemas, and the declarations
+    /// of the registered tools, along with the name, description, version,
+    /// and sub-agent hashes. Instruction providers and toolsets are resolved
+    /// per invocation, so only their presence is hashed.
+    fn config_hash(&self) -> String {
+        let mut tools: Vec<(&str, serde_json::Value)> =
+            self.tools.iter().map(|tool| (tool.name(), tool.declaration())).collect();
+        tools.sort_by(|a, b| a.0.cmp(b.0));
+        let sub_agents: Vec<String> =
+            self.sub_agents.iter().map(|agent| agent.config_hash()).collect();
+        let mut config = serde_json::json!({
+            "name": self.name,
+            "description": self.description,
+            "version": self.version,
+            "model": self.model.name(),
+            "instruction": self.instruction,
+            "instruction_provider": self.instruction_provider.is_some(),
+            "global_instruction": self.global_instruction,
+            "global_instruction_provider": self.global_instruction_provider.is_some(),
+            "generate_content_config": self.generate_content_config,
+            "input_schema": self.input_schema,
+            "output_schema": self.output_schema,
+            "tools": tools,
+            "toolsets": self.toolsets.len(),
+            "tool_filter": self.tool_filter.is_some(),
+            "stream_sub_agent_output": self.stream_sub_agent_output,
+            "sub_agents": sub_agents,
+        });
+        if !self.handoffs.is_empty() {
+            config["handoffs"] = serde_json::json!(self.handoffs);
+        }
+        if !self.examples.is_empty() {
+            config["examples"] = serde_json::json!(self.examples);
+        }
+        if let Some(compression) = &self.history_compression {
+            config["history_compression"] = serde_json::json!({
+                "token_threshold": compression.token_threshold,
+                "keep_recent_turns": compression.keep_recent_turns,
+                "model": compression.model.as_ref().map(|model| model.name()),
+            });
+        }
+        adk_core::value_fingerprint(&config)
+    }
+
+    /// Reports the model, registered tools, and sub-agents. Capabilities are
+    /// `"input_schema"` and `"output_schema"` when set, `"transfer"` when there
+    /// are sub-agents, and `"toolsets"` when some tools are only resolved per
+    /// invocation.
+    fn info(&self) -> adk_core::AgentInfo {
+        let mut info = adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_model(self.model.name())
+            .with_tools(&self.tools)
+            .with_sub_agents(&self.sub_agents);
+        if let Some(schema) = &self.input_schema {
+            info = info.with_input_schema(schema.clone());
+        }
+        if let Some(schema) = &self.output_schema {
+            info = info.with_output_schema(schema.clone());
+        }
+        if !self.sub_agents.is_empty() {
+            info = info.with_capability("transfer");
+        }
+        if !self.toolsets.is_empty() {
+            info = info.with_capability("toolsets");
+        }
+        if self.shared_history != adk_core::SharedHistory::Off {
+            info = info.with_capability("shared_history");
+        }
+        if self.turn_retry.is_some() {
+            info = info.with_capability("turn_retry");
+        }
+        if self.planner.is_some() {
+            info = info.with_capability("planner");
+        }
+        if !self.examples.is_empty() {
+            info = info.with_capability("examples");
+        }
+        if self.history_compression.is_some() {
+            info = info.with_capability("history_compression");
+        }
+        info
+    }
+
+    /// Pings the model with a tiny request and lists every toolset's tools
+    /// (which connects MCP servers), then warms up sub-agents.
+    async fn warm_up(&self) -> Result<adk_core::WarmupReport> {
+        use futures::StreamExt;
+
+        let mut report = adk_core::WarmupReport::new(&self.name);
+
+        let mut ping = LlmRequest::new(
+            self.model.name(),
+            vec![Content::new("user").with_text("Reply with OK.")],
+        );
+        ping.config = Some(adk_core::GenerateContentConfig {
+            max_output_tokens: Some(8),
+            ..Default::default()
+        });
+        let model_check = async {
+            let mut stream = self.model.generate_content(ping, false).await?;
+            while let Some(response) = stream.next().await {
+                response?;
+            }
+            Ok(())
+        }
+        .await;
+        report.record(format!("model:{}", self.model.name()), &model_check);
+
+        let ctx: Arc<dyn ReadonlyContext> = Arc::new(WarmupContext {
+            agent_name: self.name.clone(),
+            user_content: Content::new("user"),
+        });
+        for toolset in &self.toolsets {
+            let outcome = toolset.tools(ctx.clone()).await;
+            report.record(format!("toolset:{}", toolset.name()), &outcome);
+        }
+
+        for sub_agent in &self.sub_agents {
+            report.merge(sub_agent.warm_up().await?);
+        }
+
+        if !report.is_ready() {
+            for failure in report.failures() {
+                tracing::warn!(
+                    agent.name = %self.name,
+                    component = %failure.component,
+                    error = failure.error.as_deref().unwrap_or_default(),
+                    "warm-up check failed"
+                );
+            }
+        }
+        Ok(report)
+    }
+
     #[adk_telemetry::instrument(
         skip(self, ctx),
         fields(
@@ -1199,17 +1957,34 @@ impl Agent for LlmAgent {
 
         let instruction = self.instruction.clone();
         let instruction_provider = self.instruction_provider.clone();
-        let global_instruction = self.global_instruction.clone();
-        let global_instruction_provider = self.global_instruction_provider.clone();
+        // Under a runner, the root agent's global instruction replaces this one.
+        let (global_instruction, global_instruction_provider) =
+            match ctx.run_config().global_instruction.clone().or_else(|| self.global_instruction())
+            {
+                Some(GlobalInstruction::Template(template)) => (Some(template), None),
+                Some(GlobalInstruction::Provider(provider)) => (None, Some(provider)),
+                None => (None, None),
+            };
+        let template_options = self.template_options;
         let skills_index = self.skills_index.clone();
         let skill_policy = self.skill_policy.clone();
         let max_skill_chars = self.max_skill_chars;
         let output_key = self.output_key.clone();
-        let output_schema = self.output_schema.clone();
+        // A handoff's return schema applies when the agent has none of its own.
+        let output_schema = self
+            .output_schema
+            .clone()
+            .or_else(|| ctx.handoff().and_then(|handoff| handoff.return_schema.clone()));
+        let handoffs = self.handoffs.clone();
+        let output_type_check = self.output_type_check.clone();
         let output_max_retries = self.output_max_retries;
         let generate_content_config = self.generate_content_config.clone();
-        let include_contents = self.include_contents;
+        let include_contents = self.include_contents.clone();
+        let shared_history = self.shared_history;
+        let history_compression = self.history_compression.clone();
+        let examples = self.examples.clone();
         let max_iterations = self.max_iterations;
+        let max_tool_iterations = self.max_tool_iterations;
         let tool_timeout = self.tool_timeout;
         // Clone Arc references (cheap)
         let before_agent_callbacks = self.before_callbacks.clone();
@@ -1219,6 +1994,7 @@ impl Agent for LlmAgent {
         let before_tool_callbacks = self.before_tool_callbacks.clone();
         let after_tool_callbacks = self.after_tool_callbacks.clone();
         let on_tool_error_callbacks = self.on_tool_error_callbacks.clone();
+        let tool_error_policy = self.tool_error_policy.clone();
         let after_tool_callbacks_full = self.after_tool_callbacks_full.clone();
         let default_retry_budget = self.default_retry_budget.clone();
         let tool_retry_budgets = self.tool_retry_budgets.clone();
@@ -1228,6 +2004,12 @@ impl Agent for LlmAgent {
         let disallow_transfer_to_peers = self.disallow_transfer_to_peers;
         let output_guardrails = self.output_guardrails.clone();
         let agent_tool_execution_strategy = self.tool_execution_strategy;
+        let tool_concurrency = self.tool_concurrency.unwrap_or(usize::MAX);
+        let tool_selector = self.tool_selector.clone();
+        let tool_filter = self.tool_filter.clone();
+        let stream_sub_agent_output = self.stream_sub_agent_output;
+        let turn_retry = self.turn_retry.clone();
+        let planner = self.planner.clone();
         #[cfg(feature = "enhanced-plugins")]
         let enhanced_plugin_manager = self.enhanced_plugin_manager.clone();
 
@@ -1312,48 +2094,67 @@ impl Agent for LlmAgent {
                 }
             }
 
-            // ===== PROCESS GLOBAL INSTRUCTION =====
-            // GlobalInstruction provides tree-wide personality/identity
-            if let Some(provider) = &global_instruction_provider {
-                // Dynamic global instruction via provider
-                let global_inst = provider(ctx.clone() as Arc<dyn ReadonlyContext>).await?;
-                if !global_inst.is_empty() {
-                    prompt_preamble.push(Content {
-                        role: "user".to_string(),
-                        parts: vec![Part::Text { text: global_inst }],
-                    });
+            // ===== PROCESS GLOBAL AND AGENT INSTRUCTIONS =====
+            // GlobalInstruction provides tree-wide personality/identity, followed
+            // by the agent-specific instruction. Providers are re-run before every
+            // model call, so the slot they occupy in the preamble is tracked.
+            let render_global = || {
+                render_instruction(
+                    &ctx,
+                    global_instruction_provider.as_ref(),
+                    global_instruction.as_deref(),
+                    template_options,
+                    || format!("global instruction provider of agent '{agent_name}'"),
+                )
+            };
+            let render_agent = || {
+                render_instruction(
+                    &ctx,
+                    instruction_provider.as_ref(),
+                    instruction.as_deref(),
+                    template_options,
+                    || format!("instruction provider of agent '{agent_name}'"),
+                )
+            };
+            let mut global_content = match render_global().await {
+                Ok(content) => content,
+                Err(e) => {
+                    yield Err(e);
+                    return;
                 }
-            } else if let Some(ref template) = global_instruction {
-                // Static global instruction with template injection
-                let processed = adk_core::inject_session_state(ctx.as_ref(), template).await?;
-                if !processed.is_empty() {
-                    prompt_preamble.push(Content {
-                        role: "user".to_string(),
-                        parts: vec![Part::Text { text: processed }],
-                    });
+            };
+            let mut agent_content = match render_agent().await {
+                Ok(content) => content,
+                Err(e) => {
+                    yield Err(e);
+                    return;
                 }
+            };
+            let instructions_start = prompt_preamble.len();
+            let mut instructions_len = 0;
+            for content in global_content.iter().chain(&agent_content) {
+                prompt_preamble.push(content.clone());
+                instructions_len += 1;
             }
 
-            // ===== PROCESS AGENT INSTRUCTION =====
-            // Agent-specific instruction
-            if let Some(provider) = &instruction_provider {
-                // Dynamic instruction via provider
-                let inst = provider(ctx.clone() as Arc<dyn ReadonlyContext>).await?;
-                if !inst.is_empty() {
-                    prompt_preamble.push(Content {
-                        role: "user".to_string(),
-                        parts: vec![Part::Text { text: inst }],
-                    });
-                }
-            } else if let Some(ref template) = instruction {
-                // Static instruction with template injection
-                let processed = adk_core::inject_session_state(ctx.as_ref(), template).await?;
-                if !processed.is_empty() {
-                    prompt_preamble.push(Content {
-                        role: "user".to_string(),
-                        parts: vec![Part::Text { text: processed }],
-                    });
-                }
+            // ===== HANDOFF INSTRUCTION =====
+            // A coordinator's structured task follows the agent's own instruction.
+            if let Some(handoff) = ctx.handoff() {
+                prompt_preamble.push(Content {
+                    role: "user".to_string(),
+                    parts: vec![Part::Text { text: handoff.instruction(ctx.session().state()) }],
+                });
+            }
+
+            // ===== PLANNING INSTRUCTION =====
+            if let Some(instruction) = planner
+                .as_ref()
+                .and_then(|planner| planner.planning_instruction(ctx.as_ref()))
+            {
+                prompt_preamble.push(Content {
+                    role: "user".to_string(),
+                    parts: vec![Part::Text { text: instruction }],
+                });
             }
 
             // ===== OUTPUT SCHEMA INSTRUCTION INJECTION =====
@@ -1370,37 +2171,72 @@ impl Agent for LlmAgent {
                 });
             }
 
+            // ===== FEW-SHOT EXAMPLES =====
+            // Part of the preamble, so history limits never split an example.
+            prompt_preamble.extend(example_turns(&examples));
+
             // ===== LOAD SESSION HISTORY =====
             // Load previous conversation turns from the session
             // NOTE: Session history already includes the current user message (added by Runner before agent runs)
             // When transfer_targets is set, this agent was invoked via transfer — filter out
             // other agents' events so the LLM doesn't see the parent's tool calls as its own.
-            let session_history = if !ctx.run_config().transfer_targets.is_empty() {
-                ctx.session().conversation_history_for_agent(&agent_name)
-            } else {
-                ctx.session().conversation_history()
-            };
-            let mut session_history = session_history;
+            // Agents with shared history read the run-wide buffer instead, which already
+            // holds the current user message and presents other agents' turns as context.
             let current_user_content = ctx.user_content().clone();
-            if let Some(index) = session_history.iter().rposition(|content| content.role == "user") {
-                session_history[index] = current_user_content.clone();
-            } else {
-                session_history.push(current_user_content.clone());
-            }
+            let session_history = match ctx.conversation_buffer() {
+                Some(buffer) if shared_history != adk_core::SharedHistory::Off => {
+                    let mut history = buffer.history_for(&agent_name, shared_history);
+                    if history.is_empty() {
+                        history.push(current_user_content.clone());
+                    }
+                    history
+                }
+                _ => {
+                    let transferred = !ctx.run_config().transfer_targets.is_empty();
+                    let mut session_history = match &include_contents {
+                        adk_core::IncludeContents::Filtered(keep) => {
+                            ctx.session().conversation_history_filtered(&|event| {
+                                (!transferred
+                                    || event.author == "user"
+                                    || event.author == agent_name)
+                                    && keep.matches(event)
+                            })
+                        }
+                        _ if transferred => {
+                            ctx.session().conversation_history_for_agent(&agent_name)
+                        }
+                        _ => ctx.session().conversation_history(),
+                    };
+                    if let Some(index) =
+                        session_history.iter().rposition(|content| content.role == "user")
+                    {
+                        session_history[index] = current_user_content.clone();
+                    } else {
+                        session_history.push(current_user_content.clone());
+                    }
+                    session_history
+                }
+            };
 
             // ===== APPLY INCLUDE_CONTENTS FILTERING =====
             // Control what conversation history the agent sees
+            let mut preamble_len = prompt_preamble.len();
             let mut conversation_history = match include_contents {
                 adk_core::IncludeContents::None => {
                     let mut filtered = prompt_preamble.clone();
                     filtered.push(current_user_content);
                     filtered
                 }
-                adk_core::IncludeContents::Default => {
+                adk_core::IncludeContents::All | adk_core::IncludeContents::Filtered(_) => {
                     let mut full_history = prompt_preamble;
                     full_history.extend(session_history);
                     full_history
                 }
+                adk_core::IncludeContents::LastN(n) => {
+                    let mut recent_history = prompt_preamble;
+                    recent_history.extend(adk_core::last_n_turns(session_history, n));
+                    recent_history
+                }
             };
 
             // ===== RESOLVE TOOLSETS =====
@@ -1423,7 +2259,7 @@ impl Agent for LlmAgent {
             );
 
             for toolset in active_toolsets {
-                let toolset_tools = match toolset
+                let mut toolset_tools = match toolset
                     .tools(ctx.clone() as Arc<dyn ReadonlyContext>)
                     .await
                 {
@@ -1433,6 +2269,9 @@ impl Agent for LlmAgent {
                         return;
                     }
                 };
+                if let Some(filter) = &tool_filter {
+                    toolset_tools.retain(|tool| filter(ctx.as_ref(), tool.as_ref()));
+                }
                 for tool in &toolset_tools {
                     let name = tool.name().to_string();
                     // Check static-vs-toolset conflict
@@ -1457,6 +2296,11 @@ impl Agent for LlmAgent {
                 }
             }
 
+            // A per-invocation allowlist withholds every other tool.
+            if let Some(allowlist) = &ctx.run_config().tool_allowlist {
+                resolved_tools.retain(|tool| allowlist.iter().any(|name| name == tool.name()));
+            }
+
             // Build tool lookup map for O(1) access from merged resolved_tools
             let tool_map: std::collections::HashMap<String, Arc<dyn Tool>> = resolved_tools
                 .iter()
@@ -1482,8 +2326,32 @@ impl Agent for LlmAgent {
             // Uses Tool::declaration() so provider-native built-ins can attach
             // adapter-specific metadata while regular function tools retain the
             // standard name/description/schema shape.
+            let declared_tools = match &tool_selector {
+                Some(selector) => {
+                    let query: String =
+                        ctx.user_content().parts.iter().filter_map(Part::text).collect();
+                    match selector.select(&query, &resolved_tools).await {
+                        Ok(selected) => {
+                            if selected.len() < resolved_tools.len() {
+                                tracing::debug!(
+                                    agent.name = %agent_name,
+                                    available = resolved_tools.len(),
+                                    selected = selected.len(),
+                                    "narrowed tool declarations"
+                                );
+                            }
+                            selected
+                        }
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    }
+                }
+                None => resolved_tools.clone(),
+            };
             let mut tool_declarations = std::collections::HashMap::new();
-            for tool in &resolved_tools {
+            for tool in &declared_tools {
                 tool_declarations.insert(tool.name().to_string(), tool.declaration());
             }
 
@@ -1504,6 +2372,9 @@ impl Agent for LlmAgent {
                 .map(|a| a.name())
                 .collect();
 
+            // Targets withheld by the disallow flags, so a call naming one is
+            // denied rather than reported as unknown.
+            let mut disallowed_transfer_targets: Vec<String> = Vec::new();
             for target in run_config_targets {
                 // Skip if already in the list (from sub_agents)
                 if sub_agent_names.contains(target.as_str()) {
@@ -1512,10 +2383,10 @@ impl Agent for LlmAgent {
 
                 // Apply disallow flags
                 let is_parent = parent_agent_name.as_deref() == Some(target.as_str());
-                if is_parent && disallow_transfer_to_parent {
-                    continue;
-                }
-                if !is_parent && disallow_transfer_to_peers {
+                if (is_parent && disallow_transfer_to_parent)
+                    || (!is_parent && disallow_transfer_to_peers)
+                {
+                    disallowed_transfer_targets.push(target.clone());
                     continue;
                 }
 
@@ -1546,6 +2417,18 @@ impl Agent for LlmAgent {
                 tool_declarations.insert(transfer_tool_name.to_string(), transfer_tool_decl);
             }
 
+            if let Some(max_tools) = model.max_tools()
+                && tool_declarations.len() > max_tools
+            {
+                yield Err(adk_core::AdkError::agent(format!(
+                    "agent '{agent_name}' declares {} tools but model '{}' accepts at most {max_tools} per request; \
+                     narrow them with LlmAgentBuilder::tool_selection(ToolSelector::top_k(..))",
+                    tool_declarations.len(),
+                    model.name()
+                )));
+                return;
+            }
+
 
             // ===== CIRCUIT BREAKER STATE =====
             // Created fresh per invocation so it resets between runs.
@@ -1564,6 +2447,26 @@ impl Agent for LlmAgent {
             // Multi-turn loop with max iterations
             let mut iteration = 0;
             let mut schema_retry_count: usize = 0;
+            let mut turn_retry_count: u32 = 0;
+            // Text of earlier segments of a response continued after hitting max tokens
+            let mut token_continuations: u32 = 0;
+            let mut continued_text = String::new();
+            // Repeated identical tool calls, when loop detection is enabled
+            let mut loop_guard = ctx.run_config().loop_break_threshold.map(ToolLoopGuard::new);
+            let mut force_final_answer = false;
+            // Completed tool-call rounds, and the limit once it forced a final answer
+            let mut tool_iterations: u32 = 0;
+            let mut tool_iterations_truncated: Option<u32> = None;
+            // Execution steps taken under a planner, and whether its cap was hit
+            let mut planner_steps: u32 = 0;
+            let mut planner_steps_exhausted = false;
+            // Sources reported by the model and by tools, for the final event
+            let mut citations: Vec<Citation> = Vec::new();
+            // Summary of old turns from an earlier compression, if any
+            let mut history_summary: Option<HistorySummary> = history_compression
+                .as_ref()
+                .and_then(|compression| ctx.session().state().get(&compression.state_key))
+                .and_then(|value| serde_json::from_value(value).ok());
 
             loop {
                 // Cooperative cancellation: exit before starting another turn
@@ -1572,6 +2475,10 @@ impl Agent for LlmAgent {
                     tracing::info!(agent.name = %agent_name, "invocation cancelled — stopping agent loop");
                     return;
                 }
+                if ctx.run_config().time_remaining().is_some_and(|left| left.is_zero()) {
+                    tracing::info!(agent.name = %agent_name, "turn deadline passed — stopping agent loop");
+                    return;
+                }
                 iteration += 1;
                 if iteration > max_iterations {
                     yield Err(adk_core::AdkError::agent(
@@ -1580,23 +2487,67 @@ impl Agent for LlmAgent {
                     return;
                 }
 
+                // ===== REFRESH PROVIDED INSTRUCTIONS =====
+                // The first call uses the instructions rendered above; later calls
+                // in this turn, including tool round-trips, ask the providers again.
+                if iteration > 1
+                    && (global_instruction_provider.is_some() || instruction_provider.is_some())
+                {
+                    if global_instruction_provider.is_some() {
+                        match render_global().await {
+                            Ok(content) => global_content = content,
+                            Err(e) => {
+                                yield Err(e);
+                                return;
+                            }
+                        }
+                    }
+                    if instruction_provider.is_some() {
+                        match render_agent().await {
+                            Ok(content) => agent_content = content,
+                            Err(e) => {
+                                yield Err(e);
+                                return;
+                            }
+                        }
+                    }
+                    let rendered: Vec<Content> =
+                        global_content.iter().chain(&agent_content).cloned().collect();
+                    preamble_len = preamble_len - instructions_len + rendered.len();
+                    let rendered_len = rendered.len();
+                    conversation_history
+                        .splice(instructions_start..instructions_start + instructions_len, rendered);
+                    instructions_len = rendered_len;
+                }
+
                 // Build request with conversation history
                 // Merge agent-level generate_content_config with output_schema.
                 // Agent-level config provides defaults (temperature, top_p, etc.),
-                // output_schema is layered on top as response_schema.
+                // output_schema is layered on top as response_schema, or as plain
+                // JSON mode for models that cannot enforce a schema (the response
+                // is still validated against the schema below).
+                // Invocation-level overrides from RunConfig replace agent-level settings.
                 // If the runner set a cached_content name (via automatic cache lifecycle),
                 // merge it into the config so the provider can reuse cached content.
+                let generate_content_config = match &ctx.run_config().generate_content_config {
+                    Some(overrides) => Some(
+                        generate_content_config.clone().unwrap_or_default().with_overrides(overrides),
+                    ),
+                    None => generate_content_config.clone(),
+                };
                 let config = match (&generate_content_config, &output_schema) {
-                    (Some(base), Some(schema)) => {
-                        let mut merged = base.clone();
-                        merged.response_schema = Some(schema.clone());
-                        Some(merged)
+                    (base, Some(schema)) => {
+                        let merged = base.clone().unwrap_or_default();
+                        if model.supports_response_schema() {
+                            Some(adk_core::GenerateContentConfig {
+                                response_schema: Some(schema.clone()),
+                                ..merged
+                            })
+                        } else {
+                            Some(merged.json_output())
+                        }
                     }
                     (Some(base), None) => Some(base.clone()),
-                    (None, Some(schema)) => Some(adk_core::GenerateContentConfig {
-                        response_schema: Some(schema.clone()),
-                        ..Default::default()
-                    }),
                     (None, None) => None,
                 };
 
@@ -1612,10 +2563,15 @@ impl Agent for LlmAgent {
                     config
                 };
 
-                let request = LlmRequest {
+                let mut request = LlmRequest {
                     model: model.name().to_string(),
                     contents: conversation_history.clone(),
-                    tools: tool_declarations.clone(),
+                    // Withheld once a tool-call loop has been broken, so the model must answer.
+                    tools: if force_final_answer {
+                        std::collections::HashMap::new()
+                    } else {
+                        tool_declarations.clone()
+                    },
                     config,
                     // Provider-neutral continuity: carry the most recent
                     // response's `interaction_id` forward so transports that
@@ -1626,6 +2582,35 @@ impl Agent for LlmAgent {
                     previous_response_id: last_interaction_id.clone(),
                 };
 
+                // ===== HISTORY COMPRESSION =====
+                // Old turns are replaced by a summary, cached in state for later turns.
+                if let Some(compression) = &history_compression {
+                    match compress_history(
+                        compression,
+                        &model,
+                        &mut request,
+                        preamble_len,
+                        &mut history_summary,
+                    )
+                    .await
+                    {
+                        Ok(true) => {
+                            let mut summary_event = Event::new(&invocation_id);
+                            summary_event.author = agent_name.clone();
+                            summary_event.actions.state_delta.insert(
+                                compression.state_key.clone(),
+                                serde_json::to_value(&history_summary).unwrap_or_default(),
+                            );
+                            yield Ok(summary_event);
+                        }
+                        Ok(false) => {}
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    }
+                }
+
                 // ===== ENHANCED PLUGIN: BEFORE MODEL CALL =====
                 // Enhanced plugins can modify the request or short-circuit the model call.
                 // They run before legacy before_model_callbacks.
@@ -1681,12 +2666,14 @@ impl Agent for LlmAgent {
                 // Determine streaming source: cached response or real model
                 let mut accumulated_content: Option<Content> = None;
                 let mut final_provider_metadata: Option<serde_json::Value> = None;
+                let last_finish_reason: Option<adk_core::FinishReason>;
 
                 if let Some(cached_response) = model_response_override {
                     // Use callback-provided response (e.g., from cache)
                     // Yield it as an event
                     accumulated_content = cached_response.content.clone();
                     final_provider_metadata = cached_response.provider_metadata.clone();
+                    last_finish_reason = cached_response.finish_reason;
                     normalize_option_content(&mut accumulated_content);
                     if let Some(content) = accumulated_content.take() {
                         let has_function_calls = content
@@ -1701,9 +2688,15 @@ impl Agent for LlmAgent {
                         accumulated_content = Some(content);
                     }
 
+                    merge_citations(&mut citations, &cached_response.citations);
                     let mut cached_event = Event::new(&invocation_id);
                     cached_event.author = agent_name.clone();
                     cached_event.llm_response.content = accumulated_content.clone();
+                    cached_event.llm_response.citations = event_citations(
+                        accumulated_content.as_ref(),
+                        &citations,
+                        &cached_response.citations,
+                    );
                     cached_event.llm_response.provider_metadata = cached_response.provider_metadata.clone();
                     // Surface and track the response id for provider-neutral continuity.
                     cached_event.llm_response.interaction_id = cached_response.interaction_id.clone();
@@ -1742,7 +2735,9 @@ impl Agent for LlmAgent {
                         "gcp.vertex.agent.session_id" = %ctx.session_id(),
                         "gen_ai.conversation.id" = %ctx.session_id(),
                         "gcp.vertex.agent.llm_request" = %trace_request_json,
-                        "gcp.vertex.agent.llm_response" = tracing::field::Empty  // Placeholder for later recording
+                        "gcp.vertex.agent.llm_response" = tracing::field::Empty,  // Placeholder for later recording
+                        "adk.tool_iteration" = tool_iterations,
+                        "adk.tool_iterations_truncated" = tool_iterations_truncated.is_some()
                     );
                     let _llm_guard = llm_span.enter();
 
@@ -1752,16 +2747,47 @@ impl Agent for LlmAgent {
                     let should_stream_to_client = matches!(streaming_mode, StreamingMode::SSE | StreamingMode::Bidi)
                         && output_guardrails.is_empty();
 
-                    // Always use streaming internally for LLM calls
-                    let mut response_stream = model.generate_content(request, true).await?;
+                    let emit_lifecycle = ctx.run_config().lifecycle_events;
+                    let model_name = model.name().to_string();
+                    let model_start = std::time::Instant::now();
+                    if emit_lifecycle {
+                        yield Ok(Event::lifecycle(
+                            &invocation_id,
+                            &agent_name,
+                            LifecycleEvent::ModelCallStarted { model: model_name.clone() },
+                        ));
+                    }
+
+                    // Always use streaming internally for LLM calls. The call
+                    // and every chunk are bounded by the turn's deadline.
+                    let deadline = ctx.run_config().deadline_at;
+                    let model_call = adk_core::with_invocation_user_content(
+                        ctx.user_content().clone(),
+                        model.generate_content(request, true),
+                    );
+                    let Some(response_stream) = before_deadline(deadline, model_call).await
+                    else {
+                        tracing::info!(agent.name = %agent_name, "turn deadline passed during model call");
+                        return;
+                    };
+                    let mut response_stream = response_stream?;
 
                     use futures::StreamExt;
 
                     // Track last chunk for final event metadata (used in None mode)
                     let mut last_chunk: Option<LlmResponse> = None;
+                    let mut call_citations: Vec<Citation> = Vec::new();
 
                     // Stream and process chunks with AfterModel callbacks
-                    while let Some(chunk_result) = response_stream.next().await {
+                    while let Some(chunk_result) = {
+                        match before_deadline(deadline, response_stream.next()).await {
+                            Some(next) => next,
+                            None => {
+                                tracing::info!(agent.name = %agent_name, "turn deadline passed during LLM streaming");
+                                return;
+                            }
+                        }
+                    } {
                         // Cooperative cancellation: stop consuming the model
                         // stream promptly when the invocation is cancelled. This
                         // drops `response_stream`, releasing the provider connection.
@@ -1809,6 +2835,9 @@ impl Agent for LlmAgent {
                             }
                         }
 
+                        merge_citations(&mut call_citations, &chunk.citations);
+                        merge_citations(&mut citations, &chunk.citations);
+
                         // For SSE/Bidi mode: yield each chunk immediately with stable event ID
                         if should_stream_to_client {
                             let mut partial_event = Event::with_id(&llm_event_id, &invocation_id);
@@ -1823,6 +2852,25 @@ impl Agent for LlmAgent {
                             partial_event.llm_response.content = chunk.content.clone();
                             partial_event.llm_response.provider_metadata = chunk.provider_metadata.clone();
                             partial_event.llm_response.interaction_id = chunk.interaction_id.clone();
+                            partial_event.llm_response.citations = if chunk.turn_complete || chunk.finish_reason.is_some() {
+                                event_citations(accumulated_content.as_ref(), &citations, &call_citations)
+                            } else {
+                                chunk.citations.clone()
+                            };
+                            if chunk.finish_reason.is_some() {
+                                annotate_continuation(&mut partial_event, token_continuations);
+                                annotate_tool_iteration_limit(&mut partial_event, tool_iterations_truncated);
+                                // A segment that will be continued is not the final answer.
+                                if continues_after_max_tokens(
+                                    chunk.finish_reason,
+                                    accumulated_content.as_ref(),
+                                    token_continuations,
+                                    ctx.run_config().max_token_continuations,
+                                ) {
+                                    partial_event.llm_response.partial = true;
+                                    partial_event.llm_response.turn_complete = false;
+                                }
+                            }
 
                             // Populate long_running_tool_ids
                             if let Some(ref content) = chunk.content {
@@ -1848,6 +2896,8 @@ impl Agent for LlmAgent {
                         }
                     }
 
+                    last_finish_reason = last_chunk.as_ref().and_then(|c| c.finish_reason);
+
                     // For None mode: yield single final event with accumulated content
                     if !should_stream_to_client {
                         if let Some(content) = accumulated_content.take() {
@@ -1870,6 +2920,11 @@ impl Agent for LlmAgent {
                         final_event.llm_response.content = accumulated_content.clone();
                         final_event.llm_response.partial = false;
                         final_event.llm_response.turn_complete = true;
+                        final_event.llm_response.citations = event_citations(
+                            accumulated_content.as_ref(),
+                            &citations,
+                            &call_citations,
+                        );
 
                         // Copy metadata from last chunk
                         if let Some(ref last) = last_chunk {
@@ -1880,15 +2935,43 @@ impl Agent for LlmAgent {
                             final_provider_metadata = last.provider_metadata.clone();
                             final_event.provider_metadata.insert("gcp.vertex.agent.llm_response".to_string(), serde_json::to_string(last).unwrap_or_default());
                         }
+                        annotate_continuation(&mut final_event, token_continuations);
+                        annotate_tool_iteration_limit(&mut final_event, tool_iterations_truncated);
 
                         // Populate long_running_tool_ids
                         if let Some(ref content) = accumulated_content {
                             final_event.long_running_tool_ids = collect_long_running_ids(content);
                         }
 
+                        // Segments cut off by the token limit are yielded as partial
+                        // events; the last one carries the whole stitched answer.
+                        if continues_after_max_tokens(
+                            last_finish_reason,
+                            accumulated_content.as_ref(),
+                            token_continuations,
+                            ctx.run_config().max_token_continuations,
+                        ) {
+                            final_event.llm_response.partial = true;
+                            final_event.llm_response.turn_complete = false;
+                        } else {
+                            stitch_continued_text(&mut final_event, &continued_text);
+                        }
+
                         yield Ok(final_event);
                     }
 
+                    if emit_lifecycle {
+                        yield Ok(Event::lifecycle(
+                            &invocation_id,
+                            &agent_name,
+                            LifecycleEvent::ModelCallCompleted {
+                                model: model_name,
+                                duration_ms: model_start.elapsed().as_millis() as u64,
+                                finish_reason: last_finish_reason,
+                            },
+                        ));
+                    }
+
                     // Record LLM response to span before guard drops
                     if let Some(ref content) = accumulated_content {
                         let response_json = trace_json_payload(
@@ -1925,6 +3008,28 @@ impl Agent for LlmAgent {
                         }
                     }
 
+                // ===== PLANNER =====
+                // The planner reads the response as the callbacks left it.
+                let plan_step = match (&planner, &accumulated_content) {
+                    (Some(planner), Some(content)) => planner.process_response(content),
+                    _ => PlanStep::default(),
+                };
+                if let Some(ref plan) = plan_step.plan {
+                    yield Ok(Event::planned(&invocation_id, &agent_name, plan.clone()));
+                }
+                // A response that signals completion is final: calls made
+                // alongside the answer are dropped rather than executed.
+                if plan_step.complete
+                    && let Some(ref mut content) = accumulated_content
+                    && content.has_function_calls()
+                {
+                    tracing::info!(
+                        agent.name = %agent_name,
+                        "planner reported completion; not executing remaining calls"
+                    );
+                    content.parts.retain(|part| !matches!(part, Part::FunctionCall { .. }));
+                }
+
                 // After streaming/caching completes, check for function calls in accumulated content
                 let function_call_names: Vec<String> = accumulated_content.as_ref()
                     .map(|c| c.parts.iter()
@@ -1940,6 +3045,17 @@ impl Agent for LlmAgent {
 
                 let has_function_calls = !function_call_names.is_empty();
 
+                // A response cut off by the token limit is continued (when enabled)
+                // instead of treated as final. Pending function calls take precedence:
+                // a response that stops alongside tool calls always continues with the
+                // tools, whatever its finish reason.
+                let continue_after_max_tokens = continues_after_max_tokens(
+                    last_finish_reason,
+                    accumulated_content.as_ref(),
+                    token_continuations,
+                    ctx.run_config().max_token_continuations,
+                );
+
                 // Check if ALL function calls are from long-running tools
                 // If so, we should NOT continue the loop - the tool returned a pending status
                 // and the agent/client will poll for completion later
@@ -1957,13 +3073,20 @@ impl Agent for LlmAgent {
                     ));
 
                     // Handle output_key: save final agent output to state_delta
+                    // With an output schema the value is saved once it validates.
                     if let Some(ref output_key) = output_key
+                        && output_schema.is_none()
                         && !has_function_calls
+                        && !continue_after_max_tokens
                     {
-                        let mut text_parts = String::new();
-                        for part in &content.parts {
-                            if let Part::Text { text } = part {
-                                text_parts.push_str(text);
+                        let mut text_parts = continued_text.clone();
+                        if let Some(ref answer) = plan_step.final_answer {
+                            text_parts.push_str(answer);
+                        } else {
+                            for part in &content.parts {
+                                if let Part::Text { text } = part {
+                                    text_parts.push_str(text);
+                                }
                             }
                         }
                         if !text_parts.is_empty() {
@@ -1980,6 +3103,35 @@ impl Agent for LlmAgent {
                 }
 
                 if !has_function_calls {
+                    // ===== MAX TOKENS CONTINUATION =====
+                    if continue_after_max_tokens {
+                        if let Some(ref content) = accumulated_content {
+                            for part in &content.parts {
+                                if let Part::Text { text } = part {
+                                    continued_text.push_str(text);
+                                }
+                            }
+                        }
+                        token_continuations += 1;
+                        tracing::info!(
+                            agent.name = %agent_name,
+                            continuation = token_continuations,
+                            "response hit max tokens, requesting continuation"
+                        );
+                        conversation_history.push(Content {
+                            role: "user".to_string(),
+                            parts: vec![Part::Text { text: CONTINUATION_PROMPT.to_string() }],
+                        });
+                        continue;
+                    }
+                    if last_finish_reason == Some(adk_core::FinishReason::MaxTokens) {
+                        tracing::warn!(
+                            agent.name = %agent_name,
+                            continuations = token_continuations,
+                            "response truncated by max tokens"
+                        );
+                    }
+
                     // ===== OUTPUT SCHEMA VALIDATION =====
                     // When output_schema is set, validate the response text against
                     // the schema. If invalid, retry with a correction prompt up to
@@ -2001,30 +3153,90 @@ impl Agent for LlmAgent {
                                     .join("")
                             })
                             .unwrap_or_default();
+                        let text = format!("{continued_text}{text}");
 
-                        if !text.is_empty()
-                            && let Err(validation_error) = validate_output_against_schema(&text, schema)
-                        {
-                                if schema_retry_count >= output_max_retries {
-                                    yield Err(adk_core::AdkError::agent(format!(
-                                        "output schema validation failed after {} attempts",
-                                        output_max_retries
-                                    )));
-                                    return;
+                        if !text.is_empty() {
+                            let validated = validate_output_against_schema(&text, schema)
+                                .and_then(|value| match output_type_check {
+                                    Some(ref check) => check(&value).map(|()| value),
+                                    None => Ok(value),
+                                });
+                            match validated {
+                                Ok(value) => {
+                                    // Typed output is stored as parsed JSON; plain
+                                    // schemas keep the response text.
+                                    if let Some(ref output_key) = output_key {
+                                        let stored = if output_type_check.is_some() {
+                                            value
+                                        } else {
+                                            serde_json::Value::String(text)
+                                        };
+                                        let mut state_event = Event::new(&invocation_id);
+                                        state_event.author = agent_name.clone();
+                                        state_event.actions.state_delta.insert(output_key.clone(), stored);
+                                        yield Ok(state_event);
+                                    }
+                                }
+                                Err(validation_error) => {
+                                    if schema_retry_count >= output_max_retries {
+                                        yield Err(adk_core::AdkError::agent(format!(
+                                            "output schema validation failed after {} retries: {}",
+                                            output_max_retries, validation_error
+                                        )));
+                                        return;
+                                    }
+                                    schema_retry_count += 1;
+
+                                    // Append a correction prompt and retry
+                                    let correction = format!(
+                                        "Your output did not match the required schema. Error: {}. Please produce valid JSON matching the schema.",
+                                        validation_error
+                                    );
+                                    conversation_history.push(Content {
+                                        role: "user".to_string(),
+                                        parts: vec![Part::Text { text: correction }],
+                                    });
+                                    continue;
                                 }
-                                schema_retry_count += 1;
+                            }
+                        }
+                    }
 
-                                // Append a correction prompt and retry
-                                let correction = format!(
-                                    "Your output did not match the required schema. Error: {}. Please produce valid JSON matching the schema.",
-                                    validation_error
-                                );
-                                conversation_history.push(Content {
-                                    role: "user".to_string(),
-                                    parts: vec![Part::Text { text: correction }],
-                                });
-                                continue;
+                    // ===== TURN VALIDATION =====
+                    // A rejected final answer sends the validator's feedback to
+                    // the model and runs the turn again.
+                    if let Some(ref policy) = turn_retry
+                        && let Some(ref content) = accumulated_content
+                        && let TurnVerdict::Retry(feedback) = (policy.validator)(content)
+                    {
+                        if turn_retry_count >= policy.max_retries {
+                            yield Err(adk_core::AdkError::agent(format!(
+                                "turn validation failed after {} retries: {feedback}",
+                                policy.max_retries
+                            )));
+                            return;
                         }
+                        turn_retry_count += 1;
+                        tracing::info!(
+                            agent.name = %agent_name,
+                            attempt = turn_retry_count,
+                            "final answer rejected by turn validator, retrying"
+                        );
+
+                        let retry_event = Event::turn_retried(
+                            &invocation_id,
+                            &agent_name,
+                            adk_core::TurnRetry {
+                                attempt: turn_retry_count,
+                                max_retries: policy.max_retries,
+                                feedback,
+                            },
+                        );
+                        if let Some(ref feedback) = retry_event.llm_response.content {
+                            conversation_history.push(feedback.clone());
+                        }
+                        yield Ok(retry_event);
+                        continue;
                     }
 
                     // No function calls, we're done
@@ -2042,12 +3254,23 @@ impl Agent for LlmAgent {
                     break;
                 }
 
+                // Calls made after the tool iteration limit withheld the tools
+                // are not executed; the response above is the final one.
+                if tool_iterations_truncated.is_some() || planner_steps_exhausted {
+                    tracing::warn!(
+                        agent.name = %agent_name,
+                        max_tool_iterations,
+                        "model requested tools after the tool iteration or planner step limit; not executing them"
+                    );
+                    break;
+                }
+
                 // Execute function calls and add responses to history
                 if let Some(content) = &accumulated_content {
                     // ===== RESOLVE TOOL EXECUTION STRATEGY =====
-                    // Per-agent override; defaults to Sequential if not set.
+                    // Per-agent override; calls run concurrently if not set.
                     let strategy = agent_tool_execution_strategy
-                        .unwrap_or(ToolExecutionStrategy::Sequential);
+                        .unwrap_or(ToolExecutionStrategy::Parallel);
 
                     // Collect function call parts with original indices for
                     // order-preserving reassembly in parallel/auto modes.
@@ -2084,10 +3307,17 @@ impl Agent for LlmAgent {
                                         function_response: FunctionResponseData::new(
                                             fc_name.clone(),
                                             serde_json::json!({
-                                                "error": format!(
-                                                    "Agent '{}' not found. Available agents: {:?}",
-                                                    target_agent, valid_transfer_targets
-                                                )
+                                                "error": if disallowed_transfer_targets.contains(&target_agent) {
+                                                    format!(
+                                                        "Transfer to agent '{}' is not allowed. Available agents: {:?}",
+                                                        target_agent, valid_transfer_targets
+                                                    )
+                                                } else {
+                                                    format!(
+                                                        "Agent '{}' not found. Available agents: {:?}",
+                                                        target_agent, valid_transfer_targets
+                                                    )
+                                                }
                                             }),
                                         ),
                                         id: fc_id.clone(),
@@ -2103,6 +3333,7 @@ impl Agent for LlmAgent {
 
                             let mut transfer_event = Event::new(&invocation_id);
                             transfer_event.author = agent_name.clone();
+                            transfer_event.actions.handoff = handoffs.get(&target_agent).cloned();
                             transfer_event.actions.transfer_to_agent = Some(target_agent);
                             yield Ok(transfer_event);
                             transfer_handled = true;
@@ -2181,6 +3412,57 @@ impl Agent for LlmAgent {
                         return;
                     }
 
+                    // The id of each call, by position, so every result can be
+                    // linked to the call it answers whatever produced it.
+                    let call_ids: std::collections::HashMap<usize, Option<String>> =
+                        fc_parts.iter().map(|fc| (fc.0, fc.3.clone())).collect();
+
+                    // ===== TOOL LOOP DETECTION =====
+                    // Calls repeated past the threshold are answered with a reminder
+                    // of the earlier result instead of being executed again.
+                    let mut repeated_call_results: Vec<(usize, Content, EventActions, bool)> = Vec::new();
+                    let mut executed_calls: std::collections::HashMap<usize, (String, serde_json::Value)> =
+                        std::collections::HashMap::new();
+                    let fc_parts = match loop_guard.as_mut() {
+                        Some(guard) => {
+                            let mut to_execute = Vec::with_capacity(fc_parts.len());
+                            for fc in fc_parts {
+                                let nudge = match guard.check(&fc.1, &fc.2) {
+                                    LoopCheck::Execute => {
+                                        executed_calls.insert(fc.0, (fc.1.clone(), fc.2.clone()));
+                                        to_execute.push(fc);
+                                        continue;
+                                    }
+                                    LoopCheck::Nudge(nudge) => nudge,
+                                    LoopCheck::ForceAnswer(nudge) => {
+                                        force_final_answer = true;
+                                        nudge
+                                    }
+                                };
+                                tracing::warn!(
+                                    agent.name = %agent_name,
+                                    tool.name = %fc.1,
+                                    force_final_answer,
+                                    "breaking repeated tool call loop"
+                                );
+                                repeated_call_results.push((
+                                    fc.0,
+                                    Content {
+                                        role: "function".to_string(),
+                                        parts: vec![Part::FunctionResponse {
+                                            function_response: FunctionResponseData::new(fc.1, nudge),
+                                            id: fc.3,
+                                        }],
+                                    },
+                                    EventActions::default(),
+                                    false,
+                                ));
+                            }
+                            to_execute
+                        }
+                        None => fc_parts,
+                    };
+
                     // Wrap circuit breaker in Mutex for shared access across parallel futures.
                     let cb_mutex = std::sync::Mutex::new(circuit_breaker_state.take());
 
@@ -2190,12 +3472,15 @@ impl Agent for LlmAgent {
                         &ctx.run_config().tool_concurrency,
                     );
 
-                    // Channel for streaming tool progress (stdout/stderr) onto the
-                    // agent's EventStream while tools are still executing. Each
+                    // Channel for streaming tool progress (stdout/stderr) and the
+                    // events of sub-agents run by tools onto the agent's
+                    // EventStream while tools are still executing. Each
                     // AgentToolContext gets a clone; the dispatch loop below drains
                     // it concurrently and yields progress events to the client.
                     let (progress_tx, mut progress_rx) =
                         tokio::sync::mpsc::unbounded_channel::<Event>();
+                    // Set by a tool failure the tool error policy aborts on.
+                    let tool_abort: Mutex<Option<adk_core::AdkError>> = Mutex::new(None);
 
                     // Per-tool execution async block. Returns (index, Content, EventActions, escalate_or_skip).
                     // Each tool retains its own retry budget, circuit breaker, tracing span,
@@ -2211,9 +3496,13 @@ impl Agent for LlmAgent {
                         let after_tool_callbacks = &after_tool_callbacks;
                         let after_tool_callbacks_full = &after_tool_callbacks_full;
                         let on_tool_error_callbacks = &on_tool_error_callbacks;
+                        let tool_error_policy = &tool_error_policy;
+                        let tool_abort = &tool_abort;
                         let tool_confirmation_policy = &tool_confirmation_policy;
                         let cb_mutex = &cb_mutex;
                         let invocation_id = &invocation_id;
+                        let agent_name = &agent_name;
+                        let emit_lifecycle = ctx.run_config().lifecycle_events;
                         let concurrency_manager = &concurrency_manager;
                         let progress_tx = progress_tx.clone();
                         #[cfg(feature = "enhanced-plugins")]
@@ -2402,7 +3691,8 @@ impl Agent for LlmAgent {
                                 if let Some(tool) = tool_map.get(&name) {
                                     let tool_ctx: Arc<dyn ToolContext> = Arc::new(
                                         AgentToolContext::new(ctx.clone(), function_call_id.clone())
-                                            .with_progress(progress_tx.clone()),
+                                            .with_progress(progress_tx.clone())
+                                            .with_sub_agent_events(stream_sub_agent_output),
                                     );
                                     let span_name = format!("execute_tool {name}");
                                     let tool_span = tracing::info_span!(
@@ -2421,8 +3711,19 @@ impl Agent for LlmAgent {
                                     let retry_delay = budget.map(|b| b.delay).unwrap_or_default();
 
                                     let tool_clone = tool.clone();
+                                    if emit_lifecycle {
+                                        let _ = progress_tx.send(Event::lifecycle(
+                                            invocation_id,
+                                            agent_name,
+                                            LifecycleEvent::ToolCallStarted {
+                                                tool: name.clone(),
+                                                call_id: function_call_id.clone(),
+                                            },
+                                        ));
+                                    }
                                     let tool_start = std::time::Instant::now();
                                     let mut last_error = String::new();
+                                    let mut last_tool_error: Option<ToolError> = None;
                                     let mut final_attempt: u32 = 0;
                                     let mut retry_result: Option<serde_json::Value> = None;
 
@@ -2431,6 +3732,10 @@ impl Agent for LlmAgent {
                                         if attempt > 0 {
                                             tokio::time::sleep(retry_delay).await;
                                         }
+                                        // The turn's deadline caps every attempt.
+                                        let turn_left = ctx.run_config().time_remaining();
+                                        let attempt_timeout =
+                                            turn_left.map_or(tool_timeout, |left| left.min(tool_timeout));
                                         match async {
                                             let args_payload = trace_json_payload(
                                                 &final_args,
@@ -2440,7 +3745,7 @@ impl Agent for LlmAgent {
                                             tracing::debug!(tool.name = %name, tool.args = %args_payload, attempt = attempt, "tool_call");
                                             let exec_future = tool_clone.execute(tool_ctx.clone(), final_args.clone());
                                             let unwind_safe_future = std::panic::AssertUnwindSafe(
-                                                tokio::time::timeout(tool_timeout, exec_future)
+                                                tokio::time::timeout(attempt_timeout, exec_future)
                                             );
                                             match futures::FutureExt::catch_unwind(unwind_safe_future).await {
                                                 Ok(result) => result,
@@ -2461,13 +3766,29 @@ impl Agent for LlmAgent {
                                             }
                                             Ok(Err(e)) => {
                                                 last_error = e.to_string();
-                                                if attempt + 1 < max_attempts {
+                                                last_tool_error = e.tool_error();
+                                                // Typed failures such as bad arguments go straight
+                                                // back to the model; retrying cannot fix them.
+                                                let retryable = last_tool_error
+                                                    .as_ref()
+                                                    .is_none_or(ToolError::is_retryable);
+                                                if retryable && attempt + 1 < max_attempts {
                                                     tracing::warn!(tool.name = %name, attempt = attempt, error = %last_error, "tool execution failed, retrying");
                                                 } else {
                                                     tracing::warn!(tool.name = %name, error = %last_error, "tool_error");
+                                                    break;
                                                 }
                                             }
+                                            Err(_) if attempt_timeout < tool_timeout => {
+                                                last_tool_error = None;
+                                                last_error = format!(
+                                                    "Tool '{name}' was stopped at the turn deadline"
+                                                );
+                                                tracing::warn!(tool.name = %name, "tool stopped at turn deadline");
+                                                break;
+                                            }
                                             Err(_) => {
+                                                last_tool_error = None;
                                                 last_error = format!(
                                                     "Tool '{}' timed out after {} seconds",
                                                     name, tool_timeout.as_secs()
@@ -2484,8 +3805,27 @@ impl Agent for LlmAgent {
                                     let tool_duration = tool_start.elapsed();
                                     let (tool_success, tool_error_message, function_response) = match retry_result {
                                         Some(value) => (true, None, value),
-                                        None => (false, Some(last_error.clone()), serde_json::json!({ "error": last_error })),
+                                        None => {
+                                            let mut response = last_tool_error.as_ref().map_or_else(
+                                                || serde_json::json!({ "error": last_error }),
+                                                ToolError::to_model_response,
+                                            );
+                                            response["tool"] = serde_json::json!(name);
+                                            (false, Some(last_error.clone()), response)
+                                        }
                                     };
+                                    if emit_lifecycle {
+                                        let _ = progress_tx.send(Event::lifecycle(
+                                            invocation_id,
+                                            agent_name,
+                                            LifecycleEvent::ToolCallCompleted {
+                                                tool: name.clone(),
+                                                call_id: function_call_id.clone(),
+                                                duration_ms: tool_duration.as_millis() as u64,
+                                                error: tool_error_message.clone(),
+                                            },
+                                        ));
+                                    }
 
                                     let outcome = ToolOutcome {
                                         tool_name: name.clone(),
@@ -2521,11 +3861,86 @@ impl Agent for LlmAgent {
                                                 Err(e) => { tracing::warn!(error = %e, "on_tool_error callback failed"); break; }
                                             }
                                         }
-                                        fallback_result.unwrap_or(function_response)
+                                        let (response, resolution) = match fallback_result {
+                                            Some(result) => (result, ToolFailureResolution::Substituted),
+                                            None => match tool_error_policy {
+                                                ToolErrorPolicy::ReturnToModel => {
+                                                    (function_response, ToolFailureResolution::ReturnedToModel)
+                                                }
+                                                ToolErrorPolicy::Abort => {
+                                                    (function_response, ToolFailureResolution::Aborted)
+                                                }
+                                                ToolErrorPolicy::Custom(handler) => {
+                                                    match handler(&name, &final_args, &error_msg) {
+                                                        ToolErrorDecision::Substitute(value) => {
+                                                            (value, ToolFailureResolution::Substituted)
+                                                        }
+                                                        ToolErrorDecision::Abort => {
+                                                            (function_response, ToolFailureResolution::Aborted)
+                                                        }
+                                                    }
+                                                }
+                                            },
+                                        };
+                                        if resolution == ToolFailureResolution::Aborted {
+                                            tool_abort
+                                                .lock()
+                                                .unwrap_or_else(|e| e.into_inner())
+                                                .get_or_insert_with(|| adk_core::AdkError::tool(format!(
+                                                    "Tool '{name}' failed and the tool error policy aborted the run: {error_msg}"
+                                                )));
+                                        }
+                                        let _ = progress_tx.send(Event::tool_failed(
+                                            invocation_id,
+                                            agent_name,
+                                            ToolCallFailure {
+                                                tool: name.clone(),
+                                                call_id: function_call_id.clone(),
+                                                error: error_msg,
+                                                resolution,
+                                            },
+                                        ));
+                                        response
                                     } else {
                                         function_response
                                     };
 
+                                    // Tool output size limit: truncate oversized results and
+                                    // optionally spill the full output to an artifact.
+                                    let output_limits = &ctx.run_config().tool_output_limits;
+                                    let final_function_response = match output_limits
+                                        .limit_for(tool.as_ref())
+                                        .and_then(|max| adk_core::truncate_tool_output(&final_function_response, max))
+                                    {
+                                        Some(truncated) => {
+                                            tracing::warn!(
+                                                tool.name = %name,
+                                                original_bytes = truncated.original_bytes,
+                                                "tool output exceeded size limit, truncating"
+                                            );
+                                            let spill_target = if output_limits.spill_to_artifact {
+                                                ctx.artifacts()
+                                            } else {
+                                                None
+                                            };
+                                            match spill_target {
+                                                Some(artifacts) => {
+                                                    let artifact_name = format!("tool_output_{name}_{function_call_id}.txt");
+                                                    let full = Part::Text { text: truncated.full_output.clone() };
+                                                    match artifacts.save(&artifact_name, &full).await {
+                                                        Ok(version) => truncated.with_artifact(&artifact_name, version).value,
+                                                        Err(e) => {
+                                                            tracing::warn!(tool.name = %name, error = %e, "failed to spill tool output to artifact");
+                                                            truncated.value
+                                                        }
+                                                    }
+                                                }
+                                                None => truncated.value,
+                                            }
+                                        }
+                                        None => final_function_response,
+                                    };
+
                                     let confirmation_decision = tool_actions.tool_confirmation_decision;
                                     tool_actions = tool_ctx.actions();
                                     if tool_actions.tool_confirmation_decision.is_none() {
@@ -2707,11 +4122,11 @@ impl Agent for LlmAgent {
                         }
                         ToolExecutionStrategy::Parallel => {
                             use futures::StreamExt as _;
-                            // All concurrency enforcement is handled by the
-                            // ToolConcurrencyManager semaphore inside execute_one_tool.
-                            // Use fc_parts.len() as buffer so all futures can start
-                            // and queue on the semaphore for proper per-tool limiting.
-                            let buffer_size = fc_parts.len().max(1);
+                            // Per-tool limits are enforced by the ToolConcurrencyManager
+                            // semaphore inside execute_one_tool; the buffer applies the
+                            // agent's overall limit and otherwise lets every future start
+                            // and queue on the semaphore.
+                            let buffer_size = fc_parts.len().min(tool_concurrency).max(1);
                             futures::stream::iter(fc_parts.into_iter().map(
                                 |(idx, name, args, id, fcid)| {
                                     execute_one_tool(idx, name, args, id, fcid)
@@ -2737,7 +4152,7 @@ impl Agent for LlmAgent {
                             // inside execute_one_tool.
                             if !read_only_fcs.is_empty() {
                                 use futures::StreamExt as _;
-                                let buffer_size = read_only_fcs.len().max(1);
+                                let buffer_size = read_only_fcs.len().min(tool_concurrency).max(1);
                                 all_results.extend(
                                     futures::stream::iter(read_only_fcs.into_iter().map(
                                         |(idx, name, args, id, fcid)| {
@@ -2780,14 +4195,51 @@ impl Agent for LlmAgent {
                     }
                     results
                     };
+                    // A tool interrupted by cancellation may have stopped
+                    // partway through; drop the whole batch so none of its
+                    // state changes reach the session.
+                    if ctx.is_cancelled() {
+                        tracing::info!(agent.name = %agent_name, "invocation cancelled during tool execution — discarding tool results");
+                        return;
+                    }
+                    // The tool error policy aborted on a failed call.
+                    if let Some(error) = tool_abort.into_inner().unwrap_or_else(|e| e.into_inner()) {
+                        yield Err(error);
+                        return;
+                    }
+                    if let Some(guard) = loop_guard.as_mut() {
+                        for (idx, response_content, _, _) in &results {
+                            if let Some((name, args)) = executed_calls.get(idx) {
+                                let response = response_content
+                                    .parts
+                                    .iter()
+                                    .find_map(|part| match part {
+                                        Part::FunctionResponse { function_response, .. } => {
+                                            Some(function_response.response.clone())
+                                        }
+                                        _ => None,
+                                    })
+                                    .unwrap_or_default();
+                                guard.record(name, args, response);
+                            }
+                        }
+                    }
+                    results.extend(repeated_call_results);
                     // Preserve LLM-returned order even when tool futures finish out of order.
                     results.sort_by_key(|r| r.0);
 
                     // Restore circuit breaker state from the mutex
                     circuit_breaker_state = cb_mutex.into_inner().unwrap_or_else(|e| e.into_inner());
 
-                    // Yield results in original order
-                    for (_, response_content, tool_actions, escalate_or_skip) in results {
+                    // Yield results in original order, then send them back to the
+                    // model together as one turn, so a failed tool still leaves
+                    // the others answered and nothing prompts the model to ask again.
+                    let mut batch = Vec::with_capacity(results.len());
+                    for (idx, mut response_content, tool_actions, escalate_or_skip) in results {
+                        if let Some(Some(call_id)) = call_ids.get(&idx) {
+                            link_function_responses(&mut response_content, call_id);
+                        }
+                        merge_citations(&mut citations, &tool_actions.citations);
                         let mut tool_event = Event::new(&invocation_id);
                         tool_event.author = agent_name.clone();
                         tool_event.actions = tool_actions;
@@ -2798,7 +4250,41 @@ impl Agent for LlmAgent {
                             return;
                         }
 
-                        conversation_history.push(response_content);
+                        batch.push(response_content);
+                    }
+                    let failures = planner.as_ref().map(|_| tool_failures(&batch)).unwrap_or_default();
+                    conversation_history.extend(adk_core::batch_function_responses(batch));
+
+                    // ===== RE-PLANNING =====
+                    if let Some(ref planner) = planner {
+                        if !failures.is_empty()
+                            && let Some(text) = planner.replan_instruction(&failures)
+                        {
+                            conversation_history.push(Content::new("user").with_text(text));
+                        }
+                        planner_steps += 1;
+                        if planner.max_steps().is_some_and(|max| planner_steps >= max)
+                            && !force_final_answer
+                        {
+                            tracing::info!(
+                                agent.name = %agent_name,
+                                steps = planner_steps,
+                                "planner step limit reached, requesting a final answer without tools"
+                            );
+                            force_final_answer = true;
+                            planner_steps_exhausted = true;
+                        }
+                    }
+
+                    tool_iterations += 1;
+                    if tool_iterations >= max_tool_iterations && !force_final_answer {
+                        tracing::warn!(
+                            agent.name = %agent_name,
+                            max_tool_iterations,
+                            "tool iteration limit reached, requesting a final answer without tools"
+                        );
+                        force_final_answer = true;
+                        tool_iterations_truncated = Some(max_tool_iterations);
                     }
                 }
 
@@ -0,0 +1,103 @@
+//! Detection of an agent repeating the same tool call without making progress.
+//!
+//! Enabled by [`RunConfig::loop_break_threshold`](adk_core::RunConfig::loop_break_threshold).
+//! Once a tool has run `threshold` times with identical arguments in one
+//! invocation, further identical calls are not executed. The first one gets a
+//! nudge carrying the earlier result; the next one also makes the agent ask the
+//! model for a final answer with no tools available.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+/// What to do with a tool call the model just requested.
+pub(crate) enum LoopCheck {
+    /// Run the tool.
+    Execute,
+    /// Skip the tool and answer with this nudge.
+    Nudge(Value),
+    /// Skip the tool, answer with this nudge, and withhold tools from the next
+    /// model request.
+    ForceAnswer(Value),
+}
+
+#[derive(Default)]
+struct RecordedCall {
+    count: usize,
+    last_result: Value,
+    nudged: bool,
+}
+
+/// Per-invocation record of executed `(tool, args)` calls.
+pub(crate) struct ToolLoopGuard {
+    threshold: usize,
+    calls: HashMap<(String, String), RecordedCall>,
+}
+
+impl ToolLoopGuard {
+    pub(crate) fn new(threshold: usize) -> Self {
+        Self { threshold: threshold.max(1), calls: HashMap::new() }
+    }
+
+    /// Decide whether the call `name(args)` may run.
+    pub(crate) fn check(&mut self, name: &str, args: &Value) -> LoopCheck {
+        let Some(call) = self.calls.get_mut(&key(name, args)) else {
+            return LoopCheck::Execute;
+        };
+        if call.count < self.threshold {
+            return LoopCheck::Execute;
+        }
+
+        let nudge = json!({
+            "error": "repeated_tool_call",
+            "message": format!(
+                "You already called `{name}` with these arguments {} times; the result was: {}. \
+                 Calling it again will not change the result. Use it to give your final answer.",
+                call.count, call.last_result
+            ),
+        });
+        if call.nudged {
+            LoopCheck::ForceAnswer(nudge)
+        } else {
+            call.nudged = true;
+            LoopCheck::Nudge(nudge)
+        }
+    }
+
+    /// Record that `name(args)` ran and returned `result`.
+    pub(crate) fn record(&mut self, name: &str, args: &Value, result: Value) {
+        let call = self.calls.entry(key(name, args)).or_default();
+        call.count += 1;
+        call.last_result = result;
+    }
+}
+
+fn key(name: &str, args: &Value) -> (String, String) {
+    (name.to_string(), args.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nudges_then_forces_answer_after_threshold() {
+        let mut guard = ToolLoopGuard::new(2);
+        let args = json!({ "order_id": "42" });
+
+        for _ in 0..2 {
+            assert!(matches!(guard.check("lookup_order", &args), LoopCheck::Execute));
+            guard.record("lookup_order", &args, json!({ "status": "shipped" }));
+        }
+        assert!(matches!(
+            guard.check("lookup_order", &json!({ "order_id": "43" })),
+            LoopCheck::Execute
+        ));
+
+        let LoopCheck::Nudge(nudge) = guard.check("lookup_order", &args) else {
+            panic!("expected a nudge");
+        };
+        assert!(nudge["message"].as_str().unwrap().contains(r#"{"status":"shipped"}"#));
+        assert!(matches!(guard.check("lookup_order", &args), LoopCheck::ForceAnswer(_)));
+    }
+}
@@ -0,0 +1,173 @@
+//! Planning before acting.
+//!
+//! A [`Planner`] attached with
+//! [`LlmAgentBuilder::planner`](crate::LlmAgentBuilder::planner) adds a
+//! planning instruction to every model call and reads each response the model
+//! returns, after the `after_model` callbacks have run. Plans the model writes
+//! are reported as [`Event::planned`](adk_core::Event::planned) events so a UI
+//! can show them apart from the answer. When a round of tool calls fails, the
+//! planner can ask the model to revise its plan, and a response that signals
+//! completion ends the turn without running further tools.
+//!
+//! [`PlanReActPlanner`] is the built-in plan, act, re-plan loop.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_agent::{LlmAgentBuilder, PlanReActPlanner};
+//! use std::sync::Arc;
+//!
+//! let agent = LlmAgentBuilder::new("researcher")
+//!     .model(model)
+//!     .tool(search)
+//!     .planner(Arc::new(PlanReActPlanner::new().max_steps(5)))
+//!     .build()?;
+//! ```
+
+use adk_core::{Content, Part, ReadonlyContext};
+
+/// What a planner found in one model response.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanStep {
+    /// A new or revised plan written in this response.
+    pub plan: Option<String>,
+    /// The model signalled that it is done. Function calls in the same
+    /// response are not executed.
+    pub complete: bool,
+    /// The answer without planning text, stored under the agent's
+    /// `output_key` in place of the full response.
+    pub final_answer: Option<String>,
+}
+
+/// A tool call that failed during an execution step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolFailure {
+    /// Name of the tool.
+    pub name: String,
+    /// The error reported to the model.
+    pub error: String,
+}
+
+/// Shapes how an [`LlmAgent`](crate::LlmAgent) plans and executes.
+pub trait Planner: Send + Sync {
+    /// Instruction added after the agent's own instruction on every model
+    /// call. `None` adds nothing.
+    fn planning_instruction(&self, ctx: &dyn ReadonlyContext) -> Option<String>;
+
+    /// Reads a complete model response.
+    fn process_response(&self, content: &Content) -> PlanStep;
+
+    /// Message sent to the model after an execution step in which tools
+    /// failed, asking for a revised plan. The default sends nothing.
+    fn replan_instruction(&self, _failures: &[ToolFailure]) -> Option<String> {
+        None
+    }
+
+    /// Maximum number of execution steps (rounds of tool calls). Once reached,
+    /// the model is asked for its final answer without tools. The default is
+    /// no limit beyond the agent's own `max_tool_iterations`.
+    fn max_steps(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Marks the plan the model writes before acting.
+pub const PLANNING_TAG: &str = "/*PLANNING*/";
+/// Marks a plan revised after a failed step.
+pub const REPLANNING_TAG: &str = "/*REPLANNING*/";
+/// Marks the model's reasoning between tool calls.
+pub const REASONING_TAG: &str = "/*REASONING*/";
+/// Marks the tool calls that carry out a step.
+pub const ACTION_TAG: &str = "/*ACTION*/";
+/// Marks the final answer and ends the turn.
+pub const FINAL_ANSWER_TAG: &str = "/*FINAL_ANSWER*/";
+
+const TAGS: [&str; 5] = [PLANNING_TAG, REPLANNING_TAG, REASONING_TAG, ACTION_TAG, FINAL_ANSWER_TAG];
+
+/// Plan, act with tools, and re-plan when a step fails.
+///
+/// The model is asked to write its plan under [`PLANNING_TAG`], carry it out
+/// with tools, write a revised plan under [`REPLANNING_TAG`] when a step does
+/// not work, and give its answer under [`FINAL_ANSWER_TAG`].
+#[derive(Debug, Clone, Default)]
+pub struct PlanReActPlanner {
+    max_steps: Option<u32>,
+}
+
+impl PlanReActPlanner {
+    /// Creates a planner with no step limit of its own.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the number of execution steps before a final answer is required.
+    pub fn max_steps(mut self, max: u32) -> Self {
+        self.max_steps = Some(max.max(1));
+        self
+    }
+}
+
+impl Planner for PlanReActPlanner {
+    fn planning_instruction(&self, _ctx: &dyn ReadonlyContext) -> Option<String> {
+        Some(format!(
+            "Answer by planning first and then acting. Use the available tools to gather \
+             information instead of relying on memorized knowledge.\n\
+             1. Start with a numbered plan under {PLANNING_TAG}.\n\
+             2. Carry out the plan one step at a time. Before each tool call, explain the \
+             step under {REASONING_TAG}, then make the call under {ACTION_TAG}.\n\
+             3. If a step fails or the results change what is needed, write the revised \
+             remaining plan under {REPLANNING_TAG} and continue from it.\n\
+             4. When you have everything you need, write {FINAL_ANSWER_TAG} followed by \
+             the complete answer, and make no further tool calls."
+        ))
+    }
+
+    fn process_response(&self, content: &Content) -> PlanStep {
+        let text = content.parts.iter().filter_map(Part::text).collect::<String>();
+        let sections = split_sections(&text);
+        let plan = sections
+            .iter()
+            .rev()
+            .find(|(tag, body)| matches!(*tag, PLANNING_TAG | REPLANNING_TAG) && !body.is_empty())
+            .map(|(_, body)| body.to_string());
+        let final_answer = sections
+            .iter()
+            .find(|(tag, _)| *tag == FINAL_ANSWER_TAG)
+            .map(|(_, body)| body.to_string());
+        PlanStep { plan, complete: final_answer.is_some(), final_answer }
+    }
+
+    fn replan_instruction(&self, failures: &[ToolFailure]) -> Option<String> {
+        let failed = failures
+            .iter()
+            .map(|failure| format!("- {}: {}", failure.name, failure.error))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(format!(
+            "These steps failed:\n{failed}\nWrite a revised plan for the remaining work under \
+             {REPLANNING_TAG} before continuing."
+        ))
+    }
+
+    fn max_steps(&self) -> Option<u32> {
+        self.max_steps
+    }
+}
+
+/// Splits `text` at the planner tags into `(tag, trimmed body)` pairs, in order.
+/// Text before the first tag is dropped.
+fn split_sections(text: &str) -> Vec<(&'static str, &str)> {
+    let mut markers: Vec<(usize, &'static str)> = TAGS
+        .iter()
+        .flat_map(|tag| text.match_indices(tag).map(move |(at, _)| (at, *tag)))
+        .collect();
+    markers.sort_by_key(|(at, _)| *at);
+    markers
+        .iter()
+        .enumerate()
+        .map(|(i, (at, tag))| {
+            let end = markers.get(i + 1).map_or(text.len(), |(next, _)| *next);
+            (*tag, text[at + tag.len()..end].trim())
+        })
+        .collect()
+}
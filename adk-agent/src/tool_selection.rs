@@ -0,0 +1,191 @@
+//! Narrowing large tool sets before they reach the model.
+//!
+//! Providers cap how many tools a single request may declare (OpenAI accepts
+//! 128), and long tool lists dilute the model's attention even below that cap.
+//! A [`ToolSelector`] attached with
+//! [`LlmAgentBuilder::tool_selection`](crate::LlmAgentBuilder::tool_selection)
+//! ranks the agent's tools against the current user message and only declares
+//! the `top_k` most relevant ones.
+//!
+//! Without a selector, an agent whose tools exceed the model's
+//! [`max_tools`](adk_core::Llm::max_tools) fails before calling the model with
+//! an error naming the limit.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_agent::{LlmAgentBuilder, ToolSelector};
+//!
+//! let mut builder = LlmAgentBuilder::new("ops").model(model);
+//! for tool in all_sixty_tools {
+//!     builder = builder.tool(tool);
+//! }
+//! let agent = builder
+//!     .tool_selection(ToolSelector::top_k(10).always_include("escalate"))
+//!     .build()?;
+//! ```
+
+use adk_core::{AdkError, Result, Tool};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Scores tools by relevance to a query.
+///
+/// Implement this to plug in a retrieval step, for example embedding
+/// similarity between the query and each tool description.
+#[async_trait]
+pub trait ToolRanker: Send + Sync {
+    /// Returns one score per tool, in the same order as `tools`. Higher
+    /// scores are more relevant.
+    async fn score(&self, query: &str, tools: &[Arc<dyn Tool>]) -> Result<Vec<f32>>;
+}
+
+/// Ranks tools by word overlap between the query and each tool's name and
+/// description.
+///
+/// A query word found in the tool name counts twice as much as one found in
+/// the description. Words shorter than three characters are ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeywordToolRanker;
+
+#[async_trait]
+impl ToolRanker for KeywordToolRanker {
+    async fn score(&self, query: &str, tools: &[Arc<dyn Tool>]) -> Result<Vec<f32>> {
+        let query_words = words(query);
+        Ok(tools
+            .iter()
+            .map(|tool| {
+                let name = words(tool.name());
+                let description = words(tool.description());
+                query_words
+                    .iter()
+                    .map(|word| {
+                        let mut score = 0.0;
+                        if name.contains(word) {
+                            score += 2.0;
+                        }
+                        if description.contains(word) {
+                            score += 1.0;
+                        }
+                        score
+                    })
+                    .sum()
+            })
+            .collect())
+    }
+}
+
+/// Selects the tools declared to the model on each invocation.
+///
+/// When an agent has more than `top_k` tools, the selector ranks them against
+/// the current user message and keeps the `top_k` best, plus any tools named
+/// with [`always_include`](Self::always_include). Kept tools stay in
+/// registration order and ties keep the earlier tool.
+#[derive(Clone)]
+pub struct ToolSelector {
+    top_k: usize,
+    ranker: Arc<dyn ToolRanker>,
+    always_include: Vec<String>,
+}
+
+impl ToolSelector {
+    /// Keep at most `top_k` tools, ranked with [`KeywordToolRanker`].
+    pub fn top_k(top_k: usize) -> Self {
+        Self { top_k, ranker: Arc::new(KeywordToolRanker), always_include: Vec::new() }
+    }
+
+    /// Rank tools with a custom [`ToolRanker`].
+    #[must_use]
+    pub fn with_ranker(mut self, ranker: Arc<dyn ToolRanker>) -> Self {
+        self.ranker = ranker;
+        self
+    }
+
+    /// Always declare the tool named `name`, regardless of its rank.
+    ///
+    /// Pinned tools count towards `top_k`.
+    #[must_use]
+    pub fn always_include(mut self, name: impl Into<String>) -> Self {
+        self.always_include.push(name.into());
+        self
+    }
+
+    /// Maximum number of tools kept, not counting pinned tools beyond it.
+    pub fn limit(&self) -> usize {
+        self.top_k
+    }
+
+    /// Pick the tools to declare for `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ranker fails or returns the wrong number of
+    /// scores.
+    pub async fn select(&self, query: &str, tools: &[Arc<dyn Tool>]) -> Result<Vec<Arc<dyn Tool>>> {
+        if tools.len() <= self.top_k {
+            return Ok(tools.to_vec());
+        }
+
+        let scores = self.ranker.score(query, tools).await?;
+        if scores.len() != tools.len() {
+            return Err(AdkError::agent(format!(
+                "tool ranker returned {} scores for {} tools",
+                scores.len(),
+                tools.len()
+            )));
+        }
+
+        let mut keep: HashSet<usize> = tools
+            .iter()
+            .enumerate()
+            .filter(|(_, tool)| self.always_include.iter().any(|name| name == tool.name()))
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut ranked: Vec<usize> = (0..tools.len()).filter(|i| !keep.contains(i)).collect();
+        // Stable sort, so ties keep registration order.
+        ranked.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        let remaining = self.top_k.saturating_sub(keep.len());
+        keep.extend(ranked.into_iter().take(remaining));
+
+        Ok(tools
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep.contains(index))
+            .map(|(_, tool)| tool.clone())
+            .collect())
+    }
+}
+
+impl std::fmt::Debug for ToolSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolSelector")
+            .field("top_k", &self.top_k)
+            .field("always_include", &self.always_include)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Lowercase words of at least three characters; `snake_case` and other
+/// punctuation split words.
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_split_snake_case_and_drop_short_words() {
+        let words = words("get_weather for a CITY");
+        assert_eq!(
+            words,
+            HashSet::from(["get".into(), "weather".into(), "for".into(), "city".into()])
+        );
+    }
+}
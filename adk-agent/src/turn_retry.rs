@@ -0,0 +1,50 @@
+//! Retrying a whole turn when the agent's final answer fails validation.
+//!
+//! A validator attached with
+//! [`LlmAgentBuilder::turn_retry`](crate::LlmAgentBuilder::turn_retry) inspects
+//! each final answer, the content the agent would otherwise end its turn with.
+//! When it returns [`TurnVerdict::Retry`], the feedback is added to the
+//! conversation as a user message and the model gets another attempt. Each
+//! retry is recorded as an [`Event::turn_retried`](adk_core::Event::turn_retried)
+//! event. Once the retries are used up, the agent fails with an error naming the
+//! last feedback.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_agent::{LlmAgentBuilder, TurnVerdict};
+//!
+//! let agent = LlmAgentBuilder::new("writer")
+//!     .model(model)
+//!     .turn_retry(2, |content| {
+//!         let text = content.parts.iter().filter_map(|part| part.text()).collect::<String>();
+//!         if text.contains("Sources:") {
+//!             TurnVerdict::Accept
+//!         } else {
+//!             TurnVerdict::Retry("End the answer with a `Sources:` line.".into())
+//!         }
+//!     })
+//!     .build()?;
+//! ```
+
+use adk_core::Content;
+use std::sync::Arc;
+
+/// A turn validator's decision on a final answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TurnVerdict {
+    /// The answer is acceptable and ends the turn.
+    Accept,
+    /// The answer is rejected. The feedback tells the model what to fix.
+    Retry(String),
+}
+
+/// Inspects an agent's final answer. See [`TurnVerdict`].
+pub type TurnValidator = dyn Fn(&Content) -> TurnVerdict + Send + Sync;
+
+/// A validator and how many times a rejected turn may be retried.
+#[derive(Clone)]
+pub(crate) struct TurnRetryPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) validator: Arc<TurnValidator>,
+}
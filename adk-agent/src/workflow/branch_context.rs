@@ -0,0 +1,131 @@
+//! Context wrapper that runs a sub-agent on its own conversation branch.
+
+use adk_core::{
+    Agent, CallbackContext, Content, InvocationContext, Memory, ReadonlyContext, RunConfig,
+    Session, SharedState,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Context wrapper that places a sub-agent on a child branch.
+///
+/// Delegates all methods to the inner context and overrides `branch()`, so
+/// sibling sub-agents of a parallel run are told apart in the session.
+pub(crate) struct BranchContext {
+    inner: Arc<dyn InvocationContext>,
+    branch: String,
+}
+
+impl BranchContext {
+    /// Wraps `inner` on the branch `{inner.branch()}.{parent}.{child}`, or
+    /// `{parent}.{child}` from the root branch.
+    pub fn new(inner: Arc<dyn InvocationContext>, parent: &str, child: &str) -> Self {
+        let branch = match inner.branch() {
+            "" => format!("{parent}.{child}"),
+            current => format!("{current}.{parent}.{child}"),
+        };
+        Self { inner, branch }
+    }
+}
+
+#[async_trait]
+impl ReadonlyContext for BranchContext {
+    fn invocation_id(&self) -> &str {
+        self.inner.invocation_id()
+    }
+
+    fn agent_name(&self) -> &str {
+        self.inner.agent_name()
+    }
+
+    fn user_id(&self) -> &str {
+        self.inner.user_id()
+    }
+
+    fn app_name(&self) -> &str {
+        self.inner.app_name()
+    }
+
+    fn session_id(&self) -> &str {
+        self.inner.session_id()
+    }
+
+    fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    fn user_content(&self) -> &Content {
+        self.inner.user_content()
+    }
+}
+
+#[async_trait]
+impl CallbackContext for BranchContext {
+    fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
+        self.inner.artifacts()
+    }
+
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        self.inner.memory()
+    }
+
+    fn tool_outcome(&self) -> Option<adk_core::ToolOutcome> {
+        self.inner.tool_outcome()
+    }
+
+    fn tool_name(&self) -> Option<&str> {
+        self.inner.tool_name()
+    }
+
+    fn tool_input(&self) -> Option<&serde_json::Value> {
+        self.inner.tool_input()
+    }
+
+    fn shared_state(&self) -> Option<Arc<SharedState>> {
+        self.inner.shared_state()
+    }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.inner.blackboard()
+    }
+}
+
+#[async_trait]
+impl InvocationContext for BranchContext {
+    fn agent(&self) -> Arc<dyn Agent> {
+        self.inner.agent()
+    }
+
+    fn session(&self) -> &dyn Session {
+        self.inner.session()
+    }
+
+    fn run_config(&self) -> &RunConfig {
+        self.inner.run_config()
+    }
+
+    fn end_invocation(&self) {
+        self.inner.end_invocation();
+    }
+
+    fn ended(&self) -> bool {
+        self.inner.ended()
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+
+    fn conversation_buffer(&self) -> Option<Arc<adk_core::ConversationBuffer>> {
+        self.inner.conversation_buffer()
+    }
+
+    fn user_scopes(&self) -> Vec<String> {
+        self.inner.user_scopes()
+    }
+
+    fn request_metadata(&self) -> HashMap<String, serde_json::Value> {
+        self.inner.request_metadata()
+    }
+}
@@ -1,7 +1,9 @@
 //! Rule-based conditional routing agent.
 //!
-//! `ConditionalAgent` provides **synchronous, rule-based** conditional routing.
-//! The condition function is evaluated synchronously and must return a boolean.
+//! `ConditionalAgent` provides **rule-based** conditional routing. Conditions
+//! are checked in order and the agent of the first one that holds runs.
+//! A condition is either a synchronous function of the invocation context or
+//! an async predicate, for example one that awaits a quick classifier call.
 //!
 //! # When to Use
 //!
@@ -30,19 +32,44 @@
 use crate::skill_shim::load_skill_index;
 use crate::skill_shim::{SelectionPolicy, SkillIndex};
 use adk_core::{
-    AfterAgentCallback, Agent, BeforeAgentCallback, CallbackContext, Event, EventStream,
-    InvocationContext, Result,
+    AdkError, AfterAgentCallback, Agent, BeforeAgentCallback, BranchChoice, CallbackContext, Event,
+    EventStream, InvocationContext, ReadonlyContext, Result,
 };
 use async_stream::stream;
 use async_trait::async_trait;
 use futures::StreamExt;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 type ConditionFn = Arc<dyn Fn(&dyn InvocationContext) -> bool + Send + Sync>;
 
+/// Async condition for an arm of a [`ConditionalAgent`].
+pub type AsyncCondition = Arc<
+    dyn Fn(Arc<dyn ReadonlyContext>) -> Pin<Box<dyn Future<Output = Result<bool>> + Send>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
+enum Condition {
+    Sync(ConditionFn),
+    Async(AsyncCondition),
+}
+
+/// A condition and the agent that runs when it is the first to hold.
+#[derive(Clone)]
+struct Arm {
+    condition: Condition,
+    agent: Arc<dyn Agent>,
+}
+
 /// Rule-based conditional routing agent.
 ///
-/// Executes one of two sub-agents based on a synchronous condition function.
+/// Executes the sub-agent of the first condition that holds, or the else
+/// agent when none does. Before the chosen agent runs, an
+/// [`Event::branch_chosen`] event records the choice.
 /// For LLM-based intelligent routing, use [`crate::LlmConditionalAgent`] instead.
 ///
 /// # Example
@@ -54,13 +81,21 @@ type ConditionFn = Arc<dyn Fn(&dyn InvocationContext) -> bool + Send + Sync>;
 ///     |ctx| ctx.session().state().get("is_premium").map(|v| v.as_bool()).flatten().unwrap_or(false),
 ///     Arc::new(premium_agent),
 /// ).with_else(Arc::new(basic_agent));
+///
+/// // Else-if chain with async conditions
+/// let router = ConditionalAgent::builder("support_router")
+///     .when(|ctx| async move { classify(ctx).await.map(|c| c == "billing") }, billing)
+///     .when(|ctx| async move { classify(ctx).await.map(|c| c == "outage") }, outage)
+///     .otherwise(general)
+///     .branch_key("route")
+///     .build()?;
 /// ```
 pub struct ConditionalAgent {
     name: String,
     description: String,
-    condition: ConditionFn,
-    if_agent: Arc<dyn Agent>,
+    arms: Arc<Vec<Arm>>,
     else_agent: Option<Arc<dyn Agent>>,
+    branch_key: Option<String>,
     /// Cached list of all branch agents for tree discovery via `sub_agents()`.
     all_agents: Vec<Arc<dyn Agent>>,
     skills_index: Option<Arc<SkillIndex>>,
@@ -80,9 +115,12 @@ impl ConditionalAgent {
         Self {
             name: name.into(),
             description: String::new(),
-            condition: Arc::new(condition),
-            if_agent,
+            arms: Arc::new(vec![Arm {
+                condition: Condition::Sync(Arc::new(condition)),
+                agent: if_agent,
+            }]),
             else_agent: None,
+            branch_key: None,
             all_agents,
             skills_index: None,
             skill_policy: SelectionPolicy::default(),
@@ -92,12 +130,24 @@ impl ConditionalAgent {
         }
     }
 
+    /// Create a builder for an else-if chain of async conditions.
+    pub fn builder(name: impl Into<String>) -> ConditionalAgentBuilder {
+        ConditionalAgentBuilder::new(name)
+    }
+
     /// Set the agent description.
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = desc.into();
         self
     }
 
+    /// Also store the name of the chosen agent in state under `key`, or `null`
+    /// when no branch runs.
+    pub fn with_branch_key(mut self, key: impl Into<String>) -> Self {
+        self.branch_key = Some(key.into());
+        self
+    }
+
     /// Set the else-branch agent executed when the condition is false.
     pub fn with_else(mut self, else_agent: Arc<dyn Agent>) -> Self {
         self.all_agents.push(else_agent.clone());
@@ -157,6 +207,104 @@ impl ConditionalAgent {
     }
 }
 
+/// Builder for a [`ConditionalAgent`] with an else-if chain of conditions.
+pub struct ConditionalAgentBuilder {
+    name: String,
+    description: Option<String>,
+    arms: Vec<Arm>,
+    otherwise: Option<Arc<dyn Agent>>,
+    branch_key: Option<String>,
+    before_callbacks: Vec<BeforeAgentCallback>,
+    after_callbacks: Vec<AfterAgentCallback>,
+}
+
+impl ConditionalAgentBuilder {
+    /// Create a new builder with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            arms: Vec::new(),
+            otherwise: None,
+            branch_key: None,
+            before_callbacks: Vec::new(),
+            after_callbacks: Vec::new(),
+        }
+    }
+
+    /// Set a description for the agent.
+    pub fn description(mut self, desc: impl Into<String>) -> Self {
+        self.description = Some(desc.into());
+        self
+    }
+
+    /// Run `agent` when `condition` holds and no earlier condition did.
+    ///
+    /// An error from `condition` fails the invocation.
+    pub fn when<F, Fut>(mut self, condition: F, agent: Arc<dyn Agent>) -> Self
+    where
+        F: Fn(Arc<dyn ReadonlyContext>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<bool>> + Send + 'static,
+    {
+        let condition: AsyncCondition = Arc::new(move |ctx| Box::pin(condition(ctx)));
+        self.arms.push(Arm { condition: Condition::Async(condition), agent });
+        self
+    }
+
+    /// Set the agent to run when no condition holds.
+    pub fn otherwise(mut self, agent: Arc<dyn Agent>) -> Self {
+        self.otherwise = Some(agent);
+        self
+    }
+
+    /// Also store the name of the chosen agent in state under `key`.
+    pub fn branch_key(mut self, key: impl Into<String>) -> Self {
+        self.branch_key = Some(key.into());
+        self
+    }
+
+    /// Add a before-agent callback.
+    pub fn before_callback(mut self, callback: BeforeAgentCallback) -> Self {
+        self.before_callbacks.push(callback);
+        self
+    }
+
+    /// Add an after-agent callback.
+    pub fn after_callback(mut self, callback: AfterAgentCallback) -> Self {
+        self.after_callbacks.push(callback);
+        self
+    }
+
+    /// Build the ConditionalAgent.
+    pub fn build(self) -> Result<ConditionalAgent> {
+        if self.arms.is_empty() {
+            return Err(AdkError::agent(
+                "At least one `when` arm is required for ConditionalAgent",
+            ));
+        }
+
+        let mut all_agents: Vec<Arc<dyn Agent>> =
+            self.arms.iter().map(|arm| arm.agent.clone()).collect();
+        if let Some(ref otherwise) = self.otherwise {
+            all_agents.push(otherwise.clone());
+        }
+
+        Ok(ConditionalAgent {
+            name: self.name,
+            description: self.description.unwrap_or_default(),
+            arms: Arc::new(self.arms),
+            else_agent: self.otherwise,
+            branch_key: self.branch_key,
+            all_agents,
+            skills_index: None,
+            skill_policy: SelectionPolicy::default(),
+            max_skill_chars: 2000,
+            before_callbacks: Arc::new(self.before_callbacks),
+            after_callbacks: Arc::new(self.after_callbacks),
+        })
+    }
+}
+
 #[async_trait]
 impl Agent for ConditionalAgent {
     fn name(&self) -> &str {
@@ -171,6 +319,12 @@ impl Agent for ConditionalAgent {
         &self.all_agents
     }
 
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_sub_agents(&self.all_agents)
+            .with_capability("conditional")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
         let run_ctx = super::skill_context::with_skill_injected_context(
             ctx,
@@ -180,11 +334,11 @@ impl Agent for ConditionalAgent {
         );
         let before_callbacks = self.before_callbacks.clone();
         let after_callbacks = self.after_callbacks.clone();
-        let if_agent = self.if_agent.clone();
+        let arms = self.arms.clone();
         let else_agent = self.else_agent.clone();
+        let branch_key = self.branch_key.clone();
         let agent_name = self.name.clone();
         let invocation_id = run_ctx.invocation_id().to_string();
-        let condition = self.condition.clone();
 
         let s = stream! {
             for callback in before_callbacks.as_ref() {
@@ -221,11 +375,48 @@ impl Agent for ConditionalAgent {
                 }
             }
 
-            let target_agent = if condition(run_ctx.as_ref()) {
-                Some(if_agent)
-            } else {
-                else_agent
+            let mut chosen: Option<(usize, Arc<dyn Agent>)> = None;
+            for (index, arm) in arms.iter().enumerate() {
+                let holds = match &arm.condition {
+                    Condition::Sync(condition) => condition(run_ctx.as_ref()),
+                    Condition::Async(condition) => {
+                        match condition(run_ctx.clone() as Arc<dyn ReadonlyContext>).await {
+                            Ok(holds) => holds,
+                            Err(e) => {
+                                yield Err(AdkError::agent(format!(
+                                    "Condition {} of conditional agent '{}' (for '{}') failed: {}",
+                                    index + 1,
+                                    agent_name,
+                                    arm.agent.name(),
+                                    e
+                                )));
+                                return;
+                            }
+                        }
+                    }
+                };
+                if holds {
+                    chosen = Some((index + 1, arm.agent.clone()));
+                    break;
+                }
+            }
+            let (arm, target_agent) = match chosen {
+                Some((arm, agent)) => (Some(arm), Some(agent)),
+                None => (None, else_agent),
+            };
+
+            let choice = BranchChoice {
+                arm,
+                agent: target_agent.as_ref().map(|agent| agent.name().to_string()),
             };
+            tracing::debug!(agent.name = %agent_name, ?choice, "conditional branch chosen");
+            let mut choice_event =
+                Event::branch_chosen(&invocation_id, &agent_name, choice.clone());
+            if let Some(key) = &branch_key {
+                let value = choice.agent.map_or(Value::Null, Value::String);
+                choice_event.actions.state_delta.insert(key.clone(), value);
+            }
+            yield Ok(choice_event);
 
             if let Some(agent) = target_agent {
                 let mut stream = match agent.run(run_ctx.clone()).await {
@@ -258,6 +258,13 @@ impl Agent for LlmConditionalAgent {
         &self.all_agents
     }
 
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_model(self.model.name())
+            .with_sub_agents(&self.all_agents)
+            .with_capability("llm_conditional")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
         let run_ctx = super::skill_context::with_skill_injected_context(
             ctx,
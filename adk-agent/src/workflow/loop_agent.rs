@@ -3,28 +3,38 @@ use crate::skill_shim::load_skill_index;
 use crate::skill_shim::{SelectionPolicy, SkillIndex};
 use adk_core::{
     AfterAgentCallback, Agent, BeforeAgentCallback, CallbackContext, Content, Event, EventStream,
-    InvocationContext, ReadonlyContext, Result, Session, State,
+    InvocationContext, LoopExit, LoopExitReason, ReadonlyContext, Result, Session, State,
 };
 use async_stream::stream;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 /// Default maximum iterations for LoopAgent when none is specified.
 /// Prevents infinite loops from consuming unbounded resources.
 pub const DEFAULT_LOOP_MAX_ITERATIONS: u32 = 1000;
 
+/// Exit condition checked against session state after each loop iteration.
+pub type LoopCondition = Arc<dyn Fn(&dyn State) -> bool + Send + Sync>;
+
 /// Loop agent executes sub-agents repeatedly for N iterations or until escalation
 pub struct LoopAgent {
     name: String,
     description: String,
     sub_agents: Vec<Arc<dyn Agent>>,
     max_iterations: u32,
+    until: Option<LoopCondition>,
+    max_duration: Option<Duration>,
     skills_index: Option<Arc<SkillIndex>>,
     skill_policy: SelectionPolicy,
     max_skill_chars: usize,
     before_callbacks: Arc<Vec<BeforeAgentCallback>>,
     after_callbacks: Arc<Vec<AfterAgentCallback>>,
+    /// State keys for sub-agents' final texts, keyed by sub-agent name.
+    output_keys: Arc<HashMap<String, String>>,
+    /// Whether an escalation is followed by an [`Event::pipeline_stopped`] event.
+    report_escalation: bool,
 }
 
 impl LoopAgent {
@@ -35,11 +45,15 @@ impl LoopAgent {
             description: String::new(),
             sub_agents,
             max_iterations: DEFAULT_LOOP_MAX_ITERATIONS,
+            until: None,
+            max_duration: None,
             skills_index: None,
             skill_policy: SelectionPolicy::default(),
             max_skill_chars: 2000,
             before_callbacks: Arc::new(Vec::new()),
             after_callbacks: Arc::new(Vec::new()),
+            output_keys: Arc::new(HashMap::new()),
+            report_escalation: false,
         }
     }
 
@@ -55,6 +69,25 @@ impl LoopAgent {
         self
     }
 
+    /// Stop once `condition` holds for the session state at the end of an
+    /// iteration, including state changes made by tools the sub-agents called.
+    ///
+    /// ```rust,ignore
+    /// let refine = LoopAgent::new("refine", vec![writer, critic])
+    ///     .until(|state| state.get("quality_score").and_then(|v| v.as_f64()) >= Some(0.9));
+    /// ```
+    pub fn until(mut self, condition: impl Fn(&dyn State) -> bool + Send + Sync + 'static) -> Self {
+        self.until = Some(Arc::new(condition));
+        self
+    }
+
+    /// Stop once the loop has run for `max`, cancelling the sub-agent that is
+    /// running when the time is up.
+    pub fn with_max_duration(mut self, max: Duration) -> Self {
+        self.max_duration = Some(max);
+        self
+    }
+
     /// Set a preloaded skills index for this agent.
     #[cfg(feature = "skills")]
     pub fn with_skills(mut self, index: SkillIndex) -> Self {
@@ -105,6 +138,19 @@ impl LoopAgent {
         }
         self
     }
+
+    /// Store the final text of each named sub-agent under its state key as
+    /// soon as the sub-agent finishes.
+    pub(crate) fn with_output_keys(mut self, keys: HashMap<String, String>) -> Self {
+        self.output_keys = Arc::new(keys);
+        self
+    }
+
+    /// Follow an escalation with an [`Event::pipeline_stopped`] event.
+    pub(crate) fn with_escalation_report(mut self) -> Self {
+        self.report_escalation = true;
+        self
+    }
 }
 
 struct HistoryTrackingSession {
@@ -309,6 +355,14 @@ impl CallbackContext for HistoryTrackingContext {
     fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
         self.parent_ctx.artifacts()
     }
+
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.parent_ctx.memory()
+    }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.parent_ctx.blackboard()
+    }
 }
 
 #[async_trait]
@@ -317,10 +371,6 @@ impl InvocationContext for HistoryTrackingContext {
         self.parent_ctx.agent()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        self.parent_ctx.memory()
-    }
-
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -337,6 +387,14 @@ impl InvocationContext for HistoryTrackingContext {
         self.parent_ctx.ended()
     }
 
+    fn is_cancelled(&self) -> bool {
+        self.parent_ctx.is_cancelled()
+    }
+
+    fn conversation_buffer(&self) -> Option<Arc<adk_core::ConversationBuffer>> {
+        self.parent_ctx.conversation_buffer()
+    }
+
     fn user_scopes(&self) -> Vec<String> {
         self.parent_ctx.user_scopes()
     }
@@ -360,12 +418,22 @@ impl Agent for LoopAgent {
         &self.sub_agents
     }
 
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_sub_agents(&self.sub_agents)
+            .with_capability("loop")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
         let sub_agents = self.sub_agents.clone();
         let max_iterations = self.max_iterations;
         let before_callbacks = self.before_callbacks.clone();
         let after_callbacks = self.after_callbacks.clone();
         let agent_name = self.name.clone();
+        let output_keys = self.output_keys.clone();
+        let report_escalation = self.report_escalation;
+        let until = self.until.clone();
+        let max_duration = self.max_duration;
         let run_ctx = super::skill_context::with_skill_injected_context(
             ctx,
             self.skills_index.as_ref(),
@@ -407,19 +475,50 @@ impl Agent for LoopAgent {
             }
 
             let mut remaining = max_iterations;
+            let deadline = max_duration.map(|max| tokio::time::Instant::now() + max);
+            let mut iterations: u32 = 0;
+            // Only loops given an exit condition or time limit report how they ended.
+            let mut exit_reason: Option<LoopExitReason> = None;
 
             loop {
                 let mut should_exit = false;
+                let mut timed_out = false;
+                iterations += 1;
 
-                for agent in &sub_agents {
+                for (index, agent) in sub_agents.iter().enumerate() {
+                    // Cooperative cancellation: don't start the next sub-agent.
+                    if run_ctx.is_cancelled() {
+                        tracing::info!(agent.name = %agent_name, "invocation cancelled — stopping workflow");
+                        return;
+                    }
                     let mut stream = agent.run(run_ctx.clone() as Arc<dyn InvocationContext>).await?;
-
-                    while let Some(result) = stream.next().await {
+                    let mut last_text: Option<String> = None;
+                    let mut escalation_reason: Option<String> = None;
+
+                    loop {
+                        let next = match deadline {
+                            Some(deadline) => {
+                                match tokio::time::timeout_at(deadline, stream.next()).await {
+                                    Ok(next) => next,
+                                    Err(_) => {
+                                        timed_out = true;
+                                        break;
+                                    }
+                                }
+                            }
+                            None => stream.next().await,
+                        };
+                        let Some(result) = next else { break };
                         match result {
                             Ok(event) => {
                                 run_ctx.apply_event(&event);
-                                if event.actions.escalate {
+                                let text = super::final_text(&event);
+                                if event.actions.escalate && !should_exit {
                                     should_exit = true;
+                                    escalation_reason = text.clone().or_else(|| last_text.clone());
+                                }
+                                if text.is_some() {
+                                    last_text = text;
                                 }
                                 yield Ok(event);
                             }
@@ -430,21 +529,87 @@ impl Agent for LoopAgent {
                         }
                     }
 
+                    if timed_out {
+                        // Dropping the stream cancels the sub-agent mid-run.
+                        break;
+                    }
+
+                    // Make the step's answer available to the steps after it.
+                    if let (Some(key), Some(text)) = (output_keys.get(agent.name()), last_text) {
+                        let mut output_event = Event::new(run_ctx.invocation_id());
+                        output_event.author = agent_name.clone();
+                        output_event
+                            .actions
+                            .state_delta
+                            .insert(key.clone(), serde_json::Value::String(text));
+                        run_ctx.apply_event(&output_event);
+                        yield Ok(output_event);
+                    }
+
                     if should_exit {
+                        if report_escalation {
+                            let escalation = adk_core::PipelineEscalation {
+                                agent: agent.name().to_string(),
+                                step: index + 1,
+                                steps: sub_agents.len(),
+                                reason: escalation_reason,
+                            };
+                            tracing::info!(
+                                agent.name = %agent_name,
+                                step = %escalation.agent,
+                                "pipeline step escalated, skipping the remaining steps"
+                            );
+                            yield Ok(Event::pipeline_stopped(
+                                run_ctx.invocation_id(),
+                                &agent_name,
+                                escalation,
+                            ));
+                        }
                         break;
                     }
                 }
 
+                let expired =
+                    deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline);
+                if timed_out || expired {
+                    tracing::info!(
+                        agent.name = %agent_name,
+                        iterations,
+                        "loop ran past its maximum duration"
+                    );
+                    exit_reason = Some(LoopExitReason::MaxDuration);
+                    break;
+                }
+
                 if should_exit {
                     break;
                 }
 
+                if let Some(ref until) = until
+                    && until(run_ctx.session().state())
+                {
+                    tracing::info!(agent.name = %agent_name, iterations, "loop exit condition met");
+                    exit_reason = Some(LoopExitReason::Condition);
+                    break;
+                }
+
                 remaining -= 1;
                 if remaining == 0 {
+                    if until.is_some() || deadline.is_some() {
+                        exit_reason = Some(LoopExitReason::MaxIterations);
+                    }
                     break;
                 }
             }
 
+            if let Some(reason) = exit_reason {
+                yield Ok(Event::loop_exited(
+                    run_ctx.invocation_id(),
+                    &agent_name,
+                    LoopExit { reason, iterations },
+                ));
+            }
+
             // ===== AFTER AGENT CALLBACKS =====
             for callback in after_callbacks.as_ref() {
                 match callback(run_ctx.clone() as Arc<dyn CallbackContext>).await {
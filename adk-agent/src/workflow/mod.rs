@@ -1,3 +1,4 @@
+mod branch_context;
 mod conditional_agent;
 mod llm_conditional_agent;
 mod loop_agent;
@@ -6,9 +7,27 @@ mod sequential_agent;
 pub(crate) mod shared_state_context;
 mod skill_context;
 
-pub use conditional_agent::ConditionalAgent;
+pub use conditional_agent::{AsyncCondition, ConditionalAgent, ConditionalAgentBuilder};
 pub use llm_conditional_agent::{LlmConditionalAgent, LlmConditionalAgentBuilder};
-pub use loop_agent::{DEFAULT_LOOP_MAX_ITERATIONS, LoopAgent};
-pub use parallel_agent::ParallelAgent;
+pub use loop_agent::{DEFAULT_LOOP_MAX_ITERATIONS, LoopAgent, LoopCondition};
+pub use parallel_agent::{PARALLEL_OUTPUT_PREFIX, ParallelAgent};
 pub use sequential_agent::SequentialAgent;
 pub(crate) use skill_context::with_user_content_override;
+
+use adk_core::{Event, Part};
+
+/// Text of a complete model answer, or `None` for partial chunks, tool calls,
+/// and tool results.
+fn final_text(event: &Event) -> Option<String> {
+    let content = event.llm_response.content.as_ref()?;
+    if event.llm_response.partial
+        || content
+            .parts
+            .iter()
+            .any(|part| matches!(part, Part::FunctionCall { .. } | Part::FunctionResponse { .. }))
+    {
+        return None;
+    }
+    let text = content.parts.iter().filter_map(Part::text).collect::<String>();
+    (!text.is_empty()).then_some(text)
+}
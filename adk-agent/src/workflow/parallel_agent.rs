@@ -3,14 +3,21 @@ use crate::skill_shim::load_skill_index;
 use crate::skill_shim::{SelectionPolicy, SkillIndex};
 use adk_core::{
     AfterAgentCallback, Agent, BeforeAgentCallback, CallbackContext, Event, EventStream,
-    InvocationContext, Result, SharedState,
+    InvocationContext, ReadonlyContext, Result, SharedState,
 };
 use async_stream::stream;
 use async_trait::async_trait;
+use futures::{StreamExt, stream};
 use std::sync::Arc;
 
+use super::branch_context::BranchContext;
+use super::final_text;
 use super::shared_state_context::SharedStateContext;
 
+/// State key prefix under which [`ParallelAgent::output_key_per_child`] stores
+/// each sub-agent's final text, followed by the sub-agent's name.
+pub const PARALLEL_OUTPUT_PREFIX: &str = "parallel:";
+
 /// Parallel agent executes sub-agents concurrently
 pub struct ParallelAgent {
     name: String,
@@ -22,6 +29,9 @@ pub struct ParallelAgent {
     before_callbacks: Arc<Vec<BeforeAgentCallback>>,
     after_callbacks: Arc<Vec<AfterAgentCallback>>,
     shared_state_enabled: bool,
+    max_concurrency: Option<usize>,
+    output_key_per_child: bool,
+    fail_fast: bool,
 }
 
 impl ParallelAgent {
@@ -37,6 +47,9 @@ impl ParallelAgent {
             before_callbacks: Arc::new(Vec::new()),
             after_callbacks: Arc::new(Vec::new()),
             shared_state_enabled: false,
+            max_concurrency: None,
+            output_key_per_child: false,
+            fail_fast: true,
         }
     }
 
@@ -106,6 +119,39 @@ impl ParallelAgent {
         self.shared_state_enabled = true;
         self
     }
+
+    /// Run at most `n` sub-agents at a time; the rest start as earlier ones
+    /// finish. By default every sub-agent starts at once.
+    pub fn max_concurrency(mut self, n: usize) -> Self {
+        self.max_concurrency = Some(n.max(1));
+        self
+    }
+
+    /// Store each sub-agent's final text in state under
+    /// `parallel:<agent_name>` (see [`PARALLEL_OUTPUT_PREFIX`]) when it finishes.
+    pub fn output_key_per_child(mut self, enabled: bool) -> Self {
+        self.output_key_per_child = enabled;
+        self
+    }
+
+    /// Whether a failing sub-agent stops the others (the default).
+    ///
+    /// With `false`, the remaining sub-agents run to completion. Each failure
+    /// is reported as an event on the failed sub-agent's branch, carrying the
+    /// error in `llm_response.error_code` and `error_message`, and the parallel
+    /// agent itself succeeds.
+    pub fn fail_fast(mut self, enabled: bool) -> Self {
+        self.fail_fast = enabled;
+        self
+    }
+}
+
+/// A sub-agent's progress within one run.
+struct Branch {
+    agent_name: String,
+    branch: String,
+    last_text: Option<String>,
+    failed: bool,
 }
 
 #[async_trait]
@@ -122,6 +168,12 @@ impl Agent for ParallelAgent {
         &self.sub_agents
     }
 
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(&self.name, &self.description)
+            .with_sub_agents(&self.sub_agents)
+            .with_capability("parallel")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
         let sub_agents = self.sub_agents.clone();
         let run_ctx = super::skill_context::with_skill_injected_context(
@@ -135,10 +187,11 @@ impl Agent for ParallelAgent {
         let agent_name = self.name.clone();
         let invocation_id = run_ctx.invocation_id().to_string();
         let shared_state_enabled = self.shared_state_enabled;
+        let max_concurrency = self.max_concurrency;
+        let output_key_per_child = self.output_key_per_child;
+        let fail_fast = self.fail_fast;
 
         let s = stream! {
-            use futures::stream::{FuturesUnordered, StreamExt};
-
             for callback in before_callbacks.as_ref() {
                 match callback(run_ctx.clone() as Arc<dyn CallbackContext>).await {
                     Ok(Some(content)) => {
@@ -173,8 +226,6 @@ impl Agent for ParallelAgent {
                 }
             }
 
-            let mut futures = FuturesUnordered::new();
-
             // Create shared state if enabled (fresh per run)
             let shared = if shared_state_enabled {
                 Some(Arc::new(SharedState::new()))
@@ -182,50 +233,100 @@ impl Agent for ParallelAgent {
                 None
             };
 
-            for agent in sub_agents {
+            // Cooperative cancellation: don't start the branches.
+            if run_ctx.is_cancelled() {
+                tracing::info!(agent.name = %agent_name, "invocation cancelled — stopping workflow");
+                return;
+            }
+
+            let mut branches = Vec::with_capacity(sub_agents.len());
+            let mut branch_streams = Vec::with_capacity(sub_agents.len());
+            for (index, agent) in sub_agents.into_iter().enumerate() {
                 let ctx: Arc<dyn InvocationContext> = if let Some(ref shared) = shared {
                     Arc::new(SharedStateContext::new(run_ctx.clone(), shared.clone()))
                 } else {
                     run_ctx.clone()
                 };
-                futures.push(async move {
-                    agent.run(ctx).await
+                // Each sub-agent writes on its own branch, so siblings'
+                // events can be told apart in the shared session.
+                let ctx = Arc::new(BranchContext::new(ctx, &agent_name, agent.name()));
+                branches.push(Branch {
+                    agent_name: agent.name().to_string(),
+                    branch: ctx.branch().to_string(),
+                    last_text: None,
+                    failed: false,
                 });
+                // Items are tagged with the branch index; `None` marks the end
+                // of the branch. Nothing runs until the branch is polled.
+                let branch_stream = stream::once(async move { agent.run(ctx).await })
+                    .flat_map(|started| match started {
+                        Ok(events) => events.map(Some).boxed(),
+                        Err(e) => stream::once(async move { Some(Err(e)) }).boxed(),
+                    })
+                    .chain(stream::once(async { None }))
+                    .map(move |item| (index, item))
+                    .boxed();
+                branch_streams.push(branch_stream);
             }
 
-            let mut first_error: Option<adk_core::AdkError> = None;
+            // Branches run concurrently, at most `max_concurrency` at a time.
+            let mut merged = stream::iter(branch_streams).flatten_unordered(max_concurrency);
 
-            while let Some(result) = futures.next().await {
-                match result {
-                    Ok(mut stream) => {
-                        while let Some(event_result) = stream.next().await {
-                            match event_result {
-                                Ok(event) => yield Ok(event),
-                                Err(e) => {
-                                    if first_error.is_none() {
-                                        first_error = Some(e);
-                                    }
-                                    // Continue draining other agents instead of returning
-                                    break;
-                                }
-                            }
+            while let Some((index, item)) = merged.next().await {
+                let branch = &mut branches[index];
+                match item {
+                    Some(Ok(mut event)) => {
+                        if branch.failed {
+                            continue;
+                        }
+                        // Nested workflows have already set a deeper branch
+                        if event.branch.is_empty() {
+                            event.branch = branch.branch.clone();
                         }
+                        if output_key_per_child && let Some(text) = final_text(&event) {
+                            branch.last_text = Some(text);
+                        }
+                        yield Ok(event);
                     }
-                    Err(e) => {
-                        if first_error.is_none() {
-                            first_error = Some(e);
+                    Some(Err(e)) => {
+                        if branch.failed {
+                            continue;
+                        }
+                        branch.failed = true;
+                        if fail_fast {
+                            // Dropping the merged stream cancels the other branches.
+                            yield Err(e);
+                            return;
+                        }
+                        tracing::warn!(
+                            agent.name = %agent_name,
+                            branch = %branch.branch,
+                            error = %e,
+                            "parallel branch failed, continuing with the others"
+                        );
+                        let mut failed_event = Event::new(&invocation_id);
+                        failed_event.author = branch.agent_name.clone();
+                        failed_event.branch = branch.branch.clone();
+                        failed_event.llm_response.error_code = Some(e.code.to_string());
+                        failed_event.llm_response.error_message = Some(e.to_string());
+                        failed_event.llm_response.turn_complete = true;
+                        yield Ok(failed_event);
+                    }
+                    None => {
+                        if let Some(text) = branch.last_text.take() {
+                            let mut output_event = Event::new(&invocation_id);
+                            output_event.author = branch.agent_name.clone();
+                            output_event.branch = branch.branch.clone();
+                            output_event.actions.state_delta.insert(
+                                format!("{PARALLEL_OUTPUT_PREFIX}{}", branch.agent_name),
+                                serde_json::Value::String(text),
+                            );
+                            yield Ok(output_event);
                         }
-                        // Continue draining remaining futures to avoid resource leaks
                     }
                 }
             }
 
-            // After all agents complete, propagate the first error if any
-            if let Some(e) = first_error {
-                yield Err(e);
-                return;
-            }
-
             for callback in after_callbacks.as_ref() {
                 match callback(run_ctx.clone() as Arc<dyn CallbackContext>).await {
                     Ok(Some(content)) => {
@@ -8,6 +8,11 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 /// Sequential agent executes sub-agents once in order
+///
+/// A sub-agent that escalates (sets `EventActions::escalate`) stops the
+/// pipeline: the steps after it do not run, and the pipeline emits an
+/// [`Event::pipeline_stopped`](adk_core::Event::pipeline_stopped) event naming
+/// the step and its reason.
 pub struct SequentialAgent {
     loop_agent: LoopAgent,
 }
@@ -15,7 +20,30 @@ pub struct SequentialAgent {
 impl SequentialAgent {
     /// Create a new sequential agent with the given name and sub-agents.
     pub fn new(name: impl Into<String>, sub_agents: Vec<Arc<dyn Agent>>) -> Self {
-        Self { loop_agent: LoopAgent::new(name, sub_agents).with_max_iterations(1) }
+        Self {
+            loop_agent: LoopAgent::new(name, sub_agents)
+                .with_max_iterations(1)
+                .with_escalation_report(),
+        }
+    }
+
+    /// Store each listed step's final text in state when the step finishes.
+    ///
+    /// Pairs are `(sub-agent name, state key)`. The next steps can read the
+    /// value through instruction templating, e.g. `{research}`.
+    ///
+    /// ```rust,ignore
+    /// let pipeline = SequentialAgent::new("article", vec![researcher, writer, reviewer])
+    ///     .with_output_keys([("researcher", "research"), ("writer", "draft")]);
+    /// ```
+    pub fn with_output_keys<K, V>(mut self, keys: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let keys = keys.into_iter().map(|(agent, key)| (agent.into(), key.into())).collect();
+        self.loop_agent = self.loop_agent.with_output_keys(keys);
+        self
     }
 
     /// Set the agent description.
@@ -85,6 +113,12 @@ impl Agent for SequentialAgent {
         self.loop_agent.sub_agents()
     }
 
+    fn info(&self) -> adk_core::AgentInfo {
+        adk_core::AgentInfo::new(self.name(), self.description())
+            .with_sub_agents(self.sub_agents())
+            .with_capability("sequential")
+    }
+
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
         self.loop_agent.run(ctx).await
     }
@@ -60,6 +60,10 @@ impl CallbackContext for SharedStateContext {
         self.inner.artifacts()
     }
 
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        self.inner.memory()
+    }
+
     fn tool_outcome(&self) -> Option<adk_core::ToolOutcome> {
         self.inner.tool_outcome()
     }
@@ -75,6 +79,10 @@ impl CallbackContext for SharedStateContext {
     fn shared_state(&self) -> Option<Arc<SharedState>> {
         Some(self.shared_state.clone())
     }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.inner.blackboard()
+    }
 }
 
 #[async_trait]
@@ -83,10 +91,6 @@ impl InvocationContext for SharedStateContext {
         self.inner.agent()
     }
 
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        self.inner.memory()
-    }
-
     fn session(&self) -> &dyn Session {
         self.inner.session()
     }
@@ -103,6 +107,14 @@ impl InvocationContext for SharedStateContext {
         self.inner.ended()
     }
 
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+
+    fn conversation_buffer(&self) -> Option<Arc<adk_core::ConversationBuffer>> {
+        self.inner.conversation_buffer()
+    }
+
     fn user_scopes(&self) -> Vec<String> {
         self.inner.user_scopes()
     }
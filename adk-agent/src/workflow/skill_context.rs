@@ -79,6 +79,14 @@ impl CallbackContext for UserContentOverrideContext {
     fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
         self.parent.artifacts()
     }
+
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        self.parent.memory()
+    }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.parent.blackboard()
+    }
 }
 
 #[async_trait]
@@ -87,10 +95,6 @@ impl InvocationContext for UserContentOverrideContext {
         self.parent.agent()
     }
 
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        self.parent.memory()
-    }
-
     fn session(&self) -> &dyn Session {
         self.parent.session()
     }
@@ -106,6 +110,14 @@ impl InvocationContext for UserContentOverrideContext {
     fn ended(&self) -> bool {
         self.parent.ended()
     }
+
+    fn is_cancelled(&self) -> bool {
+        self.parent.is_cancelled()
+    }
+
+    fn conversation_buffer(&self) -> Option<Arc<adk_core::ConversationBuffer>> {
+        self.parent.conversation_buffer()
+    }
 }
 
 #[allow(dead_code)]
@@ -36,6 +36,7 @@ impl adk_core::Llm for MockLlm {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             });
         };
         Ok(Box::pin(s))
@@ -0,0 +1,81 @@
+//! `Agent::info` introspection of assembled agents.
+
+use adk_agent::{LlmAgentBuilder, SequentialAgent};
+use adk_core::{Agent, ToolContext};
+use adk_model::MockLlm;
+use adk_tool::FunctionTool;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+#[allow(dead_code)]
+struct RefundArgs {
+    order_id: String,
+    amount_cents: u64,
+}
+
+fn tool(name: &str, description: &str) -> FunctionTool {
+    FunctionTool::new(name, description, |_ctx: Arc<dyn ToolContext>, _args: Value| async move {
+        Ok(json!({}))
+    })
+}
+
+fn leaf(name: &str) -> Arc<dyn Agent> {
+    Arc::new(
+        LlmAgentBuilder::new(name).model(Arc::new(MockLlm::new("noop-model"))).build().unwrap(),
+    )
+}
+
+#[test]
+fn test_llm_agent_reports_tools_and_sub_agents() {
+    let agent = LlmAgentBuilder::new("support")
+        .description("Handles support requests")
+        .model(Arc::new(MockLlm::new("noop-model")))
+        .tool(Arc::new(tool("lookup_order", "Looks up an order")))
+        .tool(Arc::new(
+            tool("refund", "Refunds an order")
+                .with_parameters_schema::<RefundArgs>()
+                .with_scopes(&["billing:write"]),
+        ))
+        .sub_agent(leaf("billing"))
+        .sub_agent(leaf("shipping"))
+        .output_schema(json!({ "type": "object" }))
+        .build()
+        .unwrap();
+
+    let info = agent.info();
+
+    assert_eq!(info.name, "support");
+    assert_eq!(info.description, "Handles support requests");
+    assert_eq!(info.model.as_deref(), Some("noop-model"));
+    assert_eq!(
+        info.tools.iter().map(|tool| tool.name.as_str()).collect::<Vec<_>>(),
+        ["lookup_order", "refund"]
+    );
+    let refund = info.tool("refund").unwrap();
+    assert_eq!(refund.description, "Refunds an order");
+    assert_eq!(refund.required_scopes, ["billing:write"]);
+    let properties = &refund.parameters_schema.as_ref().unwrap()["properties"];
+    assert!(properties.get("order_id").is_some());
+    assert!(properties.get("amount_cents").is_some());
+    assert_eq!(info.tool("lookup_order").unwrap().parameters_schema, None);
+
+    assert_eq!(info.sub_agents, ["billing", "shipping"]);
+    assert!(info.has_capability("output_schema"));
+    assert!(info.has_capability("transfer"));
+    assert!(!info.has_capability("toolsets"));
+}
+
+#[test]
+fn test_workflow_agent_reports_children() {
+    let pipeline = SequentialAgent::new("pipeline", vec![leaf("draft"), leaf("review")]);
+
+    let info = pipeline.info();
+
+    assert_eq!(info.sub_agents, ["draft", "review"]);
+    assert_eq!(info.model, None);
+    assert!(info.tools.is_empty());
+    assert!(info.has_capability("sequential"));
+}
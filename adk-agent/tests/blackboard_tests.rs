@@ -0,0 +1,109 @@
+//! Run-scoped blackboard shared between tools through the runner.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, SessionId, ToolContext, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+#[derive(Clone)]
+struct Plan {
+    steps: Vec<String>,
+}
+
+#[tokio::test]
+async fn test_tools_share_blackboard_within_run_only() {
+    let model = scripted_model([
+        call("make_plan", json!({})),
+        call("read_plan", json!({})),
+        text("planned"),
+        call("read_plan", json!({})),
+        text("nothing planned"),
+    ]);
+
+    let make_plan = FunctionTool::new(
+        "make_plan",
+        "Drafts a plan",
+        |ctx: Arc<dyn ToolContext>, _args: Value| async move {
+            let board = ctx.blackboard().expect("runner provides a blackboard");
+            board.set("plan", Plan { steps: vec!["fetch".into(), "summarize".into()] });
+            Ok(json!({ "status": "drafted" }))
+        },
+    );
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let seen = observed.clone();
+    let read_plan = FunctionTool::new(
+        "read_plan",
+        "Reads the current plan",
+        move |ctx: Arc<dyn ToolContext>, _args: Value| {
+            let seen = seen.clone();
+            async move {
+                let plan = ctx.blackboard().and_then(|board| board.get::<Plan>("plan"));
+                seen.lock().unwrap().push(plan.map(|plan| plan.steps));
+                Ok(json!({}))
+            }
+        },
+    );
+
+    let agent = LlmAgentBuilder::new("planner")
+        .model(model)
+        .tool(Arc::new(make_plan))
+        .tool(Arc::new(read_plan))
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "blackboard-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("blackboard-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+
+    for prompt in ["plan it", "what is the plan?"] {
+        let mut stream = runner
+            .run(
+                UserId::new("user-1").unwrap(),
+                SessionId::new("session-1").unwrap(),
+                Content::new("user").with_text(prompt),
+            )
+            .await
+            .unwrap();
+        while let Some(event) = stream.next().await {
+            event.unwrap();
+        }
+    }
+
+    assert_eq!(
+        *observed.lock().unwrap(),
+        [Some(vec!["fetch".to_string(), "summarize".to_string()]), None],
+        "the plan is visible later in the same run but not in the next one"
+    );
+
+    let session = sessions
+        .get(adk_session::GetRequest {
+            app_name: "blackboard-app".into(),
+            user_id: "user-1".into(),
+            session_id: "session-1".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    assert!(session.state().get("plan").is_none(), "blackboard values never reach state");
+}
@@ -0,0 +1,152 @@
+//! Cancelling an in-flight invocation through `Runner::run_cancellable`.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, EventStream, LlmResponse, Part, RunConfig, SessionId, ToolContext, UserId,
+};
+use adk_model::MockLlm;
+use adk_runner::{CancellationToken, Runner};
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model};
+
+async fn runner(agent: impl Agent + 'static) -> (Runner, Arc<dyn SessionService>) {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "cancel-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("cancel-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    (runner, sessions)
+}
+
+async fn start(runner: &Runner) -> (EventStream, CancellationToken) {
+    runner
+        .run_cancellable(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("go"),
+            RunConfig::default(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Drains the stream, which must end with a single cancellation error.
+async fn expect_cancelled(mut stream: EventStream) {
+    let next = tokio::time::timeout(Duration::from_secs(2), stream.next()).await;
+    let error = next.expect("stream hung after cancel").unwrap().unwrap_err();
+    assert!(error.is_cancelled(), "{error}");
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_cancel_before_first_model_call() {
+    let model = scripted_model([]);
+    let agent = LlmAgentBuilder::new("assistant").model(model.clone()).build().unwrap();
+    let (runner, _) = runner(agent).await;
+
+    let (stream, cancel) = start(&runner).await;
+    cancel.cancel();
+
+    expect_cancelled(stream).await;
+    assert!(model.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_cancel_mid_model_stream() {
+    // The model is still streaming when the first chunk arrives.
+    let chunk = |text: &str| {
+        let mut chunk = LlmResponse::new(Content::new("model").with_text(text));
+        chunk.partial = true;
+        chunk.turn_complete = false;
+        chunk.finish_reason = None;
+        chunk
+    };
+    let model = Arc::new(
+        MockLlm::new("streaming-model")
+            .with_response(chunk("Once upon"))
+            .with_response(chunk(" a time"))
+            .with_response(LlmResponse::new(Content::new("model").with_text(" there was"))),
+    );
+    let agent = LlmAgentBuilder::new("assistant").model(model.clone()).build().unwrap();
+    let (runner, _) = runner(agent).await;
+
+    let (mut stream, cancel) = start(&runner).await;
+    let first = stream.next().await.unwrap().unwrap();
+    assert!(first.llm_response.partial);
+    cancel.cancel();
+
+    expect_cancelled(stream).await;
+    assert_eq!(model.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_mid_tool_discards_its_state_changes() {
+    let started = Arc::new(Notify::new());
+    let tool_started = started.clone();
+    let tool = FunctionTool::new(
+        "reindex",
+        "Rebuild the search index",
+        move |ctx: Arc<dyn ToolContext>, _args| {
+            let started = tool_started.clone();
+            async move {
+                let mut actions = ctx.actions();
+                actions.state_delta.insert("index_status".to_string(), json!("rebuilding"));
+                ctx.set_actions(actions);
+                started.notify_one();
+                while !ctx.is_cancelled() {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                Ok(json!({ "status": "partial" }))
+            }
+        },
+    );
+    let model = scripted_model([call("reindex", json!({}))]);
+    let agent =
+        LlmAgentBuilder::new("assistant").model(model).tool(Arc::new(tool)).build().unwrap();
+    let (runner, sessions) = runner(agent).await;
+
+    let (mut stream, cancel) = start(&runner).await;
+    // The tool only runs while the stream is polled, so cancel from elsewhere.
+    tokio::spawn(async move {
+        started.notified().await;
+        cancel.cancel();
+    });
+    let call = stream.next().await.unwrap().unwrap();
+    assert!(matches!(call.llm_response.content.unwrap().parts[0], Part::FunctionCall { .. }));
+
+    expect_cancelled(stream).await;
+    let session = sessions
+        .get(GetRequest {
+            app_name: "cancel-app".into(),
+            user_id: "user-1".into(),
+            session_id: "session-1".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    assert!(session.state().get("index_status").is_none());
+    // The model's call was recorded before the cancel; no tool response was.
+    let events = session.events();
+    assert_eq!(events.len(), 2);
+}
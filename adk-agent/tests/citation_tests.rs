@@ -0,0 +1,102 @@
+//! Citations carried onto the final event of an agent's turn.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Citation, Content, Event, FinishReason, LlmResponse, Part, SessionId, TextSpan, ToolContext,
+    UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+async fn run(agent: LlmAgentBuilder) -> Vec<Event> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "docs-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("docs-app")
+        .agent(Arc::new(agent.build().unwrap()))
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Is Rust memory safe?"),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_final_event_carries_model_and_tool_citations() {
+    let mut call = LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: "search_docs".to_string(),
+            args: json!({ "query": "memory safety" }),
+            id: None,
+            thought_signature: None,
+        }],
+    });
+    call.finish_reason = Some(FinishReason::Stop);
+    let mut answer = text("Rust is memory safe.");
+    let grounded = Citation::new("https://rust-lang.org/")
+        .with_title("Rust")
+        .with_text_span(TextSpan { start: 0, end: 4, text: Some("Rust".to_string()) });
+    answer.citations = vec![grounded.clone()];
+
+    let tool = FunctionTool::new(
+        "search_docs",
+        "Search the docs",
+        |ctx: Arc<dyn ToolContext>, _args| async move {
+            let mut actions = ctx.actions();
+            actions
+                .citations
+                .push(Citation::new("https://doc.rust-lang.org/book/").with_title("The Book"));
+            ctx.set_actions(actions);
+            Ok(json!({ "passages": ["Rust guarantees memory safety."] }))
+        },
+    );
+    let agent =
+        LlmAgentBuilder::new("docs").model(scripted_model([call, answer])).tool(Arc::new(tool));
+
+    let events = run(agent).await;
+
+    let last = events.last().unwrap();
+    assert_eq!(
+        last.llm_response.content.as_ref().and_then(|content| content.parts[0].text()),
+        Some("Rust is memory safe.")
+    );
+    assert_eq!(
+        last.llm_response.citations,
+        vec![Citation::new("https://doc.rust-lang.org/book/").with_title("The Book"), grounded]
+    );
+}
+
+#[tokio::test]
+async fn test_ungrounded_answer_has_no_citations() {
+    let agent = LlmAgentBuilder::new("docs").model(scripted_model([text("Yes.")]));
+
+    let events = run(agent).await;
+
+    assert!(events.last().unwrap().llm_response.citations.is_empty());
+}
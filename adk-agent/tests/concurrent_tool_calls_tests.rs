@@ -0,0 +1,183 @@
+//! Function calls from one model response run concurrently by default, with
+//! results and state changes applied in call order.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, FinishReason, LlmResponse, Part, SessionId, ToolContext, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+/// Calls `lookup` once per delay in one response, then answers.
+fn lookup_model(delays_ms: &[u64]) -> Arc<MockLlm> {
+    let parts = delays_ms
+        .iter()
+        .enumerate()
+        .map(|(i, delay)| Part::FunctionCall {
+            name: "lookup".to_string(),
+            args: json!({ "slot": i, "delay_ms": delay }),
+            id: Some(format!("call-{i}")),
+            thought_signature: None,
+        })
+        .collect();
+    let mut calls = LlmResponse::new(Content { role: "model".to_string(), parts });
+    calls.finish_reason = Some(FinishReason::Stop);
+    scripted_model([calls, text("Done.")])
+}
+
+/// Counts calls in flight and keeps the highest count seen.
+#[derive(Default)]
+struct InFlight {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+/// Sleeps for the call's `delay_ms`, then writes its slot to `state:winner`.
+fn lookup_tool(in_flight: Arc<InFlight>) -> Arc<FunctionTool> {
+    Arc::new(FunctionTool::new(
+        "lookup",
+        "Looks something up",
+        move |ctx: Arc<dyn ToolContext>, args: Value| {
+            let in_flight = in_flight.clone();
+            async move {
+                let now = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+                in_flight.peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(args["delay_ms"].as_u64().unwrap())).await;
+                in_flight.current.fetch_sub(1, Ordering::SeqCst);
+                ctx.set_state_as("winner", &args["slot"])?;
+                Ok(json!({ "slot": args["slot"] }))
+            }
+        },
+    ))
+}
+
+async fn run(agent: adk_agent::LlmAgent) -> Arc<dyn SessionService> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "lookup-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("lookup-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    let mut events = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Look everything up"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+    sessions
+}
+
+/// The call id and slot of every function response in `content`.
+fn linked_results(content: &Content) -> Vec<(Option<String>, Value)> {
+    content
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::FunctionResponse { function_response, id } => {
+                Some((id.clone(), function_response.response["slot"].clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_calls_run_concurrently_and_results_keep_call_order() {
+    // Finishing in reverse call order.
+    let model = lookup_model(&[90, 60, 30, 0]);
+    let in_flight = Arc::new(InFlight::default());
+    let agent = LlmAgentBuilder::new("finder")
+        .model(model.clone())
+        .tool(lookup_tool(in_flight.clone()))
+        .build()
+        .unwrap();
+
+    let sessions = run(agent).await;
+
+    assert_eq!(in_flight.peak.load(Ordering::SeqCst), 4, "all calls were in flight at once");
+
+    let batch = model.requests()[1].contents.last().cloned().unwrap();
+    assert_eq!(
+        linked_results(&batch),
+        [
+            (Some("call-0".to_string()), json!(0)),
+            (Some("call-1".to_string()), json!(1)),
+            (Some("call-2".to_string()), json!(2)),
+            (Some("call-3".to_string()), json!(3)),
+        ]
+    );
+
+    // The last call in the response wrote `winner` last, though it finished first.
+    let session = sessions
+        .get(GetRequest {
+            app_name: "lookup-app".into(),
+            user_id: "user-1".into(),
+            session_id: "session-1".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(session.state().get("winner"), Some(json!(3)));
+}
+
+#[tokio::test]
+async fn test_tool_concurrency_one_runs_calls_sequentially() {
+    let model = lookup_model(&[20, 10, 0]);
+    let in_flight = Arc::new(InFlight::default());
+    let agent = LlmAgentBuilder::new("finder")
+        .model(model.clone())
+        .tool(lookup_tool(in_flight.clone()))
+        .tool_concurrency(1)
+        .build()
+        .unwrap();
+
+    run(agent).await;
+
+    assert_eq!(in_flight.peak.load(Ordering::SeqCst), 1);
+    let requests = model.requests();
+    let ids: Vec<_> = linked_results(requests[1].contents.last().unwrap())
+        .into_iter()
+        .map(|(id, _)| id.unwrap())
+        .collect();
+    assert_eq!(ids, ["call-0", "call-1", "call-2"]);
+}
+
+#[tokio::test]
+async fn test_tool_concurrency_bounds_calls_in_flight() {
+    let model = lookup_model(&[20, 20, 20, 20, 20]);
+    let in_flight = Arc::new(InFlight::default());
+    let agent = LlmAgentBuilder::new("finder")
+        .model(model)
+        .tool(lookup_tool(in_flight.clone()))
+        .tool_concurrency(2)
+        .build()
+        .unwrap();
+
+    run(agent).await;
+
+    assert_eq!(in_flight.peak.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,143 @@
+//! `ConditionalAgent` else-if chains with async conditions.
+
+use adk_agent::{ConditionalAgent, CustomAgentBuilder};
+use adk_core::{
+    AdkError, Agent, BranchChoice, Content, Event, EventStream, ReadonlyContext, SessionId, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn route(name: &'static str) -> Arc<dyn Agent> {
+    let agent = CustomAgentBuilder::new(name)
+        .handler(move |ctx| async move {
+            let mut event = Event::new(ctx.invocation_id());
+            event.author = name.to_string();
+            event.llm_response.content = Some(Content::new("model").with_text(name));
+            Ok(Box::pin(futures::stream::iter(vec![Ok(event)])) as EventStream)
+        })
+        .build()
+        .unwrap();
+    Arc::new(agent)
+}
+
+/// Stands in for a quick classifier call.
+async fn classify(ctx: Arc<dyn ReadonlyContext>) -> adk_core::Result<String> {
+    tokio::task::yield_now().await;
+    let text = ctx.user_content().parts.iter().filter_map(|part| part.text()).collect::<String>();
+    if text.contains("???") {
+        return Err(AdkError::agent("classifier unavailable"));
+    }
+    Ok(["billing", "outage", "refund"]
+        .into_iter()
+        .find(|label| text.contains(label))
+        .unwrap_or("other")
+        .to_string())
+}
+
+fn router() -> ConditionalAgent {
+    ConditionalAgent::builder("support_router")
+        .when(|ctx| async move { Ok(classify(ctx).await? == "billing") }, route("billing_agent"))
+        .when(|ctx| async move { Ok(classify(ctx).await? == "outage") }, route("outage_agent"))
+        .when(|ctx| async move { Ok(classify(ctx).await? == "refund") }, route("refund_agent"))
+        .otherwise(route("general_agent"))
+        .branch_key("route")
+        .build()
+        .unwrap()
+}
+
+async fn run(
+    agent: ConditionalAgent,
+    message: &str,
+) -> (Vec<adk_core::Result<Event>>, HashMap<String, Value>) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    let results = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text(message),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let session = sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (results, session.state().all())
+}
+
+fn authors(results: &[adk_core::Result<Event>]) -> Vec<String> {
+    results.iter().filter_map(|result| result.as_ref().ok()).map(|e| e.author.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_first_matching_arm_runs() {
+    let (results, state) = run(router(), "I need a refund for my order").await;
+
+    let events: Vec<Event> = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(
+        events[0].branch_choice(),
+        Some(BranchChoice { arm: Some(3), agent: Some("refund_agent".to_string()) })
+    );
+    assert_eq!(events[0].author, "support_router");
+    assert_eq!(events[1].author, "refund_agent");
+    assert_eq!(events.len(), 2);
+    assert_eq!(state.get("route"), Some(&json!("refund_agent")));
+}
+
+#[tokio::test]
+async fn test_otherwise_runs_when_no_arm_matches() {
+    let (results, state) = run(router(), "What are your opening hours?").await;
+
+    let events: Vec<Event> = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(
+        events[0].branch_choice(),
+        Some(BranchChoice { arm: None, agent: Some("general_agent".to_string()) })
+    );
+    assert_eq!(events[1].author, "general_agent");
+    assert_eq!(state.get("route"), Some(&json!("general_agent")));
+}
+
+#[tokio::test]
+async fn test_condition_error_names_the_arm() {
+    let (results, state) = run(router(), "???").await;
+
+    let error = results.iter().find_map(|result| result.as_ref().err()).expect("condition error");
+    assert!(error.message.contains("Condition 1 of conditional agent 'support_router'"));
+    assert!(error.message.contains("'billing_agent'"));
+    assert!(error.message.contains("classifier unavailable"));
+    assert!(authors(&results).is_empty(), "no branch should run");
+    assert!(!state.contains_key("route"));
+}
+
+#[tokio::test]
+async fn test_builder_requires_an_arm() {
+    let result = ConditionalAgent::builder("empty").otherwise(route("general_agent")).build();
+    assert!(result.is_err());
+}
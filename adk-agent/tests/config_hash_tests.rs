@@ -0,0 +1,78 @@
+//! Stable `Agent::config_hash` identities for agent configurations.
+
+use adk_agent::{LlmAgentBuilder, SequentialAgent};
+use adk_core::{Agent, GenerateContentConfig, ToolContext};
+use adk_model::MockLlm;
+use adk_tool::FunctionTool;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+fn tool(name: &str) -> Arc<FunctionTool> {
+    Arc::new(FunctionTool::new(
+        name,
+        "Looks up an order",
+        |_ctx: Arc<dyn ToolContext>, _args: Value| async move { Ok(json!({})) },
+    ))
+}
+
+fn support_agent(instruction: &str, model: &'static str) -> LlmAgentBuilder {
+    LlmAgentBuilder::new("support")
+        .description("Handles support requests")
+        .model(Arc::new(MockLlm::new(model)))
+        .instruction(instruction)
+        .tool(tool("lookup_order"))
+        .tool(tool("refund"))
+        .generate_content_config(GenerateContentConfig {
+            temperature: Some(0.2),
+            ..Default::default()
+        })
+}
+
+#[test]
+fn test_identical_configurations_hash_equal_and_changes_do_not() {
+    let first = support_agent("Be concise.", "gemini-2.5-flash").build().unwrap();
+    let second = support_agent("Be concise.", "gemini-2.5-flash").build().unwrap();
+    assert_eq!(first.config_hash(), second.config_hash());
+    assert_eq!(first.config_hash().len(), 64);
+
+    // Tool registration order does not matter; tools are sent as a set.
+    let reordered = LlmAgentBuilder::new("support")
+        .description("Handles support requests")
+        .model(Arc::new(MockLlm::new("gemini-2.5-flash")))
+        .instruction("Be concise.")
+        .tool(tool("refund"))
+        .tool(tool("lookup_order"))
+        .generate_content_config(GenerateContentConfig {
+            temperature: Some(0.2),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    assert_eq!(reordered.config_hash(), first.config_hash());
+
+    let changed = [
+        support_agent("Be thorough.", "gemini-2.5-flash").build().unwrap(),
+        support_agent("Be concise.", "gemini-2.5-pro").build().unwrap(),
+        support_agent("Be concise.", "gemini-2.5-flash").version("2").build().unwrap(),
+        support_agent("Be concise.", "gemini-2.5-flash").tool(tool("cancel")).build().unwrap(),
+        support_agent("Be concise.", "gemini-2.5-flash")
+            .generate_content_config(GenerateContentConfig::default())
+            .build()
+            .unwrap(),
+    ];
+    for agent in &changed {
+        assert_ne!(agent.config_hash(), first.config_hash());
+    }
+}
+
+#[test]
+fn test_workflow_hash_follows_its_sub_agents() {
+    let workflow = |instruction: &str| {
+        let agent = support_agent(instruction, "gemini-2.5-flash").build().unwrap();
+        SequentialAgent::new("pipeline", vec![Arc::new(agent) as Arc<dyn Agent>])
+    };
+
+    assert_eq!(workflow("Be concise.").config_hash(), workflow("Be concise.").config_hash());
+    assert_ne!(workflow("Be concise.").config_hash(), workflow("Be thorough.").config_hash());
+    assert_eq!(workflow("Be concise.").version(), None);
+}
@@ -40,6 +40,7 @@ impl MockModel {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             },
         }
     }
@@ -182,9 +183,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -1,11 +1,12 @@
 use adk_agent::CustomAgent;
 use adk_core::{
-    Agent, CallbackContext, Content, Event, InvocationContext, Memory, Part, ReadonlyContext,
+    Agent, Artifacts, CallbackContext, Content, Event, InvocationContext, Part, ReadonlyContext,
     RunConfig, Session,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
-use std::sync::Arc;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 struct MockSession;
 
@@ -32,6 +33,7 @@ struct MockContext {
     content: Content,
     session: MockSession,
     user_content: Content,
+    artifacts: Option<Arc<dyn Artifacts>>,
 }
 
 impl MockContext {
@@ -46,8 +48,37 @@ impl MockContext {
                 role: "user".to_string(),
                 parts: vec![Part::Text { text: "test".to_string() }],
             },
+            artifacts: None,
         }
     }
+
+    fn with_artifacts(mut self, artifacts: Arc<dyn Artifacts>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+}
+
+/// Records saved artifacts, numbering each name's versions from 1.
+#[derive(Default)]
+struct MockArtifacts {
+    saved: Mutex<Vec<(String, Part)>>,
+}
+
+#[async_trait]
+impl Artifacts for MockArtifacts {
+    async fn save(&self, name: &str, data: &Part) -> adk_core::Result<i64> {
+        let mut saved = self.saved.lock().unwrap();
+        saved.push((name.to_string(), data.clone()));
+        Ok(saved.iter().filter(|(saved_name, _)| saved_name == name).count() as i64)
+    }
+
+    async fn load(&self, _name: &str) -> adk_core::Result<Part> {
+        unimplemented!()
+    }
+
+    async fn list(&self) -> adk_core::Result<Vec<String>> {
+        unimplemented!()
+    }
 }
 
 #[async_trait]
@@ -78,7 +109,7 @@ impl ReadonlyContext for MockContext {
 #[async_trait]
 impl CallbackContext for MockContext {
     fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
-        None
+        self.artifacts.clone()
     }
 }
 
@@ -87,9 +118,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -213,3 +241,109 @@ fn test_custom_agent_duplicate_sub_agents() {
 
     assert!(result.is_err());
 }
+
+async fn run_events(agent: CustomAgent, ctx: MockContext) -> Vec<adk_core::Result<Event>> {
+    let ctx = Arc::new(ctx) as Arc<dyn InvocationContext>;
+    agent.run(ctx).await.unwrap().collect().await
+}
+
+fn text(event: &Event) -> Option<&str> {
+    event.llm_response.content.as_ref()?.parts.first()?.text()
+}
+
+#[tokio::test]
+async fn test_run_emits_events_with_the_changes_made_before_them() {
+    let agent = CustomAgent::builder("reporter")
+        .run(|ctx| async move {
+            ctx.emit_text("Looking it up.");
+            ctx.set_state("step", &1)?;
+            ctx.emit_text("Found it.");
+            ctx.set_state("result", "ok")?;
+            ctx.finish();
+            Ok(())
+        })
+        .build()
+        .unwrap();
+
+    let events: Vec<Event> = run_events(agent, MockContext::new())
+        .await
+        .into_iter()
+        .map(|event| event.unwrap())
+        .collect();
+
+    assert_eq!(events.len(), 3);
+    assert_eq!(text(&events[0]), Some("Looking it up."));
+    assert!(events[0].actions.state_delta.is_empty());
+    assert_eq!(text(&events[1]), Some("Found it."));
+    assert_eq!(events[1].actions.state_delta["step"], json!(1));
+    assert!(events[2].llm_response.content.is_none());
+    assert_eq!(events[2].actions.state_delta["result"], json!("ok"));
+    assert!(events[2].llm_response.turn_complete);
+    for event in &events {
+        assert_eq!(event.author, "reporter");
+        assert_eq!(event.invocation_id, "test-inv");
+        assert_eq!(event.branch, "main");
+        assert!(event.is_final_response());
+    }
+}
+
+#[tokio::test]
+async fn test_run_without_finish_still_sends_pending_changes() {
+    let agent = CustomAgent::builder("counter")
+        .run(|ctx| async move {
+            ctx.set_state("count", &3)?;
+            Ok(())
+        })
+        .build()
+        .unwrap();
+
+    let events = run_events(agent, MockContext::new()).await;
+
+    assert_eq!(events.len(), 1);
+    let event = events[0].as_ref().unwrap();
+    assert_eq!(event.actions.state_delta["count"], json!(3));
+    assert!(event.llm_response.turn_complete);
+}
+
+#[tokio::test]
+async fn test_run_reports_saved_artifacts_with_the_next_event() {
+    let artifacts = Arc::new(MockArtifacts::default());
+    let agent = CustomAgent::builder("exporter")
+        .run(|ctx| async move {
+            ctx.save_artifact("report.csv", b"a,b\n1,2\n".to_vec(), "text/csv").await?;
+            let version =
+                ctx.save_artifact("report.csv", b"a,b\n3,4\n".to_vec(), "text/csv").await?;
+            ctx.emit_text(format!("Saved report.csv version {version}."));
+            Ok(())
+        })
+        .build()
+        .unwrap();
+
+    let events = run_events(agent, MockContext::new().with_artifacts(artifacts.clone())).await;
+
+    assert_eq!(events.len(), 1);
+    let event = events[0].as_ref().unwrap();
+    assert_eq!(text(event), Some("Saved report.csv version 2."));
+    assert_eq!(event.actions.artifact_delta["report.csv"], 2);
+    let saved = artifacts.saved.lock().unwrap();
+    assert!(matches!(&saved[1].1, Part::InlineData { mime_type, .. } if mime_type == "text/csv"));
+}
+
+#[tokio::test]
+async fn test_run_error_ends_the_stream_after_emitted_events() {
+    let agent = CustomAgent::builder("exporter")
+        .run(|ctx| async move {
+            ctx.emit_text("Exporting.");
+            ctx.save_artifact("report.csv", Vec::new(), "text/csv").await?;
+            ctx.emit_text("Exported.");
+            Ok(())
+        })
+        .build()
+        .unwrap();
+
+    let events = run_events(agent, MockContext::new()).await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(text(events[0].as_ref().unwrap()), Some("Exporting."));
+    assert!(events[1].as_ref().unwrap_err().to_string().contains("no artifact service"));
+}
@@ -0,0 +1,180 @@
+//! Cancelling delegated agents that exceed their timeout.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, DelegationTimeout, Event, Llm, LlmRequest, LlmResponseStream, Part, Result,
+    RunConfig, SessionId, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::AgentTool;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+/// Sets its flag when dropped.
+struct ReleaseGuard(Arc<AtomicBool>);
+
+impl Drop for ReleaseGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A model whose response stream never yields. The flag is set once the
+/// stream is dropped.
+struct HangingModel {
+    released: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl Llm for HangingModel {
+    fn name(&self) -> &str {
+        "hanging-model"
+    }
+
+    async fn generate_content(&self, _req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        let guard = ReleaseGuard(self.released.clone());
+        Ok(Box::pin(futures::stream::unfold(guard, |_guard| async move {
+            futures::future::pending::<()>().await;
+            None
+        })))
+    }
+}
+
+fn researcher(released: Arc<AtomicBool>) -> Arc<dyn Agent> {
+    Arc::new(
+        LlmAgentBuilder::new("researcher")
+            .description("Researches questions in depth")
+            .model(Arc::new(HangingModel { released }))
+            .build()
+            .unwrap(),
+    )
+}
+
+async fn run(agent: Arc<dyn Agent>, run_config: RunConfig) -> Vec<Event> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "delegation-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("delegation-app")
+        .agent(agent)
+        .session_service(sessions)
+        .run_config(run_config)
+        .build()
+        .unwrap();
+
+    let stream = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Summarize the latest rainfall data."),
+        )
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), stream.map(|event| event.unwrap()).collect())
+        .await
+        .expect("run finished")
+}
+
+fn texts(events: &[Event]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| event.llm_response.content.as_ref())
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            Part::Text { text } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_slow_transfer_target_is_cancelled_and_parent_answers() {
+    let released = Arc::new(AtomicBool::new(false));
+    let model = scripted_model([
+        call("transfer_to_agent", json!({ "agent_name": "researcher" })),
+        text("The researcher is unavailable, so here is a short summary instead."),
+    ]);
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(model.clone())
+        .sub_agent(researcher(released.clone()))
+        .build()
+        .unwrap();
+
+    let events = run(
+        Arc::new(coordinator),
+        RunConfig::builder().transfer_timeout(Duration::from_millis(50)).build(),
+    )
+    .await;
+
+    assert!(released.load(Ordering::SeqCst), "the researcher's model stream was released");
+    let timeout_event = events
+        .iter()
+        .find(|event| event.delegation_timeout().is_some())
+        .expect("a delegation timeout event");
+    assert_eq!(timeout_event.author, "coordinator");
+    assert_eq!(
+        timeout_event.delegation_timeout(),
+        Some(DelegationTimeout { agent: "researcher".to_string(), timeout_ms: 50 })
+    );
+
+    let last = events.last().unwrap();
+    assert_eq!(last.author, "coordinator");
+    assert_eq!(
+        texts(std::slice::from_ref(last)),
+        ["The researcher is unavailable, so here is a short summary instead."]
+    );
+
+    // The coordinator saw the timeout when it was asked to answer.
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+    let history = serde_json::to_string(&requests[1].contents).unwrap();
+    assert!(history.contains("Agent 'researcher' did not respond within 50 ms"));
+}
+
+#[tokio::test]
+async fn test_agent_tool_timeout_returns_structured_result() {
+    let released = Arc::new(AtomicBool::new(false));
+    let model = scripted_model([
+        call("researcher", json!({ "request": "Rainfall in Nairobi this week" })),
+        text("I couldn't reach the researcher; rainfall data is unavailable right now."),
+    ]);
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(model)
+        .tool(Arc::new(
+            AgentTool::new(researcher(released.clone())).timeout(Duration::from_millis(50)),
+        ))
+        .build()
+        .unwrap();
+
+    let events = run(Arc::new(coordinator), RunConfig::default()).await;
+
+    assert!(released.load(Ordering::SeqCst), "the researcher's model stream was released");
+    let response = events
+        .iter()
+        .flat_map(Event::tool_results)
+        .find(|result| result.name == "researcher")
+        .map(|result| result.response.clone())
+        .expect("a researcher tool result");
+    assert_eq!(response["error_type"], "timeout");
+    assert_eq!(response["agent"], "researcher");
+    assert_eq!(response["timeout_ms"], 50);
+    assert_eq!(
+        texts(std::slice::from_ref(events.last().unwrap())),
+        ["I couldn't reach the researcher; rainfall data is unavailable right now."]
+    );
+}
@@ -0,0 +1,156 @@
+//! Few-shot examples are sent as user/model turns after the instructions and
+//! before the live history, and never reach the session.
+
+use adk_agent::{Example, LlmAgent, LlmAgentBuilder};
+use adk_core::{Agent, Content, IncludeContents, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn text(role: &str, text: &str) -> Content {
+    Content::new(role).with_text(text)
+}
+
+fn classifier(model: Arc<MockLlm>, include_contents: IncludeContents) -> LlmAgent {
+    LlmAgentBuilder::new("classifier")
+        .model(model)
+        .instruction("Classify the sentiment.")
+        .example(text("user", "I love it!"), text("model", "positive"))
+        .examples([Example::new(text("user", "It broke after a day."), text("user", "negative"))])
+        .include_contents(include_contents)
+        .build()
+        .unwrap()
+}
+
+struct Harness {
+    runner: Runner,
+    sessions: Arc<InMemorySessionService>,
+}
+
+impl Harness {
+    async fn new(agent: LlmAgent) -> Self {
+        let sessions = Arc::new(InMemorySessionService::new());
+        sessions
+            .create(CreateRequest {
+                app_name: "app".into(),
+                user_id: "user".into(),
+                session_id: Some("session".into()),
+                state: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let runner = Runner::builder()
+            .app_name("app")
+            .agent(Arc::new(agent) as Arc<dyn Agent>)
+            .session_service(sessions.clone())
+            .build()
+            .unwrap();
+        Self { runner, sessions }
+    }
+
+    async fn turn(&self, message: &str) {
+        let mut stream = self
+            .runner
+            .run(
+                UserId::new("user").unwrap(),
+                SessionId::new("session").unwrap(),
+                text("user", message),
+            )
+            .await
+            .unwrap();
+        while let Some(event) = stream.next().await {
+            event.unwrap();
+        }
+    }
+}
+
+/// `(role, text)` of each content in a request.
+fn turns(model: &MockLlm, request: usize) -> Vec<(String, String)> {
+    model.requests()[request]
+        .contents
+        .iter()
+        .map(|content| {
+            let text: String = content.parts.iter().filter_map(Part::text).collect();
+            (content.role.clone(), text)
+        })
+        .collect()
+}
+
+fn expected(turns: &[(&str, &str)]) -> Vec<(String, String)> {
+    turns.iter().map(|(role, text)| (role.to_string(), text.to_string())).collect()
+}
+
+#[tokio::test]
+async fn test_examples_precede_the_live_history_in_order() {
+    let model = Arc::new(
+        MockLlm::new("classifier")
+            .with_turn(LlmResponse::new(text("model", "positive")))
+            .with_turn(LlmResponse::new(text("model", "negative"))),
+    );
+    let harness = Harness::new(classifier(model.clone(), IncludeContents::All)).await;
+
+    harness.turn("Best purchase this year.").await;
+    harness.turn("Never again.").await;
+
+    assert_eq!(
+        turns(&model, 1),
+        expected(&[
+            ("user", "Classify the sentiment."),
+            ("user", "I love it!"),
+            ("model", "positive"),
+            ("user", "It broke after a day."),
+            ("model", "negative"),
+            ("user", "Best purchase this year."),
+            ("model", "positive"),
+            ("user", "Never again."),
+        ])
+    );
+
+    let session = harness
+        .sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    let history: Vec<String> = session
+        .events()
+        .all()
+        .iter()
+        .filter_map(|event| event.llm_response.content.as_ref())
+        .flat_map(|content| content.parts.iter().filter_map(Part::text).map(str::to_string))
+        .collect();
+    assert_eq!(history, ["Best purchase this year.", "positive", "Never again.", "negative"]);
+}
+
+#[tokio::test]
+async fn test_history_limits_never_drop_examples() {
+    let model = Arc::new(
+        MockLlm::new("classifier")
+            .with_turn(LlmResponse::new(text("model", "positive")))
+            .with_turn(LlmResponse::new(text("model", "negative"))),
+    );
+    let harness = Harness::new(classifier(model.clone(), IncludeContents::LastN(1))).await;
+
+    harness.turn("Best purchase this year.").await;
+    harness.turn("Never again.").await;
+
+    assert_eq!(
+        turns(&model, 1),
+        expected(&[
+            ("user", "Classify the sentiment."),
+            ("user", "I love it!"),
+            ("model", "positive"),
+            ("user", "It broke after a day."),
+            ("model", "negative"),
+            ("user", "Never again."),
+        ])
+    );
+}
@@ -0,0 +1,118 @@
+//! Per-agent `generate_content_config` reaches every request the agent makes,
+//! so agents sharing one model can sample differently.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, GenerateContentConfig, Llm, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn call_lookup() -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: "lookup".to_string(),
+            args: json!({}),
+            id: Some("call-1".to_string()),
+            thought_signature: None,
+        }],
+    })
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+async fn run(agent: impl Agent + 'static, session_id: &str) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some(session_id.into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let mut events = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new(session_id).unwrap(),
+            Content::new("user").with_text("Go"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_config_reaches_initial_and_follow_up_calls() {
+    let model = Arc::new(
+        MockLlm::new("shared")
+            .with_turn(call_lookup())
+            .with_turn(reply("Found it."))
+            .with_turn(reply("Once upon a time...")),
+    );
+    let extractor_config = GenerateContentConfig {
+        temperature: Some(0.0),
+        top_k: Some(1),
+        max_output_tokens: Some(256),
+        stop_sequences: vec!["END".to_string()],
+        ..Default::default()
+    };
+    let writer_config =
+        GenerateContentConfig { temperature: Some(1.2), top_p: Some(0.95), ..Default::default() };
+
+    let lookup = FunctionTool::new("lookup", "Look something up", |_ctx, _args: Value| async {
+        Ok(json!({ "found": true }))
+    });
+    let extractor = LlmAgentBuilder::new("extractor")
+        .model(model.clone() as Arc<dyn Llm>)
+        .tool(Arc::new(lookup))
+        .generate_content_config(extractor_config.clone())
+        .build()
+        .unwrap();
+    let writer = LlmAgentBuilder::new("writer")
+        .model(model.clone() as Arc<dyn Llm>)
+        .generate_content_config(writer_config.clone())
+        .build()
+        .unwrap();
+
+    run(extractor, "extract").await;
+    run(writer, "write").await;
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 3);
+    for request in &requests[..2] {
+        let config = request.config.as_ref().expect("extractor config attached");
+        assert_eq!(config.temperature, extractor_config.temperature);
+        assert_eq!(config.top_k, extractor_config.top_k);
+        assert_eq!(config.max_output_tokens, extractor_config.max_output_tokens);
+        assert_eq!(config.stop_sequences, extractor_config.stop_sequences);
+    }
+    assert!(
+        requests[1].contents.iter().any(|content| content
+            .parts
+            .iter()
+            .any(|part| matches!(part, Part::FunctionResponse { .. }))),
+        "second request follows the tool call"
+    );
+
+    let config = requests[2].config.as_ref().expect("writer config attached");
+    assert_eq!(config.temperature, writer_config.temperature);
+    assert_eq!(config.top_p, writer_config.top_p);
+    assert_eq!(config.top_k, None);
+}
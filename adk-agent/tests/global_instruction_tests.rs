@@ -0,0 +1,143 @@
+//! The root agent's `global_instruction` applies to every LLM agent in the
+//! tree when run through the `Runner`; sub-agents' own global instructions
+//! are ignored.
+
+use adk_agent::{LlmAgent, LlmAgentBuilder};
+use adk_core::{Agent, Content, LlmRequest, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn transfer(agent_name: &str) -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: "transfer_to_agent".to_string(),
+            args: json!({ "agent_name": agent_name }),
+            id: Some(format!("to-{agent_name}")),
+            thought_signature: None,
+        }],
+    })
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+async fn run(root: impl Agent + 'static) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::from([("customer".to_string(), json!("Ada"))]),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(root) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let mut stream = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Why was I charged twice?"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+    }
+}
+
+/// The leading instruction texts of a request, before the user's message.
+fn instructions(request: &LlmRequest) -> Vec<&str> {
+    request
+        .contents
+        .iter()
+        .take_while(|content| {
+            !content.parts.iter().filter_map(Part::text).any(|text| text.contains("charged twice"))
+        })
+        .flat_map(|content| content.parts.iter().filter_map(Part::text))
+        .collect()
+}
+
+/// `front_desk` → `billing` → `refunds`, each reached by transfer.
+fn tree(
+    front_desk: LlmAgentBuilder,
+    billing_model: Arc<MockLlm>,
+    refunds_model: Arc<MockLlm>,
+) -> LlmAgent {
+    let refunds = LlmAgentBuilder::new("refunds")
+        .instruction("You issue refunds.")
+        .model(refunds_model)
+        .build()
+        .unwrap();
+    let billing = LlmAgentBuilder::new("billing")
+        .instruction("You handle billing.")
+        .global_instruction("NESTED GLOBAL")
+        .model(billing_model)
+        .sub_agent(Arc::new(refunds))
+        .build()
+        .unwrap();
+    front_desk
+        .model(Arc::new(MockLlm::new("front_desk").with_turn(transfer("billing"))))
+        .sub_agent(Arc::new(billing))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_root_global_instruction_reaches_sub_agent_two_levels_deep() {
+    let billing_model = Arc::new(MockLlm::new("billing").with_turn(transfer("refunds")));
+    let refunds_model = Arc::new(MockLlm::new("refunds").with_turn(reply("Refund issued.")));
+    let root = tree(
+        LlmAgentBuilder::new("front_desk")
+            .global_instruction("Never reveal internal IDs. You are helping {customer}."),
+        billing_model.clone(),
+        refunds_model.clone(),
+    );
+
+    run(root).await;
+
+    let global = "Never reveal internal IDs. You are helping Ada.";
+    assert_eq!(instructions(&refunds_model.requests()[0]), [global, "You issue refunds."]);
+    assert_eq!(instructions(&billing_model.requests()[0]), [global, "You handle billing."]);
+}
+
+#[tokio::test]
+async fn test_sub_agent_global_instruction_is_ignored_without_a_root_one() {
+    let billing_model = Arc::new(MockLlm::new("billing").with_turn(transfer("refunds")));
+    let refunds_model = Arc::new(MockLlm::new("refunds").with_turn(reply("Refund issued.")));
+    let root = tree(LlmAgentBuilder::new("front_desk"), billing_model.clone(), refunds_model);
+
+    run(root).await;
+
+    assert_eq!(instructions(&billing_model.requests()[0]), ["You handle billing."]);
+}
+
+#[tokio::test]
+async fn test_root_global_instruction_provider_runs_for_each_sub_agent() {
+    let billing_model = Arc::new(MockLlm::new("billing").with_turn(transfer("refunds")));
+    let refunds_model = Arc::new(MockLlm::new("refunds").with_turn(reply("Refund issued.")));
+    let root = tree(
+        LlmAgentBuilder::new("front_desk").global_instruction_fn(|ctx| async move {
+            Ok(format!("Acme voice for {}.", ctx.agent_name()))
+        }),
+        billing_model.clone(),
+        refunds_model.clone(),
+    );
+
+    run(root).await;
+
+    assert_eq!(instructions(&billing_model.requests()[0])[0], "Acme voice for billing.");
+    assert_eq!(instructions(&refunds_model.requests()[0])[0], "Acme voice for refunds.");
+}
@@ -0,0 +1,119 @@
+//! Structured handoffs from a coordinator to a sub-agent.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, Handoff, LlmRequest, Part, SessionId, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+fn request_text(request: &LlmRequest) -> String {
+    request
+        .contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            Part::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_handoff_constraints_reach_the_sub_agent_and_its_result_matches_the_schema() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "order_id": { "type": "string" },
+            "refunded": { "type": "number", "maximum": 200 }
+        },
+        "required": ["order_id", "refunded"]
+    });
+    let handoff = Handoff::new("Refund the customer's last order")
+        .with_context_keys(["order_id", "tier"])
+        .with_constraint("Never refund more than $200")
+        .with_return_schema(schema.clone());
+
+    let refunds_model = scripted_model([
+        // Misses a required field, so the agent asks again.
+        text(r#"{"refunded": 120}"#),
+        text(r#"{"order_id": "A-1042", "refunded": 120}"#),
+    ]);
+    let refunds = LlmAgentBuilder::new("refunds")
+        .description("Handles refunds")
+        .model(refunds_model.clone())
+        .build()
+        .unwrap();
+    let coordinator_model =
+        scripted_model([call("transfer_to_agent", json!({ "agent_name": "refunds" }))]);
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(coordinator_model)
+        .handoff_to(Arc::new(refunds), handoff.clone())
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "handoff-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: HashMap::from([("order_id".to_string(), json!("A-1042"))]),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("handoff-app")
+        .agent(Arc::new(coordinator) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    let mut stream = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("I want my money back"),
+        )
+        .await
+        .unwrap();
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event.unwrap());
+    }
+
+    // The transfer event carries the structured task.
+    let transfer_event =
+        events.iter().find(|event| event.actions.transfer_to_agent.is_some()).unwrap();
+    assert_eq!(transfer_event.actions.handoff.as_ref(), Some(&handoff));
+
+    // The task, its context, and its constraints reach the sub-agent.
+    let requests = refunds_model.requests();
+    let prompt = request_text(&requests[0]);
+    assert!(prompt.contains("Refund the customer's last order"), "{prompt}");
+    assert!(prompt.contains("- order_id: A-1042"), "{prompt}");
+    assert!(prompt.contains("- tier: (missing)"), "{prompt}");
+    assert!(prompt.contains("- Never refund more than $200"), "{prompt}");
+    assert!(prompt.contains("conforming to this schema"), "{prompt}");
+
+    // The non-conforming first answer was sent back for correction.
+    assert_eq!(requests.len(), 2);
+    assert!(request_text(&requests[1]).contains("did not match the required schema"));
+
+    let result = events
+        .iter()
+        .rev()
+        .filter(|event| event.author == "refunds")
+        .find_map(|event| event.llm_response.content.as_ref())
+        .map(|content| content.parts[0].text().unwrap().to_string())
+        .map(|text| serde_json::from_str::<Value>(&text).unwrap())
+        .unwrap();
+    assert_eq!(result, json!({ "order_id": "A-1042", "refunded": 120 }));
+    assert!(jsonschema::validator_for(&schema).unwrap().is_valid(&result));
+}
@@ -0,0 +1,202 @@
+//! `history_compression` summarizes old turns once a request exceeds the
+//! token threshold, and reuses the cached summary on later turns.
+
+use adk_agent::{CompressionConfig, LlmAgentBuilder};
+use adk_core::{
+    Agent, Content, LlmRequest, LlmResponse, Part, SessionId, UserId, estimate_request_tokens,
+    estimate_text_tokens,
+};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn message(turn: usize) -> String {
+    format!("turn {turn}: {}", "lorem ipsum ".repeat(40))
+}
+
+fn request_text(request: &LlmRequest) -> String {
+    request
+        .contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(Part::text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct Harness {
+    runner: Runner,
+    sessions: Arc<InMemorySessionService>,
+}
+
+impl Harness {
+    async fn new(agent: Arc<dyn Agent>) -> Self {
+        let sessions = Arc::new(InMemorySessionService::new());
+        sessions
+            .create(CreateRequest {
+                app_name: "app".into(),
+                user_id: "user".into(),
+                session_id: Some("session".into()),
+                state: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let runner = Runner::builder()
+            .app_name("app")
+            .agent(agent)
+            .session_service(sessions.clone())
+            .build()
+            .unwrap();
+        Self { runner, sessions }
+    }
+
+    async fn turn(&self, text: &str) {
+        let mut stream = self
+            .runner
+            .run(
+                UserId::new("user").unwrap(),
+                SessionId::new("session").unwrap(),
+                Content::new("user").with_text(text),
+            )
+            .await
+            .unwrap();
+        while let Some(event) = stream.next().await {
+            event.unwrap();
+        }
+    }
+
+    async fn state(&self) -> HashMap<String, Value> {
+        let session = self
+            .sessions
+            .get(GetRequest {
+                app_name: "app".into(),
+                user_id: "user".into(),
+                session_id: "session".into(),
+                num_recent_events: None,
+                after: None,
+            })
+            .await
+            .unwrap();
+        session.state().all()
+    }
+}
+
+#[tokio::test]
+async fn test_old_turns_are_summarized_and_the_summary_is_reused() {
+    let per_turn = estimate_text_tokens(&message(1));
+    // Two full turns fit, three do not.
+    let threshold = per_turn * 5 / 2;
+    let model = Arc::new(
+        MockLlm::new("agent-model")
+            .with_response(LlmResponse::new(Content::new("model").with_text("ok"))),
+    );
+    let summarizer = Arc::new(
+        MockLlm::new("cheap-model")
+            .with_response(LlmResponse::new(Content::new("model").with_text("EARLIER-SUMMARY"))),
+    );
+    let agent = LlmAgentBuilder::new("assistant")
+        .model(model.clone())
+        .history_compression(
+            CompressionConfig::new(threshold).keep_recent_turns(1).model(summarizer.clone()),
+        )
+        .build()
+        .unwrap();
+    let harness = Harness::new(Arc::new(agent)).await;
+
+    harness.turn(&message(1)).await;
+    harness.turn(&message(2)).await;
+    assert!(summarizer.requests().is_empty(), "no compression below the threshold");
+
+    harness.turn(&message(3)).await;
+    assert_eq!(summarizer.requests().len(), 1);
+    let summarized = request_text(&summarizer.requests()[0]);
+    assert!(summarized.contains("turn 1") && summarized.contains("turn 2"));
+    assert!(!summarized.contains("turn 3"), "the recent turn is never summarized");
+
+    let request = model.requests().pop().unwrap();
+    assert!(estimate_request_tokens(&request) <= threshold);
+    let text = request_text(&request);
+    assert!(text.contains("EARLIER-SUMMARY"));
+    assert!(text.contains("turn 3"));
+    assert!(!text.contains("turn 1"));
+    let cached = harness.state().await.get("adk_history_summary").cloned().unwrap();
+    assert_eq!(cached["summary"], json!("EARLIER-SUMMARY"));
+
+    // The cached summary keeps the next turn under the threshold.
+    harness.turn(&message(4)).await;
+    assert_eq!(summarizer.requests().len(), 1, "summary reused from state");
+    let request = model.requests().pop().unwrap();
+    assert!(estimate_request_tokens(&request) <= threshold);
+    let text = request_text(&request);
+    assert!(text.contains("EARLIER-SUMMARY"));
+    assert!(text.contains("turn 3") && text.contains("turn 4"));
+    assert!(!text.contains("turn 2"));
+
+    // Once it no longer does, the summary is extended with the next turns.
+    harness.turn(&message(5)).await;
+    assert_eq!(summarizer.requests().len(), 2);
+    let summarized = request_text(&summarizer.requests()[1]);
+    assert!(summarized.contains("EARLIER-SUMMARY"));
+    assert!(summarized.contains("turn 3") && summarized.contains("turn 4"));
+    assert!(!summarized.contains("turn 1"));
+    assert!(estimate_request_tokens(&model.requests().pop().unwrap()) <= threshold);
+}
+
+#[tokio::test]
+async fn test_current_turn_tool_calls_are_never_summarized() {
+    let per_turn = estimate_text_tokens(&message(1));
+    let model = Arc::new(
+        MockLlm::new("agent-model")
+            .with_turn(LlmResponse::new(Content::new("model").with_text("ok")))
+            .with_turn(LlmResponse::new(Content {
+                role: "model".to_string(),
+                parts: vec![Part::FunctionCall {
+                    name: "lookup".to_string(),
+                    args: json!({ "query": "status" }),
+                    id: Some("call-1".to_string()),
+                    thought_signature: None,
+                }],
+            }))
+            .with_response(LlmResponse::new(Content::new("model").with_text("done"))),
+    );
+    let summarizer = Arc::new(
+        MockLlm::new("cheap-model")
+            .with_response(LlmResponse::new(Content::new("model").with_text("EARLIER-SUMMARY"))),
+    );
+    let tool = FunctionTool::new("lookup", "Look something up", |_ctx, _args: Value| async {
+        Ok(json!({ "status": "green" }))
+    });
+    let agent = LlmAgentBuilder::new("assistant")
+        .model(model.clone())
+        .tool(Arc::new(tool))
+        .history_compression(
+            CompressionConfig::new(per_turn * 3 / 2).keep_recent_turns(1).model(summarizer),
+        )
+        .build()
+        .unwrap();
+    let harness = Harness::new(Arc::new(agent)).await;
+
+    harness.turn(&message(1)).await;
+    harness.turn(&message(2)).await;
+
+    let request = model.requests().pop().unwrap();
+    let parts: Vec<&Part> = request.contents.iter().flat_map(|content| &content.parts).collect();
+    assert!(
+        parts
+            .iter()
+            .any(|part| matches!(part, Part::FunctionCall { name, .. } if name == "lookup"))
+    );
+    assert!(parts.iter().any(|part| matches!(
+        part,
+        Part::FunctionResponse { function_response, .. } if function_response.name == "lookup"
+    )));
+    let text = request_text(&request);
+    assert!(text.contains("Conversation summary"));
+    assert!(text.contains("turn 2"));
+    assert!(!text.contains("turn 1"));
+}
@@ -0,0 +1,221 @@
+//! `IncludeContents` modes trim the history sent to the model without ever
+//! separating a function call from its response.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, Event, FunctionResponseData, IncludeContents, LlmResponse, Part, SessionId,
+    UserId,
+};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+fn event(author: &str, content: Content) -> Event {
+    let mut event = Event::new("earlier");
+    event.author = author.to_string();
+    event.llm_response.content = Some(content);
+    event
+}
+
+fn text(author: &str, role: &str, text: &str) -> Event {
+    event(author, Content::new(role).with_text(text))
+}
+
+fn call(author: &str, name: &str, id: &str) -> Event {
+    event(
+        author,
+        Content {
+            role: "model".to_string(),
+            parts: vec![Part::FunctionCall {
+                name: name.to_string(),
+                args: json!({}),
+                id: Some(id.to_string()),
+                thought_signature: None,
+            }],
+        },
+    )
+}
+
+fn response(author: &str, role: &str, name: &str, id: &str) -> Event {
+    event(
+        author,
+        Content {
+            role: role.to_string(),
+            parts: vec![Part::FunctionResponse {
+                function_response: FunctionResponseData::new(name, json!("ok")),
+                id: Some(id.to_string()),
+            }],
+        },
+    )
+}
+
+/// Three earlier turns with tool use, a client-side tool result sent by the
+/// user, and a sibling agent's call.
+fn earlier_events() -> Vec<Event> {
+    vec![
+        text("user", "user", "first"),
+        call("assistant", "weather", "c1"),
+        response("assistant", "function", "weather", "c1"),
+        text("assistant", "model", "sunny"),
+        text("user", "user", "second"),
+        call("assistant", "confirm", "c2"),
+        response("user", "user", "confirm", "c2"),
+        call("sibling", "lookup", "c3"),
+        response("sibling", "function", "lookup", "c3"),
+        text("sibling", "model", "sibling chatter"),
+        text("assistant", "model", "confirmed"),
+    ]
+}
+
+/// Runs the agent on `latest` after seeding `earlier_events` and returns the
+/// contents of the single model request.
+async fn request_contents(include: IncludeContents) -> Vec<Content> {
+    let model = Arc::new(
+        MockLlm::new("mock").with_response(LlmResponse::new(Content::new("model").with_text("ok"))),
+    );
+    let agent =
+        LlmAgentBuilder::new("assistant").model(model.clone()).include_contents(include).build();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    for event in earlier_events() {
+        sessions.append_event("session", event).await.unwrap();
+    }
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent.unwrap()) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    let mut events = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("third"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 1);
+    requests[0].contents.clone()
+}
+
+fn texts(contents: &[Content]) -> Vec<&str> {
+    contents.iter().flat_map(|c| c.parts.iter().filter_map(Part::text)).collect()
+}
+
+fn call_ids(contents: &[Content]) -> Vec<String> {
+    contents
+        .iter()
+        .flat_map(|c| &c.parts)
+        .filter_map(|part| match part {
+            Part::FunctionCall { id, .. } => id.clone(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every response follows its call and every call has its response.
+fn assert_no_orphans(contents: &[Content]) {
+    let mut open: HashSet<String> = HashSet::new();
+    for part in contents.iter().flat_map(|c| &c.parts) {
+        match part {
+            Part::FunctionCall { id, .. } => {
+                open.insert(id.clone().unwrap());
+            }
+            Part::FunctionResponse { id, .. } => {
+                let id = id.clone().unwrap();
+                assert!(open.remove(&id), "response {id} has no preceding call in {contents:?}");
+            }
+            _ => {}
+        }
+    }
+    assert!(open.is_empty(), "calls without responses: {open:?}");
+}
+
+#[tokio::test]
+async fn test_all_sends_full_history() {
+    let contents = request_contents(IncludeContents::All).await;
+    assert_eq!(texts(&contents).first(), Some(&"first"));
+    assert_eq!(texts(&contents).last(), Some(&"third"));
+    assert_eq!(call_ids(&contents), ["c1", "c2", "c3"]);
+    assert_no_orphans(&contents);
+}
+
+#[tokio::test]
+async fn test_none_sends_only_current_turn() {
+    let contents = request_contents(IncludeContents::None).await;
+    assert_eq!(texts(&contents), ["third"]);
+}
+
+#[tokio::test]
+async fn test_last_n_keeps_recent_turns() {
+    let contents = request_contents(IncludeContents::LastN(1)).await;
+    assert_eq!(texts(&contents), ["third"]);
+
+    let contents = request_contents(IncludeContents::LastN(2)).await;
+    assert_eq!(texts(&contents), ["second", "sibling chatter", "confirmed", "third"]);
+    assert_eq!(call_ids(&contents), ["c2", "c3"]);
+}
+
+#[tokio::test]
+async fn test_last_n_zero_keeps_current_turn() {
+    let contents = request_contents(IncludeContents::LastN(0)).await;
+    assert_eq!(texts(&contents), ["third"]);
+}
+
+#[tokio::test]
+async fn test_last_n_never_orphans_function_pairs() {
+    for n in 0..6 {
+        let contents = request_contents(IncludeContents::LastN(n)).await;
+        assert_no_orphans(&contents);
+        assert_eq!(texts(&contents).last(), Some(&"third"), "LastN({n})");
+    }
+}
+
+#[tokio::test]
+async fn test_filtered_drops_other_authors_with_their_tool_results() {
+    let contents =
+        request_contents(IncludeContents::filtered(|event| event.author != "sibling")).await;
+    assert!(!texts(&contents).contains(&"sibling chatter"));
+    assert_eq!(call_ids(&contents), ["c1", "c2"]);
+    assert_no_orphans(&contents);
+}
+
+#[tokio::test]
+async fn test_filtered_response_follows_its_call() {
+    // Rejecting only the call still removes the response.
+    let drop_calls = IncludeContents::filtered(|event| {
+        !event.llm_response.content.as_ref().is_some_and(Content::has_function_calls)
+    });
+    let contents = request_contents(drop_calls).await;
+    assert!(call_ids(&contents).is_empty());
+    assert_no_orphans(&contents);
+
+    // Rejecting only the responses keeps them alongside their calls.
+    let drop_responses = IncludeContents::filtered(|event| {
+        !event.llm_response.content.as_ref().is_some_and(|content| {
+            content.parts.iter().any(|part| matches!(part, Part::FunctionResponse { .. }))
+        })
+    });
+    let contents = request_contents(drop_responses).await;
+    assert_eq!(call_ids(&contents), ["c1", "c2", "c3"]);
+    assert_no_orphans(&contents);
+}
@@ -0,0 +1,190 @@
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    AdkError, Agent, Content, LlmRequest, LlmResponse, Part, SessionId, ToolContext, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+mod test_context;
+use test_context::TestContext;
+
+/// Replays canned responses and records each request's instruction text.
+struct RecordingModel {
+    responses: Mutex<VecDeque<LlmResponse>>,
+    instructions: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl adk_core::Llm for RecordingModel {
+    fn name(&self) -> &str {
+        "recording-model"
+    }
+
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        _stream: bool,
+    ) -> adk_core::Result<adk_core::LlmResponseStream> {
+        let instruction = request
+            .contents
+            .iter()
+            .flat_map(|c| &c.parts)
+            .filter_map(Part::text)
+            .find(|text| text.starts_with("Current time:"))
+            .unwrap_or_default()
+            .to_string();
+        self.instructions.lock().unwrap().push(instruction);
+
+        let response = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| LlmResponse::new(Content::new("model").with_text("done")));
+        Ok(Box::pin(futures::stream::once(async { Ok(response) })))
+    }
+}
+
+fn tool_call_then_answer() -> Vec<LlmResponse> {
+    vec![
+        LlmResponse::new(Content {
+            role: "model".to_string(),
+            parts: vec![Part::FunctionCall {
+                name: "lookup".to_string(),
+                args: json!({}),
+                id: Some("call-1".to_string()),
+                thought_signature: None,
+            }],
+        }),
+        LlmResponse::new(Content::new("model").with_text("done")),
+    ]
+}
+
+#[tokio::test]
+async fn test_instruction_fn_recomputed_per_model_call() {
+    let instructions = Arc::new(Mutex::new(Vec::new()));
+    let model = Arc::new(RecordingModel {
+        responses: Mutex::new(
+            tool_call_then_answer().into_iter().chain(tool_call_then_answer()).collect(),
+        ),
+        instructions: instructions.clone(),
+    });
+    let lookup = FunctionTool::new(
+        "lookup",
+        "Looks something up",
+        |_ctx: Arc<dyn ToolContext>, _args: Value| async move { Ok(json!({ "value": 42 })) },
+    );
+
+    let provider_calls = Arc::new(AtomicUsize::new(0));
+    let calls = provider_calls.clone();
+    let agent = LlmAgentBuilder::new("clock_agent")
+        .model(model)
+        .tool(Arc::new(lookup))
+        .instruction_fn(move |ctx| {
+            let calls = calls.clone();
+            async move {
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(format!("Current time: call {call} (user {})", ctx.user_id()))
+            }
+        })
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        let mut stream = agent.run(Arc::new(TestContext::new("what time is it?"))).await.unwrap();
+        while let Some(event) = stream.next().await {
+            event.unwrap();
+        }
+    }
+
+    // Two turns of two model calls each, the provider asked before every one
+    assert_eq!(provider_calls.load(Ordering::SeqCst), 4);
+    let instructions = instructions.lock().unwrap();
+    assert_eq!(
+        *instructions,
+        (1..=4)
+            .map(|call| format!("Current time: call {call} (user test-user)"))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_provided_instruction_renders_state_placeholders() {
+    let instructions = Arc::new(Mutex::new(Vec::new()));
+    let model = Arc::new(RecordingModel {
+        responses: Mutex::new(VecDeque::new()),
+        instructions: instructions.clone(),
+    });
+    let agent = LlmAgentBuilder::new("greeter")
+        .model(model)
+        .instruction_fn(|_ctx| async { Ok("Current time: noon. Greet {user_name}.".to_string()) })
+        .build()
+        .unwrap();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::from([("user_name".to_string(), json!("Ada"))]),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent))
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let mut stream = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("hi"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+    }
+
+    assert_eq!(*instructions.lock().unwrap(), vec!["Current time: noon. Greet Ada.".to_string()]);
+}
+
+#[tokio::test]
+async fn test_instruction_provider_error_fails_the_call() {
+    let instructions = Arc::new(Mutex::new(Vec::new()));
+    let model = Arc::new(RecordingModel {
+        responses: Mutex::new(VecDeque::new()),
+        instructions: instructions.clone(),
+    });
+    let agent = LlmAgentBuilder::new("clock_agent")
+        .model(model)
+        .instruction_fn(|_ctx| async { Err(AdkError::agent("clock unavailable")) })
+        .build()
+        .unwrap();
+
+    let mut stream = agent.run(Arc::new(TestContext::new("what time is it?"))).await.unwrap();
+    let mut errors = Vec::new();
+    while let Some(event) = stream.next().await {
+        if let Err(e) = event {
+            errors.push(e);
+        }
+    }
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].is_agent());
+    assert_eq!(
+        errors[0].message,
+        "instruction provider of agent 'clock_agent' failed: clock unavailable"
+    );
+    assert!(instructions.lock().unwrap().is_empty(), "the model is never called");
+}
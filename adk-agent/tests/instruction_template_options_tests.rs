@@ -0,0 +1,87 @@
+//! `LlmAgentBuilder::instruction_template_options` controls how missing state
+//! keys render in the static instruction.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, LlmResponse, MissingKeyPolicy, SessionId, TemplateOptions, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const INSTRUCTION: &str =
+    "Greet {user:name?there}.{{#if user:premium}} Offer priority support.{{/if}} Team: [{team}]";
+
+async fn run_agent(options: Option<TemplateOptions>) -> (Arc<MockLlm>, adk_core::Result<()>) {
+    let model = Arc::new(
+        MockLlm::new("mock").with_response(LlmResponse::new(Content::new("model").with_text("Hi"))),
+    );
+    let mut builder = LlmAgentBuilder::new("greeter").model(model.clone()).instruction(INSTRUCTION);
+    if let Some(options) = options {
+        builder = builder.instruction_template_options(options);
+    }
+    let agent = builder.build().unwrap();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "templates".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::from([("user:premium".to_string(), json!(true))]),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("templates")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    let mut events = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("hello"),
+        )
+        .await
+        .unwrap();
+    let mut result = Ok(());
+    while let Some(event) = events.next().await {
+        if let Err(e) = event {
+            result = Err(e);
+        }
+    }
+    (model, result)
+}
+
+#[tokio::test]
+async fn test_missing_key_fails_by_default() {
+    let (model, result) = run_agent(None).await;
+    let err = result.unwrap_err();
+    assert!(err.message.contains("'team' not found"));
+    assert!(model.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_key_renders_empty_when_configured() {
+    let options = TemplateOptions::default().missing_key(MissingKeyPolicy::Empty);
+    let (model, result) = run_agent(Some(options)).await;
+    result.unwrap();
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 1);
+    let texts: Vec<&str> = requests[0]
+        .contents
+        .iter()
+        .flat_map(|content| content.parts.iter())
+        .filter_map(|part| part.text())
+        .collect();
+    assert!(
+        texts.contains(&"Greet there. Offer priority support. Team: []"),
+        "instruction not rendered as expected: {texts:?}"
+    );
+}
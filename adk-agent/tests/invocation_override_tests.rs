@@ -0,0 +1,87 @@
+//! Per-invocation generation and tool overrides on a shared agent.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, RunConfig, SessionId, ToolContext, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+fn tool(name: &str) -> Arc<FunctionTool> {
+    Arc::new(FunctionTool::new(
+        name,
+        "Test tool",
+        |_ctx: Arc<dyn ToolContext>, _args: Value| async { Ok(json!({})) },
+    ))
+}
+
+async fn run(runner: &Runner, config: Option<RunConfig>) {
+    let user_id = UserId::new("user-1").unwrap();
+    let session_id = SessionId::new("session-1").unwrap();
+    let message = Content::new("user").with_text("Write me a poem");
+    let mut stream = match config {
+        Some(config) => runner.run_with_config(user_id, session_id, message, config).await,
+        None => runner.run(user_id, session_id, message).await,
+    }
+    .unwrap();
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_invocation_overrides_reach_the_model_and_do_not_persist() {
+    let model = scripted_model([text("ok"), text("ok")]);
+    let agent = LlmAgentBuilder::new("writer")
+        .model(model.clone())
+        .temperature(0.2)
+        .top_p(0.9)
+        .tool(tool("search"))
+        .tool(tool("publish"))
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "override-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("override-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    // "Creative mode" for one call, without publishing.
+    let creative = RunConfig::builder().temperature(1.2).tool_allowlist(["search"]).build();
+    run(&runner, Some(creative)).await;
+    run(&runner, None).await;
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+
+    let overridden = requests[0].config.as_ref().unwrap();
+    assert_eq!(overridden.temperature, Some(1.2));
+    // Settings the invocation leaves alone come from the agent.
+    assert_eq!(overridden.top_p, Some(0.9));
+    assert_eq!(requests[0].tools.keys().collect::<Vec<_>>(), ["search"]);
+
+    let next = requests[1].config.as_ref().unwrap();
+    assert_eq!(next.temperature, Some(0.2));
+    assert_eq!(next.top_p, Some(0.9));
+    let mut tools: Vec<_> = requests[1].tools.keys().collect();
+    tools.sort();
+    assert_eq!(tools, ["publish", "search"]);
+}
@@ -0,0 +1,86 @@
+//! How an agent's output schema reaches models with and without schema support.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, GenerateContentConfig, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::text;
+
+/// Runs one turn of an agent with an output schema and returns the config
+/// the model received.
+async fn sent_config(supports_schema: bool) -> GenerateContentConfig {
+    let model = Arc::new(
+        MockLlm::new("json-model")
+            .with_response_schema_support(supports_schema)
+            .with_turn(text(r#"{"city": "Nairobi"}"#)),
+    );
+    let agent = LlmAgentBuilder::new("extractor")
+        .model(model.clone())
+        .generate_content_config(GenerateContentConfig {
+            temperature: Some(0.1),
+            ..Default::default()
+        })
+        .output_schema(json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"]
+        }))
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "json-mode-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("json-mode-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let events: Vec<_> = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Where is the conference?"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(events.iter().all(|event| event.is_ok()));
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 1);
+    requests[0].config.clone().expect("a generation config was sent")
+}
+
+#[tokio::test]
+async fn test_schema_sent_when_model_supports_it() {
+    let config = sent_config(true).await;
+
+    assert_eq!(config.response_schema.unwrap()["required"], json!(["city"]));
+    assert_eq!(config.response_mime_type, None);
+    assert_eq!(config.temperature, Some(0.1));
+}
+
+#[tokio::test]
+async fn test_json_mode_fallback_when_model_lacks_schema_support() {
+    let config = sent_config(false).await;
+
+    assert_eq!(config.response_schema, None);
+    assert_eq!(config.response_mime_type.as_deref(), Some("application/json"));
+    assert_eq!(config.temperature, Some(0.1));
+}
@@ -0,0 +1,163 @@
+//! Lifecycle events reported for model calls, tool calls, and transfers.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, Event, FinishReason, LifecycleEvent, RunConfig, SessionId, ToolContext, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+async fn run(agent: Arc<dyn Agent>, sessions: Arc<dyn SessionService>) -> Vec<Event> {
+    sessions
+        .create(CreateRequest {
+            app_name: "lifecycle-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("lifecycle-app")
+        .agent(agent)
+        .session_service(sessions)
+        .run_config(RunConfig::builder().lifecycle_events(true).build())
+        .build()
+        .unwrap();
+
+    let stream = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("What's the weather in Nairobi?"),
+        )
+        .await
+        .unwrap();
+    stream.map(|event| event.unwrap()).collect().await
+}
+
+/// Lifecycle steps with timings dropped so they compare exactly.
+fn steps(events: &[Event]) -> Vec<LifecycleEvent> {
+    events
+        .iter()
+        .filter_map(Event::lifecycle_event)
+        .map(|step| match step {
+            LifecycleEvent::ModelCallCompleted { model, finish_reason, .. } => {
+                LifecycleEvent::ModelCallCompleted { model, duration_ms: 0, finish_reason }
+            }
+            LifecycleEvent::ToolCallCompleted { tool, call_id, error, .. } => {
+                LifecycleEvent::ToolCallCompleted { tool, call_id, duration_ms: 0, error }
+            }
+            other => other,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_tool_turn_emits_lifecycle_sequence_in_order() {
+    let model = scripted_model([
+        call("get_weather", json!({ "city": "Nairobi" })),
+        text("It is sunny in Nairobi."),
+    ]);
+    let weather = FunctionTool::new(
+        "get_weather",
+        "Gets the weather",
+        |_ctx: Arc<dyn ToolContext>, _args: Value| async move { Ok(json!({ "sky": "sunny" })) },
+    );
+    let agent =
+        LlmAgentBuilder::new("forecaster").model(model).tool(Arc::new(weather)).build().unwrap();
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+
+    let events = run(Arc::new(agent), sessions.clone()).await;
+
+    let model_started = LifecycleEvent::ModelCallStarted { model: "scripted-model".to_string() };
+    let model_completed = LifecycleEvent::ModelCallCompleted {
+        model: "scripted-model".to_string(),
+        duration_ms: 0,
+        finish_reason: Some(FinishReason::Stop),
+    };
+    assert_eq!(
+        steps(&events),
+        [
+            model_started.clone(),
+            model_completed.clone(),
+            LifecycleEvent::ToolCallStarted {
+                tool: "get_weather".to_string(),
+                call_id: "call-get_weather".to_string(),
+            },
+            LifecycleEvent::ToolCallCompleted {
+                tool: "get_weather".to_string(),
+                call_id: "call-get_weather".to_string(),
+                duration_ms: 0,
+                error: None,
+            },
+            model_started,
+            model_completed,
+        ]
+    );
+
+    // Content consumers can skip lifecycle events, which carry nothing else.
+    let content_events: Vec<&Event> = events.iter().filter(|event| !event.is_lifecycle()).collect();
+    assert!(
+        events.iter().filter(|event| event.is_lifecycle()).all(|event| event
+            .llm_response
+            .content
+            .is_none()
+            && !event.is_final_response())
+    );
+    assert_eq!(
+        content_events.last().unwrap().llm_response.content.as_ref().unwrap().parts[0].text(),
+        Some("It is sunny in Nairobi.")
+    );
+
+    // Nor are they persisted.
+    let session = sessions
+        .get(GetRequest {
+            app_name: "lifecycle-app".into(),
+            user_id: "user-1".into(),
+            session_id: "session-1".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    assert!(!session.events().all().iter().any(Event::is_lifecycle));
+}
+
+#[tokio::test]
+async fn test_transfer_emits_agent_delegated() {
+    let helper = LlmAgentBuilder::new("helper")
+        .model(scripted_model([text("Happy to help.")]))
+        .build()
+        .unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(scripted_model([call("transfer_to_agent", json!({ "agent_name": "helper" }))]))
+        .sub_agent(Arc::new(helper))
+        .build()
+        .unwrap();
+
+    let events = run(Arc::new(coordinator), Arc::new(InMemorySessionService::new())).await;
+
+    let delegated: Vec<&Event> = events
+        .iter()
+        .filter(|event| {
+            matches!(event.lifecycle_event(), Some(LifecycleEvent::AgentDelegated { .. }))
+        })
+        .collect();
+    assert_eq!(delegated.len(), 1);
+    assert_eq!(
+        delegated[0].lifecycle_event(),
+        Some(LifecycleEvent::AgentDelegated {
+            from: "coordinator".to_string(),
+            to: "helper".to_string(),
+        })
+    );
+    assert_eq!(delegated[0].author, "coordinator");
+}
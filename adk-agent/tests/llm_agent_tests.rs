@@ -47,6 +47,7 @@ impl adk_core::Llm for MockLlm {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             });
         };
         Ok(Box::pin(s))
@@ -94,6 +95,7 @@ impl adk_core::Llm for SpyLlm {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             });
         };
         Ok(Box::pin(s))
@@ -154,9 +156,6 @@ impl InvocationContext for TestContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn run_config(&self) -> &RunConfig {
         &self.config
     }
@@ -0,0 +1,162 @@
+//! `LoopAgent` exit conditions and time limits.
+
+use adk_agent::{CustomAgentBuilder, LoopAgent};
+use adk_core::{
+    Agent, Content, Event, EventStream, InvocationContext, LoopExit, LoopExitReason, SessionId,
+    UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// A reviser that raises `quality_score` by 0.25 each time it runs, starting
+/// from `start`.
+fn reviser(start: f64, runs: Arc<AtomicU32>) -> Arc<dyn Agent> {
+    let agent = CustomAgentBuilder::new("reviser")
+        .handler(move |ctx| {
+            let runs = runs.clone();
+            async move {
+                let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+                let mut event = Event::new(ctx.invocation_id());
+                event.author = "reviser".to_string();
+                event.llm_response.content =
+                    Some(Content::new("model").with_text(format!("Draft {run}")));
+                event
+                    .actions
+                    .state_delta
+                    .insert("quality_score".to_string(), json!(start + 0.25 * f64::from(run)));
+                Ok(Box::pin(futures::stream::iter(vec![Ok(event)])) as EventStream)
+            }
+        })
+        .build()
+        .unwrap();
+    Arc::new(agent)
+}
+
+/// An agent that waits for `delay` before answering.
+fn slow(name: &'static str, delay: Duration) -> Arc<dyn Agent> {
+    let agent = CustomAgentBuilder::new(name)
+        .handler(move |ctx: Arc<dyn InvocationContext>| async move {
+            let stream = async_stream::stream! {
+                tokio::time::sleep(delay).await;
+                let mut event = Event::new(ctx.invocation_id());
+                event.author = name.to_string();
+                event.llm_response.content = Some(Content::new("model").with_text("done"));
+                yield Ok(event);
+            };
+            Ok(Box::pin(stream) as EventStream)
+        })
+        .build()
+        .unwrap();
+    Arc::new(agent)
+}
+
+async fn run(agent: LoopAgent) -> Vec<Event> {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Write a haiku"),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await
+}
+
+fn loop_exit(events: &[Event]) -> LoopExit {
+    let exits: Vec<_> = events.iter().filter_map(Event::loop_exit).collect();
+    assert_eq!(exits.len(), 1, "expected one loop exit event");
+    exits[0].clone()
+}
+
+fn good_enough(state: &dyn adk_core::State) -> bool {
+    state.get("quality_score").and_then(|v| v.as_f64()).is_some_and(|score| score >= 0.9)
+}
+
+#[tokio::test]
+async fn test_condition_met_on_first_iteration() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let agent = LoopAgent::new("refine", vec![reviser(0.7, runs.clone())])
+        .with_max_iterations(5)
+        .until(good_enough);
+    let events = run(agent).await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    let exit = loop_exit(&events);
+    assert_eq!(exit, LoopExit { reason: LoopExitReason::Condition, iterations: 1 });
+    assert_eq!(events.last().unwrap().author, "refine");
+}
+
+#[tokio::test]
+async fn test_condition_checked_after_each_iteration() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let agent = LoopAgent::new("refine", vec![reviser(0.0, runs.clone())])
+        .with_max_iterations(10)
+        .until(good_enough);
+    let exit = loop_exit(&run(agent).await);
+
+    assert_eq!(exit, LoopExit { reason: LoopExitReason::Condition, iterations: 4 });
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_condition_never_met_falls_back_to_max_iterations() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let agent = LoopAgent::new("refine", vec![reviser(-10.0, runs.clone())])
+        .with_max_iterations(3)
+        .until(good_enough);
+    let exit = loop_exit(&run(agent).await);
+
+    assert_eq!(exit, LoopExit { reason: LoopExitReason::MaxIterations, iterations: 3 });
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_max_duration_cancels_the_running_sub_agent() {
+    let agent = LoopAgent::new(
+        "refine",
+        vec![slow("drafter", Duration::from_millis(10)), slow("critic", Duration::from_secs(30))],
+    )
+    .with_max_duration(Duration::from_millis(200));
+    let started = std::time::Instant::now();
+    let events = run(agent).await;
+
+    assert!(started.elapsed() < Duration::from_secs(5), "loop was not cut short");
+    let exit = loop_exit(&events);
+    assert_eq!(exit, LoopExit { reason: LoopExitReason::MaxDuration, iterations: 1 });
+    assert!(events.iter().any(|event| event.author == "drafter"));
+    assert!(!events.iter().any(|event| event.author == "critic"));
+}
+
+#[tokio::test]
+async fn test_plain_loop_reports_nothing() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let agent = LoopAgent::new("refine", vec![reviser(0.0, runs.clone())]).with_max_iterations(2);
+    let events = run(agent).await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert!(events.iter().all(|event| event.loop_exit().is_none()));
+}
@@ -0,0 +1,94 @@
+//! Breaking loops where the model repeats the same tool call.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Content, Event, LlmRequest, Part, SessionId, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+fn tool_responses(request: &LlmRequest) -> Vec<Value> {
+    request
+        .contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            Part::FunctionResponse { function_response, .. } => {
+                Some(function_response.response.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_repeated_tool_call_is_nudged_then_forced_to_answer() {
+    let lookup = || call("lookup_order", json!({ "order_id": "42" }));
+    let model =
+        scripted_model([lookup(), lookup(), lookup(), lookup(), text("Order 42 has shipped.")]);
+    let executions = Arc::new(AtomicUsize::new(0));
+    let counter = executions.clone();
+    let tool = FunctionTool::new("lookup_order", "Look up an order", move |_ctx, _args| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({ "status": "shipped" }))
+        }
+    });
+    let agent =
+        LlmAgentBuilder::new("support").model(model.clone()).tool(Arc::new(tool)).build().unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "support-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("support-app")
+        .agent(Arc::new(agent))
+        .session_service(sessions)
+        .loop_break_threshold(2)
+        .build()
+        .unwrap();
+
+    let events: Vec<Event> = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Where is order 42?"),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(executions.load(Ordering::SeqCst), 2, "the tool ran only up to the threshold");
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 5);
+    let nudge = tool_responses(&requests[3]).pop().unwrap();
+    assert_eq!(nudge["error"], "repeated_tool_call");
+    let message = nudge["message"].as_str().unwrap();
+    assert!(message.contains("You already called `lookup_order` with these arguments 2 times"));
+    assert!(message.contains(r#"{"status":"shipped"}"#));
+    assert!(!requests[3].tools.is_empty());
+    assert!(requests[4].tools.is_empty(), "tools are withheld once the loop is broken");
+
+    let last = events.last().unwrap();
+    assert_eq!(
+        last.llm_response.content.as_ref().and_then(|content| content.parts[0].text()),
+        Some("Order 42 has shipped.")
+    );
+}
@@ -0,0 +1,205 @@
+//! Continuing responses cut off by `FinishReason::MaxTokens`.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, Event, FinishReason, LlmResponse, Part, RunConfig, SessionId, StreamingMode,
+    ToolContext, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+fn text_response(text: &str, finish_reason: FinishReason) -> LlmResponse {
+    let mut response = LlmResponse::new(Content::new("model").with_text(text));
+    response.finish_reason = Some(finish_reason);
+    response
+}
+
+async fn collect_events(agent: impl Agent + 'static, run_config: RunConfig) -> Vec<Event> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "test-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("test-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    runner
+        .run_with_config(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("start"),
+            run_config,
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await
+}
+
+fn event_text(event: &Event) -> String {
+    event
+        .llm_response
+        .content
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(Part::text)
+        .collect()
+}
+
+/// The model events a consumer would treat as the answer.
+fn final_responses(events: &[Event]) -> Vec<&Event> {
+    events
+        .iter()
+        .filter(|event| event.llm_response.content.is_some() && event.is_final_response())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_max_tokens_response_is_continued_until_natural_stop() {
+    let model = scripted_model([
+        text_response("The quick brown ", FinishReason::MaxTokens),
+        text_response("fox jumps over ", FinishReason::MaxTokens),
+        text_response("the lazy dog.", FinishReason::Stop),
+    ]);
+    let agent = LlmAgentBuilder::new("writer").model(model.clone()).build().unwrap();
+
+    let run_config =
+        RunConfig::builder().streaming_mode(StreamingMode::None).max_token_continuations(5).build();
+    let events = collect_events(agent, run_config).await;
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 3);
+    let last_prompt = requests[2].contents.last().unwrap();
+    assert_eq!(last_prompt.role, "user");
+    assert!(last_prompt.parts[0].text().unwrap().starts_with("Continue exactly where"));
+
+    let partial: Vec<_> = events.iter().filter(|event| event.llm_response.partial).collect();
+    assert_eq!(partial.len(), 2, "truncated segments are not final answers");
+
+    let finals = final_responses(&events);
+    assert_eq!(finals.len(), 1);
+    let last = finals[0];
+    assert_eq!(event_text(last), "The quick brown fox jumps over the lazy dog.");
+    assert_eq!(last.llm_response.finish_reason, Some(FinishReason::Stop));
+    assert_eq!(
+        last.provider_metadata.get("adk.original_finish_reason").map(String::as_str),
+        Some("MaxTokens")
+    );
+    assert_eq!(
+        last.provider_metadata.get("adk.max_token_continuations").map(String::as_str),
+        Some("2")
+    );
+}
+
+#[tokio::test]
+async fn test_streamed_segments_before_continuation_are_partial() {
+    let model = scripted_model([
+        text_response("The quick brown ", FinishReason::MaxTokens),
+        text_response("fox.", FinishReason::Stop),
+    ]);
+    let agent = LlmAgentBuilder::new("writer").model(model.clone()).build().unwrap();
+
+    let run_config =
+        RunConfig::builder().streaming_mode(StreamingMode::SSE).max_token_continuations(5).build();
+    let events = collect_events(agent, run_config).await;
+
+    assert_eq!(model.requests().len(), 2);
+    let finals = final_responses(&events);
+    assert_eq!(finals.len(), 1);
+    assert_eq!(event_text(finals[0]), "fox.");
+}
+
+#[tokio::test]
+async fn test_continuation_stops_at_cap_and_surfaces_max_tokens() {
+    let model = scripted_model([
+        text_response("one ", FinishReason::MaxTokens),
+        text_response("two ", FinishReason::MaxTokens),
+        text_response("three ", FinishReason::MaxTokens),
+    ]);
+    let agent = LlmAgentBuilder::new("writer").model(model.clone()).build().unwrap();
+
+    let run_config =
+        RunConfig::builder().streaming_mode(StreamingMode::None).max_token_continuations(1).build();
+    let events = collect_events(agent, run_config).await;
+
+    assert_eq!(model.requests().len(), 2);
+    let finals = final_responses(&events);
+    assert_eq!(finals.len(), 1);
+    assert_eq!(event_text(finals[0]), "one two ");
+    assert_eq!(finals[0].llm_response.finish_reason, Some(FinishReason::MaxTokens));
+}
+
+#[tokio::test]
+async fn test_continuation_disabled_by_default() {
+    let model = scripted_model([text_response("cut", FinishReason::MaxTokens)]);
+    let agent = LlmAgentBuilder::new("writer").model(model.clone()).build().unwrap();
+
+    let run_config = RunConfig::builder().streaming_mode(StreamingMode::None).build();
+    let events = collect_events(agent, run_config).await;
+
+    assert_eq!(model.requests().len(), 1);
+    let finals = final_responses(&events);
+    assert_eq!(finals.len(), 1);
+    assert_eq!(finals[0].llm_response.finish_reason, Some(FinishReason::MaxTokens));
+}
+
+#[tokio::test]
+async fn test_stop_with_pending_tool_call_continues_with_tool() {
+    let mut tool_call = LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![
+            Part::Text { text: "Let me check.".to_string() },
+            Part::FunctionCall {
+                name: "lookup".to_string(),
+                args: json!({}),
+                id: Some("call-1".to_string()),
+                thought_signature: None,
+            },
+        ],
+    });
+    tool_call.finish_reason = Some(FinishReason::Stop);
+    let model = scripted_model([tool_call, text("The answer is 42.")]);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let lookup = FunctionTool::new(
+        "lookup",
+        "Looks up the answer",
+        move |_ctx: Arc<dyn ToolContext>, _args: Value| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(json!({ "answer": 42 }))
+            }
+        },
+    );
+    let agent = LlmAgentBuilder::new("assistant")
+        .model(model.clone())
+        .tool(Arc::new(lookup))
+        .build()
+        .unwrap();
+
+    let run_config = RunConfig::builder().streaming_mode(StreamingMode::None).build();
+    let events = collect_events(agent, run_config).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(model.requests().len(), 2);
+    assert_eq!(event_text(final_responses(&events)[0]), "The answer is 42.");
+}
@@ -0,0 +1,126 @@
+//! Bounding the model→tool→model cycle with `max_tool_iterations`.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Content, Event, LlmResponse, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+fn search(page: u32) -> LlmResponse {
+    call("search", json!({ "page": page }))
+}
+
+struct Harness {
+    runner: Runner,
+    executions: Arc<AtomicUsize>,
+}
+
+async fn harness(model: Arc<MockLlm>, max_tool_iterations: u32) -> Harness {
+    let executions = Arc::new(AtomicUsize::new(0));
+    let counter = executions.clone();
+    let tool = FunctionTool::new("search", "Search the catalog", move |_ctx, _args: Value| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({ "results": [] }))
+        }
+    });
+    let agent = LlmAgentBuilder::new("searcher")
+        .model(model)
+        .tool(Arc::new(tool))
+        .max_tool_iterations(max_tool_iterations)
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "search-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("search-app")
+        .agent(Arc::new(agent))
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    Harness { runner, executions }
+}
+
+async fn run(runner: &Runner) -> Vec<Event> {
+    runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Find me a lamp"),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await
+}
+
+fn truncated_at(event: &Event) -> Option<&str> {
+    event.provider_metadata.get("adk.tool_iterations_truncated").map(String::as_str)
+}
+
+#[tokio::test]
+async fn test_limit_forces_final_call_without_tools() {
+    let model = scripted_model([search(1), search(2), search(3), text("No lamps in stock.")]);
+    let harness = harness(model.clone(), 3).await;
+
+    let events = run(&harness.runner).await;
+
+    assert_eq!(harness.executions.load(Ordering::SeqCst), 3);
+    let requests = model.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[..3].iter().all(|request| !request.tools.is_empty()));
+    assert!(requests[3].tools.is_empty(), "the final call is made without tools");
+
+    let last = events.last().unwrap();
+    assert_eq!(
+        last.llm_response.content.as_ref().and_then(|content| content.parts[0].text()),
+        Some("No lamps in stock.")
+    );
+    assert_eq!(truncated_at(last), Some("3"));
+    assert!(events[..events.len() - 1].iter().all(|event| truncated_at(event).is_none()));
+}
+
+#[tokio::test]
+async fn test_calls_after_limit_are_not_executed() {
+    let model = scripted_model([search(1), search(2)]);
+    let harness = harness(model.clone(), 1).await;
+
+    let events = run(&harness.runner).await;
+
+    assert_eq!(harness.executions.load(Ordering::SeqCst), 1);
+    assert_eq!(model.requests().len(), 2);
+    assert_eq!(truncated_at(events.last().unwrap()), Some("1"));
+}
+
+#[tokio::test]
+async fn test_counter_resets_between_runs() {
+    let model = scripted_model([search(1), text("First."), search(1), text("Second.")]);
+    let harness = harness(model.clone(), 2).await;
+
+    let first = run(&harness.runner).await;
+    let second = run(&harness.runner).await;
+
+    assert_eq!(harness.executions.load(Ordering::SeqCst), 2);
+    let requests = model.requests();
+    assert!(requests.iter().all(|request| !request.tools.is_empty()));
+    assert!(first.iter().chain(&second).all(|event| truncated_at(event).is_none()));
+}
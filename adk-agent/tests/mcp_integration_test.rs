@@ -101,9 +101,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
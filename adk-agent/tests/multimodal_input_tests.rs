@@ -0,0 +1,67 @@
+//! Multimodal user content built with the `Content` helpers reaches the model
+//! request unchanged.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The 8-byte PNG signature followed by an empty IHDR chunk header.
+const PNG: [u8; 16] = [
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+];
+
+#[tokio::test]
+async fn test_inline_png_reaches_the_model_request() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_response(LlmResponse::new(Content::new("model").with_text("A tiny image."))),
+    );
+    let agent = LlmAgentBuilder::new("describer").model(model.clone()).build().unwrap();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "vision".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("vision")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    let input = Content::new("user")
+        .with_text("What is in this picture?")
+        .with_image_bytes(PNG.to_vec(), "image/png")
+        .with_file_uri("audio/mpeg", "gs://bucket/narration.mp3");
+    let mut events = runner
+        .run(UserId::new("user").unwrap(), SessionId::new("session").unwrap(), input)
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 1);
+    let user = requests[0].contents.iter().rfind(|c| c.role == "user").expect("user content");
+    assert!(user.parts.iter().any(|part| matches!(
+        part,
+        Part::InlineData { mime_type, data } if mime_type == "image/png" && data[..] == PNG
+    )));
+    assert!(user.parts.iter().any(|part| matches!(
+        part,
+        Part::FileData { mime_type, file_uri }
+            if mime_type == "audio/mpeg" && file_uri == "gs://bucket/narration.mp3"
+    )));
+}
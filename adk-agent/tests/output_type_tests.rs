@@ -0,0 +1,135 @@
+//! Typed structured output with `LlmAgentBuilder::output_type`.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+struct Forecast {
+    city: String,
+    high: i64,
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+/// Runs a forecaster agent once and returns the stream error, if any, and the
+/// `forecast` state value.
+async fn run_forecaster(
+    model: Arc<MockLlm>,
+    max_retries: usize,
+) -> (Option<adk_core::AdkError>, Option<serde_json::Value>) {
+    let agent = LlmAgentBuilder::new("forecaster")
+        .model(model)
+        .output_type::<Forecast>()
+        .output_max_retries(max_retries)
+        .output_key("forecast")
+        .build()
+        .unwrap();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "weather".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("weather")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+
+    let mut events = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Forecast for Paris?"),
+        )
+        .await
+        .unwrap();
+    let mut error = None;
+    while let Some(event) = events.next().await {
+        if let Err(e) = event {
+            error = Some(e);
+        }
+    }
+
+    let session = sessions
+        .get(GetRequest {
+            app_name: "weather".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (error, session.state().get("forecast"))
+}
+
+#[tokio::test]
+async fn test_malformed_output_is_retried_with_the_error() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(reply("{\"city\": \"Paris\", \"high\": "))
+            .with_turn(reply("```json\n{\"city\": \"Paris\", \"high\": 21}\n```")),
+    );
+    let (error, stored) = run_forecaster(model.clone(), 1).await;
+
+    assert!(error.is_none(), "unexpected error: {error:?}");
+    let stored = stored.expect("forecast stored under output_key");
+    assert_eq!(stored, json!({ "city": "Paris", "high": 21 }));
+    let forecast: Forecast = serde_json::from_value(stored).unwrap();
+    assert_eq!(forecast, Forecast { city: "Paris".to_string(), high: 21 });
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+    let correction = requests[1].contents.last().unwrap();
+    assert_eq!(correction.role, "user");
+    assert!(matches!(
+        &correction.parts[0],
+        Part::Text { text } if text.contains("Response is not valid JSON")
+    ));
+}
+
+#[tokio::test]
+async fn test_gives_up_with_descriptive_error() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(reply("not json"))
+            .with_turn(reply("{\"city\": \"Paris\", \"high\": \"warm\"}")),
+    );
+    let (error, stored) = run_forecaster(model.clone(), 1).await;
+
+    let error = error.expect("validation error after the retry");
+    assert!(error.message.contains("output schema validation failed after 1 retries"));
+    assert!(error.message.contains("high"), "error should name the bad field: {error}");
+    assert!(stored.is_none());
+    assert_eq!(model.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_fenced_output_is_accepted_first_time() {
+    let model = Arc::new(
+        MockLlm::new("mock").with_turn(reply("```\n{\"city\": \"Oslo\", \"high\": -3}\n```")),
+    );
+    let (error, stored) = run_forecaster(model.clone(), 0).await;
+
+    assert!(error.is_none(), "unexpected error: {error:?}");
+    assert_eq!(stored, Some(json!({ "city": "Oslo", "high": -3 })));
+    assert_eq!(model.requests().len(), 1);
+}
@@ -0,0 +1,164 @@
+//! `ParallelAgent` concurrency limits, per-child outputs, and failure handling.
+
+use adk_agent::{CustomAgentBuilder, ParallelAgent};
+use adk_core::{AdkError, Agent, Content, Event, EventStream, SessionId, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Tracks how many children are running at once.
+#[derive(Default)]
+struct Gauge {
+    active: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+/// A child that answers `text` after a short wait, or fails when `text` is `None`.
+fn child(name: &'static str, text: Option<&'static str>, gauge: Arc<Gauge>) -> Arc<dyn Agent> {
+    let agent = CustomAgentBuilder::new(name)
+        .handler(move |ctx| {
+            let gauge = gauge.clone();
+            async move {
+                let invocation_id = ctx.invocation_id().to_string();
+                let events = async_stream::stream! {
+                    let active = gauge.active.fetch_add(1, Ordering::SeqCst) + 1;
+                    gauge.peak.fetch_max(active, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    gauge.active.fetch_sub(1, Ordering::SeqCst);
+                    match text {
+                        Some(text) => {
+                            let mut event = Event::new(&invocation_id);
+                            event.author = name.to_string();
+                            event.llm_response.content =
+                                Some(Content::new("model").with_text(text));
+                            yield Ok(event);
+                        }
+                        None => yield Err(AdkError::agent(format!("{name} is unavailable"))),
+                    }
+                };
+                Ok(Box::pin(events) as EventStream)
+            }
+        })
+        .build()
+        .unwrap();
+    Arc::new(agent)
+}
+
+/// Runs `agent` and returns the stream's items and the final session state.
+async fn run(
+    agent: ParallelAgent,
+) -> (Vec<adk_core::Result<Event>>, HashMap<String, serde_json::Value>) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    let items = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Analyze"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let session = sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (items, session.state().all())
+}
+
+fn team(gauge: &Arc<Gauge>, failing: bool) -> Vec<Arc<dyn Agent>> {
+    vec![
+        child("web", Some("web findings"), gauge.clone()),
+        child("news", if failing { None } else { Some("news findings") }, gauge.clone()),
+        child("papers", Some("paper findings"), gauge.clone()),
+    ]
+}
+
+#[tokio::test]
+async fn test_children_run_concurrently_by_default() {
+    let gauge = Arc::new(Gauge::default());
+    let (items, _) = run(ParallelAgent::new("team", team(&gauge, false))).await;
+
+    assert!(items.iter().all(Result::is_ok));
+    assert_eq!(gauge.peak.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_max_concurrency_limits_running_children() {
+    let gauge = Arc::new(Gauge::default());
+    let (items, _) = run(ParallelAgent::new("team", team(&gauge, false)).max_concurrency(2)).await;
+
+    assert_eq!(items.iter().filter(|item| item.is_ok()).count(), 3);
+    assert_eq!(gauge.peak.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_output_key_per_child_stores_each_final_text() {
+    let gauge = Arc::new(Gauge::default());
+    let (items, state) =
+        run(ParallelAgent::new("team", team(&gauge, false)).output_key_per_child(true)).await;
+
+    assert_eq!(state.get("parallel:web"), Some(&json!("web findings")));
+    assert_eq!(state.get("parallel:news"), Some(&json!("news findings")));
+    assert_eq!(state.get("parallel:papers"), Some(&json!("paper findings")));
+
+    for event in items.iter().map(|item| item.as_ref().unwrap()) {
+        assert_eq!(event.branch, format!("team.{}", event.author), "{event:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_fail_fast_stops_on_first_error() {
+    let gauge = Arc::new(Gauge::default());
+    let (items, _) = run(ParallelAgent::new("team", team(&gauge, true))).await;
+
+    let error = items.last().unwrap().as_ref().unwrap_err();
+    assert!(error.message.contains("news is unavailable"));
+}
+
+#[tokio::test]
+async fn test_without_fail_fast_other_branches_finish() {
+    let gauge = Arc::new(Gauge::default());
+    let (items, state) = run(ParallelAgent::new("team", team(&gauge, true))
+        .fail_fast(false)
+        .output_key_per_child(true))
+    .await;
+
+    let events: Vec<Event> = items.into_iter().map(Result::unwrap).collect();
+    let failed: Vec<&Event> =
+        events.iter().filter(|event| event.llm_response.error_message.is_some()).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].author, "news");
+    assert_eq!(failed[0].branch, "team.news");
+    assert!(failed[0].llm_response.error_message.as_ref().unwrap().contains("news is unavailable"));
+
+    assert_eq!(state.get("parallel:web"), Some(&json!("web findings")));
+    assert_eq!(state.get("parallel:papers"), Some(&json!("paper findings")));
+    assert!(!state.contains_key("parallel:news"));
+}
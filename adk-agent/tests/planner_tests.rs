@@ -0,0 +1,191 @@
+//! `LlmAgentBuilder::planner` with the built-in `PlanReActPlanner`.
+
+use adk_agent::{LlmAgentBuilder, PlanReActPlanner, planner::PLANNING_TAG};
+use adk_core::{AdkError, Agent, Content, Event, LlmResponse, Part, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn call(name: &str, id: &str, text: &str) -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![
+            Part::Text { text: text.to_string() },
+            Part::FunctionCall {
+                name: name.to_string(),
+                args: json!({}),
+                id: Some(id.to_string()),
+                thought_signature: None,
+            },
+        ],
+    })
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+fn with_tools(builder: LlmAgentBuilder) -> LlmAgentBuilder {
+    let search = FunctionTool::new("search", "Search the web", |_ctx, _args: Value| async {
+        Ok(json!({ "results": ["rust 2024 edition"] }))
+    });
+    let fetch = FunctionTool::new("fetch", "Fetch a page", |_ctx, _args: Value| async {
+        Err(AdkError::tool("page not found"))
+    });
+    builder.tool(Arc::new(search)).tool(Arc::new(fetch))
+}
+
+/// Runs a planning agent and returns its events and the `answer` state value.
+async fn run(
+    model: Arc<MockLlm>,
+    planner: Option<PlanReActPlanner>,
+) -> (Vec<Event>, Option<Value>) {
+    let mut builder =
+        with_tools(LlmAgentBuilder::new("researcher").model(model).output_key("answer"));
+    if let Some(planner) = planner {
+        builder = builder.planner(Arc::new(planner));
+    }
+    let agent = builder.build().unwrap();
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    let mut stream = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("What is new in Rust?"),
+        )
+        .await
+        .unwrap();
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event.unwrap());
+    }
+
+    let session = sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (events, session.state().get("answer"))
+}
+
+fn plans(events: &[Event]) -> Vec<&str> {
+    events.iter().filter_map(Event::plan).collect()
+}
+
+fn request_texts(request: &adk_core::LlmRequest) -> Vec<&str> {
+    request.contents.iter().flat_map(|c| c.parts.iter().filter_map(Part::text)).collect()
+}
+
+#[tokio::test]
+async fn test_plan_is_reported_and_final_answer_stored() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(call("search", "c1", "/*PLANNING*/\n1. Search\n2. Summarize\n/*ACTION*/"))
+            .with_turn(reply("/*REASONING*/ Found it. /*FINAL_ANSWER*/ The 2024 edition.")),
+    );
+    let (events, answer) = run(model.clone(), Some(PlanReActPlanner::new())).await;
+
+    assert_eq!(plans(&events), ["1. Search\n2. Summarize"]);
+    assert_eq!(answer, Some(json!("The 2024 edition.")));
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(request_texts(&requests[0]).iter().any(|text| text.contains(PLANNING_TAG)));
+}
+
+#[tokio::test]
+async fn test_failed_step_asks_for_a_revised_plan() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(call("fetch", "c1", "/*PLANNING*/ 1. Fetch the release notes"))
+            .with_turn(call("search", "c2", "/*REPLANNING*/ 1. Search instead"))
+            .with_turn(reply("/*FINAL_ANSWER*/ Done.")),
+    );
+    let (events, _) = run(model.clone(), Some(PlanReActPlanner::new())).await;
+
+    assert_eq!(plans(&events), ["1. Fetch the release notes", "1. Search instead"]);
+    let requests = model.requests();
+    assert_eq!(requests.len(), 3);
+    let replan = requests[1].contents.last().unwrap();
+    assert_eq!(replan.role, "user");
+    let text = replan.parts[0].text().unwrap();
+    assert!(text.contains("- fetch: ") && text.contains("page not found"), "{text}");
+    assert!(
+        !request_texts(&requests[2])
+            .iter()
+            .any(|text| text.contains("These steps failed") && text.contains("search")),
+        "successful steps do not trigger re-planning"
+    );
+}
+
+#[tokio::test]
+async fn test_final_answer_ends_the_turn_before_tools_run() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(call("search", "c1", "/*FINAL_ANSWER*/ Already known."))
+            .with_turn(reply("unreachable")),
+    );
+    let (events, answer) = run(model.clone(), Some(PlanReActPlanner::new())).await;
+
+    assert_eq!(model.requests().len(), 1);
+    assert_eq!(answer, Some(json!("Already known.")));
+    assert!(!events.iter().any(|event| event.llm_response.content.as_ref().is_some_and(
+        |content| content.parts.iter().any(|p| matches!(p, Part::FunctionResponse { .. }))
+    )));
+}
+
+#[tokio::test]
+async fn test_max_steps_withholds_tools() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(call("search", "c1", "/*PLANNING*/ 1. Search forever"))
+            .with_turn(reply("/*FINAL_ANSWER*/ Stopped.")),
+    );
+    let (_, answer) = run(model.clone(), Some(PlanReActPlanner::new().max_steps(1))).await;
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(!requests[0].tools.is_empty());
+    assert!(requests[1].tools.is_empty(), "tools withheld after the last step");
+    assert_eq!(answer, Some(json!("Stopped.")));
+}
+
+#[tokio::test]
+async fn test_without_planner_nothing_changes() {
+    let model = Arc::new(
+        MockLlm::new("mock")
+            .with_turn(call("search", "c1", "/*PLANNING*/ 1. Search"))
+            .with_turn(reply("/*FINAL_ANSWER*/ Plain.")),
+    );
+    let (events, answer) = run(model.clone(), None).await;
+
+    assert!(plans(&events).is_empty());
+    assert_eq!(answer, Some(json!("/*FINAL_ANSWER*/ Plain.")));
+    assert!(!request_texts(&model.requests()[0]).iter().any(|text| text.contains(PLANNING_TAG)));
+}
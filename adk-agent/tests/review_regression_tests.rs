@@ -127,10 +127,6 @@ impl InvocationContext for TestContext {
         unimplemented!()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -172,6 +168,7 @@ impl RecordingModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 
@@ -188,6 +185,7 @@ impl RecordingModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
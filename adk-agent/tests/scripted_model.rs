@@ -0,0 +1,34 @@
+//! Canned model turns shared by the agent integration tests.
+#![allow(dead_code)]
+
+use adk_core::{Content, FinishReason, LlmResponse, Part};
+use adk_model::MockLlm;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A model that answers each call with the next of `responses`.
+pub fn scripted_model(responses: impl IntoIterator<Item = LlmResponse>) -> Arc<MockLlm> {
+    Arc::new(responses.into_iter().fold(MockLlm::new("scripted-model"), MockLlm::with_turn))
+}
+
+/// A model turn that calls `name` with `args`, using `call-{name}` as the call id.
+pub fn call(name: &str, args: Value) -> LlmResponse {
+    let mut response = LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: name.to_string(),
+            args,
+            id: Some(format!("call-{name}")),
+            thought_signature: None,
+        }],
+    });
+    response.finish_reason = Some(FinishReason::Stop);
+    response
+}
+
+/// A model turn that answers with `text`.
+pub fn text(text: &str) -> LlmResponse {
+    let mut response = LlmResponse::new(Content::new("model").with_text(text));
+    response.finish_reason = Some(FinishReason::Stop);
+    response
+}
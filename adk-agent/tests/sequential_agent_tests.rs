@@ -0,0 +1,137 @@
+//! `SequentialAgent` escalation and per-step output keys.
+
+use adk_agent::{CustomAgentBuilder, SequentialAgent};
+use adk_core::{
+    Agent, Content, Event, EventStream, InvocationContext, PipelineEscalation, SessionId, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A step that answers with `respond(ctx)` and escalates when `escalate` is set.
+fn step(
+    name: &'static str,
+    escalate: bool,
+    respond: impl Fn(&dyn InvocationContext) -> String + Send + Sync + 'static,
+) -> Arc<dyn Agent> {
+    let respond = Arc::new(respond);
+    let agent = CustomAgentBuilder::new(name)
+        .handler(move |ctx| {
+            let respond = respond.clone();
+            async move {
+                let mut event = Event::new(ctx.invocation_id());
+                event.author = name.to_string();
+                event.llm_response.content =
+                    Some(Content::new("model").with_text(respond(ctx.as_ref())));
+                event.actions.escalate = escalate;
+                Ok(Box::pin(futures::stream::iter(vec![Ok(event)])) as EventStream)
+            }
+        })
+        .build()
+        .unwrap();
+    Arc::new(agent)
+}
+
+fn state_text(ctx: &dyn InvocationContext, key: &str) -> String {
+    ctx.session().state().get(key).and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+async fn run(agent: SequentialAgent) -> (Vec<Event>, HashMap<String, serde_json::Value>) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()
+        .unwrap();
+    let events = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Write about tides"),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    let session = sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (events, session.state().all())
+}
+
+#[tokio::test]
+async fn test_output_keys_feed_the_next_step() {
+    let pipeline = SequentialAgent::new(
+        "article",
+        vec![
+            step("researcher", false, |_| "Tides follow the moon.".to_string()),
+            step("writer", false, |ctx| format!("Draft based on: {}", state_text(ctx, "research"))),
+        ],
+    )
+    .with_output_keys([("researcher", "research"), ("writer", "draft")]);
+
+    let (events, state) = run(pipeline).await;
+
+    assert_eq!(state.get("research"), Some(&json!("Tides follow the moon.")));
+    assert_eq!(state.get("draft"), Some(&json!("Draft based on: Tides follow the moon.")));
+    assert!(events.iter().all(|event| event.pipeline_escalation().is_none()));
+}
+
+#[tokio::test]
+async fn test_escalation_stops_later_steps_and_explains_why() {
+    let publisher_ran = Arc::new(AtomicBool::new(false));
+    let ran = publisher_ran.clone();
+    let pipeline = SequentialAgent::new(
+        "article",
+        vec![
+            step("writer", false, |_| "A draft.".to_string()),
+            step("reviewer", true, |_| "Content policy violation.".to_string()),
+            step("publisher", false, move |_| {
+                ran.store(true, Ordering::SeqCst);
+                "Published.".to_string()
+            }),
+        ],
+    )
+    .with_output_keys([("writer", "draft"), ("reviewer", "review")]);
+
+    let (events, state) = run(pipeline).await;
+
+    assert!(!publisher_ran.load(Ordering::SeqCst));
+    assert_eq!(state.get("review"), Some(&json!("Content policy violation.")));
+
+    let last = events.last().unwrap();
+    assert_eq!(last.author, "article");
+    assert_eq!(
+        last.pipeline_escalation(),
+        Some(PipelineEscalation {
+            agent: "reviewer".to_string(),
+            step: 2,
+            steps: 3,
+            reason: Some("Content policy violation.".to_string()),
+        })
+    );
+    let text = last.llm_response.content.as_ref().unwrap().parts[0].text().unwrap();
+    assert!(text.contains("step 2 of 3 ('reviewer')"), "{text}");
+}
@@ -0,0 +1,124 @@
+//! Sub-agents reading the run-wide conversation buffer.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, Event, LlmRequest, SessionId, SharedHistory, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+/// The role and text of every text part in `request`.
+fn request_texts(request: &LlmRequest) -> Vec<(String, String)> {
+    request
+        .contents
+        .iter()
+        .flat_map(|content| {
+            content
+                .parts
+                .iter()
+                .filter_map(|part| part.text().map(|text| (content.role.clone(), text.to_string())))
+        })
+        .collect()
+}
+
+async fn send(runner: &Runner, message: &str) -> Vec<Event> {
+    runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text(message),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await
+}
+
+async fn runner(agent: Arc<dyn Agent>) -> Runner {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "support-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    Runner::builder()
+        .app_name("support-app")
+        .agent(agent)
+        .session_service(sessions)
+        .build()
+        .unwrap()
+}
+
+fn coordinator(model: Arc<MockLlm>, billing: Arc<dyn Agent>) -> Arc<dyn Agent> {
+    Arc::new(LlmAgentBuilder::new("coordinator").model(model).sub_agent(billing).build().unwrap())
+}
+
+#[tokio::test]
+async fn test_sub_agent_recalls_fact_told_to_coordinator() {
+    let coordinator_model = scripted_model([
+        text("Thanks! I've noted that your order number is 4411."),
+        call("transfer_to_agent", json!({ "agent_name": "billing" })),
+    ]);
+    let billing_model = scripted_model([text("I've started a refund for order 4411.")]);
+    let billing = LlmAgentBuilder::new("billing")
+        .description("Handles refunds")
+        .model(billing_model.clone())
+        .shared_history(SharedHistory::Dialogue)
+        .build()
+        .unwrap();
+    let runner = runner(coordinator(coordinator_model, Arc::new(billing))).await;
+
+    send(&runner, "Hi, my order number is 4411.").await;
+    let events = send(&runner, "I'd like a refund please.").await;
+
+    let history = request_texts(&billing_model.requests()[0]);
+    assert_eq!(
+        history,
+        [
+            ("user".to_string(), "Hi, my order number is 4411.".to_string()),
+            (
+                "user".to_string(),
+                "For context: [coordinator] said: Thanks! I've noted that your order number is 4411."
+                    .to_string()
+            ),
+            ("user".to_string(), "I'd like a refund please.".to_string()),
+        ]
+    );
+    let last = events.last().unwrap();
+    assert_eq!(last.author, "billing");
+    assert_eq!(
+        last.llm_response.content.as_ref().and_then(|content| content.parts[0].text()),
+        Some("I've started a refund for order 4411.")
+    );
+}
+
+#[tokio::test]
+async fn test_sub_agent_without_shared_history_misses_coordinator_turns() {
+    let coordinator_model = scripted_model([
+        text("Thanks! I've noted that your order number is 4411."),
+        call("transfer_to_agent", json!({ "agent_name": "billing" })),
+    ]);
+    let billing_model = scripted_model([text("Which order should I refund?")]);
+    let billing = LlmAgentBuilder::new("billing")
+        .description("Handles refunds")
+        .model(billing_model.clone())
+        .build()
+        .unwrap();
+    let runner = runner(coordinator(coordinator_model, Arc::new(billing))).await;
+
+    send(&runner, "Hi, my order number is 4411.").await;
+    send(&runner, "I'd like a refund please.").await;
+
+    let history = request_texts(&billing_model.requests()[0]);
+    assert!(history.iter().all(|(_, text)| !text.contains("[coordinator]")));
+}
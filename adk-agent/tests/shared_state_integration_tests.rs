@@ -2,8 +2,8 @@
 
 use adk_agent::ParallelAgent;
 use adk_core::{
-    Agent, CallbackContext, Content, Event, EventStream, InvocationContext, Memory,
-    ReadonlyContext, Result, RunConfig, Session,
+    Agent, CallbackContext, Content, Event, EventStream, InvocationContext, ReadonlyContext,
+    Result, RunConfig, Session,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -94,9 +94,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
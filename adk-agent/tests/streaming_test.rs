@@ -53,6 +53,7 @@ impl Llm for MockModel {
                     error_message: None,
                     provider_metadata: None,
                     interaction_id: None,
+                    citations: Vec::new(),
                 });
             }
         };
@@ -138,9 +139,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -222,9 +220,6 @@ async fn test_streaming_chunks() {
         fn agent(&self) -> Arc<dyn Agent> {
             unimplemented!()
         }
-        fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-            None
-        }
         fn session(&self) -> &dyn Session {
             &self.session
         }
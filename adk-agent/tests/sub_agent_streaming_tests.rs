@@ -0,0 +1,171 @@
+//! Events of sub-agents called as tools stream through their caller as they
+//! happen, authored by the sub-agent, unless the caller opts out.
+
+use adk_agent::{LlmAgent, LlmAgentBuilder};
+use adk_core::{Agent, Content, Event, LlmResponse, Part, SessionId, StreamingMode, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::AgentTool;
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn call(name: &str, request: &str) -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: name.to_string(),
+            args: json!({ "request": request }),
+            id: Some(format!("call-{name}")),
+            thought_signature: None,
+        }],
+    })
+}
+
+fn chunk(text: &str) -> LlmResponse {
+    let mut response = LlmResponse::new(Content::new("model").with_text(text));
+    response.partial = true;
+    response.turn_complete = false;
+    response
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+/// `coordinator` calls `researcher` as a tool, which streams its answer in
+/// three chunks.
+fn coordinator(stream_sub_agent_output: bool) -> LlmAgent {
+    let researcher = LlmAgentBuilder::new("researcher")
+        .description("Finds papers")
+        .model(Arc::new(
+            MockLlm::new("researcher")
+                .with_response(chunk("Found "))
+                .with_response(chunk("three "))
+                .with_response(reply("papers.")),
+        ))
+        .build()
+        .unwrap();
+    LlmAgentBuilder::new("coordinator")
+        .model(Arc::new(
+            MockLlm::new("coordinator")
+                .with_turn(call("researcher", "papers on tides"))
+                .with_turn(reply("There are three papers on tides.")),
+        ))
+        .tool(Arc::new(AgentTool::new(Arc::new(researcher))))
+        .stream_sub_agent_output(stream_sub_agent_output)
+        .build()
+        .unwrap()
+}
+
+async fn run(agent: LlmAgent, streaming_mode: StreamingMode) -> (Vec<Event>, Vec<Event>) {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .run_config(adk_core::RunConfig::builder().streaming_mode(streaming_mode).build())
+        .build()
+        .unwrap();
+    let stream = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("What is there on tides?"),
+        )
+        .await
+        .unwrap();
+    let events = stream.map(|event| event.unwrap()).collect().await;
+    let session = sessions
+        .get(GetRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: "session".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+    (events, session.events().all())
+}
+
+/// `(author, partial, text)` of each event.
+fn authors(events: &[Event]) -> Vec<(String, bool, String)> {
+    events
+        .iter()
+        .map(|event| {
+            let text = event
+                .llm_response
+                .content
+                .iter()
+                .flat_map(|content| content.parts.iter().filter_map(Part::text))
+                .collect();
+            (event.author.clone(), event.llm_response.partial, text)
+        })
+        .collect()
+}
+
+fn expected(events: &[(&str, bool, &str)]) -> Vec<(String, bool, String)> {
+    events
+        .iter()
+        .map(|(author, partial, text)| (author.to_string(), *partial, text.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sub_agent_chunks_stream_under_its_own_name() {
+    let (events, persisted) = run(coordinator(true), StreamingMode::SSE).await;
+
+    assert_eq!(
+        authors(&events),
+        expected(&[
+            ("coordinator", false, ""),
+            ("researcher", true, "Found "),
+            ("researcher", true, "three "),
+            ("researcher", false, "papers."),
+            ("coordinator", false, ""),
+            ("coordinator", false, "There are three papers on tides."),
+        ])
+    );
+    for event in &events[1..4] {
+        assert_eq!(event.sub_agent_call_id(), Some("call-researcher"));
+    }
+    let Part::FunctionResponse { function_response, .. } =
+        &events[4].llm_response.content.as_ref().unwrap().parts[0]
+    else {
+        panic!("expected the researcher's tool result");
+    };
+    assert_eq!(function_response.response, json!({ "response": "Found three papers." }));
+
+    // The sub-agent's answer reaches the session as the tool result only.
+    assert!(persisted.iter().all(|event| event.author != "researcher"));
+}
+
+#[tokio::test]
+async fn test_sub_agent_output_can_be_consumed_silently() {
+    let (events, _) = run(coordinator(false), StreamingMode::SSE).await;
+
+    assert!(events.iter().all(|event| event.author == "coordinator"));
+    let answer = events.last().unwrap().llm_response.content.as_ref().unwrap();
+    assert_eq!(answer.parts[0].text(), Some("There are three papers on tides."));
+}
+
+#[tokio::test]
+async fn test_sub_agent_events_follow_the_caller_streaming_mode() {
+    let (events, _) = run(coordinator(true), StreamingMode::None).await;
+
+    let researcher: Vec<_> =
+        authors(&events).into_iter().filter(|(author, ..)| author == "researcher").collect();
+    assert_eq!(researcher, expected(&[("researcher", false, "Found three papers.")]));
+}
@@ -56,9 +56,6 @@ impl InvocationContext for TestContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn run_config(&self) -> &RunConfig {
         &self.config
     }
@@ -40,6 +40,7 @@ impl SequencedModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 
@@ -59,6 +60,7 @@ impl SequencedModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -210,10 +212,6 @@ impl InvocationContext for MockContext {
         unimplemented!()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -41,6 +41,7 @@ impl SequencedModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 
@@ -60,6 +61,7 @@ impl SequencedModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -223,10 +225,6 @@ impl InvocationContext for MockContext {
         unimplemented!()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -0,0 +1,138 @@
+//! `tool_error_policy` decides whether a failed tool call goes back to the
+//! model, is replaced by a substitute result, or aborts the run.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    AdkError, Agent, Content, Event, Part, Result, SessionId, ToolContext, ToolErrorDecision,
+    ToolErrorPolicy, ToolFailureResolution, UserId,
+};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+/// The function response sent back with the second request, if any.
+fn tool_result(model: &MockLlm) -> Option<Value> {
+    let requests = model.requests();
+    requests.get(1)?.contents.iter().flat_map(|content| &content.parts).find_map(
+        |part| match part {
+            Part::FunctionResponse { function_response, .. } => {
+                Some(function_response.response.clone())
+            }
+            _ => None,
+        },
+    )
+}
+
+/// Runs one turn in which `lookup` always fails, under `policy`.
+async fn run(policy: ToolErrorPolicy) -> (Arc<MockLlm>, Vec<Result<Event>>) {
+    let model = scripted_model([call("lookup", json!({ "order": 42 })), text("done")]);
+    let lookup = FunctionTool::new(
+        "lookup",
+        "Looks up an order",
+        |_ctx: Arc<dyn ToolContext>, _args: Value| async {
+            Err::<Value, _>(AdkError::tool("orders API unreachable"))
+        },
+    );
+    let agent = LlmAgentBuilder::new("support")
+        .model(model.clone())
+        .tool(Arc::new(lookup))
+        .tool_error_policy(policy)
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let results = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Where is order 42?"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    (model, results)
+}
+
+fn failure_resolutions(results: &[Result<Event>]) -> Vec<ToolFailureResolution> {
+    results
+        .iter()
+        .filter_map(|result| result.as_ref().ok()?.tool_failure())
+        .map(|failure| failure.resolution)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_return_to_model_sends_structured_error() {
+    let (model, results) = run(ToolErrorPolicy::ReturnToModel).await;
+
+    assert!(results.iter().all(Result::is_ok));
+    let response = tool_result(&model).expect("error sent to the model");
+    assert_eq!(response["tool"], "lookup");
+    assert!(response["error"].as_str().unwrap().contains("orders API unreachable"));
+
+    let failure = results
+        .iter()
+        .find_map(|result| result.as_ref().ok()?.tool_failure())
+        .expect("tool failure event");
+    assert_eq!(failure.tool, "lookup");
+    assert_eq!(failure.call_id, "call-lookup");
+    assert_eq!(failure.resolution, ToolFailureResolution::ReturnedToModel);
+}
+
+#[tokio::test]
+async fn test_abort_fails_the_invocation() {
+    let (model, results) = run(ToolErrorPolicy::Abort).await;
+
+    let error = results.iter().find_map(|result| result.as_ref().err()).expect("run aborted");
+    assert!(error.message.contains("Tool 'lookup' failed"));
+    assert!(error.message.contains("orders API unreachable"));
+    assert_eq!(model.requests().len(), 1, "the model is not called again");
+    assert_eq!(failure_resolutions(&results), vec![ToolFailureResolution::Aborted]);
+}
+
+#[tokio::test]
+async fn test_custom_policy_substitutes_or_aborts() {
+    let seen = Arc::new(Mutex::new(None));
+    let record = seen.clone();
+    let (model, results) = run(ToolErrorPolicy::Custom(Arc::new(move |tool, args, error| {
+        *record.lock().unwrap() = Some((tool.to_string(), args.clone(), error.to_string()));
+        ToolErrorDecision::Substitute(json!({ "status": "unknown" }))
+    })))
+    .await;
+
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(tool_result(&model), Some(json!({ "status": "unknown" })));
+    assert_eq!(failure_resolutions(&results), vec![ToolFailureResolution::Substituted]);
+    let (tool, args, error) = seen.lock().unwrap().clone().unwrap();
+    assert_eq!(tool, "lookup");
+    assert_eq!(args, json!({ "order": 42 }));
+    assert!(error.contains("orders API unreachable"));
+
+    let (_, results) =
+        run(ToolErrorPolicy::Custom(Arc::new(|_, _, _| ToolErrorDecision::Abort))).await;
+    assert!(results.iter().any(Result::is_err));
+    assert_eq!(failure_resolutions(&results), vec![ToolFailureResolution::Aborted]);
+}
@@ -0,0 +1,131 @@
+//! Retry and model-facing handling of each `ToolError` variant.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{AdkError, Agent, Content, RetryBudget, SessionId, ToolContext, ToolError, UserId};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+/// Runs one turn where `lookup` fails with `error` on its first `failures`
+/// attempts. Returns the attempt count and the function response the model saw.
+async fn run_lookup(error: fn() -> AdkError, failures: u32) -> (u32, Value) {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+    let lookup = FunctionTool::new(
+        "lookup",
+        "Looks up an order",
+        move |_ctx: Arc<dyn ToolContext>, _args: Value| {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    return Err(error());
+                }
+                Ok(json!({ "status": "shipped" }))
+            }
+        },
+    );
+    let agent = LlmAgentBuilder::new("support")
+        .model(scripted_model([call("lookup", json!({ "order": 42 })), text("done")]))
+        .tool(Arc::new(lookup))
+        .default_retry_budget(RetryBudget::new(2, Duration::ZERO))
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "tool-error-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("tool-error-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+    let events: Vec<_> = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Where is order 42?"),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    let response = events
+        .iter()
+        .flat_map(|event| event.tool_results().into_iter().map(|result| result.response.clone()))
+        .next()
+        .expect("a tool result was sent to the model");
+    (attempts.load(Ordering::SeqCst), response)
+}
+
+#[tokio::test]
+async fn test_invalid_arguments_return_to_model_without_retry() {
+    let (attempts, response) =
+        run_lookup(|| ToolError::InvalidArguments("'order' must be a string".into()).into(), 3)
+            .await;
+
+    assert_eq!(attempts, 1);
+    assert_eq!(
+        response,
+        json!({
+            "error": "'order' must be a string",
+            "error_type": "invalid_arguments",
+            "retryable": false,
+            "tool": "lookup",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_upstream_failures_are_retried() {
+    let (attempts, response) =
+        run_lookup(|| ToolError::Upstream("orders API returned 503".into()).into(), 1).await;
+    assert_eq!(attempts, 2);
+    assert_eq!(response, json!({ "status": "shipped" }));
+
+    let (attempts, response) =
+        run_lookup(|| ToolError::Upstream("orders API returned 503".into()).into(), 3).await;
+    assert_eq!(attempts, 3, "the whole retry budget is used");
+    assert_eq!(response["error_type"], "upstream");
+    assert_eq!(response["retryable"], true);
+}
+
+#[tokio::test]
+async fn test_not_found_and_internal_are_not_retried() {
+    let (attempts, response) =
+        run_lookup(|| ToolError::NotFound("no order 42".into()).into(), 3).await;
+    assert_eq!(attempts, 1);
+    assert_eq!(response["error"], "no order 42");
+    assert_eq!(response["error_type"], "not_found");
+
+    let (attempts, response) =
+        run_lookup(|| ToolError::Internal("index out of bounds".into()).into(), 3).await;
+    assert_eq!(attempts, 1);
+    assert_eq!(response["error_type"], "internal");
+}
+
+#[tokio::test]
+async fn test_untyped_errors_keep_retrying() {
+    let (attempts, response) = run_lookup(|| AdkError::tool("connection reset"), 3).await;
+
+    assert_eq!(attempts, 3);
+    assert!(response["error"].as_str().unwrap().contains("connection reset"));
+    assert!(response.get("error_type").is_none());
+}
@@ -0,0 +1,147 @@
+//! `tool_filter` decides per invocation, from session state, which
+//! toolset-provided tools an agent offers.
+
+use adk_agent::{LlmAgent, LlmAgentBuilder};
+use adk_core::{Agent, Content, LlmResponse, Part, SessionId, Tool, ToolContext, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::{BasicToolset, FunctionTool};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn call(name: &str) -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: name.to_string(),
+            args: json!({}),
+            id: Some(format!("call-{name}")),
+            thought_signature: None,
+        }],
+    })
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+fn tool(name: &str) -> Arc<dyn Tool> {
+    let result = json!({ name: "done" });
+    Arc::new(FunctionTool::new(name, "Does something", move |_ctx, _args: Value| {
+        let result = result.clone();
+        async move { Ok(result) }
+    }))
+}
+
+/// Offers `refund_tool` only to premium users.
+fn support_agent(model: Arc<MockLlm>, extra_tool: Option<Arc<dyn Tool>>) -> LlmAgent {
+    let mut builder = LlmAgentBuilder::new("support")
+        .model(model)
+        .toolset(Arc::new(BasicToolset::new(
+            "billing",
+            vec![tool("lookup_order"), tool("refund_tool")],
+        )))
+        .tool_filter(|ctx, tool| {
+            tool.name() != "refund_tool"
+                || ctx.session().state().get("user:tier") == Some(json!("premium"))
+        });
+    if let Some(extra_tool) = extra_tool {
+        builder = builder.tool(extra_tool);
+    }
+    builder.build().unwrap()
+}
+
+/// Creates one session per `(user, tier)`, with the tier in user-scoped state.
+async fn runner(agent: LlmAgent, users: &[(&str, &str)]) -> Runner {
+    let service = Arc::new(InMemorySessionService::new());
+    for (user_id, tier) in users {
+        service
+            .create(CreateRequest {
+                app_name: "app".into(),
+                user_id: user_id.to_string(),
+                session_id: Some("session".into()),
+                state: HashMap::from([("user:tier".to_string(), json!(tier))]),
+            })
+            .await
+            .unwrap();
+    }
+    Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(service)
+        .build()
+        .unwrap()
+}
+
+async fn turn(runner: &Runner, user_id: &str) -> Vec<adk_core::Event> {
+    let stream = runner
+        .run(
+            UserId::new(user_id).unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("I want my money back"),
+        )
+        .await
+        .unwrap();
+    stream.map(|event| event.unwrap()).collect().await
+}
+
+fn declared(model: &MockLlm, request: usize) -> Vec<String> {
+    let mut names: Vec<String> = model.requests()[request].tools.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_tool_declarations_follow_session_state() {
+    let model = Arc::new(MockLlm::new("support").with_response(reply("ok")));
+    let runner =
+        runner(support_agent(model.clone(), None), &[("ada", "premium"), ("bob", "basic")]).await;
+
+    turn(&runner, "ada").await;
+    turn(&runner, "bob").await;
+
+    assert_eq!(declared(&model, 0), ["lookup_order", "refund_tool"]);
+    assert_eq!(declared(&model, 1), ["lookup_order"]);
+}
+
+#[tokio::test]
+async fn test_tool_shown_to_the_model_stays_callable_when_state_changes_mid_turn() {
+    let downgrade = Arc::new(FunctionTool::new(
+        "downgrade",
+        "Moves the user to the basic tier",
+        |ctx: Arc<dyn ToolContext>, _args: Value| async move {
+            let mut actions = ctx.actions();
+            actions.state_delta.insert("user:tier".to_string(), json!("basic"));
+            ctx.set_actions(actions);
+            Ok(json!({ "tier": "basic" }))
+        },
+    ));
+    let model = Arc::new(
+        MockLlm::new("support")
+            .with_turn(call("downgrade"))
+            .with_turn(call("refund_tool"))
+            .with_turn(reply("Refunded and downgraded.")),
+    );
+    let runner = runner(support_agent(model.clone(), Some(downgrade)), &[("ada", "premium")]).await;
+
+    let events = turn(&runner, "ada").await;
+
+    let refund = events
+        .iter()
+        .filter_map(|event| event.llm_response.content.as_ref())
+        .flat_map(|content| &content.parts)
+        .find_map(|part| match part {
+            Part::FunctionResponse { function_response, .. }
+                if function_response.name == "refund_tool" =>
+            {
+                Some(function_response.response.clone())
+            }
+            _ => None,
+        })
+        .expect("refund_tool was dispatched");
+    assert_eq!(refund, json!({ "refund_tool": "done" }));
+    assert!(declared(&model, 2).contains(&"refund_tool".to_string()));
+}
@@ -0,0 +1,159 @@
+use adk_agent::LlmAgentBuilder;
+use adk_artifact::{ArtifactService, InMemoryArtifactService, LoadRequest};
+use adk_core::{
+    Content, Part, Result, RunConfig, SessionId, Tool, ToolContext, ToolOutputLimitConfig, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{call, scripted_model, text};
+
+/// Returns a large text payload; optionally declares its own output limit.
+struct VerboseTool {
+    size: usize,
+    own_limit: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for VerboseTool {
+    fn name(&self) -> &str {
+        "verbose_tool"
+    }
+
+    fn description(&self) -> &str {
+        "Returns a lot of text"
+    }
+
+    fn max_output_bytes(&self) -> Option<usize> {
+        self.own_limit
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, _args: Value) -> Result<Value> {
+        Ok(Value::String("x".repeat(self.size)))
+    }
+}
+
+async fn run_and_get_tool_response(
+    tool: VerboseTool,
+    run_config: RunConfig,
+    artifacts: Option<Arc<InMemoryArtifactService>>,
+) -> Value {
+    let model = scripted_model([call("verbose_tool", json!({})), text("done")]);
+    let agent =
+        LlmAgentBuilder::new("test-agent").model(model).tool(Arc::new(tool)).build().unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "test-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let mut builder =
+        Runner::builder().app_name("test-app").agent(Arc::new(agent)).session_service(sessions);
+    if let Some(artifacts) = artifacts {
+        builder = builder.artifact_service(artifacts);
+    }
+    let runner = builder.build().unwrap();
+
+    let mut stream = runner
+        .run_with_config(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("start"),
+            run_config,
+        )
+        .await
+        .unwrap();
+    let mut tool_response = None;
+    while let Some(result) = stream.next().await {
+        let event = result.unwrap();
+        if let Some(content) = &event.llm_response.content
+            && let Some(Part::FunctionResponse { function_response, .. }) = content.parts.first()
+        {
+            tool_response = Some(function_response.response.clone());
+        }
+    }
+    tool_response.expect("expected a function response event")
+}
+
+#[tokio::test]
+async fn test_oversized_tool_output_is_truncated_and_spilled() {
+    let artifacts = Arc::new(InMemoryArtifactService::new());
+    let run_config = RunConfig::builder()
+        .tool_output_limits(ToolOutputLimitConfig {
+            max_output_bytes: Some(1024),
+            spill_to_artifact: true,
+            ..Default::default()
+        })
+        .build();
+
+    let response = run_and_get_tool_response(
+        VerboseTool { size: 1_000_000, own_limit: None },
+        run_config,
+        Some(artifacts.clone()),
+    )
+    .await;
+
+    assert_eq!(response["truncated"], true);
+    assert_eq!(response["original_bytes"], 1_000_000);
+    let preview = response["output"].as_str().unwrap();
+    assert!(preview.len() < 1024 + adk_core::tool_output::TRUNCATION_MARKER.len() + 2);
+    assert!(preview.ends_with(adk_core::tool_output::TRUNCATION_MARKER));
+
+    let artifact_name = response["artifact"].as_str().unwrap();
+    assert_eq!(artifact_name, "tool_output_verbose_tool_call-verbose_tool.txt");
+    let loaded = artifacts
+        .load(LoadRequest {
+            app_name: "test-app".into(),
+            user_id: "user-1".into(),
+            session_id: "session-1".into(),
+            file_name: artifact_name.to_string(),
+            version: None,
+        })
+        .await
+        .unwrap();
+    match loaded.part {
+        Part::Text { text } => assert_eq!(text.len(), 1_000_000),
+        other => panic!("unexpected artifact part: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_tool_declared_limit_applies_without_run_config() {
+    let response = run_and_get_tool_response(
+        VerboseTool { size: 500, own_limit: Some(100) },
+        RunConfig::default(),
+        None,
+    )
+    .await;
+
+    assert_eq!(response["truncated"], true);
+    assert!(response.get("artifact").is_none());
+}
+
+#[tokio::test]
+async fn test_per_tool_override_takes_precedence() {
+    let run_config = RunConfig::builder()
+        .tool_output_limits(ToolOutputLimitConfig {
+            max_output_bytes: Some(100),
+            per_tool: HashMap::from([("verbose_tool".to_string(), 10_000)]),
+            spill_to_artifact: false,
+        })
+        .build();
+
+    let response =
+        run_and_get_tool_response(VerboseTool { size: 500, own_limit: Some(50) }, run_config, None)
+            .await;
+
+    assert_eq!(response, Value::String("x".repeat(500)));
+}
@@ -0,0 +1,143 @@
+//! Results of parallel tool calls sent back to the model as one linked batch.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, FinishReason, LlmResponse, Part, SessionId, ToolContext, ToolError,
+    ToolExecutionStrategy, UserId,
+};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use adk_tool::FunctionTool;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+/// Calls three tools at once, then answers the next two requests.
+fn travel_model() -> Arc<MockLlm> {
+    let call = |name: &str| Part::FunctionCall {
+        name: name.to_string(),
+        args: json!({ "city": "Lisbon" }),
+        id: Some(format!("call-{name}")),
+        thought_signature: None,
+    };
+    let mut calls = LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![call("weather"), call("events"), call("hotels")],
+    });
+    calls.finish_reason = Some(FinishReason::Stop);
+    scripted_model([calls, text("Sunny, no events listed, two hotels."), text("You're welcome.")])
+}
+
+/// A tool that answers after `delay_ms`, or fails when `result` is `None`.
+fn tool(name: &'static str, delay_ms: u64, result: Option<Value>) -> Arc<FunctionTool> {
+    Arc::new(FunctionTool::new(
+        name,
+        "Looks something up for a city",
+        move |_ctx: Arc<dyn ToolContext>, _args: Value| {
+            let result = result.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                result.ok_or_else(|| ToolError::Upstream("events API returned 503".into()).into())
+            }
+        },
+    ))
+}
+
+/// The name, id, and response of every function response in `content`.
+fn results(content: &Content) -> Vec<(String, Option<String>, Value)> {
+    content
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::FunctionResponse { function_response, id } => Some((
+                function_response.name.clone(),
+                id.clone(),
+                function_response.response.clone(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+async fn run(runner: &Runner, text: &str) {
+    let mut events = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text(text),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_parallel_results_reach_the_model_as_one_linked_batch() {
+    let model = travel_model();
+    // Finishing out of call order: hotels, then the failing events, then weather.
+    let agent = LlmAgentBuilder::new("travel")
+        .model(model.clone())
+        .tool(tool("weather", 60, Some(json!({ "sky": "sunny" }))))
+        .tool(tool("events", 30, None))
+        .tool(tool("hotels", 0, Some(json!({ "hotels": 2 }))))
+        .tool_execution_strategy(ToolExecutionStrategy::Parallel)
+        .build()
+        .unwrap();
+
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "batch-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("batch-app")
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    run(&runner, "Plan a day in Lisbon").await;
+    run(&runner, "Thanks!").await;
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 3);
+
+    // Within the invocation: one content right after the calls, every result
+    // in call order with its call's id, the failure included.
+    let contents = &requests[1].contents;
+    let batch = contents.last().unwrap();
+    assert_eq!(batch.role, "function");
+    assert!(contents[contents.len() - 2].has_function_calls());
+    let batch = results(batch);
+    let linked: Vec<_> = batch.iter().map(|(name, id, _)| (name.as_str(), id.as_deref())).collect();
+    assert_eq!(
+        linked,
+        [
+            ("weather", Some("call-weather")),
+            ("events", Some("call-events")),
+            ("hotels", Some("call-hotels")),
+        ]
+    );
+    assert_eq!(batch[0].2, json!({ "sky": "sunny" }));
+    assert_eq!(batch[1].2["error"], "events API returned 503");
+    assert_eq!(batch[2].2, json!({ "hotels": 2 }));
+
+    // The next invocation rebuilds the same batch from the session.
+    let history = &requests[2].contents;
+    let function_contents: Vec<_> =
+        history.iter().filter(|content| content.role == "function").collect();
+    assert_eq!(function_contents.len(), 1);
+    assert_eq!(results(function_contents[0]), batch);
+}
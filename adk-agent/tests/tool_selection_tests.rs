@@ -0,0 +1,152 @@
+use adk_agent::{LlmAgentBuilder, ToolSelector};
+use adk_core::{Agent, Content, Result, SessionId, Tool, ToolContext, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+/// Tool names declared in each request `model` received, sorted.
+fn declared(model: &MockLlm) -> Vec<Vec<String>> {
+    model
+        .requests()
+        .iter()
+        .map(|request| {
+            let mut names: Vec<String> = request.tools.keys().cloned().collect();
+            names.sort();
+            names
+        })
+        .collect()
+}
+
+struct NamedTool {
+    name: String,
+    description: String,
+}
+
+#[async_trait]
+impl Tool for NamedTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, _args: Value) -> Result<Value> {
+        Ok(Value::Null)
+    }
+}
+
+/// 100 tools: ten billing tools about invoices, the rest about other topics.
+fn hundred_tools() -> Vec<Arc<dyn Tool>> {
+    const TOPICS: [&str; 9] = [
+        "calendar",
+        "weather",
+        "shipping",
+        "inventory",
+        "hiring",
+        "travel",
+        "fleet",
+        "crm",
+        "wiki",
+    ];
+    (0..100)
+        .map(|i| {
+            let (name, description) = if i % 10 == 3 {
+                (format!("billing_{i:03}"), "Look up or update a customer invoice".to_string())
+            } else {
+                let topic = TOPICS[i % TOPICS.len()];
+                (format!("{topic}_{i:03}"), format!("Manage {topic} records"))
+            };
+            Arc::new(NamedTool { name, description }) as Arc<dyn Tool>
+        })
+        .collect()
+}
+
+fn agent_with_tools(model: Arc<MockLlm>, selector: Option<ToolSelector>) -> Arc<dyn Agent> {
+    let mut builder = LlmAgentBuilder::new("ops").model(model);
+    for tool in hundred_tools() {
+        builder = builder.tool(tool);
+    }
+    if let Some(selector) = selector {
+        builder = builder.tool_selection(selector);
+    }
+    Arc::new(builder.build().unwrap())
+}
+
+async fn run(agent: Arc<dyn Agent>, message: &str) -> Result<()> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "test-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner =
+        Runner::builder().app_name("test-app").agent(agent).session_service(sessions).build()?;
+
+    let mut stream = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text(message),
+        )
+        .await?;
+    while let Some(event) = stream.next().await {
+        event?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_selector_narrows_hundred_tools_to_ten() {
+    let model = Arc::new(MockLlm::new("limited-model").with_max_tools(128).with_turn(text("done")));
+    let agent = agent_with_tools(model.clone(), Some(ToolSelector::top_k(10)));
+
+    run(agent, "Why is the invoice for ACME overdue?").await.unwrap();
+
+    let declared = declared(&model);
+    assert_eq!(declared.len(), 1);
+    let expected: Vec<String> =
+        (0..100).filter(|i| i % 10 == 3).map(|i| format!("billing_{i:03}")).collect();
+    assert_eq!(declared[0], expected);
+}
+
+#[tokio::test]
+async fn test_pinned_tools_are_always_declared() {
+    let model = scripted_model([text("done")]);
+    let agent =
+        agent_with_tools(model.clone(), Some(ToolSelector::top_k(10).always_include("wiki_008")));
+
+    run(agent, "Why is the invoice for ACME overdue?").await.unwrap();
+
+    let declared = &declared(&model)[0];
+    assert_eq!(declared.len(), 10);
+    assert!(declared.contains(&"wiki_008".to_string()));
+    assert_eq!(declared.iter().filter(|name| name.starts_with("billing_")).count(), 9);
+}
+
+#[tokio::test]
+async fn test_exceeding_provider_limit_fails_before_model_call() {
+    let model = Arc::new(MockLlm::new("limited-model").with_max_tools(64).with_turn(text("done")));
+    let agent = agent_with_tools(model.clone(), None);
+
+    let error = run(agent, "Why is the invoice for ACME overdue?").await.unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.contains("declares 100 tools"), "{message}");
+    assert!(message.contains("at most 64"), "{message}");
+    assert!(message.contains("tool_selection"), "{message}");
+    assert!(model.requests().is_empty());
+}
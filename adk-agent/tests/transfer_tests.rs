@@ -0,0 +1,196 @@
+//! `transfer_to_agent` moves control between a coordinator and its sub-agents
+//! for the rest of the invocation.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, Event, LlmResponse, Part, RunConfig, SessionId, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn transfer(agent_name: &str) -> LlmResponse {
+    LlmResponse::new(Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: "transfer_to_agent".to_string(),
+            args: json!({ "agent_name": agent_name }),
+            id: Some(format!("to-{agent_name}")),
+            thought_signature: None,
+        }],
+    })
+}
+
+fn reply(text: &str) -> LlmResponse {
+    LlmResponse::new(Content::new("model").with_text(text))
+}
+
+async fn run(root: impl Agent + 'static, run_config: RunConfig) -> Vec<Event> {
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "app".into(),
+            user_id: "user".into(),
+            session_id: Some("session".into()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("app")
+        .agent(Arc::new(root) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .run_config(run_config)
+        .build()
+        .unwrap();
+    let stream = runner
+        .run(
+            UserId::new("user").unwrap(),
+            SessionId::new("session").unwrap(),
+            Content::new("user").with_text("Why was I charged twice?"),
+        )
+        .await
+        .unwrap();
+    stream.map(|event| event.unwrap()).collect().await
+}
+
+/// `(author, target)` for each transfer, in order.
+fn transfers(events: &[Event]) -> Vec<(&str, &str)> {
+    events
+        .iter()
+        .filter_map(|event| {
+            Some((event.author.as_str(), event.actions.transfer_to_agent.as_deref()?))
+        })
+        .collect()
+}
+
+/// `(author, text)` for each text answer, in order.
+fn answers(events: &[Event]) -> Vec<(&str, &str)> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let text = event.llm_response.content.as_ref()?.parts.iter().find_map(Part::text)?;
+            Some((event.author.as_str(), text))
+        })
+        .collect()
+}
+
+fn offered_targets(model: &MockLlm) -> Vec<Vec<String>> {
+    model
+        .requests()
+        .iter()
+        .map(|request| {
+            request
+                .tools
+                .get("transfer_to_agent")
+                .and_then(|decl| decl["parameters"]["properties"]["agent_name"]["enum"].as_array())
+                .map(|names| names.iter().map(|n| n.as_str().unwrap().to_string()).collect())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_parent_transfers_to_child() {
+    let billing_model = Arc::new(MockLlm::new("billing").with_turn(reply("Refund issued.")));
+    let billing = LlmAgentBuilder::new("billing").model(billing_model.clone()).build().unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(Arc::new(MockLlm::new("coordinator").with_turn(transfer("billing"))))
+        .sub_agent(Arc::new(billing))
+        .build()
+        .unwrap();
+
+    let events = run(coordinator, RunConfig::default()).await;
+
+    assert_eq!(transfers(&events), [("coordinator", "billing")]);
+    assert_eq!(answers(&events), [("billing", "Refund issued.")]);
+    assert_eq!(offered_targets(&billing_model), [["coordinator"]]);
+}
+
+#[tokio::test]
+async fn test_child_transfers_back_to_parent() {
+    let billing = LlmAgentBuilder::new("billing")
+        .model(Arc::new(MockLlm::new("billing").with_turn(transfer("coordinator"))))
+        .build()
+        .unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(Arc::new(
+            MockLlm::new("coordinator")
+                .with_turn(transfer("billing"))
+                .with_turn(reply("That one is for support, not billing.")),
+        ))
+        .sub_agent(Arc::new(billing))
+        .build()
+        .unwrap();
+
+    let events = run(coordinator, RunConfig::default()).await;
+
+    assert_eq!(transfers(&events), [("coordinator", "billing"), ("billing", "coordinator")]);
+    assert_eq!(answers(&events), [("coordinator", "That one is for support, not billing.")]);
+}
+
+#[tokio::test]
+async fn test_disallowed_transfer_is_denied() {
+    let billing_model = Arc::new(
+        MockLlm::new("billing")
+            .with_turn(transfer("coordinator"))
+            .with_turn(reply("I'll handle it here.")),
+    );
+    let billing = LlmAgentBuilder::new("billing")
+        .model(billing_model.clone())
+        .disallow_transfer_to_parent(true)
+        .build()
+        .unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(Arc::new(MockLlm::new("coordinator").with_turn(transfer("billing"))))
+        .sub_agent(Arc::new(billing))
+        .build()
+        .unwrap();
+
+    let events = run(coordinator, RunConfig::default()).await;
+
+    assert_eq!(transfers(&events), [("coordinator", "billing")]);
+    assert_eq!(answers(&events), [("billing", "I'll handle it here.")]);
+    assert!(offered_targets(&billing_model).iter().all(Vec::is_empty));
+
+    let denial = billing_model.requests()[1].contents.last().cloned().unwrap();
+    let Part::FunctionResponse { function_response, .. } = &denial.parts[0] else {
+        panic!("expected a function response, got {denial:?}");
+    };
+    let error = function_response.response["error"].as_str().unwrap();
+    assert!(error.contains("not allowed"), "{error}");
+}
+
+#[tokio::test]
+async fn test_transfer_loop_stops_at_limit() {
+    let billing = LlmAgentBuilder::new("billing")
+        .model(Arc::new(
+            MockLlm::new("billing")
+                .with_turn(transfer("coordinator"))
+                .with_turn(transfer("coordinator")),
+        ))
+        .build()
+        .unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(Arc::new(
+            MockLlm::new("coordinator")
+                .with_turn(transfer("billing"))
+                .with_turn(transfer("billing"))
+                .with_turn(transfer("billing")),
+        ))
+        .sub_agent(Arc::new(billing))
+        .build()
+        .unwrap();
+
+    let events = run(coordinator, RunConfig::builder().max_transfer_depth(2).build()).await;
+
+    assert_eq!(
+        transfers(&events),
+        [("coordinator", "billing"), ("billing", "coordinator"), ("coordinator", "billing")]
+    );
+    let stopped = events.last().unwrap();
+    assert_eq!(stopped.author, "coordinator");
+    assert!(stopped.transfer_limit().is_some_and(|limit| limit.limit == 2), "{stopped:?}");
+}
@@ -0,0 +1,174 @@
+//! Stopping a whole turn at `RunConfig::deadline`.
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    Agent, Content, Event, Llm, LlmRequest, LlmResponseStream, Result, RunConfig, SessionId, Tool,
+    ToolContext, TurnTimeout, UserId,
+};
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod scripted_model;
+use scripted_model::{call, scripted_model};
+
+/// Sets its flag when dropped.
+struct ReleaseGuard(Arc<AtomicBool>);
+
+impl Drop for ReleaseGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A model whose response stream never yields. The flag is set once the
+/// stream is dropped.
+struct HangingModel {
+    released: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl Llm for HangingModel {
+    fn name(&self) -> &str {
+        "hanging-model"
+    }
+
+    async fn generate_content(&self, _req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        let guard = ReleaseGuard(self.released.clone());
+        Ok(Box::pin(futures::stream::unfold(guard, |_guard| async move {
+            futures::future::pending::<()>().await;
+            None
+        })))
+    }
+}
+
+/// Takes ten seconds to look anything up. Records the deadline it was given
+/// and sets `released` when its execution is dropped.
+struct SlowLookup {
+    deadline: Mutex<Option<Instant>>,
+    released: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl Tool for SlowLookup {
+    fn name(&self) -> &str {
+        "slow_lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Looks up rainfall records"
+    }
+
+    async fn execute(&self, ctx: Arc<dyn ToolContext>, _args: Value) -> Result<Value> {
+        *self.deadline.lock().unwrap() = ctx.deadline();
+        let _guard = ReleaseGuard(self.released.clone());
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        Ok(json!({ "rainfall_mm": 12 }))
+    }
+}
+
+async fn run(agent: Arc<dyn Agent>, run_config: RunConfig) -> Vec<Event> {
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "deadline-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("deadline-app")
+        .agent(agent)
+        .session_service(sessions)
+        .run_config(run_config)
+        .build()
+        .unwrap();
+
+    let stream = runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("How much rain fell in Nairobi this week?"),
+        )
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), stream.map(|event| event.unwrap()).collect())
+        .await
+        .expect("run finished")
+}
+
+#[tokio::test]
+async fn test_slow_tool_is_stopped_at_turn_deadline() {
+    let released = Arc::new(AtomicBool::new(false));
+    let tool = Arc::new(SlowLookup { deadline: Mutex::new(None), released: released.clone() });
+    let agent = LlmAgentBuilder::new("assistant")
+        .model(scripted_model([call("slow_lookup", json!({ "city": "Nairobi" }))]))
+        .tool(tool.clone())
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let events =
+        run(Arc::new(agent), RunConfig::builder().deadline(Duration::from_millis(200)).build())
+            .await;
+    let elapsed = started.elapsed();
+
+    assert!(elapsed >= Duration::from_millis(200), "stopped early after {elapsed:?}");
+    assert!(elapsed < Duration::from_secs(1), "overran the deadline: {elapsed:?}");
+    assert!(released.load(Ordering::SeqCst), "the tool's execution was dropped");
+    let tool_deadline = tool.deadline.lock().unwrap().expect("the tool saw the turn deadline");
+    assert!(tool_deadline > started + Duration::from_millis(200));
+    assert!(tool_deadline <= started + elapsed);
+
+    // The model's tool call is kept as a partial result, then the turn ends.
+    assert!(events.iter().any(|event| !event.tool_calls().is_empty()));
+    let last = events.last().unwrap();
+    assert_eq!(last.author, "assistant");
+    assert_eq!(
+        last.turn_timeout(),
+        Some(TurnTimeout { agent: "assistant".to_string(), deadline_ms: 200 })
+    );
+    assert_eq!(events.iter().filter(|event| event.turn_timeout().is_some()).count(), 1);
+}
+
+#[tokio::test]
+async fn test_transfer_target_inherits_remaining_time() {
+    let released = Arc::new(AtomicBool::new(false));
+    let researcher = LlmAgentBuilder::new("researcher")
+        .description("Researches questions in depth")
+        .model(Arc::new(HangingModel { released: released.clone() }))
+        .build()
+        .unwrap();
+    let coordinator = LlmAgentBuilder::new("coordinator")
+        .model(scripted_model([call("transfer_to_agent", json!({ "agent_name": "researcher" }))]))
+        .sub_agent(Arc::new(researcher))
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let events = run(
+        Arc::new(coordinator),
+        RunConfig::builder()
+            .deadline(Duration::from_millis(150))
+            .transfer_timeout(Duration::from_secs(10))
+            .build(),
+    )
+    .await;
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(released.load(Ordering::SeqCst), "the researcher's model stream was released");
+    // The turn is over: control does not return to the coordinator.
+    assert!(events.iter().all(|event| event.delegation_timeout().is_none()));
+    let last = events.last().unwrap();
+    assert_eq!(
+        last.turn_timeout(),
+        Some(TurnTimeout { agent: "researcher".to_string(), deadline_ms: 150 })
+    );
+}
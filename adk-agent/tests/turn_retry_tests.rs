@@ -0,0 +1,104 @@
+//! Retrying a turn whose final answer fails the agent's validator.
+
+use adk_agent::{LlmAgentBuilder, TurnVerdict};
+use adk_core::{Content, Event, Result, SessionId, TurnRetry, UserId};
+use adk_model::MockLlm;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use futures::StreamExt;
+use std::sync::Arc;
+
+mod scripted_model;
+use scripted_model::{scripted_model, text};
+
+fn requires_sources(content: &Content) -> TurnVerdict {
+    let text = content.parts.iter().filter_map(|part| part.text()).collect::<String>();
+    if text.contains("Sources:") {
+        TurnVerdict::Accept
+    } else {
+        TurnVerdict::Retry("End the answer with a `Sources:` line.".to_string())
+    }
+}
+
+async fn run(model: Arc<MockLlm>, max_retries: u32) -> Vec<Result<Event>> {
+    let agent = LlmAgentBuilder::new("writer")
+        .model(model)
+        .turn_retry(max_retries, requires_sources)
+        .build()
+        .unwrap();
+    let sessions: Arc<dyn SessionService> = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: "writer-app".into(),
+            user_id: "user-1".into(),
+            session_id: Some("session-1".into()),
+            state: std::collections::HashMap::new(),
+        })
+        .await
+        .unwrap();
+    let runner = Runner::builder()
+        .app_name("writer-app")
+        .agent(Arc::new(agent))
+        .session_service(sessions)
+        .build()
+        .unwrap();
+
+    runner
+        .run(
+            UserId::new("user-1").unwrap(),
+            SessionId::new("session-1").unwrap(),
+            Content::new("user").with_text("Why is the sky blue?"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_rejected_answer_is_retried_with_feedback() {
+    let model = scripted_model([
+        text("Rayleigh scattering."),
+        text("Rayleigh scattering.\nSources: Britannica"),
+    ]);
+
+    let events: Vec<Event> = run(model.clone(), 2).await.into_iter().map(Result::unwrap).collect();
+
+    let retries: Vec<TurnRetry> = events.iter().filter_map(Event::turn_retry).collect();
+    assert_eq!(
+        retries,
+        [TurnRetry {
+            attempt: 1,
+            max_retries: 2,
+            feedback: "End the answer with a `Sources:` line.".to_string(),
+        }]
+    );
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 2);
+    let retry_history = &requests[1].contents;
+    let feedback = retry_history.last().unwrap();
+    assert_eq!(feedback.role, "user");
+    assert_eq!(feedback.parts[0].text(), Some("End the answer with a `Sources:` line."));
+    assert_eq!(
+        retry_history[retry_history.len() - 2].parts[0].text(),
+        Some("Rayleigh scattering.")
+    );
+
+    let last = events.last().unwrap();
+    assert_eq!(
+        last.llm_response.content.as_ref().and_then(|content| content.parts[0].text()),
+        Some("Rayleigh scattering.\nSources: Britannica")
+    );
+}
+
+#[tokio::test]
+async fn test_turn_fails_once_retries_are_used_up() {
+    let model = scripted_model([text("Rayleigh scattering."), text("Because of scattering.")]);
+
+    let events = run(model.clone(), 1).await;
+
+    assert_eq!(model.requests().len(), 2);
+    let error = events.last().unwrap().as_ref().unwrap_err();
+    assert!(error.to_string().contains("turn validation failed after 1 retries"));
+}
@@ -0,0 +1,94 @@
+use adk_agent::LlmAgentBuilder;
+use adk_core::{
+    AdkError, Agent, Content, ErrorComponent, LlmResponse, ReadonlyContext, Result, Tool, Toolset,
+};
+use adk_model::MockLlm;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A model whose credentials are rejected by the provider.
+fn rejecting_model() -> Arc<MockLlm> {
+    Arc::new(MockLlm::new("bad-key-model").with_error(AdkError::unauthorized(
+        ErrorComponent::Model,
+        "model.test.unauthorized",
+        "invalid API key",
+    )))
+}
+
+fn healthy_model() -> Arc<MockLlm> {
+    Arc::new(
+        MockLlm::new("healthy-model")
+            .with_response(LlmResponse::new(Content::new("model").with_text("OK"))),
+    )
+}
+
+/// A toolset whose backing server cannot be reached.
+struct UnreachableToolset;
+
+#[async_trait]
+impl Toolset for UnreachableToolset {
+    fn name(&self) -> &str {
+        "remote-mcp"
+    }
+
+    async fn tools(&self, _ctx: Arc<dyn ReadonlyContext>) -> Result<Vec<Arc<dyn Tool>>> {
+        Err(AdkError::unavailable(ErrorComponent::Tool, "tool.mcp.connect", "connection refused"))
+    }
+}
+
+#[tokio::test]
+async fn test_misconfigured_model_fails_warm_up() {
+    let model = rejecting_model();
+    let agent = LlmAgentBuilder::new("assistant").model(model.clone()).build().unwrap();
+
+    let report = agent.warm_up().await.unwrap();
+
+    assert_eq!(model.requests().len(), 1);
+    assert!(!report.is_ready());
+    let failures: Vec<_> = report.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].component, "model:bad-key-model");
+    assert!(failures[0].error.as_deref().unwrap().contains("invalid API key"));
+}
+
+#[tokio::test]
+async fn test_warm_up_reports_toolsets_and_sub_agents() {
+    let helper_model = rejecting_model();
+    let helper = LlmAgentBuilder::new("helper").model(helper_model.clone()).build().unwrap();
+    let model = healthy_model();
+    let agent = LlmAgentBuilder::new("assistant")
+        .model(model.clone())
+        .toolset(Arc::new(UnreachableToolset))
+        .sub_agent(Arc::new(helper))
+        .build()
+        .unwrap();
+
+    let report = agent.warm_up().await.unwrap();
+
+    assert_eq!(model.requests().len(), 1);
+    assert_eq!(helper_model.requests().len(), 1);
+    let statuses: Vec<_> =
+        report.components.iter().map(|s| (s.component.as_str(), s.ready)).collect();
+    assert_eq!(
+        statuses,
+        [
+            ("model:healthy-model", true),
+            ("toolset:remote-mcp", false),
+            ("helper/model:bad-key-model", false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_healthy_agent_is_ready() {
+    let model = healthy_model();
+    let agent = LlmAgentBuilder::new("assistant").model(model.clone()).build().unwrap();
+
+    let report = agent.warm_up().await.unwrap();
+
+    let requests = model.requests();
+    assert_eq!(requests.len(), 1, "warm-up sends a single ping");
+    assert_eq!(requests[0].config.as_ref().and_then(|config| config.max_output_tokens), Some(8));
+    assert_eq!(report.agent, "assistant");
+    assert!(report.is_ready());
+}
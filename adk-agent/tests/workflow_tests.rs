@@ -3,7 +3,9 @@ use adk_agent::LlmConditionalAgentBuilder;
 use adk_agent::{ConditionalAgent, CustomAgentBuilder, LoopAgent, ParallelAgent, SequentialAgent};
 #[cfg(feature = "skills")]
 use adk_core::LlmRequest;
-use adk_core::{Agent, Content, Event, InvocationContext, Part, ReadonlyContext, RunConfig};
+use adk_core::{
+    Agent, BranchChoice, Content, Event, InvocationContext, Part, ReadonlyContext, RunConfig,
+};
 use async_trait::async_trait;
 use futures::stream;
 use std::collections::HashMap;
@@ -105,9 +107,6 @@ impl InvocationContext for TestContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
     fn session(&self) -> &dyn adk_core::Session {
         &self.session
     }
@@ -161,6 +160,7 @@ impl adk_core::Llm for MockRouterLlm {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             });
         };
         Ok(Box::pin(s))
@@ -252,6 +252,40 @@ async fn test_parallel_agent_execution() {
     assert!(authors.contains(&"agent2"));
 }
 
+#[tokio::test]
+async fn test_parallel_agent_runs_sub_agents_on_their_own_branches() {
+    let reporter = |name: &'static str| {
+        CustomAgentBuilder::new(name)
+            .handler(move |ctx| async move {
+                // The sub-agent sees its branch; the event leaves it blank.
+                let mut event = Event::new(ctx.invocation_id());
+                event.author = format!("{name}@{}", ctx.branch());
+                event.llm_response.content = Some(Content::new("model").with_text(name));
+                Ok(Box::pin(stream::iter(vec![Ok(event)])) as adk_core::EventStream)
+            })
+            .build()
+            .unwrap()
+    };
+    let parallel =
+        ParallelAgent::new("fanout", vec![Arc::new(reporter("web")), Arc::new(reporter("news"))]);
+
+    let ctx = Arc::new(TestContext::new("test"));
+    let mut stream = parallel.run(ctx).await.unwrap();
+
+    use futures::StreamExt;
+    let mut events = Vec::new();
+    while let Some(result) = stream.next().await {
+        events.push(result.unwrap());
+    }
+    events.sort_by(|a, b| a.author.cmp(&b.author));
+
+    let seen: Vec<_> = events.iter().map(|e| (e.author.as_str(), e.branch.as_str())).collect();
+    assert_eq!(seen, [("news@fanout.news", "fanout.news"), ("web@fanout.web", "fanout.web")]);
+    let web = &events[1];
+    assert!(web.is_final_response_for("test-invocation", "fanout.web"));
+    assert!(!web.is_final_response_for("test-invocation", "fanout.news"));
+}
+
 #[tokio::test]
 async fn test_sequential_agent_empty() {
     let sequential = SequentialAgent::new("empty", vec![]);
@@ -424,8 +458,12 @@ async fn test_conditional_agent_if_branch() {
         events.push(result.unwrap());
     }
 
-    assert_eq!(events.len(), 1);
-    assert_eq!(events[0].author, "if_agent");
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0].branch_choice(),
+        Some(BranchChoice { arm: Some(1), agent: Some("if_agent".to_string()) })
+    );
+    assert_eq!(events[1].author, "if_agent");
 }
 
 #[tokio::test]
@@ -460,8 +498,12 @@ async fn test_conditional_agent_else_branch() {
         events.push(result.unwrap());
     }
 
-    assert_eq!(events.len(), 1);
-    assert_eq!(events[0].author, "else_agent");
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0].branch_choice(),
+        Some(BranchChoice { arm: None, agent: Some("else_agent".to_string()) })
+    );
+    assert_eq!(events[1].author, "else_agent");
 }
 
 #[tokio::test]
@@ -481,8 +523,9 @@ async fn test_conditional_agent_no_else() {
     let mut stream = conditional.run(ctx).await.unwrap();
 
     use futures::StreamExt;
-    let result = stream.next().await;
-    assert!(result.is_none());
+    let choice = stream.next().await.unwrap().unwrap();
+    assert_eq!(choice.branch_choice(), Some(BranchChoice { arm: None, agent: None }));
+    assert!(stream.next().await.is_none());
 }
 
 #[cfg(feature = "skills")]
@@ -622,6 +665,8 @@ async fn test_conditional_agent_with_skills_injects_user_content() {
     let ctx = Arc::new(TestContext::new("please search this repo"));
     let mut stream = conditional.run(ctx).await.unwrap();
     use futures::StreamExt;
+    let choice = stream.next().await.unwrap().unwrap();
+    assert!(choice.branch_choice().is_some());
     let event = stream.next().await.unwrap().unwrap();
     let text = event
         .llm_response
@@ -1,6 +1,8 @@
 //! Browser configuration options.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 /// Configuration for browser sessions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +36,48 @@ pub struct BrowserConfig {
 
     /// Additional browser arguments
     pub browser_args: Vec<String>,
+
+    /// Directory the browser saves downloads into.
+    ///
+    /// Must be an absolute path that is visible both to the browser and to
+    /// this process (for a remote WebDriver, a shared volume).
+    #[serde(default)]
+    pub download_dir: Option<PathBuf>,
+
+    /// Mask common automation fingerprints (best-effort).
+    ///
+    /// See [`with_stealth`](Self::with_stealth).
+    #[serde(default)]
+    pub stealth: bool,
+
+    /// Extra HTTP headers sent with every request.
+    ///
+    /// Applied through the DevTools protocol, so only Chrome and Edge honor them.
+    #[serde(default)]
+    pub extra_headers: BTreeMap<String, String>,
+
+    /// Position reported by `navigator.geolocation` (Chrome and Edge only).
+    #[serde(default)]
+    pub geolocation: Option<Geolocation>,
+
+    /// IANA time zone pages run in, e.g. "Asia/Tokyo" (Chrome and Edge only).
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// Locale used by `Intl` and date formatting, e.g. "fr-FR" (Chrome and Edge only).
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// An emulated position.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geolocation {
+    /// Latitude in degrees, between -90 and 90.
+    pub latitude: f64,
+    /// Longitude in degrees, between -180 and 180.
+    pub longitude: f64,
+    /// Accuracy radius in meters.
+    pub accuracy: f64,
 }
 
 /// Supported browser types.
@@ -59,6 +103,12 @@ impl Default for BrowserConfig {
             implicit_wait_secs: 10,
             user_agent: None,
             browser_args: Vec::new(),
+            download_dir: None,
+            stealth: false,
+            extra_headers: BTreeMap::new(),
+            geolocation: None,
+            timezone: None,
+            locale: None,
         }
     }
 }
@@ -112,6 +162,64 @@ impl BrowserConfig {
         self
     }
 
+    /// Set the directory downloads are saved into.
+    ///
+    /// Required for [`WaitForDownloadTool`](crate::WaitForDownloadTool).
+    pub fn download_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.download_dir = Some(dir.into());
+        self
+    }
+
+    /// Enable or disable stealth mode.
+    ///
+    /// Stealth mode injects a script into every page that hides
+    /// `navigator.webdriver`, reports realistic languages and plugins, and
+    /// varies minor fingerprint values per session. On Chrome it also drops
+    /// the automation switches and, unless [`user_agent`](Self::user_agent)
+    /// is set, replaces the `HeadlessChrome` user agent with
+    /// [`STEALTH_USER_AGENT`](crate::STEALTH_USER_AGENT).
+    ///
+    /// This is best-effort: it defeats simple checks, not dedicated bot
+    /// detection. Chrome and Edge inject the script before page scripts run;
+    /// other browsers only after each navigation completes.
+    pub fn with_stealth(mut self, enabled: bool) -> Self {
+        self.stealth = enabled;
+        self
+    }
+
+    /// Add an HTTP header sent with every request (Chrome and Edge only).
+    pub fn extra_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Emulate a position at `latitude`, `longitude` with an `accuracy`
+    /// radius in meters (Chrome and Edge only).
+    ///
+    /// Pages are also granted the geolocation permission, so
+    /// `navigator.geolocation` answers without a prompt.
+    pub fn geolocation(mut self, latitude: f64, longitude: f64, accuracy: f64) -> Self {
+        self.geolocation = Some(Geolocation { latitude, longitude, accuracy });
+        self
+    }
+
+    /// Emulate an IANA time zone such as "America/New_York" (Chrome and Edge only).
+    pub fn timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// Emulate a locale such as "de-DE" (Chrome and Edge only).
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Returns `true` if any geolocation, time zone, or locale emulation is set.
+    pub fn has_emulation(&self) -> bool {
+        self.geolocation.is_some() || self.timezone.is_some() || self.locale.is_some()
+    }
+
     /// Configure for observable mode (noVNC-compatible).
     ///
     /// Sets `headless: false` and viewport to 1280x720 for noVNC viewing.
@@ -143,6 +251,43 @@ mod tests {
         assert_eq!(config.viewport_width, 1280);
     }
 
+    #[test]
+    fn test_download_dir() {
+        assert!(BrowserConfig::default().download_dir.is_none());
+        let config = BrowserConfig::new().download_dir("/tmp/adk-downloads");
+        assert_eq!(config.download_dir, Some(PathBuf::from("/tmp/adk-downloads")));
+    }
+
+    #[test]
+    fn test_stealth_and_extra_headers() {
+        let config = BrowserConfig::default();
+        assert!(!config.stealth);
+        assert!(config.extra_headers.is_empty());
+
+        let config = BrowserConfig::new()
+            .with_stealth(true)
+            .extra_header("Accept-Language", "en-US,en;q=0.9");
+        assert!(config.stealth);
+        assert_eq!(config.extra_headers["Accept-Language"], "en-US,en;q=0.9");
+    }
+
+    #[test]
+    fn test_emulation() {
+        assert!(!BrowserConfig::default().has_emulation());
+
+        let config = BrowserConfig::new()
+            .geolocation(35.68, 139.69, 50.0)
+            .timezone("Asia/Tokyo")
+            .locale("ja-JP");
+        assert!(config.has_emulation());
+        assert_eq!(
+            config.geolocation,
+            Some(Geolocation { latitude: 35.68, longitude: 139.69, accuracy: 50.0 })
+        );
+        assert_eq!(config.timezone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(config.locale.as_deref(), Some("ja-JP"));
+    }
+
     #[test]
     fn test_observable() {
         let config = BrowserConfig::new().observable();
@@ -0,0 +1,242 @@
+//! Tracking files the browser saves into the configured download directory.
+
+use adk_core::{AdkError, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the download directory is polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Suffixes browsers use for files that are still being written.
+///
+/// Chrome and Edge write to `*.crdownload` and rename on completion, Firefox
+/// writes to `*.part` next to an empty placeholder, Safari uses `*.download`.
+const IN_PROGRESS_SUFFIXES: &[&str] = &[".crdownload", ".part", ".download", ".tmp"];
+
+/// A completed download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedFile {
+    /// File name as saved by the browser.
+    pub filename: String,
+    /// Full path inside the download directory.
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+    /// MIME type guessed from the file extension.
+    pub mime_type: String,
+}
+
+/// Watches a download directory for files that appear after it was prepared.
+pub(crate) struct DownloadTracker {
+    dir: PathBuf,
+    seen: Mutex<HashSet<PathBuf>>,
+}
+
+impl DownloadTracker {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir, seen: Mutex::new(HashSet::new()) }
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Create the directory and ignore everything already in it.
+    pub(crate) fn prepare(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            AdkError::tool(format!(
+                "Failed to create download directory '{}': {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+        let existing = self.scan()?;
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.extend(existing.files.into_iter().map(|(path, _, _)| path));
+        Ok(())
+    }
+
+    /// Wait until a new download has finished and return it.
+    ///
+    /// Downloads are handed out oldest first and each one only once. A file
+    /// counts as finished when no in-progress files remain and its size is
+    /// unchanged between two polls.
+    pub(crate) async fn wait_for_new(&self, timeout: Duration) -> Result<DownloadedFile> {
+        let deadline = Instant::now() + timeout;
+        let mut candidate: Option<(PathBuf, u64)> = None;
+
+        loop {
+            let scan = self.scan()?;
+            let next = {
+                let seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+                scan.files
+                    .into_iter()
+                    .filter(|(path, _, _)| !seen.contains(path))
+                    .min_by_key(|(_, _, modified)| *modified)
+                    .map(|(path, size, _)| (path, size))
+            };
+
+            if scan.in_progress {
+                candidate = None;
+            } else if let Some(next) = next {
+                if candidate.as_ref() == Some(&next) {
+                    let (path, size) = next;
+                    self.seen.lock().unwrap_or_else(|e| e.into_inner()).insert(path.clone());
+                    return Ok(downloaded_file(path, size));
+                }
+                candidate = Some(next);
+            }
+
+            if Instant::now() >= deadline {
+                let reason = if scan.in_progress {
+                    "a download is still in progress"
+                } else {
+                    "no new download appeared"
+                };
+                return Err(AdkError::tool(format!(
+                    "Timed out after {}s waiting for a download in '{}': {}",
+                    timeout.as_secs(),
+                    self.dir.display(),
+                    reason
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    fn scan(&self) -> Result<Scan> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| {
+            AdkError::tool(format!(
+                "Failed to read download directory '{}': {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+
+        let mut scan = Scan::default();
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else { continue };
+            if !metadata.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_in_progress(&name) {
+                scan.in_progress = true;
+                continue;
+            }
+            // Hidden files are browser bookkeeping (e.g. Chrome's `.com.google.Chrome.*`).
+            if name.starts_with('.') {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            scan.files.push((entry.path(), metadata.len(), modified));
+        }
+        Ok(scan)
+    }
+}
+
+#[derive(Default)]
+struct Scan {
+    files: Vec<(PathBuf, u64, SystemTime)>,
+    in_progress: bool,
+}
+
+fn is_in_progress(name: &str) -> bool {
+    IN_PROGRESS_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+fn downloaded_file(path: PathBuf, size: u64) -> DownloadedFile {
+    let filename = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mime_type = mime_type_for(&path).to_string();
+    DownloadedFile { filename, path, size, mime_type }
+}
+
+/// Guess a MIME type from the file extension.
+pub(crate) fn mime_type_for(path: &Path) -> &'static str {
+    let extension =
+        path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "csv" => "text/csv",
+        "txt" | "log" => "text/plain",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("adk-browser-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_crdownload_to_finish() {
+        let dir = temp_dir("crdownload");
+        std::fs::write(dir.join("old.csv"), "already here").unwrap();
+        let tracker = DownloadTracker::new(dir.clone());
+        tracker.prepare().unwrap();
+
+        let partial = dir.join("report.csv.crdownload");
+        std::fs::write(&partial, "a,b").unwrap();
+        let writer = {
+            let dir = dir.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(400)).await;
+                std::fs::write(&partial, "a,b\n1,2\n").unwrap();
+                std::fs::rename(&partial, dir.join("report.csv")).unwrap();
+            })
+        };
+
+        let file = tracker.wait_for_new(Duration::from_secs(5)).await.unwrap();
+        writer.await.unwrap();
+
+        assert_eq!(file.filename, "report.csv");
+        assert_eq!(file.size, 8);
+        assert_eq!(file.mime_type, "text/csv");
+
+        // Each download is returned once; nothing else arrives.
+        let err = tracker.wait_for_new(Duration::from_millis(300)).await.unwrap_err();
+        assert!(err.to_string().contains("no new download appeared"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_times_out_while_in_progress() {
+        let dir = temp_dir("stalled");
+        let tracker = DownloadTracker::new(dir.clone());
+        tracker.prepare().unwrap();
+        std::fs::write(dir.join("big.zip.crdownload"), "partial").unwrap();
+
+        let err = tracker.wait_for_new(Duration::from_millis(300)).await.unwrap_err();
+        assert!(err.to_string().contains("still in progress"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_mime_type_for() {
+        assert_eq!(mime_type_for(Path::new("a/Invoice.PDF")), "application/pdf");
+        assert_eq!(mime_type_for(Path::new("data.bin")), "application/octet-stream");
+        assert!(is_in_progress("file.zip.part"));
+        assert!(!is_in_progress("file.zip"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Filling several form fields in one round-trip.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Outcome of filling one field with [`BrowserSession::fill_form`](crate::BrowserSession::fill_form).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormFieldResult {
+    /// The identifier the field was requested by.
+    pub field: String,
+    /// Whether the field was filled.
+    pub status: FormFieldStatus,
+    /// How the identifier was matched: `name`, `id`, `label`, `legend`,
+    /// `aria-label`, `placeholder`, or `css`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_by: Option<String>,
+    /// The field's value after filling: text, the selected option(s), or the
+    /// checked state of a checkbox.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// Why the field was not filled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Status of one field in a [`FormFieldResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormFieldStatus {
+    /// The field now holds the requested value.
+    Filled,
+    /// No form control matched the identifier.
+    NotFound,
+    /// A control matched but could not take the value, for example a
+    /// non-boolean for a checkbox or an option the select does not offer.
+    Failed,
+}
+
+/// Fills the fields in `arguments[0]` and returns a result per field.
+///
+/// Each identifier is tried, in order, as a `name`, an `id`, the text of a
+/// `<label>` (or `aria-labelledby`), a fieldset `<legend>`, an `aria-label`,
+/// a placeholder, and finally a CSS selector for a form control. Values are set through the
+/// native setters and announced with `input` and `change` events so
+/// framework-managed inputs see them; checkboxes and radios are clicked.
+pub(crate) const FILL_FORM_SCRIPT: &str = r#"
+var fields = arguments[0];
+var controls = Array.prototype.slice.call(document.querySelectorAll('input, select, textarea'));
+var unfillable = ['button', 'file', 'hidden', 'image', 'reset', 'submit'];
+
+function norm(text) { return (text || '').replace(/\s+/g, ' ').trim().toLowerCase(); }
+
+function labels(el) {
+    var texts = [];
+    if (el.labels) {
+        for (var i = 0; i < el.labels.length; i++) texts.push(norm(el.labels[i].textContent));
+    }
+    (el.getAttribute('aria-labelledby') || '').split(/\s+/).forEach(function (id) {
+        var label = id && document.getElementById(id);
+        if (label) texts.push(norm(label.textContent));
+    });
+    return texts;
+}
+
+function legend(el) {
+    var fieldset = el.closest('fieldset');
+    var caption = fieldset && fieldset.querySelector('legend');
+    return caption ? norm(caption.textContent) : null;
+}
+
+function resolve(key) {
+    var wanted = norm(key);
+    var strategies = [
+        ['name', function (el) { return el.name === key; }],
+        ['id', function (el) { return el.id === key; }],
+        ['label', function (el) { return labels(el).indexOf(wanted) >= 0; }],
+        ['legend', function (el) { return legend(el) === wanted; }],
+        ['aria-label', function (el) { return norm(el.getAttribute('aria-label')) === wanted; }],
+        ['placeholder', function (el) { return norm(el.getAttribute('placeholder')) === wanted; }]
+    ];
+    for (var i = 0; i < strategies.length; i++) {
+        var matches = controls.filter(strategies[i][1]);
+        if (matches.length) return { by: strategies[i][0], elements: matches };
+    }
+    try {
+        var css = Array.prototype.filter.call(document.querySelectorAll(key), function (el) {
+            return controls.indexOf(el) >= 0;
+        });
+        if (css.length) return { by: 'css', elements: css };
+    } catch (e) {}
+    return null;
+}
+
+function announce(el) {
+    el.dispatchEvent(new Event('input', { bubbles: true }));
+    el.dispatchEvent(new Event('change', { bubbles: true }));
+}
+
+function failed(message) { return { status: 'failed', error: message }; }
+
+function fillSelect(el, value) {
+    var wanted = Array.isArray(value) ? value : [value];
+    if (wanted.some(function (v) { return typeof v !== 'string' && typeof v !== 'number'; })) {
+        return failed('expected an option value or text');
+    }
+    if (wanted.length > 1 && !el.multiple) return failed('select accepts a single option');
+    var chosen = wanted.map(function (v) {
+        return Array.prototype.find.call(el.options, function (option) {
+            return option.value === String(v) || norm(option.text) === norm(String(v));
+        });
+    });
+    var missing = wanted.filter(function (v, i) { return !chosen[i]; });
+    if (missing.length) return failed('no option matches ' + JSON.stringify(missing[0]));
+    Array.prototype.forEach.call(el.options, function (option) {
+        option.selected = chosen.indexOf(option) >= 0;
+    });
+    announce(el);
+    var selected = Array.prototype.filter.call(el.options, function (o) { return o.selected; })
+        .map(function (o) { return o.value; });
+    return { status: 'filled', value: el.multiple ? selected : selected[0] };
+}
+
+function fillRadio(elements, value) {
+    var el = elements[0];
+    var group = elements.length === 1 && el.name
+        ? Array.prototype.slice.call((el.form || document).querySelectorAll('input[type=radio]'))
+            .filter(function (r) { return r.name === el.name; })
+        : elements;
+    var target;
+    if (value === true && elements.length === 1) {
+        target = el;
+    } else if (typeof value === 'string' || typeof value === 'number') {
+        target = group.find(function (r) {
+            return r.value === String(value) || labels(r).indexOf(norm(String(value))) >= 0;
+        });
+        if (!target) return failed('no radio option matches ' + JSON.stringify(value));
+    } else {
+        return failed('expected the value or label of a radio option');
+    }
+    if (target.disabled) return failed('radio option is disabled');
+    if (!target.checked) target.click();
+    return { status: 'filled', value: target.value };
+}
+
+function fill(elements, value) {
+    var el = elements[0];
+    var type = (el.type || '').toLowerCase();
+    if (el.disabled) return failed('field is disabled');
+    if (el.tagName === 'SELECT') return fillSelect(el, value);
+    if (type === 'radio') return fillRadio(elements, value);
+    if (type === 'checkbox') {
+        if (typeof value !== 'boolean') return failed('expected true or false for a checkbox');
+        if (el.checked !== value) el.click();
+        return { status: 'filled', value: el.checked };
+    }
+    if (el.tagName !== 'TEXTAREA' && unfillable.indexOf(type) >= 0) {
+        return failed('cannot fill an input of type ' + type);
+    }
+    if (el.readOnly) return failed('field is read-only');
+    if (typeof value !== 'string' && typeof value !== 'number') return failed('expected text');
+    var proto = el.tagName === 'TEXTAREA' ? HTMLTextAreaElement.prototype : HTMLInputElement.prototype;
+    Object.getOwnPropertyDescriptor(proto, 'value').set.call(el, String(value));
+    announce(el);
+    if (el.value !== String(value)) return failed('field rejected the value, now ' + JSON.stringify(el.value));
+    return { status: 'filled', value: el.value };
+}
+
+return Object.keys(fields).map(function (key) {
+    var match = resolve(key);
+    if (!match) return { field: key, status: 'not_found', error: 'no form field matches' };
+    var result = fill(match.elements, fields[key]);
+    result.field = key;
+    result.matched_by = match.by;
+    return result;
+});
+"#;
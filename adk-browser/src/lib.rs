@@ -55,6 +55,7 @@
 //! - `browser_type` - Type text into an input
 //! - `browser_clear` - Clear an input field
 //! - `browser_select` - Select from a dropdown
+//! - `browser_fill_form` - Fill several form fields in one call
 //!
 //! ### Extraction
 //! - `browser_extract_text` - Get text from elements
@@ -62,6 +63,7 @@
 //! - `browser_extract_links` - Get all links on page
 //! - `browser_page_info` - Get current URL and title
 //! - `browser_page_source` - Get HTML source
+//! - `browser_reader_content` - Get the main article text, title, and byline
 //!
 //! ### Screenshots
 //! - `browser_screenshot` - Capture page or element screenshot
@@ -100,6 +102,9 @@
 //! - `browser_switch_to_parent_frame` - Exit current iframe
 //! - `browser_switch_to_default_content` - Exit all iframes
 //!
+//! Element tools also accept a `frame_path` of iframe selectors or indices.
+//! They act inside that frame and then return to the main page.
+//!
 //! ### Advanced Actions
 //! - `browser_drag_and_drop` - Drag and drop elements
 //! - `browser_right_click` - Right-click (context menu)
@@ -108,6 +113,8 @@
 //! - `browser_press_key` - Press keyboard keys
 //! - `browser_file_upload` - Upload files
 //! - `browser_print_to_pdf` - Print page to PDF
+//! - `browser_wait_for_download` - Wait for a download to finish (needs `BrowserConfig::download_dir`)
+//! - `browser_set_geolocation` - Change the emulated location (Chrome and Edge only)
 //!
 //! ## Requirements
 //!
@@ -162,17 +169,25 @@
 //! ```
 
 mod config;
+mod download;
 mod escape;
+mod form;
 mod pool;
+mod reader;
 mod session;
+mod stealth;
 pub mod tools;
 mod toolset;
 
 // Re-export main types
-pub use config::{BrowserConfig, BrowserType};
+pub use config::{BrowserConfig, BrowserType, Geolocation};
+pub use download::DownloadedFile;
 pub use escape::escape_js_string;
-pub use pool::BrowserSessionPool;
-pub use session::{BrowserSession, ElementState, shared_session};
+pub use form::{FormFieldResult, FormFieldStatus};
+pub use pool::{BrowserPool, BrowserSessionPool, PooledSession};
+pub use reader::{ReaderContent, extract_reader_content};
+pub use session::{BrowserSession, ElementState, FrameSelector, shared_session};
+pub use stealth::{STEALTH_LANGUAGES, STEALTH_USER_AGENT, stealth_script};
 pub use toolset::{BrowserProfile, BrowserToolset, minimal_browser_tools, readonly_browser_tools};
 
 // Re-export individual tools for selective use
@@ -200,6 +215,7 @@ pub use tools::{
     ExtractLinksTool,
     ExtractTextTool,
     FileUploadTool,
+    FillFormTool,
     FocusTool,
     ForwardTool,
     GetCookieTool,
@@ -215,12 +231,14 @@ pub use tools::{
     PageSourceTool,
     PressKeyTool,
     PrintToPdfTool,
+    ReaderContentTool,
     RefreshTool,
     RightClickTool,
     // Screenshots
     ScreenshotTool,
     ScrollTool,
     SelectTool,
+    SetGeolocationTool,
     SetWindowSizeTool,
     // Frames
     SwitchToDefaultContentTool,
@@ -229,6 +247,7 @@ pub use tools::{
     SwitchWindowTool,
     TypeTool,
     // Waiting
+    WaitForDownloadTool,
     WaitForElementTool,
     WaitForPageLoadTool,
     WaitForTextTool,
@@ -238,7 +257,7 @@ pub use tools::{
 /// Prelude module for convenient imports.
 pub mod prelude {
     pub use crate::config::{BrowserConfig, BrowserType};
-    pub use crate::pool::BrowserSessionPool;
+    pub use crate::pool::{BrowserPool, BrowserSessionPool, PooledSession};
     pub use crate::session::{BrowserSession, shared_session};
     pub use crate::toolset::{
         BrowserProfile, BrowserToolset, minimal_browser_tools, readonly_browser_tools,
@@ -1,15 +1,21 @@
-//! Browser session pool for multi-user environments.
+//! Browser session pools.
 //!
-//! Provides per-user session isolation by managing a pool of `BrowserSession`
-//! instances keyed by user ID. Sessions are created lazily on first access
-//! and can be released individually or cleaned up in bulk.
+//! [`BrowserSessionPool`] provides per-user session isolation by managing a
+//! pool of `BrowserSession` instances keyed by user ID. Sessions are created
+//! lazily on first access and can be released individually or cleaned up in bulk.
+//!
+//! [`BrowserPool`] manages a fixed number of interchangeable sessions with a
+//! checkout/return API for running page operations in parallel.
 
 use crate::config::BrowserConfig;
 use crate::session::BrowserSession;
 use adk_core::{AdkError, Result};
+use futures::StreamExt;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex as StdMutex, MutexGuard};
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
 
 /// A pool of browser sessions keyed by user ID.
 ///
@@ -115,9 +121,202 @@ impl BrowserSessionPool {
     }
 }
 
+/// A fixed-size pool of interchangeable browser sessions.
+///
+/// Unlike [`BrowserSessionPool`], sessions are not tied to a user. Callers
+/// check a session out, drive it, and check it back in so the next caller can
+/// reuse the same WebDriver connection. At most `size` sessions exist at once;
+/// `checkout` waits when all of them are in use.
+///
+/// Sessions that crashed (the WebDriver no longer answers) are discarded on
+/// return and replaced with a fresh session on the next checkout.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_browser::{BrowserConfig, BrowserPool};
+///
+/// let pool = BrowserPool::new(BrowserConfig::default(), 4);
+///
+/// let titles = pool
+///     .map_urls(urls, 4, |session, _url| async move { session.title().await })
+///     .await;
+///
+/// pool.close().await;
+/// ```
+pub struct BrowserPool {
+    config: BrowserConfig,
+    idle: Arc<StdMutex<Vec<Arc<BrowserSession>>>>,
+    permits: Arc<Semaphore>,
+    size: usize,
+}
+
+/// A session checked out of a [`BrowserPool`].
+///
+/// Holds one of the pool's slots for as long as it lives. Hand it back with
+/// [`BrowserPool::checkin`] to have its liveness checked right away; dropping
+/// it (for example when the caller's future is cancelled) returns the session
+/// to the idle list and frees the slot, and the next checkout replaces it if
+/// it has crashed in the meantime.
+pub struct PooledSession {
+    session: Option<Arc<BrowserSession>>,
+    idle: Arc<StdMutex<Vec<Arc<BrowserSession>>>>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PooledSession {
+    /// The underlying session, for callers that need a shared handle.
+    pub fn session(&self) -> &Arc<BrowserSession> {
+        self.session.as_ref().expect("pooled session is present until dropped")
+    }
+}
+
+impl Deref for PooledSession {
+    type Target = BrowserSession;
+
+    fn deref(&self) -> &BrowserSession {
+        self.session()
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            lock_idle(&self.idle).push(session);
+        }
+    }
+}
+
+fn lock_idle(
+    idle: &StdMutex<Vec<Arc<BrowserSession>>>,
+) -> MutexGuard<'_, Vec<Arc<BrowserSession>>> {
+    idle.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl BrowserPool {
+    /// Create a new pool holding at most `size` sessions.
+    ///
+    /// Sessions are started lazily on checkout. A `size` of zero is treated as one.
+    pub fn new(config: BrowserConfig, size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            config,
+            idle: Arc::new(StdMutex::new(Vec::with_capacity(size))),
+            permits: Arc::new(Semaphore::new(size)),
+            size,
+        }
+    }
+
+    /// Maximum number of sessions in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of sessions currently checked out.
+    pub fn in_use(&self) -> usize {
+        self.size - self.permits.available_permits()
+    }
+
+    /// Number of started sessions waiting to be reused.
+    pub async fn idle_count(&self) -> usize {
+        lock_idle(&self.idle).len()
+    }
+
+    /// Check out a live session, waiting if every session is in use.
+    ///
+    /// An idle session is reused when it still responds; a crashed one is
+    /// stopped and replaced by a freshly started session. The slot is held
+    /// until the returned [`PooledSession`] is passed to
+    /// [`checkin`](Self::checkin) or dropped.
+    pub async fn checkout(&self) -> Result<PooledSession> {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| AdkError::tool("Browser pool is closed"))?;
+
+        let session = self.take_live_session().await?;
+        Ok(PooledSession { session: Some(session), idle: self.idle.clone(), _permit: permit })
+    }
+
+    /// Return a session previously obtained from [`checkout`](Self::checkout).
+    ///
+    /// Sessions that no longer respond are stopped and dropped so the pool
+    /// replaces them on the next checkout.
+    pub async fn checkin(&self, mut pooled: PooledSession) {
+        let Some(session) = pooled.session.take() else { return };
+        if session.is_active().await {
+            lock_idle(&self.idle).push(session);
+        } else {
+            tracing::warn!("discarding crashed browser session returned to pool");
+            session.stop().await.ok();
+        }
+    }
+
+    /// Navigate to each URL on a pooled session and run `f` against the loaded page.
+    ///
+    /// At most `concurrency` URLs are processed at once (further bounded by the
+    /// pool size), and sessions are reused across URLs. Results are returned in
+    /// the same order as `urls`; a failure for one URL does not affect the others.
+    /// Dropping the returned future releases every slot it held.
+    pub async fn map_urls<I, S, F, Fut, T>(
+        &self,
+        urls: I,
+        concurrency: usize,
+        f: F,
+    ) -> Vec<Result<T>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        F: Fn(Arc<BrowserSession>, String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let f = &f;
+        futures::stream::iter(urls.into_iter().map(Into::into))
+            .map(|url: String| async move {
+                let pooled = self.checkout().await?;
+                let result = match pooled.navigate(&url).await {
+                    Ok(()) => f(pooled.session().clone(), url).await,
+                    Err(e) => Err(e),
+                };
+                self.checkin(pooled).await;
+                result
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Stop all idle sessions. Call during graceful shutdown once every
+    /// checked-out session has been returned.
+    pub async fn close(&self) {
+        let idle: Vec<_> = lock_idle(&self.idle).drain(..).collect();
+        for session in idle {
+            session.stop().await.ok();
+        }
+    }
+
+    async fn take_live_session(&self) -> Result<Arc<BrowserSession>> {
+        let reusable = lock_idle(&self.idle).pop();
+        if let Some(session) = reusable {
+            if session.is_active().await {
+                return Ok(session);
+            }
+            tracing::warn!("replacing crashed browser session in pool");
+            session.stop().await.ok();
+        }
+
+        let session = Arc::new(BrowserSession::new(self.config.clone()));
+        session.start().await?;
+        Ok(session)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_pool_creation() {
@@ -150,4 +349,103 @@ mod tests {
         pool.cleanup_all().await;
         assert_eq!(pool.active_count().await, 0);
     }
+
+    #[test]
+    fn test_browser_pool_creation() {
+        let pool = BrowserPool::new(BrowserConfig::default(), 3);
+        assert_eq!(pool.size(), 3);
+        assert_eq!(pool.in_use(), 0);
+    }
+
+    #[test]
+    fn test_browser_pool_zero_size_clamped() {
+        let pool = BrowserPool::new(BrowserConfig::default(), 0);
+        assert_eq!(pool.size(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_browser_pool_checkout_failure_releases_slot() {
+        let config = BrowserConfig::new().webdriver_url("http://127.0.0.1:1");
+        let pool = BrowserPool::new(config, 1);
+
+        assert!(pool.checkout().await.is_err());
+        assert_eq!(pool.in_use(), 0);
+        assert_eq!(pool.idle_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_browser_pool_map_urls_reports_per_url_errors() {
+        let config = BrowserConfig::new().webdriver_url("http://127.0.0.1:1");
+        let pool = BrowserPool::new(config, 2);
+
+        let results = pool
+            .map_urls(
+                ["https://example.com", "https://example.org"],
+                2,
+                |_session, url| async move { Ok(url) },
+            )
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_err));
+        assert_eq!(pool.in_use(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_browser_pool_cancelled_map_urls_releases_slot() {
+        // A WebDriver endpoint that accepts connections but never answers, so
+        // session start hangs until the caller gives up.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+
+        let config = BrowserConfig::new().webdriver_url(format!("http://{addr}"));
+        let pool = BrowserPool::new(config, 1);
+
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(100),
+            pool.map_urls(["https://example.com"], 1, |_session, url| async move { Ok(url) }),
+        )
+        .await;
+        assert!(cancelled.is_err(), "map_urls should still be waiting on the WebDriver");
+        assert_eq!(pool.in_use(), 0);
+
+        server.abort();
+        let _ = server.await;
+
+        // The slot is free again, so checkout fails fast instead of waiting forever.
+        let checkout = tokio::time::timeout(Duration::from_secs(5), pool.checkout())
+            .await
+            .expect("checkout should not wait for a leaked slot");
+        assert!(checkout.is_err());
+        assert_eq!(pool.in_use(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+    async fn test_browser_pool_map_urls_concurrently() {
+        let pool = BrowserPool::new(BrowserConfig::default(), 2);
+        let urls = [
+            "data:text/html,<title>one</title>",
+            "data:text/html,<title>two</title>",
+            "data:text/html,<title>three</title>",
+            "data:text/html,<title>four</title>",
+        ];
+
+        let titles =
+            pool.map_urls(urls, 2, |session, _url| async move { session.title().await }).await;
+
+        let titles: Vec<String> =
+            titles.into_iter().map(|r| r.expect("page should load")).collect();
+        assert_eq!(titles, ["one", "two", "three", "four"]);
+        assert_eq!(pool.in_use(), 0);
+        assert!(pool.idle_count().await <= 2);
+
+        pool.close().await;
+    }
 }
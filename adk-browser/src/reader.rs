@@ -0,0 +1,285 @@
+//! Readability-style extraction of the main content of a page.
+//!
+//! Paragraphs are scored by length and comma count, and each score is
+//! credited to the paragraph's parent (in full) and grandparent (half). The
+//! highest-scoring container, discounted by its link density, is taken as the
+//! main content. Navigation, sidebars, ads, scripts, and similar boilerplate
+//! are skipped both when scoring and when collecting the text.
+
+use ego_tree::NodeId;
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+/// Average adult silent reading speed, in words per minute.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Main content shorter than this is not trusted.
+const MIN_CONFIDENT_CHARS: usize = 250;
+
+/// Paragraphs shorter than this do not contribute to scoring.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Elements that never hold article content.
+const SKIPPED_TAGS: &[&str] = &[
+    "aside", "button", "footer", "form", "header", "iframe", "nav", "noscript", "script", "select",
+    "style", "svg", "template",
+];
+
+/// ARIA roles of boilerplate landmarks.
+const SKIPPED_ROLES: &[&str] = &["banner", "complementary", "contentinfo", "dialog", "navigation"];
+
+/// Words in a class or id that mark boilerplate.
+const BOILERPLATE_WORDS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "cookie",
+    "cookies",
+    "footer",
+    "menu",
+    "modal",
+    "nav",
+    "navbar",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+    "sponsor",
+    "sponsored",
+    "subscribe",
+];
+
+/// Elements whose text is emitted as one block of the extracted content.
+const BLOCK_TAGS: &[&str] =
+    &["blockquote", "h1", "h2", "h3", "h4", "h5", "h6", "li", "p", "pre", "td", "th"];
+
+/// The main content of a page, as extracted by [`extract_reader_content`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReaderContent {
+    /// Page title, preferring `og:title` over `<title>` and the first `<h1>`.
+    pub title: Option<String>,
+    /// Author, from `<meta name="author">` or a byline element.
+    pub byline: Option<String>,
+    /// Main content as plain text, one block per paragraph or heading.
+    pub text: String,
+    /// Number of words in `text`.
+    pub word_count: usize,
+    /// Estimated reading time of `text`, rounded up to whole minutes.
+    pub reading_time_minutes: usize,
+    /// Whether a main content node was found with enough text to be trusted.
+    ///
+    /// When `false`, callers should fall back to the text of the whole body.
+    pub confident: bool,
+}
+
+/// Extract the title, byline, and main text of an HTML page.
+pub fn extract_reader_content(html: &str) -> ReaderContent {
+    let document = Html::parse_document(html);
+    let title = title(&document);
+    let byline = byline(&document);
+
+    let blocks = main_content(&document).map(collect_blocks).unwrap_or_default();
+    let text = blocks.join("\n\n");
+    let word_count = text.split_whitespace().count();
+
+    ReaderContent {
+        title,
+        byline,
+        confident: text.chars().count() >= MIN_CONFIDENT_CHARS,
+        reading_time_minutes: reading_time_minutes(word_count),
+        word_count,
+        text,
+    }
+}
+
+/// Minutes needed to read `word_count` words, rounded up.
+pub(crate) fn reading_time_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE)
+}
+
+fn title(document: &Html) -> Option<String> {
+    meta_content(document, r#"meta[property="og:title"]"#)
+        .or_else(|| first_text(document, "title"))
+        .or_else(|| first_text(document, "h1"))
+}
+
+fn byline(document: &Html) -> Option<String> {
+    meta_content(document, r#"meta[name="author"]"#).or_else(|| {
+        first_text(document, r#"[rel="author"], [itemprop="author"], .byline, .author"#)
+            .map(|text| text.trim_start_matches("By ").trim_start_matches("by ").to_string())
+            .filter(|text| text.chars().count() <= 100)
+    })
+}
+
+fn meta_content(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|element| element.value().attr("content"))
+        .map(collapse_whitespace)
+        .find(|content| !content.is_empty())
+}
+
+fn first_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .map(|element| collapse_whitespace(&visible_text(element)))
+        .find(|text| !text.is_empty())
+}
+
+/// The container with the highest paragraph score, discounted by link density.
+fn main_content(document: &Html) -> Option<ElementRef<'_>> {
+    let paragraphs = Selector::parse("p, pre, td, blockquote").ok()?;
+    let mut scores: HashMap<NodeId, (ElementRef<'_>, f64)> = HashMap::new();
+
+    for paragraph in document.select(&paragraphs) {
+        if is_boilerplate(paragraph) {
+            continue;
+        }
+        let text = collapse_whitespace(&visible_text(paragraph));
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (length as f64 / 100.0).min(3.0);
+
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        if let Some(parent) = ancestors.next() {
+            scores.entry(parent.id()).or_insert((parent, 0.0)).1 += score;
+        }
+        if let Some(grandparent) = ancestors.next() {
+            scores.entry(grandparent.id()).or_insert((grandparent, 0.0)).1 += score / 2.0;
+        }
+    }
+
+    scores
+        .into_values()
+        .map(|(element, score)| (element, score * (1.0 - link_density(element))))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(element, _)| element)
+}
+
+/// Share of an element's text that sits inside links.
+fn link_density(element: ElementRef<'_>) -> f64 {
+    let total = visible_text(element).chars().count();
+    if total == 0 {
+        return 0.0;
+    }
+    let Ok(links) = Selector::parse("a") else {
+        return 0.0;
+    };
+    let linked: usize = element.select(&links).map(|link| visible_text(link).chars().count()).sum();
+    linked as f64 / total as f64
+}
+
+/// Text of each block-level element under `root`, skipping boilerplate.
+fn collect_blocks(root: ElementRef<'_>) -> Vec<String> {
+    fn walk(element: ElementRef<'_>, blocks: &mut Vec<String>) {
+        for child in element.child_elements() {
+            if is_boilerplate_element(child) {
+                continue;
+            }
+            if BLOCK_TAGS.contains(&child.value().name()) {
+                let text = collapse_whitespace(&visible_text(child));
+                if !text.is_empty() {
+                    blocks.push(text);
+                }
+            } else {
+                walk(child, blocks);
+            }
+        }
+    }
+
+    let mut blocks = Vec::new();
+    walk(root, &mut blocks);
+    if blocks.is_empty() {
+        let text = collapse_whitespace(&visible_text(root));
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+    }
+    blocks
+}
+
+/// Text of `element`, leaving out boilerplate descendants such as scripts.
+fn visible_text(element: ElementRef<'_>) -> String {
+    let mut text = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(fragment) => text.push_str(fragment),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child)
+                    && !is_boilerplate_element(child)
+                {
+                    text.push_str(&visible_text(child));
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Whether `element` or any of its ancestors is boilerplate.
+fn is_boilerplate(element: ElementRef<'_>) -> bool {
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(is_boilerplate_element)
+}
+
+fn is_boilerplate_element(element: ElementRef<'_>) -> bool {
+    let value = element.value();
+    let name = value.name();
+    if SKIPPED_TAGS.contains(&name) {
+        return true;
+    }
+    // Page-level and explicit content containers often carry layout classes
+    // such as `has-sidebar`; never discard them by class.
+    if matches!(name, "html" | "body" | "main" | "article") {
+        return false;
+    }
+    if value.attr("role").is_some_and(|role| SKIPPED_ROLES.contains(&role)) {
+        return true;
+    }
+    value.classes().chain(value.id()).any(|name| {
+        name.split(['-', '_'])
+            .any(|word| BOILERPLATE_WORDS.contains(&word.to_ascii_lowercase().as_str()))
+    })
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boilerplate_words_match_whole_class_words() {
+        let document = Html::parse_fragment(
+            r#"<div class="nav-links">a</div><div class="canvas">b</div><div id="ad_slot">c</div>"#,
+        );
+        let divs = Selector::parse("div").unwrap();
+        let flagged: Vec<bool> = document.select(&divs).map(is_boilerplate_element).collect();
+        assert_eq!(flagged, [true, false, true]);
+    }
+
+    #[test]
+    fn test_short_page_is_not_confident() {
+        let content = extract_reader_content("<html><body><p>Just a short note.</p></body></html>");
+        assert!(!content.confident);
+        assert_eq!(content.reading_time_minutes, 0);
+    }
+}
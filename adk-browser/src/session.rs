@@ -1,11 +1,19 @@
 //! Browser session management wrapping thirtyfour WebDriver.
 
-use crate::config::{BrowserConfig, BrowserType};
+use crate::config::{BrowserConfig, BrowserType, Geolocation};
+use crate::download::{DownloadTracker, DownloadedFile};
 use crate::escape::escape_js_string;
+use crate::form::{FILL_FORM_SCRIPT, FormFieldResult};
+use crate::stealth::{CHROMIUM_STEALTH_ARGS, STEALTH_USER_AGENT, stealth_script};
 use adk_core::{AdkError, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
+use thirtyfour::common::capabilities::firefox::FirefoxPreferences;
 use thirtyfour::common::print::{PrintOrientation, PrintParameters};
+use thirtyfour::extensions::cdp::ChromeDevTools;
 use thirtyfour::prelude::*;
 use tokio::sync::RwLock;
 
@@ -22,6 +30,29 @@ pub struct ElementState {
     pub is_clickable: bool,
 }
 
+/// One step of a frame path: which iframe to enter from the current frame.
+///
+/// Deserializes from a JSON number (the frame index) or string (a CSS
+/// selector for the `<iframe>` element), so a path like `["#checkout", 0]`
+/// can come straight from tool arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FrameSelector {
+    /// Frame index (0-based) among the current frame's iframes.
+    Index(u16),
+    /// CSS selector for the iframe element.
+    Selector(String),
+}
+
+impl std::fmt::Display for FrameSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Selector(selector) => write!(f, "'{selector}'"),
+        }
+    }
+}
+
 /// A browser session that wraps thirtyfour's WebDriver.
 ///
 /// This is the core abstraction for browser automation in ADK.
@@ -29,6 +60,8 @@ pub struct ElementState {
 pub struct BrowserSession {
     driver: RwLock<Option<WebDriver>>,
     config: BrowserConfig,
+    downloads: Option<DownloadTracker>,
+    stealth_script: Option<String>,
 }
 
 impl BrowserSession {
@@ -37,7 +70,9 @@ impl BrowserSession {
     /// Note: This does not start the browser immediately.
     /// Call `start()` to initialize the WebDriver connection.
     pub fn new(config: BrowserConfig) -> Self {
-        Self { driver: RwLock::new(None), config }
+        let downloads = config.download_dir.clone().map(DownloadTracker::new);
+        let stealth_script = config.stealth.then(stealth_script);
+        Self { driver: RwLock::new(None), config, downloads, stealth_script }
     }
 
     /// Create a browser session with default configuration.
@@ -53,6 +88,16 @@ impl BrowserSession {
             return Ok(()); // Already started
         }
 
+        if let Some(downloads) = &self.downloads {
+            downloads.prepare()?;
+        }
+        if self.config.has_emulation() {
+            self.require_devtools("Geolocation, time zone, and locale emulation")?;
+        }
+        if let Some(geolocation) = &self.config.geolocation {
+            validate_geolocation(geolocation)?;
+        }
+
         let caps = self.build_capabilities()?;
         let driver = WebDriver::new(&self.config.webdriver_url, caps)
             .await
@@ -80,6 +125,8 @@ impl BrowserSession {
             .await
             .map_err(|e| AdkError::tool(format!("Failed to set viewport: {}", e)))?;
 
+        self.apply_devtools_setup(&driver).await?;
+
         *driver_guard = Some(driver);
         Ok(())
     }
@@ -175,12 +222,37 @@ impl BrowserSession {
         &self.config
     }
 
+    /// Get the configured download directory, if any.
+    pub fn download_dir(&self) -> Option<&Path> {
+        self.downloads.as_ref().map(DownloadTracker::dir)
+    }
+
+    /// Wait for the next download to finish in the configured download directory.
+    ///
+    /// Files already present when the session started are ignored, and each
+    /// completed download is returned only once. In-progress files such as
+    /// Chrome's `.crdownload` are waited on until the browser renames them.
+    pub async fn wait_for_download(&self, timeout_secs: u64) -> Result<DownloadedFile> {
+        let downloads = self.downloads.as_ref().ok_or_else(|| {
+            AdkError::tool("No download directory configured; set BrowserConfig::download_dir")
+        })?;
+        downloads.wait_for_new(Duration::from_secs(timeout_secs)).await
+    }
+
     /// Navigate to a URL.
     pub async fn navigate(&self, url: &str) -> Result<()> {
         let driver = self.live_driver().await?;
 
         driver.goto(url).await.map_err(|e| AdkError::tool(format!("Navigation failed: {}", e)))?;
 
+        // Without DevTools the stealth script can only run once the page has loaded.
+        if let Some(script) = &self.stealth_script
+            && !self.uses_devtools()
+            && let Err(e) = driver.execute(script, vec![]).await
+        {
+            tracing::debug!(error = %e, "stealth script injection failed");
+        }
+
         Ok(())
     }
 
@@ -634,6 +706,51 @@ impl BrowserSession {
             .map_err(|e| AdkError::tool(format!("Failed to switch to default content: {}", e)))
     }
 
+    /// Switch to the frame at `path`, starting from the top-level document.
+    ///
+    /// Each step enters an iframe of the frame reached so far. If a step
+    /// fails the session is returned to the default content and the error
+    /// names the step.
+    pub async fn enter_frame_path(&self, path: &[FrameSelector]) -> Result<()> {
+        self.switch_to_default_content().await?;
+        for (depth, frame) in path.iter().enumerate() {
+            let entered = match frame {
+                FrameSelector::Index(index) => self.switch_to_frame_by_index(*index).await,
+                FrameSelector::Selector(selector) => {
+                    self.switch_to_frame_by_selector(selector).await
+                }
+            };
+            if let Err(e) = entered {
+                let _ = self.switch_to_default_content().await;
+                return Err(AdkError::tool(format!(
+                    "Frame path step {depth} ({frame}) could not be entered: {e}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run `action` inside the frame at `path`, then switch back to the
+    /// default content whether or not it succeeded.
+    ///
+    /// An empty path runs `action` in the current frame without switching, so
+    /// manual frame switching keeps working.
+    pub async fn within_frames<T>(
+        &self,
+        path: &[FrameSelector],
+        action: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if path.is_empty() {
+            return action.await;
+        }
+        self.enter_frame_path(path).await?;
+        let result = action.await;
+        let restored = self.switch_to_default_content().await;
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+
     // =========================================================================
     // Advanced Actions
     // =========================================================================
@@ -774,6 +891,30 @@ impl BrowserSession {
             .map_err(|e| AdkError::tool(format!("File upload failed: {}", e)))
     }
 
+    /// Fill several form fields in one call.
+    ///
+    /// `fields` maps a field identifier (a `name`, `id`, label text, fieldset
+    /// legend, `aria-label`, placeholder, or CSS selector) to its value: text
+    /// or a number for text inputs and text areas, an option value or text
+    /// for selects (an array for multi-selects), `true`/`false` for
+    /// checkboxes, and an option value or label for radio groups. Each field
+    /// is reported separately, so unmatched fields and values of the wrong
+    /// type do not stop the others from being filled.
+    pub async fn fill_form(
+        &self,
+        fields: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Vec<FormFieldResult>> {
+        let driver = self.live_driver().await?;
+
+        let result = driver
+            .execute(FILL_FORM_SCRIPT, vec![serde_json::Value::Object(fields.clone())])
+            .await
+            .map_err(|e| AdkError::tool(format!("Form fill failed: {e}")))?;
+
+        serde_json::from_value(result.json().clone())
+            .map_err(|e| AdkError::tool(format!("Unexpected form fill result: {e}")))
+    }
+
     /// Print page to PDF.
     pub async fn print_to_pdf(&self, landscape: bool, scale: f64) -> Result<String> {
         let driver = self.live_driver().await?;
@@ -794,6 +935,106 @@ impl BrowserSession {
             .map_err(|e| AdkError::tool(format!("Print to PDF failed: {}", e)))
     }
 
+    /// Override the position reported by `navigator.geolocation` (Chrome and
+    /// Edge only).
+    ///
+    /// Applies to the current tab and grants pages the geolocation
+    /// permission. Use [`BrowserConfig::geolocation`] to emulate a position
+    /// from the start of the session.
+    pub async fn set_geolocation(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        accuracy: f64,
+    ) -> Result<()> {
+        self.require_devtools("Geolocation emulation")?;
+        let geolocation = Geolocation { latitude, longitude, accuracy };
+        validate_geolocation(&geolocation)?;
+
+        let driver = self.live_driver().await?;
+        apply_geolocation(&ChromeDevTools::new(driver.handle.clone()), &geolocation).await
+    }
+
+    /// Whether the browser supports the Chrome DevTools protocol.
+    fn uses_devtools(&self) -> bool {
+        matches!(self.config.browser, BrowserType::Chrome | BrowserType::Edge)
+    }
+
+    /// Fail with a clear error when `feature` needs DevTools and the browser
+    /// does not support them.
+    fn require_devtools(&self, feature: &str) -> Result<()> {
+        if self.uses_devtools() {
+            Ok(())
+        } else {
+            Err(AdkError::tool(format!(
+                "{feature} is only supported on Chrome and Edge, not {:?}",
+                self.config.browser
+            )))
+        }
+    }
+
+    /// Install the stealth script and extra headers through the DevTools
+    /// protocol, so they apply to every page before its own scripts run.
+    async fn apply_devtools_setup(&self, driver: &WebDriver) -> Result<()> {
+        if !self.uses_devtools() {
+            if !self.config.extra_headers.is_empty() {
+                tracing::warn!(
+                    browser = ?self.config.browser,
+                    "extra_headers are only supported on Chrome and Edge; ignoring"
+                );
+            }
+            return Ok(());
+        }
+
+        let devtools = ChromeDevTools::new(driver.handle.clone());
+        if let Some(script) = &self.stealth_script {
+            devtools
+                .execute_cdp_with_params(
+                    "Page.addScriptToEvaluateOnNewDocument",
+                    serde_json::json!({ "source": script }),
+                )
+                .await
+                .map_err(|e| AdkError::tool(format!("Failed to install stealth script: {}", e)))?;
+        }
+        if !self.config.extra_headers.is_empty() {
+            devtools
+                .execute_cdp("Network.enable")
+                .await
+                .map_err(|e| AdkError::tool(format!("Failed to enable network domain: {}", e)))?;
+            devtools
+                .execute_cdp_with_params(
+                    "Network.setExtraHTTPHeaders",
+                    serde_json::json!({ "headers": self.config.extra_headers }),
+                )
+                .await
+                .map_err(|e| AdkError::tool(format!("Failed to set extra headers: {}", e)))?;
+        }
+        if let Some(geolocation) = &self.config.geolocation {
+            apply_geolocation(&devtools, geolocation).await?;
+        }
+        if let Some(timezone) = &self.config.timezone {
+            devtools
+                .execute_cdp_with_params(
+                    "Emulation.setTimezoneOverride",
+                    serde_json::json!({ "timezoneId": timezone }),
+                )
+                .await
+                .map_err(|e| {
+                    AdkError::tool(format!("Failed to set time zone '{}': {}", timezone, e))
+                })?;
+        }
+        if let Some(locale) = &self.config.locale {
+            devtools
+                .execute_cdp_with_params(
+                    "Emulation.setLocaleOverride",
+                    serde_json::json!({ "locale": locale }),
+                )
+                .await
+                .map_err(|e| AdkError::tool(format!("Failed to set locale '{}': {}", locale, e)))?;
+        }
+        Ok(())
+    }
+
     /// Build browser capabilities based on configuration.
     fn build_capabilities(&self) -> Result<Capabilities> {
         let caps = match self.config.browser {
@@ -809,17 +1050,32 @@ impl BrowserSession {
                 caps.add_arg("--disable-dev-shm-usage")
                     .map_err(|e| AdkError::tool(format!("Failed to add disable-dev-shm: {}", e)))?;
 
-                if let Some(ref ua) = self.config.user_agent {
+                let user_agent = self
+                    .config
+                    .user_agent
+                    .as_deref()
+                    .or(self.config.stealth.then_some(STEALTH_USER_AGENT));
+                if let Some(ua) = user_agent {
                     caps.add_arg(&format!("--user-agent={}", ua))
                         .map_err(|e| AdkError::tool(format!("Failed to add user-agent: {}", e)))?;
                 }
 
+                if self.config.stealth {
+                    add_chromium_stealth(&mut caps)?;
+                }
+
                 for arg in &self.config.browser_args {
                     caps.add_arg(arg).map_err(|e| {
                         AdkError::tool(format!("Failed to add arg '{}': {}", arg, e))
                     })?;
                 }
 
+                if let Some(prefs) = self.chromium_download_prefs() {
+                    caps.add_experimental_option("prefs", prefs).map_err(|e| {
+                        AdkError::tool(format!("Failed to set download prefs: {}", e))
+                    })?;
+                }
+
                 caps.into()
             }
             BrowserType::Firefox => {
@@ -828,6 +1084,14 @@ impl BrowserSession {
                     caps.add_arg("-headless")
                         .map_err(|e| AdkError::tool(format!("Failed to add headless: {}", e)))?;
                 }
+                if self.config.download_dir.is_some()
+                    || self.config.stealth
+                    || self.config.user_agent.is_some()
+                {
+                    self.firefox_prefs().and_then(|prefs| caps.set_preferences(prefs)).map_err(
+                        |e| AdkError::tool(format!("Failed to set Firefox preferences: {}", e)),
+                    )?;
+                }
                 caps.into()
             }
             BrowserType::Safari => DesiredCapabilities::safari().into(),
@@ -837,12 +1101,105 @@ impl BrowserSession {
                     caps.add_arg("--headless")
                         .map_err(|e| AdkError::tool(format!("Failed to add headless: {}", e)))?;
                 }
+                if self.config.stealth {
+                    add_chromium_stealth(&mut caps)?;
+                }
+                if let Some(prefs) = self.chromium_download_prefs() {
+                    caps.add_experimental_option("prefs", prefs).map_err(|e| {
+                        AdkError::tool(format!("Failed to set download prefs: {}", e))
+                    })?;
+                }
                 caps.into()
             }
         };
 
         Ok(caps)
     }
+
+    /// Firefox preferences for the download directory, user agent, and stealth mode.
+    fn firefox_prefs(&self) -> WebDriverResult<FirefoxPreferences> {
+        let mut prefs = FirefoxPreferences::new();
+        if let Some(dir) = &self.config.download_dir {
+            prefs.set("browser.download.folderList", 2)?;
+            prefs.set("browser.download.dir", dir.display().to_string())?;
+            prefs.set("browser.download.useDownloadDir", true)?;
+            prefs.set("browser.download.always_ask_before_handling_new_types", false)?;
+            prefs.set("pdfjs.disabled", true)?;
+        }
+        if let Some(ua) = &self.config.user_agent {
+            prefs.set("general.useragent.override", ua.clone())?;
+        }
+        if self.config.stealth {
+            prefs.set("dom.webdriver.enabled", false)?;
+            prefs.set("intl.accept_languages", "en-US, en")?;
+        }
+        Ok(prefs)
+    }
+
+    /// Chrome/Edge preferences that save downloads to `download_dir` without prompting.
+    fn chromium_download_prefs(&self) -> Option<serde_json::Value> {
+        let dir = self.config.download_dir.as_ref()?;
+        Some(serde_json::json!({
+            "download.default_directory": dir.display().to_string(),
+            "download.prompt_for_download": false,
+            "download.directory_upgrade": true,
+            "plugins.always_open_pdf_externally": true,
+            "safebrowsing.enabled": true
+        }))
+    }
+}
+
+/// Chromium arguments and options that stop the browser from advertising automation.
+fn add_chromium_stealth(caps: &mut impl ChromiumLikeCapabilities) -> Result<()> {
+    for arg in CHROMIUM_STEALTH_ARGS {
+        caps.add_arg(arg)
+            .map_err(|e| AdkError::tool(format!("Failed to add arg '{}': {}", arg, e)))?;
+    }
+    caps.add_experimental_option("excludeSwitches", ["enable-automation"])
+        .map_err(|e| AdkError::tool(format!("Failed to set stealth options: {}", e)))?;
+    Ok(())
+}
+
+/// Reject coordinates outside the valid ranges before sending them to the browser.
+fn validate_geolocation(geolocation: &Geolocation) -> Result<()> {
+    let Geolocation { latitude, longitude, accuracy } = *geolocation;
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(AdkError::tool(format!("Latitude must be between -90 and 90, got {latitude}")));
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(AdkError::tool(format!(
+            "Longitude must be between -180 and 180, got {longitude}"
+        )));
+    }
+    if !(accuracy >= 0.0 && accuracy.is_finite()) {
+        return Err(AdkError::tool(format!(
+            "Accuracy must be a non-negative number, got {accuracy}"
+        )));
+    }
+    Ok(())
+}
+
+/// Grant the geolocation permission and override the reported position.
+async fn apply_geolocation(devtools: &ChromeDevTools, geolocation: &Geolocation) -> Result<()> {
+    devtools
+        .execute_cdp_with_params(
+            "Browser.grantPermissions",
+            serde_json::json!({ "permissions": ["geolocation"] }),
+        )
+        .await
+        .map_err(|e| AdkError::tool(format!("Failed to grant geolocation permission: {e}")))?;
+    devtools
+        .execute_cdp_with_params(
+            "Emulation.setGeolocationOverride",
+            serde_json::json!({
+                "latitude": geolocation.latitude,
+                "longitude": geolocation.longitude,
+                "accuracy": geolocation.accuracy,
+            }),
+        )
+        .await
+        .map_err(|e| AdkError::tool(format!("Failed to set geolocation: {e}")))?;
+    Ok(())
 }
 
 impl Drop for BrowserSession {
@@ -920,6 +1277,43 @@ mod tests {
         assert!(caps.is_ok());
     }
 
+    #[test]
+    fn test_build_capabilities_with_download_dir() {
+        for browser in [BrowserType::Chrome, BrowserType::Firefox, BrowserType::Edge] {
+            let config = BrowserConfig::new().browser(browser).download_dir("/tmp/adk-downloads");
+            let session = BrowserSession::new(config);
+            let caps = serde_json::to_string(&session.build_capabilities().unwrap()).unwrap();
+            assert!(caps.contains("/tmp/adk-downloads"), "{browser:?}: {caps}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_download_requires_download_dir() {
+        let session = BrowserSession::with_defaults();
+        assert!(session.download_dir().is_none());
+        let err = session.wait_for_download(1).await.unwrap_err();
+        assert!(err.to_string().contains("download_dir"));
+    }
+
+    #[test]
+    fn test_build_capabilities_with_stealth() {
+        let session = BrowserSession::new(BrowserConfig::new().with_stealth(true));
+        let caps = serde_json::to_string(&session.build_capabilities().unwrap()).unwrap();
+        assert!(caps.contains("--disable-blink-features=AutomationControlled"), "{caps}");
+        assert!(caps.contains("enable-automation"), "{caps}");
+        assert!(caps.contains("Chrome/131.0.0.0"), "{caps}");
+        assert!(session.stealth_script.is_some());
+
+        let config = BrowserConfig::new()
+            .browser(BrowserType::Firefox)
+            .with_stealth(true)
+            .user_agent("Ua/1");
+        let session = BrowserSession::new(config);
+        let caps = serde_json::to_string(&session.build_capabilities().unwrap()).unwrap();
+        assert!(caps.contains("dom.webdriver.enabled"), "{caps}");
+        assert!(caps.contains("Ua/1"), "{caps}");
+    }
+
     #[test]
     fn test_build_capabilities_with_extra_args() {
         let config = BrowserConfig::new().add_arg("--disable-gpu").add_arg("--window-size=800,600");
@@ -927,4 +1321,27 @@ mod tests {
         let caps = session.build_capabilities();
         assert!(caps.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_emulation_unsupported_without_devtools() {
+        let config = BrowserConfig::new()
+            .browser(BrowserType::Firefox)
+            .webdriver_url("http://127.0.0.1:1")
+            .timezone("Asia/Tokyo");
+        let session = BrowserSession::new(config);
+
+        let err = session.start().await.unwrap_err().to_string();
+        assert!(err.contains("only supported on Chrome and Edge, not Firefox"), "{err}");
+        let err = session.set_geolocation(48.85, 2.35, 10.0).await.unwrap_err().to_string();
+        assert!(err.contains("only supported on Chrome and Edge"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_set_geolocation_rejects_invalid_coordinates() {
+        let session = BrowserSession::new(BrowserConfig::new().webdriver_url("http://127.0.0.1:1"));
+        let err = session.set_geolocation(91.0, 0.0, 10.0).await.unwrap_err().to_string();
+        assert!(err.contains("Latitude"), "{err}");
+        let err = session.set_geolocation(0.0, 0.0, -1.0).await.unwrap_err().to_string();
+        assert!(err.contains("Accuracy"), "{err}");
+    }
 }
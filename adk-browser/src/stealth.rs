@@ -0,0 +1,70 @@
+//! Best-effort masking of common automation fingerprints.
+//!
+//! Enabled with [`BrowserConfig::with_stealth`](crate::BrowserConfig::with_stealth).
+//! Sites use many more signals than the ones covered here, so stealth mode
+//! reduces how often pages block automated sessions but cannot prevent it.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// User agent used in stealth mode when none is configured.
+///
+/// Headless Chrome otherwise reports itself as `HeadlessChrome`.
+pub const STEALTH_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
+    AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+
+/// Languages reported by `navigator.languages` in stealth mode.
+pub const STEALTH_LANGUAGES: [&str; 2] = ["en-US", "en"];
+
+/// Chromium command-line arguments that stop the browser from advertising
+/// automation.
+pub(crate) const CHROMIUM_STEALTH_ARGS: [&str; 2] =
+    ["--disable-blink-features=AutomationControlled", "--lang=en-US"];
+
+/// Build the script injected into every page in stealth mode.
+///
+/// It hides `navigator.webdriver`, reports [`STEALTH_LANGUAGES`] and a
+/// non-empty plugin list, stubs `window.chrome`, and picks per-session values
+/// for `navigator.hardwareConcurrency` and `navigator.deviceMemory` so
+/// sessions do not share an identical fingerprint.
+pub fn stealth_script() -> String {
+    let seed = RandomState::new().build_hasher().finish();
+    let cores = [4, 8, 12, 16][(seed % 4) as usize];
+    let memory = [4, 8][((seed >> 8) % 2) as usize];
+    let languages = serde_json::to_string(&STEALTH_LANGUAGES).unwrap_or_default();
+
+    format!(
+        r#"(() => {{
+  const define = (target, key, value) => {{
+    try {{
+      Object.defineProperty(target, key, {{ get: () => value, configurable: true }});
+    }} catch (e) {{}}
+  }};
+  define(Navigator.prototype, 'webdriver', undefined);
+  define(Navigator.prototype, 'languages', Object.freeze({languages}));
+  define(Navigator.prototype, 'hardwareConcurrency', {cores});
+  define(Navigator.prototype, 'deviceMemory', {memory});
+  if (navigator.plugins && navigator.plugins.length === 0) {{
+    define(Navigator.prototype, 'plugins', [
+      {{ name: 'PDF Viewer', filename: 'internal-pdf-viewer', description: 'Portable Document Format' }},
+      {{ name: 'Chrome PDF Viewer', filename: 'internal-pdf-viewer', description: 'Portable Document Format' }},
+    ]);
+  }}
+  if (!window.chrome) {{
+    window.chrome = {{ runtime: {{}} }};
+  }}
+}})();"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_masks_webdriver_and_languages() {
+        let script = stealth_script();
+        assert!(script.contains("define(Navigator.prototype, 'webdriver', undefined)"));
+        assert!(script.contains(r#"["en-US","en"]"#));
+    }
+}
@@ -1,5 +1,6 @@
 //! Click tool for interacting with page elements.
 
+use super::frames::{frame_path, frame_path_schema};
 use crate::session::BrowserSession;
 use adk_core::{Result, Tool, ToolContext};
 use async_trait::async_trait;
@@ -39,7 +40,8 @@ impl Tool for ClickTool {
                 "wait_timeout": {
                     "type": "integer",
                     "description": "Optional timeout in seconds to wait for element to be clickable (default: 10)"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector"]
         }))
@@ -62,24 +64,30 @@ impl Tool for ClickTool {
             .ok_or_else(|| adk_core::AdkError::tool("Missing 'selector' parameter"))?;
 
         let wait_timeout = args.get("wait_timeout").and_then(|v| v.as_u64()).unwrap_or(10);
-
-        // Wait for element to be clickable, then click
-        let element = self.browser.wait_for_clickable(selector, wait_timeout).await?;
-
-        element
-            .click()
-            .await
-            .map_err(|e| adk_core::AdkError::tool(format!("Click failed: {}", e)))?;
-
-        // Get element info for response
-        let tag_name = element.tag_name().await.unwrap_or_else(|_| "unknown".to_string());
-
-        let text = element.text().await.unwrap_or_default();
-        let element_info = if text.is_empty() {
-            tag_name
-        } else {
-            format!("{}: {}", tag_name, text.chars().take(50).collect::<String>())
-        };
+        let frames = frame_path(&args)?;
+
+        let element_info = self
+            .browser
+            .within_frames(&frames, async {
+                // Wait for element to be clickable, then click
+                let element = self.browser.wait_for_clickable(selector, wait_timeout).await?;
+
+                element
+                    .click()
+                    .await
+                    .map_err(|e| adk_core::AdkError::tool(format!("Click failed: {}", e)))?;
+
+                // Get element info for response
+                let tag_name = element.tag_name().await.unwrap_or_else(|_| "unknown".to_string());
+
+                let text = element.text().await.unwrap_or_default();
+                Ok(if text.is_empty() {
+                    tag_name
+                } else {
+                    format!("{}: {}", tag_name, text.chars().take(50).collect::<String>())
+                })
+            })
+            .await?;
 
         // Include page context so the agent knows what happened after the click
         let context = self.browser.page_context().await.unwrap_or_default();
@@ -120,7 +128,8 @@ impl Tool for DoubleClickTool {
                 "selector": {
                     "type": "string",
                     "description": "CSS selector for the element to double-click"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector"]
         }))
@@ -133,12 +142,13 @@ impl Tool for DoubleClickTool {
             .ok_or_else(|| adk_core::AdkError::tool("Missing 'selector' parameter"))?;
 
         let escaped = crate::escape::escape_js_string(selector);
+        let frames = frame_path(&args)?;
 
         // Execute double-click via JS and get tag name in one call
         let result = self.browser
-            .execute_script(&format!(
+            .within_frames(&frames, self.browser.execute_script(&format!(
                 "var el = document.querySelector('{escaped}'); if (!el) return null; el.dispatchEvent(new MouseEvent('dblclick', {{'view': window, 'bubbles': true, 'cancelable': true}})); return el.tagName.toLowerCase();"
-            ))
+            )))
             .await?;
 
         let tag_name = result.as_str().unwrap_or("unknown");
@@ -0,0 +1,104 @@
+//! Download tool for capturing files the browser downloads.
+
+use crate::session::BrowserSession;
+use adk_core::{AdkError, Result, Tool, ToolContext};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Maximum time the tool will wait for a download.
+const MAX_TIMEOUT_SECS: u64 = 300;
+
+/// Tool for waiting on a file download triggered by a previous action.
+///
+/// Requires [`BrowserConfig::download_dir`](crate::BrowserConfig::download_dir).
+pub struct WaitForDownloadTool {
+    browser: Arc<BrowserSession>,
+}
+
+impl WaitForDownloadTool {
+    /// Create a new download tool with a shared browser session.
+    pub fn new(browser: Arc<BrowserSession>) -> Self {
+        Self { browser }
+    }
+}
+
+#[async_trait]
+impl Tool for WaitForDownloadTool {
+    fn name(&self) -> &str {
+        "browser_wait_for_download"
+    }
+
+    fn description(&self) -> &str {
+        "Wait for a file download (e.g. started by clicking a download link) to finish. Returns the filename, size, and MIME type, and can save the file to artifacts."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "timeout": {
+                    "type": "integer",
+                    "description": "Maximum seconds to wait for the download to finish (default: 30, max: 300)"
+                },
+                "save_to_artifacts": {
+                    "type": "boolean",
+                    "description": "Whether to save the downloaded file to artifacts (default: false)"
+                },
+                "artifact_name": {
+                    "type": "string",
+                    "description": "Name for the artifact if saving (default: the downloaded filename)"
+                }
+            }
+        }))
+    }
+
+    fn response_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "success": { "type": "boolean" },
+                "filename": { "type": "string" },
+                "path": { "type": "string" },
+                "size": { "type": "integer" },
+                "mime_type": { "type": "string" },
+                "saved_to_artifacts": { "type": "boolean" },
+                "artifact_name": { "type": "string" }
+            }
+        }))
+    }
+
+    async fn execute(&self, ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value> {
+        let timeout =
+            args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(30).min(MAX_TIMEOUT_SECS);
+        let save_to_artifacts =
+            args.get("save_to_artifacts").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let file = self.browser.wait_for_download(timeout).await?;
+        let artifact_name = args
+            .get("artifact_name")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| file.filename.clone());
+
+        let mut saved = false;
+        if save_to_artifacts && let Some(artifacts) = ctx.artifacts() {
+            let data = std::fs::read(&file.path).map_err(|e| {
+                AdkError::tool(format!("Failed to read download '{}': {}", file.path.display(), e))
+            })?;
+            let part = adk_core::Part::InlineData { mime_type: file.mime_type.clone(), data };
+            artifacts.save(&artifact_name, &part).await?;
+            saved = true;
+        }
+
+        Ok(json!({
+            "success": true,
+            "filename": file.filename,
+            "path": file.path.display().to_string(),
+            "size": file.size,
+            "mime_type": file.mime_type,
+            "saved_to_artifacts": saved,
+            "artifact_name": if saved { Some(artifact_name) } else { None }
+        }))
+    }
+}
@@ -0,0 +1,78 @@
+//! Emulation tools for testing location-aware pages.
+
+use crate::session::BrowserSession;
+use adk_core::{AdkError, Result, Tool, ToolContext};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Accuracy radius used when the caller does not give one, in meters.
+const DEFAULT_ACCURACY_METERS: f64 = 100.0;
+
+/// Tool for changing the position reported by `navigator.geolocation`.
+///
+/// Only Chrome and Edge support geolocation emulation; other browsers return
+/// an error.
+pub struct SetGeolocationTool {
+    browser: Arc<BrowserSession>,
+}
+
+impl SetGeolocationTool {
+    /// Create a new geolocation tool with a shared browser session.
+    pub fn new(browser: Arc<BrowserSession>) -> Self {
+        Self { browser }
+    }
+}
+
+#[async_trait]
+impl Tool for SetGeolocationTool {
+    fn name(&self) -> &str {
+        "browser_set_geolocation"
+    }
+
+    fn description(&self) -> &str {
+        "Set the location the browser reports to pages through navigator.geolocation. Reload the page if it already read the location."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "latitude": {
+                    "type": "number",
+                    "description": "Latitude in degrees, between -90 and 90"
+                },
+                "longitude": {
+                    "type": "number",
+                    "description": "Longitude in degrees, between -180 and 180"
+                },
+                "accuracy": {
+                    "type": "number",
+                    "description": "Accuracy radius in meters (default: 100)"
+                }
+            },
+            "required": ["latitude", "longitude"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value> {
+        let latitude = args
+            .get("latitude")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| AdkError::tool("Missing 'latitude' parameter"))?;
+        let longitude = args
+            .get("longitude")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| AdkError::tool("Missing 'longitude' parameter"))?;
+        let accuracy =
+            args.get("accuracy").and_then(|v| v.as_f64()).unwrap_or(DEFAULT_ACCURACY_METERS);
+
+        self.browser.set_geolocation(latitude, longitude, accuracy).await?;
+        Ok(json!({
+            "success": true,
+            "latitude": latitude,
+            "longitude": longitude,
+            "accuracy": accuracy
+        }))
+    }
+}
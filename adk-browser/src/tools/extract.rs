@@ -1,5 +1,7 @@
 //! Extract tool for getting content from the page.
 
+use super::frames::{frame_path, frame_path_schema};
+use crate::reader::{extract_reader_content, reading_time_minutes};
 use crate::session::BrowserSession;
 use adk_core::{Result, Tool, ToolContext};
 use async_trait::async_trait;
@@ -39,7 +41,8 @@ impl Tool for ExtractTextTool {
                 "all": {
                     "type": "boolean",
                     "description": "If true, extract from all matching elements. If false, only first match (default: false)"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector"]
         }))
@@ -67,16 +70,23 @@ impl Tool for ExtractTextTool {
             .ok_or_else(|| adk_core::AdkError::tool("Missing 'selector' parameter"))?;
 
         let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+        let frames = frame_path(&args)?;
 
         if all {
-            let elements = self.browser.find_elements(selector).await?;
-            let mut texts = Vec::new();
-
-            for element in elements {
-                if let Ok(text) = element.text().await {
-                    texts.push(text);
-                }
-            }
+            let texts = self
+                .browser
+                .within_frames(&frames, async {
+                    let elements = self.browser.find_elements(selector).await?;
+                    let mut texts = Vec::new();
+
+                    for element in elements {
+                        if let Ok(text) = element.text().await {
+                            texts.push(text);
+                        }
+                    }
+                    Ok(texts)
+                })
+                .await?;
 
             Ok(json!({
                 "success": true,
@@ -84,7 +94,7 @@ impl Tool for ExtractTextTool {
                 "count": texts.len()
             }))
         } else {
-            let text = self.browser.get_text(selector).await?;
+            let text = self.browser.within_frames(&frames, self.browser.get_text(selector)).await?;
 
             Ok(json!({
                 "success": true,
@@ -126,7 +136,8 @@ impl Tool for ExtractAttributeTool {
                 "attribute": {
                     "type": "string",
                     "description": "Name of the attribute to extract (e.g., 'href', 'src', 'value', 'class')"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector", "attribute"]
         }))
@@ -143,7 +154,11 @@ impl Tool for ExtractAttributeTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| adk_core::AdkError::tool("Missing 'attribute' parameter"))?;
 
-        let value = self.browser.get_attribute(selector, attribute).await?;
+        let frames = frame_path(&args)?;
+        let value = self
+            .browser
+            .within_frames(&frames, self.browser.get_attribute(selector, attribute))
+            .await?;
 
         Ok(json!({
             "success": true,
@@ -314,3 +329,79 @@ impl Tool for PageSourceTool {
         }))
     }
 }
+
+/// Tool for extracting the main readable content of the page.
+pub struct ReaderContentTool {
+    browser: Arc<BrowserSession>,
+}
+
+impl ReaderContentTool {
+    /// Create a new reader content tool with a shared browser session.
+    pub fn new(browser: Arc<BrowserSession>) -> Self {
+        Self { browser }
+    }
+}
+
+#[async_trait]
+impl Tool for ReaderContentTool {
+    fn name(&self) -> &str {
+        "browser_reader_content"
+    }
+
+    fn description(&self) -> &str {
+        "Extract the main article content of the page as clean text, without navigation, ads, \
+         or scripts. Also returns the title, byline, and estimated reading time. Prefer this over \
+         browser_page_source for reading or summarizing pages."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {}
+        }))
+    }
+
+    fn response_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "success": { "type": "boolean" },
+                "title": { "type": ["string", "null"] },
+                "byline": { "type": ["string", "null"] },
+                "text": { "type": "string" },
+                "word_count": { "type": "integer" },
+                "reading_time_minutes": { "type": "integer" },
+                "mode": {
+                    "type": "string",
+                    "enum": ["reader", "body_text"],
+                    "description": "body_text when no main content was found and the whole body text was returned"
+                }
+            }
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, _args: Value) -> Result<Value> {
+        let source = self.browser.page_source().await?;
+        let mut content = extract_reader_content(&source);
+
+        let mode = if content.confident {
+            "reader"
+        } else {
+            // Low confidence: return what browser_extract_text would for the body.
+            content.text = self.browser.get_text("body").await?;
+            content.word_count = content.text.split_whitespace().count();
+            content.reading_time_minutes = reading_time_minutes(content.word_count);
+            "body_text"
+        };
+
+        Ok(json!({
+            "success": true,
+            "title": content.title,
+            "byline": content.byline,
+            "text": content.text,
+            "word_count": content.word_count,
+            "reading_time_minutes": content.reading_time_minutes,
+            "mode": mode
+        }))
+    }
+}
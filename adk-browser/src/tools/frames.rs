@@ -1,6 +1,6 @@
 //! Frame/iframe management tools.
 
-use crate::session::BrowserSession;
+use crate::session::{BrowserSession, FrameSelector};
 use adk_core::{AdkError, Result, Tool, ToolContext};
 use async_trait::async_trait;
 use serde_json::{Value, json};
@@ -140,3 +140,65 @@ impl Tool for SwitchToDefaultContentTool {
         }))
     }
 }
+
+/// Schema of the `frame_path` argument taken by element interaction tools.
+pub(crate) fn frame_path_schema() -> Value {
+    json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "description": "Iframes to act inside, outermost first: a CSS selector for each <iframe> element or its 0-based index (e.g. [\"#checkout\", \"0\"]). The tool enters them, acts, and returns to the main page. Omit for the current frame."
+    })
+}
+
+/// Parse the optional `frame_path` argument; strings of digits and numbers
+/// are frame indices, other strings are iframe selectors.
+pub(crate) fn frame_path(args: &Value) -> Result<Vec<FrameSelector>> {
+    let Some(path) = args.get("frame_path").filter(|v| !v.is_null()) else {
+        return Ok(Vec::new());
+    };
+    let steps = path
+        .as_array()
+        .ok_or_else(|| AdkError::tool("'frame_path' must be an array of selectors or indices"))?;
+    steps
+        .iter()
+        .map(|step| {
+            let index = match step {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.trim().parse::<u64>().ok(),
+                _ => None,
+            };
+            match (index, step) {
+                (Some(index), _) => u16::try_from(index)
+                    .map(FrameSelector::Index)
+                    .map_err(|_| AdkError::tool(format!("Frame index {index} is out of range"))),
+                (None, Value::String(selector)) => Ok(FrameSelector::Selector(selector.clone())),
+                _ => Err(AdkError::tool(format!(
+                    "Invalid 'frame_path' step {step}: expected a selector or an index"
+                ))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_path_accepts_selectors_and_indices() {
+        let args = json!({ "frame_path": ["#outer", 1, "2", "iframe[name=inner]"] });
+        assert_eq!(
+            frame_path(&args).unwrap(),
+            [
+                FrameSelector::Selector("#outer".to_string()),
+                FrameSelector::Index(1),
+                FrameSelector::Index(2),
+                FrameSelector::Selector("iframe[name=inner]".to_string()),
+            ]
+        );
+        assert!(frame_path(&json!({})).unwrap().is_empty());
+        assert!(frame_path(&json!({ "frame_path": "#outer" })).is_err());
+        assert!(frame_path(&json!({ "frame_path": [70000] })).is_err());
+        assert!(frame_path(&json!({ "frame_path": [true] })).is_err());
+    }
+}
@@ -3,9 +3,11 @@
 //! This module provides a collection of tools for browser automation:
 //!
 //! - Navigation: `NavigateTool`, `BackTool`, `ForwardTool`, `RefreshTool`
-//! - Interaction: `ClickTool`, `DoubleClickTool`, `TypeTool`, `ClearTool`, `SelectTool`
-//! - Extraction: `ExtractTextTool`, `ExtractAttributeTool`, `ExtractLinksTool`, `PageInfoTool`, `PageSourceTool`
+//! - Interaction: `ClickTool`, `DoubleClickTool`, `TypeTool`, `ClearTool`, `SelectTool`, `FillFormTool`
+//! - Extraction: `ExtractTextTool`, `ExtractAttributeTool`, `ExtractLinksTool`, `PageInfoTool`, `PageSourceTool`, `ReaderContentTool`
 //! - Screenshots: `ScreenshotTool`
+//! - Downloads: `WaitForDownloadTool`
+//! - Emulation: `SetGeolocationTool`
 //! - Waiting: `WaitForElementTool`, `WaitTool`, `WaitForPageLoadTool`, `WaitForTextTool`
 //! - JavaScript: `EvaluateJsTool`, `ScrollTool`, `HoverTool`, `AlertTool`
 //! - Cookies: `GetCookiesTool`, `GetCookieTool`, `AddCookieTool`, `DeleteCookieTool`, `DeleteAllCookiesTool`
@@ -16,6 +18,8 @@
 mod actions;
 mod click;
 mod cookies;
+mod download;
+mod emulation;
 mod evaluate;
 mod extract;
 mod frames;
@@ -32,14 +36,21 @@ pub use navigate::{BackTool, ForwardTool, NavigateTool, RefreshTool};
 pub use click::{ClickTool, DoubleClickTool};
 
 // Type/form tools
-pub use type_text::{ClearTool, SelectTool, TypeTool};
+pub use type_text::{ClearTool, FillFormTool, SelectTool, TypeTool};
 
 // Screenshot tools
 pub use screenshot::ScreenshotTool;
 
+// Download tools
+pub use download::WaitForDownloadTool;
+
+// Emulation tools
+pub use emulation::SetGeolocationTool;
+
 // Extraction tools
 pub use extract::{
     ExtractAttributeTool, ExtractLinksTool, ExtractTextTool, PageInfoTool, PageSourceTool,
+    ReaderContentTool,
 };
 
 // Wait tools
@@ -1,5 +1,7 @@
-//! Type tool for entering text into form fields.
+//! Type, clear, select, and fill-form tools for entering data into form fields.
 
+use super::frames::{frame_path, frame_path_schema};
+use crate::form::FormFieldStatus;
 use crate::session::BrowserSession;
 use adk_core::{Result, Tool, ToolContext};
 use async_trait::async_trait;
@@ -47,7 +49,8 @@ impl Tool for TypeTool {
                 "press_enter": {
                     "type": "boolean",
                     "description": "Whether to press Enter after typing (default: false)"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector", "text"]
         }))
@@ -78,35 +81,39 @@ impl Tool for TypeTool {
         let clear_first = args.get("clear_first").and_then(|v| v.as_bool()).unwrap_or(true);
 
         let press_enter = args.get("press_enter").and_then(|v| v.as_bool()).unwrap_or(false);
+        let frames = frame_path(&args)?;
+
+        let field_value = self
+            .browser
+            .within_frames(&frames, async {
+                // Wait for element
+                let element = self.browser.wait_for_element(selector, 10).await?;
+
+                // Clear if requested
+                if clear_first {
+                    element
+                        .clear()
+                        .await
+                        .map_err(|e| adk_core::AdkError::tool(format!("Clear failed: {}", e)))?;
+                }
 
-        // Wait for element
-        let element = self.browser.wait_for_element(selector, 10).await?;
-
-        // Clear if requested
-        if clear_first {
-            element
-                .clear()
-                .await
-                .map_err(|e| adk_core::AdkError::tool(format!("Clear failed: {}", e)))?;
-        }
-
-        // Type the text
-        element
-            .send_keys(text)
-            .await
-            .map_err(|e| adk_core::AdkError::tool(format!("Type failed: {}", e)))?;
-
-        // Press Enter if requested
-        if press_enter {
-            element
-                .send_keys("\n")
-                .await
-                .map_err(|e| adk_core::AdkError::tool(format!("Enter key failed: {}", e)))?;
-        }
-
-        // Get the current value
-        let field_value =
-            element.attr("value").await.ok().flatten().unwrap_or_else(|| text.to_string());
+                // Type the text
+                element
+                    .send_keys(text)
+                    .await
+                    .map_err(|e| adk_core::AdkError::tool(format!("Type failed: {}", e)))?;
+
+                // Press Enter if requested
+                if press_enter {
+                    element.send_keys("\n").await.map_err(|e| {
+                        adk_core::AdkError::tool(format!("Enter key failed: {}", e))
+                    })?;
+                }
+
+                // Get the current value
+                Ok(element.attr("value").await.ok().flatten().unwrap_or_else(|| text.to_string()))
+            })
+            .await?;
 
         // Include page context so the agent knows the current state
         let context = self.browser.page_context().await.unwrap_or_default();
@@ -148,7 +155,8 @@ impl Tool for ClearTool {
                 "selector": {
                     "type": "string",
                     "description": "CSS selector for the input element to clear"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector"]
         }))
@@ -160,7 +168,8 @@ impl Tool for ClearTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| adk_core::AdkError::tool("Missing 'selector' parameter"))?;
 
-        self.browser.clear(selector).await?;
+        let frames = frame_path(&args)?;
+        self.browser.within_frames(&frames, self.browser.clear(selector)).await?;
 
         let context = self.browser.page_context().await.unwrap_or_default();
 
@@ -212,7 +221,8 @@ impl Tool for SelectTool {
                 "index": {
                     "type": "integer",
                     "description": "The index of the option to select (0-based)"
-                }
+                },
+                "frame_path": frame_path_schema()
             },
             "required": ["selector"]
         }))
@@ -227,6 +237,7 @@ impl Tool for SelectTool {
         let value = args.get("value").and_then(|v| v.as_str());
         let text = args.get("text").and_then(|v| v.as_str());
         let index = args.get("index").and_then(|v| v.as_u64());
+        let frames = frame_path(&args)?;
 
         let escaped_selector = crate::escape::escape_js_string(selector);
 
@@ -251,7 +262,8 @@ impl Tool for SelectTool {
                 "#,
             );
 
-            let result = self.browser.execute_script(&script).await?;
+            let result =
+                self.browser.within_frames(&frames, self.browser.execute_script(&script)).await?;
             if result.as_bool() == Some(true) {
                 let context = self.browser.page_context().await.unwrap_or_default();
                 return Ok(json!({
@@ -278,7 +290,8 @@ impl Tool for SelectTool {
                 "#,
             );
 
-            let result = self.browser.execute_script(&script).await?;
+            let result =
+                self.browser.within_frames(&frames, self.browser.execute_script(&script)).await?;
             if let Some(selected_text) = result.as_str() {
                 let context = self.browser.page_context().await.unwrap_or_default();
                 return Ok(json!({
@@ -295,7 +308,7 @@ impl Tool for SelectTool {
         };
 
         // Click the option
-        self.browser.click(&option_selector).await?;
+        self.browser.within_frames(&frames, self.browser.click(&option_selector)).await?;
 
         let context = self.browser.page_context().await.unwrap_or_default();
 
@@ -306,3 +319,85 @@ impl Tool for SelectTool {
         }))
     }
 }
+
+/// Tool for filling several form fields in one call.
+pub struct FillFormTool {
+    browser: Arc<BrowserSession>,
+}
+
+impl FillFormTool {
+    /// Create a new fill-form tool with a shared browser session.
+    pub fn new(browser: Arc<BrowserSession>) -> Self {
+        Self { browser }
+    }
+}
+
+#[async_trait]
+impl Tool for FillFormTool {
+    fn name(&self) -> &str {
+        "browser_fill_form"
+    }
+
+    fn description(&self) -> &str {
+        "Fill several form fields at once: text inputs, text areas, selects, checkboxes, and radio \
+         groups. Returns whether each field was filled."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "fields": {
+                    "type": "object",
+                    "description": "Map of field identifier to value. An identifier can be the field's name, id, label text, fieldset legend, aria-label, placeholder, or a CSS selector. Use text for inputs, the option value or text for selects (an array for multi-selects), true/false for checkboxes, and the option value or label for radio groups.",
+                    "additionalProperties": true
+                }
+            },
+            "required": ["fields"]
+        }))
+    }
+
+    fn response_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "success": { "type": "boolean" },
+                "filled": { "type": "integer" },
+                "failed": { "type": "integer" },
+                "fields": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "field": { "type": "string" },
+                            "status": { "type": "string", "enum": ["filled", "not_found", "failed"] },
+                            "matched_by": { "type": "string" },
+                            "value": {},
+                            "error": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value> {
+        let fields = args
+            .get("fields")
+            .and_then(|v| v.as_object())
+            .ok_or_else(|| adk_core::AdkError::tool("Missing 'fields' object parameter"))?;
+
+        let results = self.browser.fill_form(fields).await?;
+        let filled = results.iter().filter(|r| r.status == FormFieldStatus::Filled).count();
+
+        let context = self.browser.page_context().await.unwrap_or_default();
+
+        Ok(json!({
+            "success": filled == results.len(),
+            "filled": filled,
+            "failed": results.len() - filled,
+            "fields": results,
+            "page": context
+        }))
+    }
+}
@@ -27,7 +27,7 @@ impl SessionResolver {
 
 /// Pre-configured tool profiles for common use cases.
 ///
-/// Instead of using all 46 tools (which overwhelms LLM context windows),
+/// Instead of using all 49 tools (which overwhelms LLM context windows),
 /// select a profile that matches your agent's task.
 ///
 /// # Example
@@ -38,20 +38,20 @@ impl SessionResolver {
 ///
 /// let browser = Arc::new(BrowserSession::new(BrowserConfig::default()));
 /// let toolset = BrowserToolset::with_profile(browser, BrowserProfile::FormFilling);
-/// let tools = toolset.all_tools(); // 8 tools instead of 46
+/// let tools = toolset.all_tools(); // 8 tools instead of 49
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BrowserProfile {
-    /// 19 tools: navigation + interaction + extraction + wait + screenshot.
+    /// 21 tools: navigation + interaction + extraction + wait + screenshot.
     /// Best for simple browsing tasks.
     Minimal,
-    /// 19 tools: same categories as Minimal (navigation + interaction + extraction + wait + screenshot).
+    /// 21 tools: same categories as Minimal (navigation + interaction + extraction + wait + screenshot).
     /// Best for form-filling agents.
     FormFilling,
-    /// 14 tools: navigation + extraction + screenshot + JS (scroll, hover, evaluate, alert).
+    /// 15 tools: navigation + extraction + screenshot + JS (scroll, hover, evaluate, alert).
     /// Best for data extraction / scraping agents (no interaction tools).
     Scraping,
-    /// All 46 tools. Use only when the agent needs full browser control.
+    /// All 49 tools. Use only when the agent needs full browser control.
     Full,
 }
 
@@ -79,7 +79,7 @@ pub struct BrowserToolset {
     include_windows: bool,
     /// Include frame/iframe management tools
     include_frames: bool,
-    /// Include advanced action tools (drag-drop, focus, file upload, etc.)
+    /// Include advanced action tools (drag-drop, focus, file upload, downloads, geolocation, etc.)
     include_actions: bool,
 }
 
@@ -348,6 +348,7 @@ impl BrowserToolset {
             tools.push(Arc::new(TypeTool::new(browser.clone())));
             tools.push(Arc::new(ClearTool::new(browser.clone())));
             tools.push(Arc::new(SelectTool::new(browser.clone())));
+            tools.push(Arc::new(FillFormTool::new(browser.clone())));
         }
 
         if self.include_extraction {
@@ -356,6 +357,7 @@ impl BrowserToolset {
             tools.push(Arc::new(ExtractLinksTool::new(browser.clone())));
             tools.push(Arc::new(PageInfoTool::new(browser.clone())));
             tools.push(Arc::new(PageSourceTool::new(browser.clone())));
+            tools.push(Arc::new(ReaderContentTool::new(browser.clone())));
         }
 
         if self.include_wait {
@@ -408,6 +410,8 @@ impl BrowserToolset {
             tools.push(Arc::new(ElementStateTool::new(browser.clone())));
             tools.push(Arc::new(PressKeyTool::new(browser.clone())));
             tools.push(Arc::new(FileUploadTool::new(browser.clone())));
+            tools.push(Arc::new(WaitForDownloadTool::new(browser.clone())));
+            tools.push(Arc::new(SetGeolocationTool::new(browser.clone())));
             tools.push(Arc::new(PrintToPdfTool::new(browser)));
         }
 
@@ -447,6 +451,7 @@ pub fn readonly_browser_tools(browser: Arc<BrowserSession>) -> Vec<Arc<dyn Tool>
         Arc::new(ExtractAttributeTool::new(browser.clone())),
         Arc::new(ExtractLinksTool::new(browser.clone())),
         Arc::new(PageInfoTool::new(browser.clone())),
+        Arc::new(ReaderContentTool::new(browser.clone())),
         Arc::new(ScreenshotTool::new(browser.clone())),
         Arc::new(ScrollTool::new(browser)),
     ]
@@ -463,7 +468,7 @@ mod tests {
         let toolset = BrowserToolset::new(browser);
         let tools = toolset.all_tools();
 
-        // Should have 46 tools total
+        // Should have 49 tools total
         assert!(tools.len() > 40);
 
         // Check some tool names exist
@@ -477,6 +482,7 @@ mod tests {
         assert!(tool_names.contains(&"browser_new_tab"));
         assert!(tool_names.contains(&"browser_switch_to_frame"));
         assert!(tool_names.contains(&"browser_drag_and_drop"));
+        assert!(tool_names.contains(&"browser_set_geolocation"));
     }
 
     #[test]
@@ -521,6 +527,7 @@ mod tests {
         assert!(tool_names.contains(&"browser_type"));
         assert!(tool_names.contains(&"browser_select"));
         assert!(tool_names.contains(&"browser_clear"));
+        assert!(tool_names.contains(&"browser_fill_form"));
         assert!(tool_names.contains(&"browser_screenshot"));
         // Should NOT include cookies, windows, frames, actions
         assert!(!tool_names.contains(&"browser_get_cookies"));
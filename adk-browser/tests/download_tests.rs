@@ -0,0 +1,34 @@
+//! Downloads against a real browser.
+//!
+//! Requires a WebDriver server on localhost:4444 running on this machine
+//! (the download directory must be visible to both sides). Run with:
+//! `cargo test -p adk-browser --test download_tests -- --ignored`
+
+use adk_browser::{BrowserConfig, BrowserSession};
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_download_is_captured() {
+    let dir = std::env::temp_dir().join(format!("adk-browser-download-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let browser = BrowserSession::new(BrowserConfig::new().download_dir(&dir));
+    browser.start().await.unwrap();
+
+    browser
+        .navigate(
+            "data:text/html,<a id=\"dl\" download=\"report.csv\" \
+             href=\"data:text/csv;charset=utf-8,name%2Cscore%0Aada%2C42%0A\">Export</a>",
+        )
+        .await
+        .unwrap();
+    browser.click("#dl").await.unwrap();
+
+    let file = browser.wait_for_download(30).await.unwrap();
+    assert_eq!(file.filename, "report.csv");
+    assert_eq!(file.mime_type, "text/csv");
+    assert_eq!(std::fs::read_to_string(&file.path).unwrap(), "name,score\nada,42\n");
+    assert_eq!(file.size, 18);
+
+    browser.stop().await.unwrap();
+    std::fs::remove_dir_all(dir).unwrap();
+}
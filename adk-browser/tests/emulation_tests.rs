@@ -0,0 +1,59 @@
+//! Geolocation, time zone, and locale emulation against a real browser.
+//!
+//! Requires a Chrome WebDriver server on localhost:4444. Run with:
+//! `cargo test -p adk-browser --test emulation_tests -- --ignored`
+
+use adk_browser::{BrowserConfig, BrowserSession};
+use serde_json::json;
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_page_reads_emulated_timezone_and_locale() {
+    let config = BrowserConfig::new().timezone("Asia/Tokyo").locale("fr-FR");
+    let browser = BrowserSession::new(config);
+    browser.navigate("data:text/html,<title>emulation</title>").await.unwrap();
+
+    assert_eq!(
+        browser
+            .execute_script("return Intl.DateTimeFormat().resolvedOptions().timeZone;")
+            .await
+            .unwrap(),
+        json!("Asia/Tokyo")
+    );
+    // Tokyo has no daylight saving time: always UTC+9.
+    assert_eq!(
+        browser.execute_script("return new Date().getTimezoneOffset();").await.unwrap(),
+        json!(-540)
+    );
+    assert_eq!(
+        browser
+            .execute_script("return Intl.DateTimeFormat().resolvedOptions().locale;")
+            .await
+            .unwrap(),
+        json!("fr-FR")
+    );
+
+    browser.stop().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_set_geolocation_at_runtime() {
+    let browser = BrowserSession::new(BrowserConfig::new());
+    // Geolocation is only available in secure contexts.
+    browser.navigate("https://example.com").await.unwrap();
+    browser.set_geolocation(-1.2921, 36.8219, 25.0).await.unwrap();
+
+    let position = browser
+        .execute_async_script(
+            "const done = arguments[arguments.length - 1];
+             navigator.geolocation.getCurrentPosition(
+                 p => done([p.coords.latitude, p.coords.longitude, p.coords.accuracy]),
+                 e => done(e.message));",
+        )
+        .await
+        .unwrap();
+    assert_eq!(position, json!([-1.2921, 36.8219, 25.0]));
+
+    browser.stop().await.unwrap();
+}
@@ -0,0 +1,64 @@
+//! Filling a fixture form with mixed field types against a real browser.
+//!
+//! Requires a WebDriver server on localhost:4444 running on this machine
+//! (the fixture is loaded from a `file://` URL). Run with:
+//! `cargo test -p adk-browser --test fill_form_tests -- --ignored`
+
+use adk_browser::{BrowserConfig, BrowserSession, FormFieldStatus};
+use serde_json::json;
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_fill_form_reports_each_field() {
+    let browser = BrowserSession::new(BrowserConfig::new());
+    browser.start().await.unwrap();
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/signup_form.html");
+    browser.navigate(&format!("file://{fixture}")).await.unwrap();
+
+    let fields = json!({
+        "Full name": "Ada Lovelace",
+        "email": "ada@example.com",
+        "Age in years": 36,
+        "Tell us about yourself": "Writes programs for engines.",
+        "Country": "Kenya",
+        "#topics": ["rust", "Web"],
+        "Send me the newsletter": true,
+        "Plan": "Pro",
+        "Favourite colour": "green",
+        "newsletter": "yes",
+        "referral": "FRIEND",
+    });
+    let results = browser.fill_form(fields.as_object().unwrap()).await.unwrap();
+
+    let outcome: Vec<(&str, FormFieldStatus, Option<&str>)> =
+        results.iter().map(|r| (r.field.as_str(), r.status, r.matched_by.as_deref())).collect();
+    assert_eq!(
+        outcome,
+        [
+            ("Full name", FormFieldStatus::Filled, Some("label")),
+            ("email", FormFieldStatus::Filled, Some("name")),
+            ("Age in years", FormFieldStatus::Filled, Some("aria-label")),
+            ("Tell us about yourself", FormFieldStatus::Filled, Some("placeholder")),
+            ("Country", FormFieldStatus::Filled, Some("label")),
+            ("#topics", FormFieldStatus::Filled, Some("css")),
+            ("Send me the newsletter", FormFieldStatus::Filled, Some("label")),
+            ("Plan", FormFieldStatus::Filled, Some("legend")),
+            ("Favourite colour", FormFieldStatus::NotFound, None),
+            ("newsletter", FormFieldStatus::Failed, Some("name")),
+            ("referral", FormFieldStatus::Failed, Some("name")),
+        ]
+    );
+    assert_eq!(results[5].value, Some(json!(["rust", "web"])));
+    assert_eq!(results[9].error.as_deref(), Some("expected true or false for a checkbox"));
+
+    let state = browser
+        .execute_script(
+            "var f = document.getElementById('signup'); return [f.full_name.value, f.age.value, \
+             f.country.value, f.newsletter.checked, f.plan.value, f.referral.value];",
+        )
+        .await
+        .unwrap();
+    assert_eq!(state, json!(["Ada Lovelace", "36", "ke", true, "pro", "WELCOME"]));
+
+    browser.stop().await.unwrap();
+}
@@ -0,0 +1,113 @@
+//! Acting on elements inside nested iframes through `frame_path`.
+//!
+//! Requires a WebDriver server on localhost:4444 running on this machine
+//! (the fixture is loaded from a `file://` URL). Run with:
+//! `cargo test -p adk-browser --test frame_path_tests -- --ignored`
+
+use adk_browser::{BrowserConfig, BrowserSession, ClickTool, ExtractTextTool};
+use adk_core::{
+    CallbackContext, Content, EventActions, MemoryEntry, ReadonlyContext, Result, Tool, ToolContext,
+};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+struct TestContext {
+    content: Content,
+    actions: Mutex<EventActions>,
+}
+
+#[async_trait]
+impl ReadonlyContext for TestContext {
+    fn invocation_id(&self) -> &str {
+        "invocation"
+    }
+    fn agent_name(&self) -> &str {
+        "browser-agent"
+    }
+    fn user_id(&self) -> &str {
+        "user"
+    }
+    fn app_name(&self) -> &str {
+        "app"
+    }
+    fn session_id(&self) -> &str {
+        "session"
+    }
+    fn branch(&self) -> &str {
+        ""
+    }
+    fn user_content(&self) -> &Content {
+        &self.content
+    }
+}
+
+#[async_trait]
+impl CallbackContext for TestContext {
+    fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
+        None
+    }
+}
+
+#[async_trait]
+impl ToolContext for TestContext {
+    fn function_call_id(&self) -> &str {
+        "call-1"
+    }
+    fn actions(&self) -> EventActions {
+        self.actions.lock().unwrap().clone()
+    }
+    fn set_actions(&self, actions: EventActions) {
+        *self.actions.lock().unwrap() = actions;
+    }
+    async fn search_memory(&self, _query: &str) -> Result<Vec<MemoryEntry>> {
+        Ok(vec![])
+    }
+}
+
+fn ctx() -> Arc<dyn ToolContext> {
+    Arc::new(TestContext { content: Content::new("user"), actions: Mutex::default() })
+}
+
+async fn at_top_level(browser: &BrowserSession) -> bool {
+    let top = browser.execute_script("return window === window.top;").await.unwrap();
+    top == json!(true)
+}
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_click_two_frames_deep_returns_to_main_page() {
+    let browser = Arc::new(BrowserSession::new(BrowserConfig::new()));
+    browser.start().await.unwrap();
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/nested_frames.html");
+    browser.navigate(&format!("file://{fixture}")).await.unwrap();
+
+    let click = ClickTool::new(browser.clone());
+    let result = click
+        .execute(
+            ctx(),
+            json!({ "selector": "#confirm", "frame_path": ["#payment", "#confirm-frame"] }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result["success"], true);
+    assert!(at_top_level(&browser).await);
+
+    // Indices work as well as selectors.
+    let extract = ExtractTextTool::new(browser.clone());
+    let result =
+        extract.execute(ctx(), json!({ "selector": "#confirm", "frame_path": [0, "0"] })).await;
+    assert_eq!(result.unwrap()["text"], "Confirmed");
+    assert!(at_top_level(&browser).await);
+
+    // A path that cannot be followed names the failing step and still leaves
+    // the session on the main page.
+    let error = click
+        .execute(ctx(), json!({ "selector": "#confirm", "frame_path": ["#payment", "#missing"] }))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("step 1 ('#missing')"), "{error}");
+    assert!(at_top_level(&browser).await);
+
+    browser.stop().await.unwrap();
+}
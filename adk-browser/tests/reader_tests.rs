@@ -0,0 +1,56 @@
+//! Reader-mode extraction against a fixture page with typical boilerplate.
+
+use adk_browser::extract_reader_content;
+
+const ARTICLE: &str = include_str!("fixtures/reader_article.html");
+
+#[test]
+fn test_extracts_article_and_metadata() {
+    let content = extract_reader_content(ARTICLE);
+
+    assert!(content.confident);
+    assert_eq!(content.title.as_deref(), Some("What Lives in a Tide Pool"));
+    assert_eq!(content.byline.as_deref(), Some("Rosa Delgado"));
+
+    let paragraphs: Vec<&str> = content.text.split("\n\n").collect();
+    assert_eq!(paragraphs.len(), 7, "{}", content.text);
+    assert_eq!(paragraphs[0], "What Lives in a Tide Pool");
+    assert!(paragraphs[2].starts_with("Twice a day the sea pulls back from the rocky shore"));
+    assert_eq!(paragraphs[4], "Visiting responsibly");
+    assert!(paragraphs[6].ends_with("which can arrive without warning on calm days."));
+
+    assert_eq!(content.word_count, content.text.split_whitespace().count());
+    assert_eq!(content.reading_time_minutes, 1);
+}
+
+#[test]
+fn test_strips_boilerplate() {
+    let text = extract_reader_content(ARTICLE).text;
+
+    for boilerplate in [
+        "Coastal Notes",
+        "Guides",
+        "wetsuits",
+        "Share on",
+        "Popular this week",
+        "Subscribe",
+        "cookies",
+        "Copyright",
+        "analytics",
+        "font-family",
+    ] {
+        assert!(!text.contains(boilerplate), "found {boilerplate:?} in:\n{text}");
+    }
+}
+
+#[test]
+fn test_low_confidence_without_main_content() {
+    let content = extract_reader_content(
+        r#"<html><head><title>Login</title></head>
+           <body><nav><a href="/">Home</a></nav><form><input name="user"></form></body></html>"#,
+    );
+
+    assert!(!content.confident);
+    assert_eq!(content.title.as_deref(), Some("Login"));
+    assert!(content.text.is_empty());
+}
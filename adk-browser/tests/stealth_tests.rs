@@ -0,0 +1,36 @@
+//! Stealth mode against a real browser.
+//!
+//! Requires a Chrome WebDriver server on localhost:4444. Run with:
+//! `cargo test -p adk-browser --test stealth_tests -- --ignored`
+
+use adk_browser::{BrowserConfig, BrowserSession};
+
+/// A local page that records what its own scripts see of `navigator.webdriver`.
+const PAGE: &str = "data:text/html,<script>document.title = String(navigator.webdriver);</script>";
+
+#[tokio::test]
+#[ignore = "Requires a WebDriver server on localhost:4444 - run with: cargo test -p adk-browser -- --ignored"]
+async fn test_stealth_hides_webdriver_flag() {
+    let plain = BrowserSession::new(BrowserConfig::new());
+    plain.navigate(PAGE).await.unwrap();
+    assert_eq!(plain.title().await.unwrap(), "true");
+    plain.stop().await.unwrap();
+
+    let browser = BrowserSession::new(BrowserConfig::new().with_stealth(true));
+    browser.navigate(PAGE).await.unwrap();
+
+    // The page's own script ran after the stealth script was injected.
+    assert_eq!(browser.title().await.unwrap(), "undefined");
+    assert_eq!(
+        browser.execute_script("return navigator.webdriver;").await.unwrap(),
+        serde_json::Value::Null
+    );
+    let user_agent = browser.execute_script("return navigator.userAgent;").await.unwrap();
+    assert!(!user_agent.as_str().unwrap().contains("Headless"));
+    assert_eq!(
+        browser.execute_script("return navigator.languages;").await.unwrap(),
+        serde_json::json!(["en-US", "en"])
+    );
+
+    browser.stop().await.unwrap();
+}
@@ -139,6 +139,7 @@ pub struct Launcher {
     shutdown_grace_period: Duration,
     run_config: Option<RunConfig>,
     thinking_mode: ThinkingDisplayMode,
+    warm_up_on_start: bool,
 }
 
 impl Launcher {
@@ -160,6 +161,7 @@ impl Launcher {
             shutdown_grace_period: Duration::from_secs(30),
             run_config: None,
             thinking_mode: ThinkingDisplayMode::Auto,
+            warm_up_on_start: false,
         }
     }
 
@@ -252,6 +254,14 @@ impl Launcher {
         self
     }
 
+    /// Warm up the agent before serving and refuse to start if any component fails.
+    ///
+    /// See [`Agent::warm_up`]. The same check is always available at `GET /api/ready`.
+    pub fn with_warm_up_on_start(mut self, enabled: bool) -> Self {
+        self.warm_up_on_start = enabled;
+        self
+    }
+
     /// Run the launcher, parsing CLI arguments.
     ///
     /// - No arguments or `chat`: Interactive console
@@ -368,6 +378,8 @@ impl Launcher {
                     let mut current_agent = String::new();
                     let mut printed_header = false;
                     let mut interrupted = false;
+                    let turn_started_ms = unix_millis_now();
+                    let mut last_event_ms: Option<i64> = None;
 
                     loop {
                         tokio::select! {
@@ -378,6 +390,8 @@ impl Launcher {
 
                                 match event {
                                     Ok(evt) => {
+                                        last_event_ms = Some(evt.timestamp.timestamp_millis());
+
                                         // Track agent switches in multi-agent workflows
                                         if !evt.author.is_empty()
                                             && evt.author != "user"
@@ -433,7 +447,13 @@ impl Launcher {
                         continue;
                     }
 
-                    println!("\n");
+                    println!();
+                    // Time from sending the message to the turn's last event
+                    if let Some(last) = last_event_ms {
+                        let secs = (last - turn_started_ms).max(0) as f64 / 1000.0;
+                        println!("\x1b[90m[{secs:.1}s]\x1b[0m");
+                    }
+                    println!();
                 }
                 Err(rustyline::error::ReadlineError::Interrupted) => {
                     println!("\nInterrupted. Type exit to quit.\n");
@@ -547,6 +567,21 @@ impl Launcher {
     /// Use this when you already know you want serve mode (e.g. from
     /// your own CLI parser). [`run`](Self::run) calls this internally.
     pub async fn run_serve_directly(self, port: u16) -> Result<()> {
+        if self.warm_up_on_start {
+            let report = self.agent.warm_up().await?;
+            for status in &report.components {
+                let outcome = if status.ready { "ok" } else { "FAILED" };
+                println!("warm-up {}: {outcome}", status.component);
+            }
+            if !report.is_ready() {
+                let failed: Vec<_> = report.failures().map(|s| s.component.as_str()).collect();
+                return Err(adk_core::AdkError::config(format!(
+                    "agent warm-up failed for: {}",
+                    failed.join(", ")
+                )));
+            }
+        }
+
         let app = self.build_app()?;
 
         let addr = format!("0.0.0.0:{port}");
@@ -621,6 +656,13 @@ fn print_help() {
     println!();
 }
 
+/// Milliseconds since the Unix epoch, comparable with `Event::timestamp`.
+fn unix_millis_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX))
+}
+
 fn is_exit_command(input: &str) -> bool {
     matches!(input, "quit" | "exit" | "/quit" | "/exit")
 }
@@ -1,6 +1,8 @@
-use crate::{InvocationContext, Result, event::Event};
+use crate::{InvocationContext, Result, Tool, event::Event};
 use async_trait::async_trait;
 use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -24,6 +26,229 @@ pub trait Agent: Send + Sync {
 
     /// Executes the agent and returns a stream of events.
     async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream>;
+
+    /// Checks that the agent's external dependencies are usable before serving traffic.
+    ///
+    /// Failures are recorded per component in the returned [`WarmupReport`]
+    /// rather than returned as errors, so one report covers every problem.
+    /// The default implementation checks nothing itself and merges the reports
+    /// of its [`sub_agents`](Self::sub_agents).
+    async fn warm_up(&self) -> Result<WarmupReport> {
+        let mut report = WarmupReport::new(self.name());
+        for sub_agent in self.sub_agents() {
+            report.merge(sub_agent.warm_up().await?);
+        }
+        Ok(report)
+    }
+
+    /// Returns the global instruction this agent applies to its whole tree
+    /// when it is the root agent of a run.
+    ///
+    /// The runner applies only the root agent's; sub-agents that report one
+    /// are ignored. The default returns `None`.
+    fn global_instruction(&self) -> Option<crate::GlobalInstruction> {
+        None
+    }
+
+    /// Returns the version label this agent was built with, if any.
+    fn version(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns a stable hex SHA-256 identifying this agent's configuration.
+    ///
+    /// Agents configured the same way hash equal across processes, so eval
+    /// baselines and response caches can key on it and a configuration
+    /// change invalidates them. The default implementation hashes the name,
+    /// description, version, and the hashes of the sub-agents. Agents that
+    /// call a model override it to include their prompt, tools, and model.
+    fn config_hash(&self) -> String {
+        let sub_agents: Vec<String> =
+            self.sub_agents().iter().map(|agent| agent.config_hash()).collect();
+        crate::value_fingerprint(&serde_json::json!({
+            "name": self.name(),
+            "description": self.description(),
+            "version": self.version(),
+            "sub_agents": sub_agents,
+        }))
+    }
+
+    /// Describes this agent's configuration for UIs, documentation, and
+    /// access-control setup, without downcasting to the concrete type.
+    ///
+    /// The default implementation reports the name, description, and
+    /// sub-agent names. Agents that call a model or declare tools override it.
+    fn info(&self) -> AgentInfo {
+        AgentInfo::new(self.name(), self.description()).with_sub_agents(self.sub_agents())
+    }
+}
+
+/// Static description of an assembled agent, returned by [`Agent::info`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentInfo {
+    /// The agent's name.
+    pub name: String,
+    /// The agent's description.
+    pub description: String,
+    /// Identifier of the model the agent calls, if it calls one directly.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub model: Option<String>,
+    /// Tools registered on the agent. Tools a toolset resolves per invocation
+    /// are not listed.
+    #[serde(default)]
+    pub tools: Vec<ToolInfo>,
+    /// Names of the agent's direct sub-agents, in order.
+    #[serde(default)]
+    pub sub_agents: Vec<String>,
+    /// Features the agent declares, such as `"output_schema"`, `"transfer"`,
+    /// or a workflow kind like `"sequential"`.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// JSON Schema the agent expects its input to match, if it declares one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub input_schema: Option<Value>,
+    /// JSON Schema the agent's final response follows, if it declares one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub output_schema: Option<Value>,
+}
+
+impl AgentInfo {
+    /// Creates a description with no model, tools, sub-agents, or capabilities.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self { name: name.into(), description: description.into(), ..Default::default() }
+    }
+
+    /// Sets the model identifier.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Adds a [`ToolInfo`] for each tool.
+    pub fn with_tools(mut self, tools: &[Arc<dyn Tool>]) -> Self {
+        self.tools.extend(tools.iter().map(|tool| ToolInfo::from_tool(tool.as_ref())));
+        self
+    }
+
+    /// Adds the name of each sub-agent.
+    pub fn with_sub_agents(mut self, sub_agents: &[Arc<dyn Agent>]) -> Self {
+        self.sub_agents.extend(sub_agents.iter().map(|agent| agent.name().to_string()));
+        self
+    }
+
+    /// Adds a declared capability.
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capabilities.push(capability.into());
+        self
+    }
+
+    /// Sets the input schema and declares the `"input_schema"` capability.
+    pub fn with_input_schema(mut self, schema: Value) -> Self {
+        self.input_schema = Some(schema);
+        self.with_capability("input_schema")
+    }
+
+    /// Sets the output schema and declares the `"output_schema"` capability.
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.output_schema = Some(schema);
+        self.with_capability("output_schema")
+    }
+
+    /// Returns `true` if the agent declares `capability`.
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
+    /// Looks up a tool by name.
+    pub fn tool(&self, name: &str) -> Option<&ToolInfo> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+}
+
+/// Name and schemas of one tool, as listed in [`AgentInfo::tools`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolInfo {
+    /// The tool's name.
+    pub name: String,
+    /// The tool's description.
+    pub description: String,
+    /// JSON Schema of the tool's parameters, if it declares one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parameters_schema: Option<Value>,
+    /// JSON Schema of the tool's response, if it declares one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub response_schema: Option<Value>,
+    /// Scopes a caller must hold to execute the tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_scopes: Vec<String>,
+}
+
+impl ToolInfo {
+    /// Describes `tool`.
+    pub fn from_tool(tool: &dyn Tool) -> Self {
+        Self {
+            name: tool.name().to_string(),
+            description: tool.description().to_string(),
+            parameters_schema: tool.parameters_schema(),
+            response_schema: tool.response_schema(),
+            required_scopes: tool.required_scopes().iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Outcome of warming up a single component, such as a model or toolset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    /// Component identifier, e.g. `"model:gemini-2.5-flash"` or `"toolset:github"`.
+    pub component: String,
+    /// Whether the component responded successfully.
+    pub ready: bool,
+    /// The failure reason when `ready` is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Per-component readiness returned by [`Agent::warm_up`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmupReport {
+    /// Name of the agent that produced the report.
+    pub agent: String,
+    /// Status of every component checked, including those of sub-agents.
+    pub components: Vec<ComponentStatus>,
+}
+
+impl WarmupReport {
+    /// Creates an empty report for the named agent.
+    pub fn new(agent: impl Into<String>) -> Self {
+        Self { agent: agent.into(), components: Vec::new() }
+    }
+
+    /// Records the outcome of checking `component`.
+    pub fn record<T>(&mut self, component: impl Into<String>, outcome: &Result<T>) {
+        self.components.push(ComponentStatus {
+            component: component.into(),
+            ready: outcome.is_ok(),
+            error: outcome.as_ref().err().map(ToString::to_string),
+        });
+    }
+
+    /// Appends the components of a sub-agent's report, prefixed with its agent name.
+    pub fn merge(&mut self, other: WarmupReport) {
+        self.components.extend(other.components.into_iter().map(|mut status| {
+            status.component = format!("{}/{}", other.agent, status.component);
+            status
+        }));
+    }
+
+    /// Returns `true` when every checked component is ready.
+    pub fn is_ready(&self) -> bool {
+        self.components.iter().all(|status| status.ready)
+    }
+
+    /// Returns the components that failed to warm up.
+    pub fn failures(&self) -> impl Iterator<Item = &ComponentStatus> {
+        self.components.iter().filter(|status| !status.ready)
+    }
 }
 
 /// A validated context containing engineered instructions and resolved tool instances.
@@ -151,9 +376,6 @@ mod tests {
         fn agent(&self) -> Arc<dyn Agent> {
             unimplemented!()
         }
-        fn memory(&self) -> Option<Arc<dyn crate::Memory>> {
-            None
-        }
         fn session(&self) -> &dyn Session {
             &self.session
         }
@@ -194,4 +416,27 @@ mod tests {
         assert_eq!(agent.name(), "test");
         assert_eq!(agent.description(), "test agent");
     }
+
+    #[tokio::test]
+    async fn test_default_warm_up_is_ready() {
+        let agent = TestAgent { name: "test".to_string() };
+        let report = agent.warm_up().await.unwrap();
+        assert_eq!(report.agent, "test");
+        assert!(report.components.is_empty());
+        assert!(report.is_ready());
+    }
+
+    #[test]
+    fn test_warmup_report_merge_prefixes_sub_agent() {
+        let mut child = WarmupReport::new("child");
+        child.record("model:m", &Err::<(), _>(crate::AdkError::model("bad key")));
+
+        let mut report = WarmupReport::new("root");
+        report.record("model:m", &Ok(()));
+        report.merge(child);
+
+        assert!(!report.is_ready());
+        let failures: Vec<_> = report.failures().map(|s| s.component.as_str()).collect();
+        assert_eq!(failures, ["child/model:m"]);
+    }
 }
@@ -0,0 +1,155 @@
+//! Run-scoped scratch space shared by agents and tools.
+//!
+//! A [`Blackboard`] lives for exactly one `Runner::run()` call. Every agent,
+//! sub-agent, tool, and callback in that run sees the same instance through
+//! [`CallbackContext::blackboard`](crate::CallbackContext::blackboard), and the
+//! runner clears it when the run ends. Nothing on it is written to the session.
+//!
+//! # Blackboard vs. `temp:` state
+//!
+//! Session state keys prefixed with `temp:` are also discarded, but they still
+//! travel through the event stream as JSON state deltas, are visible to
+//! instruction templating, and are applied by the session service. Use the
+//! blackboard for values that are only meaningful to code in the current run
+//! (a parsed plan, an intermediate result, a handle) and that should never be
+//! serialized or reach the model unless a tool chooses to return them.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[derive(Clone)]
+//! struct Plan { steps: Vec<String> }
+//!
+//! // In a planning tool
+//! if let Some(board) = ctx.blackboard() {
+//!     board.set("plan", Plan { steps: vec!["fetch".into(), "summarize".into()] });
+//! }
+//!
+//! // Later, in another tool of the same run
+//! let plan: Option<Plan> = ctx.blackboard().and_then(|board| board.get("plan"));
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// Thread-safe, typed key-value store scoped to a single run.
+///
+/// Values are stored as-is (no serialization), so any `Send + Sync` type can be
+/// shared. [`get`](Self::get) returns `None` both when the key is missing and
+/// when it holds a value of a different type.
+#[derive(Default)]
+pub struct Blackboard {
+    entries: RwLock<HashMap<String, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Blackboard {
+    /// Creates an empty blackboard.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    pub fn set<T: Any + Send + Sync>(&self, key: impl Into<String>, value: T) {
+        self.write().insert(key.into(), Arc::new(value));
+    }
+
+    /// Returns a clone of the value under `key` if it exists and has type `T`.
+    pub fn get<T: Any + Clone>(&self, key: &str) -> Option<T> {
+        self.read().get(key).and_then(|value| value.downcast_ref::<T>()).cloned()
+    }
+
+    /// Returns a shared handle to the value under `key` if it has type `T`.
+    ///
+    /// Avoids cloning large values; the handle stays valid after the entry is
+    /// replaced or the blackboard is cleared.
+    pub fn get_arc<T: Any + Send + Sync>(&self, key: &str) -> Option<Arc<T>> {
+        self.read().get(key).cloned().and_then(|value| value.downcast::<T>().ok())
+    }
+
+    /// Removes the entry under `key`, returning whether one existed.
+    pub fn remove(&self, key: &str) -> bool {
+        self.write().remove(key).is_some()
+    }
+
+    /// Returns whether an entry exists under `key`, regardless of its type.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.read().contains_key(key)
+    }
+
+    /// Returns the keys currently on the blackboard.
+    pub fn keys(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns whether the blackboard has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, Arc<dyn Any + Send + Sync>>> {
+        self.entries.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(
+        &self,
+    ) -> std::sync::RwLockWriteGuard<'_, HashMap<String, Arc<dyn Any + Send + Sync>>> {
+        self.entries.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for Blackboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys = self.keys();
+        keys.sort();
+        f.debug_struct("Blackboard").field("keys", &keys).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Plan {
+        steps: Vec<&'static str>,
+    }
+
+    #[test]
+    fn test_typed_get_and_set() {
+        let board = Blackboard::new();
+        board.set("plan", Plan { steps: vec!["fetch", "summarize"] });
+        board.set("attempts", 2u32);
+
+        assert_eq!(board.get::<Plan>("plan").unwrap().steps, ["fetch", "summarize"]);
+        assert_eq!(board.get::<u32>("attempts"), Some(2));
+        assert_eq!(board.get::<String>("attempts"), None);
+        assert_eq!(board.get::<u32>("missing"), None);
+        assert!(board.contains_key("attempts"));
+    }
+
+    #[test]
+    fn test_get_arc_and_clear() {
+        let board = Blackboard::new();
+        board.set("rows", vec![1, 2, 3]);
+
+        let rows = board.get_arc::<Vec<i32>>("rows").unwrap();
+        board.clear();
+
+        assert!(board.is_empty());
+        assert_eq!(*rows, [1, 2, 3]);
+        assert!(!board.remove("rows"));
+    }
+}
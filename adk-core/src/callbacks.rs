@@ -103,6 +103,36 @@ pub type InstructionProvider = Box<
 /// Alias for [`InstructionProvider`] used at the global (runner) level.
 pub type GlobalInstructionProvider = InstructionProvider;
 
+/// An agent's global instruction, as reported by
+/// [`Agent::global_instruction`](crate::Agent::global_instruction).
+///
+/// The runner takes the root agent's and stores it in
+/// [`RunConfig::global_instruction`](crate::RunConfig::global_instruction),
+/// so every LLM agent in the tree applies it before its own instruction.
+#[derive(Clone)]
+pub enum GlobalInstruction {
+    /// A template whose `{key}` placeholders are filled from session state.
+    Template(String),
+    /// A provider run before every model call.
+    Provider(Arc<GlobalInstructionProvider>),
+}
+
+impl Default for GlobalInstruction {
+    /// An empty template, which adds nothing to requests.
+    fn default() -> Self {
+        Self::Template(String::new())
+    }
+}
+
+impl std::fmt::Debug for GlobalInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Template(template) => f.debug_tuple("Template").field(template).finish(),
+            Self::Provider(_) => f.write_str("Provider(<fn>)"),
+        }
+    }
+}
+
 // ===== Error Callbacks =====
 
 /// Callback invoked when a tool execution fails (after retries are exhausted).
@@ -0,0 +1,103 @@
+//! Injectable source of the current time.
+//!
+//! Services that stamp or expire data (memory TTLs, session timestamps, cache
+//! expiry) read the time through a [`Clock`] instead of calling
+//! `Utc::now()` directly. They default to [`SystemClock`], so nothing changes
+//! in production; tests hand them a [`MockClock`] and move time forward with
+//! [`MockClock::advance`] instead of sleeping.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::{Clock, MockClock};
+//! use std::time::Duration;
+//!
+//! let clock = MockClock::default();
+//! let start = clock.now();
+//! clock.advance(Duration::from_secs(90));
+//! assert_eq!((clock.now() - start).num_seconds(), 90);
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A source of the current wall-clock time.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The real clock, backed by the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Returns a shared [`SystemClock`], the default clock of every service.
+pub fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and pass another
+/// to the service under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: Arc::new(Mutex::new(start)) }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let delta = chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now = now.checked_add_signed(delta).unwrap_or(DateTime::<Utc>::MAX_UTC);
+    }
+
+    /// Sets the clock to `now`, which may be in the past.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+}
+
+/// Starts at 2025-01-01T00:00:00Z.
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(DateTime::from_timestamp(1_735_689_600, 0).unwrap_or_default())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_clones_share_time() {
+        let clock = MockClock::default();
+        let handle = clock.clone();
+        let start = clock.now();
+
+        handle.advance(Duration::from_millis(1500));
+        assert_eq!(clock.now() - start, chrono::Duration::milliseconds(1500));
+
+        clock.set(start);
+        assert_eq!(handle.now(), start);
+    }
+}
@@ -0,0 +1,202 @@
+//! Draining an [`EventStream`](crate::EventStream) into one final response.
+//!
+//! Tests, [`Tool`](crate::Tool) wrappers around agents, evaluators and servers
+//! all need the same thing from a run: the answer text, the tool traffic, and
+//! the token usage. [`collect_final_response`] does that once, correctly for
+//! both streamed and non-streamed runs.
+//!
+//! In streaming mode a model response arrives as several `partial` events
+//! followed by a closing non-partial event. Depending on the producer, the
+//! closing event carries either the last chunk or the whole response again.
+//! Chunks are buffered per author and branch, and the closing event replaces
+//! them when its text starts with the buffered text; otherwise it is appended.
+//! Either way, each response is counted once.
+
+use crate::{Event, Part, Result, UsageMetadata};
+use futures::{Stream, StreamExt};
+
+/// What a run produced, as gathered by [`collect_final_response`].
+#[derive(Debug, Clone, Default)]
+pub struct CollectedResponse {
+    /// Text of the final response, with its streamed chunks merged.
+    pub text: String,
+    /// Every [`Part::FunctionCall`] the run produced, in order.
+    pub function_calls: Vec<Part>,
+    /// Every [`Part::FunctionResponse`] the run produced, in order.
+    pub function_responses: Vec<Part>,
+    /// Token usage summed over all model responses, or `None` if no event
+    /// reported any.
+    pub usage_metadata: Option<UsageMetadata>,
+    /// The last event that was a [final response](Event::is_final_response)
+    /// with content, as emitted. In streaming mode its content is only the
+    /// last chunk; use [`text`](Self::text) for the whole answer.
+    pub final_event: Option<Event>,
+}
+
+impl CollectedResponse {
+    /// Collects events that were already gathered, e.g. from a session.
+    pub fn from_events(events: impl IntoIterator<Item = Event>) -> Self {
+        let mut collector = Collector::default();
+        for event in events {
+            collector.push(event);
+        }
+        collector.finish()
+    }
+}
+
+/// Drains `stream` and returns the final response, the tool traffic, and the
+/// summed token usage.
+///
+/// The first error in the stream is returned as-is and the rest of the stream
+/// is dropped.
+///
+/// ```rust
+/// use adk_core::{Content, Event, collect_final_response};
+///
+/// # futures::executor::block_on(async {
+/// let mut event = Event::new("inv-1");
+/// event.llm_response.content = Some(Content::new("model").with_text("4"));
+/// let stream = futures::stream::iter(vec![Ok(event)]);
+///
+/// let collected = collect_final_response(stream).await?;
+/// assert_eq!(collected.text, "4");
+/// # Ok::<(), adk_core::AdkError>(())
+/// # }).unwrap();
+/// ```
+pub async fn collect_final_response<S>(stream: S) -> Result<CollectedResponse>
+where
+    S: Stream<Item = Result<Event>>,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut collector = Collector::default();
+    while let Some(event) = stream.next().await {
+        collector.push(event?);
+    }
+    Ok(collector.finish())
+}
+
+/// Streamed chunks of one author's response that have not been closed yet.
+#[derive(Default)]
+struct Pending {
+    author: String,
+    branch: String,
+    parts: Vec<Part>,
+    usage: Option<UsageMetadata>,
+}
+
+#[derive(Default)]
+struct Collector {
+    pending: Vec<Pending>,
+    collected: CollectedResponse,
+}
+
+impl Collector {
+    fn push(&mut self, event: Event) {
+        // Tool progress is live terminal output, not part of any response.
+        if event.tool_progress_stream().is_some() {
+            return;
+        }
+        // A sub-agent's forwarded output belongs to its tool call, whose
+        // result arrives as a function response.
+        if event.sub_agent_call_id().is_some() {
+            return;
+        }
+        let parts =
+            event.llm_response.content.as_ref().map(|c| c.parts.clone()).unwrap_or_default();
+        let index =
+            self.pending.iter().position(|p| p.author == event.author && p.branch == event.branch);
+
+        if event.llm_response.partial {
+            let pending = match index {
+                Some(index) => &mut self.pending[index],
+                None => {
+                    self.pending.push(Pending {
+                        author: event.author.clone(),
+                        branch: event.branch.clone(),
+                        ..Default::default()
+                    });
+                    self.pending.last_mut().expect("just pushed")
+                }
+            };
+            pending.parts.extend(parts);
+            if let Some(usage) = &event.llm_response.usage_metadata {
+                pending.usage = Some(usage.clone());
+            }
+            return;
+        }
+
+        let pending = index.map(|index| self.pending.remove(index)).unwrap_or_default();
+        let parts = if repeats(&pending.parts, &parts) {
+            parts
+        } else {
+            let mut merged = pending.parts;
+            merged.extend(parts);
+            merged
+        };
+        let usage = event.llm_response.usage_metadata.clone().or(pending.usage);
+        self.record(&parts, usage);
+
+        if event.is_final_response() && event.llm_response.content.is_some() {
+            self.collected.text = parts.iter().filter_map(Part::text).collect();
+            self.collected.final_event = Some(event);
+        }
+    }
+
+    fn record(&mut self, parts: &[Part], usage: Option<UsageMetadata>) {
+        for part in parts {
+            match part {
+                Part::FunctionCall { .. } => self.collected.function_calls.push(part.clone()),
+                Part::FunctionResponse { .. } => {
+                    self.collected.function_responses.push(part.clone())
+                }
+                _ => {}
+            }
+        }
+        if let Some(usage) = usage {
+            match &mut self.collected.usage_metadata {
+                Some(total) => add_usage(total, &usage),
+                None => self.collected.usage_metadata = Some(usage),
+            }
+        }
+    }
+
+    fn finish(mut self) -> CollectedResponse {
+        // Chunks whose response never closed still count as tool traffic and usage.
+        for pending in std::mem::take(&mut self.pending) {
+            self.record(&pending.parts, pending.usage);
+        }
+        self.collected
+    }
+}
+
+/// Whether a closing event's `parts` repeat the buffered chunks rather than
+/// continue them.
+fn repeats(pending: &[Part], parts: &[Part]) -> bool {
+    let text = |parts: &[Part]| parts.iter().filter_map(Part::text).collect::<String>();
+    let calls =
+        |parts: &[Part]| parts.iter().filter(|p| matches!(p, Part::FunctionCall { .. })).count();
+    text(parts).starts_with(&text(pending)) && calls(parts) >= calls(pending)
+}
+
+fn add_usage(total: &mut UsageMetadata, usage: &UsageMetadata) {
+    fn add<T: std::ops::Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        }
+    }
+
+    total.prompt_token_count += usage.prompt_token_count;
+    total.candidates_token_count += usage.candidates_token_count;
+    total.total_token_count += usage.total_token_count;
+    total.cache_read_input_token_count =
+        add(total.cache_read_input_token_count, usage.cache_read_input_token_count);
+    total.cache_creation_input_token_count =
+        add(total.cache_creation_input_token_count, usage.cache_creation_input_token_count);
+    total.thinking_token_count = add(total.thinking_token_count, usage.thinking_token_count);
+    total.audio_input_token_count =
+        add(total.audio_input_token_count, usage.audio_input_token_count);
+    total.audio_output_token_count =
+        add(total.audio_output_token_count, usage.audio_output_token_count);
+    total.cost = add(total.cost, usage.cost);
+}
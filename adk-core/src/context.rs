@@ -1,7 +1,10 @@
 use crate::identity::{AdkIdentity, AppName, ExecutionIdentity, InvocationId, SessionId, UserId};
-use crate::{AdkError, Agent, Result, Toolset, types::Content};
+use crate::{
+    AdkError, Agent, ErrorCategory, ErrorComponent, Event, Result, ToolOutputLimitConfig, Toolset,
+    types::Content,
+};
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
@@ -232,6 +235,81 @@ pub trait ReadonlyState: Send + Sync {
     fn all(&self) -> HashMap<String, Value>;
 }
 
+impl dyn State + '_ {
+    /// Returns the value for `key` deserialized as `T`, or `None` if not present.
+    ///
+    /// Keys are used verbatim, so `user:`, `app:` and `temp:` prefixed keys
+    /// read the same scoped values as [`get`](State::get).
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the stored JSON does not match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let prefs: Option<Preferences> = ctx.session().state().get_as("user:preferences")?;
+    /// ```
+    pub fn get_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key).map(|value| state_value_as(key, value)).transpose()
+    }
+
+    /// Serializes `value` to JSON and stores it under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` fails [`validate_state_key`] or `value` cannot
+    /// be serialized.
+    pub fn set_as<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        let value = state_value_from(key, value)?;
+        self.set(key.to_string(), value);
+        Ok(())
+    }
+}
+
+impl dyn ReadonlyState + '_ {
+    /// Returns the value for `key` deserialized as `T`, or `None` if not present.
+    ///
+    /// Behaves like the `get_as` helper on [`State`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the stored JSON does not match `T`.
+    pub fn get_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key).map(|value| state_value_as(key, value)).transpose()
+    }
+}
+
+/// Deserializes a stored state value, naming the key and target type on mismatch.
+pub(crate) fn state_value_as<T: DeserializeOwned>(key: &str, value: Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| {
+        AdkError::serialization(
+            ErrorComponent::Session,
+            format!("state key '{key}' is not a valid {}: {e}", std::any::type_name::<T>()),
+        )
+        .with_source(e)
+    })
+}
+
+/// Validates `key` and serializes `value` for storage under it.
+pub(crate) fn state_value_from<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<Value> {
+    validate_state_key(key).map_err(|msg| {
+        AdkError::new(
+            ErrorComponent::Session,
+            ErrorCategory::InvalidInput,
+            "session.invalid_state_key",
+            format!("invalid state key '{key}': {msg}"),
+        )
+    })?;
+    serde_json::to_value(value).map_err(|e| {
+        AdkError::serialization(
+            ErrorComponent::Session,
+            format!("value for state key '{key}' could not be serialized: {e}"),
+        )
+        .with_source(e)
+    })
+}
+
 // Session trait
 /// Represents an active conversation session with identity and state.
 pub trait Session: Send + Sync {
@@ -257,6 +335,16 @@ pub trait Session: Send + Sync {
     fn conversation_history_for_agent(&self, _agent_name: &str) -> Vec<Content> {
         self.conversation_history()
     }
+    /// Returns conversation history built only from events accepted by `keep`.
+    ///
+    /// Function responses are kept or dropped together with the call that
+    /// produced them, whatever `keep` says about the response event itself.
+    ///
+    /// Default implementation delegates to [`conversation_history`](Self::conversation_history),
+    /// for sessions that do not retain events.
+    fn conversation_history_filtered(&self, _keep: &dyn Fn(&Event) -> bool) -> Vec<Content> {
+        self.conversation_history()
+    }
     /// Append content to conversation history (for sequential agent support)
     fn append_to_history(&self, _content: Content) {
         // Default no-op - implementations can override to track history
@@ -363,6 +451,11 @@ pub trait CallbackContext: ReadonlyContext {
     /// Returns the artifact store, if one is configured.
     fn artifacts(&self) -> Option<Arc<dyn Artifacts>>;
 
+    /// Returns the memory service, if one is configured.
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        None
+    }
+
     /// Returns structured metadata about the most recent tool execution.
     /// Available in after-tool callbacks and plugin hooks.
     /// Returns `None` when not in a tool execution context.
@@ -387,6 +480,57 @@ pub trait CallbackContext: ReadonlyContext {
     fn shared_state(&self) -> Option<Arc<crate::SharedState>> {
         None
     }
+
+    /// Returns the run-scoped [`Blackboard`](crate::Blackboard).
+    ///
+    /// The runner creates one blackboard per `run()` and shares it with every
+    /// agent, sub-agent, and tool in that run; it is cleared when the run ends
+    /// and never persisted. Returns `None` outside a runner-managed run.
+    fn blackboard(&self) -> Option<Arc<crate::Blackboard>> {
+        None
+    }
+}
+
+impl dyn CallbackContext + '_ {
+    /// Saves `data` as a new version of artifact `name` and returns the version.
+    ///
+    /// Callbacks share the artifact store with tools, so versions continue
+    /// from any a tool saved under the same name. Fails with an `Unsupported`
+    /// error when the run has no artifact service.
+    pub async fn save_artifact(&self, name: &str, data: &crate::Part) -> Result<i64> {
+        self.require_artifacts()?.save(name, data).await
+    }
+
+    /// Loads the latest version of artifact `name`.
+    ///
+    /// Fails with an `Unsupported` error when the run has no artifact service.
+    pub async fn load_artifact(&self, name: &str) -> Result<crate::Part> {
+        self.require_artifacts()?.load(name).await
+    }
+
+    /// Searches the memory service for entries matching `query`.
+    ///
+    /// Fails with an `Unsupported` error when the run has no memory service.
+    pub async fn search_memory(&self, query: &str) -> Result<Vec<MemoryEntry>> {
+        let memory = self.memory().ok_or_else(|| {
+            AdkError::unsupported(
+                ErrorComponent::Memory,
+                "memory.not_configured",
+                "no memory service is configured for this run",
+            )
+        })?;
+        memory.search(query).await
+    }
+
+    fn require_artifacts(&self) -> Result<Arc<dyn Artifacts>> {
+        self.artifacts().ok_or_else(|| {
+            AdkError::unsupported(
+                ErrorComponent::Artifact,
+                "artifact.not_configured",
+                "no artifact service is configured for this run",
+            )
+        })
+    }
 }
 
 /// Wraps a [`CallbackContext`] to inject tool name and input for before-tool
@@ -462,6 +606,10 @@ impl CallbackContext for ToolCallbackContext {
         self.inner.artifacts()
     }
 
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        self.inner.memory()
+    }
+
     fn tool_outcome(&self) -> Option<ToolOutcome> {
         self.inner.tool_outcome()
     }
@@ -477,18 +625,20 @@ impl CallbackContext for ToolCallbackContext {
     fn shared_state(&self) -> Option<Arc<crate::SharedState>> {
         self.inner.shared_state()
     }
+
+    fn blackboard(&self) -> Option<Arc<crate::Blackboard>> {
+        self.inner.blackboard()
+    }
 }
 
 /// Full invocation context available to agents during execution.
 ///
-/// Extends [`CallbackContext`] with access to the agent itself, memory,
-/// session, and run configuration.
+/// Extends [`CallbackContext`] with access to the agent itself, the session,
+/// and run configuration.
 #[async_trait]
 pub trait InvocationContext: CallbackContext {
     /// Returns the agent being executed.
     fn agent(&self) -> Arc<dyn Agent>;
-    /// Returns the memory service, if one is configured.
-    fn memory(&self) -> Option<Arc<dyn Memory>>;
     /// Returns the current session.
     fn session(&self) -> &dyn Session;
     /// Returns the run configuration for this invocation.
@@ -508,7 +658,10 @@ pub trait InvocationContext: CallbackContext {
     /// cleanup instead of running to natural completion.
     ///
     /// The default returns `false`. The runtime sets the underlying token when
-    /// `Runner::interrupt()` is called or `RunConfig::cancellation_token` fires.
+    /// `Runner::interrupt()` is called, the token returned by
+    /// `Runner::run_cancellable` is cancelled, or the runner's own
+    /// cancellation token fires. The run's event stream then ends with an
+    /// [`ErrorCategory::Cancelled`](crate::ErrorCategory::Cancelled) error.
     fn is_cancelled(&self) -> bool {
         false
     }
@@ -530,6 +683,34 @@ pub trait InvocationContext: CallbackContext {
         HashMap::new()
     }
 
+    /// Returns the run's shared [`ConversationBuffer`](crate::ConversationBuffer).
+    ///
+    /// The runner creates one per `run()`, seeds it with the loaded session
+    /// history, and records every persisted event into it, so agents reached
+    /// through transfer can read the full dialogue. The default returns `None`.
+    fn conversation_buffer(&self) -> Option<Arc<crate::ConversationBuffer>> {
+        None
+    }
+
+    /// Returns the structured task this agent was handed, if any.
+    ///
+    /// Set when a coordinator transferred to this agent with a
+    /// [`Handoff`](crate::Handoff). The default reads
+    /// [`RunConfig::handoff`].
+    fn handoff(&self) -> Option<&crate::Handoff> {
+        self.run_config().handoff.as_ref()
+    }
+
+    /// Returns the correlation id of the request that started this invocation.
+    ///
+    /// Servers set this from the incoming `X-Request-Id` header (generating one
+    /// if absent) and remote agents forward it, so the same id appears on both
+    /// sides of a delegated call. The default reads
+    /// [`RunConfig::request_id`].
+    fn request_id(&self) -> Option<&str> {
+        self.run_config().request_id.as_deref()
+    }
+
     /// Retrieve a secret by name from the configured secret provider.
     ///
     /// Returns `Ok(Some(value))` when a provider is configured and the secret
@@ -657,14 +838,83 @@ pub enum StreamingMode {
     Bidi,
 }
 
+/// Predicate deciding whether a session event is part of an agent's history.
+///
+/// Two filters are equal only when they share the same closure.
+#[derive(Clone)]
+pub struct EventFilter(Arc<dyn Fn(&Event) -> bool + Send + Sync>);
+
+impl EventFilter {
+    /// Wraps `predicate` as a filter.
+    pub fn new(predicate: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+
+    /// Returns `true` if `event` belongs in the history.
+    pub fn matches(&self, event: &Event) -> bool {
+        (self.0)(event)
+    }
+}
+
+impl std::fmt::Debug for EventFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("..")
+    }
+}
+
+impl PartialEq for EventFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for EventFilter {}
+
 /// Controls what parts of prior conversation history is received by llmagent
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Every mode keeps function calls and their responses together: a response
+/// is included exactly when the call that produced it is.
+///
+/// # Example
+///
+/// ```rust
+/// use adk_core::IncludeContents;
+///
+/// // Only the five most recent user turns
+/// let recent = IncludeContents::LastN(5);
+///
+/// // Drop events other agents produced on sibling branches
+/// let own_branch = IncludeContents::filtered(|event| !event.branch.starts_with("research"));
+/// # let _ = (recent, own_branch);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum IncludeContents {
     /// The llmagent operates solely on its current turn (latest user input + any following agent events)
     None,
-    /// Default - The llmagent receives the relevant conversation history
+    /// The llmagent receives the relevant conversation history
     #[default]
-    Default,
+    All,
+    /// Only the most recent `n` turns are sent, where a turn starts at a user
+    /// message and runs until the next one. The current turn always counts,
+    /// so `LastN(0)` behaves like `LastN(1)`.
+    LastN(usize),
+    /// Only session events for which the predicate returns `true` are sent.
+    ///
+    /// Function responses follow the decision made for their call, so the
+    /// predicate cannot leave a call or a response without its counterpart.
+    Filtered(EventFilter),
+}
+
+impl IncludeContents {
+    /// Former name of [`IncludeContents::All`].
+    #[allow(non_upper_case_globals)]
+    #[deprecated(note = "use IncludeContents::All")]
+    pub const Default: Self = Self::All;
+
+    /// Creates an [`IncludeContents::Filtered`] mode from a closure.
+    pub fn filtered(predicate: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        Self::Filtered(EventFilter::new(predicate))
+    }
 }
 
 /// Decision applied when a tool execution requires human confirmation.
@@ -800,6 +1050,30 @@ pub struct RunConfig {
     /// The name of the parent agent, if this agent was invoked via transfer.
     /// Used by the agent to apply `disallow_transfer_to_parent` filtering.
     pub parent_agent: Option<String>,
+    /// The structured task this agent was handed, if it was invoked via a
+    /// transfer that carried one.
+    pub handoff: Option<crate::Handoff>,
+    /// The root agent's global instruction, applied by every LLM agent in the
+    /// run before its own instruction.
+    ///
+    /// The runner sets it from [`Agent::global_instruction`] of the root
+    /// agent, to an empty instruction when the root has none. When `None`,
+    /// as for agents run without a runner, an agent uses its own.
+    pub global_instruction: Option<crate::GlobalInstruction>,
+    /// Generation settings that override the agent's for this invocation only.
+    ///
+    /// Settings present here win over the agent's
+    /// [`GenerateContentConfig`](crate::GenerateContentConfig), which in turn
+    /// wins over the model's defaults; see
+    /// [`GenerateContentConfig::with_overrides`](crate::GenerateContentConfig::with_overrides).
+    /// The agent itself is not changed, so the next invocation without an
+    /// override uses the agent's settings again.
+    pub generate_content_config: Option<crate::GenerateContentConfig>,
+    /// Names of the tools agents may offer the model during this invocation.
+    ///
+    /// Tools not listed are withheld from the request and cannot be called.
+    /// Agent transfer is not affected. `None` (the default) offers every tool.
+    pub tool_allowlist: Option<Vec<String>>,
     /// Enable automatic prompt caching for all providers that support it.
     ///
     /// When `true` (the default), the runner enables provider-level caching:
@@ -820,6 +1094,10 @@ pub struct RunConfig {
     /// The default (`ToolConcurrencyConfig::default()`) imposes no limits,
     /// preserving backward compatibility with the previous `max_tool_concurrency: None`.
     pub tool_concurrency: ToolConcurrencyConfig,
+    /// Size limits applied to tool results before they reach the model.
+    ///
+    /// The default imposes no limit.
+    pub tool_output_limits: ToolOutputLimitConfig,
     /// Whether tracing spans may include full request, response, and tool
     /// payloads when the `record-payloads` crate feature is enabled.
     pub record_payloads: bool,
@@ -831,6 +1109,59 @@ pub struct RunConfig {
     /// Prevents infinite transfer loops when agents transfer back and forth.
     /// Defaults to 10 when `None`.
     pub max_transfer_depth: Option<u32>,
+    /// Maximum time an agent reached through `transfer_to_agent` may run
+    /// before it is cancelled.
+    ///
+    /// When the deadline passes, the runner drops the delegated agent's
+    /// stream (releasing any in-flight model request), records a
+    /// [`DelegationTimeout`](crate::DelegationTimeout) event on behalf of the
+    /// delegating agent, and hands control back to it so it can answer
+    /// without the delegate. `None` (the default) waits indefinitely.
+    pub transfer_timeout: Option<std::time::Duration>,
+    /// How many times an agent may call the same tool with identical
+    /// arguments in one invocation.
+    ///
+    /// Further identical calls are not executed. The first is answered with a
+    /// reminder of the earlier result; after that the agent asks the model for
+    /// a final answer with no tools available. `None` (the default) disables
+    /// loop detection.
+    pub loop_break_threshold: Option<usize>,
+    /// Correlation id of the request that started this run (`X-Request-Id`).
+    ///
+    /// Set by the server and forwarded on outbound agent-to-agent calls so a
+    /// delegated request can be traced across hops.
+    pub request_id: Option<String>,
+    /// W3C `traceparent` of the request that started this run, forwarded on
+    /// outbound agent-to-agent calls.
+    pub traceparent: Option<String>,
+    /// Maximum number of automatic "continue" requests issued when a model
+    /// response stops with `FinishReason::MaxTokens`.
+    ///
+    /// Each continuation asks the model to resume where it was cut off, until
+    /// it stops naturally or the cap is reached. `0` (the default) disables
+    /// continuation and returns the truncated response as-is.
+    pub max_token_continuations: u32,
+    /// Emit [`LifecycleEvent`](crate::LifecycleEvent)s for model calls, tool
+    /// calls, and agent transfers alongside content events.
+    ///
+    /// Off by default so consumers that only expect content see no change.
+    pub lifecycle_events: bool,
+    /// Time budget for the whole turn, shared by every model call, tool call,
+    /// and delegated agent.
+    ///
+    /// The runner turns it into [`deadline_at`](Self::deadline_at) when the
+    /// run starts. Once that passes, in-flight work is cancelled and the run
+    /// ends with an [`Event::turn_timed_out`](crate::Event::turn_timed_out)
+    /// event after whatever was produced so far. `None` (the default) sets
+    /// no deadline.
+    pub deadline: Option<std::time::Duration>,
+    /// The instant the current turn must finish by, set by the runner from
+    /// [`deadline`](Self::deadline).
+    ///
+    /// Agents bound each model and tool call by
+    /// [`time_remaining`](Self::time_remaining), and delegated agents inherit
+    /// it, so they only get the time the turn has left.
+    pub deadline_at: Option<std::time::Instant>,
 }
 
 impl Default for RunConfig {
@@ -843,12 +1174,25 @@ impl Default for RunConfig {
             cached_content: None,
             transfer_targets: Vec::new(),
             parent_agent: None,
+            handoff: None,
+            global_instruction: None,
+            generate_content_config: None,
+            tool_allowlist: None,
             auto_cache: true,
             history_max_events: None,
             tool_concurrency: ToolConcurrencyConfig::default(),
+            tool_output_limits: ToolOutputLimitConfig::default(),
             record_payloads: false,
             trace_payload_max_bytes: 2048,
             max_transfer_depth: None,
+            transfer_timeout: None,
+            loop_break_threshold: None,
+            request_id: None,
+            traceparent: None,
+            max_token_continuations: 0,
+            lifecycle_events: false,
+            deadline: None,
+            deadline_at: None,
         }
     }
 }
@@ -874,6 +1218,12 @@ impl RunConfig {
     pub fn builder() -> RunConfigBuilder {
         RunConfigBuilder::default()
     }
+
+    /// Time left before [`deadline_at`](Self::deadline_at), zero once it has
+    /// passed, or `None` when the turn has no deadline.
+    pub fn time_remaining(&self) -> Option<std::time::Duration> {
+        self.deadline_at.map(|at| at.saturating_duration_since(std::time::Instant::now()))
+    }
 }
 
 /// Builder for [`RunConfig`].
@@ -951,6 +1301,31 @@ impl RunConfigBuilder {
         self
     }
 
+    /// Sets the structured task the agent was handed.
+    pub fn handoff(mut self, handoff: crate::Handoff) -> Self {
+        self.config.handoff = Some(handoff);
+        self
+    }
+
+    /// Overrides the agent's generation settings for this invocation.
+    pub fn generate_content_config(mut self, config: crate::GenerateContentConfig) -> Self {
+        self.config.generate_content_config = Some(config);
+        self
+    }
+
+    /// Overrides the sampling temperature for this invocation.
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.config.generate_content_config.get_or_insert_with(Default::default).temperature =
+            Some(temperature);
+        self
+    }
+
+    /// Restricts the tools offered to the model to `names` for this invocation.
+    pub fn tool_allowlist(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.config.tool_allowlist = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Enables or disables automatic prompt caching for supported providers.
     pub fn auto_cache(mut self, enabled: bool) -> Self {
         self.config.auto_cache = enabled;
@@ -969,6 +1344,18 @@ impl RunConfigBuilder {
         self
     }
 
+    /// Sets the tool result size limits.
+    pub fn tool_output_limits(mut self, config: ToolOutputLimitConfig) -> Self {
+        self.config.tool_output_limits = config;
+        self
+    }
+
+    /// Sets the default maximum serialized size of a tool result in bytes.
+    pub fn max_tool_output_bytes(mut self, max: usize) -> Self {
+        self.config.tool_output_limits.max_output_bytes = Some(max);
+        self
+    }
+
     /// Enables or disables full payload recording in tracing spans.
     pub fn record_payloads(mut self, enabled: bool) -> Self {
         self.config.record_payloads = enabled;
@@ -989,6 +1376,53 @@ impl RunConfigBuilder {
         self
     }
 
+    /// Sets how long a delegated agent may run before it is cancelled and
+    /// control returns to the agent that delegated to it.
+    pub fn transfer_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.config.transfer_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many identical tool calls an agent may make in one invocation
+    /// before the loop is broken.
+    pub fn loop_break_threshold(mut self, threshold: usize) -> Self {
+        self.config.loop_break_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the correlation id of the request that started this run.
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.config.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Sets the W3C `traceparent` of the request that started this run.
+    pub fn traceparent(mut self, traceparent: impl Into<String>) -> Self {
+        self.config.traceparent = Some(traceparent.into());
+        self
+    }
+
+    /// Sets how many times a response truncated by `FinishReason::MaxTokens`
+    /// is automatically continued.
+    pub fn max_token_continuations(mut self, max: u32) -> Self {
+        self.config.max_token_continuations = max;
+        self
+    }
+
+    /// Enables or disables lifecycle events for model calls, tool calls, and
+    /// agent transfers.
+    pub fn lifecycle_events(mut self, enabled: bool) -> Self {
+        self.config.lifecycle_events = enabled;
+        self
+    }
+
+    /// Sets how long a whole turn may take, including every model call, tool
+    /// call, and delegated agent.
+    pub fn deadline(mut self, deadline: std::time::Duration) -> Self {
+        self.config.deadline = Some(deadline);
+        self
+    }
+
     /// Consumes the builder and returns the configured [`RunConfig`].
     pub fn build(self) -> RunConfig {
         self.config
@@ -1011,6 +1445,24 @@ mod tests {
         assert_eq!(config.trace_payload_max_bytes, 2048);
         assert!(config.tool_confirmation_decisions.is_empty());
         assert_eq!(config.max_transfer_depth, None);
+        assert_eq!(config.transfer_timeout, None);
+        assert_eq!(config.loop_break_threshold, None);
+        assert_eq!(config.deadline, None);
+        assert_eq!(config.time_remaining(), None);
+    }
+
+    #[test]
+    fn test_time_remaining_saturates_at_zero() {
+        let mut config = RunConfig::builder().deadline(std::time::Duration::from_secs(30)).build();
+        assert_eq!(config.deadline, Some(std::time::Duration::from_secs(30)));
+        assert_eq!(config.time_remaining(), None);
+
+        config.deadline_at = Some(std::time::Instant::now() + std::time::Duration::from_secs(30));
+        let remaining = config.time_remaining().unwrap();
+        assert!(remaining > std::time::Duration::from_secs(29));
+
+        config.deadline_at = Some(std::time::Instant::now());
+        assert_eq!(config.time_remaining(), Some(std::time::Duration::ZERO));
     }
 
     #[test]
@@ -1099,6 +1551,8 @@ mod tests {
             })
             .record_payloads(true)
             .trace_payload_max_bytes(4096)
+            .request_id("0b7c6f3e-4d6f-4c1b-9a53-6f1f1c1e2a10")
+            .max_token_continuations(3)
             .build();
 
         assert_eq!(config.streaming_mode, StreamingMode::None);
@@ -1112,5 +1566,24 @@ mod tests {
         assert_eq!(config.tool_concurrency.backpressure, BackpressurePolicy::Fail);
         assert!(config.record_payloads);
         assert_eq!(config.trace_payload_max_bytes, 4096);
+        assert_eq!(config.request_id.as_deref(), Some("0b7c6f3e-4d6f-4c1b-9a53-6f1f1c1e2a10"));
+        assert_eq!(config.max_token_continuations, 3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_include_contents_deprecated_default_matches_all() {
+        let describe = |mode: IncludeContents| match mode {
+            IncludeContents::Default => "all",
+            IncludeContents::None => "none",
+            _ => "other",
+        };
+        assert_eq!(describe(IncludeContents::All), "all");
+        assert_eq!(describe(IncludeContents::LastN(2)), "other");
+
+        let filter = IncludeContents::filtered(|event| event.author == "user");
+        assert_eq!(filter, filter.clone());
+        assert_ne!(filter, IncludeContents::filtered(|event| event.author == "user"));
+        assert_eq!(format!("{filter:?}"), "Filtered(..)");
     }
 }
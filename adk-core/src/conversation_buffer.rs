@@ -0,0 +1,256 @@
+//! Run-wide dialogue shared by every agent in a multi-agent tree.
+//!
+//! When one agent transfers to another, the receiving agent normally sees only
+//! the user's messages and its own past turns, so facts the coordinator
+//! gathered or stated are lost. The runner records the whole dialogue into a
+//! [`ConversationBuffer`] exposed through
+//! [`InvocationContext::conversation_buffer`](crate::InvocationContext::conversation_buffer),
+//! and agents that opt in with [`SharedHistory`] build their history from it.
+//!
+//! Turns by other agents are presented to the reading agent as user-role
+//! context (`For context: [coordinator] said: ...`) so the model never mistakes
+//! them for its own replies or sees another agent's tool calls as its own.
+
+use crate::event::Event;
+use crate::types::{Content, Part};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::RwLock;
+
+/// Which parts of the shared dialogue an agent receives from
+/// [`ConversationBuffer::history_for`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedHistory {
+    /// Use the agent's own view of the session (the default).
+    #[default]
+    Off,
+    /// Include other agents' text replies.
+    Dialogue,
+    /// Include other agents' text replies, tool calls, and tool results.
+    WithToolResults,
+}
+
+/// One recorded turn of the shared dialogue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationEntry {
+    /// `"user"` or the name of the agent that produced the content.
+    pub author: String,
+    /// The recorded content.
+    pub content: Content,
+}
+
+/// Thread-safe, append-only record of the dialogue in one run.
+///
+/// The runner seeds it with the session's loaded history and records every
+/// event it persists, so the buffer always matches the full, unfiltered
+/// conversation.
+#[derive(Default)]
+pub struct ConversationBuffer {
+    entries: RwLock<Vec<ConversationEntry>>,
+}
+
+impl ConversationBuffer {
+    /// Creates an empty buffer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the content of `event`.
+    ///
+    /// Partial (streaming) chunks, lifecycle events, and events without
+    /// content are ignored.
+    pub fn record(&self, event: &Event) {
+        if event.llm_response.partial || event.is_lifecycle() {
+            return;
+        }
+        let Some(content) = &event.llm_response.content else {
+            return;
+        };
+        if content.parts.is_empty() {
+            return;
+        }
+        self.write()
+            .push(ConversationEntry { author: event.author.clone(), content: content.clone() });
+    }
+
+    /// Returns the recorded entries in order.
+    pub fn entries(&self) -> Vec<ConversationEntry> {
+        self.read().clone()
+    }
+
+    /// Returns the number of recorded entries.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    /// Builds the conversation history for `agent_name`.
+    ///
+    /// User messages and the agent's own turns are returned as recorded.
+    /// Other agents' turns become user-role context messages: their text for
+    /// [`SharedHistory::Dialogue`], plus their tool calls and results for
+    /// [`SharedHistory::WithToolResults`]. Returns an empty history for
+    /// [`SharedHistory::Off`].
+    pub fn history_for(&self, agent_name: &str, mode: SharedHistory) -> Vec<Content> {
+        if mode == SharedHistory::Off {
+            return Vec::new();
+        }
+
+        let entries = self.read();
+        let mut history = Vec::with_capacity(entries.len());
+        for entry in entries.iter() {
+            if entry.author == "user" {
+                let mut content = entry.content.clone();
+                content.role = "user".to_string();
+                history.push(content);
+            } else if entry.author == agent_name {
+                let mut content = entry.content.clone();
+                if !matches!(content.role.as_str(), "function" | "tool") {
+                    content.role = "model".to_string();
+                }
+                history.push(content);
+            } else {
+                let parts: Vec<Part> = entry
+                    .content
+                    .parts
+                    .iter()
+                    .filter_map(|part| context_text(&entry.author, part, mode))
+                    .map(|text| Part::Text { text })
+                    .collect();
+                if !parts.is_empty() {
+                    history.push(Content { role: "user".to_string(), parts });
+                }
+            }
+        }
+        history
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<ConversationEntry>> {
+        self.entries.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<ConversationEntry>> {
+        self.entries.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Describes another agent's `part` for the reading agent, or `None` if the
+/// part is not shared in `mode`.
+fn context_text(author: &str, part: &Part, mode: SharedHistory) -> Option<String> {
+    match part {
+        Part::Text { text } if !part.is_thinking() && !text.trim().is_empty() => {
+            Some(format!("For context: [{author}] said: {text}"))
+        }
+        Part::FunctionCall { name, args, .. } if mode == SharedHistory::WithToolResults => {
+            Some(format!("For context: [{author}] called tool `{name}` with parameters: {args}"))
+        }
+        Part::FunctionResponse { function_response, .. }
+            if mode == SharedHistory::WithToolResults =>
+        {
+            Some(format!(
+                "For context: [{author}] `{}` tool returned result: {}",
+                function_response.name, function_response.response
+            ))
+        }
+        _ => None,
+    }
+}
+
+impl fmt::Debug for ConversationBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConversationBuffer").field("entries", &self.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FunctionResponseData;
+
+    fn event(author: &str, content: Content) -> Event {
+        let mut event = Event::new("inv-1");
+        event.author = author.to_string();
+        event.llm_response.content = Some(content);
+        event
+    }
+
+    fn texts(history: &[Content]) -> Vec<(String, String)> {
+        history
+            .iter()
+            .flat_map(|content| {
+                content.parts.iter().filter_map(|part| {
+                    part.text().map(|text| (content.role.clone(), text.to_string()))
+                })
+            })
+            .collect()
+    }
+
+    fn buffer() -> ConversationBuffer {
+        let buffer = ConversationBuffer::new();
+        buffer.record(&event("user", Content::new("user").with_text("My order is 4411.")));
+        buffer.record(&event("coordinator", Content::new("model").with_text("Noted.")));
+        buffer.record(&event(
+            "coordinator",
+            Content {
+                role: "function".to_string(),
+                parts: vec![Part::FunctionResponse {
+                    function_response: FunctionResponseData::new(
+                        "lookup_order",
+                        serde_json::json!({ "status": "shipped" }),
+                    ),
+                    id: None,
+                }],
+            },
+        ));
+        buffer.record(&event("billing", Content::new("model").with_text("Refund issued.")));
+        buffer
+    }
+
+    #[test]
+    fn test_other_agents_become_context_for_reader() {
+        let history = buffer().history_for("billing", SharedHistory::Dialogue);
+
+        assert_eq!(
+            texts(&history),
+            [
+                ("user".to_string(), "My order is 4411.".to_string()),
+                ("user".to_string(), "For context: [coordinator] said: Noted.".to_string()),
+                ("model".to_string(), "Refund issued.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_results_shared_only_when_requested() {
+        let history = buffer().history_for("billing", SharedHistory::WithToolResults);
+
+        let text = texts(&history).into_iter().map(|(_, text)| text).collect::<Vec<_>>();
+        assert!(text.contains(
+            &r#"For context: [coordinator] `lookup_order` tool returned result: {"status":"shipped"}"#
+                .to_string()
+        ));
+        assert!(buffer().history_for("billing", SharedHistory::Off).is_empty());
+    }
+
+    #[test]
+    fn test_partial_and_empty_events_are_not_recorded() {
+        let buffer = ConversationBuffer::new();
+        let mut partial = event("coordinator", Content::new("model").with_text("Noted"));
+        partial.llm_response.partial = true;
+        buffer.record(&partial);
+        buffer.record(&Event::new("inv-1"));
+
+        assert!(buffer.is_empty());
+    }
+}
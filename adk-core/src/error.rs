@@ -125,6 +125,30 @@ impl fmt::Display for ErrorCategory {
     }
 }
 
+impl ErrorCategory {
+    /// Maps an upstream HTTP status code to the category it represents.
+    ///
+    /// ```rust
+    /// use adk_core::ErrorCategory;
+    ///
+    /// assert_eq!(ErrorCategory::from_http_status(429), ErrorCategory::RateLimited);
+    /// assert_eq!(ErrorCategory::from_http_status(422), ErrorCategory::InvalidInput);
+    /// ```
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            401 => Self::Unauthorized,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            408 => Self::Timeout,
+            429 => Self::RateLimited,
+            501 => Self::Unsupported,
+            502..=504 | 529 => Self::Unavailable,
+            400..=499 => Self::InvalidInput,
+            _ => Self::Internal,
+        }
+    }
+}
+
 /// Structured retry guidance attached to every [`AdkError`].
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RetryHint {
@@ -314,6 +338,24 @@ impl AdkError {
         self.details.provider = Some(provider.into());
         self
     }
+
+    /// Records how long the upstream service asked callers to wait, e.g. from
+    /// a `Retry-After` header, and marks the error as retryable.
+    pub fn with_retry_after(mut self, delay: Duration) -> Self {
+        self.retry = self.retry.with_retry_after(delay);
+        self.retry.should_retry = true;
+        self
+    }
+
+    /// Returns how long to wait before retrying, if the upstream service said.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry.retry_after()
+    }
+
+    /// Returns the upstream HTTP status code, if the error came from one.
+    pub fn upstream_status(&self) -> Option<u16> {
+        self.details.upstream_status_code
+    }
 }
 
 impl AdkError {
@@ -370,6 +412,60 @@ impl AdkError {
     ) -> Self {
         Self::new(component, ErrorCategory::Unavailable, code, message)
     }
+
+    /// Creates a `Cancelled` error for the given component.
+    pub fn cancelled(
+        component: ErrorComponent,
+        code: &'static str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::new(component, ErrorCategory::Cancelled, code, message)
+    }
+
+    /// Creates an error for a failed upstream HTTP call.
+    ///
+    /// The category comes from [`ErrorCategory::from_http_status`] and the
+    /// status is recorded in the details. Rate limits, timeouts, and transient
+    /// server errors (500, 502, 503, 504, 529) are marked retryable.
+    ///
+    /// ```rust
+    /// use adk_core::{AdkError, ErrorComponent};
+    ///
+    /// let err = AdkError::from_http_status(ErrorComponent::Model, "model.api_error", 503, "busy");
+    /// assert!(err.is_retryable());
+    /// assert_eq!(err.upstream_status(), Some(503));
+    ///
+    /// let err = AdkError::from_http_status(ErrorComponent::Model, "model.api_error", 400, "bad");
+    /// assert!(!err.is_retryable());
+    /// ```
+    pub fn from_http_status(
+        component: ErrorComponent,
+        code: &'static str,
+        status: u16,
+        message: impl Into<String>,
+    ) -> Self {
+        let mut err = Self::new(component, ErrorCategory::from_http_status(status), code, message)
+            .with_upstream_status(status);
+        if status == 500 {
+            err.retry.should_retry = true;
+        }
+        err
+    }
+
+    /// Creates an `Unsupported` error for the given component.
+    pub fn unsupported(
+        component: ErrorComponent,
+        code: &'static str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::new(component, ErrorCategory::Unsupported, code, message)
+    }
+
+    /// Creates an error for a value that could not be converted to or from
+    /// JSON, such as a stored state value that does not match the requested type.
+    pub fn serialization(component: ErrorComponent, message: impl Into<String>) -> Self {
+        Self::new(component, ErrorCategory::InvalidInput, "serialization", message)
+    }
 }
 
 impl AdkError {
@@ -438,6 +534,10 @@ impl AdkError {
     pub fn is_config(&self) -> bool {
         self.code == "config.legacy"
     }
+    /// Returns `true` if a value could not be converted to or from JSON.
+    pub fn is_serialization(&self) -> bool {
+        self.code == "serialization"
+    }
 }
 
 impl AdkError {
@@ -461,6 +561,10 @@ impl AdkError {
     pub fn is_timeout(&self) -> bool {
         self.category == ErrorCategory::Timeout
     }
+    /// Returns `true` if the operation was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.category == ErrorCategory::Cancelled
+    }
 }
 
 impl AdkError {
@@ -1,5 +1,5 @@
 use crate::context::{ToolConfirmationDecision, ToolConfirmationRequest};
-use crate::model::LlmResponse;
+use crate::model::{Citation, FinishReason, LlmResponse};
 use crate::types::Content;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -23,17 +23,331 @@ pub const TOOL_PROGRESS_STREAM_KEY: &str = "adk.tool_progress.stream";
 /// function-call id on a tool-progress event.
 pub const TOOL_PROGRESS_CALL_ID_KEY: &str = "adk.tool_progress.call_id";
 
+/// Event-level `provider_metadata` key carrying the function-call id of the
+/// agent tool call a forwarded sub-agent event was produced under. Present only
+/// on events passed to
+/// [`ToolContext::forward_sub_agent_event`](crate::ToolContext::forward_sub_agent_event).
+pub const SUB_AGENT_CALL_ID_KEY: &str = "adk.sub_agent.call_id";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`LifecycleEvent`] of a lifecycle event. Present only on events created by
+/// [`Event::lifecycle`].
+pub const LIFECYCLE_EVENT_KEY: &str = "adk.lifecycle";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`DelegationTimeout`] of an event created by [`Event::delegation_timed_out`].
+pub const DELEGATION_TIMEOUT_KEY: &str = "adk.delegation_timeout";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`TransferLimit`] of an event created by [`Event::transfer_stopped`].
+pub const TRANSFER_LIMIT_KEY: &str = "adk.transfer_limit";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`PipelineEscalation`] of an event created by [`Event::pipeline_stopped`].
+pub const PIPELINE_ESCALATION_KEY: &str = "adk.pipeline_escalation";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`LoopExit`] of an event created by [`Event::loop_exited`].
+pub const LOOP_EXIT_KEY: &str = "adk.loop_exit";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`BranchChoice`] of an event created by [`Event::branch_chosen`].
+pub const BRANCH_CHOICE_KEY: &str = "adk.branch_choice";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`ToolCallFailure`] of an event created by [`Event::tool_failed`].
+pub const TOOL_FAILURE_KEY: &str = "adk.tool_failure";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`TurnRetry`] of an event created by [`Event::turn_retried`].
+pub const TURN_RETRY_KEY: &str = "adk.turn_retry";
+
+/// Event-level `provider_metadata` key carrying the JSON-encoded
+/// [`TurnTimeout`] of an event created by [`Event::turn_timed_out`].
+pub const TURN_TIMEOUT_KEY: &str = "adk.turn_timeout";
+
+/// Event-level `provider_metadata` key carrying the plan text of an event
+/// created by [`Event::planned`].
+pub const PLAN_KEY: &str = "adk.plan";
+
+/// A step in an agent run, reported alongside content events when
+/// [`RunConfig::lifecycle_events`](crate::RunConfig::lifecycle_events) is set.
+///
+/// Lifecycle events let a UI show whether the agent is waiting on the model,
+/// running a tool, or handing off to another agent. They carry no content and
+/// are never persisted to the session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    /// A model request was sent.
+    ModelCallStarted {
+        /// Name of the model.
+        model: String,
+    },
+    /// The model response finished streaming.
+    ModelCallCompleted {
+        /// Name of the model.
+        model: String,
+        /// Wall-clock time from request to the last chunk.
+        duration_ms: u64,
+        /// Why the model stopped, when it reported a reason.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        finish_reason: Option<FinishReason>,
+    },
+    /// A tool started executing.
+    ToolCallStarted {
+        /// Name of the tool.
+        tool: String,
+        /// Function-call id of the request being served.
+        call_id: String,
+    },
+    /// A tool finished executing, including all retries.
+    ToolCallCompleted {
+        /// Name of the tool.
+        tool: String,
+        /// Function-call id of the request being served.
+        call_id: String,
+        /// Wall-clock execution time.
+        duration_ms: u64,
+        /// The error message when the tool failed or timed out.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Control was transferred from one agent to another.
+    AgentDelegated {
+        /// The agent that requested the transfer.
+        from: String,
+        /// The agent that takes over.
+        to: String,
+    },
+}
+
+/// A delegated agent that was cancelled because it did not finish within its
+/// timeout.
+///
+/// Reported to the delegating agent instead of the delegate's answer, either
+/// as an [`Event::delegation_timed_out`] event after a transfer or as a tool
+/// result from an agent wrapped as a tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationTimeout {
+    /// Name of the agent that was cancelled.
+    pub agent: String,
+    /// The timeout that elapsed, in milliseconds.
+    pub timeout_ms: u64,
+}
+
+impl DelegationTimeout {
+    /// Creates a timeout report for `agent` after `timeout` elapsed.
+    pub fn new(agent: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self {
+            agent: agent.into(),
+            timeout_ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// A sentence describing the timeout, suitable for the model to read.
+    pub fn message(&self) -> String {
+        format!(
+            "Agent '{}' did not respond within {} ms and was cancelled.",
+            self.agent, self.timeout_ms
+        )
+    }
+
+    /// The timeout as a structured tool result:
+    /// `{"error", "error_type": "timeout", "agent", "timeout_ms"}`.
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": self.message(),
+            "error_type": "timeout",
+            "agent": self.agent,
+            "timeout_ms": self.timeout_ms,
+        })
+    }
+}
+
+/// A transfer that was not made because the invocation had already used its
+/// [`RunConfig::max_transfer_depth`](crate::RunConfig::max_transfer_depth).
+///
+/// Recorded as an [`Event::transfer_stopped`] event, the last event of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferLimit {
+    /// Name of the agent the transfer asked for.
+    pub target: String,
+    /// Transfers allowed per invocation.
+    pub limit: u32,
+}
+
+impl TransferLimit {
+    /// A sentence describing the stopped transfer.
+    pub fn message(&self) -> String {
+        format!(
+            "Transfer to agent '{}' was not made: the limit of {} transfers per invocation was reached.",
+            self.target, self.limit
+        )
+    }
+}
+
+/// A final answer rejected by an agent's turn validator, after which the agent
+/// asks the model to try again.
+///
+/// Recorded as an [`Event::turn_retried`] event whose content is the feedback
+/// sent to the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnRetry {
+    /// The retry this event starts, counting from 1.
+    pub attempt: u32,
+    /// The most retries the agent makes before giving up.
+    pub max_retries: u32,
+    /// Why the answer was rejected, as sent to the model.
+    pub feedback: String,
+}
+
+/// A turn that was stopped because it ran past
+/// [`RunConfig::deadline`](crate::RunConfig::deadline).
+///
+/// Recorded as an [`Event::turn_timed_out`] event, the last event of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnTimeout {
+    /// Name of the agent that was running when the deadline passed.
+    pub agent: String,
+    /// The turn's deadline, in milliseconds from the start of the run.
+    pub deadline_ms: u64,
+}
+
+impl TurnTimeout {
+    /// Creates a timeout report for `agent` after the turn's `deadline` passed.
+    pub fn new(agent: impl Into<String>, deadline: std::time::Duration) -> Self {
+        Self {
+            agent: agent.into(),
+            deadline_ms: u64::try_from(deadline.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// A sentence describing the timeout.
+    pub fn message(&self) -> String {
+        format!(
+            "The turn did not finish within its {} ms deadline; agent '{}' was stopped.",
+            self.deadline_ms, self.agent
+        )
+    }
+}
+
+/// A pipeline step that escalated, stopping the steps after it.
+///
+/// Recorded as an [`Event::pipeline_stopped`] event, authored by the pipeline
+/// after the escalating step's own events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineEscalation {
+    /// Name of the step's agent.
+    pub agent: String,
+    /// Position of the step, counting from 1.
+    pub step: usize,
+    /// Number of steps in the pipeline.
+    pub steps: usize,
+    /// Why the step escalated: the text it answered with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PipelineEscalation {
+    /// A sentence describing where the pipeline stopped and why.
+    pub fn message(&self) -> String {
+        let stopped = format!(
+            "Stopped at step {} of {} ('{}'), which escalated",
+            self.step, self.steps, self.agent
+        );
+        match &self.reason {
+            Some(reason) => format!("{stopped}: {reason}"),
+            None => format!("{stopped}."),
+        }
+    }
+}
+
+/// Why a loop stopped repeating its sub-agents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopExitReason {
+    /// The loop's exit condition held at the end of an iteration.
+    Condition,
+    /// The loop ran past its maximum duration.
+    MaxDuration,
+    /// The loop completed its maximum number of iterations.
+    MaxIterations,
+}
+
+/// How a loop with an exit condition or time limit ended.
+///
+/// Recorded as an [`Event::loop_exited`] event, authored by the loop after the
+/// events of its last iteration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoopExit {
+    /// Which limit stopped the loop.
+    pub reason: LoopExitReason,
+    /// Iterations that ran, including one cut short by the time limit.
+    pub iterations: u32,
+}
+
+/// The branch a conditional agent chose.
+///
+/// Recorded as an [`Event::branch_chosen`] event, authored by the conditional
+/// agent before the chosen branch runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchChoice {
+    /// Position of the condition that held, counting from 1, or `None` when
+    /// none did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arm: Option<usize>,
+    /// Name of the agent that runs, or `None` when no condition held and there
+    /// is no fallback.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
+
+/// How an agent handled a tool call that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolFailureResolution {
+    /// The error was sent to the model as the function response.
+    ReturnedToModel,
+    /// A callback or the tool error policy supplied a substitute result.
+    Substituted,
+    /// The invocation failed with the tool's error.
+    Aborted,
+}
+
+/// A tool call that failed after its retries.
+///
+/// Recorded as an [`Event::tool_failed`] event, authored by the agent that
+/// called the tool, whether or not the agent recovered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCallFailure {
+    /// Name of the tool.
+    pub tool: String,
+    /// Function-call id of the failed call.
+    pub call_id: String,
+    /// The error message.
+    pub error: String,
+    /// What the agent did about it.
+    pub resolution: ToolFailureResolution,
+}
+
 /// Event represents a single interaction in a conversation.
 /// This struct embeds LlmResponse to match ADK-Go's design pattern.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
     /// Unique identifier for this event.
     pub id: String,
-    /// When this event was created.
+    /// When this event was created. Events persisted without one read back
+    /// as the Unix epoch.
+    #[serde(default)]
     pub timestamp: DateTime<Utc>,
     /// The invocation that produced this event.
+    #[serde(default)]
     pub invocation_id: String,
-    /// The conversation branch this event belongs to.
+    /// The conversation branch this event belongs to, as dot-separated agent
+    /// names (e.g. `"research.web"` for a parallel sub-agent). Empty for the
+    /// root branch.
+    #[serde(default)]
     pub branch: String,
     /// The agent or role that authored this event.
     pub author: String,
@@ -81,6 +395,10 @@ pub struct EventActions {
     pub skip_summarization: bool,
     /// Agent name to transfer control to.
     pub transfer_to_agent: Option<String>,
+    /// Structured task for the agent named in
+    /// [`transfer_to_agent`](Self::transfer_to_agent).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff: Option<crate::Handoff>,
     /// Whether to escalate to a human operator.
     pub escalate: bool,
     /// Tool confirmation request awaiting human approval.
@@ -97,6 +415,12 @@ pub struct EventActions {
     /// following static edges.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub route: Option<Vec<String>>,
+    /// Sources a tool drew its result from, such as retrieved documents.
+    ///
+    /// The agent carries them onto the final event of its turn as
+    /// [`LlmResponse::citations`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
 }
 
 /// A typed, borrowed view of a single tool call carried by an [`Event`].
@@ -215,6 +539,398 @@ impl Event {
         self.provider_metadata.get(TOOL_PROGRESS_STREAM_KEY).map(String::as_str)
     }
 
+    /// Returns the function-call id of the agent tool call this event was
+    /// forwarded from, if it is a sub-agent's event streamed through its
+    /// caller (see [`SUB_AGENT_CALL_ID_KEY`]). The event's `author` names the
+    /// sub-agent.
+    ///
+    /// Forwarded events only report progress: they are not written to the
+    /// session, and the tool call's result is what the calling agent sees.
+    pub fn sub_agent_call_id(&self) -> Option<&str> {
+        self.provider_metadata.get(SUB_AGENT_CALL_ID_KEY).map(String::as_str)
+    }
+
+    /// Creates a lifecycle event reporting an agent run step.
+    ///
+    /// The event has no content and is marked partial, so it is never treated
+    /// as a final response or persisted. Consumers that only want content can
+    /// skip events for which [`is_lifecycle`](Self::is_lifecycle) is true.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, LifecycleEvent};
+    ///
+    /// let step = LifecycleEvent::ModelCallStarted { model: "gemini-2.5-flash".to_string() };
+    /// let event = Event::lifecycle("inv-1", "agent", step.clone());
+    /// assert!(event.is_lifecycle());
+    /// assert_eq!(event.lifecycle_event(), Some(step));
+    /// ```
+    pub fn lifecycle(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        lifecycle: LifecycleEvent,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.partial = true;
+        event.provider_metadata.insert(
+            LIFECYCLE_EVENT_KEY.to_string(),
+            serde_json::to_string(&lifecycle).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns true if this event was created by [`Event::lifecycle`].
+    pub fn is_lifecycle(&self) -> bool {
+        self.provider_metadata.contains_key(LIFECYCLE_EVENT_KEY)
+    }
+
+    /// Returns the run step reported by a lifecycle event, otherwise `None`.
+    pub fn lifecycle_event(&self) -> Option<LifecycleEvent> {
+        self.provider_metadata
+            .get(LIFECYCLE_EVENT_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event recorded when a delegated agent is cancelled for
+    /// exceeding its timeout.
+    ///
+    /// The event is authored by the delegating agent and carries
+    /// [`DelegationTimeout::message`] as text, so the delegating agent sees the
+    /// timeout in its history when control returns to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{DelegationTimeout, Event};
+    /// use std::time::Duration;
+    ///
+    /// let timeout = DelegationTimeout::new("researcher", Duration::from_secs(5));
+    /// let event = Event::delegation_timed_out("inv-1", "coordinator", timeout.clone());
+    /// assert_eq!(event.author, "coordinator");
+    /// assert_eq!(event.delegation_timeout(), Some(timeout));
+    /// ```
+    pub fn delegation_timed_out(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        timeout: DelegationTimeout,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.content = Some(Content::new("model").with_text(timeout.message()));
+        event.llm_response.turn_complete = true;
+        event.provider_metadata.insert(
+            DELEGATION_TIMEOUT_KEY.to_string(),
+            serde_json::to_string(&timeout).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the timeout reported by an event created by
+    /// [`Event::delegation_timed_out`], otherwise `None`.
+    pub fn delegation_timeout(&self) -> Option<DelegationTimeout> {
+        self.provider_metadata
+            .get(DELEGATION_TIMEOUT_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event a loop emits when its exit condition, time limit, or
+    /// iteration limit stops it.
+    ///
+    /// The event has no content, so it does not enter the conversation history.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, LoopExit, LoopExitReason};
+    ///
+    /// let exit = LoopExit { reason: LoopExitReason::Condition, iterations: 3 };
+    /// let event = Event::loop_exited("inv-1", "refine", exit.clone());
+    /// assert_eq!(event.loop_exit(), Some(exit));
+    /// ```
+    pub fn loop_exited(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        exit: LoopExit,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event
+            .provider_metadata
+            .insert(LOOP_EXIT_KEY.to_string(), serde_json::to_string(&exit).unwrap_or_default());
+        event
+    }
+
+    /// Returns the exit reported by an event created by [`Event::loop_exited`],
+    /// otherwise `None`.
+    pub fn loop_exit(&self) -> Option<LoopExit> {
+        self.provider_metadata.get(LOOP_EXIT_KEY).and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event an agent emits when a tool call fails.
+    ///
+    /// The event has no content, so it does not enter the conversation history.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, ToolCallFailure, ToolFailureResolution};
+    ///
+    /// let failure = ToolCallFailure {
+    ///     tool: "lookup".into(),
+    ///     call_id: "call-1".into(),
+    ///     error: "orders API returned 503".into(),
+    ///     resolution: ToolFailureResolution::ReturnedToModel,
+    /// };
+    /// let event = Event::tool_failed("inv-1", "assistant", failure.clone());
+    /// assert_eq!(event.tool_failure(), Some(failure));
+    /// ```
+    pub fn tool_failed(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        failure: ToolCallFailure,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.provider_metadata.insert(
+            TOOL_FAILURE_KEY.to_string(),
+            serde_json::to_string(&failure).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the failure reported by an event created by
+    /// [`Event::tool_failed`], otherwise `None`.
+    pub fn tool_failure(&self) -> Option<ToolCallFailure> {
+        self.provider_metadata
+            .get(TOOL_FAILURE_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event a conditional agent emits when it has chosen a branch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{BranchChoice, Event};
+    ///
+    /// let choice = BranchChoice { arm: Some(2), agent: Some("billing".into()) };
+    /// let event = Event::branch_chosen("inv-1", "router", choice.clone());
+    /// assert_eq!(event.branch_choice(), Some(choice));
+    /// ```
+    pub fn branch_chosen(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        choice: BranchChoice,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.provider_metadata.insert(
+            BRANCH_CHOICE_KEY.to_string(),
+            serde_json::to_string(&choice).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the choice reported by an event created by
+    /// [`Event::branch_chosen`], otherwise `None`.
+    pub fn branch_choice(&self) -> Option<BranchChoice> {
+        self.provider_metadata
+            .get(BRANCH_CHOICE_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event a pipeline emits when one of its steps escalates.
+    ///
+    /// The event is authored by the pipeline and carries
+    /// [`PipelineEscalation::message`] as text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, PipelineEscalation};
+    ///
+    /// let escalation = PipelineEscalation {
+    ///     agent: "reviewer".into(),
+    ///     step: 3,
+    ///     steps: 3,
+    ///     reason: Some("Content policy violation.".into()),
+    /// };
+    /// let event = Event::pipeline_stopped("inv-1", "publish", escalation.clone());
+    /// assert_eq!(event.pipeline_escalation(), Some(escalation));
+    /// ```
+    pub fn pipeline_stopped(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        escalation: PipelineEscalation,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.content = Some(Content::new("model").with_text(escalation.message()));
+        event.llm_response.turn_complete = true;
+        event.provider_metadata.insert(
+            PIPELINE_ESCALATION_KEY.to_string(),
+            serde_json::to_string(&escalation).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the escalation reported by an event created by
+    /// [`Event::pipeline_stopped`], otherwise `None`.
+    pub fn pipeline_escalation(&self) -> Option<PipelineEscalation> {
+        self.provider_metadata
+            .get(PIPELINE_ESCALATION_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event that ends a run whose transfer limit stopped a transfer.
+    ///
+    /// The event is authored by the agent that asked for the transfer and
+    /// carries [`TransferLimit::message`] as text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, TransferLimit};
+    ///
+    /// let limit = TransferLimit { target: "billing".into(), limit: 10 };
+    /// let event = Event::transfer_stopped("inv-1", "coordinator", limit.clone());
+    /// assert_eq!(event.transfer_limit(), Some(limit));
+    /// ```
+    pub fn transfer_stopped(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        limit: TransferLimit,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.content = Some(Content::new("model").with_text(limit.message()));
+        event.llm_response.turn_complete = true;
+        event.provider_metadata.insert(
+            TRANSFER_LIMIT_KEY.to_string(),
+            serde_json::to_string(&limit).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the stopped transfer reported by an event created by
+    /// [`Event::transfer_stopped`], otherwise `None`.
+    pub fn transfer_limit(&self) -> Option<TransferLimit> {
+        self.provider_metadata
+            .get(TRANSFER_LIMIT_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event recorded when an agent rejects its final answer and
+    /// retries the turn.
+    ///
+    /// The event carries [`TurnRetry::feedback`] as user text, the same message
+    /// the model receives, so the session history shows why the answer was redone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, TurnRetry};
+    ///
+    /// let retry = TurnRetry { attempt: 1, max_retries: 2, feedback: "Cite a source.".into() };
+    /// let event = Event::turn_retried("inv-1", "writer", retry.clone());
+    /// assert_eq!(event.turn_retry(), Some(retry));
+    /// ```
+    pub fn turn_retried(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        retry: TurnRetry,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.content = Some(Content::new("user").with_text(retry.feedback.clone()));
+        event
+            .provider_metadata
+            .insert(TURN_RETRY_KEY.to_string(), serde_json::to_string(&retry).unwrap_or_default());
+        event
+    }
+
+    /// Returns the retry reported by an event created by
+    /// [`Event::turn_retried`], otherwise `None`.
+    pub fn turn_retry(&self) -> Option<TurnRetry> {
+        self.provider_metadata
+            .get(TURN_RETRY_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event that ends a run stopped at its deadline.
+    ///
+    /// The event is authored by the agent that was running and carries
+    /// [`TurnTimeout::message`] as text. Events produced before the deadline
+    /// are kept, so they form a partial result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::{Event, TurnTimeout};
+    /// use std::time::Duration;
+    ///
+    /// let timeout = TurnTimeout::new("assistant", Duration::from_secs(30));
+    /// let event = Event::turn_timed_out("inv-1", "assistant", timeout.clone());
+    /// assert_eq!(event.turn_timeout(), Some(timeout));
+    /// ```
+    pub fn turn_timed_out(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        timeout: TurnTimeout,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.llm_response.content = Some(Content::new("model").with_text(timeout.message()));
+        event.llm_response.turn_complete = true;
+        event.provider_metadata.insert(
+            TURN_TIMEOUT_KEY.to_string(),
+            serde_json::to_string(&timeout).unwrap_or_default(),
+        );
+        event
+    }
+
+    /// Returns the timeout reported by an event created by
+    /// [`Event::turn_timed_out`], otherwise `None`.
+    pub fn turn_timeout(&self) -> Option<TurnTimeout> {
+        self.provider_metadata
+            .get(TURN_TIMEOUT_KEY)
+            .and_then(|value| serde_json::from_str(value).ok())
+    }
+
+    /// Create the event reporting a plan written by an agent's planner.
+    ///
+    /// The plan travels in `provider_metadata` rather than as content, so UIs
+    /// can render it apart from the answer and it is not sent to the model a
+    /// second time with the session history.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use adk_core::Event;
+    ///
+    /// let event = Event::planned("inv-1", "researcher", "1. Search\n2. Summarize");
+    /// assert_eq!(event.plan(), Some("1. Search\n2. Summarize"));
+    /// assert!(event.llm_response.content.is_none());
+    /// ```
+    pub fn planned(
+        invocation_id: impl Into<String>,
+        author: impl Into<String>,
+        plan: impl Into<String>,
+    ) -> Self {
+        let mut event = Event::new(invocation_id);
+        event.author = author.into();
+        event.provider_metadata.insert(PLAN_KEY.to_string(), plan.into());
+        event
+    }
+
+    /// Returns the plan reported by an event created by [`Event::planned`],
+    /// otherwise `None`.
+    pub fn plan(&self) -> Option<&str> {
+        self.provider_metadata.get(PLAN_KEY).map(String::as_str)
+    }
+
     /// Returns the tool calls carried by this event, as a typed, render-ready view.
     ///
     /// A UI consuming the agent's `EventStream` can call this on every event to
@@ -383,6 +1099,25 @@ impl Event {
         !has_function_calls && !has_function_responses && !is_partial && !has_trailing_code_result
     }
 
+    /// Whether this event is visible from `branch`: it was written on that
+    /// branch or on one of its ancestors. Root-branch events are visible
+    /// everywhere, while sibling branches of a parallel run do not see each
+    /// other.
+    pub fn is_visible_in_branch(&self, branch: &str) -> bool {
+        self.branch.is_empty()
+            || self.branch == branch
+            || branch.strip_prefix(self.branch.as_str()).is_some_and(|rest| rest.starts_with('.'))
+    }
+
+    /// Like [`is_final_response`](Self::is_final_response), but only for an
+    /// event written by `invocation_id` on exactly `branch`.
+    ///
+    /// Use it to pick one agent's answer when several agents write to the
+    /// same session, e.g. the branches of a parallel agent.
+    pub fn is_final_response_for(&self, invocation_id: &str, branch: &str) -> bool {
+        self.invocation_id == invocation_id && self.branch == branch && self.is_final_response()
+    }
+
     /// Returns true if the event content contains function calls.
     fn has_function_calls(&self) -> bool {
         if let Some(content) = &self.llm_response.content {
@@ -449,6 +1184,84 @@ mod tests {
         assert!(!event.id.is_empty());
     }
 
+    #[test]
+    fn test_event_metadata_round_trips_and_defaults_when_missing() {
+        let mut event = Event::new("inv-123");
+        event.branch = "fanout.web".to_string();
+        let restored: Event =
+            serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();
+        assert_eq!(restored.timestamp, event.timestamp);
+        assert_eq!(restored.invocation_id, "inv-123");
+        assert_eq!(restored.branch, "fanout.web");
+
+        // Persisted before events carried timestamps and branches
+        let mut legacy = serde_json::to_value(Event::new("inv-123")).unwrap();
+        for key in ["timestamp", "invocation_id", "branch"] {
+            legacy.as_object_mut().unwrap().remove(key);
+        }
+        let legacy: Event = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.timestamp, DateTime::<Utc>::UNIX_EPOCH);
+        assert!(legacy.invocation_id.is_empty());
+        assert!(legacy.branch.is_empty());
+    }
+
+    #[test]
+    fn test_thinking_parts_round_trip_and_legacy_parts_still_load() {
+        let mut event = Event::new("inv-1");
+        event.llm_response.content = Some(Content {
+            role: "model".to_string(),
+            parts: vec![
+                Part::Thinking {
+                    thinking: "check units".to_string(),
+                    signature: Some("sig".into()),
+                },
+                Part::FunctionCall {
+                    name: "convert".to_string(),
+                    args: serde_json::json!({ "km": 5 }),
+                    id: None,
+                    thought_signature: Some("call-sig".to_string()),
+                },
+            ],
+        });
+        let restored: Event =
+            serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();
+        assert_eq!(restored.content().unwrap().parts, event.content().unwrap().parts);
+
+        // Persisted before parts carried signatures
+        let mut legacy = serde_json::to_value(&event).unwrap();
+        legacy["content"]["parts"] = serde_json::json!([
+            { "thinking": "check units" },
+            { "name": "convert", "args": { "km": 5 } },
+            { "text": "5 km is 3.1 miles." }
+        ]);
+        let legacy: Event = serde_json::from_value(legacy).unwrap();
+        let parts = &legacy.llm_response.content.unwrap().parts;
+        assert!(matches!(&parts[0], Part::Thinking { signature: None, .. }));
+        assert!(matches!(&parts[1], Part::FunctionCall { thought_signature: None, .. }));
+        assert_eq!(parts[2].text(), Some("5 km is 3.1 miles."));
+    }
+
+    #[test]
+    fn test_branch_visibility_and_final_response_for_branch() {
+        let mut root = Event::new("inv-1");
+        root.llm_response.content = Some(Content::new("model").with_text("plan"));
+        let mut web = root.clone();
+        web.branch = "fanout.web".to_string();
+        let mut news = root.clone();
+        news.branch = "fanout.news".to_string();
+
+        assert!(root.is_visible_in_branch("fanout.web"));
+        assert!(web.is_visible_in_branch("fanout.web"));
+        assert!(web.is_visible_in_branch("fanout.web.deep"));
+        assert!(!news.is_visible_in_branch("fanout.web"));
+        assert!(!web.is_visible_in_branch("fanout.webby"));
+        assert!(!web.is_visible_in_branch(""));
+
+        assert!(web.is_final_response_for("inv-1", "fanout.web"));
+        assert!(!news.is_final_response_for("inv-1", "fanout.web"));
+        assert!(!web.is_final_response_for("inv-2", "fanout.web"));
+    }
+
     #[test]
     fn test_event_actions_default() {
         let actions = EventActions::default();
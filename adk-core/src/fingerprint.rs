@@ -0,0 +1,162 @@
+//! Canonical serialization of [`Content`] and JSON values for cache keys.
+//!
+//! Hashing `serde_json::to_string` output is not stable: with `serde_json`'s
+//! `preserve_order` feature (which several dependencies turn on) object keys
+//! keep their insertion order, so the same tool arguments parsed from two
+//! providers serialize differently, and `1`, `1.0`, and `-0.0` all print
+//! differently. The canonical form fixes both:
+//!
+//! - object keys are sorted at every depth,
+//! - numbers with an integral value are written as integers and `-0.0` as `0`,
+//! - every [`Part`] carries an explicit `type` tag rather than relying on the
+//!   untagged field layout,
+//! - inline data is represented by the SHA-256 of its bytes, and
+//! - provider-assigned tool call ids and thought signatures are left out,
+//!   since they differ between otherwise identical requests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::{Content, Part, content_fingerprint};
+//! use serde_json::json;
+//!
+//! let call = |args| Content {
+//!     role: "model".to_string(),
+//!     parts: vec![Part::FunctionCall {
+//!         name: "get_weather".to_string(),
+//!         args,
+//!         id: None,
+//!         thought_signature: None,
+//!     }],
+//! };
+//!
+//! let a = call(json!({"city": "Paris", "days": 3}));
+//! let b = call(json!({"days": 3.0, "city": "Paris"}));
+//! assert_eq!(content_fingerprint(&[a]), content_fingerprint(&[b]));
+//! ```
+
+use crate::types::{Content, Part};
+use serde_json::{Map, Number, Value, json};
+use sha2::{Digest, Sha256};
+
+/// Largest integer an `f64` represents exactly (2^53).
+const MAX_EXACT_FLOAT_INT: f64 = 9_007_199_254_740_992.0;
+
+/// Returns `value` with object keys sorted at every depth and numbers
+/// normalized.
+pub fn canonical_value(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut keys: Vec<&String> = object.keys().collect();
+            keys.sort();
+            let mut sorted = Map::with_capacity(object.len());
+            for key in keys {
+                sorted.insert(key.clone(), canonical_value(&object[key]));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonical_value).collect()),
+        Value::Number(number) => Value::Number(canonical_number(number)),
+        other => other.clone(),
+    }
+}
+
+/// Serializes `value` in canonical form; see [`canonical_value`].
+pub fn canonical_json(value: &Value) -> String {
+    canonical_value(value).to_string()
+}
+
+/// Returns the hex SHA-256 of the canonical form of `value`.
+pub fn value_fingerprint(value: &Value) -> String {
+    hex::encode(Sha256::digest(canonical_json(value).as_bytes()))
+}
+
+/// Returns the hex SHA-256 of the canonical form of `contents`.
+///
+/// Contents that differ only in JSON key order, number formatting, tool call
+/// ids, or thought signatures get the same fingerprint.
+pub fn content_fingerprint(contents: &[Content]) -> String {
+    let canonical = Value::Array(contents.iter().map(Content::canonical_value).collect());
+    hex::encode(Sha256::digest(canonical.to_string().as_bytes()))
+}
+
+impl Content {
+    /// The canonical JSON form of this content, used for fingerprints.
+    pub fn canonical_value(&self) -> Value {
+        json!({
+            "parts": self.parts.iter().map(Part::canonical_value).collect::<Vec<_>>(),
+            "role": self.role,
+        })
+    }
+}
+
+impl Part {
+    /// The canonical JSON form of this part, tagged with its `type`.
+    pub fn canonical_value(&self) -> Value {
+        let value = match self {
+            Part::Thinking { thinking, .. } => json!({ "type": "thinking", "thinking": thinking }),
+            Part::Text { text } => json!({ "type": "text", "text": text }),
+            Part::InlineData { mime_type, data } => json!({
+                "type": "inline_data",
+                "mime_type": mime_type,
+                "sha256": hex::encode(Sha256::digest(data)),
+            }),
+            Part::FileData { mime_type, file_uri } => {
+                json!({ "type": "file_data", "mime_type": mime_type, "file_uri": file_uri })
+            }
+            Part::FunctionCall { name, args, .. } => {
+                json!({ "type": "function_call", "name": name, "args": args })
+            }
+            Part::FunctionResponse { function_response, .. } => json!({
+                "type": "function_response",
+                "name": function_response.name,
+                "response": function_response.response,
+                "inline_data": function_response
+                    .inline_data
+                    .iter()
+                    .map(|part| json!({
+                        "mime_type": part.mime_type,
+                        "sha256": hex::encode(Sha256::digest(&part.data)),
+                    }))
+                    .collect::<Vec<_>>(),
+                "file_data": function_response
+                    .file_data
+                    .iter()
+                    .map(|part| json!({ "mime_type": part.mime_type, "file_uri": part.file_uri }))
+                    .collect::<Vec<_>>(),
+            }),
+            Part::ServerToolCall { server_tool_call } => {
+                json!({ "type": "server_tool_call", "server_tool_call": server_tool_call })
+            }
+            Part::ServerToolResponse { server_tool_response } => json!({
+                "type": "server_tool_response",
+                "server_tool_response": server_tool_response,
+            }),
+        };
+        canonical_value(&value)
+    }
+}
+
+fn canonical_number(number: &Number) -> Number {
+    if number.is_i64() || number.is_u64() {
+        return number.clone();
+    }
+    match number.as_f64() {
+        // Also maps -0.0 to 0.
+        Some(float) if float.fract() == 0.0 && float.abs() <= MAX_EXACT_FLOAT_INT => {
+            Number::from(float as i64)
+        }
+        _ => number.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_numbers() {
+        let value = json!([1, 1.0, -0.0, 2.5, 1e20, u64::MAX]);
+        assert_eq!(canonical_json(&value), format!("[1,1,0,2.5,1e+20,{}]", u64::MAX));
+    }
+}
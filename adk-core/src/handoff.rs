@@ -0,0 +1,110 @@
+//! Structured delegation from a coordinator to a sub-agent.
+//!
+//! A plain transfer only moves control: the sub-agent sees the conversation
+//! and has to work out what it was asked to do. A [`Handoff`] states it
+//! explicitly — the task, the session state keys it should rely on, the
+//! constraints it must respect, and an optional JSON Schema its answer has to
+//! match.
+//!
+//! The coordinator attaches a handoff to its transfer event
+//! ([`EventActions::handoff`](crate::EventActions::handoff)); the runner
+//! places it in the sub-agent's [`RunConfig::handoff`](crate::RunConfig::handoff),
+//! where it is read through
+//! [`InvocationContext::handoff`](crate::InvocationContext::handoff). An
+//! `LlmAgent` that receives one adds its [`instruction`](Handoff::instruction)
+//! to the prompt and holds its answer to the return schema.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::Handoff;
+//! use serde_json::json;
+//!
+//! let handoff = Handoff::new("Refund the customer's last order")
+//!     .with_context_key("order_id")
+//!     .with_constraint("Never refund more than $200")
+//!     .with_return_schema(json!({
+//!         "type": "object",
+//!         "properties": { "refunded": { "type": "number" } },
+//!         "required": ["refunded"]
+//!     }));
+//!
+//! assert_eq!(handoff.constraints.len(), 1);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::State;
+
+/// A task delegated to another agent, with the context and limits it comes with.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Handoff {
+    /// What the receiving agent is asked to do.
+    pub task: String,
+    /// Session state keys whose values the receiving agent should use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_keys: Vec<String>,
+    /// Rules the receiving agent must follow.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<String>,
+    /// JSON Schema the receiving agent's answer must conform to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_schema: Option<Value>,
+}
+
+impl Handoff {
+    /// Creates a handoff for `task` with no context, constraints, or schema.
+    pub fn new(task: impl Into<String>) -> Self {
+        Self { task: task.into(), ..Default::default() }
+    }
+
+    /// Adds a session state key the receiving agent should use.
+    pub fn with_context_key(mut self, key: impl Into<String>) -> Self {
+        self.context_keys.push(key.into());
+        self
+    }
+
+    /// Adds several session state keys the receiving agent should use.
+    pub fn with_context_keys(mut self, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.context_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a rule the receiving agent must follow.
+    pub fn with_constraint(mut self, constraint: impl Into<String>) -> Self {
+        self.constraints.push(constraint.into());
+        self
+    }
+
+    /// Requires the receiving agent's answer to match `schema`.
+    pub fn with_return_schema(mut self, schema: Value) -> Self {
+        self.return_schema = Some(schema);
+        self
+    }
+
+    /// Renders the handoff as an instruction for the receiving agent.
+    ///
+    /// Context keys are resolved against `state`; keys with no value are
+    /// listed as missing so the agent does not assume them.
+    pub fn instruction(&self, state: &dyn State) -> String {
+        let mut text = format!("You have been handed the following task:\n{}\n", self.task);
+        if !self.context_keys.is_empty() {
+            text.push_str("\nContext:\n");
+            for key in &self.context_keys {
+                match state.get(key) {
+                    Some(Value::String(value)) => text.push_str(&format!("- {key}: {value}\n")),
+                    Some(value) => text.push_str(&format!("- {key}: {value}\n")),
+                    None => text.push_str(&format!("- {key}: (missing)\n")),
+                }
+            }
+        }
+        if !self.constraints.is_empty() {
+            text.push_str("\nConstraints:\n");
+            for constraint in &self.constraints {
+                text.push_str(&format!("- {constraint}\n"));
+            }
+        }
+        text
+    }
+}
@@ -1,4 +1,31 @@
-use crate::{AdkError, InvocationContext, Result};
+use crate::{AdkError, InvocationContext, Result, State};
+use serde_json::Value;
+
+/// How a `{key}` placeholder renders when its state key is missing and the
+/// placeholder has no `?` default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingKeyPolicy {
+    /// Fail with an error naming the key. This is the historical behavior.
+    #[default]
+    Error,
+    /// Render the placeholder as an empty string.
+    Empty,
+}
+
+/// Options for [`inject_session_state_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TemplateOptions {
+    /// What to do with a required state key that is not set.
+    pub missing_key: MissingKeyPolicy,
+}
+
+impl TemplateOptions {
+    /// Sets the policy for required state keys that are not set.
+    pub fn missing_key(mut self, policy: MissingKeyPolicy) -> Self {
+        self.missing_key = policy;
+        self
+    }
+}
 
 /// Checks if a character is valid as the first character of a placeholder identifier.
 fn is_ident_start(c: char) -> bool {
@@ -13,6 +40,7 @@ fn is_ident_body(c: char) -> bool {
 /// Finds the next placeholder `{...}` in `template` starting from byte offset `from`.
 /// Returns `Some((start, end, content))` where start/end are byte offsets of the
 /// outer braces and content is the inner string (without braces).
+/// A brace escaped as `\{` never opens a placeholder.
 /// Returns `None` when no more placeholders exist.
 fn find_next_placeholder(template: &str, from: usize) -> Option<(usize, usize, &str)> {
     let bytes = template.as_bytes();
@@ -20,7 +48,7 @@ fn find_next_placeholder(template: &str, from: usize) -> Option<(usize, usize, &
     let mut i = from;
 
     while i < len {
-        if bytes[i] == b'{' {
+        if bytes[i] == b'{' && !is_escaped(bytes, i) {
             let content_start = i + 1;
             if content_start >= len {
                 break;
@@ -35,9 +63,12 @@ fn find_next_placeholder(template: &str, from: usize) -> Option<(usize, usize, &
             while j < len && is_ident_body(bytes[j] as char) {
                 j += 1;
             }
-            // Optional trailing '?'
+            // Optional trailing '?' with an optional default value after it
             if j < len && bytes[j] == b'?' {
                 j += 1;
+                while j < len && bytes[j] != b'}' && bytes[j] != b'{' {
+                    j += 1;
+                }
             }
             // Must close with '}'
             if j < len && bytes[j] == b'}' {
@@ -50,6 +81,127 @@ fn find_next_placeholder(template: &str, from: usize) -> Option<(usize, usize, &
     None
 }
 
+/// Returns true when the byte at `at` is preceded by an unescaped backslash.
+fn is_escaped(bytes: &[u8], at: usize) -> bool {
+    let backslashes = bytes[..at].iter().rev().take_while(|&&b| b == b'\\').count();
+    backslashes % 2 == 1
+}
+
+/// Appends literal template text, turning `\{` and `\}` into bare braces.
+fn push_literal(out: &mut String, text: &str) {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' && matches!(chars.peek(), Some('{' | '}')) {
+            continue;
+        }
+        out.push(c);
+    }
+}
+
+/// Whether a state value enables an `{{#if}}` block: present and not
+/// `null`, `false`, `0`, or an empty string, array, or object.
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(Value::Object(o)) => !o.is_empty(),
+    }
+}
+
+/// A block tag found by [`find_next_block_tag`].
+enum BlockTag<'a> {
+    If(&'a str),
+    Else,
+    EndIf,
+}
+
+/// Finds the next unescaped `{{#if key}}`, `{{else}}`, or `{{/if}}` tag at or
+/// after `from`. Returns the tag with the byte offsets of its outer braces.
+fn find_next_block_tag(template: &str, from: usize) -> Option<(usize, usize, BlockTag<'_>)> {
+    let bytes = template.as_bytes();
+    let mut i = from;
+    while let Some(offset) = template[i..].find("{{") {
+        let start = i + offset;
+        let rest = &template[start + 2..];
+        if !is_escaped(bytes, start)
+            && let Some(close) = rest.find("}}")
+        {
+            let inner = rest[..close].trim();
+            let end = start + 2 + close + 2;
+            if let Some(key) = inner.strip_prefix("#if") {
+                if key.starts_with(char::is_whitespace) {
+                    return Some((start, end, BlockTag::If(key.trim())));
+                }
+            } else if inner == "else" {
+                return Some((start, end, BlockTag::Else));
+            } else if inner == "/if" {
+                return Some((start, end, BlockTag::EndIf));
+            }
+        }
+        i = start + 1;
+    }
+    None
+}
+
+/// Resolves `{{#if key}}...{{else}}...{{/if}}` blocks against session state,
+/// keeping only the text of the branches that apply. Blocks nest. Placeholders
+/// inside the kept text are left for [`replace_match`].
+fn resolve_blocks(state: &dyn State, template: &str) -> Result<String> {
+    struct Frame {
+        /// Whether the enclosing text is being kept.
+        parent_active: bool,
+        /// Whether the `{{#if}}` condition held.
+        condition: bool,
+        in_else: bool,
+    }
+
+    let mut out = String::with_capacity(template.len());
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut active = true;
+    let mut last_end = 0;
+
+    while let Some((start, end, tag)) = find_next_block_tag(template, last_end) {
+        if active {
+            out.push_str(&template[last_end..start]);
+        }
+        match tag {
+            BlockTag::If(key) => {
+                if !is_valid_state_name(key) {
+                    return Err(AdkError::agent(format!(
+                        "Invalid state key '{key}' in {{{{#if}}}} block"
+                    )));
+                }
+                let condition = is_truthy(state.get(key).as_ref());
+                stack.push(Frame { parent_active: active, condition, in_else: false });
+                active = active && condition;
+            }
+            BlockTag::Else => {
+                let frame = stack.last_mut().filter(|frame| !frame.in_else).ok_or_else(|| {
+                    AdkError::agent("Unexpected {{else}} outside an {{#if}} block")
+                })?;
+                frame.in_else = true;
+                active = frame.parent_active && !frame.condition;
+            }
+            BlockTag::EndIf => {
+                let frame = stack.pop().ok_or_else(|| {
+                    AdkError::agent("Unexpected {{/if}} without an {{#if}} block")
+                })?;
+                active = frame.parent_active;
+            }
+        }
+        last_end = end;
+    }
+
+    if !stack.is_empty() {
+        return Err(AdkError::agent("Unclosed {{#if}} block in instruction template"));
+    }
+    out.push_str(&template[last_end..]);
+    Ok(out)
+}
+
 /// Checks if a string is a valid identifier (like Python's str.isidentifier())
 /// Must start with letter or underscore, followed by letters, digits, or underscores
 fn is_identifier(s: &str) -> bool {
@@ -84,13 +236,17 @@ fn is_valid_state_name(var_name: &str) -> bool {
 }
 
 /// Replaces a single placeholder match with its resolved value
-/// Handles {var}, {var?}, and {artifact.name} syntax
-async fn replace_match(ctx: &dyn InvocationContext, content: &str) -> Result<String> {
-    let var_name = content.trim();
-
-    // Check if optional (ends with ?)
-    let (var_name, optional) =
-        if let Some(name) = var_name.strip_suffix('?') { (name, true) } else { (var_name, false) };
+/// Handles {var}, {var?}, {var?default}, and {artifact.name} syntax
+async fn replace_match(
+    ctx: &dyn InvocationContext,
+    content: &str,
+    options: TemplateOptions,
+) -> Result<String> {
+    // Everything after the first '?' is the default; `{var?}` defaults to empty
+    let (var_name, default) = match content.split_once('?') {
+        Some((name, default)) => (name.trim(), Some(default)),
+        None => (content.trim(), None),
+    };
 
     // Handle artifact.{name} pattern
     if let Some(file_name) = var_name.strip_prefix("artifact.") {
@@ -119,8 +275,8 @@ async fn replace_match(ctx: &dyn InvocationContext, content: &str) -> Result<Str
                 Ok(String::new())
             }
             Err(e) => {
-                if optional {
-                    Ok(String::new())
+                if let Some(default) = default {
+                    Ok(default.to_string())
                 } else {
                     Err(AdkError::agent(format!("Failed to load artifact {file_name}: {e}")))
                 }
@@ -137,13 +293,13 @@ async fn replace_match(ctx: &dyn InvocationContext, content: &str) -> Result<Str
                     Ok(format!("{}", value))
                 }
             }
-            None => {
-                if optional {
-                    Ok(String::new())
-                } else {
+            None => match (default, options.missing_key) {
+                (Some(default), _) => Ok(default.to_string()),
+                (None, MissingKeyPolicy::Empty) => Ok(String::new()),
+                (None, MissingKeyPolicy::Error) => {
                     Err(AdkError::agent(format!("State variable '{var_name}' not found")))
                 }
-            }
+            },
         }
     } else {
         // Not a valid variable name - return original match as literal
@@ -156,8 +312,14 @@ async fn replace_match(ctx: &dyn InvocationContext, content: &str) -> Result<Str
 /// Supports the following placeholder syntax:
 /// - `{var_name}` - Required session state variable (errors if missing)
 /// - `{var_name?}` - Optional variable (empty string if missing)
+/// - `{var_name?default}` - Optional variable with a fallback value
 /// - `{artifact.file_name}` - Artifact content insertion
 /// - `{app:var}`, `{user:var}`, `{temp:var}` - Prefixed state variables
+/// - `{{#if var}}...{{else}}...{{/if}}` - Sections kept only when `var` is set
+///   and truthy (not `null`, `false`, `0`, or empty); blocks may nest
+/// - `\{` and `\}` - Literal braces
+///
+/// Placeholders with an unknown prefix, such as `{note:x}`, are left as-is.
 ///
 /// # Examples
 ///
@@ -174,23 +336,40 @@ async fn replace_match(ctx: &dyn InvocationContext, content: &str) -> Result<Str
 /// - A required artifact cannot be loaded
 /// - The artifact service is not initialized
 pub async fn inject_session_state(ctx: &dyn InvocationContext, template: &str) -> Result<String> {
+    inject_session_state_with(ctx, template, TemplateOptions::default()).await
+}
+
+/// Like [`inject_session_state`], with [`TemplateOptions`] controlling how
+/// missing state keys render.
+///
+/// With [`MissingKeyPolicy::Empty`], a required `{var_name}` whose key is not
+/// set renders as an empty string instead of failing. Missing artifacts and
+/// malformed `{{#if}}` blocks still return errors.
+pub async fn inject_session_state_with(
+    ctx: &dyn InvocationContext,
+    template: &str,
+    options: TemplateOptions,
+) -> Result<String> {
+    let template = resolve_blocks(ctx.session().state(), template)?;
+    let template = template.as_str();
+
     // Pre-allocate 20% extra capacity to reduce reallocations when placeholders expand
     let mut result = String::with_capacity((template.len() as f32 * 1.2) as usize);
     let mut last_end = 0;
 
     while let Some((start, end, content)) = find_next_placeholder(template, last_end) {
         // Append text between last match and this one
-        result.push_str(&template[last_end..start]);
+        push_literal(&mut result, &template[last_end..start]);
 
         // Get the replacement for the current match
-        let replacement = replace_match(ctx, content).await?;
+        let replacement = replace_match(ctx, content, options).await?;
         result.push_str(&replacement);
 
         last_end = end;
     }
 
     // Append any remaining text
-    result.push_str(&template[last_end..]);
+    push_literal(&mut result, &template[last_end..]);
 
     Ok(result)
 }
@@ -235,6 +414,21 @@ mod tests {
         assert_eq!(c, "name?");
     }
 
+    #[test]
+    fn test_find_placeholder_default() {
+        let t = "Hello {user:name?there}!";
+        let (s, e, c) = find_next_placeholder(t, 0).unwrap();
+        assert_eq!(c, "user:name?there");
+        assert_eq!(&t[s..e], "{user:name?there}");
+    }
+
+    #[test]
+    fn test_find_placeholder_skips_escaped() {
+        assert!(find_next_placeholder(r"\{name}", 0).is_none());
+        let (_, _, c) = find_next_placeholder(r"\\{name}", 0).unwrap();
+        assert_eq!(c, "name");
+    }
+
     #[test]
     fn test_find_placeholder_prefixed() {
         let t = "Value: {app:config}";
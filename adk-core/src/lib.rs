@@ -71,19 +71,35 @@
 //! - `user:` - User preferences (persists across sessions)
 //! - `app:` - Application state (application-wide)
 //! - `temp:` - Temporary data (cleared each turn)
+//!
+//! For run-local values that should never become state (intermediate results,
+//! a shared plan, non-serializable handles), use the [`Blackboard`] exposed by
+//! [`CallbackContext::blackboard`].
 
 /// Core agent trait and event stream type.
 pub mod agent;
 /// Dynamic agent loading by name.
 pub mod agent_loader;
+/// Run-scoped typed key-value store shared by agents and tools.
+pub mod blackboard;
 /// Callback type aliases for agent, model, and tool lifecycle hooks.
 pub mod callbacks;
+/// Injectable source of the current time, with a mock for tests.
+pub mod clock;
+/// Draining an event stream into one final response.
+pub mod collect;
 /// Invocation context traits: state, session, artifacts, memory, and run configuration.
 pub mod context;
+/// Run-wide dialogue shared across agents in a multi-agent tree.
+pub mod conversation_buffer;
 /// Unified structured error type and result alias.
 pub mod error;
 /// Event types representing agent interactions in a conversation.
 pub mod event;
+/// Canonical serialization and fingerprints of content for cache keys.
+pub mod fingerprint;
+/// Structured task handoffs between agents.
+pub mod handoff;
 /// Typed identity primitives for app, user, session, and invocation.
 pub mod identity;
 /// Template-based instruction injection with session state interpolation.
@@ -102,53 +118,80 @@ pub mod schema_cache;
 pub mod schema_utils;
 /// Thread-safe shared state for parallel agent coordination.
 pub mod shared_state;
+/// Progressive assembly of schema-constrained JSON from streamed output.
+pub mod structured_output;
 /// Tool trait, toolset, execution strategy, and registry.
 pub mod tool;
 /// Semaphore-based tool concurrency management.
 pub mod tool_concurrency;
+/// Structured tool failures and how the agent handles them.
+pub mod tool_error;
+/// Size limits and truncation for tool results.
+pub mod tool_output;
 /// Content, Part, and multimodal data types.
 pub mod types;
 
-pub use agent::{Agent, EventStream, ResolvedContext};
+pub use agent::{
+    Agent, AgentInfo, ComponentStatus, EventStream, ResolvedContext, ToolInfo, WarmupReport,
+};
 pub use agent_loader::{AgentLoader, MultiAgentLoader, SingleAgentLoader};
+pub use blackboard::Blackboard;
 pub use callbacks::{
     AfterAgentCallback, AfterModelCallback, AfterToolCallback, AfterToolCallbackFull,
     BaseEventsSummarizer, BeforeAgentCallback, BeforeModelCallback, BeforeModelResult,
-    BeforeToolCallback, EventsCompactionConfig, GlobalInstructionProvider, InstructionProvider,
-    OnToolErrorCallback,
+    BeforeToolCallback, EventsCompactionConfig, GlobalInstruction, GlobalInstructionProvider,
+    InstructionProvider, OnToolErrorCallback,
 };
+pub use clock::{Clock, MockClock, SystemClock, system_clock};
+pub use collect::{CollectedResponse, collect_final_response};
 pub use context::{
-    Artifacts, BackpressurePolicy, CallbackContext, IncludeContents, InvocationContext,
-    MAX_STATE_KEY_LEN, Memory, MemoryEntry, ReadonlyContext, ReadonlyState, RunConfig,
-    RunConfigBuilder, RuntimeToolset, SecretService, Session, State, StreamingMode,
+    Artifacts, BackpressurePolicy, CallbackContext, EventFilter, IncludeContents,
+    InvocationContext, MAX_STATE_KEY_LEN, Memory, MemoryEntry, ReadonlyContext, ReadonlyState,
+    RunConfig, RunConfigBuilder, RuntimeToolset, SecretService, Session, State, StreamingMode,
     ToolCallbackContext, ToolConcurrencyConfig, ToolConfirmationDecision, ToolConfirmationHandler,
     ToolConfirmationPolicy, ToolConfirmationRequest, ToolOutcome, validate_state_key,
 };
+pub use conversation_buffer::{ConversationBuffer, ConversationEntry, SharedHistory};
 pub use error::{AdkError, ErrorCategory, ErrorComponent, ErrorDetails, Result, RetryHint};
 pub use event::{
-    Event, EventActions, EventCompaction, KEY_PREFIX_APP, KEY_PREFIX_TEMP, KEY_PREFIX_USER,
-    TOOL_PROGRESS_CALL_ID_KEY, TOOL_PROGRESS_STREAM_KEY, ToolCallView, ToolResultView,
+    BRANCH_CHOICE_KEY, BranchChoice, DELEGATION_TIMEOUT_KEY, DelegationTimeout, Event,
+    EventActions, EventCompaction, KEY_PREFIX_APP, KEY_PREFIX_TEMP, KEY_PREFIX_USER,
+    LIFECYCLE_EVENT_KEY, LOOP_EXIT_KEY, LifecycleEvent, LoopExit, LoopExitReason,
+    PIPELINE_ESCALATION_KEY, PLAN_KEY, PipelineEscalation, SUB_AGENT_CALL_ID_KEY, TOOL_FAILURE_KEY,
+    TOOL_PROGRESS_CALL_ID_KEY, TOOL_PROGRESS_STREAM_KEY, TRANSFER_LIMIT_KEY, TURN_RETRY_KEY,
+    TURN_TIMEOUT_KEY, ToolCallFailure, ToolCallView, ToolFailureResolution, ToolResultView,
+    TransferLimit, TurnRetry, TurnTimeout,
 };
+pub use fingerprint::{canonical_json, canonical_value, content_fingerprint, value_fingerprint};
+pub use handoff::Handoff;
 pub use identity::{
     AdkIdentity, AppName, ExecutionIdentity, IdentityError, InvocationId, SessionId, UserId,
 };
-pub use instruction_template::inject_session_state;
+pub use instruction_template::{
+    MissingKeyPolicy, TemplateOptions, inject_session_state, inject_session_state_with,
+};
 pub use intra_compaction::IntraCompactionConfig;
 pub use model::{
-    CacheCapable, CitationMetadata, CitationSource, ContextCacheConfig, FinishReason,
-    GenerateContentConfig, Llm, LlmRequest, LlmResponse, LlmResponseStream, UsageMetadata,
+    CacheCapable, Citation, CitationMetadata, CitationSource, ContextCacheConfig, FinishReason,
+    GenerateContentConfig, Llm, LlmRequest, LlmResponse, LlmResponseStream, TextSpan,
+    TokenBreakdown, UsageMetadata, estimate_request_tokens, estimate_text_tokens,
+    generate_concurrently, invocation_user_content, with_invocation_user_content,
 };
 pub use request_context::RequestContext;
 pub use schema_adapter::{GenericSchemaAdapter, SchemaAdapter};
 pub use schema_cache::SchemaCache;
 pub use shared_state::{SharedState, SharedStateError};
+pub use structured_output::StructuredOutputAssembler;
 pub use tool::{
     RetryBudget, Tool, ToolContext, ToolExecutionStrategy, ToolPredicate, ToolRegistry, Toolset,
     ValidationMode,
 };
 pub use tool_concurrency::{ConcurrencyPermit, ToolConcurrencyManager};
+pub use tool_error::{ToolError, ToolErrorDecision, ToolErrorHandler, ToolErrorPolicy};
+pub use tool_output::{ToolOutputLimitConfig, TruncatedToolOutput, truncate_tool_output};
 pub use types::{
     Content, FileDataPart, FunctionResponseData, InlineDataPart, MAX_INLINE_DATA_SIZE, Part,
+    batch_function_responses, last_n_turns,
 };
 
 // Re-export async_trait so the #[tool] macro's generated code can reference it
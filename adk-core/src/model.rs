@@ -1,6 +1,10 @@
 use crate::schema_adapter::{GenericSchemaAdapter, SchemaAdapter};
-use crate::{Result, types::Content};
+use crate::{
+    AdkError, Result,
+    types::{Content, Part},
+};
 use async_trait::async_trait;
+use futures::StreamExt;
 use futures::stream::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -45,6 +49,270 @@ pub trait Llm: Send + Sync {
     fn uses_interactions_api(&self) -> bool {
         false
     }
+
+    /// Returns the maximum number of tool declarations this model accepts in
+    /// one request, or `None` if the provider does not enforce a limit.
+    ///
+    /// Agents check this before calling the model so that an oversized tool
+    /// list fails early with a clear message instead of a provider error.
+    fn max_tools(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns `true` if this model enforces a full JSON Schema through
+    /// [`GenerateContentConfig::response_schema`].
+    ///
+    /// Agents with an output schema send it to models that return `true` and
+    /// fall back to plain JSON mode ([`GenerateContentConfig::json_output`])
+    /// for models that return `false`. Default implementation returns `true`.
+    fn supports_response_schema(&self) -> bool {
+        true
+    }
+
+    /// Generates one response per request, returned in the order of
+    /// `requests`.
+    ///
+    /// The default runs up to `concurrency` non-streaming
+    /// [`generate_content`](Self::generate_content) calls at a time via
+    /// [`generate_concurrently`]. Providers with a native batch API override
+    /// this to submit all requests as one job, typically at a lower price and
+    /// with higher latency.
+    async fn generate_batch(
+        &self,
+        requests: Vec<LlmRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<LlmResponse>> {
+        generate_concurrently(self, requests, concurrency).await
+    }
+
+    /// Counts the input tokens of `req` without generating a response.
+    ///
+    /// Use it before sending a request to check that it fits the context
+    /// window or to estimate its cost. Providers override this with their
+    /// token-counting endpoint or tokenizer and document how exact the result
+    /// is. The default is [`estimate_request_tokens`], which needs no
+    /// tokenizer and is usually within 20% of the real count.
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        Ok(estimate_request_tokens(req))
+    }
+
+    /// Counts the input tokens of `req` like [`count_tokens`](Self::count_tokens),
+    /// split into the system instruction, the tool declarations and the
+    /// conversation, to show what dominates a request.
+    ///
+    /// The default counts `req` once more without its `system` contents and
+    /// once more without its tools, concurrently, and attributes each
+    /// difference to the part left out. The conversation gets the rest,
+    /// including any per-message framing the provider adds, so the parts
+    /// always add up to [`TokenBreakdown::total`]. Parts the request does not
+    /// have are `0` and cost no extra count.
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<TokenBreakdown> {
+        let has_system = req.contents.iter().any(|content| content.role == "system");
+        let without_system = LlmRequest {
+            contents: req
+                .contents
+                .iter()
+                .filter(|content| content.role != "system")
+                .cloned()
+                .collect(),
+            ..req.clone()
+        };
+        let without_tools = LlmRequest { tools: HashMap::new(), ..req.clone() };
+
+        let count_unless = |skip: bool, request: LlmRequest| async move {
+            if skip { Ok(None) } else { self.count_tokens(&request).await.map(Some) }
+        };
+        let (total, without_system, without_tools) = futures::try_join!(
+            self.count_tokens(req),
+            count_unless(!has_system, without_system),
+            count_unless(req.tools.is_empty(), without_tools),
+        )?;
+
+        let system_instruction = without_system.map_or(0, |count| total.saturating_sub(count));
+        let tools = without_tools.map_or(0, |count| total.saturating_sub(count));
+        Ok(TokenBreakdown {
+            total,
+            system_instruction,
+            tools,
+            contents: total.saturating_sub(system_instruction + tools),
+        })
+    }
+}
+
+/// Input tokens of a request by where they come from, as returned by
+/// [`Llm::count_tokens_breakdown`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBreakdown {
+    /// Tokens of the whole request, as [`Llm::count_tokens`] reports them.
+    pub total: usize,
+    /// Tokens of the `system` contents.
+    pub system_instruction: usize,
+    /// Tokens of the tool declarations.
+    pub tools: usize,
+    /// Tokens of the rest of the conversation, including message framing.
+    pub contents: usize,
+}
+
+/// Estimates the input tokens of `req` without a tokenizer.
+///
+/// Sums [`estimate_text_tokens`] over every content part, including the
+/// system instruction, with function calls, function responses and tool
+/// declarations counted as their JSON. Inline and file data are not counted.
+pub fn estimate_request_tokens(req: &LlmRequest) -> usize {
+    let contents: usize = req
+        .contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .map(|part| match part {
+            Part::Text { text } => estimate_text_tokens(text),
+            Part::Thinking { thinking, .. } => estimate_text_tokens(thinking),
+            Part::FunctionCall { name, args, .. } => {
+                estimate_text_tokens(name) + estimate_text_tokens(&args.to_string())
+            }
+            Part::FunctionResponse { function_response, .. } => {
+                estimate_text_tokens(&function_response.name)
+                    + estimate_text_tokens(&function_response.response.to_string())
+            }
+            Part::ServerToolCall { server_tool_call } => {
+                estimate_text_tokens(&server_tool_call.to_string())
+            }
+            Part::ServerToolResponse { server_tool_response } => {
+                estimate_text_tokens(&server_tool_response.to_string())
+            }
+            Part::InlineData { .. } | Part::FileData { .. } => 0,
+        })
+        .sum();
+    let tools: usize = req
+        .tools
+        .iter()
+        .map(|(name, declaration)| {
+            estimate_text_tokens(name) + estimate_text_tokens(&declaration.to_string())
+        })
+        .sum();
+    contents + tools
+}
+
+/// Estimates how many tokens a BPE tokenizer splits `text` into.
+///
+/// Mirrors how GPT-style tokenizers pre-split text: an ASCII word becomes one
+/// token per 8 letters, a number one per 3 digits, a run of punctuation one
+/// per 3 characters, a line break with its indentation one token, and any
+/// other character, such as CJK, one token each. Against `cl100k_base` this
+/// is within about 20% for English prose, source code, and JSON, where a flat
+/// characters-per-token ratio can be off by half.
+///
+/// # Example
+///
+/// ```
+/// use adk_core::estimate_text_tokens;
+///
+/// assert_eq!(estimate_text_tokens("Hello, world!"), 4);
+/// ```
+pub fn estimate_text_tokens(text: &str) -> usize {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Run {
+        Word,
+        Digits,
+        Space,
+        Newline,
+        Punctuation,
+        Other,
+    }
+    let run_of = |c: char| match c {
+        c if c.is_ascii_alphabetic() => Run::Word,
+        c if c.is_ascii_digit() => Run::Digits,
+        '\n' => Run::Newline,
+        c if c.is_whitespace() => Run::Space,
+        c if c.is_ascii() => Run::Punctuation,
+        _ => Run::Other,
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0;
+    let mut start = 0;
+    while start < chars.len() {
+        let run = run_of(chars[start]);
+        let mut end = start + 1;
+        while end < chars.len() {
+            let next = run_of(chars[end]);
+            // Indentation belongs to the line break before it
+            if next != run && !(run == Run::Newline && next == Run::Space) {
+                break;
+            }
+            end += 1;
+        }
+        let len = end - start;
+        tokens += match run {
+            Run::Word => len.div_ceil(8),
+            Run::Digits => len.div_ceil(3),
+            // A single space is part of the word after it
+            Run::Space => usize::from(start == 0 || len > 1),
+            Run::Newline => 1,
+            Run::Punctuation => len.div_ceil(3),
+            Run::Other => len,
+        };
+        start = end;
+    }
+    tokens
+}
+
+/// Runs each request through [`Llm::generate_content`] without streaming,
+/// with at most `concurrency` calls in flight (at least one), and returns the
+/// final response of each in the order of `requests`.
+///
+/// This is the default [`Llm::generate_batch`]; batch-capable providers can
+/// fall back to it when their batch API is unavailable.
+pub async fn generate_concurrently<L: Llm + ?Sized>(
+    llm: &L,
+    requests: Vec<LlmRequest>,
+    concurrency: usize,
+) -> Vec<Result<LlmResponse>> {
+    let mut results: Vec<(usize, Result<LlmResponse>)> =
+        futures::stream::iter(requests.into_iter().enumerate())
+            .map(|(index, request)| async move {
+                let result = match llm.generate_content(request, false).await {
+                    Ok(stream) => final_response(stream).await,
+                    Err(error) => Err(error),
+                };
+                (index, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The last response of a non-streaming call.
+async fn final_response(mut stream: LlmResponseStream) -> Result<LlmResponse> {
+    let mut last = None;
+    while let Some(response) = stream.next().await {
+        last = Some(response?);
+    }
+    last.ok_or_else(|| AdkError::model("model returned no response"))
+}
+
+tokio::task_local! {
+    static INVOCATION_USER_CONTENT: Content;
+}
+
+/// Runs `future` with `content` recorded as the user message that started the
+/// current invocation.
+///
+/// Agents wrap their model calls in this so that models routing on the
+/// prompt can tell the user's message apart from prompts the agent adds
+/// mid-turn, such as continuation or schema-correction requests.
+pub async fn with_invocation_user_content<F: std::future::Future>(
+    content: Content,
+    future: F,
+) -> F::Output {
+    INVOCATION_USER_CONTENT.scope(content, future).await
+}
+
+/// The user message that started the current invocation, when called from a
+/// model call wrapped in [`with_invocation_user_content`].
+pub fn invocation_user_content() -> Option<Content> {
+    INVOCATION_USER_CONTENT.try_with(Content::clone).ok()
 }
 
 /// A request to an LLM provider.
@@ -96,6 +364,10 @@ pub struct GenerateContentConfig {
     /// JSON Schema for structured output.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_schema: Option<serde_json::Value>,
+    /// MIME type of the response, e.g. `application/json` to request JSON
+    /// without a schema. Providers without a JSON mode ignore it.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub response_mime_type: Option<String>,
 
     /// Optional cached content name for Gemini provider.
     /// When set, the Gemini provider attaches this to the generation request.
@@ -119,6 +391,14 @@ pub struct LlmResponse {
     /// Citation sources referenced in the response.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub citation_metadata: Option<CitationMetadata>,
+    /// Sources backing the response, for rendering footnotes.
+    ///
+    /// Filled by providers from grounding metadata. On the final event of an
+    /// agent's turn it also includes sources reported by tools during the turn
+    /// (see [`EventActions::citations`](crate::EventActions::citations)).
+    /// Empty when the response is not grounded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
     /// Whether this is a partial streaming chunk.
     pub partial: bool,
     /// Whether the model has finished its turn.
@@ -286,6 +566,54 @@ pub struct CitationSource {
     pub publication_date: Option<String>,
 }
 
+/// A source that supports part of a response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Citation {
+    /// The part of the response text this source supports, or `None` when it
+    /// supports the response as a whole.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_span: Option<TextSpan>,
+    /// Where the source can be found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_uri: Option<String>,
+    /// Human-readable title of the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Citation {
+    /// Creates a citation of `source_uri` for the whole response.
+    pub fn new(source_uri: impl Into<String>) -> Self {
+        Self { source_uri: Some(source_uri.into()), ..Self::default() }
+    }
+
+    /// Sets the source title.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the part of the response text the source supports.
+    #[must_use]
+    pub fn with_text_span(mut self, span: TextSpan) -> Self {
+        self.text_span = Some(span);
+        self
+    }
+}
+
+/// A range of response text, as byte offsets into the response's text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TextSpan {
+    /// Start offset (inclusive).
+    pub start: usize,
+    /// End offset (exclusive).
+    pub end: usize,
+    /// The covered text, when the provider reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
 /// Reason the model stopped generating content.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FinishReason {
@@ -327,6 +655,66 @@ impl LlmRequest {
     }
 }
 
+impl GenerateContentConfig {
+    /// Request a JSON response without a schema by setting
+    /// [`response_mime_type`](Self::response_mime_type) to `application/json`.
+    ///
+    /// ```rust
+    /// use adk_core::GenerateContentConfig;
+    ///
+    /// let config = GenerateContentConfig::default().json_output();
+    /// assert_eq!(config.response_mime_type.as_deref(), Some("application/json"));
+    /// ```
+    #[must_use]
+    pub fn json_output(mut self) -> Self {
+        self.response_mime_type = Some("application/json".to_string());
+        self
+    }
+
+    /// Layer `overrides` on top of this config.
+    ///
+    /// Every setting present in `overrides` replaces this config's value; the
+    /// rest are kept. Stop sequences are replaced when `overrides` has any,
+    /// and extensions are merged by provider key.
+    ///
+    /// ```rust
+    /// use adk_core::GenerateContentConfig;
+    ///
+    /// let agent = GenerateContentConfig { temperature: Some(0.2), top_p: Some(0.9), ..Default::default() };
+    /// let creative = GenerateContentConfig { temperature: Some(1.1), ..Default::default() };
+    ///
+    /// let merged = agent.with_overrides(&creative);
+    /// assert_eq!(merged.temperature, Some(1.1));
+    /// assert_eq!(merged.top_p, Some(0.9));
+    /// ```
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: &GenerateContentConfig) -> Self {
+        fn replace<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+            if other.is_some() {
+                value.clone_from(other);
+            }
+        }
+        replace(&mut self.temperature, &overrides.temperature);
+        replace(&mut self.top_p, &overrides.top_p);
+        replace(&mut self.top_k, &overrides.top_k);
+        replace(&mut self.frequency_penalty, &overrides.frequency_penalty);
+        replace(&mut self.presence_penalty, &overrides.presence_penalty);
+        replace(&mut self.max_output_tokens, &overrides.max_output_tokens);
+        replace(&mut self.seed, &overrides.seed);
+        replace(&mut self.top_logprobs, &overrides.top_logprobs);
+        replace(&mut self.response_schema, &overrides.response_schema);
+        replace(&mut self.response_mime_type, &overrides.response_mime_type);
+        replace(&mut self.cached_content, &overrides.cached_content);
+        if !overrides.stop_sequences.is_empty() {
+            self.stop_sequences.clone_from(&overrides.stop_sequences);
+        }
+        for (key, value) in &overrides.extensions {
+            self.extensions.insert(key.clone(), value.clone());
+        }
+        self
+    }
+}
+
 impl LlmResponse {
     /// Creates a complete (non-streaming) response with the given content.
     pub fn new(content: Content) -> Self {
@@ -335,6 +723,7 @@ impl LlmResponse {
             usage_metadata: None,
             finish_reason: Some(FinishReason::Stop),
             citation_metadata: None,
+            citations: Vec::new(),
             partial: false,
             turn_complete: true,
             interrupted: false,
@@ -344,6 +733,44 @@ impl LlmResponse {
             interaction_id: None,
         }
     }
+
+    /// Returns the model's reasoning, kept apart from the answer.
+    ///
+    /// Concatenates all [`Part::Thinking`](crate::Part::Thinking) parts, or `None`
+    /// if the response carries no reasoning.
+    pub fn thinking_text(&self) -> Option<String> {
+        let parts = &self.content.as_ref()?.parts;
+        let thinking: String = parts.iter().filter_map(|p| p.thinking_text()).collect();
+        (!thinking.is_empty()).then_some(thinking)
+    }
+
+    /// Returns the answer text, excluding any reasoning.
+    ///
+    /// Concatenates all [`Part::Text`](crate::Part::Text) parts, or `None` if the
+    /// response carries no text.
+    pub fn answer_text(&self) -> Option<String> {
+        let parts = &self.content.as_ref()?.parts;
+        let text: String = parts.iter().filter_map(|p| p.text()).collect();
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Returns the concatenated answer text, or an empty string if the
+    /// response carries none.
+    ///
+    /// Audio, images, and other media are skipped; use [`parts`](Self::parts)
+    /// to get them in order with the text.
+    pub fn text(&self) -> String {
+        self.parts().iter().filter_map(|p| p.text()).collect()
+    }
+
+    /// Returns the output parts in the order the model produced them.
+    ///
+    /// Multimodal output such as text interleaved with audio keeps its
+    /// sequence, so each [`Part::InlineData`] can be saved as an artifact
+    /// alongside the text around it.
+    pub fn parts(&self) -> &[Part] {
+        self.content.as_ref().map_or(&[], |content| &content.parts)
+    }
 }
 
 #[cfg(test)]
@@ -412,6 +839,21 @@ mod tests {
         assert!(resp.provider_metadata.is_none());
     }
 
+    #[test]
+    fn test_llm_response_separates_thinking_from_answer() {
+        let resp = LlmResponse::new(
+            Content::new("model")
+                .with_thinking("step 1. ")
+                .with_thinking("step 2.")
+                .with_text("42"),
+        );
+        assert_eq!(resp.thinking_text().as_deref(), Some("step 1. step 2."));
+        assert_eq!(resp.answer_text().as_deref(), Some("42"));
+
+        let plain = LlmResponse::new(Content::new("model").with_text("hi"));
+        assert_eq!(plain.thinking_text(), None);
+    }
+
     #[test]
     fn test_llm_response_deserialize_without_citations() {
         let json = serde_json::json!({
@@ -451,6 +893,7 @@ mod tests {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         };
 
         let encoded = serde_json::to_string(&response).expect("serialize");
@@ -489,6 +932,7 @@ mod tests {
                 "properties": { "answer": { "type": "string" } },
                 "required": ["answer"]
             })),
+            response_mime_type: Some("application/json".to_string()),
             cached_content: Some("cachedContents/abc123".to_string()),
             extensions,
         };
@@ -506,10 +950,27 @@ mod tests {
         assert_eq!(decoded.top_logprobs, config.top_logprobs);
         assert_eq!(decoded.stop_sequences, config.stop_sequences);
         assert_eq!(decoded.response_schema, config.response_schema);
+        assert_eq!(decoded.response_mime_type, config.response_mime_type);
         assert_eq!(decoded.cached_content, config.cached_content);
         assert_eq!(decoded.extensions, config.extensions);
     }
 
+    #[test]
+    fn test_response_mime_type_serialization() {
+        let json = serde_json::to_value(GenerateContentConfig::default().json_output()).unwrap();
+        assert_eq!(json["response_mime_type"], "application/json");
+
+        let json = serde_json::to_value(GenerateContentConfig::default()).unwrap();
+        assert!(json.get("response_mime_type").is_none());
+
+        // Configs serialized before the field existed still deserialize.
+        let decoded: GenerateContentConfig = serde_json::from_str(
+            r#"{"temperature":0.2,"top_p":null,"top_k":null,"max_output_tokens":null}"#,
+        )
+        .unwrap();
+        assert_eq!(decoded.response_mime_type, None);
+    }
+
     #[test]
     fn test_llm_response_and_usage_roundtrip_with_provider_metadata() {
         let response = LlmResponse {
@@ -548,6 +1009,7 @@ mod tests {
                 }
             })),
             interaction_id: None,
+            citations: Vec::new(),
         };
 
         let encoded = serde_json::to_string(&response).expect("serialize");
@@ -573,4 +1035,126 @@ mod tests {
         assert_eq!(FinishReason::Stop, FinishReason::Stop);
         assert_ne!(FinishReason::Stop, FinishReason::MaxTokens);
     }
+
+    /// Echoes the request text after a delay that shrinks with the index, so
+    /// later requests finish first.
+    struct DelayedEchoLlm {
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Llm for DelayedEchoLlm {
+        fn name(&self) -> &str {
+            "delayed-echo"
+        }
+
+        async fn generate_content(
+            &self,
+            req: LlmRequest,
+            _stream: bool,
+        ) -> Result<LlmResponseStream> {
+            use std::sync::atomic::Ordering;
+
+            let text = req.contents[0].parts[0].text().unwrap_or_default().to_string();
+            let index: u64 = text.parse().unwrap();
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50 - index * 10)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let response = LlmResponse::new(Content::new("model").with_text(text));
+            Ok(Box::pin(futures::stream::iter([Ok(response)])))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_generate_batch_preserves_request_order() {
+        let llm =
+            DelayedEchoLlm { in_flight: Default::default(), max_in_flight: Default::default() };
+        let requests = (0..5)
+            .map(|i| {
+                LlmRequest::new("delayed-echo", vec![Content::new("user").with_text(i.to_string())])
+            })
+            .collect();
+
+        let results = llm.generate_batch(requests, 2).await;
+
+        let texts: Vec<String> =
+            results.into_iter().map(|r| r.unwrap().answer_text().unwrap()).collect();
+        assert_eq!(texts, ["0", "1", "2", "3", "4"]);
+        assert_eq!(llm.max_in_flight.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    /// Counts one token per character of text and tool declaration, plus two
+    /// per message for framing.
+    struct CharCountLlm;
+
+    #[async_trait]
+    impl Llm for CharCountLlm {
+        fn name(&self) -> &str {
+            "char-count"
+        }
+
+        async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+            let contents: usize = req
+                .contents
+                .iter()
+                .map(|content| {
+                    2 + content.parts.iter().filter_map(Part::text).map(str::len).sum::<usize>()
+                })
+                .sum();
+            let tools: usize = req.tools.values().map(|tool| tool.to_string().len()).sum();
+            Ok(contents + tools)
+        }
+
+        async fn generate_content(
+            &self,
+            _req: LlmRequest,
+            _stream: bool,
+        ) -> Result<LlmResponseStream> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_breakdown_attributes_each_part() {
+        let mut req = LlmRequest::new(
+            "char-count",
+            vec![
+                Content::new("system").with_text("Be brief."),
+                Content::new("user").with_text("Hello"),
+            ],
+        );
+        req.tools.insert("lookup".to_string(), serde_json::json!({ "name": "lookup" }));
+
+        let breakdown = CharCountLlm.count_tokens_breakdown(&req).await.unwrap();
+
+        assert_eq!(
+            breakdown,
+            TokenBreakdown { total: 35, system_instruction: 11, tools: 17, contents: 7 }
+        );
+        assert_eq!(
+            CharCountLlm
+                .count_tokens_breakdown(&LlmRequest::new(
+                    "char-count",
+                    vec![Content::new("user").with_text("Hello")]
+                ))
+                .await
+                .unwrap(),
+            TokenBreakdown { total: 7, system_instruction: 0, tools: 0, contents: 7 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invocation_user_content_is_scoped() {
+        assert!(invocation_user_content().is_none());
+
+        let content = Content::new("user").with_text("Summarize this report");
+        let seen = with_invocation_user_content(content, async { invocation_user_content() }).await;
+
+        let text = seen.and_then(|content| content.parts[0].text().map(str::to_string));
+        assert_eq!(text.as_deref(), Some("Summarize this report"));
+        assert!(invocation_user_content().is_none());
+    }
 }
@@ -29,6 +29,7 @@ use std::sync::Mutex;
 use serde_json::Value;
 
 use crate::SchemaAdapter;
+use crate::fingerprint::canonical_json;
 
 /// A thread-safe cache for normalized JSON Schemas.
 ///
@@ -137,10 +138,10 @@ impl SchemaCache {
 
     /// Computes a 64-bit hash of the serialized schema bytes.
     ///
-    /// Uses `serde_json::to_vec` for deterministic serialization and
+    /// Uses [`canonical_json`] so key order does not matter, and
     /// `DefaultHasher` (SipHash) for the hash function.
     fn hash_schema(schema: &Value) -> u64 {
-        let bytes = serde_json::to_vec(schema).unwrap_or_default();
+        let bytes = canonical_json(schema).into_bytes();
         let mut hasher = DefaultHasher::new();
         bytes.hash(&mut hasher);
         hasher.finish()
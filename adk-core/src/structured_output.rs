@@ -0,0 +1,347 @@
+//! Progressive assembly of structured output from a streamed response.
+//!
+//! A model asked for JSON matching an output schema streams that JSON in
+//! fragments, and no prefix of it parses until the closing brace arrives.
+//! [`StructuredOutputAssembler`] tracks the top-level object as text comes in
+//! and reports a partial object each time another field is complete, so a UI
+//! can render fields as they are produced. Each completed field is checked
+//! against its property schema before it is reported, and
+//! [`finish`](StructuredOutputAssembler::finish) validates the whole object,
+//! including `required` fields.
+//!
+//! Validation covers `type`, `enum`, `required`, `properties`, and `items`.
+//! Other keywords are not checked.
+//!
+//! # Example
+//!
+//! ```
+//! use adk_core::StructuredOutputAssembler;
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": { "name": { "type": "string" }, "age": { "type": "integer" } },
+//!     "required": ["name", "age"]
+//! });
+//! let mut assembler = StructuredOutputAssembler::new(schema);
+//!
+//! assert_eq!(assembler.push(r#"{"name": "Ad"#).unwrap(), None);
+//! assert_eq!(assembler.push(r#"a", "age": 3"#).unwrap(), Some(json!({ "name": "Ada" })));
+//! assert_eq!(assembler.push("6}").unwrap(), Some(json!({ "name": "Ada", "age": 36 })));
+//! assert_eq!(assembler.finish().unwrap(), json!({ "name": "Ada", "age": 36 }));
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::{AdkError, LlmResponse, Part, Result};
+
+/// Builds a schema-validated object from streamed JSON text.
+///
+/// See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct StructuredOutputAssembler {
+    schema: Value,
+    buffer: String,
+    /// Byte offset in `buffer` just past the last completed field.
+    cursor: usize,
+    fields: Map<String, Value>,
+}
+
+impl StructuredOutputAssembler {
+    /// Creates an assembler for output matching `schema`, a JSON Schema for an object.
+    pub fn new(schema: Value) -> Self {
+        Self { schema, buffer: String::new(), cursor: 0, fields: Map::new() }
+    }
+
+    /// Appends streamed text.
+    ///
+    /// Returns the object assembled so far when `text` completed at least one
+    /// more top-level field, otherwise `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a completed field does not match its schema or
+    /// the text is not a JSON object.
+    pub fn push(&mut self, text: &str) -> Result<Option<Value>> {
+        self.buffer.push_str(text);
+        let before = self.fields.len();
+        while let Some((key, value, end)) = self.next_field()? {
+            if let Some(property) = self.schema.get("properties").and_then(|p| p.get(&key)) {
+                check(&value, property, &key)
+                    .map_err(|e| AdkError::model(format!("structured output: {e}")))?;
+            }
+            self.fields.insert(key, value);
+            self.cursor = end;
+        }
+        Ok((self.fields.len() > before).then(|| self.partial()))
+    }
+
+    /// Appends the text parts of a streamed response chunk. See [`push`](Self::push).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`push`](Self::push).
+    pub fn push_response(&mut self, chunk: &LlmResponse) -> Result<Option<Value>> {
+        let text: String = chunk
+            .content
+            .iter()
+            .flat_map(|content| &content.parts)
+            .filter_map(|part| match part {
+                Part::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        self.push(&text)
+    }
+
+    /// The fields completed so far, as an object.
+    pub fn partial(&self) -> Value {
+        Value::Object(self.fields.clone())
+    }
+
+    /// Parses the complete text and validates it against the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the text is not a complete JSON object or does
+    /// not match the schema.
+    pub fn finish(self) -> Result<Value> {
+        let start = self.buffer.find('{').unwrap_or(0);
+        let end = self.buffer.rfind('}').map_or(self.buffer.len(), |end| end + 1);
+        let value: Value = serde_json::from_str(&self.buffer[start..end.max(start)])
+            .map_err(|e| AdkError::model(format!("structured output is not valid JSON: {e}")))?;
+        check(&value, &self.schema, "output")
+            .map_err(|e| AdkError::model(format!("structured output: {e}")))?;
+        Ok(value)
+    }
+
+    /// Scans the next completed `"key": value` pair after the cursor,
+    /// returning it with the offset just past the value.
+    fn next_field(&self) -> Result<Option<(String, Value, usize)>> {
+        let bytes = self.buffer.as_bytes();
+        let mut pos = self.cursor;
+        if pos == 0 {
+            // Skip anything the model wrote before the object, such as a code fence
+            let Some(open) = self.buffer.find('{') else {
+                return Ok(None);
+            };
+            pos = open + 1;
+        }
+
+        pos = skip_whitespace(bytes, pos);
+        if bytes.get(pos) == Some(&b',') {
+            pos = skip_whitespace(bytes, pos + 1);
+        }
+        match bytes.get(pos) {
+            None | Some(b'}') => return Ok(None),
+            Some(b'"') => {}
+            Some(_) => {
+                return Err(AdkError::model(format!(
+                    "structured output is not a JSON object: unexpected text at byte {pos}"
+                )));
+            }
+        }
+
+        let Some(key_end) = value_end(bytes, pos) else {
+            return Ok(None);
+        };
+        let key: String = serde_json::from_str(&self.buffer[pos..key_end])
+            .map_err(|e| AdkError::model(format!("structured output has an invalid key: {e}")))?;
+
+        pos = skip_whitespace(bytes, key_end);
+        match bytes.get(pos) {
+            None => return Ok(None),
+            Some(b':') => {}
+            Some(_) => {
+                return Err(AdkError::model(format!(
+                    "structured output is not a JSON object: expected ':' after \"{key}\""
+                )));
+            }
+        }
+        let value_start = skip_whitespace(bytes, pos + 1);
+        let Some(end) = value_end(bytes, value_start) else {
+            return Ok(None);
+        };
+        let value = serde_json::from_str(&self.buffer[value_start..end]).map_err(|e| {
+            AdkError::model(format!("structured output has an invalid value for \"{key}\": {e}"))
+        })?;
+        Ok(Some((key, value, end)))
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Finds the end of the JSON value starting at `start`, or `None` if the
+/// buffer does not yet hold all of it. Numbers and literals only count as
+/// complete once the character after them has arrived.
+fn value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match *bytes.get(start)? {
+        b'"' => {
+            let mut pos = start + 1;
+            while pos < bytes.len() {
+                match bytes[pos] {
+                    b'\\' => pos += 2,
+                    b'"' => return Some(pos + 1),
+                    _ => pos += 1,
+                }
+            }
+            None
+        }
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut in_string = false;
+            let mut pos = start;
+            while pos < bytes.len() {
+                match bytes[pos] {
+                    b'\\' if in_string => pos += 1,
+                    b'"' => in_string = !in_string,
+                    b'{' | b'[' if !in_string => depth += 1,
+                    b'}' | b']' if !in_string => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(pos + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+            None
+        }
+        _ => (start..bytes.len()).find(|&pos| {
+            matches!(bytes[pos], b',' | b'}' | b']') || bytes[pos].is_ascii_whitespace()
+        }),
+    }
+}
+
+/// Checks `value` against the supported subset of `schema`.
+fn check(value: &Value, schema: &Value, path: &str) -> std::result::Result<(), String> {
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            return Err(format!("`{path}` should be {}, got {value}", types.join(" or ")));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        return Err(format!(
+            "`{path}` should be one of {}, got {value}",
+            Value::from(allowed.clone())
+        ));
+    }
+
+    match value {
+        Value::Object(object) => {
+            for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                if let Some(name) = name.as_str()
+                    && !object.contains_key(name)
+                {
+                    return Err(format!("`{path}` is missing required field `{name}`"));
+                }
+            }
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, field) in object {
+                    if let Some(property) = properties.get(name) {
+                        check(field, property, &format!("{path}.{name}"))?;
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(item, item_schema, &format!("{path}[{index}]"))?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "rating": { "type": "number" },
+                "status": { "enum": ["draft", "final"] }
+            },
+            "required": ["name", "rating", "status"]
+        })
+    }
+
+    #[test]
+    fn test_fragmented_stream_yields_growing_partials_then_final_object() {
+        let mut assembler = StructuredOutputAssembler::new(schema());
+        let fragments = [
+            "```json\n{\"na",
+            "me\": \"Cafe \\\"Lu",
+            "na\\\"\", \"tags\": [\"cozy\", ",
+            "\"wifi\"], \"rat",
+            "ing\": 4.",
+            "5, \"status\": \"fi",
+            "nal\"}\n```",
+        ];
+
+        let partials: Vec<Value> =
+            fragments.iter().filter_map(|fragment| assembler.push(fragment).unwrap()).collect();
+
+        assert_eq!(
+            partials,
+            [
+                json!({ "name": "Cafe \"Luna\"" }),
+                json!({ "name": "Cafe \"Luna\"", "tags": ["cozy", "wifi"] }),
+                json!({ "name": "Cafe \"Luna\"", "tags": ["cozy", "wifi"], "rating": 4.5 }),
+                json!({
+                    "name": "Cafe \"Luna\"",
+                    "tags": ["cozy", "wifi"],
+                    "rating": 4.5,
+                    "status": "final"
+                }),
+            ]
+        );
+        assert_eq!(assembler.finish().unwrap(), partials[3]);
+    }
+
+    #[test]
+    fn test_invalid_field_and_missing_required_field_are_rejected() {
+        let mut assembler = StructuredOutputAssembler::new(schema());
+        let error = assembler.push(r#"{"name": "Luna", "rating": "great", "#).unwrap_err();
+        assert!(error.to_string().contains("`rating` should be number"));
+
+        let mut assembler = StructuredOutputAssembler::new(schema());
+        assembler.push(r#"{"name": "Luna", "rating": 4}"#).unwrap();
+        let error = assembler.finish().unwrap_err();
+        assert!(error.to_string().contains("missing required field `status`"));
+    }
+}
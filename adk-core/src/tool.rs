@@ -1,6 +1,7 @@
-use crate::{CallbackContext, EventActions, MemoryEntry, Result};
+use crate::context::{state_value_as, state_value_from};
+use crate::{CallbackContext, Event, EventActions, MemoryEntry, Result, StreamingMode};
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use std::sync::Arc;
 
@@ -113,6 +114,15 @@ pub trait Tool: Send + Sync {
         false
     }
 
+    /// Maximum serialized size of this tool's result in bytes.
+    ///
+    /// Results larger than this are truncated before being sent to the model.
+    /// `None` (the default) defers to the run's
+    /// [`ToolOutputLimitConfig`](crate::ToolOutputLimitConfig).
+    fn max_output_bytes(&self) -> Option<usize> {
+        None
+    }
+
     /// Executes the tool with the given context and arguments.
     async fn execute(&self, ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value>;
 }
@@ -158,6 +168,26 @@ pub trait ToolContext: CallbackContext {
         // Default: discard. Override in runners that support streaming tool output.
     }
 
+    /// The streaming mode a tool that runs a sub-agent should use so its events
+    /// can be passed to [`forward_sub_agent_event`](Self::forward_sub_agent_event),
+    /// or `None` when the caller does not take them.
+    ///
+    /// The default returns `None`. `LlmAgent` returns its own run's mode unless
+    /// built with `stream_sub_agent_output(false)`.
+    fn sub_agent_streaming_mode(&self) -> Option<StreamingMode> {
+        None
+    }
+
+    /// Forward an event of a sub-agent this tool is running, including partial
+    /// chunks, onto the calling agent's event stream as it happens.
+    ///
+    /// The event keeps the sub-agent's `author` and is tagged with this tool
+    /// call's id (see [`Event::sub_agent_call_id`]). Forwarded events are not
+    /// written to the session. The default implementation is a no-op.
+    async fn forward_sub_agent_event(&self, _event: Event) {
+        // Default: discard. Override in agents that stream sub-agent output.
+    }
+
     /// Returns the scopes granted to the current user for this invocation.
     ///
     /// Implementations may resolve scopes from session state, JWT claims,
@@ -187,6 +217,69 @@ pub trait ToolContext: CallbackContext {
     async fn get_secret(&self, _name: &str) -> Result<Option<String>> {
         Ok(None)
     }
+
+    /// Returns `true` once the invocation running this tool has been
+    /// cancelled, including when the turn's deadline has passed.
+    ///
+    /// Long-running tools can poll this to stop early. The default returns
+    /// `false`.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
+    /// Returns the instant the current turn must finish by, from
+    /// [`RunConfig::deadline_at`](crate::RunConfig::deadline_at).
+    ///
+    /// Tools that call other services can use it to bound their own requests.
+    /// The default returns `None`.
+    fn deadline(&self) -> Option<std::time::Instant> {
+        None
+    }
+
+    /// Returns the session state value for `key` as this tool sees it.
+    ///
+    /// Values this tool has already written to
+    /// [`state_delta`](EventActions::state_delta) take precedence over the
+    /// session's stored state. The default only consults the pending
+    /// `state_delta`; contexts backed by a session override this to fall back
+    /// to it.
+    fn get_state(&self, key: &str) -> Option<Value> {
+        self.actions().state_delta.get(key).cloned()
+    }
+}
+
+impl dyn ToolContext + '_ {
+    /// Returns the state value for `key` deserialized as `T`, or `None` if not
+    /// present. See [`get_state`](ToolContext::get_state) for lookup order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the stored JSON does not match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let config: Option<IndexConfig> = ctx.get_state_as("app:index_config")?;
+    /// ```
+    pub fn get_state_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get_state(key).map(|value| state_value_as(key, value)).transpose()
+    }
+
+    /// Serializes `value` and records it in this tool's
+    /// [`state_delta`](EventActions::state_delta), to be applied to the session
+    /// with the tool's response event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` fails [`validate_state_key`](crate::validate_state_key)
+    /// or `value` cannot be serialized.
+    pub fn set_state_as<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<()> {
+        let value = state_value_from(key, value)?;
+        let mut actions = self.actions();
+        actions.state_delta.insert(key.to_string(), value);
+        self.set_actions(actions);
+        Ok(())
+    }
 }
 
 /// Configuration for automatic tool retry on failure.
@@ -0,0 +1,230 @@
+//! Structured tool failures.
+//!
+//! [`Tool::execute`](crate::Tool::execute) returns an [`AdkError`], and a bare
+//! [`AdkError::tool`] message does not say whether the model sent bad
+//! arguments, an upstream service is down, or the tool has a bug. A tool that
+//! returns a [`ToolError`] instead (converted with `?` or `.into()`) lets the
+//! agent decide what to do:
+//!
+//! | Variant | Category | Retried | Meaning |
+//! |---------|----------|---------|---------|
+//! | [`InvalidArguments`](ToolError::InvalidArguments) | `InvalidInput` | no | The model should fix its call |
+//! | [`Upstream`](ToolError::Upstream) | `Unavailable` | yes | A dependency failed; try again |
+//! | [`NotFound`](ToolError::NotFound) | `NotFound` | no | The requested resource does not exist |
+//! | [`Internal`](ToolError::Internal) | `Internal` | no | A bug in the tool |
+//!
+//! Failures that are not retried skip the tool's remaining
+//! [`RetryBudget`](crate::RetryBudget) and go straight back to the model as a
+//! function response built by [`ToolError::to_model_response`]. Untyped errors
+//! keep the previous behavior and are retried up to the budget.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::{AdkError, ErrorCategory, ToolError};
+//!
+//! fn parse_city(args: &serde_json::Value) -> Result<String, AdkError> {
+//!     let city = args["city"]
+//!         .as_str()
+//!         .ok_or_else(|| ToolError::InvalidArguments("'city' must be a string".into()))?;
+//!     Ok(city.to_string())
+//! }
+//!
+//! let err = parse_city(&serde_json::json!({ "city": 42 })).unwrap_err();
+//! assert_eq!(err.category, ErrorCategory::InvalidInput);
+//! assert_eq!(
+//!     err.tool_error(),
+//!     Some(ToolError::InvalidArguments("'city' must be a string".into()))
+//! );
+//! ```
+
+use crate::error::{AdkError, ErrorCategory, ErrorComponent};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Error code of [`ToolError::InvalidArguments`].
+pub const TOOL_INVALID_ARGUMENTS: &str = "tool.invalid_arguments";
+/// Error code of [`ToolError::Upstream`].
+pub const TOOL_UPSTREAM: &str = "tool.upstream";
+/// Error code of [`ToolError::NotFound`].
+pub const TOOL_NOT_FOUND: &str = "tool.not_found";
+/// Error code of [`ToolError::Internal`].
+pub const TOOL_INTERNAL: &str = "tool.internal";
+
+/// Why a tool call failed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ToolError {
+    /// The arguments were missing, malformed, or out of range.
+    #[error("invalid arguments: {0}")]
+    InvalidArguments(String),
+    /// A service the tool depends on failed or timed out.
+    #[error("upstream failure: {0}")]
+    Upstream(String),
+    /// The resource the call refers to does not exist.
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// The tool itself failed.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl ToolError {
+    /// The human-readable message, without the variant prefix.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::InvalidArguments(message)
+            | Self::Upstream(message)
+            | Self::NotFound(message)
+            | Self::Internal(message) => message,
+        }
+    }
+
+    /// The short name reported to the model as `error_type`.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Self::InvalidArguments(_) => "invalid_arguments",
+            Self::Upstream(_) => "upstream",
+            Self::NotFound(_) => "not_found",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    /// The [`AdkError::code`] this variant converts to.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidArguments(_) => TOOL_INVALID_ARGUMENTS,
+            Self::Upstream(_) => TOOL_UPSTREAM,
+            Self::NotFound(_) => TOOL_NOT_FOUND,
+            Self::Internal(_) => TOOL_INTERNAL,
+        }
+    }
+
+    /// The [`ErrorCategory`] this variant converts to.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::InvalidArguments(_) => ErrorCategory::InvalidInput,
+            Self::Upstream(_) => ErrorCategory::Unavailable,
+            Self::NotFound(_) => ErrorCategory::NotFound,
+            Self::Internal(_) => ErrorCategory::Internal,
+        }
+    }
+
+    /// Returns `true` if retrying the same call may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Upstream(_))
+    }
+
+    /// The function response sent to the model for this failure.
+    ///
+    /// ```rust
+    /// use adk_core::ToolError;
+    ///
+    /// let response = ToolError::NotFound("no order 42".into()).to_model_response();
+    /// assert_eq!(response["error"], "no order 42");
+    /// assert_eq!(response["error_type"], "not_found");
+    /// ```
+    pub fn to_model_response(&self) -> Value {
+        json!({
+            "error": self.message(),
+            "error_type": self.error_type(),
+            "retryable": self.is_retryable(),
+        })
+    }
+}
+
+impl From<ToolError> for AdkError {
+    fn from(err: ToolError) -> Self {
+        AdkError::new(ErrorComponent::Tool, err.category(), err.code(), err.message())
+    }
+}
+
+impl AdkError {
+    /// Recovers the [`ToolError`] this error was converted from, if any.
+    pub fn tool_error(&self) -> Option<ToolError> {
+        if self.component != ErrorComponent::Tool {
+            return None;
+        }
+        let message = self.message.clone();
+        match self.code {
+            TOOL_INVALID_ARGUMENTS => Some(ToolError::InvalidArguments(message)),
+            TOOL_UPSTREAM => Some(ToolError::Upstream(message)),
+            TOOL_NOT_FOUND => Some(ToolError::NotFound(message)),
+            TOOL_INTERNAL => Some(ToolError::Internal(message)),
+            _ => None,
+        }
+    }
+}
+
+/// What a [`ToolErrorPolicy::Custom`] handler decides for a failed tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolErrorDecision {
+    /// Send this value to the model as the tool's result.
+    Substitute(Value),
+    /// Fail the invocation with the tool's error.
+    Abort,
+}
+
+/// Decides what happens to a failed tool call, given the tool name, the
+/// call's arguments, and the error message.
+pub type ToolErrorHandler = dyn Fn(&str, &Value, &str) -> ToolErrorDecision + Send + Sync;
+
+/// What an agent does with a tool call that still fails after its retries.
+///
+/// `on_tool_error` callbacks run first; the policy applies when none of them
+/// substitutes a result.
+///
+/// ```rust
+/// use adk_core::{ToolErrorDecision, ToolErrorPolicy};
+/// use std::sync::Arc;
+///
+/// // Searches may fail softly; anything else stops the run.
+/// let policy = ToolErrorPolicy::Custom(Arc::new(|tool, _args, _error| {
+///     if tool == "search" {
+///         ToolErrorDecision::Substitute(serde_json::json!({ "results": [] }))
+///     } else {
+///         ToolErrorDecision::Abort
+///     }
+/// }));
+/// ```
+#[derive(Clone, Default)]
+pub enum ToolErrorPolicy {
+    /// Send the error to the model as the function response, so it can retry
+    /// or pick another tool. Default.
+    #[default]
+    ReturnToModel,
+    /// Fail the invocation with the tool's error.
+    Abort,
+    /// Let a handler substitute a result or abort.
+    Custom(Arc<ToolErrorHandler>),
+}
+
+impl std::fmt::Debug for ToolErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReturnToModel => f.write_str("ReturnToModel"),
+            Self::Abort => f.write_str("Abort"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_through_adk_error() {
+        for err in [
+            ToolError::InvalidArguments("a".into()),
+            ToolError::Upstream("b".into()),
+            ToolError::NotFound("c".into()),
+            ToolError::Internal("d".into()),
+        ] {
+            let adk: AdkError = err.clone().into();
+            assert!(adk.is_tool());
+            assert_eq!(adk.is_retryable(), err.is_retryable());
+            assert_eq!(adk.tool_error(), Some(err));
+        }
+        assert_eq!(AdkError::tool("legacy").tool_error(), None);
+    }
+}
@@ -0,0 +1,163 @@
+//! Size limits for tool results.
+//!
+//! A tool that returns megabytes of text can exhaust the model's context window
+//! and fail the whole turn. [`ToolOutputLimitConfig`] caps the serialized size of
+//! tool results; [`truncate_tool_output`] replaces an oversized result with a
+//! truncated preview and a clear marker so the model knows output is missing.
+//!
+//! The limit for a call is resolved in order: a per-tool override in the config,
+//! the tool's own [`Tool::max_output_bytes`](crate::Tool::max_output_bytes), then
+//! the config's default.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::tool_output::truncate_tool_output;
+//! use serde_json::json;
+//!
+//! let big = json!({ "text": "x".repeat(10_000) });
+//! let truncated = truncate_tool_output(&big, 256).unwrap();
+//!
+//! assert_eq!(truncated.value["truncated"], true);
+//! assert!(truncated.original_bytes > 10_000);
+//! ```
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::Tool;
+
+/// Marker appended to the preview of a truncated tool result.
+pub const TRUNCATION_MARKER: &str = "[... tool output truncated ...]";
+
+/// Configuration for tool result size limits.
+///
+/// The default imposes no limit, preserving previous behavior.
+///
+/// # Example
+///
+/// ```rust
+/// use adk_core::ToolOutputLimitConfig;
+/// use std::collections::HashMap;
+///
+/// let config = ToolOutputLimitConfig {
+///     max_output_bytes: Some(32 * 1024),
+///     per_tool: HashMap::from([("read_file".to_string(), 128 * 1024)]),
+///     spill_to_artifact: true,
+/// };
+///
+/// assert_eq!(config.max_output_bytes, Some(32 * 1024));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutputLimitConfig {
+    /// Default maximum serialized size of a tool result in bytes. `None` means unlimited.
+    pub max_output_bytes: Option<usize>,
+
+    /// Per-tool limits. Take precedence over both the tool's own limit and the default.
+    pub per_tool: HashMap<String, usize>,
+
+    /// Save the full output as an artifact when truncating, and include the
+    /// artifact name in the truncated result. Requires an artifact service.
+    pub spill_to_artifact: bool,
+}
+
+impl ToolOutputLimitConfig {
+    /// Resolve the effective limit for a tool.
+    pub fn limit_for(&self, tool: &dyn Tool) -> Option<usize> {
+        self.per_tool
+            .get(tool.name())
+            .copied()
+            .or(tool.max_output_bytes())
+            .or(self.max_output_bytes)
+    }
+}
+
+/// A tool result that was cut down to fit a size limit.
+#[derive(Debug, Clone)]
+pub struct TruncatedToolOutput {
+    /// Replacement result sent to the model.
+    pub value: Value,
+    /// Serialized size of the original result in bytes.
+    pub original_bytes: usize,
+    /// The full serialized original result, for spilling to an artifact.
+    pub full_output: String,
+}
+
+impl TruncatedToolOutput {
+    /// Record the artifact holding the full output in the replacement result.
+    pub fn with_artifact(mut self, name: &str, version: i64) -> Self {
+        if let Value::Object(map) = &mut self.value {
+            map.insert("artifact".to_string(), Value::String(name.to_string()));
+            map.insert("artifact_version".to_string(), Value::from(version));
+        }
+        self
+    }
+}
+
+/// Truncate a tool result whose serialized size exceeds `max_bytes`.
+///
+/// String results are measured as-is; other values are measured as compact
+/// JSON. Returns `None` when the result fits. The replacement is an object with
+/// `truncated`, `original_bytes`, and an `output` preview of at most `max_bytes`
+/// bytes (cut on a character boundary) followed by [`TRUNCATION_MARKER`].
+pub fn truncate_tool_output(value: &Value, max_bytes: usize) -> Option<TruncatedToolOutput> {
+    let full_output = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if full_output.len() <= max_bytes {
+        return None;
+    }
+
+    let mut cut = max_bytes;
+    while !full_output.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let original_bytes = full_output.len();
+    let preview = format!("{}\n{TRUNCATION_MARKER}", &full_output[..cut]);
+
+    Some(TruncatedToolOutput {
+        value: serde_json::json!({
+            "truncated": true,
+            "original_bytes": original_bytes,
+            "output": preview,
+        }),
+        original_bytes,
+        full_output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_small_output_untouched() {
+        assert!(truncate_tool_output(&json!({ "ok": true }), 1024).is_none());
+    }
+
+    #[test]
+    fn test_string_output_truncated_with_marker() {
+        let truncated = truncate_tool_output(&json!("a".repeat(100)), 10).unwrap();
+        assert_eq!(truncated.original_bytes, 100);
+        assert_eq!(truncated.value["output"], format!("{}\n{TRUNCATION_MARKER}", "a".repeat(10)));
+        assert_eq!(truncated.full_output.len(), 100);
+    }
+
+    #[test]
+    fn test_truncation_respects_char_boundaries() {
+        let truncated = truncate_tool_output(&json!("é".repeat(10)), 5).unwrap();
+        let output = truncated.value["output"].as_str().unwrap();
+        assert!(output.starts_with("éé\n"));
+    }
+
+    #[test]
+    fn test_with_artifact_reference() {
+        let truncated =
+            truncate_tool_output(&json!("x".repeat(50)), 8).unwrap().with_artifact("out.txt", 0);
+        assert_eq!(truncated.value["artifact"], "out.txt");
+        assert_eq!(truncated.value["artifact_version"], 0);
+    }
+}
@@ -1,4 +1,6 @@
+use crate::{AdkError, ErrorCategory, ErrorComponent, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Maximum allowed size for inline binary data (10 MB).
 /// Prevents accidental or malicious embedding of oversized payloads in Content parts.
@@ -325,6 +327,28 @@ impl Content {
         self
     }
 
+    /// Add an image from raw bytes, e.g. `image/png` or `image/jpeg`.
+    ///
+    /// # Panics
+    /// Panics if `data` exceeds [`MAX_INLINE_DATA_SIZE`] (10 MB).
+    pub fn with_image_bytes(self, data: Vec<u8>, mime_type: impl Into<String>) -> Self {
+        self.with_inline_data(mime_type, data)
+    }
+
+    /// Add an audio clip from raw bytes, e.g. `audio/wav` or `audio/mpeg`.
+    ///
+    /// # Panics
+    /// Panics if `data` exceeds [`MAX_INLINE_DATA_SIZE`] (10 MB).
+    pub fn with_audio_bytes(self, data: Vec<u8>, mime_type: impl Into<String>) -> Self {
+        self.with_inline_data(mime_type, data)
+    }
+
+    /// Append an already-built part, such as one from [`Part::from_path`].
+    pub fn with_part(mut self, part: Part) -> Self {
+        self.parts.push(part);
+        self
+    }
+
     /// Add a thinking/reasoning trace part.
     pub fn with_thinking(mut self, thinking: impl Into<String>) -> Self {
         self.parts.push(Part::Thinking { thinking: thinking.into(), signature: None });
@@ -342,6 +366,95 @@ impl Content {
     }
 }
 
+/// Merges runs of consecutive tool result contents into one content each.
+///
+/// When the model issues several tool calls in one turn, every result is
+/// usually recorded as a `function` content of its own. Providers expect the
+/// results of one turn together: Gemini in a single content, Anthropic in a
+/// single user message. Adjacent contents with the same `function` or `tool`
+/// role that hold only [`Part::FunctionResponse`] parts are combined, keeping
+/// their order; everything else is left as is.
+///
+/// # Example
+///
+/// ```rust
+/// use adk_core::{Content, FunctionResponseData, Part, batch_function_responses};
+///
+/// let result = |name: &str| Content {
+///     role: "function".to_string(),
+///     parts: vec![Part::FunctionResponse {
+///         function_response: FunctionResponseData::new(name, serde_json::json!("ok")),
+///         id: None,
+///     }],
+/// };
+/// let batched = batch_function_responses(vec![result("a"), result("b")]);
+/// assert_eq!(batched.len(), 1);
+/// assert_eq!(batched[0].parts.len(), 2);
+/// ```
+pub fn batch_function_responses(contents: Vec<Content>) -> Vec<Content> {
+    let mut batched: Vec<Content> = Vec::with_capacity(contents.len());
+    for content in contents {
+        if is_function_responses(&content)
+            && let Some(last) = batched.last_mut()
+            && last.role == content.role
+            && is_function_responses(last)
+        {
+            last.parts.extend(content.parts);
+        } else {
+            batched.push(content);
+        }
+    }
+    batched
+}
+
+/// Keeps the most recent `n` turns of `history`.
+///
+/// A turn starts at a user message carrying anything other than function
+/// responses, so a cut never lands between a function call and its response.
+/// Content before the first user message (such as a compaction summary) is
+/// dropped along with the oldest turns. At least one turn is always kept.
+///
+/// # Example
+///
+/// ```rust
+/// use adk_core::{Content, last_n_turns};
+///
+/// let history = vec![
+///     Content::new("user").with_text("first"),
+///     Content::new("model").with_text("one"),
+///     Content::new("user").with_text("second"),
+///     Content::new("model").with_text("two"),
+/// ];
+/// let recent = last_n_turns(history, 1);
+/// assert_eq!(recent.len(), 2);
+/// assert_eq!(recent[0].parts[0].text(), Some("second"));
+/// ```
+pub fn last_n_turns(mut history: Vec<Content>, n: usize) -> Vec<Content> {
+    let mut turns = 0;
+    let mut start = None;
+    for (index, content) in history.iter().enumerate().rev() {
+        if content.role == "user"
+            && content.parts.iter().any(|part| !matches!(part, Part::FunctionResponse { .. }))
+        {
+            turns += 1;
+            start = Some(index);
+            if turns >= n.max(1) {
+                break;
+            }
+        }
+    }
+    match start {
+        Some(start) => history.split_off(start),
+        None => history,
+    }
+}
+
+fn is_function_responses(content: &Content) -> bool {
+    matches!(content.role.as_str(), "function" | "tool")
+        && !content.parts.is_empty()
+        && content.parts.iter().all(|part| matches!(part, Part::FunctionResponse { .. }))
+}
+
 impl Part {
     /// Returns the text content if this is a Text part, None otherwise
     pub fn text(&self) -> Option<&str> {
@@ -409,6 +522,58 @@ impl Part {
     pub fn file_data(mime_type: impl Into<String>, file_uri: impl Into<String>) -> Self {
         Part::FileData { mime_type: mime_type.into(), file_uri: file_uri.into() }
     }
+
+    /// Read a local file into an inline data part, inferring the MIME type
+    /// from its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its extension does not
+    /// map to a known MIME type, or it exceeds [`MAX_INLINE_DATA_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use adk_core::{Content, Part};
+    ///
+    /// let content = Content::new("user")
+    ///     .with_text("Summarize this report")
+    ///     .with_part(Part::from_path("report.pdf")?);
+    /// # Ok::<(), adk_core::AdkError>(())
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mime_type = mime_guess::from_path(path).first_raw().ok_or_else(|| {
+            AdkError::new(
+                ErrorComponent::Model,
+                ErrorCategory::InvalidInput,
+                "content.unknown_mime_type",
+                format!("cannot infer a MIME type for '{}'", path.display()),
+            )
+        })?;
+        let data = std::fs::read(path).map_err(|e| {
+            AdkError::new(
+                ErrorComponent::Model,
+                ErrorCategory::InvalidInput,
+                "content.read_failed",
+                format!("failed to read '{}': {e}", path.display()),
+            )
+            .with_source(e)
+        })?;
+        if data.len() > MAX_INLINE_DATA_SIZE {
+            return Err(AdkError::new(
+                ErrorComponent::Model,
+                ErrorCategory::InvalidInput,
+                "content.too_large",
+                format!(
+                    "'{}' is {} bytes, above the inline limit of {MAX_INLINE_DATA_SIZE} bytes",
+                    path.display(),
+                    data.len()
+                ),
+            ));
+        }
+        Ok(Part::InlineData { mime_type: mime_type.to_string(), data })
+    }
 }
 
 #[cfg(test)]
@@ -444,6 +609,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_content_media_builders() {
+        let content = Content::new("user")
+            .with_image_bytes(vec![0x89, 0x50], "image/png")
+            .with_audio_bytes(vec![0x52, 0x49], "audio/wav")
+            .with_part(Part::file_data("application/pdf", "gs://bucket/report.pdf"));
+        let mime_types: Vec<_> = content.parts.iter().filter_map(Part::mime_type).collect();
+        assert_eq!(mime_types, ["image/png", "audio/wav", "application/pdf"]);
+    }
+
+    #[test]
+    fn test_part_from_path_infers_mime_type() {
+        let dir = std::env::temp_dir().join(format!("adk-part-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let png = dir.join("pixel.png");
+        std::fs::write(&png, [0x89, 0x50, 0x4E, 0x47]).unwrap();
+        let unknown = dir.join("blob.unknownext");
+        std::fs::write(&unknown, [0]).unwrap();
+
+        let part = Part::from_path(&png).unwrap();
+        assert!(matches!(
+            &part,
+            Part::InlineData { mime_type, data } if mime_type == "image/png" && data.len() == 4
+        ));
+        assert!(Part::from_path(&unknown).is_err());
+        assert!(Part::from_path(dir.join("missing.pdf")).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn test_part_serialization() {
         let part = Part::Text { text: "test".to_string() };
@@ -0,0 +1,144 @@
+//! `collect_final_response` merges streamed chunks without double counting,
+//! gathers tool traffic and usage, and propagates errors.
+
+use adk_core::{
+    AdkError, CollectedResponse, Content, ErrorComponent, Event, FunctionResponseData, Part,
+    Result, UsageMetadata, collect_final_response,
+};
+use futures::stream;
+use serde_json::json;
+
+fn text_event(author: &str, text: &str, partial: bool) -> Event {
+    let mut event = Event::new("inv-1");
+    event.author = author.to_string();
+    event.llm_response.content = Some(Content::new("model").with_text(text));
+    event.llm_response.partial = partial;
+    event
+}
+
+fn usage(prompt: i32, candidates: i32) -> UsageMetadata {
+    UsageMetadata {
+        prompt_token_count: prompt,
+        candidates_token_count: candidates,
+        total_token_count: prompt + candidates,
+        ..Default::default()
+    }
+}
+
+async fn collect(events: Vec<Event>) -> CollectedResponse {
+    collect_final_response(stream::iter(events.into_iter().map(Ok))).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_closing_chunk_is_appended_to_streamed_text() {
+    let collected = collect(vec![
+        text_event("agent", "Hel", true),
+        text_event("agent", "lo", true),
+        text_event("agent", " world", false),
+    ])
+    .await;
+
+    assert_eq!(collected.text, "Hello world");
+    assert_eq!(collected.final_event.unwrap().llm_response.content.unwrap().parts.len(), 1);
+}
+
+#[tokio::test]
+async fn test_closing_aggregate_is_not_double_counted() {
+    let mut last = text_event("agent", "Hello world", false);
+    last.llm_response.usage_metadata = Some(usage(12, 4));
+    let mut chunk = text_event("agent", "Hello", true);
+    chunk.llm_response.usage_metadata = Some(usage(12, 1));
+
+    let collected = collect(vec![chunk, text_event("agent", " world", true), last]).await;
+
+    assert_eq!(collected.text, "Hello world");
+    let usage = collected.usage_metadata.unwrap();
+    assert_eq!((usage.prompt_token_count, usage.candidates_token_count), (12, 4));
+}
+
+#[tokio::test]
+async fn test_tool_loop_collects_calls_responses_and_summed_usage() {
+    let mut call = Event::new("inv-1");
+    call.author = "agent".to_string();
+    call.llm_response.content = Some(Content::new("model").with_part(Part::FunctionCall {
+        name: "get_weather".to_string(),
+        args: json!({ "city": "Paris" }),
+        id: Some("call-1".to_string()),
+        thought_signature: None,
+    }));
+    call.llm_response.usage_metadata = Some(usage(10, 5));
+
+    let mut response = Event::new("inv-1");
+    response.author = "agent".to_string();
+    response.llm_response.content =
+        Some(Content::new("function").with_part(Part::FunctionResponse {
+            function_response: FunctionResponseData::new("get_weather", json!({ "celsius": 21 })),
+            id: Some("call-1".to_string()),
+        }));
+
+    let mut answer = text_event("agent", "It is 21°C in Paris.", false);
+    answer.llm_response.usage_metadata =
+        Some(UsageMetadata { thinking_token_count: Some(3), ..usage(20, 7) });
+    let answer_id = answer.id.clone();
+
+    let progress = Event::tool_progress("inv-1", "agent", "call-1", "stdout", "fetching\n");
+
+    let collected = collect(vec![call, progress, response, answer]).await;
+
+    assert_eq!(collected.text, "It is 21°C in Paris.");
+    assert!(matches!(
+        &collected.function_calls[..],
+        [Part::FunctionCall { name, id: Some(id), .. }] if name == "get_weather" && id == "call-1"
+    ));
+    assert_eq!(collected.function_responses.len(), 1);
+    let usage = collected.usage_metadata.unwrap();
+    assert_eq!(usage.prompt_token_count, 30);
+    assert_eq!(usage.candidates_token_count, 12);
+    assert_eq!(usage.total_token_count, 42);
+    assert_eq!(usage.thinking_token_count, Some(3));
+    assert_eq!(collected.final_event.unwrap().id, answer_id);
+}
+
+#[tokio::test]
+async fn test_interleaved_branches_are_merged_separately() {
+    let on_branch = |text: &str, branch: &str, partial: bool| {
+        let mut event = text_event(branch, text, partial);
+        event.branch = format!("parallel.{branch}");
+        event
+    };
+
+    let collected = collect(vec![
+        on_branch("Bon", "fr", true),
+        on_branch("Hel", "en", true),
+        on_branch("jour", "fr", false),
+        on_branch("lo", "en", false),
+    ])
+    .await;
+
+    assert_eq!(collected.text, "Hello");
+    assert_eq!(collected.final_event.unwrap().branch, "parallel.en");
+}
+
+#[tokio::test]
+async fn test_mid_stream_error_is_returned() {
+    let events: Vec<Result<Event>> = vec![
+        Ok(text_event("agent", "partial answer", true)),
+        Err(AdkError::model("stream dropped")),
+        Ok(text_event("agent", "never seen", false)),
+    ];
+
+    let error = collect_final_response(stream::iter(events)).await.unwrap_err();
+    assert_eq!(error.component, ErrorComponent::Model);
+    assert!(error.to_string().contains("stream dropped"));
+}
+
+#[test]
+fn test_from_events_matches_streaming_collection() {
+    let collected = CollectedResponse::from_events(vec![
+        text_event("agent", "4", true),
+        text_event("agent", "2", false),
+    ]);
+    assert_eq!(collected.text, "42");
+    assert!(collected.function_calls.is_empty());
+    assert!(collected.usage_metadata.is_none());
+}
@@ -0,0 +1,76 @@
+//! Stable fingerprints of logically equal contents.
+
+use adk_core::{Content, FunctionResponseData, Part, canonical_json, content_fingerprint};
+use serde_json::{Value, json};
+
+fn call(args: Value, id: Option<&str>) -> Content {
+    Content {
+        role: "model".to_string(),
+        parts: vec![Part::FunctionCall {
+            name: "book_flight".to_string(),
+            args,
+            id: id.map(str::to_string),
+            thought_signature: None,
+        }],
+    }
+}
+
+#[test]
+fn test_key_order_does_not_change_fingerprint() {
+    let a: Value = serde_json::from_str(
+        r#"{"from": "NBO", "to": "CDG", "passenger": {"name": "A", "age": 30}}"#,
+    )
+    .unwrap();
+    let b: Value = serde_json::from_str(
+        r#"{"passenger": {"age": 30, "name": "A"}, "to": "CDG", "from": "NBO"}"#,
+    )
+    .unwrap();
+    assert_eq!(content_fingerprint(&[call(a, None)]), content_fingerprint(&[call(b, None)]));
+}
+
+#[test]
+fn test_number_formatting_and_call_ids_do_not_change_fingerprint() {
+    let a = call(json!({"seats": 2, "budget": 0.0, "ratio": 0.5}), Some("call_abc"));
+    let b = call(json!({"seats": 2.0, "budget": -0.0, "ratio": 0.5}), Some("toolu_xyz"));
+
+    assert_eq!(content_fingerprint(&[a]), content_fingerprint(&[b]));
+}
+
+#[test]
+fn test_function_responses_and_text_fingerprint_stably() {
+    let response = |payload: &str| Content {
+        role: "user".to_string(),
+        parts: vec![Part::FunctionResponse {
+            function_response: FunctionResponseData::new(
+                "book_flight",
+                serde_json::from_str(payload).unwrap(),
+            ),
+            id: None,
+        }],
+    };
+    let history = |payload| vec![Content::new("user").with_text("Book it"), response(payload)];
+
+    assert_eq!(
+        content_fingerprint(&history(r#"{"status": "ok", "ref": "X1"}"#)),
+        content_fingerprint(&history(r#"{"ref": "X1", "status": "ok"}"#))
+    );
+}
+
+#[test]
+fn test_different_contents_get_different_fingerprints() {
+    let fingerprint = content_fingerprint(&[Content::new("user").with_text("Hello")]);
+
+    assert_ne!(fingerprint, content_fingerprint(&[Content::new("user").with_text("Hello!")]));
+    assert_ne!(fingerprint, content_fingerprint(&[Content::new("model").with_text("Hello")]));
+    assert_ne!(
+        content_fingerprint(&[call(json!({"seats": 1}), None)]),
+        content_fingerprint(&[call(json!({"seats": 2}), None)])
+    );
+    assert_eq!(fingerprint.len(), 64);
+}
+
+#[test]
+fn test_canonical_json_sorts_nested_keys() {
+    let value: Value = serde_json::from_str(r#"{"b": [{"y": 1, "x": 2}], "a": true}"#).unwrap();
+    assert_eq!(canonical_json(&value), r#"{"a":true,"b":[{"x":2,"y":1}]}"#);
+}
@@ -1,6 +1,7 @@
 use adk_core::{
-    AdkError, Agent, Artifacts, CallbackContext, Content, InvocationContext, Memory,
-    ReadonlyContext, RunConfig, Session, State, inject_session_state,
+    AdkError, Agent, Artifacts, CallbackContext, Content, InvocationContext, MissingKeyPolicy,
+    ReadonlyContext, RunConfig, Session, State, TemplateOptions, inject_session_state,
+    inject_session_state_with,
 };
 use async_trait::async_trait;
 use serde_json::{Value, json};
@@ -19,6 +20,10 @@ impl MockState {
         data.insert("user_name".to_string(), json!("Alice"));
         data.insert("role".to_string(), json!("admin"));
         data.insert("user:pref".to_string(), json!("dark_mode"));
+        data.insert("user:premium".to_string(), json!(true));
+        data.insert("user:trial".to_string(), json!(false));
+        data.insert("temp:notes".to_string(), json!(""));
+        data.insert("app:flags".to_string(), json!(["beta"]));
         Self { data }
     }
 }
@@ -139,9 +144,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
@@ -271,3 +273,102 @@ async fn test_complex_mix() {
     let result = inject_session_state(&ctx, template).await.unwrap();
     assert_eq!(result, "Alice read 'Welcome to ADK!' (Theme: dark_mode)");
 }
+
+#[tokio::test]
+async fn test_default_value_when_missing() {
+    let ctx = MockContext::new();
+    let template = "Hi {user:name?there}, you are {role?guest}.";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "Hi there, you are admin.");
+}
+
+#[tokio::test]
+async fn test_default_value_for_missing_artifact() {
+    let ctx = MockContext::new().with_artifacts();
+    let template = "Notes: {artifact.missing.txt?none yet}";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "Notes: none yet");
+}
+
+#[tokio::test]
+async fn test_missing_key_empty_policy() {
+    let ctx = MockContext::new();
+    let options = TemplateOptions::default().missing_key(MissingKeyPolicy::Empty);
+    let result = inject_session_state_with(&ctx, "Group: [{group}]", options).await.unwrap();
+    assert_eq!(result, "Group: []");
+}
+
+#[tokio::test]
+async fn test_missing_key_error_policy_is_default() {
+    let ctx = MockContext::new();
+    let err = inject_session_state_with(&ctx, "Group: {group}", TemplateOptions::default())
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("'group' not found"));
+}
+
+#[tokio::test]
+async fn test_if_block_truthy_and_falsy() {
+    let ctx = MockContext::new();
+    let template = "A{{#if user:premium}} premium{{/if}}{{#if user:trial}} trial{{/if}}\
+                    {{#if temp:notes}} notes{{/if}}{{#if app:flags}} flags{{/if}}\
+                    {{#if user:unset}} unset{{/if}}";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "A premium flags");
+}
+
+#[tokio::test]
+async fn test_if_else_block() {
+    let ctx = MockContext::new();
+    let template = "{{#if user:trial}}Trial user{{else}}Hello {user_name}{{/if}}!";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "Hello Alice!");
+}
+
+#[tokio::test]
+async fn test_nested_blocks() {
+    let ctx = MockContext::new();
+    let template = "{{#if user:premium}}P{{#if user:trial}}T{{else}}F{{/if}}\
+                    {{#if role}}R{{/if}}{{/if}}{{#if user:trial}}{{#if role}}X{{/if}}{{/if}}";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "PFR");
+}
+
+#[tokio::test]
+async fn test_skipped_block_does_not_resolve_placeholders() {
+    let ctx = MockContext::new();
+    let template = "{{#if user:trial}}{group}{{/if}}ok";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "ok");
+}
+
+#[tokio::test]
+async fn test_malformed_blocks_error() {
+    let ctx = MockContext::new();
+    for template in [
+        "{{#if role}}never closed",
+        "stray {{/if}}",
+        "stray {{else}}",
+        "{{#if role}}a{{else}}b{{else}}c{{/if}}",
+        "{{#if bad:prefix}}x{{/if}}",
+    ] {
+        let result = inject_session_state(&ctx, template).await;
+        assert!(result.is_err(), "expected error for {template:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_prefix_left_literal() {
+    let ctx = MockContext::new();
+    let template = "Keep {note:x} and {note:y?fallback} as written";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "Keep {note:x} and {note:y?fallback} as written");
+}
+
+#[tokio::test]
+async fn test_escaped_braces() {
+    let ctx = MockContext::new();
+    let template = r"Literal \{user_name\} and \{{#if role}}x\{{/if}} for {user_name}";
+    let result = inject_session_state(&ctx, template).await.unwrap();
+    assert_eq!(result, "Literal {user_name} and {{#if role}}x{{/if}} for Alice");
+}
@@ -0,0 +1,204 @@
+//! Typed state accessors: `get_as` / `set_as` on session state and the
+//! matching helpers on `ToolContext`.
+
+use adk_core::{
+    Artifacts, CallbackContext, Content, EventActions, MemoryEntry, ReadonlyContext, ReadonlyState,
+    Result, State, ToolContext,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+struct MapState {
+    values: HashMap<String, Value>,
+}
+
+impl State for MapState {
+    fn get(&self, key: &str) -> Option<Value> {
+        self.values.get(key).cloned()
+    }
+
+    fn set(&mut self, key: String, value: Value) {
+        self.values.insert(key, value);
+    }
+
+    fn all(&self) -> HashMap<String, Value> {
+        self.values.clone()
+    }
+}
+
+impl ReadonlyState for MapState {
+    fn get(&self, key: &str) -> Option<Value> {
+        self.values.get(key).cloned()
+    }
+
+    fn all(&self) -> HashMap<String, Value> {
+        self.values.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Preferences {
+    theme: String,
+    notifications: Notifications,
+    favourites: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Notifications {
+    email: bool,
+    digest_hour: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Plan {
+    Free,
+    Pro { seats: u32 },
+}
+
+fn preferences() -> Preferences {
+    Preferences {
+        theme: "dark".to_string(),
+        notifications: Notifications { email: true, digest_hour: Some(8) },
+        favourites: vec!["rust".to_string()],
+    }
+}
+
+#[test]
+fn test_nested_struct_round_trips_under_prefixed_keys() {
+    let mut state = MapState::default();
+    let writable: &mut dyn State = &mut state;
+
+    writable.set_as("user:preferences", &preferences()).unwrap();
+    writable.set_as("temp:draft", &preferences()).unwrap();
+
+    assert_eq!(writable.get_as::<Preferences>("user:preferences").unwrap(), Some(preferences()));
+    assert_eq!(writable.get_as::<Preferences>("temp:draft").unwrap(), Some(preferences()));
+    assert_eq!(writable.get_as::<Preferences>("preferences").unwrap(), None);
+    assert_eq!(state.values["user:preferences"]["notifications"]["digest_hour"], json!(8));
+
+    let readonly: &dyn ReadonlyState = &state;
+    assert_eq!(readonly.get_as::<Preferences>("user:preferences").unwrap(), Some(preferences()));
+}
+
+#[test]
+fn test_internally_tagged_enum_round_trips() {
+    let mut state = MapState::default();
+    let writable: &mut dyn State = &mut state;
+
+    writable.set_as("app:plan", &Plan::Pro { seats: 5 }).unwrap();
+    assert_eq!(state.values["app:plan"], json!({ "kind": "pro", "seats": 5 }));
+
+    state.values.insert("user:plan".to_string(), json!({ "kind": "free" }));
+    let readonly: &dyn ReadonlyState = &state;
+    assert_eq!(readonly.get_as::<Plan>("app:plan").unwrap(), Some(Plan::Pro { seats: 5 }));
+    assert_eq!(readonly.get_as::<Plan>("user:plan").unwrap(), Some(Plan::Free));
+}
+
+#[test]
+fn test_mismatched_json_is_a_serialization_error() {
+    let mut state = MapState::default();
+    state.values.insert("app:plan".to_string(), json!({ "kind": "enterprise" }));
+    state.values.insert("user:preferences".to_string(), json!({ "theme": 3 }));
+
+    let readonly: &dyn ReadonlyState = &state;
+    let error = readonly.get_as::<Plan>("app:plan").unwrap_err();
+    assert!(error.is_serialization(), "{error}");
+    assert!(error.to_string().contains("app:plan"), "{error}");
+
+    let writable: &mut dyn State = &mut state;
+    let error = writable.get_as::<Preferences>("user:preferences").unwrap_err();
+    assert!(error.is_serialization(), "{error}");
+}
+
+#[test]
+fn test_set_as_rejects_invalid_keys() {
+    let mut state = MapState::default();
+    let writable: &mut dyn State = &mut state;
+
+    let error = writable.set_as("user:../secrets", &Plan::Free).unwrap_err();
+    assert!(!error.is_serialization());
+    assert!(state.values.is_empty());
+}
+
+/// A tool context that sees a fixed session state.
+struct StateToolContext {
+    session: MapState,
+    content: Content,
+    actions: Mutex<EventActions>,
+}
+
+#[async_trait]
+impl ReadonlyContext for StateToolContext {
+    fn invocation_id(&self) -> &str {
+        "invocation"
+    }
+    fn agent_name(&self) -> &str {
+        "agent"
+    }
+    fn user_id(&self) -> &str {
+        "user"
+    }
+    fn app_name(&self) -> &str {
+        "app"
+    }
+    fn session_id(&self) -> &str {
+        "session"
+    }
+    fn branch(&self) -> &str {
+        ""
+    }
+    fn user_content(&self) -> &Content {
+        &self.content
+    }
+}
+
+#[async_trait]
+impl CallbackContext for StateToolContext {
+    fn artifacts(&self) -> Option<Arc<dyn Artifacts>> {
+        None
+    }
+}
+
+#[async_trait]
+impl ToolContext for StateToolContext {
+    fn function_call_id(&self) -> &str {
+        "call-1"
+    }
+    fn actions(&self) -> EventActions {
+        self.actions.lock().unwrap().clone()
+    }
+    fn set_actions(&self, actions: EventActions) {
+        *self.actions.lock().unwrap() = actions;
+    }
+    async fn search_memory(&self, _query: &str) -> Result<Vec<MemoryEntry>> {
+        Ok(vec![])
+    }
+    fn get_state(&self, key: &str) -> Option<Value> {
+        let pending = self.actions.lock().unwrap().state_delta.get(key).cloned();
+        pending.or_else(|| State::get(&self.session, key))
+    }
+}
+
+#[test]
+fn test_tool_context_reads_session_state_and_its_own_writes() {
+    let mut session = MapState::default();
+    session.values.insert("app:plan".to_string(), json!({ "kind": "free" }));
+    session.values.insert("user:preferences".to_string(), json!("not a struct"));
+    let ctx: Arc<dyn ToolContext> = Arc::new(StateToolContext {
+        session,
+        content: Content::new("user"),
+        actions: Mutex::new(EventActions::default()),
+    });
+
+    assert_eq!(ctx.get_state_as::<Plan>("app:plan").unwrap(), Some(Plan::Free));
+    assert!(ctx.get_state_as::<Preferences>("user:preferences").unwrap_err().is_serialization());
+
+    ctx.set_state_as("app:plan", &Plan::Pro { seats: 2 }).unwrap();
+    assert_eq!(ctx.get_state_as::<Plan>("app:plan").unwrap(), Some(Plan::Pro { seats: 2 }));
+    assert_eq!(ctx.actions().state_delta["app:plan"], json!({ "kind": "pro", "seats": 2 }));
+}
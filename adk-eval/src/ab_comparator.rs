@@ -2,7 +2,9 @@
 //!
 //! Runs two agent configurations against the same eval set and applies
 //! the Wilcoxon signed-rank test to determine whether performance differences
-//! are statistically significant.
+//! are statistically significant. Each difference also gets a bootstrap
+//! confidence interval, and agents can be run several times so that
+//! run-to-run noise is averaged out rather than mistaken for an improvement.
 //!
 //! # Example
 //!
@@ -10,19 +12,25 @@
 //! use adk_eval::AbComparator;
 //!
 //! let comparator = AbComparator::new(evaluator);
-//! let report = comparator.compare(agent_a, agent_b, &eval_cases).await?;
+//! let report = comparator.compare_agents(agent_a, agent_b, &eval_set, 5).await?;
 //! for comparison in &report.criteria_comparisons {
-//!     println!("{}: p={:.4}, significant={}", comparison.criterion, comparison.p_value, comparison.significant);
+//!     let ci = comparison.delta_interval;
+//!     println!(
+//!         "{}: delta={:.3} 95% CI [{:.3}, {:.3}] p={:.4} significant={}",
+//!         comparison.criterion, comparison.delta, ci.lower, ci.upper,
+//!         comparison.p_value, comparison.significant,
+//!     );
 //! }
 //! ```
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::error::{EvalError, Result};
 use crate::evaluator::Evaluator;
-use crate::schema::EvalCase;
+use crate::schema::{EvalCase, EvalSet};
 
 use adk_core::Agent;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 /// Result of comparing two agents across an eval set.
@@ -32,10 +40,17 @@ pub struct ComparisonReport {
     pub agent_a_name: String,
     /// Name of agent B
     pub agent_b_name: String,
-    /// Per-criterion statistical comparisons
+    /// Per-criterion statistical comparisons, sorted by criterion name
     pub criteria_comparisons: Vec<CriterionComparison>,
     /// Total number of eval cases run
     pub total_cases: usize,
+    /// Number of times each agent was run over the eval set
+    #[serde(default = "default_runs")]
+    pub runs: usize,
+}
+
+fn default_runs() -> usize {
+    1
 }
 
 /// Statistical comparison for a single criterion.
@@ -47,8 +62,18 @@ pub struct CriterionComparison {
     pub agent_a_mean: f64,
     /// Mean score for agent B
     pub agent_b_mean: f64,
+    /// Standard deviation of agent A's mean score from run to run
+    /// (0.0 for a single run)
+    #[serde(default)]
+    pub agent_a_run_stddev: f64,
+    /// Standard deviation of agent B's mean score from run to run
+    /// (0.0 for a single run)
+    #[serde(default)]
+    pub agent_b_run_stddev: f64,
     /// Score delta (A mean - B mean)
     pub delta: f64,
+    /// Bootstrap confidence interval for the delta
+    pub delta_interval: BootstrapInterval,
     /// p-value from Wilcoxon signed-rank test
     pub p_value: f64,
     /// Whether the difference is statistically significant
@@ -61,24 +86,62 @@ pub struct CriterionComparison {
     pub ties: usize,
 }
 
+/// Bootstrap confidence interval for a mean paired difference.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BootstrapInterval {
+    /// Lower bound of the interval
+    pub lower: f64,
+    /// Upper bound of the interval
+    pub upper: f64,
+    /// Confidence level of the interval, e.g. 0.95
+    pub confidence_level: f64,
+    /// Two-sided bootstrap p-value for a mean difference of zero
+    pub p_value: f64,
+}
+
+impl BootstrapInterval {
+    /// Whether the interval excludes zero, i.e. the difference is significant
+    /// at `1 - confidence_level`.
+    pub fn excludes_zero(&self) -> bool {
+        self.lower > 0.0 || self.upper < 0.0
+    }
+}
+
 /// Compares two agents using statistical significance testing.
 ///
 /// Executes both agents against the same eval set, then applies the
-/// Wilcoxon signed-rank test to per-case score differences.
+/// Wilcoxon signed-rank test to per-case score differences and reports a
+/// bootstrap confidence interval for the mean difference.
 pub struct AbComparator {
     evaluator: Evaluator,
     significance_level: f64,
+    resamples: usize,
+    seed: u64,
 }
 
 impl AbComparator {
     /// Create a new comparator with default significance level (0.05).
     pub fn new(evaluator: Evaluator) -> Self {
-        Self { evaluator, significance_level: 0.05 }
+        Self::with_significance_level(evaluator, 0.05)
     }
 
     /// Create a comparator with a custom significance level.
+    ///
+    /// The bootstrap interval's confidence level is `1 - level`.
     pub fn with_significance_level(evaluator: Evaluator, level: f64) -> Self {
-        Self { evaluator, significance_level: level }
+        Self { evaluator, significance_level: level, resamples: 10_000, seed: 0 }
+    }
+
+    /// Set the number of bootstrap resamples (default 10,000).
+    pub fn with_resamples(mut self, resamples: usize) -> Self {
+        self.resamples = resamples;
+        self
+    }
+
+    /// Set the seed for bootstrap resampling, so reports are reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
     }
 
     /// Run A/B comparison between two agents.
@@ -88,49 +151,81 @@ impl AbComparator {
     ///
     /// # Errors
     ///
-    /// Returns [`EvalError::StatisticsError`] if evaluation or statistical
-    /// computation fails.
+    /// Returns [`EvalError::StatisticsError`](crate::EvalError::StatisticsError)
+    /// if evaluation or statistical computation fails.
     pub async fn compare(
         &self,
         agent_a: Arc<dyn Agent>,
         agent_b: Arc<dyn Agent>,
         eval_cases: &[EvalCase],
     ) -> Result<ComparisonReport> {
-        use std::collections::HashMap;
+        self.compare_runs(agent_a, agent_b, eval_cases, 1).await
+    }
+
+    /// Compare two agents over the inline cases of an [`EvalSet`], running
+    /// each agent `runs` times.
+    ///
+    /// Per-case scores are averaged over the runs before the agents are
+    /// compared, which smooths out nondeterministic agents; the run-to-run
+    /// spread is reported as `agent_a_run_stddev` and `agent_b_run_stddev`.
+    /// A case is compared only if both agents completed it at least once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EvalError::StatisticsError`](crate::EvalError::StatisticsError)
+    /// if `runs` is zero.
+    pub async fn compare_agents(
+        &self,
+        agent_a: Arc<dyn Agent>,
+        agent_b: Arc<dyn Agent>,
+        eval_set: &EvalSet,
+        runs: usize,
+    ) -> Result<ComparisonReport> {
+        if runs == 0 {
+            return Err(EvalError::StatisticsError("runs must be at least 1".to_string()));
+        }
+        self.compare_runs(agent_a, agent_b, &eval_set.eval_cases, runs).await
+    }
 
+    async fn compare_runs(
+        &self,
+        agent_a: Arc<dyn Agent>,
+        agent_b: Arc<dyn Agent>,
+        eval_cases: &[EvalCase],
+        runs: usize,
+    ) -> Result<ComparisonReport> {
         let agent_a_name = agent_a.name().to_string();
         let agent_b_name = agent_b.name().to_string();
 
-        // Evaluate agent A against all cases
-        let results_a: Vec<_> =
-            self.evaluator.evaluate_cases_parallel(agent_a, eval_cases, 1).await;
+        let runs_a = self.run_cases(agent_a, eval_cases, runs).await;
+        let runs_b = self.run_cases(agent_b, eval_cases, runs).await;
 
-        // Evaluate agent B against all cases
-        let results_b: Vec<_> =
-            self.evaluator.evaluate_cases_parallel(agent_b, eval_cases, 1).await;
+        // Per criterion: one (score_a, score_b) pair per compared case, plus
+        // each agent's scores by run for the run-to-run spread
+        let mut criterion_pairs: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
+        let mut run_scores: HashMap<String, (RunScores, RunScores)> = HashMap::new();
 
-        // Collect per-criterion scores for each agent, keyed by criterion name
-        // Each entry maps criterion -> vec of (score_a, score_b) pairs
-        let mut criterion_pairs: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
-
-        for (res_a, res_b) in results_a.iter().zip(results_b.iter()) {
-            let scores_a = match res_a {
-                Ok(r) => &r.scores,
-                Err(_) => continue,
-            };
-            let scores_b = match res_b {
-                Ok(r) => &r.scores,
-                Err(_) => continue,
-            };
+        for (case_a, case_b) in runs_a.iter().zip(&runs_b) {
+            let completed_a: Vec<_> = case_a.iter().flatten().collect();
+            let completed_b: Vec<_> = case_b.iter().flatten().collect();
+            if completed_a.is_empty() || completed_b.is_empty() {
+                continue;
+            }
 
-            // Collect all criteria from both results
-            let mut all_criteria: std::collections::HashSet<&String> = scores_a.keys().collect();
-            all_criteria.extend(scores_b.keys());
+            // Collect all criteria from both agents' runs
+            let all_criteria: HashSet<&String> =
+                completed_a.iter().chain(&completed_b).flat_map(|scores| scores.keys()).collect();
 
             for criterion in all_criteria {
-                let score_a = scores_a.get(criterion).copied().unwrap_or(0.0);
-                let score_b = scores_b.get(criterion).copied().unwrap_or(0.0);
+                let score_a = mean_score(&completed_a, criterion);
+                let score_b = mean_score(&completed_b, criterion);
                 criterion_pairs.entry(criterion.clone()).or_default().push((score_a, score_b));
+
+                let (by_run_a, by_run_b) = run_scores
+                    .entry(criterion.clone())
+                    .or_insert_with(|| (vec![Vec::new(); runs], vec![Vec::new(); runs]));
+                record_runs(by_run_a, case_a, criterion);
+                record_runs(by_run_b, case_b, criterion);
             }
         }
 
@@ -139,9 +234,6 @@ impl AbComparator {
 
         for (criterion, pairs) in &criterion_pairs {
             let n = pairs.len();
-            if n == 0 {
-                continue;
-            }
 
             let sum_a: f64 = pairs.iter().map(|(a, _)| a).sum();
             let sum_b: f64 = pairs.iter().map(|(_, b)| b).sum();
@@ -154,6 +246,12 @@ impl AbComparator {
 
             let p_value = wilcoxon_signed_rank(&differences);
             let significant = p_value < self.significance_level;
+            let delta_interval = bootstrap_mean_interval(
+                &differences,
+                1.0 - self.significance_level,
+                self.resamples,
+                self.seed,
+            )?;
 
             // Count wins, losses, ties
             let mut wins_a = 0usize;
@@ -170,11 +268,16 @@ impl AbComparator {
                 }
             }
 
+            let (by_run_a, by_run_b) = &run_scores[criterion];
+
             criteria_comparisons.push(CriterionComparison {
                 criterion: criterion.clone(),
                 agent_a_mean,
                 agent_b_mean,
+                agent_a_run_stddev: run_stddev(by_run_a),
+                agent_b_run_stddev: run_stddev(by_run_b),
                 delta,
+                delta_interval,
                 p_value,
                 significant,
                 wins_a,
@@ -188,8 +291,130 @@ impl AbComparator {
             agent_b_name,
             criteria_comparisons,
             total_cases: eval_cases.len(),
+            runs,
         })
     }
+
+    /// Scores of every case for every run, indexed `[case][run]`; `None`
+    /// where the evaluation failed.
+    async fn run_cases(
+        &self,
+        agent: Arc<dyn Agent>,
+        eval_cases: &[EvalCase],
+        runs: usize,
+    ) -> Vec<Vec<Option<HashMap<String, f64>>>> {
+        let mut scores = vec![Vec::with_capacity(runs); eval_cases.len()];
+        for _ in 0..runs {
+            for (case, case_scores) in eval_cases.iter().zip(&mut scores) {
+                let result = self.evaluator.evaluate_case(agent.clone(), case).await;
+                case_scores.push(result.ok().map(|r| r.scores));
+            }
+        }
+        scores
+    }
+}
+
+/// Scores for one criterion, grouped by run.
+type RunScores = Vec<Vec<f64>>;
+
+/// Mean of `criterion` over completed runs; a run without it scores 0.0.
+fn mean_score(runs: &[&HashMap<String, f64>], criterion: &str) -> f64 {
+    let sum: f64 = runs.iter().map(|scores| scores.get(criterion).copied().unwrap_or(0.0)).sum();
+    sum / runs.len() as f64
+}
+
+fn record_runs(by_run: &mut RunScores, case: &[Option<HashMap<String, f64>>], criterion: &str) {
+    for (run, scores) in by_run.iter_mut().zip(case) {
+        if let Some(scores) = scores {
+            run.push(scores.get(criterion).copied().unwrap_or(0.0));
+        }
+    }
+}
+
+/// Sample standard deviation of the per-run mean scores.
+fn run_stddev(by_run: &RunScores) -> f64 {
+    let means: Vec<f64> = by_run
+        .iter()
+        .filter(|scores| !scores.is_empty())
+        .map(|scores| scores.iter().sum::<f64>() / scores.len() as f64)
+        .collect();
+    if means.len() < 2 {
+        return 0.0;
+    }
+    let mean = means.iter().sum::<f64>() / means.len() as f64;
+    let variance = means.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / (means.len() - 1) as f64;
+    variance.sqrt()
+}
+
+/// Percentile bootstrap confidence interval for the mean of paired differences.
+///
+/// Resamples `differences` with replacement `resamples` times and takes the
+/// `(1 - confidence_level) / 2` and `(1 + confidence_level) / 2` quantiles of
+/// the resampled means. The p-value is twice the share of resampled means on
+/// the far side of zero, with a +1 correction so it is never exactly zero.
+/// The same `seed` always gives the same interval.
+///
+/// # Errors
+///
+/// Returns [`EvalError::StatisticsError`](crate::EvalError::StatisticsError)
+/// if `differences` is empty, `resamples` is zero, or `confidence_level` is
+/// not strictly between 0 and 1.
+pub fn bootstrap_mean_interval(
+    differences: &[f64],
+    confidence_level: f64,
+    resamples: usize,
+    seed: u64,
+) -> Result<BootstrapInterval> {
+    if differences.is_empty() {
+        return Err(EvalError::StatisticsError("no differences to resample".to_string()));
+    }
+    if resamples == 0 {
+        return Err(EvalError::StatisticsError("resamples must be at least 1".to_string()));
+    }
+    if !(confidence_level > 0.0 && confidence_level < 1.0) {
+        return Err(EvalError::StatisticsError(format!(
+            "confidence level must be between 0 and 1, got {confidence_level}"
+        )));
+    }
+
+    let n = differences.len();
+    let mut rng = SplitMix64(seed);
+    let mut means: Vec<f64> = (0..resamples)
+        .map(|_| (0..n).map(|_| differences[rng.below(n)]).sum::<f64>() / n as f64)
+        .collect();
+    means.sort_by(f64::total_cmp);
+
+    let quantile = |q: f64| means[((resamples - 1) as f64 * q).round() as usize];
+    let tail = (1.0 - confidence_level) / 2.0;
+
+    let at_or_below = means.iter().filter(|m| **m <= 0.0).count();
+    let at_or_above = means.iter().filter(|m| **m >= 0.0).count();
+    let p_value = 2.0 * (at_or_below.min(at_or_above) + 1) as f64 / (resamples + 1) as f64;
+
+    Ok(BootstrapInterval {
+        lower: quantile(tail),
+        upper: quantile(1.0 - tail),
+        confidence_level,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// Small seedable generator for bootstrap resampling.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
 }
 
 /// Wilcoxon signed-rank test for paired samples.
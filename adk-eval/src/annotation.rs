@@ -1,27 +1,39 @@
-//! Human annotation workflow via JSONL export/import.
+//! Human annotation workflow via JSONL or CSV export/import.
 //!
-//! Provides JSONL-based file format for exporting evaluation cases for human review
-//! and importing human verdicts back into the system.
+//! Provides file formats for exporting evaluation cases for human review and
+//! importing human verdicts back into an [`EvaluationReport`].
 //!
 //! # Format
 //!
-//! Each line in the JSONL file is a JSON object representing an [`AnnotationRecord`].
+//! In JSONL files, each line is a JSON object representing an [`AnnotationRecord`].
 //! On export, the `verdict` field is `null`; on import, annotators fill it in.
 //!
+//! CSV files have one row per record with the columns `case_id`, `criterion`,
+//! `input`, `expected_response`, `actual_response`, `automatic_score`,
+//! `threshold`, `human_score`, `reasoning`, and `annotator_id`. The last three
+//! are empty on export and filled in by annotators.
+//!
 //! # Example
 //!
 //! ```rust,ignore
-//! use adk_eval::annotation::{AnnotationStore, AnnotationRecord};
-//! use adk_eval::schema::EvalCase;
-//! use adk_eval::report::EvaluationResult;
+//! use adk_eval::annotation::{AnnotationFormat, AnnotationStore, ReviewSelection};
 //! use std::collections::HashSet;
 //!
-//! // Export cases for human review
-//! AnnotationStore::export(&cases, &results, "annotations.jsonl")?;
+//! // Send judge scores within 0.1 of the 0.7 threshold to human review
+//! let selection = ReviewSelection::new("rubric_quality", 0.7, 0.1);
+//! AnnotationStore::export_for_review(
+//!     &cases,
+//!     &report.results,
+//!     &selection,
+//!     "review.csv",
+//!     AnnotationFormat::Csv,
+//! )?;
 //!
-//! // Import annotated verdicts
+//! // Fold the annotated verdicts back into the report
 //! let valid_ids: HashSet<String> = cases.iter().map(|c| c.eval_id.clone()).collect();
-//! let (records, warnings) = AnnotationStore::import("annotations.jsonl", &valid_ids)?;
+//! let (records, warnings) =
+//!     AnnotationStore::import_from("review.csv", AnnotationFormat::Csv, &valid_ids)?;
+//! report.apply_annotations(&records);
 //! ```
 
 use std::collections::HashSet;
@@ -34,17 +46,31 @@ use crate::error::{EvalError, Result};
 use crate::report::EvaluationResult;
 use crate::schema::EvalCase;
 
+/// Criterion that a human verdict scores when its record names no criterion.
+///
+/// Records written by [`AnnotationStore::export`] leave the criterion unset.
+pub const HUMAN_REVIEW_CRITERION: &str = "human_review";
+
 /// A single annotation record for human review.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnnotationRecord {
     /// Identifier of the evaluation case
     pub case_id: String,
+    /// Criterion the human score replaces (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criterion: Option<String>,
     /// Input text or conversation
     pub input: String,
     /// Expected response (if available)
     pub expected_response: Option<String>,
     /// Actual agent response (if available)
     pub actual_response: Option<String>,
+    /// Automatic score for the criterion, for the annotator's reference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automatic_score: Option<f64>,
+    /// Pass threshold for the criterion (if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
     /// Human-provided verdict (empty on export, filled on import)
     pub verdict: Option<HumanVerdict>,
 }
@@ -60,7 +86,98 @@ pub struct HumanVerdict {
     pub annotator_id: String,
 }
 
-/// Manages JSONL export and import for human annotation.
+/// File format for annotation export and import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationFormat {
+    /// One JSON [`AnnotationRecord`] per line
+    Jsonl,
+    /// One CSV row per record, with `human_score`, `reasoning`, and
+    /// `annotator_id` columns to fill in
+    Csv,
+}
+
+/// Selects results whose automatic score is too close to the pass threshold
+/// to trust, for routing to human review.
+#[derive(Debug, Clone)]
+pub struct ReviewSelection {
+    /// Criterion whose score is checked
+    pub criterion: String,
+    /// Pass threshold for the criterion
+    pub threshold: f64,
+    /// Scores within this distance of the threshold are low-confidence
+    pub margin: f64,
+}
+
+impl ReviewSelection {
+    /// Select results whose `criterion` score is within `margin` of `threshold`.
+    pub fn new(criterion: impl Into<String>, threshold: f64, margin: f64) -> Self {
+        Self { criterion: criterion.into(), threshold, margin }
+    }
+
+    /// Whether `result` has an automatic score for the criterion within the margin.
+    ///
+    /// Scores already assigned by a human are never selected again.
+    pub fn is_low_confidence(&self, result: &EvaluationResult) -> bool {
+        !result.human_scores.contains_key(&self.criterion)
+            && result
+                .scores
+                .get(&self.criterion)
+                .is_some_and(|score| (score - self.threshold).abs() <= self.margin)
+    }
+}
+
+/// Flat CSV row for an [`AnnotationRecord`].
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    case_id: String,
+    criterion: Option<String>,
+    input: String,
+    expected_response: Option<String>,
+    actual_response: Option<String>,
+    automatic_score: Option<f64>,
+    threshold: Option<f64>,
+    human_score: Option<f64>,
+    reasoning: Option<String>,
+    annotator_id: Option<String>,
+}
+
+impl From<&AnnotationRecord> for CsvRow {
+    fn from(record: &AnnotationRecord) -> Self {
+        Self {
+            case_id: record.case_id.clone(),
+            criterion: record.criterion.clone(),
+            input: record.input.clone(),
+            expected_response: record.expected_response.clone(),
+            actual_response: record.actual_response.clone(),
+            automatic_score: record.automatic_score,
+            threshold: record.threshold,
+            human_score: record.verdict.as_ref().map(|v| v.score),
+            reasoning: record.verdict.as_ref().map(|v| v.reasoning.clone()),
+            annotator_id: record.verdict.as_ref().map(|v| v.annotator_id.clone()),
+        }
+    }
+}
+
+impl From<CsvRow> for AnnotationRecord {
+    fn from(row: CsvRow) -> Self {
+        Self {
+            case_id: row.case_id,
+            criterion: row.criterion,
+            input: row.input,
+            expected_response: row.expected_response,
+            actual_response: row.actual_response,
+            automatic_score: row.automatic_score,
+            threshold: row.threshold,
+            verdict: row.human_score.map(|score| HumanVerdict {
+                score,
+                reasoning: row.reasoning.unwrap_or_default(),
+                annotator_id: row.annotator_id.unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+/// Manages JSONL and CSV export and import for human annotation.
 pub struct AnnotationStore;
 
 impl AnnotationStore {
@@ -81,62 +198,46 @@ impl AnnotationStore {
         results: &[EvaluationResult],
         output_path: impl AsRef<Path>,
     ) -> Result<()> {
-        let file = std::fs::File::create(output_path.as_ref()).map_err(|e| {
-            EvalError::AnnotationError(format!(
-                "failed to create annotation file '{}': {e}",
-                output_path.as_ref().display()
-            ))
-        })?;
-        let mut writer = BufWriter::new(file);
-
-        for case in cases {
-            // Build input from conversation turns
-            let input = case
-                .conversation
-                .iter()
-                .map(|turn| turn.user_content.get_text())
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            // Get expected response from last turn's final_response
-            let expected_response = case
-                .conversation
-                .last()
-                .and_then(|turn| turn.final_response.as_ref())
-                .map(|content| content.get_text());
-
-            // Find matching result for this case to get actual response
-            let actual_response = results
-                .iter()
-                .find(|r| r.eval_id == case.eval_id)
-                .and_then(|r| r.turn_results.last())
-                .and_then(|tr| tr.actual_response.clone());
-
-            let record = AnnotationRecord {
-                case_id: case.eval_id.clone(),
-                input,
-                expected_response,
-                actual_response,
-                verdict: None,
-            };
-
-            let line = serde_json::to_string(&record).map_err(|e| {
-                EvalError::AnnotationError(format!(
-                    "failed to serialize annotation record for case '{}': {e}",
-                    case.eval_id
-                ))
-            })?;
-
-            writeln!(writer, "{line}").map_err(|e| {
-                EvalError::AnnotationError(format!("failed to write annotation line: {e}"))
-            })?;
-        }
-
-        writer.flush().map_err(|e| {
-            EvalError::AnnotationError(format!("failed to flush annotation file: {e}"))
-        })?;
+        let records: Vec<AnnotationRecord> =
+            cases.iter().map(|case| Self::record_for(case, results)).collect();
+        Self::write(&records, output_path.as_ref(), AnnotationFormat::Jsonl)
+    }
 
-        Ok(())
+    /// Export the low-confidence results picked by `selection` for human review.
+    ///
+    /// Each record names the selection's criterion and threshold and carries
+    /// the automatic score, so imported verdicts replace that score and decide
+    /// pass/fail for it. Cases without a matching low-confidence result are
+    /// left out.
+    ///
+    /// Returns the number of records written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError::AnnotationError` if the file cannot be created or written to.
+    pub fn export_for_review(
+        cases: &[EvalCase],
+        results: &[EvaluationResult],
+        selection: &ReviewSelection,
+        output_path: impl AsRef<Path>,
+        format: AnnotationFormat,
+    ) -> Result<usize> {
+        let records: Vec<AnnotationRecord> = cases
+            .iter()
+            .filter_map(|case| {
+                let result = results.iter().find(|r| r.eval_id == case.eval_id)?;
+                if !selection.is_low_confidence(result) {
+                    return None;
+                }
+                let mut record = Self::record_for(case, results);
+                record.criterion = Some(selection.criterion.clone());
+                record.automatic_score = result.scores.get(&selection.criterion).copied();
+                record.threshold = Some(selection.threshold);
+                Some(record)
+            })
+            .collect();
+        Self::write(&records, output_path.as_ref(), format)?;
+        Ok(records.len())
     }
 
     /// Import annotations from a JSONL file.
@@ -157,6 +258,23 @@ impl AnnotationStore {
     pub fn import(
         path: impl AsRef<Path>,
         valid_case_ids: &HashSet<String>,
+    ) -> Result<(Vec<AnnotationRecord>, Vec<String>)> {
+        Self::import_from(path, AnnotationFormat::Jsonl, valid_case_ids)
+    }
+
+    /// Import annotations from a JSONL or CSV file.
+    ///
+    /// Behaves like [`import`](Self::import); CSV rows with an empty
+    /// `human_score` produce records without a verdict.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvalError::AnnotationError` if the file cannot be read or a
+    /// line or row is malformed.
+    pub fn import_from(
+        path: impl AsRef<Path>,
+        format: AnnotationFormat,
+        valid_case_ids: &HashSet<String>,
     ) -> Result<(Vec<AnnotationRecord>, Vec<String>)> {
         let file = std::fs::File::open(path.as_ref()).map_err(|e| {
             EvalError::AnnotationError(format!(
@@ -164,12 +282,111 @@ impl AnnotationStore {
                 path.as_ref().display()
             ))
         })?;
-        let reader = BufReader::new(file);
+
+        let parsed = match format {
+            AnnotationFormat::Jsonl => Self::read_jsonl(file)?,
+            AnnotationFormat::Csv => Self::read_csv(file)?,
+        };
 
         let mut records = Vec::new();
         let mut warnings = Vec::new();
+        for (line_num, record) in parsed {
+            if valid_case_ids.contains(&record.case_id) {
+                records.push(record);
+            } else {
+                warnings.push(format!("unmatched case_id '{}' at line {line_num}", record.case_id));
+            }
+        }
+
+        Ok((records, warnings))
+    }
+
+    /// Build the record for `case`, without a criterion or verdict.
+    fn record_for(case: &EvalCase, results: &[EvaluationResult]) -> AnnotationRecord {
+        // Build input from conversation turns
+        let input = case
+            .conversation
+            .iter()
+            .map(|turn| turn.user_content.get_text())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Get expected response from last turn's final_response
+        let expected_response = case
+            .conversation
+            .last()
+            .and_then(|turn| turn.final_response.as_ref())
+            .map(|content| content.get_text());
+
+        // Find matching result for this case to get actual response
+        let actual_response = results
+            .iter()
+            .find(|r| r.eval_id == case.eval_id)
+            .and_then(|r| r.turn_results.last())
+            .and_then(|tr| tr.actual_response.clone());
+
+        AnnotationRecord {
+            case_id: case.eval_id.clone(),
+            criterion: None,
+            input,
+            expected_response,
+            actual_response,
+            automatic_score: None,
+            threshold: None,
+            verdict: None,
+        }
+    }
 
-        for (line_num, line_result) in reader.lines().enumerate() {
+    fn write(records: &[AnnotationRecord], path: &Path, format: AnnotationFormat) -> Result<()> {
+        let file = std::fs::File::create(path).map_err(|e| {
+            EvalError::AnnotationError(format!(
+                "failed to create annotation file '{}': {e}",
+                path.display()
+            ))
+        })?;
+
+        match format {
+            AnnotationFormat::Jsonl => {
+                let mut writer = BufWriter::new(file);
+                for record in records {
+                    let line = serde_json::to_string(record).map_err(|e| {
+                        EvalError::AnnotationError(format!(
+                            "failed to serialize annotation record for case '{}': {e}",
+                            record.case_id
+                        ))
+                    })?;
+
+                    writeln!(writer, "{line}").map_err(|e| {
+                        EvalError::AnnotationError(format!("failed to write annotation line: {e}"))
+                    })?;
+                }
+                writer.flush().map_err(|e| {
+                    EvalError::AnnotationError(format!("failed to flush annotation file: {e}"))
+                })?;
+            }
+            AnnotationFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(file);
+                for record in records {
+                    writer.serialize(CsvRow::from(record)).map_err(|e| {
+                        EvalError::AnnotationError(format!(
+                            "failed to write annotation row for case '{}': {e}",
+                            record.case_id
+                        ))
+                    })?;
+                }
+                writer.flush().map_err(|e| {
+                    EvalError::AnnotationError(format!("failed to flush annotation file: {e}"))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse JSONL records, paired with their 1-based line numbers.
+    fn read_jsonl(file: std::fs::File) -> Result<Vec<(usize, AnnotationRecord)>> {
+        let mut records = Vec::new();
+        for (line_num, line_result) in BufReader::new(file).lines().enumerate() {
             let line = line_result.map_err(|e| {
                 EvalError::AnnotationError(format!("failed to read line {}: {e}", line_num + 1))
             })?;
@@ -186,19 +403,30 @@ impl AnnotationStore {
                     line_num + 1
                 ))
             })?;
-
-            if valid_case_ids.contains(&record.case_id) {
-                records.push(record);
-            } else {
-                warnings.push(format!(
-                    "unmatched case_id '{}' at line {}",
-                    record.case_id,
-                    line_num + 1
-                ));
-            }
+            records.push((line_num + 1, record));
         }
+        Ok(records)
+    }
 
-        Ok((records, warnings))
+    /// Parse CSV rows, paired with their 1-based line numbers.
+    fn read_csv(file: std::fs::File) -> Result<Vec<(usize, AnnotationRecord)>> {
+        let csv_error =
+            |e: csv::Error| EvalError::AnnotationError(format!("failed to parse annotation: {e}"));
+
+        let mut reader = csv::Reader::from_reader(file);
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let mut row = csv::StringRecord::new();
+        let mut records = Vec::new();
+        while reader.read_record(&mut row).map_err(csv_error)? {
+            let line_num = row.position().map_or(0, |p| p.line() as usize);
+            let parsed: CsvRow = row.deserialize(Some(&headers)).map_err(|e| {
+                EvalError::AnnotationError(format!(
+                    "failed to parse annotation at line {line_num}: {e}"
+                ))
+            })?;
+            records.push((line_num, AnnotationRecord::from(parsed)));
+        }
+        Ok(records)
     }
 }
 
@@ -278,9 +506,12 @@ mod tests {
         let records = [
             AnnotationRecord {
                 case_id: "case_1".to_string(),
+                criterion: None,
                 input: "Hello".to_string(),
                 expected_response: Some("Hi".to_string()),
                 actual_response: Some("Hey".to_string()),
+                automatic_score: None,
+                threshold: None,
                 verdict: Some(HumanVerdict {
                     score: 0.9,
                     reasoning: "Good response".to_string(),
@@ -289,9 +520,12 @@ mod tests {
             },
             AnnotationRecord {
                 case_id: "case_2".to_string(),
+                criterion: None,
                 input: "Bye".to_string(),
                 expected_response: None,
                 actual_response: None,
+                automatic_score: None,
+                threshold: None,
                 verdict: None,
             },
         ];
@@ -322,9 +556,12 @@ mod tests {
 
         let record = AnnotationRecord {
             case_id: "unknown_case".to_string(),
+            criterion: None,
             input: "test".to_string(),
             expected_response: None,
             actual_response: None,
+            automatic_score: None,
+            threshold: None,
             verdict: None,
         };
 
@@ -361,9 +598,12 @@ mod tests {
 
         let record = AnnotationRecord {
             case_id: "case_1".to_string(),
+            criterion: None,
             input: "hello".to_string(),
             expected_response: None,
             actual_response: None,
+            automatic_score: None,
+            threshold: None,
             verdict: None,
         };
 
@@ -415,6 +655,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_low_confidence_case_round_trips_through_csv_into_report() {
+        use crate::report::{EvaluationReport, Failure, ScoreSource};
+        use serde_json::Value;
+
+        let cases = vec![
+            make_case("borderline", "Cancel my order", Some("Your order is cancelled")),
+            make_case("confident", "Track my order", Some("It ships tomorrow")),
+        ];
+        let results = vec![
+            EvaluationResult::failed(
+                "borderline",
+                HashMap::from([("rubric_quality".to_string(), 0.65)]),
+                vec![Failure::new("rubric_quality", Value::Null, Value::Null, 0.65, 0.7)],
+                Duration::from_millis(50),
+            ),
+            EvaluationResult::passed(
+                "confident",
+                HashMap::from([("rubric_quality".to_string(), 0.95)]),
+                Duration::from_millis(50),
+            ),
+        ];
+        let mut report = EvaluationReport::new("run-1", results, chrono::Utc::now());
+        assert_eq!(report.summary.passed, 1);
+
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_path_buf();
+        let selection = ReviewSelection::new("rubric_quality", 0.7, 0.1);
+        let exported = AnnotationStore::export_for_review(
+            &cases,
+            &report.results,
+            &selection,
+            &path,
+            AnnotationFormat::Csv,
+        )
+        .unwrap();
+        assert_eq!(exported, 1);
+        let sheet = std::fs::read_to_string(&path).unwrap();
+        assert!(sheet.starts_with("case_id,criterion,input,"));
+        assert!(sheet.contains("borderline,rubric_quality,Cancel my order,"));
+        assert!(!sheet.contains("confident"));
+
+        // An annotator fills in the score columns.
+        let valid_ids: HashSet<String> = cases.iter().map(|c| c.eval_id.clone()).collect();
+        let (mut records, _) =
+            AnnotationStore::import_from(&path, AnnotationFormat::Csv, &valid_ids).unwrap();
+        assert!(records[0].verdict.is_none());
+        assert_eq!(records[0].automatic_score, Some(0.65));
+        records[0].verdict = Some(HumanVerdict {
+            score: 0.85,
+            reasoning: "Cancellation confirmed correctly".to_string(),
+            annotator_id: "reviewer_1".to_string(),
+        });
+        AnnotationStore::write(&records, &path, AnnotationFormat::Csv).unwrap();
+
+        let (records, warnings) =
+            AnnotationStore::import_from(&path, AnnotationFormat::Csv, &valid_ids).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(report.apply_annotations(&records), 1);
+
+        let borderline = &report.results[0];
+        assert!(borderline.passed);
+        assert!(borderline.failures.is_empty());
+        assert_eq!(borderline.scores["rubric_quality"], 0.85);
+        assert_eq!(borderline.score_source("rubric_quality"), Some(ScoreSource::Human));
+        assert_eq!(borderline.human_scores["rubric_quality"].annotator_id, "reviewer_1");
+        assert_eq!(report.results[1].score_source("rubric_quality"), Some(ScoreSource::Automatic));
+        assert_eq!(report.summary.passed, 2);
+        assert_eq!(report.summary.pass_rate, 1.0);
+        assert!((report.summary.avg_scores["rubric_quality"] - 0.9).abs() < 1e-9);
+
+        // Human-scored cases are not selected for review again.
+        assert!(!selection.is_low_confidence(borderline));
+    }
+
+    #[test]
+    fn test_human_score_below_threshold_fails_case() {
+        let mut result = EvaluationResult::passed(
+            "case_1",
+            HashMap::from([("rubric_quality".to_string(), 0.75)]),
+            Duration::from_millis(50),
+        );
+        let verdict = HumanVerdict {
+            score: 0.4,
+            reasoning: "Ignores the refund question".to_string(),
+            annotator_id: "reviewer_2".to_string(),
+        };
+
+        result.apply_human_verdict("rubric_quality", verdict, Some(0.7));
+
+        assert!(!result.passed);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].score, 0.4);
+        assert!(result.failures[0].details.as_deref().unwrap().contains("reviewer_2"));
+    }
+
     #[test]
     fn test_export_nonexistent_directory_returns_error() {
         let cases = vec![make_case("c1", "hi", None)];
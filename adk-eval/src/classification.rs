@@ -0,0 +1,394 @@
+//! Classification metrics for label-producing agents.
+//!
+//! This module provides [`ClassificationScorer`], which extracts a predicted
+//! label from each agent response and a gold label from each expected
+//! response, builds a [`ConfusionMatrix`], and computes per-class and
+//! macro/micro precision, recall, and F1.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_eval::classification::ClassificationScorer;
+//!
+//! let scorer = ClassificationScorer::new().label_path("label");
+//!
+//! let report = scorer.report_from_pairs([
+//!     ("positive", r#"{"label": "positive"}"#),
+//!     ("negative", r#"{"label": "positive"}"#),
+//!     ("negative", r#"{"label": "negative"}"#),
+//! ]);
+//!
+//! assert_eq!(report.matrix.count("negative", "positive"), 1);
+//! assert!((report.accuracy - 2.0 / 3.0).abs() < 1e-9);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::report::EvaluationResult;
+
+/// Label recorded when no label could be extracted from the agent output.
+pub const UNKNOWN_LABEL: &str = "<unknown>";
+
+/// Builds classification reports from gold and predicted labels.
+///
+/// Labels are read from a dotted path (e.g. `"label"` or `"result.category"`)
+/// into the JSON response text. With an empty path the whole trimmed response
+/// is the label. Gold labels fall back to the trimmed expected text when it is
+/// not JSON, so test files can store either `"positive"` or `{"label": "positive"}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClassificationScorer {
+    /// Dotted path to the label inside a JSON response. Empty means the whole text.
+    #[serde(default)]
+    pub label_path: String,
+    /// Compare labels case-insensitively (labels are lowercased).
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+impl ClassificationScorer {
+    /// Create a scorer that treats the whole response text as the label.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the dotted path used to extract labels from JSON responses.
+    pub fn label_path(mut self, path: impl Into<String>) -> Self {
+        self.label_path = path.into();
+        self
+    }
+
+    /// Compare labels case-insensitively.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Extract a label from a response using the configured path.
+    ///
+    /// Returns `None` when the path is set but the text is not JSON or the
+    /// path does not resolve to a string, number, or boolean.
+    pub fn extract_label(&self, text: &str) -> Option<String> {
+        let label = if self.label_path.is_empty() {
+            let trimmed = text.trim();
+            (!trimmed.is_empty()).then(|| trimmed.to_string())
+        } else {
+            let value: Value = serde_json::from_str(text.trim()).ok()?;
+            let pointer = format!("/{}", self.label_path.replace('.', "/"));
+            match value.pointer(&pointer)? {
+                Value::String(s) => Some(s.trim().to_string()),
+                Value::Number(n) => Some(n.to_string()),
+                Value::Bool(b) => Some(b.to_string()),
+                Value::Null | Value::Array(_) | Value::Object(_) => None,
+            }
+        };
+        label.map(|l| if self.case_insensitive { l.to_lowercase() } else { l })
+    }
+
+    /// Build a report from `(expected, actual)` response pairs.
+    pub fn report_from_pairs<'a>(
+        &self,
+        pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> ClassificationReport {
+        let mut matrix = ConfusionMatrix::default();
+        for (expected, actual) in pairs {
+            let gold = self.extract_label(expected).unwrap_or_else(|| {
+                let trimmed = expected.trim();
+                if self.case_insensitive { trimmed.to_lowercase() } else { trimmed.to_string() }
+            });
+            let predicted = self.extract_label(actual).unwrap_or_else(|| UNKNOWN_LABEL.to_string());
+            matrix.record(&gold, &predicted);
+        }
+        ClassificationReport::from_matrix(matrix)
+    }
+
+    /// Build a report from evaluation results using each case's final turn.
+    ///
+    /// Cases without turn details or without an expected response are skipped;
+    /// a missing actual response counts as an [`UNKNOWN_LABEL`] prediction.
+    pub fn report(&self, results: &[EvaluationResult]) -> ClassificationReport {
+        self.report_from_pairs(results.iter().filter_map(|result| {
+            let turn = result.turn_results.last()?;
+            let expected = turn.expected_response.as_deref()?;
+            Some((expected, turn.actual_response.as_deref().unwrap_or_default()))
+        }))
+    }
+}
+
+/// Counts of `(gold, predicted)` label pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfusionMatrix {
+    /// Counts keyed by gold label, then predicted label.
+    pub counts: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl ConfusionMatrix {
+    /// Record one prediction.
+    pub fn record(&mut self, gold: &str, predicted: &str) {
+        *self
+            .counts
+            .entry(gold.to_string())
+            .or_default()
+            .entry(predicted.to_string())
+            .or_default() += 1;
+    }
+
+    /// Number of cases with the given gold and predicted labels.
+    pub fn count(&self, gold: &str, predicted: &str) -> usize {
+        self.counts.get(gold).and_then(|row| row.get(predicted)).copied().unwrap_or(0)
+    }
+
+    /// Total number of recorded cases.
+    pub fn total(&self) -> usize {
+        self.counts.values().flat_map(BTreeMap::values).sum()
+    }
+
+    /// All gold and predicted labels, sorted, excluding [`UNKNOWN_LABEL`].
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels = BTreeSet::new();
+        for (gold, row) in &self.counts {
+            labels.insert(gold.clone());
+            labels.extend(row.keys().cloned());
+        }
+        labels.remove(UNKNOWN_LABEL);
+        labels.into_iter().collect()
+    }
+}
+
+/// Precision, recall, and F1 for one class.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassMetrics {
+    /// Correct predictions of this class.
+    pub true_positives: usize,
+    /// Predictions of this class whose gold label differs.
+    pub false_positives: usize,
+    /// Gold cases of this class predicted as something else.
+    pub false_negatives: usize,
+    /// Precision (0.0 when the class was never predicted).
+    pub precision: f64,
+    /// Recall (0.0 when the class never occurs in the gold labels).
+    pub recall: f64,
+    /// Harmonic mean of precision and recall.
+    pub f1: f64,
+    /// Number of gold cases of this class.
+    pub support: usize,
+}
+
+impl ClassMetrics {
+    fn new(true_positives: usize, false_positives: usize, false_negatives: usize) -> Self {
+        let precision = ratio(true_positives, true_positives + false_positives);
+        let recall = ratio(true_positives, true_positives + false_negatives);
+        Self {
+            true_positives,
+            false_positives,
+            false_negatives,
+            precision,
+            recall,
+            f1: f1(precision, recall),
+            support: true_positives + false_negatives,
+        }
+    }
+}
+
+/// Aggregated classification metrics for an evaluation run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassificationReport {
+    /// The underlying confusion matrix.
+    pub matrix: ConfusionMatrix,
+    /// Per-class metrics keyed by label.
+    pub per_class: BTreeMap<String, ClassMetrics>,
+    /// Fraction of cases whose predicted label equals the gold label.
+    pub accuracy: f64,
+    /// Unweighted mean of per-class precision.
+    pub macro_precision: f64,
+    /// Unweighted mean of per-class recall.
+    pub macro_recall: f64,
+    /// Unweighted mean of per-class F1.
+    pub macro_f1: f64,
+    /// Precision over pooled true/false positives.
+    pub micro_precision: f64,
+    /// Recall over pooled true positives/false negatives.
+    pub micro_recall: f64,
+    /// F1 of micro precision and recall.
+    pub micro_f1: f64,
+}
+
+impl ClassificationReport {
+    /// Compute all metrics from a confusion matrix.
+    pub fn from_matrix(matrix: ConfusionMatrix) -> Self {
+        let labels = matrix.labels();
+        let mut per_class = BTreeMap::new();
+        let (mut tp_sum, mut fp_sum, mut fn_sum) = (0, 0, 0);
+
+        for label in &labels {
+            let tp = matrix.count(label, label);
+            let fp = matrix
+                .counts
+                .iter()
+                .filter(|(gold, _)| *gold != label)
+                .map(|(gold, _)| matrix.count(gold, label))
+                .sum();
+            let fn_ = matrix
+                .counts
+                .get(label)
+                .map(|row| row.iter().filter(|(p, _)| *p != label).map(|(_, c)| c).sum())
+                .unwrap_or(0);
+            tp_sum += tp;
+            fp_sum += fp;
+            fn_sum += fn_;
+            per_class.insert(label.clone(), ClassMetrics::new(tp, fp, fn_));
+        }
+
+        let class_count = per_class.len().max(1) as f64;
+        let macro_precision = per_class.values().map(|m| m.precision).sum::<f64>() / class_count;
+        let macro_recall = per_class.values().map(|m| m.recall).sum::<f64>() / class_count;
+        let macro_f1 = per_class.values().map(|m| m.f1).sum::<f64>() / class_count;
+        let micro_precision = ratio(tp_sum, tp_sum + fp_sum);
+        let micro_recall = ratio(tp_sum, tp_sum + fn_sum);
+
+        Self {
+            accuracy: ratio(tp_sum, matrix.total()),
+            matrix,
+            per_class,
+            macro_precision,
+            macro_recall,
+            macro_f1,
+            micro_precision,
+            micro_recall,
+            micro_f1: f1(micro_precision, micro_recall),
+        }
+    }
+
+    /// Format as a human-readable table.
+    pub fn format_summary(&self) -> String {
+        let mut output = String::from("Classification:\n");
+        output.push_str(&format!("  Accuracy: {:.3}\n", self.accuracy));
+        output.push_str(&format!(
+            "  Macro P/R/F1: {:.3} / {:.3} / {:.3}\n",
+            self.macro_precision, self.macro_recall, self.macro_f1
+        ));
+        output.push_str(&format!(
+            "  Micro P/R/F1: {:.3} / {:.3} / {:.3}\n",
+            self.micro_precision, self.micro_recall, self.micro_f1
+        ));
+        for (label, m) in &self.per_class {
+            output.push_str(&format!(
+                "  {label}: precision {:.3}, recall {:.3}, f1 {:.3}, support {}\n",
+                m.precision, m.recall, m.f1, m.support
+            ));
+        }
+        output
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 { 0.0 } else { 2.0 * precision * recall / (precision + recall) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::TurnResult;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn labeled_set() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("cat", r#"{"label": "cat"}"#),
+            ("cat", r#"{"label": "cat"}"#),
+            ("cat", r#"{"label": "dog"}"#),
+            ("dog", r#"{"label": "dog"}"#),
+            ("dog", r#"{"label": "cat"}"#),
+            ("bird", r#"{"label": "bird"}"#),
+            ("bird", "not json"),
+        ]
+    }
+
+    #[test]
+    fn test_confusion_matrix_counts() {
+        let report =
+            ClassificationScorer::new().label_path("label").report_from_pairs(labeled_set());
+
+        assert_eq!(report.matrix.total(), 7);
+        assert_eq!(report.matrix.count("cat", "cat"), 2);
+        assert_eq!(report.matrix.count("cat", "dog"), 1);
+        assert_eq!(report.matrix.count("dog", "cat"), 1);
+        assert_eq!(report.matrix.count("dog", "dog"), 1);
+        assert_eq!(report.matrix.count("bird", "bird"), 1);
+        assert_eq!(report.matrix.count("bird", UNKNOWN_LABEL), 1);
+        assert_eq!(report.matrix.labels(), ["bird", "cat", "dog"]);
+    }
+
+    #[test]
+    fn test_per_class_and_aggregate_metrics() {
+        let report =
+            ClassificationScorer::new().label_path("label").report_from_pairs(labeled_set());
+
+        let cat = &report.per_class["cat"];
+        assert_eq!((cat.true_positives, cat.false_positives, cat.false_negatives), (2, 1, 1));
+        assert!(approx(cat.precision, 2.0 / 3.0));
+        assert!(approx(cat.recall, 2.0 / 3.0));
+        assert!(approx(cat.f1, 2.0 / 3.0));
+
+        let dog = &report.per_class["dog"];
+        assert!(approx(dog.precision, 0.5));
+        assert!(approx(dog.recall, 0.5));
+        assert!(approx(dog.f1, 0.5));
+
+        let bird = &report.per_class["bird"];
+        assert!(approx(bird.precision, 1.0));
+        assert!(approx(bird.recall, 0.5));
+        assert!(approx(bird.f1, 2.0 / 3.0));
+        assert_eq!(bird.support, 2);
+
+        assert!(approx(report.accuracy, 4.0 / 7.0));
+        assert!(approx(report.macro_f1, (2.0 / 3.0 + 0.5 + 2.0 / 3.0) / 3.0));
+        assert!(approx(report.micro_precision, 4.0 / 6.0));
+        assert!(approx(report.micro_recall, 4.0 / 7.0));
+        assert!(approx(report.micro_f1, 2.0 * (4.0 / 6.0) * (4.0 / 7.0) / (4.0 / 6.0 + 4.0 / 7.0)));
+    }
+
+    #[test]
+    fn test_extract_label_paths() {
+        let scorer = ClassificationScorer::new().label_path("result.category");
+        assert_eq!(
+            scorer.extract_label(r#"{"result": {"category": "spam"}}"#).as_deref(),
+            Some("spam")
+        );
+        assert_eq!(scorer.extract_label(r#"{"result": {}}"#), None);
+
+        let plain = ClassificationScorer::new().case_insensitive(true);
+        assert_eq!(plain.extract_label("  Positive\n").as_deref(), Some("positive"));
+    }
+
+    #[test]
+    fn test_report_from_evaluation_results() {
+        let result = |expected: &str, actual: &str| {
+            EvaluationResult::passed("case", HashMap::new(), Duration::ZERO).with_turn_results(
+                vec![TurnResult {
+                    invocation_id: "inv".to_string(),
+                    actual_response: Some(actual.to_string()),
+                    expected_response: Some(expected.to_string()),
+                    actual_tool_calls: vec![],
+                    expected_tool_calls: vec![],
+                    scores: HashMap::new(),
+                }],
+            )
+        };
+        let results = vec![result("yes", "yes"), result("no", "yes")];
+
+        let report = ClassificationScorer::new().report(&results);
+        assert_eq!(report.matrix.count("no", "yes"), 1);
+        assert!(approx(report.accuracy, 0.5));
+    }
+}
@@ -20,7 +20,7 @@
 //! assert!(cost.is_none());
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -45,6 +45,140 @@ pub struct CostMetrics {
     pub cost_usd: Option<f64>,
     /// Wall-clock latency in milliseconds.
     pub latency_ms: u64,
+    /// Token usage and cost broken down by the model that produced it.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, ModelUsage>,
+}
+
+/// Model name recorded for usage whose request did not name a model.
+pub const UNKNOWN_MODEL: &str = "unknown";
+
+/// Token usage and estimated cost attributed to one model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelUsage {
+    /// Number of responses that reported usage.
+    pub responses: u64,
+    /// Number of prompt/input tokens used.
+    pub prompt_tokens: u64,
+    /// Number of completion/output tokens generated.
+    pub completion_tokens: u64,
+    /// Total token count (prompt + completion).
+    pub total_tokens: u64,
+    /// Estimated cost in USD (None if the model has no pricing entry).
+    pub cost_usd: Option<f64>,
+}
+
+impl ModelUsage {
+    fn add(&mut self, other: &ModelUsage) {
+        self.responses += other.responses;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cost_usd = match (self.cost_usd, other.cost_usd) {
+            (Some(a), Some(b)) => Some(a + b),
+            _ => None,
+        };
+    }
+}
+
+/// Sum of per-model costs, or `None` when there is no usage or any model
+/// lacks pricing.
+fn total_cost<'a>(models: impl IntoIterator<Item = &'a ModelUsage>) -> Option<f64> {
+    let mut models = models.into_iter().peekable();
+    models.peek()?;
+    models.map(|usage| usage.cost_usd).sum()
+}
+
+/// Token usage and estimated cost aggregated over every case of a run.
+///
+/// Built by [`EvaluationReport`](crate::EvaluationReport) from the
+/// [`CostMetrics`] of its results. Use [`cost_usd`](Self::cost_usd) to gate
+/// CI runs on a budget.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Number of cases that recorded cost metrics.
+    pub cases: usize,
+    /// Number of prompt/input tokens used.
+    pub prompt_tokens: u64,
+    /// Number of completion/output tokens generated.
+    pub completion_tokens: u64,
+    /// Total token count (prompt + completion).
+    pub total_tokens: u64,
+    /// Average total tokens per case.
+    pub avg_tokens_per_case: f64,
+    /// Estimated cost in USD (None if any model lacks pricing).
+    pub cost_usd: Option<f64>,
+    /// Usage broken down by model.
+    pub models: BTreeMap<String, ModelUsage>,
+}
+
+impl UsageReport {
+    /// Aggregate the metrics of several cases, or `None` when there are none.
+    pub fn from_metrics<'a>(metrics: impl IntoIterator<Item = &'a CostMetrics>) -> Option<Self> {
+        let mut report = Self::default();
+        for case in metrics {
+            report.cases += 1;
+            report.prompt_tokens += case.prompt_tokens;
+            report.completion_tokens += case.completion_tokens;
+            report.total_tokens += case.total_tokens;
+            // Metrics recorded without a breakdown still count, unpriced.
+            let unattributed = ModelUsage {
+                prompt_tokens: case.prompt_tokens,
+                completion_tokens: case.completion_tokens,
+                total_tokens: case.total_tokens,
+                ..Default::default()
+            };
+            let breakdown: Vec<_> = if case.models.is_empty() && case.total_tokens > 0 {
+                vec![(UNKNOWN_MODEL, &unattributed)]
+            } else {
+                case.models.iter().map(|(model, usage)| (model.as_str(), usage)).collect()
+            };
+            for (model, usage) in breakdown {
+                report
+                    .models
+                    .entry(model.to_string())
+                    .or_insert_with(|| ModelUsage { cost_usd: Some(0.0), ..Default::default() })
+                    .add(usage);
+            }
+        }
+        if report.cases == 0 {
+            return None;
+        }
+        report.avg_tokens_per_case = report.total_tokens as f64 / report.cases as f64;
+        report.cost_usd = total_cost(report.models.values());
+        Some(report)
+    }
+
+    /// Whether the estimated cost is known and at most `max_usd`.
+    pub fn within_budget(&self, max_usd: f64) -> bool {
+        self.cost_usd.is_some_and(|cost| cost <= max_usd)
+    }
+
+    /// Format the usage for display
+    pub fn format_summary(&self) -> String {
+        let mut output = String::from("Usage:\n");
+        output.push_str(&format!(
+            "  Tokens: {} ({} prompt, {} completion)\n",
+            self.total_tokens, self.prompt_tokens, self.completion_tokens
+        ));
+        output.push_str(&format!("  Avg Tokens/Case: {:.1}\n", self.avg_tokens_per_case));
+        output.push_str(&format!("  Estimated Cost: {}\n", format_cost(self.cost_usd)));
+        for (model, usage) in &self.models {
+            output.push_str(&format!(
+                "  {model}: {} tokens, {}\n",
+                usage.total_tokens,
+                format_cost(usage.cost_usd)
+            ));
+        }
+        output
+    }
+}
+
+fn format_cost(cost_usd: Option<f64>) -> String {
+    match cost_usd {
+        Some(cost) => format!("${cost:.4}"),
+        None => "unknown (no pricing)".to_string(),
+    }
 }
 
 /// Tracks cost and latency metrics from agent event streams.
@@ -98,9 +232,9 @@ impl CostTracker {
     ///
     /// The `duration` parameter is converted to milliseconds for the `latency_ms` field.
     ///
-    /// Note: The `cost_usd` field will be `None` because the model name is not
-    /// available on the Event struct. Use [`compute_cost`](Self::compute_cost)
-    /// separately when the model name is known.
+    /// Usage is attributed to the model named in the event's recorded LLM
+    /// request, or to [`UNKNOWN_MODEL`] when there is none. The `cost_usd`
+    /// field is `None` unless every model that reported usage has pricing.
     ///
     /// # Arguments
     ///
@@ -114,23 +248,38 @@ impl CostTracker {
         let mut prompt_tokens: u64 = 0;
         let mut completion_tokens: u64 = 0;
         let mut total_tokens: u64 = 0;
+        let mut models: BTreeMap<String, ModelUsage> = BTreeMap::new();
 
         for event in events {
             if let Some(usage) = &event.llm_response.usage_metadata {
                 // Accumulate token counts, treating negative values as zero
-                prompt_tokens += u64::try_from(usage.prompt_token_count.max(0)).unwrap_or(0);
-                completion_tokens +=
-                    u64::try_from(usage.candidates_token_count.max(0)).unwrap_or(0);
-                total_tokens += u64::try_from(usage.total_token_count.max(0)).unwrap_or(0);
+                let prompt = u64::try_from(usage.prompt_token_count.max(0)).unwrap_or(0);
+                let completion = u64::try_from(usage.candidates_token_count.max(0)).unwrap_or(0);
+                let total = u64::try_from(usage.total_token_count.max(0)).unwrap_or(0);
+                prompt_tokens += prompt;
+                completion_tokens += completion;
+                total_tokens += total;
+
+                let model = request_model(event).unwrap_or_else(|| UNKNOWN_MODEL.to_string());
+                let entry = models.entry(model).or_default();
+                entry.responses += 1;
+                entry.prompt_tokens += prompt;
+                entry.completion_tokens += completion;
+                entry.total_tokens += total;
             }
         }
 
+        for (model, usage) in &mut models {
+            usage.cost_usd = self.compute_cost(model, usage.prompt_tokens, usage.completion_tokens);
+        }
+
         CostMetrics {
             prompt_tokens,
             completion_tokens,
             total_tokens,
-            cost_usd: None,
+            cost_usd: total_cost(models.values()),
             latency_ms: duration.as_millis() as u64,
+            models,
         }
     }
 
@@ -161,6 +310,12 @@ impl CostTracker {
     }
 }
 
+/// The model named in the LLM request recorded on an event.
+fn request_model(event: &Event) -> Option<String> {
+    let request: serde_json::Value = serde_json::from_str(event.llm_request.as_deref()?).ok()?;
+    request.get("model")?.as_str().filter(|model| !model.is_empty()).map(str::to_string)
+}
+
 impl Default for CostTracker {
     fn default() -> Self {
         Self::new()
@@ -245,7 +400,7 @@ mod tests {
         assert_eq!(metrics.prompt_tokens, 100);
         assert_eq!(metrics.completion_tokens, 50);
         assert_eq!(metrics.total_tokens, 150);
-        // cost_usd is None because model can't be determined from events
+        // cost_usd is None because the events don't name a priced model
         assert_eq!(metrics.cost_usd, None);
         assert_eq!(metrics.latency_ms, 2000);
     }
@@ -290,7 +445,7 @@ mod tests {
         assert_eq!(metrics.prompt_tokens, 110);
         assert_eq!(metrics.completion_tokens, 55);
         assert_eq!(metrics.total_tokens, 165);
-        // cost_usd is None because model can't be determined from events
+        // cost_usd is None because the events don't name a priced model
         assert_eq!(metrics.cost_usd, None);
         assert_eq!(metrics.latency_ms, 300);
     }
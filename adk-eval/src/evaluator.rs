@@ -2,17 +2,20 @@
 //!
 //! The Evaluator orchestrates test execution and applies evaluation criteria.
 
+use crate::classification::ClassificationScorer;
 use crate::cost_tracker::CostTracker;
 use crate::criteria::EvaluationCriteria;
 use crate::error::Result;
 use crate::llm_judge::LlmJudge;
-use crate::report::{EvaluationReport, EvaluationResult, Failure, TurnResult};
-use crate::schema::{EvalCase, TestFile, ToolUse, Turn};
+use crate::report::{EvaluationReport, EvaluationResult, Failure, SkippedCase, TurnResult};
+use crate::schema::{
+    ContentData, EvalCase, EvalSet, IntermediateData, SessionInput, TestFile, ToolUse, Turn,
+};
 use crate::scoring::{ResponseScorer, ToolTrajectoryScorer};
 use crate::structured_judge::StructuredJudge;
 use crate::trace_analyzer::TraceAnalyzer;
 
-use adk_core::{Agent, Content, Event, Llm};
+use adk_core::{Agent, CollectedResponse, Content, Event, Llm};
 use async_trait::async_trait;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
@@ -73,6 +76,8 @@ pub struct Evaluator {
     embedding_scorer: Option<Arc<EmbeddingScorer>>,
     /// Optional conversation scorer for multi-turn metrics
     conversation_scorer: Option<Arc<crate::conversation_scorer::ConversationScorer>>,
+    /// Optional classification scorer for confusion-matrix reporting
+    classification_scorer: Option<ClassificationScorer>,
 }
 
 impl Evaluator {
@@ -101,6 +106,7 @@ impl Evaluator {
             #[cfg(feature = "embedding")]
             embedding_scorer: None,
             conversation_scorer: None,
+            classification_scorer: None,
         }
     }
 
@@ -129,6 +135,7 @@ impl Evaluator {
             #[cfg(feature = "embedding")]
             embedding_scorer: None,
             conversation_scorer: None,
+            classification_scorer: None,
         }
     }
 
@@ -171,6 +178,14 @@ impl Evaluator {
         self.conversation_scorer = Some(scorer);
     }
 
+    /// Set the classification scorer for per-class precision/recall/F1 reporting
+    ///
+    /// Labels are taken from each case's final turn, so `collect_turn_details`
+    /// must stay enabled.
+    pub fn set_classification_scorer(&mut self, scorer: ClassificationScorer) {
+        self.classification_scorer = Some(scorer);
+    }
+
     /// Check if a structured judge is configured
     pub fn has_structured_judge(&self) -> bool {
         self.structured_judge.is_some()
@@ -212,12 +227,36 @@ impl Evaluator {
         &self,
         agent: Arc<dyn Agent>,
         test_file: &TestFile,
+    ) -> Result<EvaluationReport> {
+        self.evaluate_test_file_filtered(agent, test_file, &[], &[]).await
+    }
+
+    /// Evaluate only the cases of a TestFile selected by tags
+    ///
+    /// A case runs when it carries at least one of `include_tags` (or
+    /// `include_tags` is empty) and none of `exclude_tags`; exclusion wins.
+    /// File-level tags count as tags of every case. Cases left out are listed
+    /// in [`EvaluationReport::skipped`] with the reason.
+    pub async fn evaluate_test_file_filtered(
+        &self,
+        agent: Arc<dyn Agent>,
+        test_file: &TestFile,
+        include_tags: &[&str],
+        exclude_tags: &[&str],
     ) -> Result<EvaluationReport> {
         let started_at = chrono::Utc::now();
         let run_id = format!("{}_{}", test_file.eval_set_id, uuid::Uuid::new_v4());
         let mut results = Vec::new();
+        let mut skipped = Vec::new();
 
         for eval_case in &test_file.eval_cases {
+            let tags = test_file.case_tags(eval_case);
+            if let Some(reason) = tag_skip_reason(&tags, include_tags, exclude_tags) {
+                tracing::debug!(eval_id = %eval_case.eval_id, %reason, "skipping eval case");
+                skipped.push(SkippedCase { eval_id: eval_case.eval_id.clone(), tags, reason });
+                continue;
+            }
+
             let result = self.evaluate_case(agent.clone(), eval_case).await;
 
             match result {
@@ -249,7 +288,11 @@ impl Evaluator {
             }
         }
 
-        Ok(EvaluationReport::new(&run_id, results, started_at))
+        let report = EvaluationReport::new(&run_id, results, started_at).with_skipped(skipped);
+        Ok(match &self.classification_scorer {
+            Some(scorer) => report.with_classification(scorer),
+            None => report,
+        })
     }
 
     /// Evaluate a single test case
@@ -432,30 +475,19 @@ impl Evaluator {
 
     /// Extract response text and tool calls from events
     fn extract_from_events(&self, events: &[Event]) -> (Option<String>, Vec<ToolUse>) {
-        let mut response_text = String::new();
-        let mut tool_calls = Vec::new();
-
-        for event in events {
-            // Extract text content
-            if let Some(content) = event.content() {
-                for part in &content.parts {
-                    // Extract text content
-                    if let Some(text) = part.text() {
-                        response_text.push_str(text);
-                    }
-                    // Extract function calls using pattern matching
-                    if let adk_core::Part::FunctionCall { name, args, .. } = part {
-                        tool_calls.push(ToolUse {
-                            name: name.clone(),
-                            args: args.clone(),
-                            expected_response: None,
-                        });
-                    }
+        let collected = CollectedResponse::from_events(events.iter().cloned());
+
+        let tool_calls = collected
+            .function_calls
+            .into_iter()
+            .filter_map(|part| match part {
+                adk_core::Part::FunctionCall { name, args, .. } => {
+                    Some(ToolUse { name, args, expected_response: None })
                 }
-            }
-        }
-
-        let response = if response_text.is_empty() { None } else { Some(response_text) };
+                _ => None,
+            })
+            .collect();
+        let response = (!collected.text.is_empty()).then_some(collected.text);
 
         (response, tool_calls)
     }
@@ -751,6 +783,97 @@ impl Evaluator {
         Ok(reports)
     }
 
+    /// Evaluate the inline cases of an [`EvalSet`]
+    ///
+    /// Recorded goldens that still carry `needs_review` are evaluated, but a
+    /// warning is logged since they only assert that behavior hasn't changed.
+    pub async fn evaluate_eval_set(
+        &self,
+        agent: Arc<dyn Agent>,
+        eval_set: &EvalSet,
+    ) -> Result<EvaluationReport> {
+        self.evaluate_set_filtered(agent, eval_set, &[], &[]).await
+    }
+
+    /// Evaluate the inline cases of an [`EvalSet`] selected by tags
+    ///
+    /// Lets CI run a quick subset (`include_tags = ["smoke"]`) while nightly
+    /// runs exclude nothing. Filtering follows
+    /// [`evaluate_test_file_filtered`](Self::evaluate_test_file_filtered).
+    pub async fn evaluate_set_filtered(
+        &self,
+        agent: Arc<dyn Agent>,
+        eval_set: &EvalSet,
+        include_tags: &[&str],
+        exclude_tags: &[&str],
+    ) -> Result<EvaluationReport> {
+        if eval_set.needs_review {
+            tracing::warn!(
+                eval_set_id = %eval_set.eval_set_id,
+                "evaluating a recorded golden set that has not been reviewed"
+            );
+        }
+        let test_file = TestFile {
+            eval_set_id: eval_set.eval_set_id.clone(),
+            name: eval_set.name.clone(),
+            description: eval_set.description.clone(),
+            tags: Vec::new(),
+            eval_cases: eval_set.eval_cases.clone(),
+        };
+        self.evaluate_test_file_filtered(agent, &test_file, include_tags, exclude_tags).await
+    }
+
+    /// Record a golden [`EvalSet`] by running the agent on each input
+    ///
+    /// Every input becomes a single-turn case whose expected response and tool
+    /// trajectory are whatever the agent actually did, including the tool
+    /// results it saw. The set is flagged `needs_review` so the captured
+    /// behavior gets a human check before it is trusted; use
+    /// [`GoldenDiff`](crate::GoldenDiff) to compare later recordings against it.
+    pub async fn record<I, S>(&self, agent: Arc<dyn Agent>, inputs: I) -> Result<EvalSet>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let agent_name = agent.name().to_string();
+        let mut eval_cases = Vec::new();
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            let input = input.as_ref();
+            let events =
+                self.run_agent(agent.clone(), Content::new("user").with_text(input)).await?;
+            let (response, mut tool_uses) = self.extract_from_events(&events);
+            attach_tool_responses(&events, &mut tool_uses);
+
+            let eval_id = format!("{agent_name}_{}", index + 1);
+            eval_cases.push(EvalCase {
+                eval_id: eval_id.clone(),
+                description: String::new(),
+                conversation: vec![Turn {
+                    invocation_id: eval_id,
+                    user_content: ContentData::text(input),
+                    final_response: response.as_deref().map(ContentData::model_response),
+                    intermediate_data: Some(IntermediateData {
+                        tool_uses,
+                        intermediate_responses: Vec::new(),
+                    }),
+                }],
+                session_input: SessionInput::default(),
+                tags: vec!["golden".to_string()],
+                metadata: None,
+            });
+        }
+
+        Ok(EvalSet {
+            eval_set_id: format!("{agent_name}_golden"),
+            name: format!("{agent_name} golden trajectories"),
+            description: format!("Recorded from agent '{agent_name}'"),
+            test_files: Vec::new(),
+            eval_cases,
+            needs_review: true,
+        })
+    }
+
     /// Run a multi-turn conversation between a [`UserSimulator`](crate::personas::UserSimulator)
     /// and the agent under test for a configurable number of turns.
     ///
@@ -792,6 +915,40 @@ impl Evaluator {
     }
 }
 
+/// Why a case with `tags` is filtered out of the run, or `None` if it runs
+fn tag_skip_reason(
+    tags: &[String],
+    include_tags: &[&str],
+    exclude_tags: &[&str],
+) -> Option<String> {
+    if let Some(tag) = exclude_tags.iter().find(|tag| tags.iter().any(|t| t == *tag)) {
+        return Some(format!("excluded tag '{tag}'"));
+    }
+    if !include_tags.is_empty() && !include_tags.iter().any(|tag| tags.iter().any(|t| t == tag)) {
+        return Some(format!("no tag in [{}]", include_tags.join(", ")));
+    }
+    None
+}
+
+/// Fill each recorded tool use's `expected_response` with the result the agent received
+fn attach_tool_responses(events: &[Event], tool_uses: &mut [ToolUse]) {
+    let responses = events
+        .iter()
+        .filter_map(|event| event.content())
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            adk_core::Part::FunctionResponse { function_response, .. } => Some(function_response),
+            _ => None,
+        });
+    for response in responses {
+        if let Some(tool_use) =
+            tool_uses.iter_mut().find(|t| t.name == response.name && t.expected_response.is_none())
+        {
+            tool_use.expected_response = Some(response.response.clone());
+        }
+    }
+}
+
 impl Default for Evaluator {
     fn default() -> Self {
         Self::new(EvaluationConfig::default())
@@ -869,10 +1026,6 @@ impl adk_core::InvocationContext for EvalInvocationContext {
         self.agent.clone()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn adk_core::Session {
         &self.session
     }
@@ -0,0 +1,265 @@
+//! Golden trajectory diffs.
+//!
+//! Compares a golden [`EvalSet`] produced by [`Evaluator::record`](crate::Evaluator::record)
+//! with a fresh recording, so intentional behavior changes can be reviewed and
+//! written back into the golden file.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_eval::{EvalSet, Evaluator, GoldenDiff};
+//!
+//! let mut golden = EvalSet::load("tests/support_agent.golden.json")?;
+//! let inputs: Vec<String> = golden
+//!     .eval_cases
+//!     .iter()
+//!     .map(|case| case.conversation[0].user_content.get_text())
+//!     .collect();
+//! let recorded = Evaluator::default().record(agent, &inputs).await?;
+//!
+//! let diff = GoldenDiff::between(&golden, &recorded);
+//! for change in &diff.changes {
+//!     println!("{change}");
+//! }
+//! diff.apply(&mut golden, &recorded);
+//! golden.save("tests/support_agent.golden.json")?;
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::schema::{EvalCase, EvalSet};
+
+/// Which part of a golden case changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoldenField {
+    /// The recording has an input the golden set lacks
+    NewCase,
+    /// The final response text differs
+    FinalResponse,
+    /// The tool names, arguments, or results differ
+    ToolUses,
+}
+
+/// A single difference between a golden case and its new recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenChange {
+    /// Identifier of the golden case (or the recorded case for new inputs)
+    pub eval_id: String,
+    /// The user input both cases were recorded from
+    pub input: String,
+    /// Which part changed
+    pub field: GoldenField,
+    /// Value in the golden set (`null` for new cases)
+    pub golden: Value,
+    /// Value in the new recording
+    pub recorded: Value,
+}
+
+impl fmt::Display for GoldenChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:?}): {} -> {}", self.eval_id, self.field, self.golden, self.recorded)
+    }
+}
+
+/// Differences between a golden [`EvalSet`] and a new recording of the same inputs.
+///
+/// Cases are matched by the first turn's user input, not by `eval_id`, so
+/// re-recording a subset or a reordered list of inputs still lines up.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GoldenDiff {
+    /// All detected changes, in recording order
+    pub changes: Vec<GoldenChange>,
+}
+
+impl GoldenDiff {
+    /// Compare every recorded case with the golden case for the same input.
+    pub fn between(golden: &EvalSet, recorded: &EvalSet) -> Self {
+        let mut changes = Vec::new();
+
+        for recorded_case in &recorded.eval_cases {
+            let input = case_input(recorded_case);
+            let Some(golden_case) = find_case(golden, &input) else {
+                changes.push(GoldenChange {
+                    eval_id: recorded_case.eval_id.clone(),
+                    input,
+                    field: GoldenField::NewCase,
+                    golden: Value::Null,
+                    recorded: serde_json::to_value(recorded_case).unwrap_or_default(),
+                });
+                continue;
+            };
+
+            let comparisons = [
+                (
+                    GoldenField::FinalResponse,
+                    final_response(golden_case),
+                    final_response(recorded_case),
+                ),
+                (GoldenField::ToolUses, tool_uses(golden_case), tool_uses(recorded_case)),
+            ];
+            for (field, golden_value, recorded_value) in comparisons {
+                if golden_value != recorded_value {
+                    changes.push(GoldenChange {
+                        eval_id: golden_case.eval_id.clone(),
+                        input: input.clone(),
+                        field,
+                        golden: golden_value,
+                        recorded: recorded_value,
+                    });
+                }
+            }
+        }
+
+        Self { changes }
+    }
+
+    /// Returns `true` when the recording matches the golden set.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Accept the recorded behavior for every changed case.
+    ///
+    /// Changed cases take the recorded conversation but keep their golden
+    /// `eval_id`, description, and tags; new inputs are appended. Golden cases
+    /// that were not re-recorded are left alone. When anything changed the set
+    /// is flagged `needs_review` again.
+    pub fn apply(&self, golden: &mut EvalSet, recorded: &EvalSet) {
+        for change in &self.changes {
+            let Some(recorded_case) = find_case(recorded, &change.input) else {
+                continue;
+            };
+            match golden.eval_cases.iter_mut().find(|case| case_input(case) == change.input) {
+                Some(golden_case) => golden_case.conversation = recorded_case.conversation.clone(),
+                None => golden.eval_cases.push(recorded_case.clone()),
+            }
+        }
+        if !self.is_empty() {
+            golden.needs_review = true;
+        }
+    }
+}
+
+fn case_input(case: &EvalCase) -> String {
+    case.conversation.first().map(|turn| turn.user_content.get_text()).unwrap_or_default()
+}
+
+fn find_case<'a>(set: &'a EvalSet, input: &str) -> Option<&'a EvalCase> {
+    set.eval_cases.iter().find(|case| case_input(case) == input)
+}
+
+fn final_response(case: &EvalCase) -> Value {
+    case.conversation
+        .iter()
+        .map(|turn| match &turn.final_response {
+            Some(content) => Value::String(content.get_text()),
+            None => Value::Null,
+        })
+        .collect()
+}
+
+fn tool_uses(case: &EvalCase) -> Value {
+    case.conversation
+        .iter()
+        .map(|turn| {
+            turn.intermediate_data
+                .as_ref()
+                .map(|data| serde_json::to_value(&data.tool_uses).unwrap_or_default())
+                .unwrap_or_else(|| Value::Array(Vec::new()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{ContentData, IntermediateData, SessionInput, ToolUse, Turn};
+    use serde_json::json;
+
+    fn case(eval_id: &str, input: &str, response: &str, tool_uses: Vec<ToolUse>) -> EvalCase {
+        EvalCase {
+            eval_id: eval_id.to_string(),
+            description: String::new(),
+            conversation: vec![Turn {
+                invocation_id: eval_id.to_string(),
+                user_content: ContentData::text(input),
+                final_response: Some(ContentData::model_response(response)),
+                intermediate_data: Some(IntermediateData {
+                    tool_uses,
+                    intermediate_responses: Vec::new(),
+                }),
+            }],
+            session_input: SessionInput::default(),
+            tags: vec!["golden".to_string()],
+            metadata: None,
+        }
+    }
+
+    fn set(cases: Vec<EvalCase>) -> EvalSet {
+        EvalSet {
+            eval_set_id: "agent_golden".to_string(),
+            name: "agent golden trajectories".to_string(),
+            description: String::new(),
+            test_files: Vec::new(),
+            eval_cases: cases,
+            needs_review: false,
+        }
+    }
+
+    #[test]
+    fn test_identical_recording_has_no_changes() {
+        let golden = set(vec![case("agent_1", "hi", "hello", Vec::new())]);
+        let recorded = set(vec![case("agent_9", "hi", "hello", Vec::new())]);
+
+        assert!(GoldenDiff::between(&golden, &recorded).is_empty());
+    }
+
+    #[test]
+    fn test_detects_tool_and_new_case_changes() {
+        let golden = set(vec![case(
+            "agent_1",
+            "weather?",
+            "Sunny",
+            vec![ToolUse::new("get_weather").with_args(json!({ "city": "Paris" }))],
+        )]);
+        let recorded = set(vec![
+            case(
+                "agent_1",
+                "weather?",
+                "Sunny",
+                vec![ToolUse::new("get_weather").with_args(json!({ "city": "Lyon" }))],
+            ),
+            case("agent_2", "time?", "Noon", Vec::new()),
+        ]);
+
+        let diff = GoldenDiff::between(&golden, &recorded);
+        let fields: Vec<_> = diff.changes.iter().map(|c| (c.eval_id.as_str(), c.field)).collect();
+        assert_eq!(fields, [("agent_1", GoldenField::ToolUses), ("agent_2", GoldenField::NewCase)]);
+    }
+
+    #[test]
+    fn test_apply_keeps_golden_ids_and_flags_review() {
+        let mut golden = set(vec![
+            case("greeting", "hi", "hello", Vec::new()),
+            case("untouched", "bye", "goodbye", Vec::new()),
+        ]);
+        let recorded = set(vec![
+            case("agent_1", "hi", "hello there", Vec::new()),
+            case("agent_2", "new", "fresh", Vec::new()),
+        ]);
+
+        let diff = GoldenDiff::between(&golden, &recorded);
+        diff.apply(&mut golden, &recorded);
+
+        assert!(golden.needs_review);
+        let ids: Vec<_> = golden.eval_cases.iter().map(|c| c.eval_id.as_str()).collect();
+        assert_eq!(ids, ["greeting", "untouched", "agent_2"]);
+        let greeting = &golden.eval_cases[0].conversation[0];
+        assert_eq!(greeting.final_response.as_ref().unwrap().get_text(), "hello there");
+        assert!(GoldenDiff::between(&golden, &recorded).is_empty());
+    }
+}
@@ -23,6 +23,7 @@ use crate::report::EvaluationReport;
 ///
 /// Maps each [`EvalCase`](crate::schema::EvalCase) to a `<testcase>` element
 /// and failures to `<failure>` child elements within a `<testsuite>` wrapper.
+/// Cases left out by tag filters get a `<skipped>` child.
 pub struct JunitReporter;
 
 impl JunitReporter {
@@ -47,7 +48,7 @@ impl JunitReporter {
             .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
             .map_err(|e| crate::error::EvalError::IoError(std::io::Error::other(e.to_string())))?;
 
-        let total_cases = report.results.len();
+        let total_cases = report.results.len() + report.skipped.len();
         let failures = report.results.iter().filter(|r| !r.passed).count();
         let total_time = report.duration.as_secs_f64();
 
@@ -57,6 +58,7 @@ impl JunitReporter {
         testsuite.push_attribute(("tests", total_cases.to_string().as_str()));
         testsuite.push_attribute(("failures", failures.to_string().as_str()));
         testsuite.push_attribute(("errors", "0"));
+        testsuite.push_attribute(("skipped", report.skipped.len().to_string().as_str()));
         testsuite.push_attribute(("time", format!("{total_time:.3}").as_str()));
 
         writer
@@ -110,6 +112,25 @@ impl JunitReporter {
             }
         }
 
+        for case in &report.skipped {
+            let mut testcase = BytesStart::new("testcase");
+            testcase.push_attribute(("name", case.eval_id.as_str()));
+            testcase.push_attribute(("classname", suite_name));
+            testcase.push_attribute(("time", "0.000"));
+            writer.write_event(Event::Start(testcase)).map_err(|e| {
+                crate::error::EvalError::IoError(std::io::Error::other(e.to_string()))
+            })?;
+
+            let mut skipped = BytesStart::new("skipped");
+            skipped.push_attribute(("message", case.reason.as_str()));
+            writer.write_event(Event::Empty(skipped)).map_err(|e| {
+                crate::error::EvalError::IoError(std::io::Error::other(e.to_string()))
+            })?;
+            writer.write_event(Event::End(BytesEnd::new("testcase"))).map_err(|e| {
+                crate::error::EvalError::IoError(std::io::Error::other(e.to_string()))
+            })?;
+        }
+
         // </testsuite>
         writer
             .write_event(Event::End(BytesEnd::new("testsuite")))
@@ -180,4 +201,17 @@ mod tests {
         assert!(xml.contains("<failure"));
         assert!(xml.contains("type=\"accuracy\""));
     }
+
+    #[test]
+    fn test_generate_with_skipped_cases() {
+        let report = make_report(vec![]).with_skipped(vec![crate::report::SkippedCase {
+            eval_id: "slow_case".to_string(),
+            tags: vec!["live".to_string()],
+            reason: "excluded tag 'live'".to_string(),
+        }]);
+        let xml = JunitReporter::generate(&report, "skip_suite").unwrap();
+        assert!(xml.contains("tests=\"1\""));
+        assert!(xml.contains("skipped=\"1\""));
+        assert!(xml.contains("<skipped message=\"excluded tag &apos;live&apos;\"/>"));
+    }
 }
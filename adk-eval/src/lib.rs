@@ -12,6 +12,7 @@
 //! - **Response Quality**: Assess final output quality with multiple metrics
 //! - **Multiple Criteria**: Ground truth, rubric-based, and LLM-judged evaluation
 //! - **Automation**: Run evaluations programmatically or via CLI
+//! - **Golden Recording**: Capture an agent's actual trajectories as a reviewable regression set
 //!
 //! ## Quick Start
 //!
@@ -49,10 +50,12 @@
 pub mod criteria;
 pub mod error;
 pub mod evaluator;
+pub mod golden;
 pub mod llm_judge;
 pub mod report;
 pub mod schema;
 pub mod scoring;
+pub mod trajectory_diff;
 
 #[cfg(feature = "personas")]
 pub mod personas;
@@ -62,6 +65,7 @@ pub mod optimizer;
 // New unconditional modules
 pub mod annotation;
 pub mod baseline;
+pub mod classification;
 pub mod conversation_scorer;
 pub mod cost_tracker;
 pub mod pricing;
@@ -85,21 +89,31 @@ pub use criteria::{
 };
 pub use error::{EvalError, Result};
 pub use evaluator::{EvaluationConfig, Evaluator};
+pub use golden::{GoldenChange, GoldenDiff, GoldenField};
 pub use llm_judge::{
     LlmJudge, LlmJudgeConfig, RubricEvaluationResult, RubricScore, SemanticMatchResult,
 };
-pub use report::{EvaluationReport, EvaluationResult, Failure, TestCaseResult};
+pub use report::{
+    EvaluationReport, EvaluationResult, Failure, ScoreSource, SkippedCase, TestCaseResult,
+};
 pub use schema::{EvalCase, EvalSet, IntermediateData, SessionInput, TestFile, ToolUse, Turn};
 pub use scoring::{ResponseScorer, ToolTrajectoryScorer};
+pub use trajectory_diff::{TrajectoryDiff, TrajectoryStep, TrajectoryStepKind, TurnTrajectoryDiff};
 
 // Optimizer re-exports
 pub use optimizer::{OptimizationResult, OptimizerConfig, PromptOptimizer};
 
 // New module re-exports
-pub use annotation::{AnnotationRecord, AnnotationStore, HumanVerdict};
+pub use annotation::{
+    AnnotationFormat, AnnotationRecord, AnnotationStore, HUMAN_REVIEW_CRITERION, HumanVerdict,
+    ReviewSelection,
+};
 pub use baseline::{Baseline, BaselineStore, Regression};
+pub use classification::{
+    ClassMetrics, ClassificationReport, ClassificationScorer, ConfusionMatrix,
+};
 pub use conversation_scorer::{ConversationMetrics, ConversationScorer, ConversationScorerConfig};
-pub use cost_tracker::{CostMetrics, CostTracker};
+pub use cost_tracker::{CostMetrics, CostTracker, ModelUsage, UsageReport};
 pub use pricing::ModelPricing;
 pub use structured_judge::{
     JudgeRubric, ScalePoint, StructuredJudge, StructuredJudgeConfig, StructuredVerdict, Verdict,
@@ -116,7 +130,7 @@ pub use embedding_scorer::EmbeddingScorer;
 pub use junit_reporter::JunitReporter;
 
 #[cfg(feature = "statistics")]
-pub use ab_comparator::AbComparator;
+pub use ab_comparator::{AbComparator, BootstrapInterval, ComparisonReport, CriterionComparison};
 
 /// Prelude for convenient imports
 pub mod prelude {
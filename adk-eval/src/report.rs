@@ -7,9 +7,12 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Duration;
 
-use crate::cost_tracker::CostMetrics;
+use crate::annotation::{AnnotationRecord, HUMAN_REVIEW_CRITERION, HumanVerdict};
+use crate::classification::{ClassificationReport, ClassificationScorer};
+use crate::cost_tracker::{CostMetrics, UsageReport};
 use crate::structured_judge::StructuredVerdict;
 use crate::trace_analyzer::TraceAnalysis;
+use crate::trajectory_diff::TrajectoryDiff;
 
 /// Complete evaluation report for a test file or eval set
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +29,15 @@ pub struct EvaluationReport {
     pub results: Vec<EvaluationResult>,
     /// Summary statistics
     pub summary: EvaluationSummary,
+    /// Classification metrics (populated when a ClassificationScorer is active)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<ClassificationReport>,
+    /// Cases not run because of tag filters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedCase>,
+    /// Token usage and estimated cost (populated when CostTracker is active)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<UsageReport>,
 }
 
 impl EvaluationReport {
@@ -38,8 +50,64 @@ impl EvaluationReport {
         let completed_at = chrono::Utc::now();
         let duration = (completed_at - started_at).to_std().unwrap_or_default();
         let summary = EvaluationSummary::from_results(&results);
+        let usage =
+            UsageReport::from_metrics(results.iter().filter_map(|r| r.cost_metrics.as_ref()));
+
+        Self {
+            run_id: run_id.to_string(),
+            started_at,
+            completed_at,
+            duration,
+            results,
+            summary,
+            classification: None,
+            skipped: Vec::new(),
+            usage,
+        }
+    }
+
+    /// Estimated cost of the run in USD, for budget gates.
+    ///
+    /// `None` when no usage was tracked or a model used has no pricing.
+    pub fn estimated_cost_usd(&self) -> Option<f64> {
+        self.usage.as_ref().and_then(|usage| usage.cost_usd)
+    }
+
+    /// Record the cases that were filtered out of this run
+    pub fn with_skipped(mut self, skipped: Vec<SkippedCase>) -> Self {
+        self.skipped = skipped;
+        self
+    }
+
+    /// Compute classification metrics from the results with the given scorer
+    pub fn with_classification(mut self, scorer: &ClassificationScorer) -> Self {
+        self.classification = Some(scorer.report(&self.results));
+        self
+    }
 
-        Self { run_id: run_id.to_string(), started_at, completed_at, duration, results, summary }
+    /// Fold human verdicts from imported annotations into the report.
+    ///
+    /// Each verdict replaces the automatic score for the record's criterion
+    /// (or [`HUMAN_REVIEW_CRITERION`] when it has none) and is tracked in
+    /// [`EvaluationResult::human_scores`]. When the record carries a threshold,
+    /// the case's pass/fail outcome for that criterion is decided by the human
+    /// score. Summary statistics are recomputed afterwards.
+    ///
+    /// Records without a verdict or without a matching result are ignored.
+    /// Returns the number of verdicts applied.
+    pub fn apply_annotations(&mut self, records: &[AnnotationRecord]) -> usize {
+        let mut applied = 0;
+        for record in records {
+            let Some(verdict) = &record.verdict else { continue };
+            let Some(result) = self.results.iter_mut().find(|r| r.eval_id == record.case_id) else {
+                continue;
+            };
+            let criterion = record.criterion.as_deref().unwrap_or(HUMAN_REVIEW_CRITERION);
+            result.apply_human_verdict(criterion, verdict.clone(), record.threshold);
+            applied += 1;
+        }
+        self.summary = EvaluationSummary::from_results(&self.results);
+        applied
     }
 
     /// Check if all tests passed
@@ -62,6 +130,9 @@ impl EvaluationReport {
         output.push_str(&format!("  Passed: {}\n", self.summary.passed));
         output.push_str(&format!("  Failed: {}\n", self.summary.failed));
         output.push_str(&format!("  Pass Rate: {:.1}%\n", self.summary.pass_rate * 100.0));
+        if !self.skipped.is_empty() {
+            output.push_str(&format!("  Skipped: {}\n", self.skipped.len()));
+        }
 
         if !self.summary.avg_scores.is_empty() {
             output.push_str("\nAverage Scores:\n");
@@ -70,6 +141,16 @@ impl EvaluationReport {
             }
         }
 
+        if let Some(usage) = &self.usage {
+            output.push('\n');
+            output.push_str(&usage.format_summary());
+        }
+
+        if let Some(classification) = &self.classification {
+            output.push('\n');
+            output.push_str(&classification.format_summary());
+        }
+
         if self.summary.failed > 0 {
             output.push_str("\nFailed Tests:\n");
             for result in self.failures() {
@@ -86,6 +167,13 @@ impl EvaluationReport {
             }
         }
 
+        if !self.skipped.is_empty() {
+            output.push_str("\nSkipped Tests:\n");
+            for case in &self.skipped {
+                output.push_str(&format!("  - {} ({})\n", case.eval_id, case.reason));
+            }
+        }
+
         output
     }
 
@@ -95,6 +183,17 @@ impl EvaluationReport {
     }
 }
 
+/// A case left out of a run by tag filters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedCase {
+    /// Test case ID
+    pub eval_id: String,
+    /// Effective tags of the case (file and case tags)
+    pub tags: Vec<String>,
+    /// Why the case was skipped
+    pub reason: String,
+}
+
 /// Summary statistics for an evaluation run
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EvaluationSummary {
@@ -160,6 +259,21 @@ pub struct EvaluationResult {
     /// Structured verdicts from the judge
     #[serde(default)]
     pub verdicts: Vec<StructuredVerdict>,
+    /// Human verdicts by criterion. The matching entries in
+    /// [`scores`](Self::scores) came from these rather than from automatic
+    /// scoring.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub human_scores: HashMap<String, HumanVerdict>,
+}
+
+/// Where a criterion score in an [`EvaluationResult`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreSource {
+    /// Computed by a scorer or LLM judge during the run.
+    Automatic,
+    /// Assigned by a human annotator.
+    Human,
 }
 
 impl EvaluationResult {
@@ -175,6 +289,7 @@ impl EvaluationResult {
             cost_metrics: None,
             trace_analysis: None,
             verdicts: vec![],
+            human_scores: HashMap::new(),
         }
     }
 
@@ -195,6 +310,7 @@ impl EvaluationResult {
             cost_metrics: None,
             trace_analysis: None,
             verdicts: vec![],
+            human_scores: HashMap::new(),
         }
     }
 
@@ -203,6 +319,53 @@ impl EvaluationResult {
         self.turn_results = turn_results;
         self
     }
+
+    /// Side-by-side diff of the expected and actual tool calls of every turn.
+    ///
+    /// Arguments are compared partially, as by the default
+    /// [`ToolTrajectoryConfig`](crate::criteria::ToolTrajectoryConfig); use
+    /// [`TrajectoryDiff::from_result`] to require exact arguments.
+    pub fn trajectory_diff(&self) -> TrajectoryDiff {
+        TrajectoryDiff::from_result(self, false)
+    }
+
+    /// Where the score for `criterion` came from, or `None` if it has no score
+    pub fn score_source(&self, criterion: &str) -> Option<ScoreSource> {
+        if self.human_scores.contains_key(criterion) {
+            Some(ScoreSource::Human)
+        } else if self.scores.contains_key(criterion) {
+            Some(ScoreSource::Automatic)
+        } else {
+            None
+        }
+    }
+
+    /// Replace the score for `criterion` with a human verdict.
+    ///
+    /// With a `threshold`, any failure recorded for the criterion is replaced
+    /// by the human outcome and [`passed`](Self::passed) is recomputed.
+    pub fn apply_human_verdict(
+        &mut self,
+        criterion: &str,
+        verdict: HumanVerdict,
+        threshold: Option<f64>,
+    ) {
+        self.scores.insert(criterion.to_string(), verdict.score);
+        if let Some(threshold) = threshold {
+            self.failures.retain(|f| f.criterion != criterion);
+            if verdict.score < threshold {
+                self.failures.push(
+                    Failure::new(criterion, Value::Null, Value::Null, verdict.score, threshold)
+                        .with_details(&format!(
+                            "human review by {}: {}",
+                            verdict.annotator_id, verdict.reasoning
+                        )),
+                );
+            }
+            self.passed = self.failures.is_empty();
+        }
+        self.human_scores.insert(criterion.to_string(), verdict);
+    }
 }
 
 /// A single failure in evaluation
@@ -20,6 +20,9 @@ pub struct TestFile {
     /// Description of what these tests cover
     #[serde(default)]
     pub description: String,
+    /// Tags applied to every case in this file, in addition to the case's own tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// List of evaluation cases
     pub eval_cases: Vec<EvalCase>,
 }
@@ -38,6 +41,17 @@ impl TestFile {
         std::fs::write(path, content)?;
         Ok(())
     }
+
+    /// Tags of `case` combined with the file-level tags
+    pub fn case_tags(&self, case: &EvalCase) -> Vec<String> {
+        let mut tags = self.tags.clone();
+        for tag in &case.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
 }
 
 /// An eval set references multiple test files
@@ -56,6 +70,9 @@ pub struct EvalSet {
     /// Inline eval cases (alternative to test_files)
     #[serde(default)]
     pub eval_cases: Vec<EvalCase>,
+    /// Set on recorded goldens until a human has checked the captured behavior
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub needs_review: bool,
 }
 
 impl EvalSet {
@@ -66,7 +83,21 @@ impl EvalSet {
         Ok(eval_set)
     }
 
+    /// Save eval set to disk
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Clear the review flag once the recorded trajectories have been checked
+    pub fn mark_reviewed(&mut self) {
+        self.needs_review = false;
+    }
+
     /// Get all eval cases, loading from test files if needed
+    ///
+    /// Cases loaded from a test file inherit that file's tags.
     pub fn get_all_cases(&self, base_path: impl AsRef<Path>) -> Result<Vec<EvalCase>> {
         let mut all_cases = self.eval_cases.clone();
 
@@ -75,7 +106,12 @@ impl EvalSet {
             let test_file = TestFile::load(&full_path).map_err(|e| {
                 EvalError::LoadError(format!("Failed to load {}: {}", test_file_path, e))
             })?;
-            all_cases.extend(test_file.eval_cases);
+            all_cases.extend(
+                test_file
+                    .eval_cases
+                    .iter()
+                    .map(|case| EvalCase { tags: test_file.case_tags(case), ..case.clone() }),
+            );
         }
 
         Ok(all_cases)
@@ -0,0 +1,418 @@
+//! Side-by-side diffs of expected and actual tool trajectories.
+//!
+//! A trajectory score says *that* a test case called the wrong tools, not
+//! *which* ones. [`TrajectoryDiff`] lines up the expected and actual calls of
+//! every turn of a [`TestCaseResult`](crate::report::TestCaseResult) and marks
+//! each step as matching, missing, extra, reordered, or called with different
+//! arguments. The diff renders as a Markdown table for PR comments and logs,
+//! or as a standalone HTML fragment for reports.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_eval::Evaluator;
+//!
+//! let report = evaluator.evaluate_file(agent, "tests/booking.test.json").await?;
+//! for result in report.failures() {
+//!     std::fs::write(
+//!         format!("target/{}.trajectory.html", result.eval_id),
+//!         result.trajectory_diff().to_html(),
+//!     )?;
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::report::EvaluationResult;
+use crate::schema::ToolUse;
+
+/// How one step of a trajectory compares to the expectation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrajectoryStepKind {
+    /// The expected call was made at its expected position.
+    Match,
+    /// The expected call was made at its expected position with different
+    /// arguments.
+    ArgsMismatch,
+    /// The expected call was never made.
+    Missing,
+    /// A call was made that was not expected.
+    Extra,
+    /// The expected call was made, but out of order.
+    Reordered,
+}
+
+impl TrajectoryStepKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::ArgsMismatch => "args differ",
+            Self::Missing => "missing",
+            Self::Extra => "extra",
+            Self::Reordered => "reordered",
+        }
+    }
+
+    fn css_class(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::ArgsMismatch => "args-mismatch",
+            Self::Missing => "missing",
+            Self::Extra => "extra",
+            Self::Reordered => "reordered",
+        }
+    }
+}
+
+impl fmt::Display for TrajectoryStepKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One row of a trajectory diff: an expected call, an actual call, or both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectoryStep {
+    /// How the calls compare
+    pub kind: TrajectoryStepKind,
+    /// Position in the expected trajectory, if the step has an expected call
+    pub expected_index: Option<usize>,
+    /// Position in the actual trajectory, if the step has an actual call
+    pub actual_index: Option<usize>,
+    /// The expected call
+    pub expected: Option<ToolUse>,
+    /// The actual call
+    pub actual: Option<ToolUse>,
+    /// Top-level argument keys whose values differ
+    #[serde(default)]
+    pub arg_mismatches: Vec<String>,
+}
+
+impl TrajectoryStep {
+    /// Returns `true` unless the step is an exact [`TrajectoryStepKind::Match`].
+    pub fn is_difference(&self) -> bool {
+        self.kind != TrajectoryStepKind::Match
+    }
+
+    /// Name of the tool called in this step.
+    pub fn tool_name(&self) -> &str {
+        self.expected.as_ref().or(self.actual.as_ref()).map(|call| call.name.as_str()).unwrap_or("")
+    }
+}
+
+/// The aligned trajectories of a single turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnTrajectoryDiff {
+    /// Turn/invocation identifier
+    pub invocation_id: String,
+    /// Steps in expected order, with extra calls placed where they were made
+    pub steps: Vec<TrajectoryStep>,
+}
+
+impl TurnTrajectoryDiff {
+    /// Align `expected` and `actual` calls.
+    ///
+    /// Calls are paired along the longest common subsequence of tool names.
+    /// An unpaired expected call whose tool was called elsewhere is reported
+    /// as reordered; the rest are missing, and unpaired actual calls are
+    /// extra. Arguments are compared like [`ToolUse::matches`]: with
+    /// `strict_args` they must be equal, otherwise the expected arguments must
+    /// be present in the actual ones.
+    pub fn compare(
+        invocation_id: impl Into<String>,
+        expected: &[ToolUse],
+        actual: &[ToolUse],
+        strict_args: bool,
+    ) -> Self {
+        let mut steps = Vec::new();
+        let mut taken = vec![false; actual.len()];
+        let aligned = align(expected, actual);
+
+        // Pair leftover calls to the same tool first, so they are reordered
+        // rather than missing and extra.
+        let mut reordered = vec![None; expected.len()];
+        for &(exp, act) in &aligned {
+            if let (Some(i), None) = (exp, act)
+                && let Some(j) = aligned.iter().find_map(|&(e, a)| match (e, a) {
+                    (None, Some(j)) if !taken[j] && actual[j].name == expected[i].name => Some(j),
+                    _ => None,
+                })
+            {
+                taken[j] = true;
+                reordered[i] = Some(j);
+            }
+        }
+
+        for (exp, act) in aligned {
+            let step = match (exp, act) {
+                (Some(i), Some(j)) => {
+                    let arg_mismatches = arg_mismatches(&expected[i], &actual[j], strict_args);
+                    let kind = if arg_mismatches.is_empty() {
+                        TrajectoryStepKind::Match
+                    } else {
+                        TrajectoryStepKind::ArgsMismatch
+                    };
+                    step(kind, Some((i, &expected[i])), Some((j, &actual[j])), arg_mismatches)
+                }
+                (Some(i), None) => match reordered[i] {
+                    Some(j) => step(
+                        TrajectoryStepKind::Reordered,
+                        Some((i, &expected[i])),
+                        Some((j, &actual[j])),
+                        arg_mismatches(&expected[i], &actual[j], strict_args),
+                    ),
+                    None => {
+                        step(TrajectoryStepKind::Missing, Some((i, &expected[i])), None, vec![])
+                    }
+                },
+                (None, Some(j)) if taken[j] => continue,
+                (None, Some(j)) => {
+                    step(TrajectoryStepKind::Extra, None, Some((j, &actual[j])), vec![])
+                }
+                (None, None) => continue,
+            };
+            steps.push(step);
+        }
+
+        Self { invocation_id: invocation_id.into(), steps }
+    }
+
+    /// Returns `true` if any step differs from the expectation.
+    pub fn has_differences(&self) -> bool {
+        self.steps.iter().any(TrajectoryStep::is_difference)
+    }
+}
+
+/// Expected versus actual tool trajectories of one test case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectoryDiff {
+    /// Test case identifier
+    pub eval_id: String,
+    /// One diff per turn, in conversation order
+    pub turns: Vec<TurnTrajectoryDiff>,
+}
+
+impl TrajectoryDiff {
+    /// Diff every turn of `result`.
+    pub fn from_result(result: &EvaluationResult, strict_args: bool) -> Self {
+        Self {
+            eval_id: result.eval_id.clone(),
+            turns: result
+                .turn_results
+                .iter()
+                .map(|turn| {
+                    TurnTrajectoryDiff::compare(
+                        &turn.invocation_id,
+                        &turn.expected_tool_calls,
+                        &turn.actual_tool_calls,
+                        strict_args,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if any turn differs from the expectation.
+    pub fn has_differences(&self) -> bool {
+        self.turns.iter().any(TurnTrajectoryDiff::has_differences)
+    }
+
+    /// Render the diff as Markdown, one table per turn.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("## Trajectory: {}\n", self.eval_id);
+        for turn in &self.turns {
+            out.push_str(&format!("\n### Turn `{}`\n\n", turn.invocation_id));
+            if turn.steps.is_empty() {
+                out.push_str("No tool calls expected or made.\n");
+                continue;
+            }
+            out.push_str("| # | Expected | # | Actual | Status |\n");
+            out.push_str("|---|----------|---|--------|--------|\n");
+            for step in &turn.steps {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    position(step.expected_index),
+                    step.expected.as_ref().map(markdown_call).unwrap_or_default(),
+                    position(step.actual_index),
+                    step.actual.as_ref().map(markdown_call).unwrap_or_default(),
+                    status(step).replace('|', "\\|"),
+                ));
+            }
+        }
+        out
+    }
+
+    /// Render the diff as a self-contained HTML fragment, one table per turn.
+    ///
+    /// Rows carry the step kind as a CSS class (`match`, `args-mismatch`,
+    /// `missing`, `extra`, `reordered`) so the default colors can be restyled.
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(HTML_STYLE);
+        out.push_str(&format!(
+            "<section class=\"trajectory-diff\">\n<h2>Trajectory: {}</h2>\n",
+            escape_html(&self.eval_id)
+        ));
+        for turn in &self.turns {
+            out.push_str(&format!(
+                "<h3>Turn <code>{}</code></h3>\n",
+                escape_html(&turn.invocation_id)
+            ));
+            out.push_str(
+                "<table>\n<tr><th>#</th><th>Expected</th><th>#</th><th>Actual</th><th>Status</th></tr>\n",
+            );
+            for step in &turn.steps {
+                out.push_str(&format!(
+                    "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    step.kind.css_class(),
+                    position(step.expected_index),
+                    step.expected.as_ref().map(html_call).unwrap_or_default(),
+                    position(step.actual_index),
+                    step.actual.as_ref().map(html_call).unwrap_or_default(),
+                    escape_html(&status(step)),
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</section>\n");
+        out
+    }
+}
+
+impl fmt::Display for TrajectoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_markdown())
+    }
+}
+
+const HTML_STYLE: &str = "<style>
+.trajectory-diff table { border-collapse: collapse; font-family: sans-serif; }
+.trajectory-diff td, .trajectory-diff th { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }
+.trajectory-diff tr.args-mismatch { background: #fff4d6; }
+.trajectory-diff tr.missing { background: #fde2e2; }
+.trajectory-diff tr.extra { background: #e2f0fd; }
+.trajectory-diff tr.reordered { background: #efe2fd; }
+</style>
+";
+
+/// Pairs of (expected, actual) indices along the longest common subsequence
+/// of tool names, in order; unpaired calls have `None` on the other side.
+fn align(expected: &[ToolUse], actual: &[ToolUse]) -> Vec<(Option<usize>, Option<usize>)> {
+    let (n, m) = (expected.len(), actual.len());
+    // lcs[i][j]: length of the LCS of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i].name == actual[j].name {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if expected[i].name == actual[j].name {
+            pairs.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            pairs.push((Some(i), None));
+            i += 1;
+        } else {
+            pairs.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    pairs.extend((i..n).map(|i| (Some(i), None)));
+    pairs.extend((j..m).map(|j| (None, Some(j))));
+    pairs
+}
+
+/// Top-level argument keys that keep `actual` from matching `expected`.
+fn arg_mismatches(expected: &ToolUse, actual: &ToolUse, strict_args: bool) -> Vec<String> {
+    if expected.matches(actual, strict_args) {
+        return Vec::new();
+    }
+    match (&expected.args, &actual.args) {
+        (Value::Object(exp), Value::Object(act)) => {
+            let mut keys: Vec<String> = exp
+                .iter()
+                .filter(|(key, value)| act.get(*key) != Some(value))
+                .map(|(key, _)| key.clone())
+                .collect();
+            if strict_args {
+                keys.extend(act.keys().filter(|key| !exp.contains_key(*key)).cloned());
+            }
+            keys.sort();
+            keys
+        }
+        _ => vec!["args".to_string()],
+    }
+}
+
+fn step(
+    kind: TrajectoryStepKind,
+    expected: Option<(usize, &ToolUse)>,
+    actual: Option<(usize, &ToolUse)>,
+    arg_mismatches: Vec<String>,
+) -> TrajectoryStep {
+    TrajectoryStep {
+        kind,
+        expected_index: expected.map(|(i, _)| i),
+        actual_index: actual.map(|(j, _)| j),
+        expected: expected.map(|(_, call)| call.clone()),
+        actual: actual.map(|(_, call)| call.clone()),
+        arg_mismatches,
+    }
+}
+
+/// One-based position for display.
+fn position(index: Option<usize>) -> String {
+    index.map(|i| (i + 1).to_string()).unwrap_or_default()
+}
+
+fn status(step: &TrajectoryStep) -> String {
+    if step.arg_mismatches.is_empty() {
+        step.kind.label().to_string()
+    } else if step.kind == TrajectoryStepKind::ArgsMismatch {
+        format!("args differ: {}", step.arg_mismatches.join(", "))
+    } else {
+        format!("{}, args differ: {}", step.kind.label(), step.arg_mismatches.join(", "))
+    }
+}
+
+fn call_text(call: &ToolUse) -> String {
+    match &call.args {
+        Value::Null => call.name.clone(),
+        Value::Object(args) if args.is_empty() => call.name.clone(),
+        args => format!("{} {args}", call.name),
+    }
+}
+
+fn markdown_call(call: &ToolUse) -> String {
+    format!("`{}`", call_text(call).replace('`', "'").replace('|', "\\|"))
+}
+
+fn html_call(call: &ToolUse) -> String {
+    format!("<code>{}</code>", escape_html(&call_text(call)))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "statistics")]
+//! Comparing two agents with bootstrap confidence intervals over repeated runs.
+
+use adk_core::{Agent, Content, Event, EventStream, InvocationContext, Part, Result};
+use adk_eval::ab_comparator::bootstrap_mean_interval;
+use adk_eval::criteria::{ResponseMatchConfig, SimilarityAlgorithm};
+use adk_eval::schema::ContentData;
+use adk_eval::{
+    AbComparator, EvalCase, EvalSet, EvaluationConfig, EvaluationCriteria, Evaluator, SessionInput,
+    Turn,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Echoes the input back, except for inputs it gets wrong.
+struct ScriptedAgent {
+    name: &'static str,
+    wrong: fn(case: usize, run: usize) -> bool,
+    cases: usize,
+    calls: AtomicUsize,
+}
+
+impl ScriptedAgent {
+    fn new(name: &'static str, cases: usize, wrong: fn(usize, usize) -> bool) -> Arc<Self> {
+        Arc::new(Self { name, wrong, cases, calls: AtomicUsize::new(0) })
+    }
+}
+
+#[async_trait]
+impl Agent for ScriptedAgent {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        "Answers from a script"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
+        let input = ctx.user_content().parts.iter().filter_map(Part::text).collect::<String>();
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        let case = input.trim_start_matches("case-").parse().unwrap();
+        let answer =
+            if (self.wrong)(case, call / self.cases) { "no idea".to_string() } else { input };
+
+        let mut event = Event::new(ctx.invocation_id());
+        event.set_content(Content::new("model").with_text(answer));
+        Ok(Box::pin(futures::stream::iter([Ok(event)])))
+    }
+}
+
+fn eval_set(cases: usize) -> EvalSet {
+    let eval_cases = (0..cases)
+        .map(|i| {
+            let text = format!("case-{i}");
+            EvalCase {
+                eval_id: text.clone(),
+                description: String::new(),
+                conversation: vec![Turn {
+                    invocation_id: text.clone(),
+                    user_content: ContentData::text(&text),
+                    final_response: Some(ContentData::model_response(&text)),
+                    intermediate_data: None,
+                }],
+                session_input: SessionInput::default(),
+                tags: Vec::new(),
+                metadata: None,
+            }
+        })
+        .collect();
+    EvalSet {
+        eval_set_id: "ab".to_string(),
+        name: "ab".to_string(),
+        description: String::new(),
+        test_files: Vec::new(),
+        eval_cases,
+        needs_review: false,
+    }
+}
+
+fn comparator() -> AbComparator {
+    let mut criteria = EvaluationCriteria::response_similarity(1.0);
+    criteria.response_match_config =
+        Some(ResponseMatchConfig { algorithm: SimilarityAlgorithm::Exact, ..Default::default() });
+    AbComparator::new(Evaluator::new(EvaluationConfig::with_criteria(criteria))).with_seed(7)
+}
+
+#[test]
+fn test_bootstrap_interval_on_known_data() {
+    // Ten wins and ten ties: the resampled mean is Binomial(20, 0.5) / 20,
+    // whose 2.5% and 97.5% quantiles are 6/20 and 14/20
+    let differences: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 1.0 } else { 0.0 }).collect();
+
+    let interval = bootstrap_mean_interval(&differences, 0.95, 10_000, 42).unwrap();
+
+    assert_eq!((interval.lower, interval.upper), (0.3, 0.7));
+    assert!(interval.excludes_zero());
+    // Only a resample of all ties (0.5^20) reaches zero
+    assert!(interval.p_value < 0.001, "p={}", interval.p_value);
+    assert_eq!(bootstrap_mean_interval(&differences, 0.95, 10_000, 42).unwrap(), interval);
+
+    let noise = [0.2, -0.1, 0.05, -0.2, 0.1, -0.05];
+    let interval = bootstrap_mean_interval(&noise, 0.95, 10_000, 42).unwrap();
+    assert!(interval.lower < 0.0 && interval.upper > 0.0);
+    assert!(!interval.excludes_zero());
+    assert!(interval.p_value > 0.5, "p={}", interval.p_value);
+
+    assert!(bootstrap_mean_interval(&[], 0.95, 10_000, 42).is_err());
+    assert!(bootstrap_mean_interval(&noise, 1.0, 10_000, 42).is_err());
+}
+
+#[tokio::test]
+async fn test_compare_agents_reports_significant_improvement() {
+    let set = eval_set(20);
+    let better = ScriptedAgent::new("better", 20, |_, _| false);
+    let worse = ScriptedAgent::new("worse", 20, |case, _| case % 2 == 1);
+
+    let report = comparator().compare_agents(better, worse, &set, 1).await.unwrap();
+
+    assert_eq!((report.agent_a_name.as_str(), report.agent_b_name.as_str()), ("better", "worse"));
+    assert_eq!((report.total_cases, report.runs), (20, 1));
+    let comparison = &report.criteria_comparisons[0];
+    assert_eq!(comparison.criterion, "response_similarity");
+    assert_eq!(
+        (comparison.agent_a_mean, comparison.agent_b_mean, comparison.delta),
+        (1.0, 0.5, 0.5)
+    );
+    assert_eq!((comparison.wins_a, comparison.wins_b, comparison.ties), (10, 0, 10));
+    assert_eq!((comparison.delta_interval.lower, comparison.delta_interval.upper), (0.3, 0.7));
+    assert_eq!(comparison.delta_interval.confidence_level, 0.95);
+    assert!(comparison.significant && comparison.delta_interval.excludes_zero());
+}
+
+#[tokio::test]
+async fn test_compare_agents_averages_noisy_runs() {
+    let set = eval_set(10);
+    // Gets the odd cases wrong on odd runs only
+    let flaky = ScriptedAgent::new("flaky", 10, |case, run| case % 2 == 1 && run % 2 == 1);
+    // Gets case 0 wrong on every run
+    let steady = ScriptedAgent::new("steady", 10, |case, _| case == 0);
+
+    let report = comparator().compare_agents(flaky.clone(), steady, &set, 4).await.unwrap();
+
+    assert_eq!(report.runs, 4);
+    assert_eq!(flaky.calls.load(Ordering::SeqCst), 40);
+    let comparison = &report.criteria_comparisons[0];
+    assert_eq!(comparison.agent_a_mean, 0.75);
+    assert_eq!(comparison.agent_b_mean, 0.9);
+    // Run means alternate 1.0 and 0.5 for the flaky agent and stay at 0.9
+    assert!((comparison.agent_a_run_stddev - (1.0f64 / 12.0).sqrt()).abs() < 1e-12);
+    assert_eq!(comparison.agent_b_run_stddev, 0.0);
+    assert!(comparison.delta_interval.lower < 0.0 && comparison.delta_interval.upper > 0.0);
+    assert!(!comparison.delta_interval.excludes_zero());
+
+    let err = comparator().compare_agents(flaky.clone(), flaky, &set, 0).await;
+    assert!(err.is_err());
+}
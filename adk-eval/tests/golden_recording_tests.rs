@@ -0,0 +1,121 @@
+//! Recording golden trajectories and evaluating against them.
+
+use adk_core::{
+    Agent, Content, Event, EventStream, FunctionResponseData, InvocationContext, Part, Result,
+};
+use adk_eval::{EvalSet, EvaluationConfig, EvaluationCriteria, Evaluator, GoldenDiff, GoldenField};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Looks up the weather for the city named in the input, then answers.
+struct WeatherAgent {
+    forecast: &'static str,
+}
+
+#[async_trait]
+impl Agent for WeatherAgent {
+    fn name(&self) -> &str {
+        "weather_agent"
+    }
+
+    fn description(&self) -> &str {
+        "Answers weather questions"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
+        let city = ctx.user_content().parts.iter().filter_map(Part::text).collect::<String>();
+        let invocation_id = ctx.invocation_id().to_string();
+
+        let mut call = Event::new(&invocation_id);
+        call.set_content(Content {
+            role: "model".to_string(),
+            parts: vec![Part::FunctionCall {
+                name: "get_weather".to_string(),
+                args: json!({ "city": city }),
+                id: Some("call-1".to_string()),
+                thought_signature: None,
+            }],
+        });
+
+        let mut result = Event::new(&invocation_id);
+        result.set_content(Content {
+            role: "function".to_string(),
+            parts: vec![Part::FunctionResponse {
+                function_response: FunctionResponseData::new(
+                    "get_weather",
+                    json!({ "forecast": self.forecast }),
+                ),
+                id: Some("call-1".to_string()),
+            }],
+        });
+
+        let mut answer = Event::new(&invocation_id);
+        answer.set_content(
+            Content::new("model").with_text(format!("It is {} in {city}.", self.forecast)),
+        );
+
+        Ok(Box::pin(futures::stream::iter([Ok(call), Ok(result), Ok(answer)])))
+    }
+}
+
+fn golden_evaluator() -> Evaluator {
+    Evaluator::new(EvaluationConfig::with_criteria(
+        EvaluationCriteria::exact_tools().with_response_similarity(1.0),
+    ))
+}
+
+#[tokio::test]
+async fn test_recorded_golden_passes_against_same_agent() {
+    let agent: Arc<dyn Agent> = Arc::new(WeatherAgent { forecast: "sunny" });
+    let evaluator = golden_evaluator();
+
+    let mut golden = evaluator.record(agent.clone(), ["Paris", "Oslo"]).await.unwrap();
+
+    assert!(golden.needs_review);
+    assert_eq!(golden.eval_set_id, "weather_agent_golden");
+    assert_eq!(golden.eval_cases.len(), 2);
+    let turn = &golden.eval_cases[0].conversation[0];
+    assert_eq!(turn.final_response.as_ref().unwrap().get_text(), "It is sunny in Paris.");
+    let tool_use = &turn.intermediate_data.as_ref().unwrap().tool_uses[0];
+    assert_eq!(tool_use.name, "get_weather");
+    assert_eq!(tool_use.args, json!({ "city": "Paris" }));
+    assert_eq!(tool_use.expected_response, Some(json!({ "forecast": "sunny" })));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("weather.golden.json");
+    golden.mark_reviewed();
+    golden.save(&path).unwrap();
+    let golden = EvalSet::load(&path).unwrap();
+    assert!(!golden.needs_review);
+
+    let report = evaluator.evaluate_eval_set(agent, &golden).await.unwrap();
+    assert!(report.all_passed(), "{}", report.format_summary());
+    assert_eq!(report.summary.passed, 2);
+}
+
+#[tokio::test]
+async fn test_golden_diff_flags_changed_behavior() {
+    let evaluator = golden_evaluator();
+    let mut golden =
+        evaluator.record(Arc::new(WeatherAgent { forecast: "sunny" }), ["Paris"]).await.unwrap();
+    golden.mark_reviewed();
+
+    let changed: Arc<dyn Agent> = Arc::new(WeatherAgent { forecast: "rainy" });
+    let report = evaluator.evaluate_eval_set(changed.clone(), &golden).await.unwrap();
+    assert!(!report.all_passed());
+
+    let recorded = evaluator.record(changed.clone(), ["Paris"]).await.unwrap();
+    let diff = GoldenDiff::between(&golden, &recorded);
+    let fields: Vec<_> = diff.changes.iter().map(|c| c.field).collect();
+    assert_eq!(fields, [GoldenField::FinalResponse, GoldenField::ToolUses]);
+
+    diff.apply(&mut golden, &recorded);
+    assert!(golden.needs_review);
+    let report = evaluator.evaluate_eval_set(changed, &golden).await.unwrap();
+    assert!(report.all_passed(), "{}", report.format_summary());
+}
@@ -0,0 +1,130 @@
+//! Running a subset of eval cases selected by tags.
+
+use adk_core::{Agent, Content, Event, EventStream, InvocationContext, Part, Result};
+use adk_eval::schema::ContentData;
+use adk_eval::{
+    EvalCase, EvalSet, EvaluationConfig, EvaluationCriteria, Evaluator, SessionInput, TestFile,
+    Turn,
+};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Echoes the input back and records every input it was run with.
+struct EchoAgent {
+    inputs: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl Agent for EchoAgent {
+    fn name(&self) -> &str {
+        "echo_agent"
+    }
+
+    fn description(&self) -> &str {
+        "Repeats the user message"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    async fn run(&self, ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
+        let input = ctx.user_content().parts.iter().filter_map(Part::text).collect::<String>();
+        self.inputs.lock().unwrap().push(input.clone());
+
+        let mut answer = Event::new(ctx.invocation_id());
+        answer.set_content(Content::new("model").with_text(input));
+        Ok(Box::pin(futures::stream::iter([Ok(answer)])))
+    }
+}
+
+fn case(eval_id: &str, tags: &[&str]) -> EvalCase {
+    EvalCase {
+        eval_id: eval_id.to_string(),
+        description: String::new(),
+        conversation: vec![Turn {
+            invocation_id: eval_id.to_string(),
+            user_content: ContentData::text(eval_id),
+            final_response: Some(ContentData::model_response(eval_id)),
+            intermediate_data: None,
+        }],
+        session_input: SessionInput::default(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        metadata: None,
+    }
+}
+
+fn setup() -> (Arc<EchoAgent>, Evaluator, EvalSet) {
+    let agent = Arc::new(EchoAgent { inputs: Mutex::new(Vec::new()) });
+    let evaluator = Evaluator::new(EvaluationConfig::with_criteria(
+        EvaluationCriteria::default().with_response_similarity(1.0),
+    ));
+    let set = EvalSet {
+        eval_set_id: "suite".to_string(),
+        name: "suite".to_string(),
+        description: String::new(),
+        test_files: Vec::new(),
+        eval_cases: vec![
+            case("greeting", &["smoke"]),
+            case("refund_flow", &["regression"]),
+            case("live_search", &["smoke", "live"]),
+            case("untagged", &[]),
+        ],
+        needs_review: false,
+    };
+    (agent, evaluator, set)
+}
+
+#[tokio::test]
+async fn test_include_and_exclude_select_subset() {
+    let (agent, evaluator, set) = setup();
+
+    let report =
+        evaluator.evaluate_set_filtered(agent.clone(), &set, &["smoke"], &["live"]).await.unwrap();
+
+    assert_eq!(*agent.inputs.lock().unwrap(), ["greeting"]);
+    let ran: Vec<_> = report.results.iter().map(|r| r.eval_id.as_str()).collect();
+    assert_eq!(ran, ["greeting"]);
+    assert!(report.all_passed());
+
+    let skipped: Vec<_> =
+        report.skipped.iter().map(|s| (s.eval_id.as_str(), s.reason.as_str())).collect();
+    assert_eq!(
+        skipped,
+        [
+            ("refund_flow", "no tag in [smoke]"),
+            ("live_search", "excluded tag 'live'"),
+            ("untagged", "no tag in [smoke]"),
+        ]
+    );
+    assert!(report.format_summary().contains("Skipped: 3"));
+}
+
+#[tokio::test]
+async fn test_unfiltered_run_evaluates_everything() {
+    let (agent, evaluator, set) = setup();
+
+    let report = evaluator.evaluate_eval_set(agent.clone(), &set).await.unwrap();
+
+    assert_eq!(agent.inputs.lock().unwrap().len(), 4);
+    assert!(report.skipped.is_empty());
+}
+
+#[tokio::test]
+async fn test_file_tags_apply_to_every_case() {
+    let (agent, evaluator, set) = setup();
+    let file = TestFile {
+        eval_set_id: "live_file".to_string(),
+        name: "live cases".to_string(),
+        description: String::new(),
+        tags: vec!["live".to_string()],
+        eval_cases: set.eval_cases[..2].to_vec(),
+    };
+
+    let report =
+        evaluator.evaluate_test_file_filtered(agent.clone(), &file, &[], &["live"]).await.unwrap();
+
+    assert!(agent.inputs.lock().unwrap().is_empty());
+    assert_eq!(report.skipped.len(), 2);
+    assert_eq!(report.skipped[1].tags, ["live", "regression"]);
+}
@@ -0,0 +1,104 @@
+//! Expected versus actual trajectory diffs of test case results.
+
+use adk_eval::report::TurnResult;
+use adk_eval::{TestCaseResult, ToolUse, TrajectoryStepKind, TurnTrajectoryDiff};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn turn(expected: Vec<ToolUse>, actual: Vec<ToolUse>) -> TurnResult {
+    TurnResult {
+        invocation_id: "turn-1".to_string(),
+        actual_response: None,
+        expected_response: None,
+        actual_tool_calls: actual,
+        expected_tool_calls: expected,
+        scores: HashMap::new(),
+    }
+}
+
+fn kinds(diff: &TurnTrajectoryDiff) -> Vec<(TrajectoryStepKind, &str)> {
+    diff.steps.iter().map(|step| (step.kind, step.tool_name())).collect()
+}
+
+#[test]
+fn test_diff_marks_extra_and_missing_calls() {
+    let result = TestCaseResult::failed("book_flight", HashMap::new(), vec![], Duration::ZERO)
+        .with_turn_results(vec![turn(
+            vec![
+                ToolUse::new("search_flights").with_args(json!({ "to": "NRT" })),
+                ToolUse::new("book_flight").with_args(json!({ "flight": "JL5" })),
+                ToolUse::new("send_confirmation"),
+            ],
+            vec![
+                ToolUse::new("search_flights").with_args(json!({ "to": "NRT", "date": "5/1" })),
+                ToolUse::new("check_weather").with_args(json!({ "city": "Tokyo" })),
+                ToolUse::new("book_flight").with_args(json!({ "flight": "JL5" })),
+            ],
+        )]);
+
+    let diff = result.trajectory_diff();
+    assert!(diff.has_differences());
+    let steps = &diff.turns[0];
+    assert_eq!(
+        kinds(steps),
+        [
+            // Extra actual arguments are fine with partial matching.
+            (TrajectoryStepKind::Match, "search_flights"),
+            (TrajectoryStepKind::Extra, "check_weather"),
+            (TrajectoryStepKind::Match, "book_flight"),
+            (TrajectoryStepKind::Missing, "send_confirmation"),
+        ]
+    );
+    assert_eq!(steps.steps[1].actual_index, Some(1));
+    assert_eq!(steps.steps[1].expected_index, None);
+    assert_eq!(steps.steps[3].expected_index, Some(2));
+    assert_eq!(steps.steps[3].actual_index, None);
+
+    let markdown = diff.to_markdown();
+    assert!(
+        markdown.contains("|  |  | 2 | `check_weather {\"city\":\"Tokyo\"}` | extra |"),
+        "{markdown}"
+    );
+    assert!(markdown.contains("| 3 | `send_confirmation` |  |  | missing |"), "{markdown}");
+
+    let html = diff.to_html();
+    assert!(html.contains("<tr class=\"extra\"><td></td><td></td><td>2</td>"), "{html}");
+    assert!(html.contains("<tr class=\"missing\"><td>3</td><td><code>send_confirmation</code>"));
+    assert!(html.contains("{&quot;city&quot;:&quot;Tokyo&quot;}"));
+}
+
+#[test]
+fn test_diff_marks_reordered_calls_and_argument_mismatches() {
+    let expected = [
+        ToolUse::new("lookup_user").with_args(json!({ "id": 7 })),
+        ToolUse::new("fetch_orders"),
+        ToolUse::new("refund").with_args(json!({ "order": "A1", "amount": 20 })),
+    ];
+    let actual = [
+        ToolUse::new("fetch_orders"),
+        ToolUse::new("lookup_user").with_args(json!({ "id": 7 })),
+        ToolUse::new("refund").with_args(json!({ "order": "A1", "amount": 25 })),
+    ];
+
+    let diff = TurnTrajectoryDiff::compare("turn-1", &expected, &actual, false);
+    assert_eq!(
+        kinds(&diff),
+        [
+            (TrajectoryStepKind::Reordered, "lookup_user"),
+            (TrajectoryStepKind::Match, "fetch_orders"),
+            (TrajectoryStepKind::ArgsMismatch, "refund"),
+        ]
+    );
+    assert_eq!((diff.steps[0].expected_index, diff.steps[0].actual_index), (Some(0), Some(1)));
+    assert_eq!(diff.steps[2].arg_mismatches, ["amount"]);
+
+    // Strict arguments also flag keys the expectation does not mention.
+    let actual = [ToolUse::new("fetch_orders").with_args(json!({ "limit": 10 }))];
+    let diff = TurnTrajectoryDiff::compare("turn-1", &expected[1..2], &actual, true);
+    assert_eq!(diff.steps[0].kind, TrajectoryStepKind::ArgsMismatch);
+    assert_eq!(diff.steps[0].arg_mismatches, ["limit"]);
+
+    let same = TurnTrajectoryDiff::compare("turn-1", &expected, &expected, true);
+    assert!(!same.has_differences());
+}
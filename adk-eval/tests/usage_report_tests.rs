@@ -0,0 +1,101 @@
+//! Token usage and cost aggregated across the cases of an evaluation report.
+
+use adk_core::{Event, LlmRequest, UsageMetadata};
+use adk_eval::{CostTracker, EvaluationReport, EvaluationResult, ModelPricing};
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn response(model: Option<&str>, prompt: i32, completion: i32) -> Event {
+    let mut event = Event::new("inv-1");
+    event.llm_request =
+        model.map(|model| serde_json::to_string(&LlmRequest::new(model, vec![])).unwrap());
+    event.llm_response.usage_metadata = Some(UsageMetadata {
+        prompt_token_count: prompt,
+        candidates_token_count: completion,
+        total_token_count: prompt + completion,
+        ..Default::default()
+    });
+    event
+}
+
+fn case(tracker: &CostTracker, eval_id: &str, events: &[Event]) -> EvaluationResult {
+    let mut result = EvaluationResult::passed(eval_id, HashMap::new(), Duration::ZERO);
+    result.cost_metrics = Some(tracker.extract_metrics(events, Duration::from_millis(10)));
+    result
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+}
+
+#[test]
+fn test_report_aggregates_usage_and_cost_per_model() {
+    let tracker = CostTracker::with_pricing(vec![
+        ModelPricing::new("fast-model", 0.001, 0.002),
+        ModelPricing::new("judge-model", 0.01, 0.03),
+    ]);
+    let results = vec![
+        case(
+            &tracker,
+            "refund",
+            &[response(Some("fast-model"), 1000, 200), response(Some("judge-model"), 500, 100)],
+        ),
+        case(&tracker, "greeting", &[response(Some("fast-model"), 2000, 300)]),
+    ];
+
+    let report = EvaluationReport::new("run-1", results, chrono::Utc::now());
+    let usage = report.usage.as_ref().unwrap();
+    assert_eq!(usage.cases, 2);
+    assert_eq!(usage.prompt_tokens, 3500);
+    assert_eq!(usage.completion_tokens, 600);
+    assert_eq!(usage.total_tokens, 4100);
+    assert_close(usage.avg_tokens_per_case, 2050.0);
+
+    let fast = &usage.models["fast-model"];
+    assert_eq!((fast.responses, fast.prompt_tokens, fast.completion_tokens), (2, 3000, 500));
+    // 3000/1000 * 0.001 + 500/1000 * 0.002
+    assert_close(fast.cost_usd.unwrap(), 0.004);
+    let judge = &usage.models["judge-model"];
+    assert_eq!(judge.responses, 1);
+    // 500/1000 * 0.01 + 100/1000 * 0.03
+    assert_close(judge.cost_usd.unwrap(), 0.008);
+
+    assert_close(report.estimated_cost_usd().unwrap(), 0.012);
+    assert!(usage.within_budget(0.02));
+    assert!(!usage.within_budget(0.01));
+
+    let summary = report.format_summary();
+    assert!(summary.contains("Tokens: 4100 (3500 prompt, 600 completion)"), "{summary}");
+    assert!(summary.contains("Avg Tokens/Case: 2050.0"), "{summary}");
+    assert!(summary.contains("Estimated Cost: $0.0120"), "{summary}");
+    assert!(summary.contains("fast-model: 3500 tokens, $0.0040"), "{summary}");
+
+    let json = report.to_json().unwrap();
+    let parsed: EvaluationReport = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.usage.as_ref(), Some(usage));
+}
+
+#[test]
+fn test_unpriced_usage_leaves_the_cost_unknown() {
+    let tracker = CostTracker::with_pricing(vec![ModelPricing::new("fast-model", 0.001, 0.002)]);
+    let results = vec![case(
+        &tracker,
+        "mixed",
+        &[response(Some("fast-model"), 1000, 0), response(None, 400, 100)],
+    )];
+
+    let report = EvaluationReport::new("run-2", results, chrono::Utc::now());
+    let usage = report.usage.as_ref().unwrap();
+    assert_eq!(usage.total_tokens, 1500);
+    assert_eq!(usage.models["unknown"].cost_usd, None);
+    assert_close(usage.models["fast-model"].cost_usd.unwrap(), 0.001);
+    assert_eq!(report.estimated_cost_usd(), None);
+    assert!(!usage.within_budget(100.0));
+    assert!(report.format_summary().contains("Estimated Cost: unknown (no pricing)"));
+
+    // Without a cost tracker there is nothing to report.
+    let untracked = EvaluationResult::passed("plain", HashMap::new(), Duration::ZERO);
+    let report = EvaluationReport::new("run-3", vec![untracked], chrono::Utc::now());
+    assert!(report.usage.is_none());
+    assert!(!report.format_summary().contains("Usage:"));
+}
@@ -24,13 +24,17 @@ use crate::{
         EmbedContentRequest,
     },
     files::model::{File, ListFilesResponse},
-    generation::{GenerateContentRequest, GenerationResponse},
+    generation::{CountTokensResponse, GenerateContentRequest, GenerationResponse},
     model_info::{ListModelsResponse, ModelInfo},
+    models::Content,
+    tools::Tool,
 };
 use async_trait::async_trait;
 use futures::Stream;
 use mime::Mime;
+use serde::Serialize;
 use std::pin::Pin;
+use std::time::Duration;
 
 /// A boxed, pinned stream of results — the common return type for streaming operations.
 pub type BackendStream<T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + Send>>;
@@ -62,6 +66,16 @@ pub trait GeminiBackend: Send + Sync + std::fmt::Debug {
         request: EmbedContentRequest,
     ) -> Result<ContentEmbeddingResponse, Error>;
 
+    // ── Token counting ──────────────────────────────────────────────────
+
+    /// Count the prompt tokens of a generation request without running it.
+    async fn count_tokens(
+        &self,
+        _request: GenerateContentRequest,
+    ) -> Result<CountTokensResponse, Error> {
+        Err(Error::GoogleCloudUnsupported { operation: "countTokens" })
+    }
+
     // ── Batch embeddings ────────────────────────────────────────────────
 
     /// Embed multiple contents in a single request.
@@ -281,3 +295,89 @@ pub trait GeminiBackend: Send + Sync + std::fmt::Debug {
         Err(Error::GoogleCloudUnsupported { operation: "downloadEnvironment" })
     }
 }
+
+/// Body of an AI Studio `countTokens` call: the whole request, with its
+/// model, under `generateContentRequest`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StudioCountTokensBody<'a> {
+    generate_content_request: ModelRequest<'a>,
+}
+
+#[derive(Serialize)]
+struct ModelRequest<'a> {
+    model: String,
+    #[serde(flatten)]
+    request: &'a GenerateContentRequest,
+}
+
+impl<'a> StudioCountTokensBody<'a> {
+    pub(crate) fn new(model: String, request: &'a GenerateContentRequest) -> Self {
+        Self { generate_content_request: ModelRequest { model, request } }
+    }
+}
+
+/// Body of a Vertex AI `countTokens` call, which takes the prompt fields at
+/// the top level and rejects the rest of a generation request.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VertexCountTokensBody<'a> {
+    contents: &'a [Content],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<&'a Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<&'a [Tool]>,
+}
+
+impl<'a> VertexCountTokensBody<'a> {
+    pub(crate) fn new(request: &'a GenerateContentRequest) -> Self {
+        Self {
+            contents: &request.contents,
+            system_instruction: request.system_instruction.as_ref(),
+            tools: request.tools.as_deref(),
+        }
+    }
+}
+
+/// Reads how long the server asked callers to wait before retrying a failed
+/// request: the `Retry-After` header in seconds, or else the `retryDelay` of a
+/// `google.rpc.RetryInfo` detail in the error body (e.g. `"35s"`).
+pub(crate) fn retry_after(
+    headers: &reqwest::header::HeaderMap,
+    body: Option<&str>,
+) -> Option<Duration> {
+    let header = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    header.or_else(|| {
+        let body: serde_json::Value = serde_json::from_str(body?).ok()?;
+        body["error"]["details"].as_array()?.iter().find_map(|detail| {
+            let delay = detail.get("retryDelay")?.as_str()?.strip_suffix('s')?;
+            Duration::try_from_secs_f64(delay.parse().ok()?).ok()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    #[test]
+    fn retry_after_prefers_the_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers, None), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn retry_after_reads_retry_info_from_the_body() {
+        let body = r#"{"error":{"code":429,"status":"RESOURCE_EXHAUSTED","details":[
+            {"@type":"type.googleapis.com/google.rpc.QuotaFailure","violations":[]},
+            {"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"35.5s"}]}}"#;
+        assert_eq!(retry_after(&HeaderMap::new(), Some(body)), Some(Duration::from_millis(35_500)));
+        assert_eq!(retry_after(&HeaderMap::new(), Some("not json")), None);
+    }
+}
@@ -3,7 +3,7 @@
 //! This backend communicates with `generativelanguage.googleapis.com` using
 //! API-key authentication and standard REST/SSE endpoints.
 
-use super::{BackendStream, GeminiBackend};
+use super::{BackendStream, GeminiBackend, StudioCountTokensBody, VertexCountTokensBody};
 use crate::{
     batch::model::{
         BatchGenerateContentRequest, BatchGenerateContentResponse, ListBatchesResponse,
@@ -22,7 +22,7 @@ use crate::{
         EmbedContentRequest,
     },
     files::model::{File, ListFilesResponse},
-    generation::{GenerateContentRequest, GenerationResponse},
+    generation::{CountTokensResponse, GenerateContentRequest, GenerationResponse},
 };
 use async_trait::async_trait;
 use eventsource_stream::Eventsource;
@@ -138,8 +138,10 @@ impl StudioBackend {
     async fn check_response(response: Response) -> Result<Response, Error> {
         let status = response.status();
         if !status.is_success() {
+            let headers = response.headers().clone();
             let description = response.text().await.ok();
-            BadResponseSnafu { code: status.as_u16(), description }.fail()
+            let retry_after = super::retry_after(&headers, description.as_deref());
+            BadResponseSnafu { code: status.as_u16(), description, retry_after }.fail()
         } else {
             Ok(response)
         }
@@ -194,6 +196,7 @@ impl StudioBackend {
                 v.to_str().map(str::to_string).map_err(|_| Error::BadResponse {
                     code: 500,
                     description: Some("Missing upload URL in response".to_string()),
+                    retry_after: None,
                 })
             })
             .and_then(|url| Url::parse(&url).context(UrlParseSnafu))
@@ -253,6 +256,20 @@ impl GeminiBackend for StudioBackend {
         self.post_json(url, &request).await
     }
 
+    // ── Token counting ──────────────────────────────────────────────────
+
+    async fn count_tokens(
+        &self,
+        request: GenerateContentRequest,
+    ) -> Result<CountTokensResponse, Error> {
+        let url = self.build_url("countTokens")?;
+        if self.is_vertex_url {
+            return self.post_json(url, &VertexCountTokensBody::new(&request)).await;
+        }
+        let body = StudioCountTokensBody::new(self.model.to_string(), &request);
+        self.post_json(url, &body).await
+    }
+
     // ── Batch embeddings ────────────────────────────────────────────────
 
     async fn batch_embed_contents(
@@ -9,7 +9,7 @@
 //! Streaming support inspired by [PR #74](https://github.com/zavora-ai/adk-rust/pull/74)
 //! by @mikefaille.
 
-use super::{BackendStream, GeminiBackend};
+use super::{BackendStream, GeminiBackend, VertexCountTokensBody};
 use crate::{
     client::{
         BadResponseSnafu, DecodeResponseSnafu, DeserializeSnafu, Error,
@@ -19,7 +19,7 @@ use crate::{
         GoogleCloudResponseSerializeSnafu, Model, UrlParseSnafu,
     },
     embedding::{ContentEmbeddingResponse, EmbedContentRequest},
-    generation::{GenerateContentRequest, GenerationResponse},
+    generation::{CountTokensResponse, GenerateContentRequest, GenerationResponse},
 };
 use async_trait::async_trait;
 use eventsource_stream::Eventsource;
@@ -69,8 +69,10 @@ impl VertexBackend {
     async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, Error> {
         let status = response.status();
         if !status.is_success() {
+            let headers = response.headers().clone();
             let description = response.text().await.ok();
-            BadResponseSnafu { code: status.as_u16(), description }.fail()
+            let retry_after = super::retry_after(&headers, description.as_deref());
+            BadResponseSnafu { code: status.as_u16(), description, retry_after }.fail()
         } else {
             Ok(response)
         }
@@ -266,4 +268,28 @@ impl GeminiBackend for VertexBackend {
             serde_json::to_value(&vertex_resp).context(GoogleCloudResponseSerializeSnafu)?;
         serde_json::from_value(value).context(GoogleCloudResponseDeserializeSnafu)
     }
+
+    async fn count_tokens(
+        &self,
+        request: GenerateContentRequest,
+    ) -> Result<CountTokensResponse, Error> {
+        let url = Url::parse(&format!(
+            "{}/v1/{}:countTokens",
+            self.endpoint.trim_end_matches('/'),
+            self.model
+        ))
+        .context(UrlParseSnafu)?;
+
+        let auth_headers = self.auth_headers().await?;
+
+        let response = Client::new()
+            .post(url.clone())
+            .headers(auth_headers)
+            .json(&VertexCountTokensBody::new(&request))
+            .send()
+            .await
+            .map_err(|source| Error::PerformRequest { source, url })?;
+        let response = Self::check_response(response).await?;
+        response.json().await.context(DecodeResponseSnafu)
+    }
 }
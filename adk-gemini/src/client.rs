@@ -10,7 +10,7 @@ use crate::{
         handle::FileHandle,
         model::{File, ListFilesResponse},
     },
-    generation::{ContentBuilder, GenerateContentRequest, GenerationResponse},
+    generation::{ContentBuilder, CountTokensResponse, GenerateContentRequest, GenerationResponse},
 };
 use eventsource_stream::EventStreamError;
 use futures::Stream;
@@ -310,6 +310,9 @@ pub enum Error {
         code: u16,
         /// An optional error description from the server.
         description: Option<String>,
+        /// How long the server asked callers to wait before retrying, from the
+        /// `Retry-After` header or the body's `RetryInfo` detail.
+        retry_after: Option<std::time::Duration>,
     },
 
     /// A required response header was missing.
@@ -570,6 +573,19 @@ impl GeminiClient {
         self.backend.generate_content_stream(request).await
     }
 
+    #[instrument(skip_all, fields(
+        model,
+        messages.parts.count = request.contents.len(),
+        tools.present = request.tools.is_some(),
+        system.instruction.present = request.system_instruction.is_some(),
+    ), err)]
+    pub(crate) async fn count_tokens(
+        &self,
+        request: GenerateContentRequest,
+    ) -> Result<CountTokensResponse, Error> {
+        self.backend.count_tokens(request).await
+    }
+
     #[instrument(skip_all, fields(
         model,
         task.type = request.task_type.as_ref().map(|t| format!("{:?}", t)),
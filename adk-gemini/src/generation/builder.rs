@@ -3,8 +3,8 @@ use std::sync::Arc;
 use tracing::instrument;
 
 use crate::{
-    Blob, Content, FunctionCallingMode, FunctionDeclaration, GenerationConfig, GenerationResponse,
-    Message, Role, Tool,
+    Blob, Content, CountTokensResponse, FunctionCallingMode, FunctionDeclaration, GenerationConfig,
+    GenerationResponse, Message, Role, Tool,
     cache::CachedContentHandle,
     client::{Error as ClientError, GeminiClient},
     generation::model::ThinkingLevel,
@@ -220,6 +220,14 @@ impl ContentBuilder {
         self
     }
 
+    /// Requests a JSON response without a schema.
+    ///
+    /// Shorthand for `with_response_mime_type("application/json")`, for models
+    /// that support JSON mode but not [`with_response_schema`](Self::with_response_schema).
+    pub fn json_output(self) -> Self {
+        self.with_response_mime_type("application/json")
+    }
+
     /// Sets the response schema for structured output.
     ///
     /// When used with a JSON MIME type, this schema will be used to validate the model's
@@ -394,6 +402,16 @@ impl ContentBuilder {
         }
     }
 
+    /// Counts the prompt tokens of the request without generating content.
+    ///
+    /// The count covers the contents, system instruction, and tools, as
+    /// tokenized by the model.
+    pub async fn count_tokens(self) -> Result<CountTokensResponse, ClientError> {
+        let client = self.client.clone();
+        let request = self.build();
+        client.count_tokens(request).await
+    }
+
     /// Executes the content generation request.
     #[instrument(skip_all, fields(
         messages.parts.count = self.contents.len(),
@@ -576,6 +576,18 @@ impl GenerateContentRequest {
     }
 }
 
+/// Response from the `countTokens` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CountTokensResponse {
+    /// The number of tokens the request's prompt is tokenized into
+    #[serde(default)]
+    pub total_tokens: i32,
+    /// The number of tokens in the cached part of the prompt, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_content_token_count: Option<i32>,
+}
+
 /// Native thinking level for Gemini 3 models.
 ///
 /// Controls the amount of reasoning effort the model applies. This is the
@@ -784,6 +796,8 @@ impl GenerationConfig {
     /// - `top_p`: must be between 0.0 and 1.0
     /// - `top_k`: must be positive
     /// - `max_output_tokens`: must be positive
+    /// - `response_schema`: requires `response_mime_type` to be unset,
+    ///   `application/json`, or `text/x.enum`
     ///
     /// If `thinking_config` is present, delegates to [`ThinkingConfig::validate`] as well.
     /// All `None` fields are accepted without error.
@@ -808,6 +822,15 @@ impl GenerationConfig {
         {
             return Err("max_output_tokens must be positive".to_string());
         }
+        if self.response_schema.is_some()
+            && let Some(ref mime) = self.response_mime_type
+            && mime != "application/json"
+            && mime != "text/x.enum"
+        {
+            return Err(format!(
+                "response_schema requires response_mime_type application/json or text/x.enum, got {mime}"
+            ));
+        }
         if let Some(ref tc) = self.thinking_config {
             tc.validate()?;
         }
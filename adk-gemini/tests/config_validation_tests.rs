@@ -164,3 +164,29 @@ proptest! {
         prop_assert!(config.validate().is_ok(), "expected Ok when all fields are None");
     }
 }
+
+// ---------------------------------------------------------------------------
+// Response MIME type / schema compatibility
+// ---------------------------------------------------------------------------
+
+#[test]
+fn generation_config_rejects_schema_with_non_json_mime_type() {
+    let schema = serde_json::json!({ "type": "object" });
+    let config = |mime: Option<&str>| GenerationConfig {
+        response_schema: Some(schema.clone()),
+        response_mime_type: mime.map(str::to_string),
+        ..Default::default()
+    };
+
+    assert!(config(Some("text/plain")).validate().is_err());
+    assert!(config(Some("application/json")).validate().is_ok());
+    assert!(config(Some("text/x.enum")).validate().is_ok());
+    assert!(config(None).validate().is_ok());
+
+    // JSON mode without a schema is always valid.
+    let json_mode = GenerationConfig {
+        response_mime_type: Some("application/json".to_string()),
+        ..Default::default()
+    };
+    assert!(json_mode.validate().is_ok());
+}
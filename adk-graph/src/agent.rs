@@ -6,9 +6,12 @@ use crate::checkpoint::Checkpointer;
 use crate::deferred::DeferredNodeConfig;
 use crate::edge::{END, Edge, EdgeTarget, START};
 use crate::error::{GraphError, Result};
+use crate::error_policy::OnError;
 use crate::graph::{CompiledGraph, StateGraph};
+use crate::interrupt::ConditionalInterrupt;
+use crate::map::MapNode;
 use crate::node::{ExecutionConfig, FunctionNode, Node, NodeContext, NodeOutput};
-use crate::state::{State, StateSchema};
+use crate::state::{Reducer, State, StateSchema};
 use crate::stream::{StreamEvent, StreamMode};
 use crate::timeout::TimeoutPolicy;
 use adk_core::{Agent, Content, Event, EventStream, InvocationContext};
@@ -236,6 +239,7 @@ pub struct GraphAgentBuilder {
     checkpointer: Option<Arc<dyn Checkpointer>>,
     interrupt_before: Vec<String>,
     interrupt_after: Vec<String>,
+    interrupt_conditions: Vec<ConditionalInterrupt>,
     recursion_limit: usize,
     input_mapper: Option<InputMapper>,
     output_mapper: Option<OutputMapper>,
@@ -243,7 +247,9 @@ pub struct GraphAgentBuilder {
     after_callback: Option<AfterAgentCallback>,
     timeout_policies: HashMap<String, TimeoutPolicy>,
     default_timeout: Option<TimeoutPolicy>,
+    error_policies: HashMap<String, OnError>,
     deferred_configs: HashMap<String, DeferredNodeConfig>,
+    map_nodes: HashMap<String, Arc<MapNode>>,
     #[cfg(feature = "node-cache")]
     cache_policies: HashMap<String, crate::cache::NodeCachePolicy>,
 }
@@ -260,6 +266,7 @@ impl GraphAgentBuilder {
             checkpointer: None,
             interrupt_before: vec![],
             interrupt_after: vec![],
+            interrupt_conditions: vec![],
             recursion_limit: 50,
             input_mapper: None,
             output_mapper: None,
@@ -267,7 +274,9 @@ impl GraphAgentBuilder {
             after_callback: None,
             timeout_policies: HashMap::new(),
             default_timeout: None,
+            error_policies: HashMap::new(),
             deferred_configs: HashMap::new(),
+            map_nodes: HashMap::new(),
             #[cfg(feature = "node-cache")]
             cache_policies: HashMap::new(),
         }
@@ -307,6 +316,23 @@ impl GraphAgentBuilder {
         self
     }
 
+    /// Add a map node that runs `worker` once per item of the list in the
+    /// `items_key` channel and folds the workers' updates with `reducer`.
+    ///
+    /// See [`StateGraph::map_node`].
+    pub fn map_node<N: Node + 'static>(
+        mut self,
+        name: &str,
+        items_key: &str,
+        worker: N,
+        reducer: Reducer,
+    ) -> Self {
+        let map = Arc::new(MapNode::new(name, items_key, worker, reducer));
+        self.nodes.push(map.clone());
+        self.map_nodes.insert(name.to_string(), map);
+        self
+    }
+
     /// Add a direct edge
     pub fn edge(mut self, source: &str, target: &str) -> Self {
         let target =
@@ -384,6 +410,43 @@ impl GraphAgentBuilder {
         self
     }
 
+    /// Interrupt before `node` whenever `predicate` holds for the state at
+    /// that point.
+    ///
+    /// The predicate is evaluated each time the node is about to run, so a
+    /// resumed run pauses again unless the state has changed, for example by
+    /// recording an approval the predicate checks. The resulting
+    /// [`InterruptedExecution::predicate`](crate::InterruptedExecution::predicate)
+    /// is the index of the triggering predicate among all conditional
+    /// interrupts, in registration order.
+    pub fn interrupt_before_if<F>(mut self, node: &str, predicate: F) -> Self
+    where
+        F: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.interrupt_conditions.push(ConditionalInterrupt {
+            node: node.to_string(),
+            before: true,
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Interrupt after `node` whenever `predicate` holds for the state
+    /// including the node's updates.
+    ///
+    /// See [`interrupt_before_if`](Self::interrupt_before_if).
+    pub fn interrupt_after_if<F>(mut self, node: &str, predicate: F) -> Self
+    where
+        F: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.interrupt_conditions.push(ConditionalInterrupt {
+            node: node.to_string(),
+            before: false,
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
     /// Set recursion limit
     pub fn recursion_limit(mut self, limit: usize) -> Self {
         self.recursion_limit = limit;
@@ -438,6 +501,29 @@ impl GraphAgentBuilder {
         self
     }
 
+    /// Set an error handling policy for a specific node.
+    ///
+    /// When the node fails, the executor retries it, routes to a fallback node,
+    /// or propagates the error according to the policy. Timeouts that exhaust
+    /// the node's [`TimeoutPolicy`] are handled the same way.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use adk_graph::error_policy::{Backoff, OnError};
+    ///
+    /// let agent = GraphAgent::builder("my_graph")
+    ///     .node_on_error("flaky_api", OnError::retry(3, Backoff::Fixed(Duration::from_millis(200))))
+    ///     .node_on_error("primary", OnError::fallback("secondary"))
+    ///     // ...
+    ///     .build()?;
+    /// ```
+    pub fn node_on_error(mut self, node_name: &str, policy: OnError) -> Self {
+        self.error_policies.insert(node_name.to_string(), policy);
+        self
+    }
+
     /// Add a deferred (fan-in barrier) node to the graph.
     ///
     /// A deferred node waits for all upstream parallel paths to complete before
@@ -616,6 +702,7 @@ impl GraphAgentBuilder {
 
         // Add edges
         graph.edges = self.edges;
+        graph.map_nodes = self.map_nodes;
 
         // Compile
         let mut compiled = graph.compile()?;
@@ -626,9 +713,11 @@ impl GraphAgentBuilder {
         }
         compiled.interrupt_before = self.interrupt_before.into_iter().collect();
         compiled.interrupt_after = self.interrupt_after.into_iter().collect();
+        compiled.interrupt_conditions = self.interrupt_conditions;
         compiled.recursion_limit = self.recursion_limit;
         compiled.timeout_policies = self.timeout_policies;
         compiled.default_timeout = self.default_timeout;
+        compiled.error_policies = self.error_policies;
         compiled.deferred_configs = self.deferred_configs;
 
         #[cfg(feature = "node-cache")]
@@ -123,7 +123,9 @@ pub struct NodeCachePolicy {
 /// Computes a deterministic cache key from a node name and its input state.
 ///
 /// The key is the hex-encoded blake3 hash of the node name concatenated with
-/// the canonical (sorted-key) JSON serialization of the input state.
+/// the canonical JSON serialization of the input state (see
+/// [`adk_core::canonical_json`]), so nested key order and number formatting
+/// do not cause misses.
 ///
 /// # Arguments
 ///
@@ -150,18 +152,11 @@ pub struct NodeCachePolicy {
 /// assert_eq!(key, key2);
 /// ```
 pub fn compute_cache_key(node_name: &str, input_state: &State) -> String {
-    // Sort keys for deterministic serialization
-    let mut sorted_keys: Vec<&String> = input_state.keys().collect();
-    sorted_keys.sort();
-
-    let mut canonical = serde_json::Map::new();
-    for key in sorted_keys {
-        if let Some(value) = input_state.get(key) {
-            canonical.insert(key.clone(), value.clone());
-        }
-    }
-
-    let state_json = serde_json::to_string(&canonical).unwrap_or_default();
+    // Canonical form: keys sorted at every depth and numbers normalized
+    let state = serde_json::Value::Object(
+        input_state.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+    );
+    let state_json = adk_core::canonical_json(&state);
     let input = format!("{node_name}{state_json}");
     let hash = blake3::hash(input.as_bytes());
     hash.to_hex().to_string()
@@ -90,6 +90,10 @@ pub struct InterruptedExecution {
     pub state: crate::state::State,
     /// Step number when interrupted
     pub step: usize,
+    /// Index of the `interrupt_before_if` / `interrupt_after_if` predicate
+    /// that triggered, in registration order; `None` for static and dynamic
+    /// interrupts
+    pub predicate: Option<usize>,
 }
 
 impl InterruptedExecution {
@@ -101,7 +105,13 @@ impl InterruptedExecution {
         state: crate::state::State,
         step: usize,
     ) -> Self {
-        Self { thread_id, checkpoint_id, interrupt, state, step }
+        Self { thread_id, checkpoint_id, interrupt, state, step, predicate: None }
+    }
+
+    /// Record the conditional interrupt predicate that triggered
+    pub fn with_predicate(mut self, predicate: Option<usize>) -> Self {
+        self.predicate = predicate;
+        self
     }
 }
 
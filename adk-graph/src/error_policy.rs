@@ -0,0 +1,304 @@
+//! Per-node error handling for graph execution.
+//!
+//! By default a failing node aborts the whole graph. An [`OnError`] policy lets a
+//! node recover instead: retry with backoff, route to a fallback node, or decide
+//! dynamically from the error with a custom handler.
+//!
+//! Retries and fallbacks are reported as [`StreamEvent::NodeRetry`] and
+//! [`StreamEvent::NodeFallback`] in `Debug`/`Custom` stream modes, and recorded in
+//! the step's checkpoint metadata under `node_retries` and `node_fallbacks`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use adk_graph::error_policy::{Backoff, OnError};
+//!
+//! let graph = StateGraph::with_channels(&["result"])
+//!     .add_node_fn("fetch", fetch)
+//!     .add_node_fn("cached", cached)
+//!     .add_edge(START, "fetch")
+//!     .add_edge("fetch", END)
+//!     .add_edge("cached", END)
+//!     .compile()?
+//!     .with_node_error_policy("fetch", OnError::retry(3, Backoff::exponential(Duration::from_millis(100))));
+//!
+//! // Or skip retries and serve a cached answer when `fetch` fails:
+//! // .with_node_error_policy("fetch", OnError::fallback("cached"))
+//! ```
+//!
+//! [`StreamEvent::NodeRetry`]: crate::stream::StreamEvent::NodeRetry
+//! [`StreamEvent::NodeFallback`]: crate::stream::StreamEvent::NodeFallback
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::error::{GraphError, Result};
+use crate::node::{Node, NodeContext, NodeOutput};
+use crate::timeout::{TimeoutPolicy, execute_with_timeout};
+
+/// Delay between retry attempts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Backoff {
+    /// Retry immediately.
+    #[default]
+    None,
+    /// Wait the same duration before every retry.
+    Fixed(Duration),
+    /// Double the delay after each retry, starting at `initial` and capped at `max`.
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    /// Exponential backoff starting at `initial`, capped at 30 seconds.
+    pub fn exponential(initial: Duration) -> Self {
+        Self::Exponential { initial, max: Duration::from_secs(30) }
+    }
+
+    /// Delay before retry number `attempt` (0-based).
+    pub fn delay(&self, attempt: usize) -> Duration {
+        match self {
+            Self::None => Duration::ZERO,
+            Self::Fixed(delay) => *delay,
+            Self::Exponential { initial, max } => {
+                let factor = 1u32.checked_shl(attempt.min(31) as u32).unwrap_or(u32::MAX);
+                initial.saturating_mul(factor).min(*max)
+            }
+        }
+    }
+}
+
+/// What the executor should do with a failed node attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorAction {
+    /// Run the node again after `delay`.
+    Retry { delay: Duration },
+    /// Abandon the node and schedule `node` in the next super-step instead.
+    Fallback { node: String },
+    /// Fail the graph with the error.
+    Propagate,
+}
+
+/// Custom error handler: receives the error and the number of retries so far.
+pub type ErrorHandlerFn = Arc<dyn Fn(&GraphError, usize) -> ErrorAction + Send + Sync>;
+
+/// Error handling policy for a graph node.
+#[derive(Clone, Default)]
+pub enum OnError {
+    /// Fail the graph with `GraphError::NodeExecutionFailed`.
+    #[default]
+    Propagate,
+    /// Retry the node up to `max_retries` times, waiting per `backoff` between attempts.
+    Retry { max_retries: usize, backoff: Backoff },
+    /// Route to the named node instead of the failed node's outgoing edges.
+    Fallback { node: String },
+    /// Decide per failure with a custom handler.
+    Custom(ErrorHandlerFn),
+}
+
+impl OnError {
+    /// Retry up to `max_retries` times with the given backoff.
+    pub fn retry(max_retries: usize, backoff: Backoff) -> Self {
+        Self::Retry { max_retries, backoff }
+    }
+
+    /// Route to `node` when this node fails.
+    pub fn fallback(node: &str) -> Self {
+        Self::Fallback { node: node.to_string() }
+    }
+
+    /// Decide with a custom handler that sees the error and the retry count.
+    pub fn custom<F>(handler: F) -> Self
+    where
+        F: Fn(&GraphError, usize) -> ErrorAction + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(handler))
+    }
+
+    /// Resolve the action for a failure after `retries` retries have already run.
+    pub fn action_for(&self, error: &GraphError, retries: usize) -> ErrorAction {
+        match self {
+            Self::Propagate => ErrorAction::Propagate,
+            Self::Retry { max_retries, backoff } => {
+                if retries < *max_retries {
+                    ErrorAction::Retry { delay: backoff.delay(retries) }
+                } else {
+                    ErrorAction::Propagate
+                }
+            }
+            Self::Fallback { node } => ErrorAction::Fallback { node: node.clone() },
+            Self::Custom(handler) => handler(error, retries),
+        }
+    }
+}
+
+impl fmt::Debug for OnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Propagate => f.write_str("Propagate"),
+            Self::Retry { max_retries, backoff } => f
+                .debug_struct("Retry")
+                .field("max_retries", max_retries)
+                .field("backoff", backoff)
+                .finish(),
+            Self::Fallback { node } => f.debug_struct("Fallback").field("node", node).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// A retry performed under an [`OnError`] policy.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeRetryRecord {
+    /// Node that was retried.
+    pub node: String,
+    /// Retry number (1-based).
+    pub attempt: usize,
+    /// Error that triggered the retry.
+    pub error: String,
+    /// Backoff delay before the retry, in milliseconds.
+    pub delay_ms: u64,
+}
+
+/// Outcome of running a node under its error policy.
+pub(crate) struct PolicyOutcome {
+    /// Final result. `Err` only when the error is propagated.
+    pub result: Result<NodeOutput>,
+    /// Retries performed before the final result.
+    pub retries: Vec<NodeRetryRecord>,
+    /// Fallback node and the error that triggered it, when the policy routed away.
+    pub fallback: Option<(String, String)>,
+}
+
+/// Execute a node, applying its timeout policy per attempt and its error policy on failure.
+///
+/// Interrupts are never treated as failures.
+pub(crate) async fn execute_with_error_policy(
+    node: &dyn Node,
+    ctx: &NodeContext,
+    timeout: Option<&TimeoutPolicy>,
+    policy: Option<&OnError>,
+) -> PolicyOutcome {
+    let mut retries = Vec::new();
+
+    loop {
+        let result = match timeout {
+            Some(timeout_policy) => execute_with_timeout(node, ctx, timeout_policy).await,
+            None => node.execute(ctx).await,
+        };
+
+        let error = match result {
+            Err(e) if !matches!(e, GraphError::Interrupted(_)) => e,
+            result => return PolicyOutcome { result, retries, fallback: None },
+        };
+
+        let Some(policy) = policy else {
+            return PolicyOutcome { result: Err(error), retries, fallback: None };
+        };
+
+        match policy.action_for(&error, retries.len()) {
+            ErrorAction::Retry { delay } => {
+                tracing::warn!(
+                    node = %node.name(),
+                    attempt = retries.len() + 1,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %error,
+                    "node failed, retrying"
+                );
+                retries.push(NodeRetryRecord {
+                    node: node.name().to_string(),
+                    attempt: retries.len() + 1,
+                    error: error.to_string(),
+                    delay_ms: delay.as_millis() as u64,
+                });
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            ErrorAction::Fallback { node: fallback } => {
+                tracing::warn!(
+                    node = %node.name(),
+                    fallback = %fallback,
+                    error = %error,
+                    "node failed, routing to fallback"
+                );
+                return PolicyOutcome {
+                    result: Ok(NodeOutput::new()),
+                    retries,
+                    fallback: Some((fallback, error.to_string())),
+                };
+            }
+            ErrorAction::Propagate => {
+                return PolicyOutcome { result: Err(error), retries, fallback: None };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::{ExecutionConfig, FunctionNode};
+    use crate::state::State;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn flaky_node(failures: usize) -> (FunctionNode, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let node = FunctionNode::new("flaky", move |_ctx| {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    Err(GraphError::Other("boom".to_string()))
+                } else {
+                    Ok(NodeOutput::new())
+                }
+            }
+        });
+        (node, calls)
+    }
+
+    #[test]
+    fn test_exponential_backoff_is_capped() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(10),
+            max: Duration::from_millis(50),
+        };
+        assert_eq!(backoff.delay(0), Duration::from_millis(10));
+        assert_eq!(backoff.delay(2), Duration::from_millis(40));
+        assert_eq!(backoff.delay(3), Duration::from_millis(50));
+        assert_eq!(backoff.delay(100), Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhaustion_propagates() {
+        let (node, calls) = flaky_node(usize::MAX);
+        let ctx = NodeContext::new(State::new(), ExecutionConfig::default(), 0);
+        let policy = OnError::retry(2, Backoff::None);
+
+        let outcome = execute_with_error_policy(&node, &ctx, None, Some(&policy)).await;
+        assert!(outcome.result.is_err());
+        assert_eq!(outcome.retries.len(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_custom_handler_sees_error() {
+        let (node, _) = flaky_node(usize::MAX);
+        let ctx = NodeContext::new(State::new(), ExecutionConfig::default(), 0);
+        let policy = OnError::custom(|error, _| {
+            if error.to_string().contains("boom") {
+                ErrorAction::Fallback { node: "recover".to_string() }
+            } else {
+                ErrorAction::Propagate
+            }
+        });
+
+        let outcome = execute_with_error_policy(&node, &ctx, None, Some(&policy)).await;
+        assert!(outcome.result.is_ok());
+        assert_eq!(outcome.fallback, Some(("recover".to_string(), "boom".to_string())));
+    }
+}
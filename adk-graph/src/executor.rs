@@ -6,8 +6,9 @@
 use crate::cache::{NodeCache, compute_cache_key};
 use crate::deferred::FanInTracker;
 use crate::error::{GraphError, InterruptedExecution, Result};
+use crate::error_policy::{NodeRetryRecord, execute_with_error_policy};
 use crate::graph::CompiledGraph;
-use crate::interrupt::Interrupt;
+use crate::interrupt::{ConditionalInterrupt, Interrupt};
 use crate::node::{ExecutionConfig, NodeContext};
 use crate::state::{Checkpoint, State};
 use crate::stream::{StreamEvent, StreamMode};
@@ -23,8 +24,12 @@ pub struct SuperStepResult {
     pub executed_nodes: Vec<String>,
     /// Interrupt if one occurred
     pub interrupt: Option<Interrupt>,
+    /// Index of the conditional interrupt predicate that triggered, if any
+    pub interrupt_predicate: Option<usize>,
     /// Stream events generated
     pub events: Vec<StreamEvent>,
+    /// Fallback nodes scheduled by error policies, run in the next super-step
+    pub fallback_nodes: Vec<String>,
 }
 
 /// Pregel-based executor for graphs
@@ -38,6 +43,10 @@ pub struct PregelExecutor<'a> {
     pending_deferred: HashMap<String, FanInTracker>,
     /// Tracks when each deferred node first entered the pending state (for fan-in timeout).
     deferred_start_times: HashMap<String, Instant>,
+    /// Retries performed in the current super-step, recorded in its checkpoint.
+    step_retries: Vec<NodeRetryRecord>,
+    /// Fallbacks taken in the current super-step, recorded in its checkpoint.
+    step_fallbacks: Vec<serde_json::Value>,
     /// Per-node caches initialized from `CompiledGraph::cache_policies`.
     #[cfg(feature = "node-cache")]
     node_caches: HashMap<String, NodeCache>,
@@ -61,6 +70,8 @@ impl<'a> PregelExecutor<'a> {
             pending_nodes: vec![],
             pending_deferred: HashMap::new(),
             deferred_start_times: HashMap::new(),
+            step_retries: Vec::new(),
+            step_fallbacks: Vec::new(),
             #[cfg(feature = "node-cache")]
             node_caches,
         }
@@ -129,21 +140,26 @@ impl<'a> PregelExecutor<'a> {
 
             // Handle interrupts
             if let Some(interrupt) = result.interrupt {
-                let checkpoint_id = self.save_checkpoint().await?;
-                return Err(GraphError::Interrupted(Box::new(InterruptedExecution::new(
-                    self.config.thread_id.clone(),
-                    checkpoint_id,
-                    interrupt,
-                    self.state.clone(),
-                    self.step,
-                ))));
+                let checkpoint_id = self.save_checkpoint(Some(&interrupt)).await?;
+                return Err(GraphError::Interrupted(Box::new(
+                    InterruptedExecution::new(
+                        self.config.thread_id.clone(),
+                        checkpoint_id,
+                        interrupt,
+                        self.state.clone(),
+                        self.step,
+                    )
+                    .with_predicate(result.interrupt_predicate),
+                )));
             }
 
             // Save checkpoint after each step
-            self.save_checkpoint().await?;
+            self.save_checkpoint(None).await?;
 
             // Check if we're done (all paths led to END)
-            if self.graph.leads_to_end(&result.executed_nodes, &self.state) {
+            if result.fallback_nodes.is_empty()
+                && self.graph.leads_to_end(&result.executed_nodes, &self.state)
+            {
                 let next = self.graph.get_next_nodes(&result.executed_nodes, &self.state);
                 if next.is_empty() {
                     break;
@@ -151,7 +167,7 @@ impl<'a> PregelExecutor<'a> {
             }
 
             // Determine next nodes and apply deferred node filtering
-            let next_candidates = self.graph.get_next_nodes(&result.executed_nodes, &self.state);
+            let next_candidates = self.next_candidates(&result);
             self.pending_nodes =
                 self.filter_deferred_nodes(next_candidates, &result.executed_nodes)?;
             self.step += 1;
@@ -324,6 +340,10 @@ impl<'a> PregelExecutor<'a> {
 
                 // Handle interrupts
                 if let Some(interrupt) = result.interrupt {
+                    if let Err(e) = self.save_checkpoint(Some(&interrupt)).await {
+                        yield Err(e);
+                        return;
+                    }
                     yield Ok(StreamEvent::interrupted(
                         result.executed_nodes.first().map(|s| s.as_str()).unwrap_or("unknown"),
                         &interrupt.to_string(),
@@ -331,8 +351,16 @@ impl<'a> PregelExecutor<'a> {
                     return;
                 }
 
+                // Save checkpoint after each step
+                if let Err(e) = self.save_checkpoint(None).await {
+                    yield Err(e);
+                    return;
+                }
+
                 // Check if done
-                if self.graph.leads_to_end(&result.executed_nodes, &self.state) {
+                if result.fallback_nodes.is_empty()
+                    && self.graph.leads_to_end(&result.executed_nodes, &self.state)
+                {
                     let next = self.graph.get_next_nodes(&result.executed_nodes, &self.state);
                     if next.is_empty() {
                         break;
@@ -340,7 +368,7 @@ impl<'a> PregelExecutor<'a> {
                 }
 
                 self.pending_nodes = {
-                    let next_candidates = self.graph.get_next_nodes(&result.executed_nodes, &self.state);
+                    let next_candidates = self.next_candidates(&result);
                     match self.filter_deferred_nodes(next_candidates, &result.executed_nodes) {
                         Ok(nodes) => nodes,
                         Err(e) => {
@@ -356,6 +384,17 @@ impl<'a> PregelExecutor<'a> {
         }
     }
 
+    /// Nodes reachable from the executed nodes plus any fallback nodes.
+    fn next_candidates(&self, result: &SuperStepResult) -> Vec<String> {
+        let mut next = self.graph.get_next_nodes(&result.executed_nodes, &self.state);
+        for fallback in &result.fallback_nodes {
+            if !next.contains(fallback) {
+                next.push(fallback.clone());
+            }
+        }
+        next
+    }
+
     /// Filter deferred nodes from the next candidates.
     ///
     /// For each candidate node that is configured as deferred, check whether all
@@ -490,12 +529,21 @@ impl<'a> PregelExecutor<'a> {
     /// Execute one super-step (plan -> execute -> update)
     async fn execute_super_step(&mut self) -> Result<SuperStepResult> {
         let mut result = SuperStepResult::default();
+        self.step_retries.clear();
+        self.step_fallbacks.clear();
 
-        // Check for interrupt_before
+        // Check for interrupt_before, static or conditional on the state
         for node_name in &self.pending_nodes {
-            if self.graph.interrupt_before.contains(node_name) {
+            let predicate = ConditionalInterrupt::find(
+                &self.graph.interrupt_conditions,
+                node_name,
+                true,
+                &self.state,
+            );
+            if self.graph.interrupt_before.contains(node_name) || predicate.is_some() {
                 return Ok(SuperStepResult {
                     interrupt: Some(Interrupt::Before(node_name.clone())),
+                    interrupt_predicate: predicate,
                     ..Default::default()
                 });
             }
@@ -561,16 +609,44 @@ impl<'a> PregelExecutor<'a> {
             .filter_map(|name| self.graph.nodes.get(name).map(|n| (name.clone(), n.clone())))
             .collect();
 
-        // Look up timeout policies for each node before spawning futures
-        let timeout_policies: Vec<_> =
-            nodes.iter().map(|(name, _)| self.graph.timeout_policy_for(name).cloned()).collect();
+        // Look up timeout and error policies for each node before spawning futures
+        let policies: Vec<_> = nodes
+            .iter()
+            .map(|(name, _)| {
+                (
+                    self.graph.timeout_policy_for(name).cloned(),
+                    self.graph.error_policy_for(name).cloned(),
+                )
+            })
+            .collect();
 
-        let futures: Vec<_> = nodes
-            .into_iter()
-            .zip(timeout_policies)
-            .map(|((name, node), policy)| {
-                let mut ctx = NodeContext::new(self.state.clone(), self.config.clone(), self.step);
+        // Expand map nodes into one worker invocation per item, so the workers
+        // run in parallel with the rest of the super-step
+        let mut invocations = Vec::new();
+        let mut maps = Vec::new();
+        for ((name, node), (policy, error_policy)) in nodes.into_iter().zip(policies) {
+            let ctx = NodeContext::new(self.state.clone(), self.config.clone(), self.step);
+            if let Some(map) = self.graph.map_nodes.get(&name) {
+                for (index, worker_ctx) in map.worker_contexts(&ctx)?.into_iter().enumerate() {
+                    invocations.push((
+                        name.clone(),
+                        Some(index),
+                        map.worker().clone(),
+                        worker_ctx,
+                        policy.clone(),
+                        error_policy.clone(),
+                    ));
+                }
+                maps.push((name, map.clone()));
+            } else {
+                invocations.push((name, None, node, ctx, policy, error_policy));
+            }
+        }
 
+        let concurrency = invocations.len().max(1);
+        let futures: Vec<_> = invocations
+            .into_iter()
+            .map(|(name, item, node, mut ctx, policy, error_policy)| {
                 // Attach a ProgressHandle when idle timeout is configured
                 if let Some(ref p) = policy
                     && p.idle_timeout.is_some()
@@ -581,29 +657,74 @@ impl<'a> PregelExecutor<'a> {
                 let step = self.step;
                 async move {
                     let start = Instant::now();
-                    let output = match policy {
-                        Some(ref timeout_policy) => {
-                            execute_with_timeout(node.as_ref(), &ctx, timeout_policy).await
-                        }
-                        None => node.execute(&ctx).await,
-                    };
+                    let outcome = execute_with_error_policy(
+                        node.as_ref(),
+                        &ctx,
+                        policy.as_ref(),
+                        error_policy.as_ref(),
+                    )
+                    .await;
                     let duration_ms = start.elapsed().as_millis() as u64;
-                    (name, output, duration_ms, step)
+                    (name, item, outcome, duration_ms, step)
                 }
             })
             .collect();
 
-        let outputs: Vec<_> =
-            stream::iter(futures).buffer_unordered(pending_for_execution.len()).collect().await;
+        let finished: Vec<_> = stream::iter(futures).buffer_unordered(concurrency).collect().await;
+
+        // Join each map node's workers, in item order, into a single outcome
+        let mut outputs = Vec::with_capacity(finished.len());
+        let mut workers: HashMap<String, Vec<_>> = HashMap::new();
+        for (name, item, outcome, duration_ms, step) in finished {
+            match item {
+                Some(index) => workers.entry(name).or_default().push((index, outcome, duration_ms)),
+                None => outputs.push((name, outcome, duration_ms, step)),
+            }
+        }
+        for (name, map) in maps {
+            let mut runs = workers.remove(&name).unwrap_or_default();
+            runs.sort_by_key(|(index, _, _)| *index);
+            let duration_ms = runs.iter().map(|(_, _, duration_ms)| *duration_ms).max();
+            let outcome = map.join(runs.into_iter().map(|(_, outcome, _)| outcome).collect());
+            outputs.push((name, outcome, duration_ms.unwrap_or(0), self.step));
+        }
 
         // Collect all updates and check for errors/interrupts
         let mut all_updates = Vec::new();
 
-        for (node_name, output_result, duration_ms, step) in outputs {
-            result.executed_nodes.push(node_name.clone());
+        for (node_name, outcome, duration_ms, step) in outputs {
+            for retry in outcome.retries {
+                result.events.push(StreamEvent::node_retry(
+                    &node_name,
+                    step,
+                    retry.attempt,
+                    &retry.error,
+                ));
+                self.step_retries.push(retry);
+            }
+
             result.events.push(StreamEvent::node_end(&node_name, step, duration_ms));
 
-            match output_result {
+            // A node routed to its fallback does not follow its own outgoing edges
+            if let Some((fallback, error)) = outcome.fallback {
+                if !self.graph.nodes.contains_key(&fallback) {
+                    return Err(GraphError::NodeNotFound(fallback));
+                }
+                result.events.push(StreamEvent::node_fallback(&node_name, step, &fallback, &error));
+                self.step_fallbacks.push(serde_json::json!({
+                    "node": node_name,
+                    "fallback": fallback,
+                    "error": error,
+                }));
+                if !result.fallback_nodes.contains(&fallback) {
+                    result.fallback_nodes.push(fallback);
+                }
+                continue;
+            }
+
+            result.executed_nodes.push(node_name.clone());
+
+            match outcome.result {
                 Ok(output) => {
                     // Check for dynamic interrupt
                     if let Some(interrupt) = output.interrupt {
@@ -611,6 +732,8 @@ impl<'a> PregelExecutor<'a> {
                             interrupt: Some(interrupt),
                             executed_nodes: result.executed_nodes,
                             events: result.events,
+                            fallback_nodes: result.fallback_nodes,
+                            interrupt_predicate: None,
                         });
                     }
 
@@ -648,11 +771,18 @@ impl<'a> PregelExecutor<'a> {
             }
         }
 
-        // Check for interrupt_after
+        // Check for interrupt_after, static or conditional on the state
         for node_name in &result.executed_nodes {
-            if self.graph.interrupt_after.contains(node_name) {
+            let predicate = ConditionalInterrupt::find(
+                &self.graph.interrupt_conditions,
+                node_name,
+                false,
+                &self.state,
+            );
+            if self.graph.interrupt_after.contains(node_name) || predicate.is_some() {
                 return Ok(SuperStepResult {
                     interrupt: Some(Interrupt::After(node_name.clone())),
+                    interrupt_predicate: predicate,
                     ..result
                 });
             }
@@ -662,14 +792,29 @@ impl<'a> PregelExecutor<'a> {
     }
 
     /// Save a checkpoint
-    async fn save_checkpoint(&self) -> Result<String> {
+    /// Save a checkpoint of the current step, recording `interrupt` as the
+    /// reason execution paused.
+    async fn save_checkpoint(&self, interrupt: Option<&Interrupt>) -> Result<String> {
         if let Some(cp) = &self.graph.checkpointer {
-            let checkpoint = Checkpoint::new(
+            let mut checkpoint = Checkpoint::new(
                 &self.config.thread_id,
                 self.state.clone(),
                 self.step,
                 self.pending_nodes.clone(),
             );
+            if !self.step_retries.is_empty() {
+                checkpoint = checkpoint
+                    .with_metadata("node_retries", serde_json::to_value(&self.step_retries)?);
+            }
+            if !self.step_fallbacks.is_empty() {
+                checkpoint = checkpoint.with_metadata(
+                    "node_fallbacks",
+                    serde_json::Value::Array(self.step_fallbacks.clone()),
+                );
+            }
+            if let Some(interrupt) = interrupt {
+                checkpoint = checkpoint.with_interrupt(interrupt)?;
+            }
             return cp.save(&checkpoint).await;
         }
         Ok(String::new())
@@ -4,8 +4,10 @@ use crate::checkpoint::Checkpointer;
 use crate::deferred::DeferredNodeConfig;
 use crate::edge::{END, Edge, EdgeTarget, RouterFn, START};
 use crate::error::{GraphError, Result};
+use crate::interrupt::ConditionalInterrupt;
+use crate::map::MapNode;
 use crate::node::{FunctionNode, Node, NodeContext, NodeOutput};
-use crate::state::{State, StateSchema};
+use crate::state::{Reducer, State, StateSchema};
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
@@ -20,12 +22,20 @@ pub struct StateGraph {
     pub edges: Vec<Edge>,
     /// Fan-in (deferred) node configurations, keyed by node name.
     pub deferred_configs: HashMap<String, DeferredNodeConfig>,
+    /// Map (fan-out) nodes, keyed by node name.
+    pub map_nodes: HashMap<String, Arc<MapNode>>,
 }
 
 impl StateGraph {
     /// Create a new graph with the given state schema
     pub fn new(schema: StateSchema) -> Self {
-        Self { schema, nodes: HashMap::new(), edges: vec![], deferred_configs: HashMap::new() }
+        Self {
+            schema,
+            nodes: HashMap::new(),
+            edges: vec![],
+            deferred_configs: HashMap::new(),
+            map_nodes: HashMap::new(),
+        }
     }
 
     /// Create with a simple schema (just channel names, all overwrite)
@@ -95,6 +105,37 @@ impl StateGraph {
         self
     }
 
+    /// Add a **map** node that runs `worker` once per item of the list in the
+    /// `items_key` channel.
+    ///
+    /// All worker invocations run in the same super-step, in parallel with
+    /// each other and with any other scheduled node. Each one sees the item
+    /// under [`MAP_ITEM_KEY`](crate::map::MAP_ITEM_KEY) and its position under
+    /// [`MAP_INDEX_KEY`](crate::map::MAP_INDEX_KEY); `reducer` folds their
+    /// updates into a single update for the map node. Timeout and error
+    /// policies set for the map node apply to each worker invocation.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use adk_graph::{Reducer, StateGraph};
+    /// let graph = StateGraph::with_channels(&["urls", "summaries"])
+    ///     .map_node("summarize_all", "urls", summarize_page, Reducer::Append)
+    ///     .add_edge(START, "summarize_all")
+    ///     .add_edge("summarize_all", END);
+    /// ```
+    pub fn map_node<N: Node + 'static>(
+        mut self,
+        name: &str,
+        items_key: &str,
+        worker: N,
+        reducer: Reducer,
+    ) -> Self {
+        let map = Arc::new(MapNode::new(name, items_key, worker, reducer));
+        self.nodes.insert(name.to_string(), map.clone());
+        self.map_nodes.insert(name.to_string(), map);
+        self
+    }
+
     /// Add a direct edge from source to target
     pub fn add_edge(mut self, source: &str, target: &str) -> Self {
         let target = EdgeTarget::from(target);
@@ -176,10 +217,13 @@ impl StateGraph {
             checkpointer: None,
             interrupt_before: HashSet::new(),
             interrupt_after: HashSet::new(),
+            interrupt_conditions: Vec::new(),
             recursion_limit: 50,
             timeout_policies: HashMap::new(),
             default_timeout: None,
+            error_policies: HashMap::new(),
             deferred_configs: self.deferred_configs,
+            map_nodes: self.map_nodes,
             #[cfg(feature = "node-cache")]
             cache_policies: HashMap::new(),
         })
@@ -240,13 +284,19 @@ pub struct CompiledGraph {
     pub(crate) checkpointer: Option<Arc<dyn Checkpointer>>,
     pub(crate) interrupt_before: HashSet<String>,
     pub(crate) interrupt_after: HashSet<String>,
+    /// Conditional interrupts, in registration order.
+    pub(crate) interrupt_conditions: Vec<ConditionalInterrupt>,
     pub(crate) recursion_limit: usize,
     /// Per-node timeout policies, keyed by node name.
     pub(crate) timeout_policies: HashMap<String, crate::timeout::TimeoutPolicy>,
     /// Default timeout policy applied to all nodes without an explicit override.
     pub(crate) default_timeout: Option<crate::timeout::TimeoutPolicy>,
+    /// Per-node error handling policies, keyed by node name.
+    pub(crate) error_policies: HashMap<String, crate::error_policy::OnError>,
     /// Deferred node configurations, keyed by node name.
     pub(crate) deferred_configs: HashMap<String, crate::deferred::DeferredNodeConfig>,
+    /// Map (fan-out) nodes, keyed by node name.
+    pub(crate) map_nodes: HashMap<String, Arc<MapNode>>,
     /// Per-node cache policies, keyed by node name.
     #[cfg(feature = "node-cache")]
     pub(crate) cache_policies: HashMap<String, crate::cache::NodeCachePolicy>,
@@ -277,6 +327,34 @@ impl CompiledGraph {
         self
     }
 
+    /// Configure an interrupt before `node` whenever `predicate` holds for
+    /// the state at that point
+    pub fn with_interrupt_before_if<F>(mut self, node: &str, predicate: F) -> Self
+    where
+        F: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.interrupt_conditions.push(ConditionalInterrupt {
+            node: node.to_string(),
+            before: true,
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Configure an interrupt after `node` whenever `predicate` holds for the
+    /// state including the node's updates
+    pub fn with_interrupt_after_if<F>(mut self, node: &str, predicate: F) -> Self
+    where
+        F: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.interrupt_conditions.push(ConditionalInterrupt {
+            node: node.to_string(),
+            before: false,
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
     /// Set recursion limit for cycles
     pub fn with_recursion_limit(mut self, limit: usize) -> Self {
         self.recursion_limit = limit;
@@ -292,6 +370,23 @@ impl CompiledGraph {
         self.timeout_policies.get(node_name).or(self.default_timeout.as_ref())
     }
 
+    /// Set the error handling policy for a node.
+    ///
+    /// Without a policy, a node failure aborts the graph.
+    pub fn with_node_error_policy(
+        mut self,
+        node_name: &str,
+        policy: crate::error_policy::OnError,
+    ) -> Self {
+        self.error_policies.insert(node_name.to_string(), policy);
+        self
+    }
+
+    /// Get the error handling policy for a node, if one was configured.
+    pub fn error_policy_for(&self, node_name: &str) -> Option<&crate::error_policy::OnError> {
+        self.error_policies.get(node_name)
+    }
+
     /// Get entry nodes
     pub fn get_entry_nodes(&self) -> Vec<String> {
         for edge in &self.edges {
@@ -1,7 +1,9 @@
 //! Human-in-the-loop interrupt types
 
+use crate::state::State;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::Arc;
 
 /// Interrupt request from a node or configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,3 +40,31 @@ pub fn interrupt(message: &str) -> Interrupt {
 pub fn interrupt_with_data(message: &str, data: Value) -> Interrupt {
     Interrupt::Dynamic { message: message.to_string(), data: Some(data) }
 }
+
+/// Predicate over the graph state that decides whether a conditional
+/// interrupt fires
+pub type InterruptPredicate = Arc<dyn Fn(&State) -> bool + Send + Sync>;
+
+/// Interrupt declared at the graph level that fires only while its predicate
+/// holds, registered with `interrupt_before_if` or `interrupt_after_if`
+#[derive(Clone)]
+pub(crate) struct ConditionalInterrupt {
+    pub node: String,
+    pub before: bool,
+    pub predicate: InterruptPredicate,
+}
+
+impl ConditionalInterrupt {
+    /// Index of the first predicate in `conditions` that guards `node` on the
+    /// given side and holds for `state`
+    pub fn find(
+        conditions: &[ConditionalInterrupt],
+        node: &str,
+        before: bool,
+        state: &State,
+    ) -> Option<usize> {
+        conditions.iter().position(|condition| {
+            condition.before == before && condition.node == node && (condition.predicate)(state)
+        })
+    }
+}
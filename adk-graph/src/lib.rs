@@ -74,9 +74,11 @@ pub mod checkpoint;
 pub mod deferred;
 pub mod edge;
 pub mod error;
+pub mod error_policy;
 pub mod executor;
 pub mod graph;
 pub mod interrupt;
+pub mod map;
 pub mod node;
 pub mod state;
 pub mod stream;
@@ -114,9 +116,11 @@ pub use checkpoint::{Checkpointer, MemoryCheckpointer};
 pub use deferred::{DeferredNodeConfig, FanInTracker, MergeStrategy};
 pub use edge::{END, Edge, EdgeTarget, Router, START};
 pub use error::{GraphError, InterruptedExecution, Result};
+pub use error_policy::{Backoff, ErrorAction, NodeRetryRecord, OnError};
 pub use executor::PregelExecutor;
 pub use graph::{CompiledGraph, StateGraph};
-pub use interrupt::{Interrupt, interrupt, interrupt_with_data};
+pub use interrupt::{Interrupt, InterruptPredicate, interrupt, interrupt_with_data};
+pub use map::{MAP_INDEX_KEY, MAP_ITEM_KEY, MapNode};
 pub use node::{AgentNode, ExecutionConfig, FunctionNode, Node, NodeContext, NodeOutput};
 pub use state::{Channel, Checkpoint, Reducer, State, StateSchema, StateSchemaBuilder};
 pub use stream::{StreamEvent, StreamMode};
@@ -143,6 +147,7 @@ pub mod prelude {
     pub use crate::deferred::{DeferredNodeConfig, FanInTracker, MergeStrategy};
     pub use crate::edge::{END, Edge, EdgeTarget, Router, START};
     pub use crate::error::{GraphError, InterruptedExecution, Result};
+    pub use crate::error_policy::{Backoff, OnError};
     pub use crate::graph::{CompiledGraph, StateGraph};
     pub use crate::interrupt::{Interrupt, interrupt, interrupt_with_data};
     pub use crate::node::{
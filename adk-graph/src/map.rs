@@ -0,0 +1,219 @@
+//! Map-reduce fan-out over a list in state.
+//!
+//! Fanning out over a variable number of items with hand-written branches and
+//! conditional edges is error-prone. A map node runs one worker invocation per
+//! item of a state list, all within the same super-step, and reduces the
+//! workers' updates into a single update (the equivalent of LangGraph's
+//! `Send`).
+//!
+//! Each worker sees the current state plus the item under [`MAP_ITEM_KEY`]
+//! and its position under [`MAP_INDEX_KEY`]. The [`Reducer`] folds the
+//! workers' updates key by key, in item order, starting from `null`; the
+//! result is applied to state like any other node update, through the
+//! channel's own reducer. A missing or `null` list runs no workers and
+//! produces no update.
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_graph::{END, NodeOutput, Reducer, START, StateGraph};
+//! use adk_graph::node::{ExecutionConfig, FunctionNode};
+//! use serde_json::json;
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let square = FunctionNode::new("square", |ctx| async move {
+//!     let n = ctx.get("item").and_then(|v| v.as_i64()).unwrap_or(0);
+//!     Ok(NodeOutput::new().with_update("total", json!(n * n)))
+//! });
+//!
+//! let graph = StateGraph::with_channels(&["numbers", "total"])
+//!     .map_node("squares", "numbers", square, Reducer::Sum)
+//!     .add_edge(START, "squares")
+//!     .add_edge("squares", END)
+//!     .compile()
+//!     .unwrap();
+//!
+//! let input = [("numbers".to_string(), json!([1, 2, 3]))].into();
+//! let state = graph.invoke(input, ExecutionConfig::new("squares")).await.unwrap();
+//! assert_eq!(state["total"], json!(14.0));
+//! # });
+//! ```
+
+use crate::error::{GraphError, Result};
+use crate::error_policy::PolicyOutcome;
+use crate::node::{Node, NodeContext, NodeOutput};
+use crate::state::Reducer;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// State key holding the item a map worker processes.
+pub const MAP_ITEM_KEY: &str = "item";
+
+/// State key holding the position of the item in the mapped list.
+pub const MAP_INDEX_KEY: &str = "item_index";
+
+/// A node that runs a worker once per item of a state list and reduces the
+/// results.
+///
+/// Created by [`StateGraph::map_node`](crate::StateGraph::map_node). The
+/// executor expands it into one worker invocation per item so the workers run
+/// in parallel with the rest of the super-step, each under the map node's
+/// timeout and error policies.
+pub struct MapNode {
+    name: String,
+    items_key: String,
+    worker: Arc<dyn Node>,
+    reducer: Reducer,
+}
+
+impl MapNode {
+    /// Create a map node that runs `worker` over the list in `items_key`.
+    pub fn new<N: Node + 'static>(
+        name: &str,
+        items_key: &str,
+        worker: N,
+        reducer: Reducer,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            items_key: items_key.to_string(),
+            worker: Arc::new(worker),
+            reducer,
+        }
+    }
+
+    /// The state key holding the list to map over.
+    pub fn items_key(&self) -> &str {
+        &self.items_key
+    }
+
+    /// The node run once per item.
+    pub fn worker(&self) -> &Arc<dyn Node> {
+        &self.worker
+    }
+
+    /// The reducer combining the workers' updates.
+    pub fn reducer(&self) -> &Reducer {
+        &self.reducer
+    }
+
+    /// One worker context per item of the list in `ctx`'s state.
+    pub fn worker_contexts(&self, ctx: &NodeContext) -> Result<Vec<NodeContext>> {
+        let items = match ctx.state.get(&self.items_key) {
+            None | Some(Value::Null) => return Ok(Vec::new()),
+            Some(Value::Array(items)) => items,
+            Some(other) => {
+                return Err(GraphError::NodeExecutionFailed {
+                    node: self.name.clone(),
+                    message: format!(
+                        "map input '{}' must be a list, got {}",
+                        self.items_key,
+                        type_name(other)
+                    ),
+                });
+            }
+        };
+
+        Ok(items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut state = ctx.state.clone();
+                state.insert(MAP_ITEM_KEY.to_string(), item.clone());
+                state.insert(MAP_INDEX_KEY.to_string(), json!(index));
+                NodeContext::new(state, ctx.config.clone(), ctx.step)
+            })
+            .collect())
+    }
+
+    /// Fold the workers' outputs, in item order, into one output.
+    ///
+    /// Updates are reduced key by key; events are concatenated and the first
+    /// interrupt wins.
+    pub fn reduce(&self, outputs: Vec<NodeOutput>) -> NodeOutput {
+        let mut reduced = NodeOutput::new();
+        for output in outputs {
+            for (key, value) in output.updates {
+                let current = reduced.updates.remove(&key).unwrap_or(Value::Null);
+                reduced.updates.insert(key, self.reducer.apply(current, value));
+            }
+            reduced.events.extend(output.events);
+            if reduced.interrupt.is_none() {
+                reduced.interrupt = output.interrupt;
+            }
+        }
+        reduced
+    }
+
+    /// Join the outcomes of the worker invocations, in item order, into the
+    /// outcome of the map node.
+    ///
+    /// A worker routed to a fallback routes the whole map node there; the
+    /// first failed worker fails it.
+    pub(crate) fn join(&self, outcomes: Vec<PolicyOutcome>) -> PolicyOutcome {
+        let mut retries = Vec::new();
+        let mut fallback = None;
+        let mut outputs = Vec::with_capacity(outcomes.len());
+        let mut error = None;
+
+        for (index, outcome) in outcomes.into_iter().enumerate() {
+            retries.extend(outcome.retries);
+            if fallback.is_none() {
+                fallback = outcome.fallback;
+            }
+            match outcome.result {
+                Ok(output) => outputs.push(output),
+                Err(e) if error.is_none() => {
+                    error = Some(GraphError::Other(format!("worker for item {index} failed: {e}")))
+                }
+                Err(_) => {}
+            }
+        }
+
+        let result = match (fallback.is_some(), error) {
+            (true, _) => Ok(NodeOutput::new()),
+            (false, Some(error)) => Err(error),
+            (false, None) => Ok(self.reduce(outputs)),
+        };
+        PolicyOutcome { result, retries, fallback }
+    }
+}
+
+#[async_trait]
+impl Node for MapNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs every worker concurrently and reduces their outputs. The executor
+    /// schedules the workers itself in regular super-steps; this is used when
+    /// the node is executed directly.
+    async fn execute(&self, ctx: &NodeContext) -> Result<NodeOutput> {
+        let contexts = self.worker_contexts(ctx)?;
+        let results =
+            futures::future::join_all(contexts.iter().map(|ctx| self.worker.execute(ctx))).await;
+        let outputs = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| {
+                result.map_err(|e| GraphError::NodeExecutionFailed {
+                    node: self.name.clone(),
+                    message: format!("worker for item {index} failed: {e}"),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.reduce(outputs))
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
@@ -557,10 +557,6 @@ impl adk_core::InvocationContext for GraphInvocationContext {
         self.agent.clone()
     }
 
-    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
-        None
-    }
-
     fn session(&self) -> &dyn adk_core::Session {
         self.session.as_ref()
     }
@@ -2,6 +2,7 @@
 //!
 //! Provides typed state with reducers for controlling how updates are merged.
 
+use crate::interrupt::Interrupt;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::collections::HashMap;
@@ -31,6 +32,33 @@ impl Default for Reducer {
     }
 }
 
+impl Reducer {
+    /// Merge `update` into `current` (`null` when the channel has no value yet).
+    pub fn apply(&self, current: Value, update: Value) -> Value {
+        match self {
+            Self::Overwrite => update,
+            Self::Append => {
+                let mut arr = match current {
+                    Value::Array(a) => a,
+                    Value::Null => vec![],
+                    _ => vec![current],
+                };
+                match update {
+                    Value::Array(items) => arr.extend(items),
+                    _ => arr.push(update),
+                }
+                Value::Array(arr)
+            }
+            Self::Sum => {
+                let current_num = current.as_f64().unwrap_or(0.0);
+                let add_num = update.as_f64().unwrap_or(0.0);
+                json!(current_num + add_num)
+            }
+            Self::Custom(f) => f(current, update),
+        }
+    }
+}
+
 impl std::fmt::Debug for Reducer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -121,31 +149,8 @@ impl StateSchema {
 
     /// Apply an update to state using the appropriate reducer
     pub fn apply_update(&self, state: &mut State, key: &str, value: Value) {
-        let reducer = self.get_reducer(key);
         let current = state.get(key).cloned().unwrap_or(Value::Null);
-
-        let new_value = match reducer {
-            Reducer::Overwrite => value,
-            Reducer::Append => {
-                let mut arr = match current {
-                    Value::Array(a) => a,
-                    Value::Null => vec![],
-                    _ => vec![current],
-                };
-                match value {
-                    Value::Array(items) => arr.extend(items),
-                    _ => arr.push(value),
-                }
-                Value::Array(arr)
-            }
-            Reducer::Sum => {
-                let current_num = current.as_f64().unwrap_or(0.0);
-                let add_num = value.as_f64().unwrap_or(0.0);
-                json!(current_num + add_num)
-            }
-            Reducer::Custom(f) => f(current, value),
-        };
-
+        let new_value = self.get_reducer(key).apply(current, value);
         state.insert(key.to_string(), new_value);
     }
 
@@ -224,6 +229,9 @@ pub struct Checkpoint {
 }
 
 impl Checkpoint {
+    /// Metadata key holding the serialized [`Interrupt`] of an interrupted run.
+    pub const INTERRUPT_METADATA_KEY: &'static str = "interrupt";
+
     /// Create a new checkpoint
     pub fn new(thread_id: &str, state: State, step: usize, pending_nodes: Vec<String>) -> Self {
         Self {
@@ -242,6 +250,22 @@ impl Checkpoint {
         self.metadata.insert(key.to_string(), value);
         self
     }
+
+    /// Record the interrupt that paused execution at this checkpoint.
+    pub fn with_interrupt(self, interrupt: &Interrupt) -> serde_json::Result<Self> {
+        Ok(self.with_metadata(Self::INTERRUPT_METADATA_KEY, serde_json::to_value(interrupt)?))
+    }
+
+    /// The interrupt that paused execution at this checkpoint, if any.
+    ///
+    /// Checkpoints saved after a step that completed normally have none, so
+    /// the latest checkpoint of a thread tells whether a run is waiting to be
+    /// resumed.
+    pub fn pending_interrupt(&self) -> Option<Interrupt> {
+        self.metadata
+            .get(Self::INTERRUPT_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +63,12 @@ pub enum StreamEvent {
 
     /// Emitted when dynamic route dispatch occurs.
     RouteDispatched { source: String, targets: Vec<String> },
+
+    /// A failed node is being retried under its error policy
+    NodeRetry { node: String, step: usize, attempt: usize, error: String },
+
+    /// A failed node was routed to its fallback node
+    NodeFallback { node: String, step: usize, fallback: String, error: String },
 }
 
 impl StreamEvent {
@@ -130,4 +136,19 @@ impl StreamEvent {
     pub fn route_dispatched(source: &str, targets: Vec<String>) -> Self {
         Self::RouteDispatched { source: source.to_string(), targets }
     }
+
+    /// Create a node retry event
+    pub fn node_retry(node: &str, step: usize, attempt: usize, error: &str) -> Self {
+        Self::NodeRetry { node: node.to_string(), step, attempt, error: error.to_string() }
+    }
+
+    /// Create a node fallback event
+    pub fn node_fallback(node: &str, step: usize, fallback: &str, error: &str) -> Self {
+        Self::NodeFallback {
+            node: node.to_string(),
+            step,
+            fallback: fallback.to_string(),
+            error: error.to_string(),
+        }
+    }
 }
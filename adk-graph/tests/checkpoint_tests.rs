@@ -172,3 +172,25 @@ async fn test_checkpoint_ordering() {
         assert_eq!(cp.step, i);
     }
 }
+
+#[tokio::test]
+async fn test_checkpoint_round_trips_pending_interrupt() {
+    let interrupt =
+        adk_graph::interrupt_with_data("Approve?", json!({"approved": {"type": "boolean"}}));
+    let checkpoint = Checkpoint::new("thread-1", State::new(), 1, vec!["review".to_string()])
+        .with_interrupt(&interrupt)
+        .unwrap();
+
+    let checkpointer = MemoryCheckpointer::new();
+    checkpointer.save(&checkpoint).await.unwrap();
+    let loaded = checkpointer.load("thread-1").await.unwrap().unwrap();
+
+    match loaded.pending_interrupt() {
+        Some(adk_graph::Interrupt::Dynamic { message, data }) => {
+            assert_eq!(message, "Approve?");
+            assert_eq!(data, Some(json!({"approved": {"type": "boolean"}})));
+        }
+        other => panic!("unexpected interrupt: {other:?}"),
+    }
+    assert!(Checkpoint::new("thread-1", State::new(), 2, vec![]).pending_interrupt().is_none());
+}
@@ -0,0 +1,102 @@
+//! Interrupts declared at the graph level with state predicates
+
+use adk_graph::checkpoint::MemoryCheckpointer;
+use adk_graph::edge::{END, START};
+use adk_graph::error::GraphError;
+use adk_graph::interrupt::Interrupt;
+use adk_graph::node::{ExecutionConfig, NodeOutput};
+use adk_graph::state::State;
+use adk_graph::{GraphAgent, GraphAgentBuilder};
+use serde_json::json;
+
+fn risk_score(state: &State) -> f64 {
+    state.get("risk_score").and_then(|v| v.as_f64()).unwrap_or(0.0)
+}
+
+fn approved(state: &State) -> bool {
+    state.get("approved").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// assess -> execute_trade, pausing before the trade when it is risky and
+/// unapproved
+fn trading_agent() -> GraphAgent {
+    GraphAgentBuilder::new("trader")
+        .channels(&["amount", "risk_score", "approved", "executed", "audited"])
+        .node_fn("assess", |ctx| async move {
+            let amount = ctx.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            Ok(NodeOutput::new().with_update("risk_score", json!(amount / 10_000.0)))
+        })
+        .node_fn("execute_trade", |_ctx| async move {
+            Ok(NodeOutput::new().with_update("executed", json!(true)))
+        })
+        .node_fn("audit", |_ctx| async move {
+            Ok(NodeOutput::new().with_update("audited", json!(true)))
+        })
+        .edge(START, "assess")
+        .edge("assess", "execute_trade")
+        .edge("execute_trade", "audit")
+        .edge("audit", END)
+        // Never fires, so the trade guard below is predicate 1.
+        .interrupt_after_if("assess", |_state| false)
+        .interrupt_before_if("execute_trade", |state| risk_score(state) > 0.8 && !approved(state))
+        .checkpointer(MemoryCheckpointer::new())
+        .build()
+        .unwrap()
+}
+
+fn order(amount: f64) -> State {
+    State::from([("amount".to_string(), json!(amount))])
+}
+
+#[tokio::test]
+async fn test_low_risk_runs_complete_uninterrupted() {
+    let agent = trading_agent();
+
+    let result = agent.invoke(order(2_000.0), ExecutionConfig::new("low-risk")).await.unwrap();
+
+    assert_eq!(result.get("executed"), Some(&json!(true)));
+    assert_eq!(result.get("audited"), Some(&json!(true)));
+}
+
+#[tokio::test]
+async fn test_high_risk_runs_pause_before_guarded_node() {
+    let agent = trading_agent();
+
+    let error = agent.invoke(order(9_500.0), ExecutionConfig::new("high-risk")).await.unwrap_err();
+    let GraphError::Interrupted(interrupted) = error else {
+        panic!("expected an interrupt, got {error:?}");
+    };
+    assert!(matches!(&interrupted.interrupt, Interrupt::Before(node) if node == "execute_trade"));
+    // The second registered conditional interrupt triggered.
+    assert_eq!(interrupted.predicate, Some(1));
+    assert_eq!(interrupted.state.get("risk_score"), Some(&json!(0.95)));
+    assert!(!interrupted.state.contains_key("executed"));
+
+    // Approving flips the predicate, so the resumed run goes through.
+    agent.graph().update_state("high-risk", [("approved".to_string(), json!(true))]).await.unwrap();
+    let result = agent.invoke(State::new(), ExecutionConfig::new("high-risk")).await.unwrap();
+    assert_eq!(result.get("executed"), Some(&json!(true)));
+    assert_eq!(result.get("audited"), Some(&json!(true)));
+}
+
+#[tokio::test]
+async fn test_static_interrupts_carry_no_predicate() {
+    let graph = adk_graph::graph::StateGraph::with_channels(&["value"])
+        .add_node_fn(
+            "step",
+            |_ctx| async move { Ok(NodeOutput::new().with_update("value", json!(1))) },
+        )
+        .add_edge(START, "step")
+        .add_edge("step", END)
+        .compile()
+        .unwrap()
+        .with_interrupt_before(&["step"])
+        .with_interrupt_after_if("step", |_state| true);
+
+    let error = graph.invoke(State::new(), ExecutionConfig::new("static")).await.unwrap_err();
+    let GraphError::Interrupted(interrupted) = error else {
+        panic!("expected an interrupt, got {error:?}");
+    };
+    assert!(matches!(&interrupted.interrupt, Interrupt::Before(node) if node == "step"));
+    assert_eq!(interrupted.predicate, None);
+}
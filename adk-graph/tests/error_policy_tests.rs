@@ -0,0 +1,110 @@
+//! Per-node error policy tests
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use adk_graph::checkpoint::{Checkpointer, MemoryCheckpointer};
+use adk_graph::edge::{END, START};
+use adk_graph::error::GraphError;
+use adk_graph::error_policy::{Backoff, OnError};
+use adk_graph::graph::StateGraph;
+use adk_graph::node::{ExecutionConfig, NodeOutput};
+use adk_graph::state::State;
+use adk_graph::stream::{StreamEvent, StreamMode};
+use futures::StreamExt;
+use serde_json::json;
+
+fn flaky_graph(failures: usize, calls: Arc<AtomicUsize>) -> StateGraph {
+    StateGraph::with_channels(&["result"])
+        .add_node_fn("flaky", move |_ctx| {
+            let calls = calls.clone();
+            async move {
+                if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                    Err(GraphError::Other("upstream unavailable".to_string()))
+                } else {
+                    Ok(NodeOutput::new().with_update("result", json!("primary")))
+                }
+            }
+        })
+        .add_node_fn("fallback", |_ctx| async {
+            Ok(NodeOutput::new().with_update("result", json!("fallback")))
+        })
+        .add_node_fn("after", |_ctx| async { Ok(NodeOutput::new()) })
+        .add_edge(START, "flaky")
+        .add_edge("flaky", "after")
+        .add_edge("after", END)
+        .add_edge("fallback", END)
+}
+
+#[tokio::test]
+async fn test_retry_succeeds_after_two_failures() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let checkpointer = Arc::new(MemoryCheckpointer::new());
+    let graph = flaky_graph(2, calls.clone())
+        .compile()
+        .unwrap()
+        .with_checkpointer_arc(checkpointer.clone())
+        .with_node_error_policy(
+            "flaky",
+            OnError::retry(3, Backoff::Fixed(std::time::Duration::from_millis(1))),
+        );
+
+    let result = graph.invoke(State::new(), ExecutionConfig::new("retry-thread")).await.unwrap();
+
+    assert_eq!(result.get("result"), Some(&json!("primary")));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let checkpoints = checkpointer.list("retry-thread").await.unwrap();
+    let retries = checkpoints
+        .iter()
+        .find_map(|cp| cp.metadata.get("node_retries"))
+        .expect("retries should be recorded in checkpoint metadata");
+    assert_eq!(retries.as_array().unwrap().len(), 2);
+    assert_eq!(retries[0]["node"], "flaky");
+    assert_eq!(retries[1]["attempt"], 2);
+}
+
+#[tokio::test]
+async fn test_retry_exhausted_fails_graph() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let graph = flaky_graph(usize::MAX, calls.clone())
+        .compile()
+        .unwrap()
+        .with_node_error_policy("flaky", OnError::retry(1, Backoff::None));
+
+    let err = graph.invoke(State::new(), ExecutionConfig::new("t")).await.unwrap_err();
+
+    assert!(matches!(err, GraphError::NodeExecutionFailed { ref node, .. } if node == "flaky"));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_failure_routes_to_fallback_node() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let graph = flaky_graph(usize::MAX, calls)
+        .compile()
+        .unwrap()
+        .with_node_error_policy("flaky", OnError::fallback("fallback"));
+
+    let events: Vec<_> = graph
+        .stream(State::new(), ExecutionConfig::new("fallback-thread"), StreamMode::Debug)
+        .collect()
+        .await;
+    let events: Vec<_> = events.into_iter().map(|e| e.unwrap()).collect();
+
+    assert!(events.iter().any(|e| matches!(
+        e,
+        StreamEvent::NodeFallback { node, fallback, error, .. }
+            if node == "flaky" && fallback == "fallback" && error.contains("upstream unavailable")
+    )));
+    // The failed node's own edges are not followed
+    assert!(
+        !events.iter().any(|e| matches!(e, StreamEvent::NodeStart { node, .. } if node == "after"))
+    );
+    match events.last() {
+        Some(StreamEvent::Done { state, .. }) => {
+            assert_eq!(state.get("result"), Some(&json!("fallback")));
+        }
+        other => panic!("expected done event, got {other:?}"),
+    }
+}
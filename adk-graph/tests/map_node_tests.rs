@@ -0,0 +1,107 @@
+//! Map-reduce fan-out with `StateGraph::map_node`.
+
+use adk_graph::edge::{END, START};
+use adk_graph::error::GraphError;
+use adk_graph::graph::StateGraph;
+use adk_graph::node::{ExecutionConfig, FunctionNode, NodeOutput};
+use adk_graph::state::{Reducer, State, StateSchema};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn input(items: serde_json::Value) -> State {
+    State::from([("orders".to_string(), items)])
+}
+
+#[tokio::test]
+async fn test_map_node_sums_worker_outputs() {
+    // Each worker prices one order: quantity * unit price.
+    let price = FunctionNode::new("price", |ctx| async move {
+        let order = ctx.get("item").cloned().unwrap_or_default();
+        let quantity = order["quantity"].as_i64().unwrap_or(0);
+        let unit_price = order["unit_price"].as_i64().unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Ok(NodeOutput::new()
+            .with_update("total", json!(quantity * unit_price))
+            .with_update("priced", json!(1)))
+    });
+    let graph = StateGraph::with_channels(&["orders", "total", "priced", "report"])
+        .map_node("price_orders", "orders", price, Reducer::Sum)
+        .add_node_fn("report", |ctx| async move {
+            let total = ctx.get("total").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            Ok(NodeOutput::new().with_update("report", json!(format!("total: {total}"))))
+        })
+        .add_edge(START, "price_orders")
+        .add_edge("price_orders", "report")
+        .add_edge("report", END)
+        .compile()
+        .unwrap();
+
+    let orders = json!([
+        { "quantity": 1, "unit_price": 10 },
+        { "quantity": 2, "unit_price": 15 },
+        { "quantity": 3, "unit_price": 20 },
+        { "quantity": 4, "unit_price": 25 },
+        { "quantity": 5, "unit_price": 30 },
+    ]);
+    let started = Instant::now();
+    let result = graph.invoke(input(orders), ExecutionConfig::new("orders")).await.unwrap();
+
+    // 10 + 30 + 60 + 100 + 150
+    assert_eq!(result["total"], json!(350.0));
+    assert_eq!(result["report"], json!("total: 350"));
+    assert_eq!(result["priced"], json!(5.0));
+    // All five workers ran in parallel, in the same super-step.
+    assert!(started.elapsed() < Duration::from_millis(400), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn test_map_node_appends_results_in_item_order() {
+    let label = FunctionNode::new("label", |ctx| async move {
+        let index = ctx.get("item_index").and_then(|v| v.as_u64()).unwrap_or(0);
+        let name = ctx.get("item").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        // Later items finish first.
+        tokio::time::sleep(Duration::from_millis(50 - index * 10)).await;
+        Ok(NodeOutput::new().with_update("labels", json!(format!("{index}:{name}"))))
+    });
+    let schema = StateSchema::builder().channel("orders").list_channel("labels").build();
+    let graph = StateGraph::new(schema)
+        .map_node("label_all", "orders", label, Reducer::Append)
+        .add_edge(START, "label_all")
+        .add_edge("label_all", END)
+        .compile()
+        .unwrap();
+
+    let result = graph
+        .invoke(input(json!(["tea", "milk", "bread"])), ExecutionConfig::new("labels"))
+        .await
+        .unwrap();
+    assert_eq!(result["labels"], json!(["0:tea", "1:milk", "2:bread"]));
+
+    // An empty list runs no workers and the graph carries on.
+    let result = graph.invoke(input(json!([])), ExecutionConfig::new("empty")).await.unwrap();
+    assert_eq!(result["labels"], json!([]));
+}
+
+#[tokio::test]
+async fn test_map_node_failures() {
+    let check = FunctionNode::new("check", |ctx| async move {
+        match ctx.get("item").and_then(|v| v.as_i64()) {
+            Some(n) if n >= 0 => Ok(NodeOutput::new().with_update("total", json!(n))),
+            _ => Err(GraphError::Other("negative quantity".to_string())),
+        }
+    });
+    let graph = StateGraph::with_channels(&["orders", "total"])
+        .map_node("check_all", "orders", check, Reducer::Sum)
+        .add_edge(START, "check_all")
+        .add_edge("check_all", END)
+        .compile()
+        .unwrap();
+
+    let err = graph.invoke(input(json!([3, -1, 2])), ExecutionConfig::new("bad")).await;
+    let message = err.unwrap_err().to_string();
+    assert!(message.contains("check_all") && message.contains("item 1"), "{message}");
+
+    let err = graph.invoke(input(json!("3 orders")), ExecutionConfig::new("not-a-list")).await;
+    let message = err.unwrap_err().to_string();
+    assert!(message.contains("must be a list, got a string"), "{message}");
+}
@@ -182,6 +182,7 @@ impl ScriptedLlm {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -230,6 +231,7 @@ impl Llm for ScriptedLlm {
                 error_message: None,
                 provider_metadata: None,
                 interaction_id: None,
+                citations: Vec::new(),
             }
         };
 
@@ -1,7 +1,8 @@
 //! Embedding provider trait for vector-based memory search.
 //!
 //! Provides [`EmbeddingProvider`], a trait for pluggable embedding models
-//! used by [`PostgresMemoryService`](super::postgres::PostgresMemoryService).
+//! used by [`InMemoryMemoryService`](crate::InMemoryMemoryService) and the
+//! vector-capable database backends.
 
 use adk_core::Result;
 use async_trait::async_trait;
@@ -1,8 +1,11 @@
+use crate::embedding::EmbeddingProvider;
 use crate::service::*;
-use adk_core::Result;
+use adk_core::{AdkError, Clock, Result, system_clock};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct MemoryKey {
@@ -15,17 +18,112 @@ struct StoredEntry {
     entry: MemoryEntry,
     words: HashSet<String>,
     project_id: Option<String>,
+    stored_at: DateTime<Utc>,
+    /// Cached embedding of the entry's text, when a provider is set.
+    embedding: Option<Vec<f32>>,
 }
 
 type MemoryStore = HashMap<MemoryKey, HashMap<String, Vec<StoredEntry>>>;
 
+/// Memory kept in process, searched by keyword or, with an
+/// [`EmbeddingProvider`], by meaning.
 pub struct InMemoryMemoryService {
     store: Arc<RwLock<MemoryStore>>,
+    clock: Arc<dyn Clock>,
+    ttl: Option<Duration>,
+    embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
 }
 
 impl InMemoryMemoryService {
     pub fn new() -> Self {
-        Self { store: Arc::new(RwLock::new(HashMap::new())) }
+        Self {
+            store: Arc::new(RwLock::new(HashMap::new())),
+            clock: system_clock(),
+            ttl: None,
+            embedding_provider: None,
+        }
+    }
+
+    /// Search by embedding similarity instead of shared keywords.
+    ///
+    /// Entries are embedded once when they are added and the embedding is kept
+    /// with the entry; each search embeds its query and ranks entries by cosine
+    /// similarity, dropping those below `min_score`. Without a provider,
+    /// searches return the entries that share a word with the query.
+    pub fn with_embedding_provider(mut self, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        self.embedding_provider = Some(provider);
+        self
+    }
+
+    /// Expire entries `ttl` after they were stored.
+    ///
+    /// Expired entries are skipped by searches and listings right away and
+    /// removed from memory by [`sweep_expired`](Self::sweep_expired).
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Read the time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Removes every expired entry and returns how many were removed.
+    ///
+    /// Does nothing without a TTL.
+    pub fn sweep_expired(&self) -> usize {
+        let Some(cutoff) = self.expiry_cutoff() else {
+            return 0;
+        };
+
+        let mut store = self.store.write().unwrap();
+        let mut removed = 0;
+        for sessions in store.values_mut() {
+            for entries in sessions.values_mut() {
+                let before = entries.len();
+                entries.retain(|stored| stored.stored_at > cutoff);
+                removed += before - entries.len();
+            }
+            sessions.retain(|_, entries| !entries.is_empty());
+        }
+        store.retain(|_, sessions| !sessions.is_empty());
+        removed
+    }
+
+    /// Entries stored at or before this instant have expired.
+    fn expiry_cutoff(&self) -> Option<DateTime<Utc>> {
+        let ttl = chrono::Duration::from_std(self.ttl?).ok()?;
+        self.clock.now().checked_sub_signed(ttl)
+    }
+
+    /// Embeds the text of `entries` in one batch when a provider is set.
+    async fn embed(&self, entries: &mut [StoredEntry]) -> Result<()> {
+        let Some(provider) = &self.embedding_provider else {
+            return Ok(());
+        };
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let texts: Vec<String> =
+            entries.iter().map(|stored| crate::text::extract_text(&stored.entry.content)).collect();
+        let embeddings = provider.embed(&texts).await?;
+        if embeddings.len() != entries.len() {
+            return Err(AdkError::memory(format!(
+                "embedding provider returned {} embeddings for {} entries",
+                embeddings.len(),
+                entries.len()
+            )));
+        }
+        for (stored, embedding) in entries.iter_mut().zip(embeddings) {
+            stored.embedding = Some(embedding);
+        }
+        Ok(())
+    }
+
+    fn is_live(stored: &StoredEntry, cutoff: Option<DateTime<Utc>>) -> bool {
+        cutoff.is_none_or(|cutoff| stored.stored_at > cutoff)
     }
 
     fn has_intersection(set1: &HashSet<String>, set2: &HashSet<String>) -> bool {
@@ -53,11 +151,12 @@ impl MemoryService for InMemoryMemoryService {
     ) -> Result<()> {
         let key = MemoryKey { app_name: app_name.to_string(), user_id: user_id.to_string() };
 
-        let stored_entries: Vec<StoredEntry> = entries
+        let stored_at = self.clock.now();
+        let mut stored_entries: Vec<StoredEntry> = entries
             .into_iter()
             .map(|entry| {
                 let words = crate::text::extract_words_from_content(&entry.content);
-                StoredEntry { entry, words, project_id: None }
+                StoredEntry { entry, words, project_id: None, stored_at, embedding: None }
             })
             .filter(|e| !e.words.is_empty())
             .collect();
@@ -65,6 +164,7 @@ impl MemoryService for InMemoryMemoryService {
         if stored_entries.is_empty() {
             return Ok(());
         }
+        self.embed(&mut stored_entries).await?;
 
         let mut store = self.store.write().unwrap();
         let sessions = store.entry(key).or_default();
@@ -85,11 +185,18 @@ impl MemoryService for InMemoryMemoryService {
 
         let key = MemoryKey { app_name: app_name.to_string(), user_id: user_id.to_string() };
 
-        let stored_entries: Vec<StoredEntry> = entries
+        let stored_at = self.clock.now();
+        let mut stored_entries: Vec<StoredEntry> = entries
             .into_iter()
             .map(|entry| {
                 let words = crate::text::extract_words_from_content(&entry.content);
-                StoredEntry { entry, words, project_id: Some(project_id.to_string()) }
+                StoredEntry {
+                    entry,
+                    words,
+                    project_id: Some(project_id.to_string()),
+                    stored_at,
+                    embedding: None,
+                }
             })
             .filter(|e| !e.words.is_empty())
             .collect();
@@ -97,6 +204,7 @@ impl MemoryService for InMemoryMemoryService {
         if stored_entries.is_empty() {
             return Ok(());
         }
+        self.embed(&mut stored_entries).await?;
 
         let mut store = self.store.write().unwrap();
         let sessions = store.entry(key).or_default();
@@ -108,7 +216,9 @@ impl MemoryService for InMemoryMemoryService {
     async fn add_entry(&self, app_name: &str, user_id: &str, entry: MemoryEntry) -> Result<()> {
         let key = MemoryKey { app_name: app_name.to_string(), user_id: user_id.to_string() };
         let words = crate::text::extract_words_from_content(&entry.content);
-        let stored = StoredEntry { entry, words, project_id: None };
+        let stored_at = self.clock.now();
+        let mut stored = StoredEntry { entry, words, project_id: None, stored_at, embedding: None };
+        self.embed(std::slice::from_mut(&mut stored)).await?;
 
         let mut store = self.store.write().unwrap();
         let sessions = store.entry(key).or_default();
@@ -128,7 +238,15 @@ impl MemoryService for InMemoryMemoryService {
 
         let key = MemoryKey { app_name: app_name.to_string(), user_id: user_id.to_string() };
         let words = crate::text::extract_words_from_content(&entry.content);
-        let stored = StoredEntry { entry, words, project_id: Some(project_id.to_string()) };
+        let stored_at = self.clock.now();
+        let mut stored = StoredEntry {
+            entry,
+            words,
+            project_id: Some(project_id.to_string()),
+            stored_at,
+            embedding: None,
+        };
+        self.embed(std::slice::from_mut(&mut stored)).await?;
 
         let mut store = self.store.write().unwrap();
         let sessions = store.entry(key).or_default();
@@ -171,11 +289,17 @@ impl MemoryService for InMemoryMemoryService {
         limit: usize,
     ) -> Result<Vec<MemoryEntry>> {
         let key = MemoryKey { app_name: app_name.to_string(), user_id: user_id.to_string() };
+        let cutoff = self.expiry_cutoff();
         let store = self.store.read().unwrap();
         let mut entries: Vec<MemoryEntry> = store
             .get(&key)
             .map(|sessions| {
-                sessions.values().flatten().map(|stored| stored.entry.clone()).collect()
+                sessions
+                    .values()
+                    .flatten()
+                    .filter(|stored| Self::is_live(stored, cutoff))
+                    .map(|stored| stored.entry.clone())
+                    .collect()
             })
             .unwrap_or_default();
         entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
@@ -248,8 +372,21 @@ impl MemoryService for InMemoryMemoryService {
     async fn search(&self, req: SearchRequest) -> Result<SearchResponse> {
         let query_words = crate::text::extract_words(&req.query);
         let limit = req.limit.unwrap_or(10);
+        let query_embedding = match &self.embedding_provider {
+            Some(provider) => {
+                let mut embeddings = provider.embed(std::slice::from_ref(&req.query)).await?;
+                match embeddings.pop() {
+                    Some(embedding) => Some(embedding),
+                    None => {
+                        return Err(AdkError::memory("embedding provider returned no embedding"));
+                    }
+                }
+            }
+            None => None,
+        };
 
         let key = MemoryKey { app_name: req.app_name, user_id: req.user_id };
+        let cutoff = self.expiry_cutoff();
 
         let store = self.store.read().unwrap();
         let sessions = match store.get(&key) {
@@ -257,34 +394,50 @@ impl MemoryService for InMemoryMemoryService {
             None => return Ok(SearchResponse { memories: Vec::new() }),
         };
 
-        let mut memories = Vec::new();
-        for stored_entries in sessions.values() {
-            for stored in stored_entries {
-                if !Self::has_intersection(&stored.words, &query_words) {
-                    continue;
-                }
-
-                match &req.project_id {
-                    // Global search: only include global entries
-                    None => {
-                        if stored.project_id.is_none() {
-                            memories.push(stored.entry.clone());
-                        }
-                    }
-                    // Project search: include global + matching project entries
-                    Some(pid) => {
-                        if stored.project_id.is_none()
-                            || stored.project_id.as_deref() == Some(pid.as_str())
-                        {
-                            memories.push(stored.entry.clone());
-                        }
-                    }
-                }
+        let in_scope = |stored: &StoredEntry| match &req.project_id {
+            // Global search: only include global entries
+            None => stored.project_id.is_none(),
+            // Project search: include global + matching project entries
+            Some(pid) => {
+                stored.project_id.is_none() || stored.project_id.as_deref() == Some(pid.as_str())
             }
-        }
+        };
+        let candidates = sessions
+            .values()
+            .flatten()
+            .filter(|stored| Self::is_live(stored, cutoff) && in_scope(stored));
+
+        let mut memories: Vec<MemoryEntry> = match query_embedding {
+            Some(query) => {
+                let mut scored: Vec<(f32, &StoredEntry)> = candidates
+                    .filter_map(|stored| {
+                        let score = cosine_similarity(&query, stored.embedding.as_deref()?);
+                        req.min_score.is_none_or(|min| score >= min).then_some((score, stored))
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+                scored.into_iter().map(|(_, stored)| stored.entry.clone()).collect()
+            }
+            None => candidates
+                .filter(|stored| Self::has_intersection(&stored.words, &query_words))
+                .map(|stored| stored.entry.clone())
+                .collect(),
+        };
 
         memories.truncate(limit);
 
         Ok(SearchResponse { memories })
     }
 }
+
+/// Cosine similarity of two vectors; `0.0` when their lengths differ or
+/// either is all zeros.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+}
@@ -6,7 +6,9 @@
 //!
 //! This crate provides long-term memory capabilities:
 //!
-//! - [`InMemoryMemoryService`] - Simple in-memory memory storage
+//! - [`InMemoryMemoryService`] - Simple in-memory memory storage, with keyword or
+//!   embedding search
+//! - [`EmbeddingProvider`] - Pluggable embedding model for semantic search
 //! - [`MemoryService`] - Trait for custom backends
 //! - [`MemoryEntry`] - Structured memory with metadata
 //! - [`MemoryServiceAdapter`] - Bridge to [`adk_core::Memory`] with optional project scope
@@ -56,18 +58,12 @@
 //! - GDPR `delete_user` across all projects
 
 pub mod adapter;
+pub mod embedding;
 pub mod inmemory;
 pub mod migration;
 pub mod service;
 pub mod text;
 
-#[cfg(any(
-    feature = "database-memory",
-    feature = "mongodb-memory",
-    feature = "neo4j-memory",
-    feature = "embedding-trait"
-))]
-pub mod embedding;
 #[cfg(feature = "graph-memory")]
 pub mod graph;
 #[cfg(feature = "mongodb-memory")]
@@ -82,16 +78,10 @@ pub mod redis;
 pub mod sqlite;
 
 pub use adapter::MemoryServiceAdapter;
+pub use embedding::EmbeddingProvider;
 pub use inmemory::InMemoryMemoryService;
 pub use service::{MemoryEntry, MemoryService, SearchRequest, SearchResponse, validate_project_id};
 
-#[cfg(any(
-    feature = "database-memory",
-    feature = "mongodb-memory",
-    feature = "neo4j-memory",
-    feature = "embedding-trait"
-))]
-pub use embedding::EmbeddingProvider;
 #[cfg(feature = "graph-memory")]
 pub use graph::{
     CreateEntityInput, CreateRelationInput, Entity, GraphMemoryService, GraphSearchResult,
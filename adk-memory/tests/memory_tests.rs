@@ -1,6 +1,8 @@
-use adk_core::{Content, Part};
+use adk_core::{Content, MockClock, Part};
 use adk_memory::*;
 use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_add_and_search() {
@@ -254,3 +256,122 @@ async fn test_adapter_without_project_preserves_behavior() {
     let results = adapter.search("data").await.unwrap();
     assert_eq!(results.len(), 2);
 }
+
+#[tokio::test]
+async fn test_entries_expire_after_ttl() {
+    let clock = MockClock::default();
+    let service = InMemoryMemoryService::new()
+        .with_ttl(Duration::from_secs(60))
+        .with_clock(Arc::new(clock.clone()));
+    let search = || SearchRequest {
+        query: "weather".to_string(),
+        user_id: "user1".to_string(),
+        app_name: "app1".to_string(),
+        limit: None,
+        min_score: None,
+        project_id: None,
+    };
+    let entry = |text: &str| MemoryEntry {
+        content: Content::new("assistant").with_text(text),
+        author: "assistant".to_string(),
+        timestamp: Utc::now(),
+    };
+
+    service
+        .add_session("app1", "user1", "old", vec![entry("The weather was rainy")])
+        .await
+        .unwrap();
+    clock.advance(Duration::from_secs(30));
+    service.add_session("app1", "user1", "new", vec![entry("The weather is sunny")]).await.unwrap();
+    assert_eq!(service.search(search()).await.unwrap().memories.len(), 2);
+
+    // The first entry is now 61 seconds old, the second 31.
+    clock.advance(Duration::from_secs(31));
+    let memories = service.search(search()).await.unwrap().memories;
+    assert_eq!(memories.len(), 1);
+    assert_eq!(memories[0].content.parts[0].text(), Some("The weather is sunny"));
+    assert_eq!(service.list_recent("app1", "user1", 10).await.unwrap().len(), 1);
+
+    assert_eq!(service.sweep_expired(), 1);
+    assert_eq!(service.sweep_expired(), 0);
+
+    clock.advance(Duration::from_secs(30));
+    assert!(service.search(search()).await.unwrap().memories.is_empty());
+    assert_eq!(service.sweep_expired(), 1);
+}
+
+/// Embeds text by topic: rain, food, and a constant component.
+struct TopicEmbedder {
+    embedded: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl EmbeddingProvider for TopicEmbedder {
+    async fn embed(&self, texts: &[String]) -> adk_core::Result<Vec<Vec<f32>>> {
+        self.embedded.lock().unwrap().extend(texts.iter().cloned());
+        let has = |text: &str, words: &[&str]| {
+            let text = text.to_lowercase();
+            if words.iter().any(|word| text.contains(word)) { 1.0 } else { 0.0 }
+        };
+        Ok(texts
+            .iter()
+            .map(|text| {
+                vec![
+                    has(text, &["rain", "umbrella", "storm"]),
+                    has(text, &["pasta", "dinner"]),
+                    1.0,
+                ]
+            })
+            .collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        3
+    }
+}
+
+#[tokio::test]
+async fn test_embedding_search_ranks_paraphrases_by_meaning() {
+    let entry = |text: &str| MemoryEntry {
+        content: Content::new("assistant").with_text(text),
+        author: "assistant".to_string(),
+        timestamp: Utc::now(),
+    };
+    let entries = || {
+        vec![
+            entry("I cooked pasta for dinner today"),
+            entry("Heavy rain is expected in Seattle this afternoon"),
+        ]
+    };
+    let search = |min_score| SearchRequest {
+        query: "Will I need an umbrella today?".to_string(),
+        user_id: "user1".to_string(),
+        app_name: "app1".to_string(),
+        limit: None,
+        min_score,
+        project_id: None,
+    };
+    let text = |memory: &MemoryEntry| memory.content.parts[0].text().unwrap().to_string();
+
+    // Keywords only match the unrelated entry on "today".
+    let keyword = InMemoryMemoryService::new();
+    keyword.add_session("app1", "user1", "s1", entries()).await.unwrap();
+    let memories = keyword.search(search(None)).await.unwrap().memories;
+    assert_eq!(memories.iter().map(text).collect::<Vec<_>>(), ["I cooked pasta for dinner today"]);
+
+    let embedder = Arc::new(TopicEmbedder { embedded: Default::default() });
+    let semantic = InMemoryMemoryService::new().with_embedding_provider(embedder.clone());
+    semantic.add_session("app1", "user1", "s1", entries()).await.unwrap();
+    let memories = semantic.search(search(None)).await.unwrap().memories;
+    assert_eq!(
+        memories.iter().map(text).collect::<Vec<_>>(),
+        ["Heavy rain is expected in Seattle this afternoon", "I cooked pasta for dinner today"]
+    );
+
+    // Similarities are 1.0 and 0.5.
+    let memories = semantic.search(search(Some(0.9))).await.unwrap().memories;
+    assert_eq!(memories.len(), 1);
+
+    // Entries were embedded once, when added; searches embed only the query.
+    assert_eq!(embedder.embedded.lock().unwrap().len(), 4);
+}
@@ -525,6 +525,7 @@ impl MistralRsAdapterModel {
             citation_metadata: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -590,6 +591,7 @@ impl Llm for MistralRsAdapterModel {
                                             error_message: None,
                                             provider_metadata: None,
                                             interaction_id: None,
+                                            citations: Vec::new(),
                                         };
                                         yield Ok(response);
                                     }
@@ -614,6 +616,7 @@ impl Llm for MistralRsAdapterModel {
                                         citation_metadata: None,
                                         provider_metadata: None,
                                         interaction_id: None,
+                                        citations: Vec::new(),
                                     };
                                     yield Ok(response);
                                 }
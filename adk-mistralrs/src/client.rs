@@ -375,6 +375,7 @@ impl MistralRsModel {
             citation_metadata: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -434,6 +435,7 @@ impl Llm for MistralRsModel {
                                             error_message: None,
                                             provider_metadata: None,
                                             interaction_id: None,
+                                            citations: Vec::new(),
                                         };
                                         yield Ok(response);
                                     }
@@ -458,6 +460,7 @@ impl Llm for MistralRsModel {
                                         citation_metadata: None,
                                         provider_metadata: None,
                                         interaction_id: None,
+                                        citations: Vec::new(),
                                     };
                                     yield Ok(response);
                                 }
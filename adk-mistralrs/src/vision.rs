@@ -445,6 +445,7 @@ impl MistralRsVisionModel {
             citation_metadata: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         }
     }
 }
@@ -500,6 +501,7 @@ impl Llm for MistralRsVisionModel {
                                             error_message: None,
                                             provider_metadata: None,
                                             interaction_id: None,
+                                            citations: Vec::new(),
                                         };
                                         yield Ok(response);
                                     }
@@ -524,6 +526,7 @@ impl Llm for MistralRsVisionModel {
                                         citation_metadata: None,
                                         provider_metadata: None,
                                         interaction_id: None,
+                                        citations: Vec::new(),
                                     };
                                     yield Ok(response);
                                 }
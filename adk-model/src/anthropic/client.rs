@@ -5,6 +5,7 @@ use super::convert;
 use super::error::AnthropicApiError;
 use super::rate_limit::RateLimitInfo;
 use super::schema_adapter::AnthropicSchemaAdapter;
+use crate::prompt_layout::PromptLayout;
 use crate::retry::{RetryConfig, ServerRetryHint, execute_with_retry, is_retryable_model_error};
 use adk_anthropic::{
     Anthropic, ContentBlock, ContentBlockDelta, ContentBlockDeltaEvent, MessageStreamEvent,
@@ -47,8 +48,8 @@ impl AnthropicClient {
             client,
             model: config.model.clone(),
             max_tokens: config.max_tokens,
+            retry_config: config.retry_config.clone(),
             config,
-            retry_config: RetryConfig::default(),
             latest_rate_limit: Arc::new(RwLock::new(RateLimitInfo::default())),
         })
     }
@@ -109,28 +110,13 @@ impl AnthropicClient {
         request: &LlmRequest,
         anthropic_config: &super::config::AnthropicConfig,
     ) -> Result<adk_anthropic::MessageCreateParams, AdkError> {
-        let mut system_parts: Vec<String> = Vec::new();
+        // Requirement 1.1: Extract system-role content text parts
+        let layout = PromptLayout::anthropic().normalize(&request.contents);
+        let mut system_parts: Vec<String> = layout.system_instruction.into_iter().collect();
         let mut messages = Vec::new();
 
-        for content in &request.contents {
-            if content.role == "system" {
-                // Requirement 1.1: Extract system-role content text parts
-                let text: String = content
-                    .parts
-                    .iter()
-                    .filter_map(|p| match p {
-                        Part::Text { text } => Some(text.clone()),
-                        _ => None,
-                    })
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                if !text.is_empty() {
-                    system_parts.push(text);
-                }
-            } else {
-                messages
-                    .push(convert::content_to_message(content, anthropic_config.prompt_caching)?);
-            }
+        for content in &layout.contents {
+            messages.push(convert::content_to_message(content, anthropic_config.prompt_caching)?);
         }
 
         // Requirement 1.2: Heuristic — re-route leading user-role text-only messages
@@ -226,6 +212,13 @@ impl AnthropicClient {
         } else {
             request.config.as_ref().and_then(|c| c.temperature)
         };
+        if let Some(config) = &request.config {
+            crate::generation_config::log_unsupported_fields(
+                "anthropic",
+                config,
+                &["temperature", "top_p", "top_k", "max_output_tokens", "stop_sequences"],
+            );
+        }
         let top_p = request.config.as_ref().and_then(|c| c.top_p);
         let top_k = request.config.as_ref().and_then(|c| c.top_k);
         let effective_max_tokens = request
@@ -241,7 +234,7 @@ impl AnthropicClient {
         // Claude "learns to avoid parallel calls" from the conversation history.
         merge_consecutive_messages(&mut messages);
 
-        Ok(convert::build_message_params(
+        let mut params = convert::build_message_params(
             model,
             effective_max_tokens,
             messages,
@@ -257,7 +250,13 @@ impl AnthropicClient {
             anthropic_config.inference_geo.as_deref(),
             anthropic_config.service_tier.as_deref(),
             anthropic_config.context_management.as_ref(),
-        ))
+        );
+        if let Some(config) = &request.config
+            && !config.stop_sequences.is_empty()
+        {
+            params.stop_sequences = Some(config.stop_sequences.clone());
+        }
+        Ok(params)
     }
 }
 
@@ -376,7 +375,11 @@ pub(super) fn convert_anthropic_error(e: adk_anthropic::Error) -> AdkError {
         "anthropic api error"
     );
 
-    api_error.into()
+    let error: AdkError = api_error.into();
+    match extract_retry_hint(&e).and_then(|hint| hint.retry_after) {
+        Some(delay) => error.with_retry_after(delay),
+        None => error,
+    }
 }
 
 /// Build an [`AnthropicApiError`] from an `adk_anthropic::Error`, extracting the
@@ -462,7 +465,6 @@ fn to_anthropic_api_error(e: &adk_anthropic::Error) -> AnthropicApiError {
 
 /// Extract a [`ServerRetryHint`] from an `adk_anthropic::Error`, if the error
 /// contains a server-provided `retry_after` value.
-#[allow(dead_code)]
 fn extract_retry_hint(e: &adk_anthropic::Error) -> Option<ServerRetryHint> {
     match e {
         adk_anthropic::Error::RateLimit { retry_after: Some(secs), .. }
@@ -484,6 +486,19 @@ impl Llm for AnthropicClient {
         &ADAPTER
     }
 
+    fn max_tools(&self) -> Option<usize> {
+        Some(super::ANTHROPIC_MAX_TOOLS)
+    }
+
+    /// Counts input tokens with the `/v1/messages/count_tokens` endpoint.
+    ///
+    /// Claude's tokenizer is not public, so this asks the API rather than
+    /// estimating locally. See the inherent [`AnthropicClient::count_tokens`].
+    async fn count_tokens(&self, request: &LlmRequest) -> Result<usize, AdkError> {
+        let count = AnthropicClient::count_tokens(self, request).await?;
+        Ok(count.input_tokens as usize)
+    }
+
     #[tracing::instrument(
         name = "model.generate_content",
         skip_all,
@@ -532,6 +547,7 @@ impl Llm for AnthropicClient {
                 let mut current_tool_calls: Vec<(String, String, String)> = Vec::new(); // (id, name, args_json)
                 let mut current_tool_index: Option<usize> = None;
                 let mut pending_server_parts: Vec<Part> = Vec::new();
+                let mut thinking_signature = String::new();
 
                 // Track usage from MessageStart for propagation to final MessageDelta
                 let mut stream_input_tokens: i32 = 0;
@@ -620,7 +636,9 @@ impl Llm for AnthropicClient {
                                     }
                                 }
                                 // Requirement 3.2: Accumulate signature deltas silently
-                                ContentBlockDelta::SignatureDelta(_) => {}
+                                ContentBlockDelta::SignatureDelta(sd) => {
+                                    thinking_signature.push_str(&sd.signature);
+                                }
                                 // Requirement 3.5: Log unrecognized deltas at debug level
                                 ContentBlockDelta::CitationsDelta(cd) => {
                                     debug!(?cd, "citations delta received (not yet mapped)");
@@ -629,6 +647,13 @@ impl Llm for AnthropicClient {
                         }
                         MessageStreamEvent::ContentBlockStop { .. } => {
                             current_tool_index = None;
+                            // Hand the finished thinking block's signature back so
+                            // the next request can replay the block.
+                            if !thinking_signature.is_empty() {
+                                yield convert::from_thinking_signature(&std::mem::take(
+                                    &mut thinking_signature,
+                                ));
+                            }
                         }
                         MessageStreamEvent::MessageDelta(delta_event) => {
                             // Check for stop reason
@@ -687,6 +712,7 @@ impl Llm for AnthropicClient {
                                         error_message: None,
                                         provider_metadata: None,
                                         interaction_id: None,
+                                        citations: Vec::new(),
                                     };
                                     continue;
                                 }
@@ -711,6 +737,7 @@ impl Llm for AnthropicClient {
                                     error_message: None,
                                     provider_metadata: None,
                                     interaction_id: None,
+                                    citations: Vec::new(),
                                 };
                             }
                         }
@@ -1070,6 +1097,34 @@ mod tests {
         assert_eq!(error.code, "model.anthropic.invalid_tool");
     }
 
+    #[test]
+    fn request_sampling_config_overrides_client_defaults() {
+        let mut request = make_request(vec![Content::new("user").with_text("Hi")]);
+        request.config = Some(GenerateContentConfig {
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            top_k: Some(40),
+            max_output_tokens: Some(256),
+            stop_sequences: vec!["END".to_string()],
+            seed: Some(7),
+            ..Default::default()
+        });
+
+        let params = AnthropicClient::build_message_params(
+            "claude-sonnet-4-5-20250929",
+            4096,
+            &request,
+            &AnthropicConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(params.max_tokens, 256);
+        assert_eq!(params.temperature, Some(0.2));
+        assert_eq!(params.top_p, Some(0.9));
+        assert_eq!(params.top_k, Some(40));
+        assert_eq!(params.stop_sequences, Some(vec!["END".to_string()]));
+    }
+
     /// Requirement 3.3: Ping events are treated as keep-alive no-ops and don't produce
     /// LlmResponse emissions. Verifies that the Ping variant of MessageStreamEvent
     /// is handled silently without yielding any content.
@@ -1331,6 +1386,29 @@ mod tests {
         assert!(msg.contains("req_test"), "Should contain request_id");
     }
 
+    #[test]
+    fn test_convert_anthropic_error_carries_retry_after() {
+        let err = adk_anthropic::Error::RateLimit {
+            message: "Too many requests".to_string(),
+            retry_after: Some(30),
+        };
+        let adk_err = convert_anthropic_error(err);
+        assert!(adk_err.is_rate_limited());
+        assert!(adk_err.is_retryable());
+        assert_eq!(adk_err.retry_after(), Some(std::time::Duration::from_secs(30)));
+        assert_eq!(adk_err.upstream_status(), Some(429));
+
+        let err = adk_anthropic::Error::Api {
+            status_code: 400,
+            error_type: Some("invalid_request_error".to_string()),
+            message: "bad".to_string(),
+            request_id: None,
+        };
+        let adk_err = convert_anthropic_error(err);
+        assert!(!adk_err.is_retryable());
+        assert_eq!(adk_err.upstream_status(), Some(400));
+    }
+
     // ---- Property-based tests for system prompt routing ----
 
     use proptest::prelude::*;
@@ -1,5 +1,6 @@
 //! Configuration types for Anthropic provider.
 
+use crate::retry::RetryConfig;
 use adk_anthropic::ToolSearchConfig;
 use serde::{Deserialize, Serialize};
 
@@ -127,6 +128,10 @@ pub struct AnthropicConfig {
     /// When `None`, all available tools are loaded.
     #[serde(skip)]
     pub tool_search: Option<ToolSearchConfig>,
+
+    /// Retry policy for transient failures such as rate limits.
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 fn default_max_tokens() -> u32 {
@@ -155,6 +160,7 @@ impl Default for AnthropicConfig {
             beta_features: Vec::new(),
             api_version: None,
             tool_search: None,
+            retry_config: RetryConfig::default(),
         }
     }
 }
@@ -261,4 +267,10 @@ impl AnthropicConfig {
         self.tool_search = Some(config);
         self
     }
+
+    /// Set the retry policy for transient failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
 }
@@ -6,8 +6,9 @@ use adk_anthropic::ImageMediaType;
 use adk_anthropic::{
     Base64ImageSource, Base64PdfSource, CacheControlEphemeral, ContentBlock, ContextManagement,
     DocumentBlock, ImageBlock, Message, MessageCreateParams, MessageParam, MessageRole, Model,
-    PlainTextSource, StopReason, SystemPrompt, TextBlock, ToolParam, ToolResultBlock,
-    ToolResultBlockContent, ToolUnionParam, ToolUseBlock, UrlImageSource, UrlPdfSource,
+    PlainTextSource, StopReason, SystemPrompt, TextBlock, ThinkingBlock, ToolParam,
+    ToolResultBlock, ToolResultBlockContent, ToolUnionParam, ToolUseBlock, UrlImageSource,
+    UrlPdfSource,
 };
 use adk_core::{
     Content, FinishReason, LlmResponse, Part, SchemaAdapter, SchemaCache, UsageMetadata,
@@ -46,8 +47,8 @@ pub fn content_to_message(
     // level only (max 4 blocks). Individual message blocks do not get cache_control
     // to avoid exceeding Anthropic's 4-block limit.
 
-    let blocks: Vec<ContentBlock> = content
-        .parts
+    let parts = merge_thinking_parts(&content.parts);
+    let blocks: Vec<ContentBlock> = parts
         .iter()
         .filter_map(|part| match part {
             Part::Text { text } => {
@@ -125,6 +126,16 @@ pub fn content_to_message(
                     ))))
                 }
             }
+            // Signed thinking goes back as-is; Claude requires it before the
+            // tool calls it led to when extended thinking is on.
+            Part::Thinking { thinking, signature: Some(signature) }
+                if role == MessageRole::Assistant =>
+            {
+                Some(ContentBlock::Thinking(ThinkingBlock::new(
+                    thinking.clone(),
+                    signature.clone(),
+                )))
+            }
             Part::Thinking { thinking, .. } => {
                 if thinking.is_empty() {
                     None
@@ -277,6 +288,7 @@ pub fn from_anthropic_message(message: &Message) -> (LlmResponse, HashMap<String
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         },
         cache_meta,
     )
@@ -299,9 +311,31 @@ pub fn from_text_delta(text: &str) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
+/// Joins runs of adjacent thinking parts, as left by streamed thinking deltas,
+/// into one part that keeps the run's signature.
+fn merge_thinking_parts(parts: &[Part]) -> Vec<Part> {
+    let mut merged: Vec<Part> = Vec::with_capacity(parts.len());
+    for part in parts {
+        if let (
+            Some(Part::Thinking { thinking, signature }),
+            Part::Thinking { thinking: next, signature: next_signature },
+        ) = (merged.last_mut(), part)
+        {
+            thinking.push_str(next);
+            if next_signature.is_some() {
+                signature.clone_from(next_signature);
+            }
+            continue;
+        }
+        merged.push(part.clone());
+    }
+    merged
+}
+
 /// Convert streaming thinking delta to ADK LlmResponse.
 pub fn from_thinking_delta(thinking_text: &str) -> LlmResponse {
     LlmResponse {
@@ -315,6 +349,26 @@ pub fn from_thinking_delta(thinking_text: &str) -> LlmResponse {
     }
 }
 
+/// Convert the signature that closes a streamed thinking block to ADK LlmResponse.
+///
+/// The signature arrives after the block's thinking deltas, so it is carried by
+/// an empty `Thinking` part that [`content_to_message`] merges back into the
+/// preceding thinking text.
+pub fn from_thinking_signature(signature: &str) -> LlmResponse {
+    LlmResponse {
+        content: Some(Content {
+            role: "model".to_string(),
+            parts: vec![Part::Thinking {
+                thinking: String::new(),
+                signature: Some(signature.to_string()),
+            }],
+        }),
+        partial: true,
+        turn_complete: false,
+        ..Default::default()
+    }
+}
+
 /// Create an LlmResponse representing a streaming error event.
 pub fn from_stream_error(error_type: &str, message: &str) -> LlmResponse {
     LlmResponse {
@@ -329,6 +383,7 @@ pub fn from_stream_error(error_type: &str, message: &str) -> LlmResponse {
         error_message: Some(message.to_string()),
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -701,6 +756,46 @@ mod tests {
         assert_eq!(block["content"], "hello");
     }
 
+    #[test]
+    fn test_streamed_thinking_is_replayed_as_one_signed_block() {
+        let mut content = Content::new("model");
+        for chunk in [
+            convert_thinking_chunk(from_thinking_delta("Check the ")),
+            convert_thinking_chunk(from_thinking_delta("units.")),
+            convert_thinking_chunk(from_thinking_signature("sig_abc")),
+        ] {
+            content.parts.extend(chunk);
+        }
+        content.parts.push(Part::FunctionCall {
+            name: "convert".to_string(),
+            args: serde_json::json!({ "km": 5 }),
+            id: Some("toolu_1".to_string()),
+            thought_signature: None,
+        });
+
+        let message = content_to_message(&content, false).expect("content should convert");
+        let json = serde_json::to_value(message).expect("message should serialize");
+        let blocks = json["content"].as_array().expect("blocks");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["type"], "thinking");
+        assert_eq!(blocks[0]["thinking"], "Check the units.");
+        assert_eq!(blocks[0]["signature"], "sig_abc");
+        assert_eq!(blocks[1]["type"], "tool_use");
+    }
+
+    #[test]
+    fn test_unsigned_thinking_is_not_replayed_as_a_thinking_block() {
+        let content = Content::new("model").with_thinking("from another provider");
+
+        let message = content_to_message(&content, false).expect("content should convert");
+        let json = serde_json::to_value(message).expect("message should serialize");
+        assert_eq!(json["content"][0]["type"], "text");
+    }
+
+    fn convert_thinking_chunk(response: LlmResponse) -> Vec<Part> {
+        response.content.expect("chunk content").parts
+    }
+
     #[test]
     fn test_from_anthropic_message_with_thinking_block() {
         use adk_anthropic::{ThinkingBlock, Usage};
@@ -60,20 +60,15 @@ impl From<AnthropicApiError> for AdkError {
     fn from(err: AnthropicApiError) -> Self {
         use adk_core::{ErrorCategory, ErrorComponent};
         let msg = err.to_string();
-        let category = match err.status_code {
-            401 => ErrorCategory::Unauthorized,
-            403 => ErrorCategory::Forbidden,
-            404 => ErrorCategory::NotFound,
-            408 => ErrorCategory::Timeout,
-            429 => ErrorCategory::RateLimited,
-            503 | 529 => ErrorCategory::Unavailable,
-            s if s >= 500 => ErrorCategory::Internal,
-            _ => ErrorCategory::InvalidInput,
-        };
-        let mut adk_err =
-            AdkError::new(ErrorComponent::Model, category, "model.anthropic.api_error", msg)
-                .with_upstream_status(err.status_code)
-                .with_provider("anthropic");
+        let code = "model.anthropic.api_error";
+        let mut adk_err = if err.status_code == 0 {
+            // Client-side failures (connection, serialization) carry no HTTP status.
+            AdkError::new(ErrorComponent::Model, ErrorCategory::InvalidInput, code, msg)
+                .with_upstream_status(0)
+        } else {
+            AdkError::from_http_status(ErrorComponent::Model, code, err.status_code, msg)
+        }
+        .with_provider("anthropic");
         if let Some(ref rid) = err.request_id {
             adk_err = adk_err.with_request_id(rid.clone());
         }
@@ -100,5 +100,9 @@ pub use rate_limit::RateLimitInfo;
 pub use schema_adapter::AnthropicSchemaAdapter;
 pub use token_count::TokenCount;
 
+/// Maximum number of tools Anthropic accepts in a single request, counting
+/// tools deferred to tool search.
+pub(crate) const ANTHROPIC_MAX_TOOLS: usize = 10_000;
+
 // Re-export ToolSearchConfig from adk-anthropic for convenience.
 pub use adk_anthropic::ToolSearchConfig;
@@ -40,7 +40,6 @@ pub(crate) fn inline_attachment_to_text(mime_type: &str, data: &[u8]) -> String
 /// Convert file URI attachments into a text payload for providers without URI-native attachment
 /// support.
 #[cfg(any(
-    feature = "gemini",
     feature = "openai",
     feature = "anthropic",
     feature = "ollama",
@@ -101,6 +101,14 @@ impl Llm for AzureAIClient {
         request: LlmRequest,
         stream: bool,
     ) -> Result<LlmResponseStream, AdkError> {
+        if let Some(mime_type) = convert::first_attachment_mime(&request.contents) {
+            return Err(AdkError::unsupported(
+                ErrorComponent::Model,
+                "model.azure_ai.unsupported_media",
+                format!("Azure AI Inference does not accept {mime_type} attachments"),
+            ));
+        }
+
         let usage_span = adk_telemetry::llm_generate_span("azure-ai", &self.model, stream);
         let api_url = self.api_url();
         let api_key = self.api_key.clone();
@@ -8,6 +8,7 @@
 //! All functions in this module are `pub(crate)` since they are internal
 //! implementation details of the Azure AI provider.
 
+use crate::prompt_layout::PromptLayout;
 use adk_core::{Content, FinishReason, GenerateContentConfig, LlmResponse, Part, UsageMetadata};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -23,6 +24,8 @@ pub(crate) fn build_request_body(
     config: Option<&GenerateContentConfig>,
     stream: bool,
 ) -> Value {
+    // Azure AI Inference speaks the Chat Completions format.
+    let contents = PromptLayout::openai().normalize(contents).contents;
     let messages: Vec<Value> = contents.iter().map(content_to_message).collect();
 
     let mut body = serde_json::json!({
@@ -69,6 +72,14 @@ pub(crate) fn build_request_body(
     body
 }
 
+/// Returns the MIME type of the first inline or file attachment in `contents`.
+///
+/// The Azure AI Inference chat format carries text only, so requests with
+/// attachments are rejected rather than sent without them.
+pub(crate) fn first_attachment_mime(contents: &[Content]) -> Option<&str> {
+    contents.iter().flat_map(|content| &content.parts).find_map(Part::mime_type)
+}
+
 /// Convert a single ADK `Content` to an Azure AI message JSON object.
 fn content_to_message(content: &Content) -> Value {
     match content.role.as_str() {
@@ -259,6 +270,7 @@ pub(crate) fn parse_response(body: &Value) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -338,6 +350,7 @@ pub(crate) fn parse_sse_chunk(chunk: &Value) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -357,6 +370,19 @@ mod tests {
     use super::*;
     use adk_core::FunctionResponseData;
 
+    #[test]
+    fn test_first_attachment_mime_finds_media_parts() {
+        let text_only = vec![Content::new("user").with_text("hi")];
+        assert_eq!(first_attachment_mime(&text_only), None);
+
+        let with_image = vec![
+            Content::new("user")
+                .with_text("what is this?")
+                .with_inline_data("image/png", vec![0x89, 0x50]),
+        ];
+        assert_eq!(first_attachment_mime(&with_image), Some("image/png"));
+    }
+
     #[test]
     fn test_build_request_body_basic() {
         let contents = vec![Content {
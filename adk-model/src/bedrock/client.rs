@@ -6,11 +6,11 @@
 
 use super::config::BedrockConfig;
 use super::convert::{
-    adk_request_to_bedrock, bedrock_response_to_adk, bedrock_stream_content_start_to_adk,
-    bedrock_stream_delta_to_adk, bedrock_stream_stop_to_adk,
+    ToolUseAssembler, adk_request_to_bedrock, bedrock_response_to_adk, bedrock_stream_delta_to_adk,
+    bedrock_stream_stop_to_adk, first_unsupported_media, supports_prompt_caching,
 };
 use crate::retry::RetryConfig;
-use adk_core::{AdkError, Llm, LlmRequest, LlmResponse, LlmResponseStream};
+use adk_core::{AdkError, ErrorComponent, Llm, LlmRequest, LlmResponse, LlmResponseStream};
 use async_stream::try_stream;
 use async_trait::async_trait;
 use aws_sdk_bedrockruntime::types::ConverseStreamOutput;
@@ -54,7 +54,15 @@ impl BedrockClient {
     pub async fn new(config: BedrockConfig) -> Result<Self, AdkError> {
         let region = config.region.clone();
         let model_id = config.model_id.clone();
-        let prompt_caching = config.prompt_caching.clone();
+        // Models without cache checkpoints, such as Llama, reject requests
+        // that carry them.
+        let prompt_caching = config.prompt_caching.clone().filter(|_| {
+            let supported = supports_prompt_caching(&model_id);
+            if !supported {
+                debug!("prompt caching is not available for model={model_id}, skipping it");
+            }
+            supported
+        });
 
         let mut sdk_config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
             .region(aws_config::Region::new(config.region.clone()));
@@ -101,6 +109,14 @@ impl Llm for BedrockClient {
         request: LlmRequest,
         stream: bool,
     ) -> Result<LlmResponseStream, AdkError> {
+        if let Some(mime_type) = first_unsupported_media(&request.contents) {
+            return Err(AdkError::unsupported(
+                ErrorComponent::Model,
+                "model.bedrock.unsupported_media",
+                format!("Bedrock Converse does not accept {mime_type} attachments"),
+            ));
+        }
+
         let usage_span = adk_telemetry::llm_generate_span("bedrock", &self.model_id, stream);
         let bedrock_input = adk_request_to_bedrock(
             &request.contents,
@@ -115,7 +131,7 @@ impl Llm for BedrockClient {
             ))
         })?;
 
-        if stream && request.tools.is_empty() {
+        if stream {
             let result = self.generate_streaming(bedrock_input).await?;
             Ok(crate::usage_tracking::with_usage_tracking(result, usage_span))
         } else {
@@ -192,10 +208,8 @@ impl BedrockClient {
         let region = self.region.clone();
 
         let response_stream = try_stream! {
-            // Track tool use state for accumulating partial JSON arguments.
-            let mut tool_name: Option<String> = None;
-            let mut tool_id: Option<String> = None;
-            let mut tool_args_buf = String::new();
+            // Assembles tool use input streamed across deltas.
+            let mut tool_use = ToolUseAssembler::default();
             // Track reasoning signature for the current reasoning content block.
             let mut reasoning_signature: Option<String> = None;
             // Buffer the stop response and usage metadata so they can be merged.
@@ -211,31 +225,16 @@ impl BedrockClient {
             })? {
                 match event {
                     ConverseStreamOutput::ContentBlockStart(start_event) => {
+                        // A tool use block starts with the tool name and ID; the
+                        // call is emitted once its arguments are complete.
                         if let Some(start) = &start_event.start {
-                            // If starting a tool use block, track the name and ID.
-                            if let aws_sdk_bedrockruntime::types::ContentBlockStart::ToolUse(
-                                tool_start,
-                            ) = start
-                            {
-                                tool_name = Some(tool_start.name.clone());
-                                tool_id = Some(tool_start.tool_use_id.clone());
-                                tool_args_buf.clear();
-                            }
-
-                            if let Some(response) = bedrock_stream_content_start_to_adk(start) {
-                                yield response;
-                            }
+                            tool_use.start(start);
                         }
                     }
                     ConverseStreamOutput::ContentBlockDelta(delta_event) => {
                         if let Some(delta) = &delta_event.delta {
                             // Accumulate tool use argument deltas.
-                            if let aws_sdk_bedrockruntime::types::ContentBlockDelta::ToolUse(
-                                tool_delta,
-                            ) = delta
-                            {
-                                tool_args_buf.push_str(&tool_delta.input);
-                            }
+                            tool_use.delta(delta);
 
                             // Capture reasoning signature deltas for later attachment.
                             if let aws_sdk_bedrockruntime::types::ContentBlockDelta::ReasoningContent(
@@ -252,32 +251,8 @@ impl BedrockClient {
                     }
                     ConverseStreamOutput::ContentBlockStop(_) => {
                         // If we were accumulating tool arguments, emit the complete FunctionCall.
-                        if let (Some(name), Some(id)) = (tool_name.take(), tool_id.take()) {
-                            let args: serde_json::Value =
-                                serde_json::from_str(&tool_args_buf).unwrap_or_default();
-                            tool_args_buf.clear();
-
-                            yield LlmResponse {
-                                content: Some(adk_core::Content {
-                                    role: "model".to_string(),
-                                    parts: vec![adk_core::Part::FunctionCall {
-                                        name,
-                                        args,
-                                        id: Some(id),
-                                        thought_signature: None,
-                                    }],
-                                }),
-                                usage_metadata: None,
-                                finish_reason: None,
-                                citation_metadata: None,
-                                partial: false,
-                                turn_complete: false,
-                                interrupted: false,
-                                error_code: None,
-                                error_message: None,
-                                provider_metadata: None,
-                                interaction_id: None,
-                            };
+                        if let Some(call) = tool_use.stop() {
+                            yield call;
                         }
 
                         // If we accumulated a reasoning signature, emit it as a
@@ -302,6 +277,7 @@ impl BedrockClient {
                                 error_message: None,
                                 provider_metadata: None,
                                 interaction_id: None,
+                                citations: Vec::new(),
                             };
                         }
                     }
@@ -54,6 +54,8 @@ pub struct BedrockCacheConfig {
 ///
 /// Prompt caching is enabled by default with a 5-minute TTL.
 /// Use [`without_prompt_caching`](BedrockConfig::without_prompt_caching) to disable.
+/// It only applies to Anthropic Claude and Amazon Nova models; for other
+/// models, such as Meta Llama, the client leaves cache checkpoints out.
 ///
 /// # Inference Profiles
 ///
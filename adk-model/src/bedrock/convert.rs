@@ -48,8 +48,10 @@ pub(crate) fn adk_request_to_bedrock(
 ) -> Result<BedrockConverseInput, String> {
     let mut messages: Vec<Message> = Vec::new();
     let mut system = Vec::new();
+    let mut contents = contents.to_vec();
+    crate::tool_result::link_tool_call_ids(&mut contents);
 
-    for content in contents {
+    for content in &contents {
         match content.role.as_str() {
             "system" => {
                 for part in &content.parts {
@@ -113,6 +115,20 @@ pub(crate) fn adk_request_to_bedrock(
     Ok(BedrockConverseInput { messages, system, inference_config, tool_config })
 }
 
+/// Returns `true` if `model_id` accepts `CachePoint` blocks.
+///
+/// Bedrock supports prompt caching for Anthropic Claude and Amazon Nova
+/// models, addressed directly or through a cross-region inference profile
+/// (`us.`, `eu.`, `global.` and so on). Other families, such as Meta Llama,
+/// reject requests that contain cache checkpoints.
+pub(crate) fn supports_prompt_caching(model_id: &str) -> bool {
+    // Inference profile and foundation model ARNs end with the model ID.
+    let model_id = model_id.rsplit('/').next().unwrap_or(model_id);
+    ["anthropic.claude", "amazon.nova"]
+        .iter()
+        .any(|family| model_id.starts_with(family) || model_id.contains(&format!(".{family}")))
+}
+
 /// Build a `CachePointBlock` from the given cache configuration.
 ///
 /// Sets the TTL to 1 hour when `BedrockCacheTtl::OneHour` is configured;
@@ -187,7 +203,7 @@ fn adk_parts_to_bedrock(parts: &[Part]) -> Vec<ContentBlock> {
                         .ok()
                         .map(ContentBlock::Document)
                 } else {
-                    // Unsupported MIME type — skip silently
+                    // Rejected up front by `first_unsupported_media`.
                     None
                 }
             }
@@ -212,6 +228,27 @@ fn adk_parts_to_bedrock(parts: &[Part]) -> Vec<ContentBlock> {
         .collect()
 }
 
+/// Returns the MIME type of the first attachment in `contents` that the
+/// Converse API cannot carry, so the request can be rejected instead of
+/// silently dropping it.
+pub(crate) fn first_unsupported_media(contents: &[Content]) -> Option<&str> {
+    contents.iter().flat_map(|content| &content.parts).find_map(|part| match part {
+        Part::InlineData { mime_type, .. }
+            if mime_to_bedrock_image_format(mime_type).is_none()
+                && mime_to_bedrock_document_format(mime_type).is_none() =>
+        {
+            Some(mime_type.as_str())
+        }
+        Part::FileData { mime_type, .. }
+            if !mime_type.starts_with("image/")
+                && mime_to_bedrock_document_format(mime_type).is_none() =>
+        {
+            Some(mime_type.as_str())
+        }
+        _ => None,
+    })
+}
+
 /// Map a MIME type to a Bedrock `ImageFormat`, if supported.
 fn mime_to_bedrock_image_format(mime_type: &str) -> Option<BedrockImageFormat> {
     match mime_type {
@@ -343,6 +380,7 @@ pub(crate) fn bedrock_response_to_adk(
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -423,43 +461,6 @@ fn bedrock_stop_reason_to_adk(stop_reason: &StopReason) -> FinishReason {
 
 // --- Streaming conversion helpers ---
 
-/// Convert a streaming `ContentBlockStart` event to an ADK `LlmResponse`.
-///
-/// This handles the start of a tool use block, which provides the tool name and ID.
-/// Text blocks don't have a start event with content.
-pub(crate) fn bedrock_stream_content_start_to_adk(
-    start: &ContentBlockStart,
-) -> Option<LlmResponse> {
-    match start {
-        ContentBlockStart::ToolUse(tool_start) => {
-            // Return a partial response with the tool call metadata.
-            // The actual arguments will come in subsequent delta events.
-            Some(LlmResponse {
-                content: Some(Content {
-                    role: "model".to_string(),
-                    parts: vec![Part::FunctionCall {
-                        name: tool_start.name.clone(),
-                        args: Value::Null,
-                        id: Some(tool_start.tool_use_id.clone()),
-                        thought_signature: None,
-                    }],
-                }),
-                usage_metadata: None,
-                finish_reason: None,
-                citation_metadata: None,
-                partial: true,
-                turn_complete: false,
-                interrupted: false,
-                error_code: None,
-                error_message: None,
-                provider_metadata: None,
-                interaction_id: None,
-            })
-        }
-        _ => None,
-    }
-}
-
 /// Convert a streaming `ContentBlockDelta` event to an ADK `LlmResponse`.
 ///
 /// Handles text deltas, tool use input deltas, and reasoning content deltas.
@@ -484,6 +485,7 @@ pub(crate) fn bedrock_stream_delta_to_adk(delta: &ContentBlockDelta) -> Option<L
                     error_message: None,
                     provider_metadata: None,
                     interaction_id: None,
+                    citations: Vec::new(),
                 })
             }
         }
@@ -510,6 +512,7 @@ pub(crate) fn bedrock_stream_delta_to_adk(delta: &ContentBlockDelta) -> Option<L
                         error_message: None,
                         provider_metadata: None,
                         interaction_id: None,
+                        citations: Vec::new(),
                     })
                 }
             } else {
@@ -521,6 +524,73 @@ pub(crate) fn bedrock_stream_delta_to_adk(delta: &ContentBlockDelta) -> Option<L
     }
 }
 
+/// Assembles a streamed tool use block into one `FunctionCall` response.
+///
+/// Bedrock streams a tool's input as JSON fragments between the block's
+/// `ContentBlockStart` and `ContentBlockStop` events, so the call is only
+/// complete once the block stops.
+#[derive(Debug, Default)]
+pub(crate) struct ToolUseAssembler {
+    /// Name and ID of the tool use block in progress.
+    tool: Option<(String, String)>,
+    input: String,
+}
+
+impl ToolUseAssembler {
+    /// Begin a tool use block if `start` opens one.
+    pub(crate) fn start(&mut self, start: &ContentBlockStart) {
+        if let ContentBlockStart::ToolUse(tool_start) = start {
+            self.tool = Some((tool_start.name.clone(), tool_start.tool_use_id.clone()));
+            self.input.clear();
+        }
+    }
+
+    /// Append the input fragment carried by `delta`, if any.
+    pub(crate) fn delta(&mut self, delta: &ContentBlockDelta) {
+        if let ContentBlockDelta::ToolUse(tool_delta) = delta {
+            self.input.push_str(&tool_delta.input);
+        }
+    }
+
+    /// End the current block, returning its `FunctionCall` if it was a tool use.
+    pub(crate) fn stop(&mut self) -> Option<LlmResponse> {
+        let (name, id) = self.tool.take()?;
+        let input = std::mem::take(&mut self.input);
+        // Tools without parameters stream no input at all.
+        let args = if input.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&input).unwrap_or_else(|e| {
+                tracing::warn!(tool = %name, error = %e, "bedrock tool use input is not valid JSON");
+                serde_json::json!({})
+            })
+        };
+
+        Some(LlmResponse {
+            content: Some(Content {
+                role: "model".to_string(),
+                parts: vec![Part::FunctionCall {
+                    name,
+                    args,
+                    id: Some(id),
+                    thought_signature: None,
+                }],
+            }),
+            usage_metadata: None,
+            finish_reason: None,
+            citation_metadata: None,
+            partial: false,
+            turn_complete: false,
+            interrupted: false,
+            error_code: None,
+            error_message: None,
+            provider_metadata: None,
+            interaction_id: None,
+            citations: Vec::new(),
+        })
+    }
+}
+
 /// Convert a streaming `MessageStop` event to an ADK `LlmResponse`.
 pub(crate) fn bedrock_stream_stop_to_adk(stop_reason: &StopReason) -> LlmResponse {
     LlmResponse {
@@ -535,6 +605,7 @@ pub(crate) fn bedrock_stream_stop_to_adk(stop_reason: &StopReason) -> LlmRespons
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -595,6 +666,20 @@ mod tests {
     use super::*;
     use adk_core::FunctionResponseData;
 
+    #[test]
+    fn test_first_unsupported_media_allows_images_and_documents() {
+        let supported = vec![
+            Content::new("user")
+                .with_inline_data("image/png", vec![0x89, 0x50])
+                .with_inline_data("application/pdf", b"%PDF".to_vec())
+                .with_file_uri("image/jpeg", "s3://bucket/photo.jpg"),
+        ];
+        assert_eq!(first_unsupported_media(&supported), None);
+
+        let audio = vec![Content::new("user").with_inline_data("audio/wav", vec![0x52, 0x49])];
+        assert_eq!(first_unsupported_media(&audio), Some("audio/wav"));
+    }
+
     #[test]
     fn test_json_value_to_document_roundtrip() {
         let value = serde_json::json!({
@@ -852,6 +937,27 @@ mod tests {
         assert!(bedrock_stream_delta_to_adk(&delta).is_none());
     }
 
+    #[test]
+    fn test_prompt_caching_support_by_model_family() {
+        for model_id in [
+            "anthropic.claude-3-5-haiku-20241022-v1:0",
+            "us.anthropic.claude-sonnet-4-6",
+            "global.anthropic.claude-opus-4-5-20251101-v1:0",
+            "us.amazon.nova-pro-v1:0",
+            "arn:aws:bedrock:us-east-1:123456789012:inference-profile/us.anthropic.claude-sonnet-4-6",
+        ] {
+            assert!(supports_prompt_caching(model_id), "{model_id}");
+        }
+        for model_id in [
+            "meta.llama3-1-70b-instruct-v1:0",
+            "us.meta.llama3-3-70b-instruct-v1:0",
+            "mistral.mistral-large-2407-v1:0",
+            "cohere.command-r-plus-v1:0",
+        ] {
+            assert!(!supports_prompt_caching(model_id), "{model_id}");
+        }
+    }
+
     #[test]
     fn test_stream_stop_event() {
         let response = bedrock_stream_stop_to_adk(&StopReason::EndTurn);
@@ -1229,4 +1335,55 @@ mod tests {
             _ => panic!("expected Part::InlineData"),
         }
     }
+
+    fn tool_use_start(name: &str, id: &str) -> ContentBlockStart {
+        ContentBlockStart::ToolUse(
+            bedrock::ToolUseBlockStart::builder().name(name).tool_use_id(id).build().unwrap(),
+        )
+    }
+
+    fn tool_use_delta(input: &str) -> ContentBlockDelta {
+        ContentBlockDelta::ToolUse(
+            bedrock::ToolUseBlockDelta::builder().input(input).build().unwrap(),
+        )
+    }
+
+    fn function_call(response: LlmResponse) -> (String, Value, Option<String>) {
+        match response.content.unwrap().parts.remove(0) {
+            Part::FunctionCall { name, args, id, .. } => (name, args, id),
+            other => panic!("expected a function call, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_tool_use_input_is_assembled_across_deltas() {
+        let mut assembler = ToolUseAssembler::default();
+        assembler.start(&tool_use_start("get_weather", "tooluse-1"));
+        assembler.delta(&tool_use_delta(r#"{"city": "#));
+        assembler.delta(&ContentBlockDelta::Text("ignored".to_string()));
+        assembler.delta(&tool_use_delta(r#""Paris", "days": 3}"#));
+
+        let call = assembler.stop().expect("a tool use block was open");
+        assert!(!call.partial);
+        assert_eq!(
+            function_call(call),
+            (
+                "get_weather".to_string(),
+                serde_json::json!({ "city": "Paris", "days": 3 }),
+                Some("tooluse-1".to_string())
+            )
+        );
+        // The block is closed; a text block's stop emits nothing.
+        assert!(assembler.stop().is_none());
+    }
+
+    #[test]
+    fn test_tool_use_without_input_gets_empty_object() {
+        let mut assembler = ToolUseAssembler::default();
+        assembler.start(&tool_use_start("list_orders", "tooluse-2"));
+
+        let (name, args, _) = function_call(assembler.stop().unwrap());
+        assert_eq!(name, "list_orders");
+        assert_eq!(args, serde_json::json!({}));
+    }
 }
@@ -0,0 +1,492 @@
+//! Content policy enforcement on prompts and responses, for any provider.
+//!
+//! Guardrails configured per agent are easy to forget on one agent and hard to
+//! audit. [`FilteredLlm`] wraps a model instead, so every agent that uses it is
+//! held to the same policy whatever the provider:
+//!
+//! - the latest user message is checked before the request is sent, and
+//! - the answer text is checked before it is handed back.
+//!
+//! When a [`ContentFilter`] blocks, the caller receives a refusal response
+//! (finish reason [`FinishReason::Safety`], error code [`CONTENT_FILTERED`])
+//! instead of the model output, and a [`ContentFilterEvent`] is logged and
+//! passed to the audit hook.
+//!
+//! Two filters are built in: [`BlocklistFilter`] for keywords and regular
+//! expressions, and [`ModerationModelFilter`], which asks a model to judge the
+//! text against a written policy.
+//!
+//! Response filtering needs the whole answer, so when response filters are
+//! configured, streamed chunks are held back until the response is complete.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_model::content_filter::{BlocklistFilter, FilteredLlm};
+//! use std::sync::Arc;
+//!
+//! let blocklist = BlocklistFilter::new()
+//!     .with_keywords(["project aurora", "internal only"])
+//!     .with_pattern(r"\b\d{3}-\d{2}-\d{4}\b")?;
+//! let model = Arc::new(
+//!     FilteredLlm::new(Arc::new(gemini))
+//!         .with_filter(Arc::new(blocklist))
+//!         .with_audit_hook(Arc::new(|event| audit_log.record(event))),
+//! );
+//! let agent = LlmAgentBuilder::new("assistant").model(model).build()?;
+//! ```
+
+use adk_core::{
+    AdkError, Content, FinishReason, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+
+/// `error_code` of the refusal returned when a filter blocks.
+pub const CONTENT_FILTERED: &str = "CONTENT_FILTERED";
+
+/// Refusal text used unless [`FilteredLlm::with_refusal_message`] sets another.
+pub const DEFAULT_REFUSAL_MESSAGE: &str = "I can't help with that request.";
+
+/// Which side of the model call is being checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterDirection {
+    /// Text sent to the model.
+    Prompt,
+    /// Text generated by the model.
+    Response,
+}
+
+impl std::fmt::Display for FilterDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prompt => f.write_str("prompt"),
+            Self::Response => f.write_str("response"),
+        }
+    }
+}
+
+/// Outcome of a [`ContentFilter`] check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterDecision {
+    /// The text complies with the policy.
+    Allow,
+    /// The text violates the policy.
+    Block {
+        /// Why the text was blocked, for the audit trail.
+        reason: String,
+    },
+}
+
+impl FilterDecision {
+    /// Block with `reason`.
+    pub fn block(reason: impl Into<String>) -> Self {
+        Self::Block { reason: reason.into() }
+    }
+}
+
+/// Checks text against a content policy.
+#[async_trait]
+pub trait ContentFilter: Send + Sync {
+    /// Name recorded in audit events.
+    fn name(&self) -> &str;
+
+    /// Check `text` travelling in `direction`.
+    ///
+    /// An error fails the model call; it does not let the text through.
+    async fn check(&self, text: &str, direction: FilterDirection) -> Result<FilterDecision>;
+}
+
+/// Record of a blocked prompt or response.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentFilterEvent {
+    /// Name of the wrapped model.
+    pub model: String,
+    /// Name of the filter that blocked.
+    pub filter: String,
+    /// Whether the prompt or the response was blocked.
+    pub direction: FilterDirection,
+    /// Reason given by the filter.
+    pub reason: String,
+    /// When the block happened.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Receives every [`ContentFilterEvent`], for example to write an audit log.
+pub type AuditHook = Arc<dyn Fn(&ContentFilterEvent) + Send + Sync>;
+
+/// Blocks text containing listed keywords or matching regular expressions.
+///
+/// Keywords match case-insensitively on word boundaries, so `"secret"` blocks
+/// "Top SECRET plans" but not "secretary".
+#[derive(Debug, Clone, Default)]
+pub struct BlocklistFilter {
+    rules: Vec<(String, Regex)>,
+}
+
+impl BlocklistFilter {
+    /// An empty blocklist that allows everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Block text containing `keyword`.
+    #[must_use]
+    pub fn with_keyword(mut self, keyword: impl Into<String>) -> Self {
+        let keyword = keyword.into();
+        let pattern = format!(r"(?i)\b{}\b", regex::escape(keyword.trim()));
+        // An escaped literal is always a valid pattern.
+        if let Ok(regex) = Regex::new(&pattern) {
+            self.rules.push((format!("blocked keyword \"{keyword}\""), regex));
+        }
+        self
+    }
+
+    /// Block text containing any of `keywords`.
+    #[must_use]
+    pub fn with_keywords<I, S>(self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        keywords.into_iter().fold(self, Self::with_keyword)
+    }
+
+    /// Block text matching the regular expression `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `pattern` is not a valid regular
+    /// expression.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| AdkError::config(format!("invalid blocklist pattern '{pattern}': {e}")))?;
+        self.rules.push((format!("blocked pattern /{pattern}/"), regex));
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl ContentFilter for BlocklistFilter {
+    fn name(&self) -> &str {
+        "blocklist"
+    }
+
+    async fn check(&self, text: &str, _direction: FilterDirection) -> Result<FilterDecision> {
+        Ok(self
+            .rules
+            .iter()
+            .find(|(_, regex)| regex.is_match(text))
+            .map_or(FilterDecision::Allow, |(rule, _)| {
+                FilterDecision::block(format!("matched {rule}"))
+            }))
+    }
+}
+
+/// Asks a moderation model whether text complies with a written policy.
+///
+/// The model is told to answer `SAFE`, or `UNSAFE: <reason>`. Any answer that
+/// does not start with `UNSAFE` allows the text.
+pub struct ModerationModelFilter {
+    model: Arc<dyn Llm>,
+    policy: String,
+}
+
+impl ModerationModelFilter {
+    /// Moderate with `model` against a general policy on harmful content.
+    pub fn new(model: Arc<dyn Llm>) -> Self {
+        Self {
+            model,
+            policy: "No hate speech, harassment, sexual content involving minors, instructions \
+                     for violence or weapons, self-harm encouragement, or illegal activity."
+                .to_string(),
+        }
+    }
+
+    /// Moderate against `policy` instead of the general one.
+    #[must_use]
+    pub fn with_policy(mut self, policy: impl Into<String>) -> Self {
+        self.policy = policy.into();
+        self
+    }
+}
+
+#[async_trait]
+impl ContentFilter for ModerationModelFilter {
+    fn name(&self) -> &str {
+        "moderation_model"
+    }
+
+    async fn check(&self, text: &str, direction: FilterDirection) -> Result<FilterDecision> {
+        let prompt = format!(
+            "You are a content moderator. Policy:\n{}\n\nReply with SAFE if the {direction} below complies with the policy, or UNSAFE: <short reason> if it does not.\n\n{direction}:\n{text}",
+            self.policy
+        );
+        let request =
+            LlmRequest::new(self.model.name(), vec![Content::new("user").with_text(prompt)]);
+        let mut stream = self.model.generate_content(request, false).await?;
+        let mut answer = String::new();
+        while let Some(response) = stream.next().await {
+            if let Some(text) = response?.answer_text() {
+                answer.push_str(&text);
+            }
+        }
+
+        let answer = answer.trim();
+        if !answer.to_uppercase().starts_with("UNSAFE") {
+            return Ok(FilterDecision::Allow);
+        }
+        let reason = answer
+            .get("UNSAFE".len()..)
+            .unwrap_or_default()
+            .trim_start_matches([':', ' ', '-'])
+            .trim();
+        Ok(FilterDecision::block(if reason.is_empty() {
+            "flagged by moderation model".to_string()
+        } else {
+            reason.to_string()
+        }))
+    }
+}
+
+/// Wraps a model so prompts and responses pass through content filters.
+pub struct FilteredLlm {
+    inner: Arc<dyn Llm>,
+    prompt_filters: Vec<Arc<dyn ContentFilter>>,
+    response_filters: Vec<Arc<dyn ContentFilter>>,
+    refusal_message: String,
+    audit_hook: Option<AuditHook>,
+}
+
+impl FilteredLlm {
+    /// Wrap `inner` with no filters.
+    pub fn new(inner: Arc<dyn Llm>) -> Self {
+        Self {
+            inner,
+            prompt_filters: Vec::new(),
+            response_filters: Vec::new(),
+            refusal_message: DEFAULT_REFUSAL_MESSAGE.to_string(),
+            audit_hook: None,
+        }
+    }
+
+    /// Check both prompts and responses with `filter`.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Arc<dyn ContentFilter>) -> Self {
+        self.prompt_filters.push(filter.clone());
+        self.response_filters.push(filter);
+        self
+    }
+
+    /// Check only prompts with `filter`.
+    #[must_use]
+    pub fn with_prompt_filter(mut self, filter: Arc<dyn ContentFilter>) -> Self {
+        self.prompt_filters.push(filter);
+        self
+    }
+
+    /// Check only responses with `filter`.
+    #[must_use]
+    pub fn with_response_filter(mut self, filter: Arc<dyn ContentFilter>) -> Self {
+        self.response_filters.push(filter);
+        self
+    }
+
+    /// Text of the refusal returned in place of blocked content.
+    #[must_use]
+    pub fn with_refusal_message(mut self, message: impl Into<String>) -> Self {
+        self.refusal_message = message.into();
+        self
+    }
+
+    /// Call `hook` with every block, in addition to logging it.
+    #[must_use]
+    pub fn with_audit_hook(mut self, hook: AuditHook) -> Self {
+        self.audit_hook = Some(hook);
+        self
+    }
+
+    /// Run `filters` over `text` and turn the first block into a refusal.
+    async fn first_block(
+        &self,
+        filters: &[Arc<dyn ContentFilter>],
+        text: &str,
+        direction: FilterDirection,
+    ) -> Result<Option<LlmResponse>> {
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        for filter in filters {
+            if let FilterDecision::Block { reason } = filter.check(text, direction).await? {
+                let event = ContentFilterEvent {
+                    model: self.inner.name().to_string(),
+                    filter: filter.name().to_string(),
+                    direction,
+                    reason,
+                    timestamp: Utc::now(),
+                };
+                adk_telemetry::warn!(
+                    model = %event.model,
+                    filter = %event.filter,
+                    direction = %event.direction,
+                    reason = %event.reason,
+                    "content blocked by filter"
+                );
+                if let Some(hook) = &self.audit_hook {
+                    hook(&event);
+                }
+                return Ok(Some(self.refusal(&event)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replaces a complete `response` with a refusal if a response filter blocks it.
+    async fn filter_response(&self, response: LlmResponse) -> Result<LlmResponse> {
+        let answer = response.answer_text().unwrap_or_default();
+        let refusal =
+            self.first_block(&self.response_filters, &answer, FilterDirection::Response).await?;
+        Ok(refusal.unwrap_or(response))
+    }
+
+    fn refusal(&self, event: &ContentFilterEvent) -> LlmResponse {
+        LlmResponse {
+            content: Some(Content::new("model").with_text(&self.refusal_message)),
+            finish_reason: Some(FinishReason::Safety),
+            turn_complete: true,
+            error_code: Some(CONTENT_FILTERED.to_string()),
+            error_message: Some(event.reason.clone()),
+            provider_metadata: Some(json!({
+                "content_filter": {
+                    "filter": event.filter,
+                    "direction": event.direction,
+                    "reason": event.reason,
+                }
+            })),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl Llm for FilteredLlm {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schema_adapter(&self) -> &dyn adk_core::SchemaAdapter {
+        self.inner.schema_adapter()
+    }
+
+    fn max_tools(&self) -> Option<usize> {
+        self.inner.max_tools()
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.inner.supports_response_schema()
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        self.inner.count_tokens(req).await
+    }
+
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<adk_core::TokenBreakdown> {
+        self.inner.count_tokens_breakdown(req).await
+    }
+
+    /// Filters each prompt, sends the allowed requests to the wrapped model as
+    /// one batch, and filters each response.
+    async fn generate_batch(
+        &self,
+        requests: Vec<LlmRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<LlmResponse>> {
+        let mut results: Vec<Option<Result<LlmResponse>>> = Vec::new();
+        results.resize_with(requests.len(), || None);
+        let mut allowed = Vec::new();
+        let mut allowed_indices = Vec::new();
+        for (index, request) in requests.into_iter().enumerate() {
+            let prompt = last_user_text(&request);
+            match self.first_block(&self.prompt_filters, &prompt, FilterDirection::Prompt).await {
+                Ok(None) => {
+                    allowed_indices.push(index);
+                    allowed.push(request);
+                }
+                blocked => results[index] = blocked.transpose(),
+            }
+        }
+
+        if !allowed.is_empty() {
+            let responses = self.inner.generate_batch(allowed, concurrency).await;
+            for (index, response) in allowed_indices.into_iter().zip(responses) {
+                results[index] = Some(match response {
+                    Ok(response) => self.filter_response(response).await,
+                    Err(error) => Err(error),
+                });
+            }
+        }
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| Err(AdkError::model("model returned no response")))
+            })
+            .collect()
+    }
+
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        let prompt = last_user_text(&request);
+        if let Some(event) =
+            self.first_block(&self.prompt_filters, &prompt, FilterDirection::Prompt).await?
+        {
+            return Ok(Box::pin(futures::stream::once(async move { Ok(event) })));
+        }
+
+        let mut responses = self.inner.generate_content(request, stream).await?;
+        if self.response_filters.is_empty() {
+            return Ok(responses);
+        }
+
+        let mut held = Vec::new();
+        let mut answer = String::new();
+        while let Some(response) = responses.next().await {
+            let response = response?;
+            if let Some(text) = response.answer_text() {
+                answer.push_str(&text);
+            }
+            held.push(Ok(response));
+        }
+        if let Some(refusal) =
+            self.first_block(&self.response_filters, &answer, FilterDirection::Response).await?
+        {
+            held = vec![Ok(refusal)];
+        }
+        Ok(Box::pin(futures::stream::iter(held)))
+    }
+}
+
+/// Returns `true` if `response` is a refusal returned by [`FilteredLlm`].
+pub fn is_content_filtered(response: &LlmResponse) -> bool {
+    response.error_code.as_deref() == Some(CONTENT_FILTERED)
+}
+
+fn last_user_text(request: &LlmRequest) -> String {
+    request
+        .contents
+        .iter()
+        .rev()
+        .find(|content| content.role == "user")
+        .map(|content| {
+            content.parts.iter().filter_map(|part| part.text()).collect::<Vec<_>>().join("\n")
+        })
+        .unwrap_or_default()
+}
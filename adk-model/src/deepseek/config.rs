@@ -3,6 +3,7 @@
 //! Supports DeepSeek V4 models (`deepseek-v4-pro`, `deepseek-v4-flash`) and
 //! legacy models (`deepseek-chat`, `deepseek-reasoner`).
 
+use crate::retry::RetryConfig;
 use serde::{Deserialize, Serialize};
 
 /// Default DeepSeek API base URL.
@@ -100,6 +101,10 @@ pub struct DeepSeekConfig {
     /// Prefer using `thinking` directly for new code.
     #[serde(default)]
     pub thinking_enabled: bool,
+
+    /// Retry policy for transient failures such as rate limits.
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 impl Default for DeepSeekConfig {
@@ -114,6 +119,7 @@ impl Default for DeepSeekConfig {
             beta: false,
             strict_tools: false,
             thinking_enabled: false,
+            retry_config: RetryConfig::default(),
         }
     }
 }
@@ -226,6 +232,12 @@ impl DeepSeekConfig {
         self
     }
 
+    /// Set the retry policy for transient failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
     /// Get the effective base URL.
     pub fn effective_base_url(&self) -> &str {
         if let Some(ref url) = self.base_url {
@@ -183,9 +183,12 @@ pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
-    /// Tokens used for reasoning (thinking mode).
+    /// Tokens used for reasoning (thinking mode), as reported by older API versions.
     #[serde(default)]
     pub reasoning_tokens: Option<u32>,
+    /// Breakdown of completion tokens, including reasoning tokens.
+    #[serde(default)]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
     /// Cache hit tokens for prefix caching.
     #[serde(default)]
     pub prompt_cache_hit_tokens: Option<u32>,
@@ -194,6 +197,49 @@ pub struct Usage {
     pub prompt_cache_miss_tokens: Option<u32>,
 }
 
+/// Breakdown of completion token usage.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CompletionTokensDetails {
+    /// Tokens spent on `reasoning_content`.
+    #[serde(default)]
+    pub reasoning_tokens: Option<u32>,
+}
+
+impl Usage {
+    /// Convert to ADK usage metadata, reporting reasoning tokens as thinking tokens.
+    pub fn to_usage_metadata(&self) -> UsageMetadata {
+        let reasoning_tokens = self
+            .completion_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens)
+            .or(self.reasoning_tokens);
+
+        UsageMetadata {
+            prompt_token_count: self.prompt_tokens as i32,
+            candidates_token_count: self.completion_tokens as i32,
+            total_token_count: self.total_tokens as i32,
+            thinking_token_count: reasoning_tokens.map(|t| t as i32),
+            cache_read_input_token_count: self.prompt_cache_hit_tokens.map(|t| t as i32),
+            cache_creation_input_token_count: self.prompt_cache_miss_tokens.map(|t| t as i32),
+            ..Default::default()
+        }
+    }
+}
+
+/// Drop `reasoning_content` from assistant messages of earlier turns.
+///
+/// DeepSeek only accepts reasoning for the current turn (where it carries the
+/// chain-of-thought across tool calls); reasoning from turns that precede the
+/// latest user message must not be sent back.
+pub fn strip_prior_reasoning(messages: &mut [Message]) {
+    let Some(last_user) = messages.iter().rposition(|m| m.role == "user") else {
+        return;
+    };
+    for message in &mut messages[..last_user] {
+        message.reasoning_content = None;
+    }
+}
+
 /// Convert ADK Content to DeepSeek Message.
 pub fn content_to_message(content: &Content) -> Message {
     let role = match content.role.as_str() {
@@ -343,15 +389,7 @@ pub fn from_response(response: &ChatCompletionResponse) -> LlmResponse {
         (None, None)
     };
 
-    let usage = response.usage.as_ref().map(|u| UsageMetadata {
-        prompt_token_count: u.prompt_tokens as i32,
-        candidates_token_count: u.completion_tokens as i32,
-        total_token_count: u.total_tokens as i32,
-        thinking_token_count: u.reasoning_tokens.map(|t| t as i32),
-        cache_read_input_token_count: u.prompt_cache_hit_tokens.map(|t| t as i32),
-        cache_creation_input_token_count: u.prompt_cache_miss_tokens.map(|t| t as i32),
-        ..Default::default()
-    });
+    let usage = response.usage.as_ref().map(Usage::to_usage_metadata);
 
     // A turn that emits tool calls is not complete — tool results must still be
     // processed and sent back to the model (issue #401).
@@ -369,6 +407,7 @@ pub fn from_response(response: &ChatCompletionResponse) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -406,6 +445,7 @@ pub fn create_tool_call_response(
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -474,4 +514,72 @@ mod tests {
         assert_eq!(message.reasoning_content.as_deref(), Some("reason through the tool plan"));
         assert_eq!(message.content.as_deref(), Some("let me check the prices"));
     }
+
+    /// Recorded `deepseek-reasoner` response (non-streaming).
+    const REASONER_RESPONSE: &str = r#"{
+        "id": "930c60df-bf64-41c9-a88e-3ec75f81e00e",
+        "object": "chat.completion",
+        "created": 1738000000,
+        "model": "deepseek-reasoner",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": "9.11 is smaller than 9.8.",
+                "reasoning_content": "Compare the decimals: 0.11 < 0.80, so 9.11 < 9.8."
+            },
+            "logprobs": null,
+            "finish_reason": "stop"
+        }],
+        "usage": {
+            "prompt_tokens": 17,
+            "completion_tokens": 142,
+            "total_tokens": 159,
+            "prompt_tokens_details": {"cached_tokens": 0},
+            "completion_tokens_details": {"reasoning_tokens": 121},
+            "prompt_cache_hit_tokens": 0,
+            "prompt_cache_miss_tokens": 17
+        },
+        "system_fingerprint": "fp_7e73fd9a08"
+    }"#;
+
+    #[test]
+    fn reasoner_response_separates_reasoning_from_answer() {
+        let response: ChatCompletionResponse = serde_json::from_str(REASONER_RESPONSE).unwrap();
+        let llm_response = from_response(&response);
+
+        assert_eq!(
+            llm_response.thinking_text().as_deref(),
+            Some("Compare the decimals: 0.11 < 0.80, so 9.11 < 9.8.")
+        );
+        assert_eq!(llm_response.answer_text().as_deref(), Some("9.11 is smaller than 9.8."));
+
+        let usage = llm_response.usage_metadata.unwrap();
+        assert_eq!(usage.thinking_token_count, Some(121));
+        assert_eq!(usage.candidates_token_count, 142);
+    }
+
+    #[test]
+    fn strip_prior_reasoning_keeps_only_current_turn() {
+        let turn = |role: &str, reasoning: Option<&str>| Message {
+            role: role.to_string(),
+            content: Some("text".to_string()),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            reasoning_content: reasoning.map(str::to_string),
+        };
+        let mut messages = vec![
+            turn("user", None),
+            turn("assistant", Some("old reasoning")),
+            turn("user", None),
+            turn("assistant", Some("current tool plan")),
+            turn("tool", None),
+        ];
+
+        strip_prior_reasoning(&mut messages);
+
+        assert_eq!(messages[1].reasoning_content, None);
+        assert_eq!(messages[3].reasoning_content.as_deref(), Some("current tool plan"));
+    }
 }
@@ -56,6 +56,12 @@
 //! - `ThinkingMode::Enabled` / `ThinkingMode::Disabled` — explicit toggle
 //! - `ReasoningEffort::High` / `ReasoningEffort::Max` — thinking depth
 //!
+//! Reasoning is returned as `Part::Thinking`, separate from the answer's
+//! `Part::Text` (streamed as its own partial events), so UIs can render it apart;
+//! use `LlmResponse::thinking_text()` and `LlmResponse::answer_text()` to split a
+//! response. Reasoning tokens are reported in `UsageMetadata::thinking_token_count`.
+//! Reasoning from earlier turns is not sent back to the API.
+//!
 //! In thinking mode, `temperature`, `top_p`, `presence_penalty`, and
 //! `frequency_penalty` are silently ignored by the API.
 //!
@@ -1,9 +1,12 @@
 use crate::attachment;
-use crate::retry::{RetryConfig, execute_with_retry, is_retryable_model_error};
+use crate::prompt_layout::PromptLayout;
+#[cfg(feature = "gemini-interactions")]
+use crate::retry::execute_with_retry;
+use crate::retry::{RetryConfig, execute_stream_with_retry, is_retryable_model_error};
 use adk_core::{
-    CacheCapable, CitationMetadata, CitationSource, Content, ErrorCategory, ErrorComponent,
-    FinishReason, Llm, LlmRequest, LlmResponse, LlmResponseStream, Part, Result, SchemaAdapter,
-    SchemaCache, UsageMetadata,
+    CacheCapable, Citation, CitationMetadata, CitationSource, Content, ErrorCategory,
+    ErrorComponent, FinishReason, Llm, LlmRequest, LlmResponse, LlmResponseStream, Part, Result,
+    SchemaAdapter, SchemaCache, UsageMetadata,
 };
 use adk_gemini::Gemini;
 use adk_gemini::schema_adapter::GeminiSchemaAdapter;
@@ -102,6 +105,10 @@ pub struct GeminiModel {
     /// `ThinkingLevel` (Low/Medium/High). For Gemini 2.5 series, use
     /// `thinking_budget` (token count).
     thinking_config: Option<adk_gemini::ThinkingConfig>,
+    /// Output modalities requested on every request, e.g. `["TEXT", "AUDIO"]`.
+    response_modalities: Option<Vec<String>>,
+    /// Voice settings for audio output.
+    speech_config: Option<adk_gemini::SpeechConfig>,
     /// Selected wire transport. Defaults to
     /// [`GeminiTransport::GenerateContent`]; set to
     /// [`GeminiTransport::Interactions`] via [`GeminiModel::use_interactions_api`].
@@ -114,25 +121,57 @@ pub struct GeminiModel {
     /// Faithful-to-API options for the Interactions transport.
     #[cfg(feature = "gemini-interactions")]
     interaction_options: InteractionOptions,
+    /// How often [`Llm::generate_batch`] polls a submitted batch job.
+    batch_poll_interval: std::time::Duration,
+    /// Explicit answer for [`Llm::supports_response_schema`]; `None` infers
+    /// it from the model name.
+    response_schema_support: Option<bool>,
+}
+
+/// Default interval between batch job status checks. Batch jobs typically
+/// take minutes to hours, so there is no point polling more often.
+const DEFAULT_BATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Maximum number of function declarations Gemini accepts in a single request.
+const GEMINI_MAX_TOOLS: usize = 512;
+
+/// Render an error followed by each of its sources, separated by `": "`.
+fn format_error_chain(e: &dyn std::error::Error) -> String {
+    let mut msg = e.to_string();
+    let mut source = e.source();
+    while let Some(s) = source {
+        msg.push_str(": ");
+        msg.push_str(&s.to_string());
+        source = s.source();
+    }
+    msg
 }
 
 /// Convert a Gemini client error to a structured `AdkError` with proper category and retry hints.
 fn gemini_error_to_adk(e: &adk_gemini::ClientError) -> adk_core::AdkError {
-    fn format_error_chain(e: &dyn std::error::Error) -> String {
-        let mut msg = e.to_string();
-        let mut source = e.source();
-        while let Some(s) = source {
-            msg.push_str(": ");
-            msg.push_str(&s.to_string());
-            source = s.source();
+    let message = format_error_chain(e);
+
+    if let adk_gemini::ClientError::BadResponse { code: status, retry_after, .. } = e {
+        let code = match status {
+            429 => "model.gemini.rate_limited",
+            503 => "model.gemini.unavailable",
+            529 => "model.gemini.overloaded",
+            408 => "model.gemini.timeout",
+            401 => "model.gemini.unauthorized",
+            400 => "model.gemini.bad_request",
+            404 => "model.gemini.not_found",
+            _ => "model.gemini.api_error",
+        };
+        let mut err =
+            adk_core::AdkError::from_http_status(ErrorComponent::Model, code, *status, message)
+                .with_provider("gemini");
+        if let Some(delay) = retry_after {
+            err = err.with_retry_after(*delay);
         }
-        msg
+        return err;
     }
 
-    let message = format_error_chain(e);
-
-    // Extract status code from BadResponse variant via Display output
-    // BadResponse format: "bad response from server; code {code}; description: ..."
+    // Errors that reach us wrapped carry the status only in their message.
     let (category, code, status_code) = if message.contains("code 429")
         || message.contains("RESOURCE_EXHAUSTED")
         || message.contains("rate limit")
@@ -209,6 +248,81 @@ fn interaction_terminal_error(code: &'static str, message: &str) -> adk_core::Ad
     .with_provider("gemini")
 }
 
+/// Error code surfaced when Gemini ends a turn with `STOP` and no answer while
+/// it was in the middle of using a built-in tool (Google Search, URL context).
+pub const EMPTY_STOP_WITH_TOOL_INTENT: &str = "model.gemini.empty_stop_with_tool_intent";
+
+/// Builds the retryable [`adk_core::AdkError`] for an empty `STOP` with tool intent.
+///
+/// Categorized as [`ErrorCategory::Unavailable`] so the retry loop re-issues
+/// the request, giving the built-in tool another chance to run. Streaming
+/// callers that were already sent chunks receive it as a stream error.
+fn empty_stop_error() -> adk_core::AdkError {
+    adk_core::AdkError::new(
+        ErrorComponent::Model,
+        ErrorCategory::Unavailable,
+        EMPTY_STOP_WITH_TOOL_INTENT,
+        "Gemini finished with STOP and no content while invoking a built-in tool",
+    )
+    .with_provider("gemini")
+}
+
+/// Watches a (possibly streamed) Gemini response for a `STOP` that arrives
+/// before any answer, while the model was reaching for a built-in tool.
+///
+/// Gemini sometimes truncates a turn this way when `google_search` or
+/// `url_context` is enabled; passing the empty response on would look like a
+/// legitimate empty answer.
+#[derive(Debug, Default)]
+struct EmptyStopDetector {
+    native_tools_requested: bool,
+    saw_answer: bool,
+    saw_tool_intent: bool,
+}
+
+impl EmptyStopDetector {
+    fn new(native_tools_requested: bool) -> Self {
+        Self { native_tools_requested, ..Default::default() }
+    }
+
+    /// Records one response chunk, failing if it ends the turn empty-handed.
+    fn observe(&mut self, resp: &adk_gemini::GenerationResponse) -> Result<()> {
+        let Some(candidate) = resp.candidates.first() else {
+            return Ok(());
+        };
+
+        for part in candidate.content.parts.iter().flatten() {
+            match part {
+                adk_gemini::Part::Text { text, thought, .. }
+                    if thought != &Some(true) && !text.trim().is_empty() =>
+                {
+                    self.saw_answer = true;
+                }
+                adk_gemini::Part::FunctionCall { .. }
+                | adk_gemini::Part::InlineData { .. }
+                | adk_gemini::Part::FileData { .. } => self.saw_answer = true,
+                adk_gemini::Part::ToolCall { .. } | adk_gemini::Part::ExecutableCode { .. } => {
+                    self.saw_tool_intent = true;
+                }
+                _ => {}
+            }
+        }
+        if candidate.grounding_metadata.is_some() {
+            self.saw_tool_intent = true;
+        }
+
+        let stopped = candidate.finish_reason == Some(adk_gemini::FinishReason::Stop);
+        if stopped && !self.saw_answer && (self.saw_tool_intent || self.native_tools_requested) {
+            adk_telemetry::warn!(
+                grounded = self.saw_tool_intent,
+                "Gemini returned STOP with empty content while using a built-in tool"
+            );
+            return Err(empty_stop_error());
+        }
+        Ok(())
+    }
+}
+
 impl GeminiModel {
     fn gemini_part_thought_signature(value: &serde_json::Value) -> Option<String> {
         value.get("thoughtSignature").and_then(serde_json::Value::as_str).map(str::to_string)
@@ -225,12 +339,16 @@ impl GeminiModel {
             model_name,
             retry_config: RetryConfig::default(),
             thinking_config: None,
+            response_modalities: None,
+            speech_config: None,
             #[cfg(feature = "gemini-interactions")]
             transport: GeminiTransport::GenerateContent,
             #[cfg(feature = "gemini-interactions")]
             interaction_target: None,
             #[cfg(feature = "gemini-interactions")]
             interaction_options: InteractionOptions::default(),
+            batch_poll_interval: DEFAULT_BATCH_POLL_INTERVAL,
+            response_schema_support: None,
         }
     }
 
@@ -243,6 +361,26 @@ impl GeminiModel {
         Ok(Self::from_client(client, model_name))
     }
 
+    /// Create a Gemini model client that sends requests to `base_url` instead
+    /// of the public Gemini API endpoint, e.g. a proxy or a local mock server.
+    ///
+    /// `base_url` is the prefix that `models/{model}:generateContent` is
+    /// appended to, so it should end with a `/`.
+    pub fn new_with_base_url(
+        api_key: impl Into<String>,
+        model: impl Into<String>,
+        base_url: impl AsRef<str>,
+    ) -> Result<Self> {
+        let model_name = model.into();
+        let base_url = base_url.as_ref().parse().map_err(|e| {
+            adk_core::AdkError::model(format!("invalid base URL '{}': {e}", base_url.as_ref()))
+        })?;
+        let client = Gemini::with_model_and_base_url(api_key.into(), model_name.clone(), base_url)
+            .map_err(|e| adk_core::AdkError::model(e.to_string()))?;
+
+        Ok(Self::from_client(client, model_name))
+    }
+
     /// Create a Gemini model via Vertex AI with API key auth.
     ///
     /// Requires `gemini-vertex` feature.
@@ -385,6 +523,57 @@ impl GeminiModel {
         self.thinking_config.as_ref()
     }
 
+    /// Request the given output modalities, e.g. `["TEXT", "AUDIO"]`.
+    /// Applies to the `generateContent` transport.
+    ///
+    /// Audio comes back as [`Part::InlineData`] in the order the model
+    /// interleaves it with text; [`LlmResponse::parts`] keeps that order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use adk_gemini::SpeechConfig;
+    ///
+    /// let model = GeminiModel::new(api_key, "gemini-2.5-flash-preview-tts")?
+    ///     .with_response_modalities(["AUDIO"])
+    ///     .with_speech_config(SpeechConfig::single_voice("Kore"));
+    /// ```
+    #[must_use]
+    pub fn with_response_modalities<I, S>(mut self, modalities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.response_modalities = Some(modalities.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the voice used for audio output.
+    #[must_use]
+    pub fn with_speech_config(mut self, speech_config: adk_gemini::SpeechConfig) -> Self {
+        self.speech_config = Some(speech_config);
+        self
+    }
+
+    /// Set how often [`Llm::generate_batch`] checks a submitted batch job
+    /// for completion. Defaults to 30 seconds.
+    #[must_use]
+    pub fn with_batch_poll_interval(mut self, interval: std::time::Duration) -> Self {
+        self.batch_poll_interval = interval;
+        self
+    }
+
+    /// Override whether this model accepts a `responseSchema`.
+    ///
+    /// By default Gemini 1.0 models (`gemini-1.0-*`, `gemini-pro`) are treated
+    /// as JSON-mode only and every other model as schema-capable. Use this for
+    /// tuned or aliased model names the default cannot recognize.
+    #[must_use]
+    pub fn with_response_schema_support(mut self, supported: bool) -> Self {
+        self.response_schema_support = Some(supported);
+        self
+    }
+
     /// Enable (or disable) the Interactions API transport.
     ///
     /// When enabling, the model's configured model id is validated against the
@@ -620,6 +809,8 @@ impl GeminiModel {
             .and_then(|c| c.grounding_metadata.as_ref())
             .and_then(|g| serde_json::to_value(g).ok());
 
+        let citations = resp.candidates.first().map(Self::candidate_citations).unwrap_or_default();
+
         Ok(LlmResponse {
             content,
             usage_metadata,
@@ -632,9 +823,72 @@ impl GeminiModel {
             error_message: None,
             provider_metadata,
             interaction_id: None,
+            citations,
         })
     }
 
+    /// Collect the sources behind a candidate from its grounding supports,
+    /// any grounding chunks no support refers to, and its citation metadata.
+    fn candidate_citations(candidate: &adk_gemini::Candidate) -> Vec<Citation> {
+        let mut citations = Vec::new();
+
+        if let Some(grounding) = &candidate.grounding_metadata {
+            let chunks = grounding.grounding_chunks.as_deref().unwrap_or_default();
+            let source = |chunk: &adk_gemini::GroundingChunk| {
+                let (uri, title) = match (&chunk.web, &chunk.maps) {
+                    (Some(web), _) => (web.uri.as_ref(), web.title.as_ref()),
+                    (None, Some(maps)) => (maps.uri.as_ref(), maps.title.as_ref()),
+                    (None, None) => (None, None),
+                };
+                Citation {
+                    text_span: None,
+                    source_uri: uri.map(|uri| uri.to_string()),
+                    title: title.cloned(),
+                }
+            };
+
+            let mut supported = vec![false; chunks.len()];
+            for support in grounding.grounding_supports.iter().flatten() {
+                let segment = &support.segment;
+                let span = adk_core::TextSpan {
+                    start: segment.start_index.unwrap_or(0) as usize,
+                    end: segment.end_index.unwrap_or(0) as usize,
+                    text: segment.text.clone(),
+                };
+                for &index in &support.grounding_chunk_indices {
+                    if let Some(chunk) = chunks.get(index as usize) {
+                        supported[index as usize] = true;
+                        citations.push(source(chunk).with_text_span(span.clone()));
+                    }
+                }
+            }
+            citations.extend(
+                chunks
+                    .iter()
+                    .zip(supported)
+                    .filter(|(_, supported)| !supported)
+                    .map(|(c, _)| source(c)),
+            );
+        }
+
+        if let Some(meta) = &candidate.citation_metadata {
+            citations.extend(meta.citation_sources.iter().map(|source| Citation {
+                text_span: match (source.start_index, source.end_index) {
+                    (Some(start), Some(end)) => Some(adk_core::TextSpan {
+                        start: start.max(0) as usize,
+                        end: end.max(0) as usize,
+                        text: None,
+                    }),
+                    _ => None,
+                },
+                source_uri: source.uri.clone(),
+                title: source.title.clone(),
+            }));
+        }
+
+        citations
+    }
+
     fn gemini_function_response_payload(response: serde_json::Value) -> serde_json::Value {
         match response {
             // Gemini functionResponse.response must be a JSON object.
@@ -778,25 +1032,49 @@ impl GeminiModel {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         };
 
         (vec![synthetic_partial, response], true)
     }
 
-    async fn generate_content_internal(
-        &self,
-        req: LlmRequest,
-        stream: bool,
-    ) -> Result<LlmResponseStream> {
+    /// Translate an ADK request into a Gemini `generateContent` request builder.
+    /// Maps an inline blob or file reference onto Gemini's native media part.
+    fn media_part(part: &Part) -> Option<adk_gemini::Part> {
+        match part {
+            Part::InlineData { mime_type, data } => Some(adk_gemini::Part::InlineData {
+                inline_data: adk_gemini::Blob {
+                    mime_type: mime_type.clone(),
+                    data: attachment::encode_base64(data),
+                },
+            }),
+            Part::FileData { mime_type, file_uri } => Some(adk_gemini::Part::FileData {
+                file_data: adk_gemini::FileDataRef {
+                    mime_type: mime_type.clone(),
+                    file_uri: file_uri.clone(),
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    fn build_request(&self, req: LlmRequest) -> Result<adk_gemini::ContentBuilder> {
         let mut builder = self.client.generate_content();
 
+        // `generateContent` takes the system instruction in its own field and
+        // has no `system` role in `contents`.
+        let layout = PromptLayout::gemini().normalize(&req.contents);
+        if let Some(instruction) = layout.system_instruction {
+            builder = builder.with_system_instruction(instruction);
+        }
+
         // Build a map of function_name → thought_signature from FunctionCall parts
         // in model content. Gemini 3.x requires thought_signature on FunctionResponse
         // parts when thinking is active, but adk_core::Part::FunctionResponse doesn't
         // carry it (it's Gemini-specific). We recover it here at the provider boundary.
         let mut fn_call_signatures: std::collections::HashMap<String, String> =
             std::collections::HashMap::new();
-        for content in &req.contents {
+        for content in &layout.contents {
             if content.role == "model" {
                 for part in &content.parts {
                     if let Part::FunctionCall { name, thought_signature: Some(sig), .. } = part {
@@ -807,7 +1085,7 @@ impl GeminiModel {
         }
 
         // Add contents using proper builder methods
-        for content in &req.contents {
+        for content in &layout.contents {
             match content.role.as_str() {
                 "user" => {
                     // For user messages, build gemini Content with potentially multiple parts
@@ -828,21 +1106,8 @@ impl GeminiModel {
                                     thought_signature: signature.clone(),
                                 });
                             }
-                            Part::InlineData { data, mime_type } => {
-                                let encoded = attachment::encode_base64(data);
-                                gemini_parts.push(adk_gemini::Part::InlineData {
-                                    inline_data: adk_gemini::Blob {
-                                        mime_type: mime_type.clone(),
-                                        data: encoded,
-                                    },
-                                });
-                            }
-                            Part::FileData { mime_type, file_uri } => {
-                                gemini_parts.push(adk_gemini::Part::Text {
-                                    text: attachment::file_attachment_to_text(mime_type, file_uri),
-                                    thought: None,
-                                    thought_signature: None,
-                                });
+                            Part::InlineData { .. } | Part::FileData { .. } => {
+                                gemini_parts.extend(Self::media_part(part));
                             }
                             _ => {}
                         }
@@ -888,6 +1153,9 @@ impl GeminiModel {
                                     thought_signature: thought_signature.clone(),
                                 });
                             }
+                            Part::InlineData { .. } | Part::FileData { .. } => {
+                                gemini_parts.extend(Self::media_part(part));
+                            }
                             Part::ServerToolCall { server_tool_call } => {
                                 if let Ok(native_part) = serde_json::from_value::<adk_gemini::Part>(
                                     server_tool_call.clone(),
@@ -1006,19 +1274,26 @@ impl GeminiModel {
 
         // Add generation config
         if let Some(config) = req.config {
+            crate::generation_config::log_unsupported_fields(
+                "gemini",
+                &config,
+                &["temperature", "top_p", "top_k", "max_output_tokens", "stop_sequences"],
+            );
             let has_schema = config.response_schema.is_some();
             let gen_config = adk_gemini::GenerationConfig {
                 temperature: config.temperature,
                 top_p: config.top_p,
                 top_k: config.top_k,
                 max_output_tokens: config.max_output_tokens,
+                stop_sequences: (!config.stop_sequences.is_empty())
+                    .then_some(config.stop_sequences),
                 response_schema: config.response_schema,
-                response_mime_type: if has_schema {
-                    Some("application/json".to_string())
-                } else {
-                    None
-                },
+                response_mime_type: config
+                    .response_mime_type
+                    .or_else(|| has_schema.then(|| "application/json".to_string())),
                 thinking_config: self.thinking_config.clone(),
+                response_modalities: self.response_modalities.clone(),
+                speech_config: self.speech_config.clone(),
                 ..Default::default()
             };
             builder = builder.with_generation_config(gen_config);
@@ -1028,11 +1303,16 @@ impl GeminiModel {
                 let handle = self.client.get_cached_content(name);
                 builder = builder.with_cached_content(&handle);
             }
-        } else if self.thinking_config.is_some() {
-            // No generation config from the request, but we have a default
-            // thinking config — apply it in an otherwise-default gen config.
+        } else if self.thinking_config.is_some()
+            || self.response_modalities.is_some()
+            || self.speech_config.is_some()
+        {
+            // No generation config from the request, but we have model
+            // defaults — apply them in an otherwise-default gen config.
             let gen_config = adk_gemini::GenerationConfig {
                 thinking_config: self.thinking_config.clone(),
+                response_modalities: self.response_modalities.clone(),
+                speech_config: self.speech_config.clone(),
                 ..Default::default()
             };
             builder = builder.with_generation_config(gen_config);
@@ -1053,6 +1333,19 @@ impl GeminiModel {
             }
         }
 
+        Ok(builder)
+    }
+
+    async fn generate_content_internal(
+        &self,
+        req: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        let native_tools_requested =
+            req.tools.values().any(|decl| decl.get("x-adk-gemini-tool").is_some());
+        let builder = self.build_request(req)?;
+        let mut empty_stop = EmptyStopDetector::new(native_tools_requested);
+
         if stream {
             adk_telemetry::debug!("Executing streaming request");
             let response_stream = builder.execute_stream().await.map_err(|e| {
@@ -1066,6 +1359,10 @@ impl GeminiModel {
                 while let Some(result) = stream.try_next().await.transpose() {
                     match result {
                         Ok(resp) => {
+                            if let Err(e) = empty_stop.observe(&resp) {
+                                yield Err(e);
+                                break;
+                            }
                             match Self::convert_response(&resp) {
                                 Ok(llm_resp) => {
                                     let (chunks, next_saw_partial) =
@@ -1097,6 +1394,7 @@ impl GeminiModel {
                 gemini_error_to_adk(&e)
             })?;
 
+            empty_stop.observe(&response)?;
             let llm_response = Self::convert_response(&response)?;
 
             let stream = async_stream::stream! {
@@ -1107,6 +1405,41 @@ impl GeminiModel {
         }
     }
 
+    /// Poll a batch job every `batch_poll_interval` until it finishes.
+    async fn wait_for_batch(
+        &self,
+        handle: &adk_gemini::BatchHandle,
+    ) -> Result<Vec<adk_gemini::BatchGenerationResponseItem>> {
+        loop {
+            let status = handle.status().await.map_err(|e| {
+                adk_core::AdkError::model(format!(
+                    "batch '{}' status check failed: {}",
+                    handle.name(),
+                    format_error_chain(&e)
+                ))
+            })?;
+            match status {
+                adk_gemini::BatchStatus::Succeeded { results } => return Ok(results),
+                adk_gemini::BatchStatus::Cancelled => {
+                    return Err(adk_core::AdkError::model(format!(
+                        "batch '{}' was cancelled",
+                        handle.name()
+                    )));
+                }
+                adk_gemini::BatchStatus::Expired => {
+                    return Err(adk_core::AdkError::model(format!(
+                        "batch '{}' expired before finishing",
+                        handle.name()
+                    )));
+                }
+                adk_gemini::BatchStatus::Pending | adk_gemini::BatchStatus::Running { .. } => {
+                    adk_telemetry::debug!(batch = %handle.name(), "batch job still running");
+                    tokio::time::sleep(self.batch_poll_interval).await;
+                }
+            }
+        }
+    }
+
     /// Create a cached content resource with the given system instruction, tools, and TTL.
     ///
     /// Returns the cache name (e.g., "cachedContents/abc123") on success.
@@ -1331,10 +1664,10 @@ impl GeminiModel {
     ///
     /// Stream setup (target resolution, request building, opening the SSE
     /// connection) is fallible and returns `Err` synchronously, so
-    /// `execute_with_retry` (which wraps this in `generate_content`) can retry
-    /// transient setup failures. Errors that occur *after* the stream starts
-    /// are yielded into the stream and not retried, mirroring the
-    /// generateContent streaming path.
+    /// `execute_stream_with_retry` (which wraps this in `generate_content`) can
+    /// retry transient setup failures, as well as a failure on the first
+    /// event. Errors that occur after a chunk was yielded are passed through
+    /// and not retried, mirroring the generateContent streaming path.
     ///
     /// The stale-continuation fallback used by the non-streaming path is not
     /// applied here: a `NotFound` on stream setup surfaces as a normal error
@@ -1411,6 +1744,28 @@ impl Llm for GeminiModel {
         self.transport == GeminiTransport::Interactions
     }
 
+    fn max_tools(&self) -> Option<usize> {
+        Some(GEMINI_MAX_TOOLS)
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.response_schema_support.unwrap_or_else(|| {
+            let model = self.model_name.trim_start_matches("models/");
+            !(model.starts_with("gemini-1.0") || model.starts_with("gemini-pro"))
+        })
+    }
+
+    /// Counts tokens with Gemini's `countTokens` endpoint, which tokenizes
+    /// the request exactly as generation would, images and files included.
+    /// This costs a network call but no quota.
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        let response = self.build_request(req.clone())?.count_tokens().await.map_err(|e| {
+            adk_telemetry::error!(error = %e, "Token count request failed");
+            gemini_error_to_adk(&e)
+        })?;
+        Ok(response.total_tokens.max(0) as usize)
+    }
+
     // Named distinctly from the agent layer's `call_llm` span (which carries
     // the gcp.vertex.agent.* attributes and is what trace exporters capture):
     // this is the model-transport layer, so traces show one `call_llm` per
@@ -1433,22 +1788,21 @@ impl Llm for GeminiModel {
         // path is unchanged; the Interactions path (task 7.3/7.4) is only
         // reachable when `use_interactions_api` switched the transport.
         //
-        // Retries only cover request setup/execution. Stream failures after the
-        // stream starts are yielded to the caller and are not replayed
-        // automatically.
+        // Retries cover request setup and the first chunk of a stream. Stream
+        // failures after that are yielded to the caller, tagged with the
+        // attempt count, and are not replayed automatically.
         #[cfg(feature = "gemini-interactions")]
         if self.transport == GeminiTransport::Interactions {
             // Streaming and non-streaming are distinct completion modes. The
             // streaming path consumes the Interactions SSE stream and yields
             // partial→final chunks; the non-streaming path sends a single
             // request and (optionally) polls a background interaction to
-            // completion. `execute_with_retry` wraps only request *setup* in
-            // both cases — once a stream starts, its mid-flight errors are
-            // surfaced to the caller rather than replayed (mirroring the
-            // generateContent streaming path).
+            // completion. Once a stream has produced a chunk, its mid-flight
+            // errors are surfaced to the caller rather than replayed
+            // (mirroring the generateContent streaming path).
             if stream {
                 let mapped =
-                    execute_with_retry(&self.retry_config, is_retryable_model_error, || {
+                    execute_stream_with_retry(&self.retry_config, is_retryable_model_error, || {
                         self.generate_interactions_stream(req.clone())
                     })
                     .await?;
@@ -1464,12 +1818,105 @@ impl Llm for GeminiModel {
             return Ok(crate::usage_tracking::with_usage_tracking(Box::pin(single), usage_span));
         }
 
-        let result = execute_with_retry(&self.retry_config, is_retryable_model_error, || {
-            self.generate_content_internal(req.clone(), stream)
-        })
-        .await?;
+        let result =
+            execute_stream_with_retry(&self.retry_config, is_retryable_model_error, || {
+                self.generate_content_internal(req.clone(), stream)
+            })
+            .await?;
         Ok(crate::usage_tracking::with_usage_tracking(result, usage_span))
     }
+
+    /// Submits all requests as one job to the Gemini Batch API and polls it
+    /// until it finishes (see [`GeminiModel::with_batch_poll_interval`]).
+    ///
+    /// `concurrency` only applies when falling back to concurrent
+    /// `generateContent` calls: with the Interactions transport, or on
+    /// backends without the Batch API such as Vertex AI.
+    async fn generate_batch(
+        &self,
+        requests: Vec<LlmRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<LlmResponse>> {
+        #[cfg(feature = "gemini-interactions")]
+        if self.transport == GeminiTransport::Interactions {
+            return adk_core::generate_concurrently(self, requests, concurrency).await;
+        }
+
+        let mut results: Vec<Option<Result<LlmResponse>>> = requests.iter().map(|_| None).collect();
+        // Batch keys are positions in `batch`; map them back to request indices.
+        let mut submitted = Vec::new();
+        let mut batch = Vec::new();
+        for (index, req) in requests.iter().enumerate() {
+            match self.build_request(req.clone()) {
+                Ok(builder) => {
+                    submitted.push(index);
+                    batch.push(builder.build());
+                }
+                Err(e) => results[index] = Some(Err(e)),
+            }
+        }
+
+        if !batch.is_empty() {
+            let submission = self
+                .client
+                .batch_generate_content()
+                .with_name(format!("adk-{}", self.model_name))
+                .with_requests(batch)
+                .execute()
+                .await;
+            let outcome = match submission {
+                Ok(handle) => {
+                    adk_telemetry::info!(batch = %handle.name(), size = submitted.len(), "submitted batch job");
+                    self.wait_for_batch(&handle).await
+                }
+                Err(adk_gemini::BatchError::Client {
+                    source: adk_gemini::ClientError::GoogleCloudUnsupported { .. },
+                }) => {
+                    adk_telemetry::debug!(
+                        "batch API unavailable on this backend, generating concurrently"
+                    );
+                    return adk_core::generate_concurrently(self, requests, concurrency).await;
+                }
+                Err(adk_gemini::BatchError::Client { source }) => Err(gemini_error_to_adk(&source)),
+                Err(e) => Err(adk_core::AdkError::model(format!(
+                    "batch submission failed: {}",
+                    format_error_chain(&e)
+                ))),
+            };
+
+            match outcome {
+                Ok(items) => {
+                    for item in items {
+                        let Some(&index) = submitted.get(item.meta.key) else {
+                            continue;
+                        };
+                        results[index] = Some(match item.response {
+                            Ok(response) => Self::convert_response(&response),
+                            Err(e) => Err(adk_core::AdkError::model(format!(
+                                "batch request failed with code {}: {}",
+                                e.code, e.message
+                            ))),
+                        });
+                    }
+                }
+                Err(e) => {
+                    adk_telemetry::error!(error = %e, "batch job failed");
+                    for &index in &submitted {
+                        results[index] = Some(Err(adk_core::AdkError::model(e.to_string())));
+                    }
+                }
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(adk_core::AdkError::model("batch job returned no result for this request"))
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -1704,6 +2151,7 @@ impl CacheCapable for GeminiModel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::retry::execute_with_retry;
     use adk_core::AdkError;
     use std::{
         sync::{
@@ -1724,6 +2172,85 @@ mod tests {
         accepts_sync_constructor(|api_key, model| GeminiModel::new(api_key, model));
     }
 
+    #[test]
+    fn response_schema_support_follows_model_family() {
+        let model = |name: &str| GeminiModel::new("test-key", name).expect("construct model");
+
+        assert!(model("gemini-2.5-flash").supports_response_schema());
+        assert!(!model("gemini-1.0-pro").supports_response_schema());
+        assert!(!model("models/gemini-pro").supports_response_schema());
+        assert!(
+            model("tunedModels/my-pro-tune")
+                .with_response_schema_support(true)
+                .supports_response_schema()
+        );
+    }
+
+    #[test]
+    fn request_maps_inline_and_file_parts_to_native_media() {
+        let model = GeminiModel::new("test-key", "gemini-2.5-flash").expect("construct model");
+        let request = LlmRequest::new(
+            "gemini-2.5-flash",
+            vec![
+                Content::new("user")
+                    .with_text("compare these")
+                    .with_inline_data("image/png", vec![0x89, 0x50, 0x4E, 0x47])
+                    .with_file_uri("application/pdf", "gs://bucket/report.pdf"),
+                Content::new("model").with_inline_data("image/png", vec![1, 2, 3]),
+            ],
+        );
+
+        let builder = model.build_request(request).expect("build request");
+        let body = serde_json::to_value(builder.build()).expect("serialize request");
+
+        let user_parts = body["contents"][0]["parts"].as_array().expect("user parts");
+        let part_with = |key: &str| user_parts.iter().find(|p| p.get(key).is_some()).unwrap();
+        assert_eq!(part_with("inlineData")["inlineData"]["mimeType"], "image/png");
+        assert_eq!(
+            part_with("inlineData")["inlineData"]["data"],
+            crate::attachment::encode_base64(&[0x89, 0x50, 0x4E, 0x47])
+        );
+        assert_eq!(part_with("fileData")["fileData"]["fileUri"], "gs://bucket/report.pdf");
+        assert_eq!(part_with("fileData")["fileData"]["mimeType"], "application/pdf");
+        assert_eq!(part_with("text")["text"], "compare these");
+        assert_eq!(body["contents"][1]["parts"][0]["inlineData"]["mimeType"], "image/png");
+    }
+
+    #[test]
+    fn bad_response_maps_status_and_retry_after() {
+        let rate_limited = gemini_error_to_adk(&adk_gemini::ClientError::BadResponse {
+            code: 429,
+            description: Some("RESOURCE_EXHAUSTED".to_string()),
+            retry_after: Some(Duration::from_millis(35_500)),
+        });
+        assert!(rate_limited.is_rate_limited());
+        assert!(rate_limited.is_retryable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_millis(35_500)));
+        assert_eq!(rate_limited.upstream_status(), Some(429));
+
+        let bad_request = gemini_error_to_adk(&adk_gemini::ClientError::BadResponse {
+            code: 400,
+            description: None,
+            retry_after: None,
+        });
+        assert_eq!(bad_request.code, "model.gemini.bad_request");
+        assert!(!bad_request.is_retryable());
+        assert_eq!(bad_request.upstream_status(), Some(400));
+    }
+
+    #[test]
+    fn json_output_request_sets_mime_type_without_schema() {
+        let model = GeminiModel::new("test-key", "gemini-1.0-pro").expect("construct model");
+        let request = LlmRequest::new("gemini-1.0-pro", vec![Content::new("user").with_text("hi")])
+            .with_config(adk_core::GenerateContentConfig::default().json_output());
+
+        let builder = model.build_request(request).expect("build request");
+        let body = serde_json::to_value(builder.build()).expect("serialize request");
+
+        assert_eq!(body["generationConfig"]["responseMimeType"], "application/json");
+        assert!(body["generationConfig"].get("responseSchema").is_none());
+    }
+
     #[test]
     fn stream_chunks_from_response_injects_partial_before_lone_final_chunk() {
         let response = LlmResponse {
@@ -1738,6 +2265,7 @@ mod tests {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         };
 
         let (chunks, saw_partial) = GeminiModel::stream_chunks_from_response(response, false);
@@ -1764,6 +2292,7 @@ mod tests {
             error_message: None,
             provider_metadata: None,
             interaction_id: None,
+            citations: Vec::new(),
         };
 
         let (chunks, saw_partial) = GeminiModel::stream_chunks_from_response(response, true);
@@ -1879,6 +2408,79 @@ mod tests {
         assert_eq!(metadata.citation_sources[0].uri.as_deref(), Some("https://example.com"));
         assert_eq!(metadata.citation_sources[0].start_index, Some(0));
         assert_eq!(metadata.citation_sources[0].end_index, Some(5));
+        assert_eq!(
+            converted.citations,
+            vec![
+                adk_core::Citation::new("https://example.com")
+                    .with_title("Example")
+                    .with_text_span(adk_core::TextSpan { start: 0, end: 5, text: None })
+            ]
+        );
+    }
+
+    #[test]
+    fn convert_response_maps_grounding_to_citations() {
+        let web = |uri: &str, title: &str| adk_gemini::GroundingChunk {
+            maps: None,
+            web: Some(adk_gemini::WebGroundingChunk {
+                uri: Some(uri.parse().unwrap()),
+                title: Some(title.to_string()),
+            }),
+        };
+        let grounding = adk_gemini::GroundingMetadata {
+            grounding_chunks: Some(vec![
+                web("https://rust-lang.org/", "Rust"),
+                web("https://docs.rs/", "Docs.rs"),
+            ]),
+            grounding_supports: Some(vec![adk_gemini::GroundingSupport {
+                segment: adk_gemini::GroundingSegment {
+                    start_index: None,
+                    end_index: Some(14),
+                    text: Some("Rust is memory".to_string()),
+                },
+                grounding_chunk_indices: vec![0],
+            }]),
+            web_search_queries: None,
+            google_maps_widget_context_token: None,
+        };
+        let response = gemini_chunk(
+            vec![adk_gemini::Part::Text {
+                text: "Rust is memory safe.".to_string(),
+                thought: None,
+                thought_signature: None,
+            }],
+            Some(grounding),
+            Some(adk_gemini::FinishReason::Stop),
+        );
+
+        let converted =
+            GeminiModel::convert_response(&response).expect("conversion should succeed");
+        assert_eq!(
+            converted.citations,
+            vec![
+                adk_core::Citation::new("https://rust-lang.org/")
+                    .with_title("Rust")
+                    .with_text_span(adk_core::TextSpan {
+                        start: 0,
+                        end: 14,
+                        text: Some("Rust is memory".to_string()),
+                    }),
+                adk_core::Citation::new("https://docs.rs/").with_title("Docs.rs"),
+            ]
+        );
+
+        let ungrounded = gemini_chunk(
+            vec![adk_gemini::Part::Text {
+                text: "hi".to_string(),
+                thought: None,
+                thought_signature: None,
+            }],
+            None,
+            Some(adk_gemini::FinishReason::Stop),
+        );
+        let converted =
+            GeminiModel::convert_response(&ungrounded).expect("conversion should succeed");
+        assert!(converted.citations.is_empty());
     }
 
     #[test]
@@ -1934,6 +2536,244 @@ mod tests {
         }));
     }
 
+    #[tokio::test]
+    async fn convert_response_keeps_interleaved_text_and_audio_in_order() {
+        // Recorded from a native-audio model asked for TEXT and AUDIO output,
+        // with the audio payloads shortened.
+        let pcm = |bytes: &[u8]| crate::attachment::encode_base64(bytes);
+        let recorded = serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        { "text": "Here is the first line." },
+                        { "inlineData": { "mimeType": "audio/L16;codec=pcm;rate=24000", "data": pcm(&[1, 2]) } },
+                        { "text": "And the second." },
+                        { "inlineData": { "mimeType": "audio/L16;codec=pcm;rate=24000", "data": pcm(&[3, 4]) } }
+                    ]
+                },
+                "finishReason": "STOP",
+                "index": 0
+            }],
+            "modelVersion": "gemini-2.5-flash-native-audio"
+        });
+        let response: adk_gemini::GenerationResponse =
+            serde_json::from_value(recorded).expect("parse recorded response");
+
+        let converted = GeminiModel::convert_response(&response).expect("convert response");
+        let kinds: Vec<_> = converted
+            .parts()
+            .iter()
+            .map(|part| match part {
+                Part::Text { text } => format!("text:{text}"),
+                Part::InlineData { mime_type, data } => format!("{mime_type}:{data:?}"),
+                other => panic!("unexpected part {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "text:Here is the first line.",
+                "audio/L16;codec=pcm;rate=24000:[1, 2]",
+                "text:And the second.",
+                "audio/L16;codec=pcm;rate=24000:[3, 4]",
+            ]
+        );
+        assert_eq!(converted.text(), "Here is the first line.And the second.");
+
+        // Streamed as one chunk per part plus a split audio chunk, the
+        // aggregate keeps the same sequence.
+        let mut chunks: Vec<adk_core::Result<LlmResponse>> = converted
+            .parts()
+            .iter()
+            .map(|part| {
+                Ok(LlmResponse::new(Content { role: "model".into(), parts: vec![part.clone()] }))
+            })
+            .collect();
+        chunks.push(Ok(LlmResponse::new(Content {
+            role: "model".into(),
+            parts: vec![Part::InlineData {
+                mime_type: "audio/L16;codec=pcm;rate=24000".into(),
+                data: vec![5],
+            }],
+        })));
+        let aggregated =
+            crate::gemini::streaming::aggregate_stream(Box::pin(futures::stream::iter(chunks)))
+                .await
+                .expect("aggregate stream");
+        assert_eq!(aggregated.parts().len(), 4);
+        assert!(matches!(
+            &aggregated.parts()[3],
+            Part::InlineData { data, .. } if data.as_slice() == [3, 4, 5]
+        ));
+        assert_eq!(aggregated.text(), converted.text());
+    }
+
+    #[test]
+    fn audio_output_settings_reach_generation_config() {
+        let model = GeminiModel::new("test-key", "gemini-2.5-flash-preview-tts")
+            .expect("construct model")
+            .with_response_modalities(["AUDIO"])
+            .with_speech_config(adk_gemini::SpeechConfig::single_voice("Kore"));
+        let request = LlmRequest::new(
+            "gemini-2.5-flash-preview-tts",
+            vec![Content::new("user").with_text("Say hello")],
+        );
+
+        let builder = model.build_request(request).expect("build request");
+        let body = serde_json::to_value(builder.build()).expect("serialize request");
+
+        assert_eq!(body["generationConfig"]["responseModalities"], serde_json::json!(["AUDIO"]));
+        assert_eq!(
+            body["generationConfig"]["speechConfig"]["voiceConfig"]["prebuiltVoiceConfig"]["voiceName"],
+            "Kore"
+        );
+    }
+
+    #[test]
+    fn request_sampling_config_reaches_generation_config() {
+        let model = GeminiModel::new("test-key", "gemini-2.5-flash").expect("construct model");
+        let mut request =
+            LlmRequest::new("gemini-2.5-flash", vec![Content::new("user").with_text("Hi")]);
+        request.config = Some(adk_core::GenerateContentConfig {
+            temperature: Some(0.0),
+            top_p: Some(0.5),
+            top_k: Some(20),
+            max_output_tokens: Some(128),
+            stop_sequences: vec!["END".to_string()],
+            frequency_penalty: Some(0.3),
+            ..Default::default()
+        });
+
+        let builder = model.build_request(request).expect("build request");
+        let body = serde_json::to_value(builder.build()).expect("serialize request");
+        let config = &body["generationConfig"];
+
+        assert_eq!(config["temperature"], serde_json::json!(0.0));
+        assert_eq!(config["topP"], serde_json::json!(0.5));
+        assert_eq!(config["topK"], 20);
+        assert_eq!(config["maxOutputTokens"], 128);
+        assert_eq!(config["stopSequences"], serde_json::json!(["END"]));
+        assert!(config.get("frequencyPenalty").is_none());
+    }
+
+    fn gemini_chunk(
+        parts: Vec<adk_gemini::Part>,
+        grounding_metadata: Option<adk_gemini::GroundingMetadata>,
+        finish_reason: Option<adk_gemini::FinishReason>,
+    ) -> adk_gemini::GenerationResponse {
+        adk_gemini::GenerationResponse {
+            candidates: vec![adk_gemini::Candidate {
+                content: adk_gemini::Content {
+                    role: Some(adk_gemini::Role::Model),
+                    parts: (!parts.is_empty()).then_some(parts),
+                },
+                safety_ratings: None,
+                citation_metadata: None,
+                grounding_metadata,
+                finish_reason,
+                index: Some(0),
+            }],
+            prompt_feedback: None,
+            usage_metadata: None,
+            model_version: None,
+            response_id: None,
+        }
+    }
+
+    fn search_grounding() -> adk_gemini::GroundingMetadata {
+        adk_gemini::GroundingMetadata {
+            grounding_chunks: None,
+            grounding_supports: None,
+            web_search_queries: Some(vec!["rust 2024 edition release date".to_string()]),
+            google_maps_widget_context_token: None,
+        }
+    }
+
+    fn text_part(text: &str) -> adk_gemini::Part {
+        adk_gemini::Part::Text { text: text.to_string(), thought: None, thought_signature: None }
+    }
+
+    #[test]
+    fn empty_stop_with_grounding_is_surfaced_as_retryable_error() {
+        let response =
+            gemini_chunk(vec![], Some(search_grounding()), Some(adk_gemini::FinishReason::Stop));
+
+        let error = EmptyStopDetector::new(true).observe(&response).unwrap_err();
+        assert_eq!(error.code, EMPTY_STOP_WITH_TOOL_INTENT);
+        assert_eq!(error.details.provider.as_deref(), Some("gemini"));
+        assert!(is_retryable_model_error(&error));
+    }
+
+    #[test]
+    fn empty_stop_after_streamed_server_tool_call_is_detected() {
+        let tool_call = adk_gemini::Part::ToolCall {
+            tool_call: serde_json::json!({ "toolType": "URL_CONTEXT" }),
+            thought_signature: None,
+        };
+        let mut detector = EmptyStopDetector::new(false);
+
+        assert!(detector.observe(&gemini_chunk(vec![tool_call], None, None)).is_ok());
+        let error = detector
+            .observe(&gemini_chunk(vec![], None, Some(adk_gemini::FinishReason::Stop)))
+            .unwrap_err();
+        assert_eq!(error.code, EMPTY_STOP_WITH_TOOL_INTENT);
+    }
+
+    #[test]
+    fn stop_after_streamed_answer_is_accepted() {
+        let mut detector = EmptyStopDetector::new(true);
+
+        let first = gemini_chunk(vec![text_part("Rust 2024 shipped")], None, None);
+        let last = gemini_chunk(
+            vec![text_part("")],
+            Some(search_grounding()),
+            Some(adk_gemini::FinishReason::Stop),
+        );
+        assert!(detector.observe(&first).is_ok());
+        assert!(detector.observe(&last).is_ok());
+    }
+
+    #[test]
+    fn empty_stop_without_tool_intent_is_passed_through() {
+        let response = gemini_chunk(vec![], None, Some(adk_gemini::FinishReason::Stop));
+        assert!(EmptyStopDetector::new(false).observe(&response).is_ok());
+    }
+
+    #[tokio::test]
+    async fn empty_stop_with_tool_intent_is_reissued_by_retry() {
+        let retry_config = RetryConfig::default()
+            .with_max_retries(2)
+            .with_initial_delay(Duration::from_millis(0))
+            .with_max_delay(Duration::from_millis(0));
+        let responses = Arc::new(std::sync::Mutex::new(vec![
+            gemini_chunk(
+                vec![text_part("Rust 2024 shipped in February 2025.")],
+                Some(search_grounding()),
+                Some(adk_gemini::FinishReason::Stop),
+            ),
+            gemini_chunk(vec![], Some(search_grounding()), Some(adk_gemini::FinishReason::Stop)),
+        ]));
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let converted = execute_with_retry(&retry_config, is_retryable_model_error, || {
+            let responses = Arc::clone(&responses);
+            let attempts = Arc::clone(&attempts);
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                let response = responses.lock().unwrap().pop().unwrap();
+                EmptyStopDetector::new(true).observe(&response)?;
+                GeminiModel::convert_response(&response)
+            }
+        })
+        .await
+        .expect("second attempt should produce an answer");
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        let content = converted.content.expect("answer should not be empty");
+        assert_eq!(content.parts[0].text(), Some("Rust 2024 shipped in February 2025."));
+    }
+
     #[test]
     fn gemini_function_response_payload_preserves_objects() {
         let value = serde_json::json!({
@@ -3,12 +3,17 @@ use futures::Stream;
 use std::pin::Pin;
 
 /// Aggregate streaming responses into a single response
+///
+/// Parts keep the order they were streamed in. Adjacent text chunks are
+/// joined, as are adjacent chunks of raw PCM audio with the same MIME type,
+/// so text interleaved with audio comes out as alternating text and audio
+/// parts.
 pub async fn aggregate_stream(
     mut stream: Pin<Box<dyn Stream<Item = adk_core::Result<LlmResponse>> + Send>>,
 ) -> adk_core::Result<LlmResponse> {
     use futures::StreamExt;
 
-    let mut aggregated_text = String::new();
+    let mut parts: Vec<Part> = Vec::new();
     let mut last_response: Option<LlmResponse> = None;
 
     while let Some(result) = stream.next().await {
@@ -16,9 +21,7 @@ pub async fn aggregate_stream(
 
         if let Some(content) = &response.content {
             for part in &content.parts {
-                if let Part::Text { text } = part {
-                    aggregated_text.push_str(text);
-                }
+                push_part(&mut parts, part.clone());
             }
         }
 
@@ -28,12 +31,32 @@ pub async fn aggregate_stream(
     let mut final_response = last_response
         .ok_or_else(|| adk_core::AdkError::model("No responses received from stream"))?;
 
-    final_response.content = Some(Content {
-        role: "model".to_string(),
-        parts: vec![Part::Text { text: aggregated_text }],
-    });
+    final_response.content = Some(Content { role: "model".to_string(), parts });
     final_response.partial = false;
     final_response.turn_complete = true;
 
     Ok(final_response)
 }
+
+/// Append `part`, joining it onto the previous part when both are text or
+/// both are raw PCM audio of the same type. Encoded audio has headers, so its
+/// chunks stay separate.
+fn push_part(parts: &mut Vec<Part>, part: Part) {
+    match (parts.last_mut(), part) {
+        (Some(Part::Text { text }), Part::Text { text: next }) => text.push_str(&next),
+        (
+            Some(Part::InlineData { mime_type, data }),
+            Part::InlineData { mime_type: next_mime_type, data: next },
+        ) if is_raw_pcm(mime_type) && *mime_type == next_mime_type => {
+            data.extend(next);
+        }
+        (_, part) => parts.push(part),
+    }
+}
+
+/// Whether `mime_type` is headerless PCM audio, such as Gemini's
+/// `audio/L16;codec=pcm;rate=24000`.
+fn is_raw_pcm(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    matches!(essence.as_str(), "audio/l16" | "audio/pcm")
+}
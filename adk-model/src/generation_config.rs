@@ -0,0 +1,28 @@
+//! Shared handling of [`GenerateContentConfig`] sampling fields across providers.
+
+use adk_core::GenerateContentConfig;
+
+/// Logs, at debug level, each sampling field set on `config` that `provider`
+/// does not map, so unsupported settings are skipped instead of failing.
+pub(crate) fn log_unsupported_fields(
+    provider: &str,
+    config: &GenerateContentConfig,
+    supported: &[&str],
+) {
+    let set_fields = [
+        ("temperature", config.temperature.is_some()),
+        ("top_p", config.top_p.is_some()),
+        ("top_k", config.top_k.is_some()),
+        ("frequency_penalty", config.frequency_penalty.is_some()),
+        ("presence_penalty", config.presence_penalty.is_some()),
+        ("max_output_tokens", config.max_output_tokens.is_some()),
+        ("seed", config.seed.is_some()),
+        ("top_logprobs", config.top_logprobs.is_some()),
+        ("stop_sequences", !config.stop_sequences.is_empty()),
+    ];
+    for (field, is_set) in set_fields {
+        if is_set && !supported.contains(&field) {
+            tracing::debug!(provider, field, "generation config field not supported; ignoring");
+        }
+    }
+}
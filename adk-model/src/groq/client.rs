@@ -2,7 +2,10 @@
 
 use super::config::{GROQ_API_BASE, GroqConfig};
 use super::convert::{self, ChatCompletionRequest, ChatCompletionResponse};
-use crate::retry::{RetryConfig, execute_with_retry, is_retryable_model_error};
+use crate::prompt_layout::PromptLayout;
+use crate::retry::{
+    RetryConfig, execute_stream_with_retry, is_retryable_model_error, retry_after_from_headers,
+};
 use adk_core::{
     AdkError, ErrorCategory, ErrorComponent, FinishReason, Llm, LlmRequest, LlmResponse,
     LlmResponseStream, Part,
@@ -37,7 +40,8 @@ impl GroqClient {
             .build()
             .map_err(|e| AdkError::model(format!("Failed to create HTTP client: {e}")))?;
 
-        Ok(Self { client, config, retry_config: RetryConfig::default() })
+        let retry_config = config.retry_config.clone();
+        Ok(Self { client, config, retry_config })
     }
 
     /// Create a client for llama-3.3-70b-versatile model.
@@ -80,7 +84,8 @@ impl GroqClient {
 
     /// Build a chat completion request from an LLM request.
     fn build_request(&self, request: &LlmRequest, stream: bool) -> ChatCompletionRequest {
-        let messages: Vec<_> = request.contents.iter().map(convert::content_to_message).collect();
+        let contents = PromptLayout::groq().normalize(&request.contents).contents;
+        let messages: Vec<_> = contents.iter().map(convert::content_to_message).collect();
 
         let tools = if request.tools.is_empty() {
             None
@@ -110,85 +115,18 @@ impl GroqClient {
             include_reasoning,
         }
     }
-}
 
-#[async_trait]
-impl Llm for GroqClient {
-    fn name(&self) -> &str {
-        &self.config.model
-    }
-
-    #[tracing::instrument(
-        name = "model.generate_content",
-        skip_all,
-        fields(
-            model.name = %self.name(),
-            stream = %stream,
-            request.contents_count = %request.contents.len(),
-            request.tools_count = %request.tools.len()
-        )
-    )]
-    async fn generate_content(
-        &self,
-        request: LlmRequest,
+    /// Sends `chat_request` and turns the response into chunks. A failed
+    /// request is yielded as the stream's first item.
+    fn response_stream(
+        client: Client,
+        api_url: String,
+        api_key: String,
+        chat_request: ChatCompletionRequest,
         stream: bool,
-    ) -> Result<LlmResponseStream, AdkError> {
-        let usage_span = adk_telemetry::llm_generate_span("groq", &self.config.model, stream);
-        let api_url = self.api_url();
-        let api_key = self.config.api_key.clone();
-        let chat_request = self.build_request(&request, stream);
-        let client = self.client.clone();
-        let retry_config = self.retry_config.clone();
-
+    ) -> LlmResponseStream {
         let response_stream = try_stream! {
-            // Retries only cover request setup/execution. Stream failures after start are surfaced
-            // directly and are not auto-replayed.
-            let response = execute_with_retry(&retry_config, is_retryable_model_error, || {
-                let client = client.clone();
-                let api_url = api_url.clone();
-                let api_key = api_key.clone();
-                let chat_request = chat_request.clone();
-                async move {
-                    let response = client
-                        .post(&api_url)
-                        .header("Authorization", format!("Bearer {}", api_key))
-                        .header("Content-Type", "application/json")
-                        .json(&chat_request)
-                        .send()
-                        .await
-                        .map_err(|e| AdkError::new(
-                            ErrorComponent::Model,
-                            ErrorCategory::Unavailable,
-                            "model.groq.request",
-                            format!("Groq API request failed: {e}"),
-                        ).with_provider("groq"))?;
-
-                    if !response.status().is_success() {
-                        let status = response.status();
-                        let status_code = status.as_u16();
-                        let error_text = response.text().await.unwrap_or_default();
-                        let category = match status_code {
-                            401 => ErrorCategory::Unauthorized,
-                            403 => ErrorCategory::Forbidden,
-                            404 => ErrorCategory::NotFound,
-                            408 => ErrorCategory::Timeout,
-                            429 => ErrorCategory::RateLimited,
-                            503 | 529 => ErrorCategory::Unavailable,
-                            _ if status_code >= 500 => ErrorCategory::Internal,
-                            _ => ErrorCategory::InvalidInput,
-                        };
-                        return Err(AdkError::new(
-                            ErrorComponent::Model,
-                            category,
-                            "model.groq.api_error",
-                            format!("Groq API error (HTTP {status}): {error_text}"),
-                        ).with_upstream_status(status_code).with_provider("groq"));
-                    }
-
-                    Ok(response)
-                }
-            })
-            .await?;
+            let response = send_request(&client, &api_url, &api_key, &chat_request).await?;
 
             if stream {
                 // Streaming mode - process SSE events
@@ -324,6 +262,7 @@ impl Llm for GroqClient {
                                                 error_message: None,
                                                 provider_metadata: None,
                                                 interaction_id: None,
+                                                citations: Vec::new(),
                                             };
                                         } else {
                                             // Emit partial text content
@@ -347,6 +286,7 @@ impl Llm for GroqClient {
                                                             error_message: None,
                                                             provider_metadata: None,
                                                             interaction_id: None,
+                                                            citations: Vec::new(),
                                                         };
                                                     }
                                         }
@@ -372,7 +312,86 @@ impl Llm for GroqClient {
                 yield convert::from_response(&chat_response);
             }
         };
+        Box::pin(response_stream)
+    }
+}
 
-        Ok(crate::usage_tracking::with_usage_tracking(Box::pin(response_stream), usage_span))
+#[async_trait]
+impl Llm for GroqClient {
+    fn name(&self) -> &str {
+        &self.config.model
     }
+
+    #[tracing::instrument(
+        name = "model.generate_content",
+        skip_all,
+        fields(
+            model.name = %self.name(),
+            stream = %stream,
+            request.contents_count = %request.contents.len(),
+            request.tools_count = %request.tools.len()
+        )
+    )]
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream, AdkError> {
+        let usage_span = adk_telemetry::llm_generate_span("groq", &self.config.model, stream);
+        let api_url = self.api_url();
+        let chat_request = self.build_request(&request, stream);
+
+        let response_stream =
+            execute_stream_with_retry(&self.retry_config, is_retryable_model_error, || {
+                std::future::ready(Ok(Self::response_stream(
+                    self.client.clone(),
+                    api_url.clone(),
+                    self.config.api_key.clone(),
+                    chat_request.clone(),
+                    stream,
+                )))
+            })
+            .await?;
+
+        Ok(crate::usage_tracking::with_usage_tracking(response_stream, usage_span))
+    }
+}
+
+/// Sends a chat completion request, mapping a failed status to an `AdkError`.
+async fn send_request(
+    client: &Client,
+    api_url: &str,
+    api_key: &str,
+    chat_request: &ChatCompletionRequest,
+) -> Result<reqwest::Response, AdkError> {
+    let response =
+        client.post(api_url).bearer_auth(api_key).json(chat_request).send().await.map_err(|e| {
+            AdkError::new(
+                ErrorComponent::Model,
+                ErrorCategory::Unavailable,
+                "model.groq.request",
+                format!("Groq API request failed: {e}"),
+            )
+            .with_provider("groq")
+        })?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let retry_after =
+            retry_after_from_headers(|name| response.headers().get(name)?.to_str().ok());
+        let error_text = response.text().await.unwrap_or_default();
+        let mut err = AdkError::from_http_status(
+            ErrorComponent::Model,
+            "model.groq.api_error",
+            status.as_u16(),
+            format!("Groq API error (HTTP {status}): {error_text}"),
+        )
+        .with_provider("groq");
+        if let Some(delay) = retry_after {
+            err = err.with_retry_after(delay);
+        }
+        return Err(err);
+    }
+
+    Ok(response)
 }
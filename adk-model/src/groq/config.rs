@@ -1,5 +1,6 @@
 //! Configuration types for Groq provider.
 
+use crate::retry::RetryConfig;
 use serde::{Deserialize, Serialize};
 
 /// Default Groq API base URL.
@@ -21,6 +22,9 @@ pub struct GroqConfig {
     /// Maximum tokens for output.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    /// Retry policy for transient failures such as rate limits.
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 impl Default for GroqConfig {
@@ -31,6 +35,7 @@ impl Default for GroqConfig {
             base_url: None,
             reasoning_enabled: false,
             max_tokens: None,
+            retry_config: RetryConfig::default(),
         }
     }
 }
@@ -79,6 +84,12 @@ impl GroqConfig {
         self
     }
 
+    /// Set the retry policy for transient failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
     /// Get the effective base URL.
     pub fn effective_base_url(&self) -> &str {
         self.base_url.as_deref().unwrap_or(GROQ_API_BASE)
@@ -315,6 +315,7 @@ pub fn from_response(response: &ChatCompletionResponse) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -345,6 +346,7 @@ pub fn create_tool_call_response(
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -0,0 +1,300 @@
+//! Tool calling emulated over JSON output for models without native function calling.
+//!
+//! Some OpenAI-compatible endpoints (typically local models) reject the
+//! `tools` request field but can still be asked for a JSON response. For those,
+//! [`JsonModeToolAdapter`] describes the available tools in a system message,
+//! asks for a JSON object naming the chosen tool and its arguments, and parses
+//! that object back into [`Part::FunctionCall`] entries the runner can execute.
+//!
+//! The model is expected to answer with one of:
+//!
+//! ```json
+//! {"tool_calls": [{"name": "get_weather", "arguments": {"city": "Paris"}}]}
+//! {"response": "It is sunny in Paris."}
+//! ```
+//!
+//! Earlier tool calls and results in the conversation are replayed as plain
+//! text so the endpoint never sees tool-specific message types. Output that is
+//! not valid tool-selection JSON is passed through unchanged as text.
+
+use adk_core::{
+    Content, FinishReason, GenerateContentConfig, Llm, LlmRequest, LlmResponse, LlmResponseStream,
+    Part, Result,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Wraps a model so tool calls are negotiated through JSON output instead of
+/// the provider's native function-calling API.
+///
+/// Requests without tools are forwarded untouched.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_model::JsonModeToolAdapter;
+/// use std::sync::Arc;
+///
+/// let model = Arc::new(JsonModeToolAdapter::new(Arc::new(local_model)));
+/// ```
+pub struct JsonModeToolAdapter {
+    inner: Arc<dyn Llm>,
+}
+
+impl JsonModeToolAdapter {
+    /// Wrap `inner`, emulating tool calls for every request that declares tools.
+    pub fn new(inner: Arc<dyn Llm>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl Llm for JsonModeToolAdapter {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        if request.tools.is_empty() {
+            return self.inner.generate_content(request, stream).await;
+        }
+        generate_with_json_tools(self.inner.as_ref(), request).await
+    }
+}
+
+/// Run `request` against `model` with its tools emulated over JSON output.
+///
+/// The whole reply is needed before it can be parsed, so the inner model is
+/// always called without streaming and a single response is yielded.
+pub(crate) async fn generate_with_json_tools(
+    model: &dyn Llm,
+    request: LlmRequest,
+) -> Result<LlmResponseStream> {
+    let tools = request.tools.clone();
+    let rewritten = rewrite_request(request);
+
+    let mut inner = model.generate_content(rewritten, false).await?;
+    let mut text = String::new();
+    let mut last: Option<LlmResponse> = None;
+    while let Some(response) = inner.next().await {
+        let response = response?;
+        if let Some(content) = &response.content {
+            text.extend(content.parts.iter().filter_map(Part::text));
+        }
+        last = Some(response);
+    }
+
+    let mut response = last.unwrap_or_else(|| LlmResponse::new(Content::new("model")));
+    let parts = parse_tool_selection(&text, &tools)
+        .unwrap_or_else(|| if text.is_empty() { Vec::new() } else { vec![Part::Text { text }] });
+    let has_calls = parts.iter().any(|part| matches!(part, Part::FunctionCall { .. }));
+
+    response.content = (!parts.is_empty()).then(|| Content { role: "model".to_string(), parts });
+    response.partial = false;
+    // Tool-call turns are not complete until the tool results are processed.
+    response.turn_complete = !has_calls;
+    if has_calls && response.finish_reason.is_none() {
+        response.finish_reason = Some(FinishReason::Stop);
+    }
+
+    Ok(Box::pin(futures::stream::once(async { Ok(response) })))
+}
+
+/// Strip native tool declarations from `request`, describe them in a system
+/// message instead, and flatten earlier tool traffic into text.
+fn rewrite_request(mut request: LlmRequest) -> LlmRequest {
+    let tools = std::mem::take(&mut request.tools);
+
+    let mut contents = Vec::with_capacity(request.contents.len() + 1);
+    contents.push(Content::new("system").with_text(tool_instructions(&tools)));
+    contents.extend(request.contents.into_iter().map(flatten_tool_parts));
+    request.contents = contents;
+
+    // Ask OpenAI-compatible endpoints for a JSON object; other providers ignore this.
+    let config = request.config.get_or_insert_with(GenerateContentConfig::default);
+    if config.response_schema.is_none() {
+        let openai = config.extensions.entry("openai").or_insert_with(|| json!({}));
+        if let Some(openai) = openai.as_object_mut() {
+            openai.entry("response_format").or_insert_with(|| json!({ "type": "json_object" }));
+        }
+    }
+
+    request
+}
+
+fn tool_instructions(tools: &HashMap<String, Value>) -> String {
+    let mut names: Vec<&String> = tools.keys().collect();
+    names.sort();
+
+    let mut instructions = String::from(
+        "You can call the tools listed below. Reply with a single JSON object and nothing else.\n\
+         To call one or more tools, reply with:\n\
+         {\"tool_calls\": [{\"name\": \"<tool name>\", \"arguments\": {<arguments matching the tool parameters>}}]}\n\
+         To answer without calling a tool, reply with:\n\
+         {\"response\": \"<your answer>\"}\n\nAvailable tools:",
+    );
+    for name in names {
+        let decl = &tools[name];
+        let description = decl.get("description").and_then(Value::as_str).unwrap_or_default();
+        let parameters = decl.get("parameters").cloned().unwrap_or_else(|| json!({}));
+        instructions.push_str(&format!("\n- {name}: {description}\n  parameters: {parameters}"));
+    }
+    instructions
+}
+
+/// Replace function calls and responses with text the endpoint can accept.
+fn flatten_tool_parts(content: Content) -> Content {
+    let has_tool_parts = content
+        .parts
+        .iter()
+        .any(|part| matches!(part, Part::FunctionCall { .. } | Part::FunctionResponse { .. }));
+    if !has_tool_parts {
+        return content;
+    }
+
+    let role = if content.role == "function" { "user".to_string() } else { content.role };
+    let mut calls = Vec::new();
+    let mut parts = Vec::new();
+    for part in content.parts {
+        match part {
+            Part::FunctionCall { name, args, .. } => {
+                calls.push(json!({ "name": name, "arguments": args }));
+            }
+            Part::FunctionResponse { function_response, .. } => parts.push(Part::Text {
+                text: format!(
+                    "Result of tool `{}`: {}",
+                    function_response.name, function_response.response
+                ),
+            }),
+            other => parts.push(other),
+        }
+    }
+    if !calls.is_empty() {
+        parts.push(Part::Text { text: json!({ "tool_calls": calls }).to_string() });
+    }
+    Content { role, parts }
+}
+
+/// Parse a tool-selection reply into parts, or `None` if `text` is not one.
+///
+/// Calls naming tools that were not declared are dropped; if nothing usable
+/// remains the reply is treated as plain text.
+pub(crate) fn parse_tool_selection(
+    text: &str,
+    tools: &HashMap<String, Value>,
+) -> Option<Vec<Part>> {
+    let value: Value = serde_json::from_str(strip_code_fence(text)).ok()?;
+    let object = value.as_object()?;
+
+    if let Some(calls) = object.get("tool_calls").and_then(Value::as_array) {
+        let parts: Vec<Part> = calls.iter().filter_map(|call| function_call(call, tools)).collect();
+        return (!parts.is_empty()).then_some(parts);
+    }
+    if object.contains_key("name") {
+        return function_call(&value, tools).map(|part| vec![part]);
+    }
+    object
+        .get("response")
+        .and_then(Value::as_str)
+        .map(|text| vec![Part::Text { text: text.to_string() }])
+}
+
+fn function_call(call: &Value, tools: &HashMap<String, Value>) -> Option<Part> {
+    let name = call.get("name").and_then(Value::as_str)?;
+    if !tools.contains_key(name) {
+        tracing::warn!(tool = name, "model selected an undeclared tool in JSON mode");
+        return None;
+    }
+    let args = match call.get("arguments") {
+        // Some models double-encode the arguments as a JSON string.
+        Some(Value::String(raw)) => serde_json::from_str(raw).unwrap_or_else(|_| json!({})),
+        Some(args) => args.clone(),
+        None => json!({}),
+    };
+    Some(Part::FunctionCall { name: name.to_string(), args, id: None, thought_signature: None })
+}
+
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weather_tools() -> HashMap<String, Value> {
+        HashMap::from([(
+            "get_weather".to_string(),
+            json!({
+                "description": "Current weather for a city",
+                "parameters": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }),
+        )])
+    }
+
+    #[test]
+    fn parses_fenced_single_call_with_string_arguments() {
+        let text = "```json\n{\"name\": \"get_weather\", \"arguments\": \"{\\\"city\\\": \\\"Oslo\\\"}\"}\n```";
+        let parts = parse_tool_selection(text, &weather_tools()).unwrap();
+        assert!(matches!(
+            &parts[0],
+            Part::FunctionCall { name, args, .. } if name == "get_weather" && args["city"] == "Oslo"
+        ));
+    }
+
+    #[test]
+    fn undeclared_tools_and_plain_text_are_not_calls() {
+        let tools = weather_tools();
+        assert!(
+            parse_tool_selection(r#"{"tool_calls": [{"name": "rm_rf", "arguments": {}}]}"#, &tools)
+                .is_none()
+        );
+        assert!(parse_tool_selection("It is sunny.", &tools).is_none());
+    }
+
+    #[test]
+    fn history_tool_traffic_is_flattened_to_text() {
+        let call = Content {
+            role: "model".to_string(),
+            parts: vec![Part::FunctionCall {
+                name: "get_weather".to_string(),
+                args: json!({ "city": "Oslo" }),
+                id: None,
+                thought_signature: None,
+            }],
+        };
+        let result = Content {
+            role: "function".to_string(),
+            parts: vec![Part::FunctionResponse {
+                function_response: adk_core::FunctionResponseData::new(
+                    "get_weather",
+                    json!({ "temp_c": 4 }),
+                ),
+                id: None,
+            }],
+        };
+
+        let call = flatten_tool_parts(call);
+        let result = flatten_tool_parts(result);
+        let replayed: Value = serde_json::from_str(call.parts[0].text().unwrap()).unwrap();
+        assert_eq!(
+            replayed,
+            json!({ "tool_calls": [{ "name": "get_weather", "arguments": { "city": "Oslo" } }] })
+        );
+        assert_eq!(result.role, "user");
+        assert_eq!(result.parts[0].text(), Some(r#"Result of tool `get_weather`: {"temp_c":4}"#));
+    }
+}
@@ -17,6 +17,7 @@
 //! - `AnthropicClient` - Anthropic Claude models — requires `anthropic` feature
 //! - `DeepSeekClient` - DeepSeek models — requires `deepseek` feature
 //! - `GroqClient` - Groq ultra-fast inference — requires `groq` feature
+//! - `MistralClient` - Mistral La Plateforme (Small, Large, Codestral, Magistral) — requires `mistral` feature
 //! - `OpenRouterClient` - OpenRouter-native chat, responses, and discovery APIs — requires `openrouter` feature
 //! - `OllamaModel` - Local LLMs via Ollama — requires `ollama` feature
 //! - `BedrockClient` - Amazon Bedrock via AWS SDK — requires `bedrock` feature
@@ -215,6 +216,9 @@
 //! | SambaNova | `OpenAICompatibleConfig::sambanova()` | `SAMBANOVA_API_KEY` |
 //! | xAI (Grok) | `OpenAICompatibleConfig::xai()` | `XAI_API_KEY` |
 //!
+//! The `mistral` feature also enables the native `MistralClient`, which handles Mistral's
+//! tool call id format, reasoning chunks, and JSON schema output.
+//!
 //! ### Other Providers
 //!
 //! | Provider | Feature Flag | Env Var |
@@ -260,13 +264,21 @@ pub(crate) mod attachment;
 pub mod azure_ai;
 #[cfg(feature = "bedrock")]
 pub mod bedrock;
+/// Content policy enforcement on prompts and responses, for any provider.
+pub mod content_filter;
 #[cfg(feature = "deepseek")]
 pub mod deepseek;
 /// Gemini model provider (Google AI Studio and Vertex AI).
 #[cfg(feature = "gemini")]
 pub mod gemini;
+#[cfg(any(feature = "anthropic", feature = "gemini", feature = "openai"))]
+pub(crate) mod generation_config;
 #[cfg(feature = "groq")]
 pub mod groq;
+/// Tool calling emulated over JSON output for models without native function calling.
+pub mod json_mode_tools;
+#[cfg(feature = "mistral")]
+pub mod mistral;
 /// Mock LLM for testing without real API calls.
 pub mod mock;
 #[cfg(feature = "ollama")]
@@ -277,20 +289,23 @@ pub mod openai;
 pub mod openai_compatible;
 #[cfg(feature = "openrouter")]
 pub mod openrouter;
+/// Request validation before a provider is called.
+pub mod preflight;
+/// Provider-specific placement of system instructions and multimodal parts.
+pub mod prompt_layout;
 /// Canonical provider identifiers and metadata.
 pub mod provider;
+/// Caching of full responses for repeated deterministic requests.
+pub mod response_cache;
 /// Retry logic with exponential backoff for transient provider errors.
 pub mod retry;
+/// Per-request routing between several models.
+pub mod router;
+/// Keeping partial output when a streaming connection drops mid-generation.
+pub mod stream_recovery;
 pub mod tool_call_parser;
-#[cfg(any(
-    feature = "openai",
-    feature = "ollama",
-    feature = "deepseek",
-    feature = "groq",
-    feature = "bedrock",
-    feature = "azure-ai"
-))]
-pub(crate) mod tool_result;
+/// Linking tool results to their calls across providers.
+pub mod tool_result;
 pub mod usage_tracking;
 
 #[cfg(feature = "anthropic")]
@@ -299,12 +314,16 @@ pub use anthropic::AnthropicClient;
 pub use azure_ai::{AzureAIClient, AzureAIConfig};
 #[cfg(feature = "bedrock")]
 pub use bedrock::{BedrockClient, BedrockConfig};
+pub use content_filter::{BlocklistFilter, ContentFilter, FilteredLlm, ModerationModelFilter};
 #[cfg(feature = "deepseek")]
 pub use deepseek::{DeepSeekClient, DeepSeekConfig};
 #[cfg(feature = "gemini")]
 pub use gemini::GeminiModel;
 #[cfg(feature = "groq")]
 pub use groq::{GroqClient, GroqConfig};
+pub use json_mode_tools::JsonModeToolAdapter;
+#[cfg(feature = "mistral")]
+pub use mistral::{MistralClient, MistralConfig};
 pub use mock::MockLlm;
 #[cfg(feature = "ollama")]
 pub use ollama::{OllamaConfig, OllamaModel};
@@ -314,6 +333,12 @@ pub use openai::{AzureConfig, AzureOpenAIClient, OpenAIClient, OpenAIConfig, Rea
 pub use openai_compatible::{OpenAICompatible, OpenAICompatibleConfig};
 #[cfg(feature = "openrouter")]
 pub use openrouter::{OpenRouterApiMode, OpenRouterClient, OpenRouterConfig};
+pub use preflight::{MediaLimits, PreflightLlm};
+pub use prompt_layout::PromptLayout;
 pub use provider::ModelProvider;
+pub use response_cache::{CacheStats, CachingLlm, InMemoryResponseCache, ResponseCache};
 pub use retry::RetryConfig;
 pub use retry::ServerRetryHint;
+pub use retry::StatusClasses;
+pub use router::{RouterLlm, RoutingPolicy};
+pub use stream_recovery::{StreamRecovery, StreamRecoveryLlm};
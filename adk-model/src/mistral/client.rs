@@ -0,0 +1,384 @@
+//! Mistral client implementation.
+
+use super::config::MistralConfig;
+use super::convert::{self, ChatCompletionRequest, ChatCompletionResponse, StreamAccumulator};
+use crate::prompt_layout::PromptLayout;
+use crate::retry::{RetryConfig, execute_with_retry, is_retryable_model_error};
+use adk_core::{AdkError, ErrorCategory, ErrorComponent, Llm, LlmRequest, LlmResponseStream};
+use async_stream::try_stream;
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+
+/// Client for Mistral's La Plateforme chat completions API.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_model::mistral::{MistralClient, MistralConfig};
+///
+/// let client = MistralClient::new(MistralConfig::small(
+///     std::env::var("MISTRAL_API_KEY").unwrap()
+/// ))?;
+/// ```
+pub struct MistralClient {
+    client: Client,
+    config: MistralConfig,
+    retry_config: RetryConfig,
+}
+
+impl MistralClient {
+    /// Create a new Mistral client.
+    pub fn new(config: MistralConfig) -> Result<Self, AdkError> {
+        let client = Client::builder()
+            .build()
+            .map_err(|e| AdkError::model(format!("Failed to create HTTP client: {e}")))?;
+
+        Ok(Self { client, config, retry_config: RetryConfig::default() })
+    }
+
+    /// Create a client for the `mistral-small-latest` model.
+    pub fn small(api_key: impl Into<String>) -> Result<Self, AdkError> {
+        Self::new(MistralConfig::small(api_key))
+    }
+
+    /// Create a client for the `mistral-large-latest` model.
+    pub fn large(api_key: impl Into<String>) -> Result<Self, AdkError> {
+        Self::new(MistralConfig::large(api_key))
+    }
+
+    /// Set the retry configuration (builder pattern).
+    #[must_use]
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Set the retry configuration (mutable reference).
+    pub fn set_retry_config(&mut self, retry_config: RetryConfig) {
+        self.retry_config = retry_config;
+    }
+
+    /// Returns the current retry configuration.
+    pub fn retry_config(&self) -> &RetryConfig {
+        &self.retry_config
+    }
+
+    /// Build the API URL for chat completions.
+    fn api_url(&self) -> String {
+        format!("{}/chat/completions", self.config.effective_base_url().trim_end_matches('/'))
+    }
+
+    /// Build a chat completion request from an LLM request.
+    fn build_request(&self, request: &LlmRequest, stream: bool) -> ChatCompletionRequest {
+        let normalized = PromptLayout::mistral().normalize(&request.contents);
+        let messages = normalized.contents.iter().flat_map(convert::content_to_messages).collect();
+
+        let tools = if request.tools.is_empty() {
+            None
+        } else {
+            Some(convert::convert_tools(&request.tools))
+        };
+
+        let config = request.config.as_ref();
+        let max_tokens =
+            config.and_then(|c| c.max_output_tokens).map(|t| t as u32).or(self.config.max_tokens);
+        let extensions = config
+            .and_then(|c| c.extensions.get("mistral"))
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        ChatCompletionRequest {
+            model: self.config.model.clone(),
+            messages,
+            temperature: config.and_then(|c| c.temperature),
+            top_p: config.and_then(|c| c.top_p),
+            max_tokens,
+            stream,
+            tools,
+            response_format: config.and_then(|c| convert::response_format(c, &self.config.model)),
+            random_seed: config.and_then(|c| c.seed),
+            stop: config.map(|c| c.stop_sequences.clone()).unwrap_or_default(),
+            presence_penalty: config.and_then(|c| c.presence_penalty),
+            frequency_penalty: config.and_then(|c| c.frequency_penalty),
+            safe_prompt: self.config.safe_prompt,
+            extensions,
+        }
+    }
+}
+
+#[async_trait]
+impl Llm for MistralClient {
+    fn name(&self) -> &str {
+        &self.config.model
+    }
+
+    #[tracing::instrument(
+        name = "model.generate_content",
+        skip_all,
+        fields(
+            model.name = %self.name(),
+            stream = %stream,
+            request.contents_count = %request.contents.len(),
+            request.tools_count = %request.tools.len()
+        )
+    )]
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream, AdkError> {
+        let usage_span = adk_telemetry::llm_generate_span("mistral", &self.config.model, stream);
+        let api_url = self.api_url();
+        let api_key = self.config.api_key.clone();
+        let chat_request = self.build_request(&request, stream);
+        let client = self.client.clone();
+        let retry_config = self.retry_config.clone();
+
+        let response_stream = try_stream! {
+            // Retries only cover request setup/execution. Stream failures after start are surfaced
+            // directly and are not auto-replayed.
+            let response = execute_with_retry(&retry_config, is_retryable_model_error, || {
+                let client = client.clone();
+                let api_url = api_url.clone();
+                let api_key = api_key.clone();
+                let chat_request = chat_request.clone();
+                async move {
+                    let response = client
+                        .post(&api_url)
+                        .header("Authorization", format!("Bearer {api_key}"))
+                        .header("Content-Type", "application/json")
+                        .json(&chat_request)
+                        .send()
+                        .await
+                        .map_err(|e| AdkError::new(
+                            ErrorComponent::Model,
+                            ErrorCategory::Unavailable,
+                            "model.mistral.request",
+                            format!("Mistral API request failed: {e}"),
+                        ).with_provider("mistral"))?;
+
+                    if !response.status().is_success() {
+                        let status = response.status();
+                        let status_code = status.as_u16();
+                        let error_text = response.text().await.unwrap_or_default();
+                        let category = match status_code {
+                            401 => ErrorCategory::Unauthorized,
+                            403 => ErrorCategory::Forbidden,
+                            404 => ErrorCategory::NotFound,
+                            408 => ErrorCategory::Timeout,
+                            429 => ErrorCategory::RateLimited,
+                            503 | 529 => ErrorCategory::Unavailable,
+                            _ if status_code >= 500 => ErrorCategory::Internal,
+                            _ => ErrorCategory::InvalidInput,
+                        };
+                        return Err(AdkError::new(
+                            ErrorComponent::Model,
+                            category,
+                            "model.mistral.api_error",
+                            format!("Mistral API error (HTTP {status}): {error_text}"),
+                        ).with_upstream_status(status_code).with_provider("mistral"));
+                    }
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+            if stream {
+                let mut byte_stream = response.bytes_stream();
+                let mut buffer = String::new();
+                let mut accumulator = Some(StreamAccumulator::default());
+
+                'read: while let Some(chunk_result) = byte_stream.next().await {
+                    let chunk = chunk_result
+                        .map_err(|e| AdkError::model(format!("Stream read error: {e}")))?;
+
+                    buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                    while let Some(line_end) = buffer.find('\n') {
+                        let line = buffer[..line_end].trim().to_string();
+                        buffer = buffer[line_end + 1..].to_string();
+
+                        if line == "data: [DONE]" {
+                            break 'read;
+                        }
+                        let Some(data) = line.strip_prefix("data: ") else {
+                            continue;
+                        };
+
+                        let chunk_response = match serde_json::from_str::<ChatCompletionResponse>(data) {
+                            Ok(chunk_response) => chunk_response,
+                            Err(e) => {
+                                tracing::warn!("Failed to parse Mistral chunk: {e} - {data}");
+                                continue;
+                            }
+                        };
+                        let Some(choice) = chunk_response.choices.first() else {
+                            continue;
+                        };
+                        let Some(current) = accumulator.as_mut() else {
+                            continue;
+                        };
+
+                        if let Some(delta) = &choice.delta {
+                            for partial in current.push(delta) {
+                                yield partial;
+                            }
+                        }
+
+                        // Mistral reports usage on the chunk that carries the finish reason.
+                        if let Some(reason) = choice.finish_reason.as_deref()
+                            && let Some(done) = accumulator.take()
+                        {
+                            yield done.finish(Some(reason), chunk_response.usage.as_ref());
+                        }
+                    }
+                }
+
+                // The stream ended without a finish reason; emit what arrived.
+                if let Some(done) = accumulator {
+                    yield done.finish(None, None);
+                }
+            } else {
+                let response_text = response.text().await
+                    .map_err(|e| AdkError::model(format!("Failed to read response: {e}")))?;
+
+                let chat_response: ChatCompletionResponse = serde_json::from_str(&response_text)
+                    .map_err(|e| AdkError::model(format!(
+                        "Failed to parse response: {e} - {response_text}"
+                    )))?;
+
+                yield convert::from_response(&chat_response);
+            }
+        };
+
+        Ok(crate::usage_tracking::with_usage_tracking(Box::pin(response_stream), usage_span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adk_core::{Content, GenerateContentConfig, Part};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn client() -> MistralClient {
+        MistralClient::new(MistralConfig::small("test-key").with_max_tokens(256)).unwrap()
+    }
+
+    #[test]
+    fn chat_request_puts_the_system_instruction_first() {
+        let mut request = LlmRequest::new(
+            "mistral-small-latest",
+            vec![
+                Content::new("user").with_text("Hello"),
+                Content::new("system").with_text("Be brief."),
+            ],
+        );
+        request.config = Some(GenerateContentConfig {
+            temperature: Some(0.2),
+            seed: Some(7),
+            stop_sequences: vec!["END".to_string()],
+            ..Default::default()
+        });
+
+        let body = serde_json::to_value(client().build_request(&request, true)).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "model": "mistral-small-latest",
+                "messages": [
+                    { "role": "system", "content": "Be brief." },
+                    { "role": "user", "content": "Hello" }
+                ],
+                "temperature": 0.2f32,
+                "max_tokens": 256,
+                "stream": true,
+                "random_seed": 7,
+                "stop": ["END"]
+            })
+        );
+    }
+
+    #[test]
+    fn tool_request_links_calls_and_results() {
+        let mut request = LlmRequest::new(
+            "mistral-small-latest",
+            vec![
+                Content::new("user").with_text("Weather in Paris?"),
+                Content {
+                    role: "model".to_string(),
+                    parts: vec![Part::FunctionCall {
+                        name: "get_weather".to_string(),
+                        args: json!({ "city": "Paris" }),
+                        id: None,
+                        thought_signature: None,
+                    }],
+                },
+                Content {
+                    role: "function".to_string(),
+                    parts: vec![Part::FunctionResponse {
+                        function_response: adk_core::FunctionResponseData::new(
+                            "get_weather",
+                            json!({ "sky": "clear" }),
+                        ),
+                        id: None,
+                    }],
+                },
+            ],
+        );
+        request.tools = HashMap::from([(
+            "get_weather".to_string(),
+            json!({
+                "name": "get_weather",
+                "description": "Current weather",
+                "parameters": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }),
+        )]);
+        let mut extensions = serde_json::Map::new();
+        extensions.insert("mistral".to_string(), json!({ "tool_choice": "any" }));
+        request.config = Some(GenerateContentConfig { extensions, ..Default::default() });
+
+        let body = serde_json::to_value(client().build_request(&request, false)).unwrap();
+        assert_eq!(body["tool_choice"], "any");
+        assert_eq!(body["tools"][0]["type"], "function");
+        assert_eq!(body["tools"][0]["function"]["name"], "get_weather");
+
+        let call_id = &body["messages"][1]["tool_calls"][0]["id"];
+        let call_id = call_id.as_str().unwrap();
+        assert_eq!(call_id.len(), 9);
+        assert!(call_id.bytes().all(|b| b.is_ascii_alphanumeric()));
+        assert_eq!(body["messages"][2]["role"], "tool");
+        assert_eq!(body["messages"][2]["tool_call_id"], call_id);
+        assert_eq!(body["messages"][2]["name"], "get_weather");
+    }
+
+    #[test]
+    fn json_mode_and_schema_set_the_response_format() {
+        let mut request =
+            LlmRequest::new("mistral-small-latest", vec![Content::new("user").with_text("Hi")]);
+        request.config = Some(GenerateContentConfig {
+            response_mime_type: Some("application/json".to_string()),
+            ..Default::default()
+        });
+        let body = serde_json::to_value(client().build_request(&request, false)).unwrap();
+        assert_eq!(body["response_format"], json!({ "type": "json_object" }));
+
+        let schema = json!({ "type": "object", "properties": { "answer": { "type": "string" } } });
+        request.config = Some(GenerateContentConfig {
+            response_schema: Some(schema.clone()),
+            ..Default::default()
+        });
+        let body = serde_json::to_value(client().build_request(&request, false)).unwrap();
+        assert_eq!(
+            body["response_format"],
+            json!({
+                "type": "json_schema",
+                "json_schema": { "name": "mistral_small_latest", "schema": schema, "strict": true }
+            })
+        );
+    }
+}
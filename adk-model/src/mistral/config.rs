@@ -0,0 +1,86 @@
+//! Configuration types for the Mistral provider.
+
+use serde::{Deserialize, Serialize};
+
+/// Default Mistral (La Plateforme) API base URL.
+pub const MISTRAL_API_BASE: &str = "https://api.mistral.ai/v1";
+
+/// Configuration for the Mistral chat completions API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralConfig {
+    /// Mistral API key.
+    pub api_key: String,
+    /// Model name.
+    pub model: String,
+    /// Optional custom base URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Maximum tokens for output, used when the request does not set one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Prepend Mistral's safety prompt to the conversation.
+    #[serde(default)]
+    pub safe_prompt: bool,
+}
+
+impl Default for MistralConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            model: "mistral-small-latest".to_string(),
+            base_url: None,
+            max_tokens: None,
+            safe_prompt: false,
+        }
+    }
+}
+
+impl MistralConfig {
+    /// Create a new Mistral config with the given API key and model.
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self { api_key: api_key.into(), model: model.into(), ..Default::default() }
+    }
+
+    /// Create a config for the `mistral-small-latest` model.
+    pub fn small(api_key: impl Into<String>) -> Self {
+        Self::new(api_key, "mistral-small-latest")
+    }
+
+    /// Create a config for the `mistral-medium-latest` model.
+    pub fn medium(api_key: impl Into<String>) -> Self {
+        Self::new(api_key, "mistral-medium-latest")
+    }
+
+    /// Create a config for the `mistral-large-latest` model.
+    pub fn large(api_key: impl Into<String>) -> Self {
+        Self::new(api_key, "mistral-large-latest")
+    }
+
+    /// Create a config for the `codestral-latest` model.
+    pub fn codestral(api_key: impl Into<String>) -> Self {
+        Self::new(api_key, "codestral-latest")
+    }
+
+    /// Set max tokens for output.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Enable or disable Mistral's safety prompt.
+    pub fn with_safe_prompt(mut self, enabled: bool) -> Self {
+        self.safe_prompt = enabled;
+        self
+    }
+
+    /// Set custom base URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Get the effective base URL.
+    pub fn effective_base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(MISTRAL_API_BASE)
+    }
+}
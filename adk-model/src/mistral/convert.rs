@@ -0,0 +1,682 @@
+//! Type conversion utilities for the Mistral chat completions API.
+//!
+//! Mistral's schema is close to OpenAI's, with differences that matter for
+//! agents: tool call ids must be exactly nine ASCII letters or digits, `tool`
+//! messages carry the function `name`, content may be a list of typed chunks
+//! (text, images, and reasoning), and function arguments may come back as a
+//! JSON string or an object.
+
+use crate::attachment;
+use adk_core::{Content, FinishReason, GenerateContentConfig, LlmResponse, Part, UsageMetadata};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Message content: a plain string or a list of typed chunks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    /// Plain text.
+    Text(String),
+    /// Typed chunks, used for images and reasoning.
+    Chunks(Vec<ContentChunk>),
+}
+
+/// A typed piece of message content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentChunk {
+    /// Text.
+    Text { text: String },
+    /// An image by URL or `data:` URL.
+    ImageUrl { image_url: String },
+    /// Reasoning emitted by Magistral models.
+    Thinking { thinking: Vec<ContentChunk> },
+    /// A chunk type this client does not use.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ContentChunk {
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::Text { text } => Some(text.clone()),
+            Self::Thinking { thinking } => {
+                Some(thinking.iter().filter_map(Self::text).collect::<Vec<_>>().join(""))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Mistral chat message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    /// Omitted on streaming deltas after the first.
+    #[serde(default)]
+    pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<MessageContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Tool call in a message or streaming delta.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub call_type: Option<String>,
+    pub function: FunctionCall,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+}
+
+/// Function call details. Mistral returns `arguments` as a JSON string and
+/// accepts either a string or an object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCall {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl FunctionCall {
+    /// The arguments as a JSON value, parsing them when sent as a string.
+    pub fn args(&self) -> Value {
+        match &self.arguments {
+            Value::String(text) if text.trim().is_empty() => serde_json::json!({}),
+            Value::String(text) => serde_json::from_str(text).unwrap_or(serde_json::json!({})),
+            Value::Null => serde_json::json!({}),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Tool definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tool {
+    #[serde(rename = "type")]
+    pub tool_type: String,
+    pub function: FunctionDef,
+}
+
+/// Function definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionDef {
+    pub name: String,
+    pub description: String,
+    pub parameters: Value,
+}
+
+/// Requested output format.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Any valid JSON object.
+    JsonObject,
+    /// JSON matching a schema.
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+/// Schema for [`ResponseFormat::JsonSchema`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub schema: Value,
+    pub strict: bool,
+}
+
+/// Mistral chat completion request.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub safe_prompt: bool,
+    /// Extra fields from `config.extensions["mistral"]`, such as `tool_choice`.
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, Value>,
+}
+
+/// Mistral chat completion response or streaming chunk.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionResponse {
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// Response choice.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Choice {
+    #[serde(default)]
+    pub message: Option<Message>,
+    #[serde(default)]
+    pub delta: Option<Message>,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Token usage information.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Usage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+impl Usage {
+    /// Convert to ADK usage metadata.
+    pub fn to_usage_metadata(&self) -> UsageMetadata {
+        UsageMetadata {
+            prompt_token_count: self.prompt_tokens as i32,
+            candidates_token_count: self.completion_tokens as i32,
+            total_token_count: self.total_tokens as i32,
+            ..Default::default()
+        }
+    }
+}
+
+/// Map a Mistral finish reason.
+pub fn finish_reason(reason: &str) -> FinishReason {
+    match reason {
+        "stop" | "tool_calls" => FinishReason::Stop,
+        "length" | "model_length" => FinishReason::MaxTokens,
+        "content_filter" => FinishReason::Safety,
+        _ => FinishReason::Stop,
+    }
+}
+
+const ID_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// A tool call id Mistral accepts: nine ASCII letters or digits.
+///
+/// Ids that already qualify pass through. Others, such as `call_0` from
+/// another provider, are replaced by a stable hash so a call and its result
+/// still share an id.
+pub fn tool_call_id(id: &str) -> String {
+    if id.len() == 9 && id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return id.to_string();
+    }
+    Sha256::digest(id.as_bytes())
+        .iter()
+        .take(9)
+        .map(|byte| ID_ALPHABET[*byte as usize % ID_ALPHABET.len()] as char)
+        .collect()
+}
+
+/// Convert an ADK content into Mistral messages.
+///
+/// Expects contents laid out by
+/// [`PromptLayout::openai`](crate::prompt_layout::PromptLayout::openai), so
+/// every tool result already has its call's id and a content of its own.
+pub fn content_to_messages(content: &Content) -> Vec<Message> {
+    let role = match content.role.as_str() {
+        "model" | "assistant" => "assistant",
+        "tool" | "function" => "tool",
+        "system" => "system",
+        _ => "user",
+    };
+
+    if role == "tool" {
+        return content
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::FunctionResponse { function_response, id } => Some(Message {
+                    role: "tool".to_string(),
+                    content: Some(MessageContent::Text(crate::tool_result::serialize_tool_result(
+                        &function_response.response,
+                    ))),
+                    tool_calls: None,
+                    tool_call_id: id.as_deref().map(tool_call_id),
+                    name: Some(function_response.name.clone()),
+                }),
+                _ => None,
+            })
+            .collect();
+    }
+
+    let mut chunks = Vec::new();
+    let mut tool_calls = Vec::new();
+    for part in &content.parts {
+        match part {
+            Part::Text { text } => chunks.push(ContentChunk::Text { text: text.clone() }),
+            // Reasoning from earlier turns is not sent back.
+            Part::Thinking { .. } => {}
+            Part::FunctionCall { name, args, id, .. } => tool_calls.push(ToolCall {
+                id: Some(tool_call_id(id.as_deref().unwrap_or(name))),
+                call_type: Some("function".to_string()),
+                function: FunctionCall {
+                    name: name.clone(),
+                    arguments: Value::String(serde_json::to_string(args).unwrap_or_default()),
+                },
+                index: None,
+            }),
+            Part::InlineData { mime_type, data } if role == "user" && is_image(mime_type) => {
+                chunks.push(ContentChunk::ImageUrl {
+                    image_url: format!(
+                        "data:{mime_type};base64,{}",
+                        attachment::encode_base64(data)
+                    ),
+                });
+            }
+            Part::FileData { mime_type, file_uri }
+                if role == "user" && is_image(mime_type) && file_uri.starts_with("http") =>
+            {
+                chunks.push(ContentChunk::ImageUrl { image_url: file_uri.clone() });
+            }
+            Part::InlineData { mime_type, data } => chunks.push(ContentChunk::Text {
+                text: attachment::inline_attachment_to_text(mime_type, data),
+            }),
+            Part::FileData { mime_type, file_uri } => chunks.push(ContentChunk::Text {
+                text: attachment::file_attachment_to_text(mime_type, file_uri),
+            }),
+            Part::FunctionResponse { .. }
+            | Part::ServerToolCall { .. }
+            | Part::ServerToolResponse { .. } => {}
+        }
+    }
+
+    let has_images = chunks.iter().any(|chunk| matches!(chunk, ContentChunk::ImageUrl { .. }));
+    let content = if has_images {
+        Some(MessageContent::Chunks(chunks))
+    } else {
+        let text = chunks.iter().filter_map(ContentChunk::text).collect::<Vec<_>>().join("\n");
+        // An assistant message needs content or tool calls.
+        (!text.is_empty() || tool_calls.is_empty()).then_some(MessageContent::Text(text))
+    };
+
+    vec![Message {
+        role: role.to_string(),
+        content,
+        tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        tool_call_id: None,
+        name: None,
+    }]
+}
+
+fn is_image(mime_type: &str) -> bool {
+    mime_type.starts_with("image/")
+}
+
+/// Convert ADK tool declarations into Mistral tools, sorted by name so
+/// requests are stable.
+pub fn convert_tools(tools: &HashMap<String, Value>) -> Vec<Tool> {
+    let mut converted: Vec<Tool> = tools
+        .values()
+        .filter_map(|tool| {
+            let name = tool.get("name")?.as_str()?;
+            let description = tool.get("description").and_then(|d| d.as_str()).unwrap_or("");
+            let parameters = tool.get("parameters").cloned().unwrap_or(serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }));
+            Some(Tool {
+                tool_type: "function".to_string(),
+                function: FunctionDef {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    parameters,
+                },
+            })
+        })
+        .collect();
+    converted.sort_by(|a, b| a.function.name.cmp(&b.function.name));
+    converted
+}
+
+/// The output format requested by a generation config: a JSON schema when one
+/// is set, otherwise JSON mode when the MIME type is `application/json`.
+pub fn response_format(config: &GenerateContentConfig, model: &str) -> Option<ResponseFormat> {
+    if let Some(schema) = &config.response_schema {
+        return Some(ResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: model.replace(['-', '.', '/'], "_"),
+                schema: schema.clone(),
+                strict: true,
+            },
+        });
+    }
+    (config.response_mime_type.as_deref() == Some("application/json"))
+        .then_some(ResponseFormat::JsonObject)
+}
+
+/// Split message content into reasoning and answer text.
+pub fn split_content(content: Option<&MessageContent>) -> (String, String) {
+    match content {
+        None => (String::new(), String::new()),
+        Some(MessageContent::Text(text)) => (String::new(), text.clone()),
+        Some(MessageContent::Chunks(chunks)) => {
+            let mut thinking = String::new();
+            let mut text = String::new();
+            for chunk in chunks {
+                match chunk {
+                    ContentChunk::Thinking { .. } => {
+                        thinking.push_str(&chunk.text().unwrap_or_default())
+                    }
+                    ContentChunk::Text { text: chunk_text } => text.push_str(chunk_text),
+                    _ => {}
+                }
+            }
+            (thinking, text)
+        }
+    }
+}
+
+/// Build the model content from reasoning, answer text, and tool calls.
+pub fn model_content(
+    thinking: String,
+    text: String,
+    tool_calls: Vec<(String, String, Value)>,
+) -> Option<Content> {
+    let mut parts = Vec::new();
+    if !thinking.is_empty() {
+        parts.push(Part::Thinking { thinking, signature: None });
+    }
+    if !text.is_empty() {
+        parts.push(Part::Text { text });
+    }
+    parts.extend(tool_calls.into_iter().map(|(id, name, args)| Part::FunctionCall {
+        name,
+        args,
+        id: Some(id),
+        thought_signature: None,
+    }));
+    (!parts.is_empty()).then(|| Content { role: "model".to_string(), parts })
+}
+
+/// Convert a non-streaming Mistral response to an ADK response.
+pub fn from_response(response: &ChatCompletionResponse) -> LlmResponse {
+    let choice = response.choices.first();
+    let content = choice.and_then(|choice| choice.message.as_ref()).and_then(|message| {
+        let (thinking, text) = split_content(message.content.as_ref());
+        let tool_calls = message
+            .tool_calls
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, call)| {
+                let id = call.id.clone().unwrap_or_else(|| tool_call_id(&format!("call_{index}")));
+                (id, call.function.name.clone(), call.function.args())
+            })
+            .collect();
+        model_content(thinking, text, tool_calls)
+    });
+    let turn_complete = content.as_ref().is_none_or(|c| !c.has_function_calls());
+
+    LlmResponse {
+        content,
+        usage_metadata: response.usage.as_ref().map(Usage::to_usage_metadata),
+        finish_reason: choice.and_then(|c| c.finish_reason.as_deref()).map(finish_reason),
+        partial: false,
+        turn_complete,
+        ..Default::default()
+    }
+}
+
+/// Accumulates streamed deltas into the final response.
+///
+/// Mistral sends each tool call whole in one delta, but arguments are still
+/// appended by index in case they arrive in pieces.
+#[derive(Debug, Default)]
+pub struct StreamAccumulator {
+    thinking: String,
+    text: String,
+    tool_calls: BTreeMap<u32, (Option<String>, String, String)>,
+}
+
+impl StreamAccumulator {
+    /// Record a delta, returning partial responses for its reasoning and text.
+    pub fn push(&mut self, delta: &Message) -> Vec<LlmResponse> {
+        let (thinking, text) = split_content(delta.content.as_ref());
+        let mut partials = Vec::new();
+        if !thinking.is_empty() {
+            self.thinking.push_str(&thinking);
+            partials.push(partial(Part::Thinking { thinking, signature: None }));
+        }
+        if !text.is_empty() {
+            self.text.push_str(&text);
+            partials.push(partial(Part::Text { text }));
+        }
+        for (position, call) in delta.tool_calls.iter().flatten().enumerate() {
+            let index = call.index.unwrap_or(position as u32);
+            let entry = self.tool_calls.entry(index).or_default();
+            if call.id.is_some() {
+                entry.0.clone_from(&call.id);
+            }
+            if !call.function.name.is_empty() {
+                entry.1.clone_from(&call.function.name);
+            }
+            match &call.function.arguments {
+                Value::String(chunk) => entry.2.push_str(chunk),
+                Value::Null => {}
+                other => entry.2 = other.to_string(),
+            }
+        }
+        partials
+    }
+
+    /// The complete, non-partial response.
+    pub fn finish(self, finish: Option<&str>, usage: Option<&Usage>) -> LlmResponse {
+        let tool_calls = self
+            .tool_calls
+            .into_iter()
+            .map(|(index, (id, name, arguments))| {
+                let id = id.unwrap_or_else(|| tool_call_id(&format!("call_{index}")));
+                let args =
+                    FunctionCall { name: String::new(), arguments: Value::String(arguments) }
+                        .args();
+                (id, name, args)
+            })
+            .collect();
+        let content = model_content(self.thinking, self.text, tool_calls);
+        let turn_complete = content.as_ref().is_none_or(|c| !c.has_function_calls());
+        LlmResponse {
+            content,
+            usage_metadata: usage.map(Usage::to_usage_metadata),
+            finish_reason: finish.map(finish_reason),
+            partial: false,
+            turn_complete,
+            ..Default::default()
+        }
+    }
+}
+
+fn partial(part: Part) -> LlmResponse {
+    LlmResponse {
+        content: Some(Content { role: "model".to_string(), parts: vec![part] }),
+        partial: true,
+        turn_complete: false,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adk_core::FunctionResponseData;
+    use serde_json::json;
+
+    #[test]
+    fn tool_call_ids_are_nine_alphanumerics_and_stable() {
+        assert_eq!(tool_call_id("D681PevKs"), "D681PevKs");
+        let converted = tool_call_id("call_0");
+        assert_eq!(converted.len(), 9);
+        assert!(converted.bytes().all(|b| b.is_ascii_alphanumeric()));
+        assert_eq!(converted, tool_call_id("call_0"));
+        assert_ne!(converted, tool_call_id("call_1"));
+    }
+
+    #[test]
+    fn tool_results_become_named_tool_messages() {
+        let call = Content {
+            role: "model".to_string(),
+            parts: vec![Part::FunctionCall {
+                name: "get_weather".to_string(),
+                args: json!({ "city": "Paris" }),
+                id: Some("call_0".to_string()),
+                thought_signature: None,
+            }],
+        };
+        let result = Content {
+            role: "function".to_string(),
+            parts: vec![Part::FunctionResponse {
+                function_response: FunctionResponseData::new(
+                    "get_weather",
+                    json!({ "sky": "clear" }),
+                ),
+                id: Some("call_0".to_string()),
+            }],
+        };
+
+        let assistant = serde_json::to_value(content_to_messages(&call)).unwrap();
+        let tool = serde_json::to_value(content_to_messages(&result)).unwrap();
+        let id = tool_call_id("call_0");
+        assert_eq!(
+            assistant,
+            json!([{
+                "role": "assistant",
+                "tool_calls": [{
+                    "id": id,
+                    "type": "function",
+                    "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                }]
+            }])
+        );
+        assert_eq!(
+            tool,
+            json!([{
+                "role": "tool",
+                "content": "{\"sky\":\"clear\"}",
+                "tool_call_id": id,
+                "name": "get_weather"
+            }])
+        );
+    }
+
+    #[test]
+    fn user_images_become_image_url_chunks() {
+        let content = Content {
+            role: "user".to_string(),
+            parts: vec![
+                Part::Text { text: "What is this?".to_string() },
+                Part::InlineData { mime_type: "image/png".to_string(), data: vec![1, 2, 3] },
+            ],
+        };
+        let message = serde_json::to_value(content_to_messages(&content)).unwrap();
+        assert_eq!(
+            message[0]["content"],
+            json!([
+                { "type": "text", "text": "What is this?" },
+                { "type": "image_url", "image_url": "data:image/png;base64,AQID" }
+            ])
+        );
+    }
+
+    #[test]
+    fn response_with_reasoning_chunks_and_string_arguments() {
+        let response: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "cmpl-1",
+            "object": "chat.completion",
+            "model": "magistral-small-latest",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": [
+                        { "type": "thinking", "thinking": [{ "type": "text", "text": "Check it." }] },
+                        { "type": "text", "text": "Looking it up." }
+                    ],
+                    "tool_calls": [{
+                        "id": "D681PevKs",
+                        "function": { "name": "get_weather", "arguments": "{\"city\": \"Paris\"}" },
+                        "index": 0
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }],
+            "usage": { "prompt_tokens": 12, "completion_tokens": 8, "total_tokens": 20 }
+        }))
+        .unwrap();
+
+        let converted = from_response(&response);
+        let parts = &converted.content.as_ref().unwrap().parts;
+        assert_eq!(parts[0].thinking_text(), Some("Check it."));
+        assert_eq!(parts[1].text(), Some("Looking it up."));
+        assert!(matches!(
+            &parts[2],
+            Part::FunctionCall { name, args, id: Some(id), .. }
+                if name == "get_weather" && args == &json!({ "city": "Paris" }) && id == "D681PevKs"
+        ));
+        assert!(!converted.turn_complete);
+        assert_eq!(converted.finish_reason, Some(FinishReason::Stop));
+        let usage = converted.usage_metadata.unwrap();
+        assert_eq!(
+            (usage.prompt_token_count, usage.candidates_token_count, usage.total_token_count),
+            (12, 8, 20)
+        );
+    }
+
+    #[test]
+    fn streamed_deltas_accumulate_into_one_final_response() {
+        let deltas: Vec<Message> = serde_json::from_value(json!([
+            { "role": "assistant", "content": "Let me " },
+            { "content": "check." },
+            { "content": "", "tool_calls": [{
+                "id": "abcDEF123",
+                "function": { "name": "lookup", "arguments": "{\"q\": \"rust\"}" },
+                "index": 0
+            }] }
+        ]))
+        .unwrap();
+
+        let mut accumulator = StreamAccumulator::default();
+        let partials: Vec<_> = deltas.iter().flat_map(|delta| accumulator.push(delta)).collect();
+        assert_eq!(partials.len(), 2);
+        assert!(partials.iter().all(|response| response.partial));
+
+        let usage = Usage { prompt_tokens: 5, completion_tokens: 7, total_tokens: 12 };
+        let last = accumulator.finish(Some("tool_calls"), Some(&usage));
+        let parts = &last.content.as_ref().unwrap().parts;
+        assert_eq!(parts[0].text(), Some("Let me check."));
+        assert!(matches!(
+            &parts[1],
+            Part::FunctionCall { name, args, id: Some(id), .. }
+                if name == "lookup" && args == &json!({ "q": "rust" }) && id == "abcDEF123"
+        ));
+        assert!(!last.partial && !last.turn_complete);
+        assert_eq!(last.usage_metadata.unwrap().total_token_count, 12);
+    }
+}
@@ -0,0 +1,34 @@
+//! Mistral provider implementation for ADK.
+//!
+//! Native support for Mistral's La Plateforme chat completions API:
+//! - Mistral Small, Medium, and Large (`mistral-*-latest`)
+//! - Codestral (`codestral-latest`)
+//! - Magistral reasoning models, whose thinking chunks become `Part::Thinking`
+//!
+//! # Features
+//!
+//! - **Tool Calling**: Tool call ids are adapted to Mistral's nine-character format
+//! - **Streaming**: Partial text and reasoning, then one complete response with usage
+//! - **JSON Mode**: `response_mime_type = "application/json"` or a `response_schema`
+//! - **Extensions**: Fields under `config.extensions["mistral"]` (e.g. `tool_choice`,
+//!   `prompt_mode`) are passed through to the request
+//!
+//! Embeddings are provided by `adk-rag`'s `MistralEmbeddingProvider`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_model::mistral::{MistralClient, MistralConfig};
+//!
+//! let client = MistralClient::new(
+//!     MistralConfig::large(std::env::var("MISTRAL_API_KEY").unwrap())
+//!         .with_max_tokens(1024),
+//! )?;
+//! ```
+
+mod client;
+mod config;
+mod convert;
+
+pub use client::MistralClient;
+pub use config::{MISTRAL_API_BASE, MistralConfig};
@@ -1,18 +1,34 @@
-use adk_core::{Llm, LlmRequest, LlmResponse, LlmResponseStream, Result};
+use adk_core::{AdkError, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result};
 use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
 
 /// A mock LLM implementation for testing without real API calls.
 ///
-/// Returns pre-configured responses in order when `generate_content` is called.
+/// Returns pre-configured responses in order when `generate_content` is called,
+/// and records every request it receives. Responses added with
+/// [`with_turn`](Self::with_turn) or [`with_error`](Self::with_error) are
+/// answered one per call, for scripting multi-call exchanges such as retries.
 pub struct MockLlm {
     name: String,
     responses: Vec<LlmResponse>,
+    turns: Mutex<VecDeque<Result<LlmResponse>>>,
+    requests: Mutex<Vec<LlmRequest>>,
+    max_tools: Option<usize>,
+    response_schema_support: bool,
 }
 
 impl MockLlm {
     /// Create a new mock LLM with the given name.
     pub fn new(name: impl Into<String>) -> Self {
-        Self { name: name.into(), responses: vec![] }
+        Self {
+            name: name.into(),
+            responses: vec![],
+            turns: Mutex::new(VecDeque::new()),
+            requests: Mutex::new(vec![]),
+            max_tools: None,
+            response_schema_support: true,
+        }
     }
 
     /// Add a response to the queue of responses returned by this mock.
@@ -20,6 +36,42 @@ impl MockLlm {
         self.responses.push(response);
         self
     }
+
+    /// Queue a response for a single call.
+    ///
+    /// Each call returns the next queued turn; once they run out, calls return
+    /// the responses added with [`with_response`](Self::with_response).
+    pub fn with_turn(self, response: LlmResponse) -> Self {
+        self.turns.lock().unwrap_or_else(|e| e.into_inner()).push_back(Ok(response));
+        self
+    }
+
+    /// Queue a failure for a single call, in order with [`with_turn`](Self::with_turn).
+    ///
+    /// The call returns `error` instead of a response stream, like a provider
+    /// that rejects the request.
+    pub fn with_error(self, error: AdkError) -> Self {
+        self.turns.lock().unwrap_or_else(|e| e.into_inner()).push_back(Err(error));
+        self
+    }
+
+    /// Report `max_tools` as the provider's tool limit.
+    pub fn with_max_tools(mut self, max_tools: usize) -> Self {
+        self.max_tools = Some(max_tools);
+        self
+    }
+
+    /// Set whether the mock claims to enforce a response schema, like a
+    /// provider without structured output when `false`.
+    pub fn with_response_schema_support(mut self, supported: bool) -> Self {
+        self.response_schema_support = supported;
+        self
+    }
+
+    /// Returns the requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<LlmRequest> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
 }
 
 #[async_trait]
@@ -28,8 +80,21 @@ impl Llm for MockLlm {
         &self.name
     }
 
-    async fn generate_content(&self, _req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
-        let responses = self.responses.clone();
+    fn max_tools(&self) -> Option<usize> {
+        self.max_tools
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.response_schema_support
+    }
+
+    async fn generate_content(&self, req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).push(req);
+        let turn = self.turns.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        let responses = match turn {
+            Some(turn) => vec![turn?],
+            None => self.responses.clone(),
+        };
         let stream = async_stream::stream! {
             for response in responses {
                 yield Ok(response);
@@ -64,5 +129,18 @@ mod tests {
 
         let response = stream.next().await.unwrap().unwrap();
         assert!(response.content.is_some());
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_llm_error_turn() {
+        let mock = MockLlm::new("test")
+            .with_error(AdkError::model("rejected"))
+            .with_turn(LlmResponse::new(Content::new("assistant").with_text("Hello")));
+
+        let req = LlmRequest::new("test", vec![]);
+        assert!(mock.generate_content(req.clone(), false).await.is_err());
+        assert!(mock.generate_content(req, false).await.is_ok());
+        assert_eq!(mock.requests().len(), 2);
     }
 }
@@ -2,6 +2,8 @@
 
 use super::config::OllamaConfig;
 use super::convert;
+use crate::prompt_layout::PromptLayout;
+use crate::retry::{RetryConfig, execute_stream_with_retry, is_retryable_model_error};
 use adk_core::{
     AdkError, Content, ErrorCategory, ErrorComponent, GenericSchemaAdapter, Llm, LlmRequest,
     LlmResponse, LlmResponseStream, Part, Result, SchemaAdapter,
@@ -45,6 +47,23 @@ impl OllamaModel {
         Self::new(OllamaConfig::new(model))
     }
 
+    /// Set the retry configuration (builder pattern).
+    #[must_use]
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.config.retry_config = retry_config;
+        self
+    }
+
+    /// Set the retry configuration (mutable reference).
+    pub fn set_retry_config(&mut self, retry_config: RetryConfig) {
+        self.config.retry_config = retry_config;
+    }
+
+    /// Returns the current retry configuration.
+    pub fn retry_config(&self) -> &RetryConfig {
+        &self.config.retry_config
+    }
+
     /// Build ModelOptions from config and request.
     fn build_options(&self, request: &LlmRequest) -> ModelOptions {
         let mut options = ModelOptions::default();
@@ -101,77 +120,14 @@ impl OllamaModel {
             })
             .collect()
     }
-}
 
-/// Map an Ollama error message to a structured `AdkError`.
-///
-/// Ollama errors don't carry HTTP status codes directly, so we classify
-/// based on message content.
-fn ollama_error_to_adk(msg: &str) -> AdkError {
-    let upper = msg.to_ascii_uppercase();
-    let (category, code) =
-        if upper.contains("CONNECTION REFUSED") || upper.contains("CONNECT ERROR") {
-            (ErrorCategory::Unavailable, "model.ollama.unavailable")
-        } else if upper.contains("TIMEOUT") || upper.contains("TIMED OUT") {
-            (ErrorCategory::Timeout, "model.ollama.timeout")
-        } else if upper.contains("NOT FOUND") || upper.contains("NO SUCH MODEL") {
-            (ErrorCategory::NotFound, "model.ollama.not_found")
-        } else {
-            (ErrorCategory::Internal, "model.ollama.error")
-        };
-    AdkError::new(ErrorComponent::Model, category, code, msg).with_provider("ollama")
-}
-
-#[async_trait]
-impl Llm for OllamaModel {
-    fn name(&self) -> &str {
-        &self.model_name
-    }
-
-    // OllamaModel uses the default GenericSchemaAdapter from the Llm trait.
-    // This explicit override documents the intentional adapter selection.
-    fn schema_adapter(&self) -> &dyn SchemaAdapter {
-        static ADAPTER: GenericSchemaAdapter = GenericSchemaAdapter;
-        &ADAPTER
-    }
-
-    #[tracing::instrument(
-        name = "model.generate_content",
-        skip_all,
-        fields(
-            model.name = %self.name(),
-            stream = %stream,
-            request.contents_count = %request.contents.len(),
-            request.tools_count = %request.tools.len()
-        )
-    )]
-    async fn generate_content(
-        &self,
-        request: LlmRequest,
+    /// Sends `chat_request` and turns the reply into chunks. A failed request
+    /// is yielded as the stream's first item.
+    fn response_stream(
+        client: Ollama,
+        chat_request: ChatMessageRequest,
         stream: bool,
-    ) -> Result<LlmResponseStream> {
-        let usage_span = adk_telemetry::llm_generate_span("ollama", &self.model_name, stream);
-        let model = self.model_name.clone();
-        let client = self.client.clone();
-        let options = self.build_options(&request);
-
-        // Convert ADK contents to Ollama messages
-        let mut messages: Vec<ChatMessage> = Vec::new();
-        for content in &request.contents {
-            if let Some(msg) = convert::content_to_chat_message(content) {
-                messages.push(msg);
-            }
-        }
-
-        // Build chat request
-        let mut chat_request = ChatMessageRequest::new(model, messages).options(options);
-
-        // Add tools if present
-        if !request.tools.is_empty() {
-            let tools = self.convert_tools(&request.tools);
-            chat_request = chat_request.tools(tools);
-        }
-
+    ) -> LlmResponseStream {
         let response_stream = try_stream! {
             if stream {
                 // Streaming mode — Ollama supports streaming with tool calls since May 2025
@@ -224,6 +180,7 @@ impl Llm for OllamaModel {
                                     error_message: None,
                                     provider_metadata: None,
                                     interaction_id: None,
+                                    citations: Vec::new(),
                                 };
                             }
 
@@ -255,7 +212,89 @@ impl Llm for OllamaModel {
                 yield convert::chat_response_to_llm_response(&response, false);
             }
         };
+        Box::pin(response_stream)
+    }
+}
+
+/// Map an Ollama error message to a structured `AdkError`.
+///
+/// Ollama errors don't carry HTTP status codes directly, so we classify
+/// based on message content.
+fn ollama_error_to_adk(msg: &str) -> AdkError {
+    let upper = msg.to_ascii_uppercase();
+    let (category, code) =
+        if upper.contains("CONNECTION REFUSED") || upper.contains("CONNECT ERROR") {
+            (ErrorCategory::Unavailable, "model.ollama.unavailable")
+        } else if upper.contains("TIMEOUT") || upper.contains("TIMED OUT") {
+            (ErrorCategory::Timeout, "model.ollama.timeout")
+        } else if upper.contains("NOT FOUND") || upper.contains("NO SUCH MODEL") {
+            (ErrorCategory::NotFound, "model.ollama.not_found")
+        } else {
+            (ErrorCategory::Internal, "model.ollama.error")
+        };
+    AdkError::new(ErrorComponent::Model, category, code, msg).with_provider("ollama")
+}
+
+#[async_trait]
+impl Llm for OllamaModel {
+    fn name(&self) -> &str {
+        &self.model_name
+    }
+
+    // OllamaModel uses the default GenericSchemaAdapter from the Llm trait.
+    // This explicit override documents the intentional adapter selection.
+    fn schema_adapter(&self) -> &dyn SchemaAdapter {
+        static ADAPTER: GenericSchemaAdapter = GenericSchemaAdapter;
+        &ADAPTER
+    }
+
+    #[tracing::instrument(
+        name = "model.generate_content",
+        skip_all,
+        fields(
+            model.name = %self.name(),
+            stream = %stream,
+            request.contents_count = %request.contents.len(),
+            request.tools_count = %request.tools.len()
+        )
+    )]
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        let usage_span = adk_telemetry::llm_generate_span("ollama", &self.model_name, stream);
+        let model = self.model_name.clone();
+        let client = self.client.clone();
+        let options = self.build_options(&request);
+
+        // Convert ADK contents to Ollama messages
+        let mut messages: Vec<ChatMessage> = Vec::new();
+        for content in &PromptLayout::ollama().normalize(&request.contents).contents {
+            if let Some(msg) = convert::content_to_chat_message(content) {
+                messages.push(msg);
+            }
+        }
+
+        // Build chat request
+        let mut chat_request = ChatMessageRequest::new(model, messages).options(options);
+
+        // Add tools if present
+        if !request.tools.is_empty() {
+            let tools = self.convert_tools(&request.tools);
+            chat_request = chat_request.tools(tools);
+        }
+
+        let response_stream =
+            execute_stream_with_retry(&self.config.retry_config, is_retryable_model_error, || {
+                std::future::ready(Ok(Self::response_stream(
+                    self.client.clone(),
+                    chat_request.clone(),
+                    stream,
+                )))
+            })
+            .await?;
 
-        Ok(crate::usage_tracking::with_usage_tracking(Box::pin(response_stream), usage_span))
+        Ok(crate::usage_tracking::with_usage_tracking(response_stream, usage_span))
     }
 }
@@ -1,5 +1,7 @@
 //! Configuration for Ollama client.
 
+use crate::retry::RetryConfig;
+
 /// Configuration for connecting to an Ollama server.
 #[derive(Debug, Clone)]
 pub struct OllamaConfig {
@@ -15,6 +17,8 @@ pub struct OllamaConfig {
     pub top_p: Option<f32>,
     /// Top-k sampling. None uses model default.
     pub top_k: Option<i32>,
+    /// Retry policy for transient failures, such as the server not being up yet.
+    pub retry_config: RetryConfig,
 }
 
 impl Default for OllamaConfig {
@@ -26,6 +30,7 @@ impl Default for OllamaConfig {
             temperature: None,
             top_p: None,
             top_k: None,
+            retry_config: RetryConfig::default(),
         }
     }
 }
@@ -40,4 +45,10 @@ impl OllamaConfig {
     pub fn with_host(host: impl Into<String>, model: impl Into<String>) -> Self {
         Self { host: host.into(), model: model.into(), ..Default::default() }
     }
+
+    /// Set the retry configuration.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
 }
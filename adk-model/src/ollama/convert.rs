@@ -116,6 +116,7 @@ pub fn chat_response_to_llm_response(response: &ChatMessageResponse, partial: bo
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -136,6 +137,7 @@ pub fn text_delta_response(text: &str) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 /// Create a thinking delta response for streaming.
@@ -155,6 +157,7 @@ pub fn thinking_delta_response(thinking: &str) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -1,10 +1,13 @@
 //! OpenAI client implementation.
 
+use super::OPENAI_MAX_TOOLS;
 use super::config::{AzureConfig, OpenAIConfig};
 use super::convert;
 use super::schema_adapter::OpenAiSchemaAdapter;
 use crate::openai_compatible::{OpenAICompatible, OpenAICompatibleConfig, build_request_json};
-use crate::retry::{RetryConfig, execute_with_retry, is_retryable_model_error};
+use crate::retry::{
+    RetryConfig, execute_with_retry, is_retryable_model_error, retry_after_from_headers,
+};
 use adk_core::{
     AdkError, ErrorCategory, ErrorComponent, Llm, LlmRequest, LlmResponseStream, SchemaAdapter,
     SchemaCache,
@@ -27,8 +30,9 @@ impl OpenAIClient {
             super::config::ReasoningEffort::High => ReasoningEffort::High,
         });
 
-        let mut compat_config =
-            OpenAICompatibleConfig::new(config.api_key, config.model).with_provider_name("openai");
+        let mut compat_config = OpenAICompatibleConfig::new(config.api_key, config.model)
+            .with_provider_name("openai")
+            .with_retry_config(config.retry_config);
         if let Some(base_url) = config.base_url {
             compat_config = compat_config.with_base_url(base_url);
         }
@@ -86,6 +90,18 @@ impl Llm for OpenAIClient {
         &ADAPTER
     }
 
+    fn max_tools(&self) -> Option<usize> {
+        Some(OPENAI_MAX_TOOLS)
+    }
+
+    /// Counts tokens locally with the model's tiktoken encoding.
+    ///
+    /// Message text is exact. Tool declarations are approximate, and images
+    /// and files are not counted.
+    async fn count_tokens(&self, request: &LlmRequest) -> Result<usize, AdkError> {
+        Ok(super::token_count::count_request_tokens(self.name(), request))
+    }
+
     #[tracing::instrument(
         name = "model.generate_content",
         skip_all,
@@ -157,6 +173,18 @@ impl Llm for AzureOpenAIClient {
         &ADAPTER
     }
 
+    fn max_tools(&self) -> Option<usize> {
+        Some(OPENAI_MAX_TOOLS)
+    }
+
+    /// Counts tokens locally with the model's tiktoken encoding.
+    ///
+    /// Message text is exact. Tool declarations are approximate, and images
+    /// and files are not counted.
+    async fn count_tokens(&self, request: &LlmRequest) -> Result<usize, AdkError> {
+        Ok(super::token_count::count_request_tokens(self.name(), request))
+    }
+
     #[tracing::instrument(
         name = "model.generate_content",
         skip_all,
@@ -219,22 +247,24 @@ impl Llm for AzureOpenAIClient {
 
                     if !http_resp.status().is_success() {
                         let status_code = http_resp.status().as_u16();
+                        let retry_after = retry_after_from_headers(|name| {
+                            http_resp.headers().get(name)?.to_str().ok()
+                        });
                         let body_text = http_resp.text().await.unwrap_or_default();
                         let msg = format!("Azure OpenAI API error (HTTP {status_code}): {body_text}");
-                        let (category, code, status) = match status_code {
-                            429 => (ErrorCategory::RateLimited, "model.azure_openai.rate_limited", Some(429u16)),
-                            503 => (ErrorCategory::Unavailable, "model.azure_openai.unavailable", Some(503u16)),
-                            529 => (ErrorCategory::Unavailable, "model.azure_openai.overloaded", Some(529u16)),
-                            408 => (ErrorCategory::Timeout, "model.azure_openai.timeout", Some(408u16)),
-                            401 => (ErrorCategory::Unauthorized, "model.azure_openai.unauthorized", Some(401u16)),
-                            404 => (ErrorCategory::NotFound, "model.azure_openai.not_found", Some(404u16)),
-                            _ if status_code >= 500 => (ErrorCategory::Internal, "model.azure_openai.api_error", Some(status_code)),
-                            _ => (ErrorCategory::Internal, "model.azure_openai.api_error", Some(status_code)),
+                        let code = match status_code {
+                            429 => "model.azure_openai.rate_limited",
+                            503 => "model.azure_openai.unavailable",
+                            529 => "model.azure_openai.overloaded",
+                            408 => "model.azure_openai.timeout",
+                            401 => "model.azure_openai.unauthorized",
+                            404 => "model.azure_openai.not_found",
+                            _ => "model.azure_openai.api_error",
                         };
-                        let mut err = AdkError::new(ErrorComponent::Model, category, code, msg)
+                        let mut err = AdkError::from_http_status(ErrorComponent::Model, code, status_code, msg)
                             .with_provider("azure-openai");
-                        if let Some(sc) = status {
-                            err = err.with_upstream_status(sc);
+                        if let Some(delay) = retry_after {
+                            err = err.with_retry_after(delay);
                         }
                         return Err(err);
                     }
@@ -1,5 +1,6 @@
 //! Configuration types for OpenAI providers.
 
+use crate::retry::RetryConfig;
 use serde::{Deserialize, Serialize};
 
 /// Reasoning effort level for OpenAI reasoning models (e.g., o1, o3).
@@ -39,6 +40,9 @@ pub struct OpenAIConfig {
     /// Only applicable to reasoning-capable models.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<ReasoningEffort>,
+    /// Retry policy for transient failures such as rate limits.
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 impl Default for OpenAIConfig {
@@ -50,6 +54,7 @@ impl Default for OpenAIConfig {
             project_id: None,
             base_url: None,
             reasoning_effort: None,
+            retry_config: RetryConfig::default(),
         }
     }
 }
@@ -91,6 +96,12 @@ impl OpenAIConfig {
         self.reasoning_effort = Some(effort);
         self
     }
+
+    /// Set the retry policy for transient failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
 }
 
 /// Configuration for Azure OpenAI Service.
@@ -340,6 +340,7 @@ pub fn from_openai_response(resp: &CreateChatCompletionResponse) -> LlmResponse
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -461,6 +462,7 @@ pub fn from_raw_openai_response(json: &serde_json::Value) -> LlmResponse {
         error_message: None,
         provider_metadata: None,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
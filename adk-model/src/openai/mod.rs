@@ -26,6 +26,7 @@ pub mod pricing;
 mod responses_client;
 mod responses_convert;
 pub mod schema_adapter;
+mod token_count;
 #[cfg(feature = "openai-ws")]
 pub mod ws_transport;
 
@@ -38,6 +39,9 @@ pub use config::{
 #[cfg(feature = "openai-conversations")]
 pub use conversations::ConversationsClient;
 pub use responses_client::OpenAIResponsesClient;
+
+/// Maximum number of tools OpenAI accepts in a single request.
+pub(crate) const OPENAI_MAX_TOOLS: usize = 128;
 pub use schema_adapter::{OpenAiSchemaAdapter, OpenAiStrictSchemaAdapter};
 #[cfg(feature = "openai-ws")]
 pub use ws_transport::WsTransport;
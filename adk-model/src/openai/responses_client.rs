@@ -239,6 +239,18 @@ impl Llm for OpenAIResponsesClient {
         &self.model
     }
 
+    fn max_tools(&self) -> Option<usize> {
+        Some(super::OPENAI_MAX_TOOLS)
+    }
+
+    /// Counts tokens locally with the model's tiktoken encoding.
+    ///
+    /// Message text is exact. Tool declarations are approximate, and images
+    /// and files are not counted.
+    async fn count_tokens(&self, request: &LlmRequest) -> Result<usize, AdkError> {
+        Ok(super::token_count::count_request_tokens(self.name(), request))
+    }
+
     #[tracing::instrument(
         name = "model.generate_content",
         skip_all,
@@ -4,6 +4,7 @@
 //! `async-openai` Responses API types (`InputItem`, `OutputItem`, `CreateResponse`, etc.).
 
 use crate::attachment;
+use crate::prompt_layout::PromptLayout;
 use adk_core::{
     AdkError, Content, ErrorCategory, ErrorComponent, FinishReason, LlmRequest, LlmResponse, Part,
     UsageMetadata,
@@ -277,27 +278,12 @@ pub fn build_create_response(
 ) -> Result<CreateResponse, AdkError> {
     let config = request.config.as_ref();
 
-    // 1. Separate system content from non-system content
-    let mut system_texts = Vec::new();
-    let mut non_system_contents = Vec::new();
-
-    for content in &request.contents {
-        if content.role == "system" {
-            for part in &content.parts {
-                if let Part::Text { text } = part {
-                    system_texts.push(text.clone());
-                }
-            }
-        } else {
-            non_system_contents.push(content.clone());
-        }
-    }
-
-    // 2. Build instructions from system content
-    let instructions = if system_texts.is_empty() { None } else { Some(system_texts.join("\n")) };
+    // 1-2. Merge system content into `instructions`
+    let layout = PromptLayout::openai_responses().normalize(&request.contents);
+    let instructions = layout.system_instruction;
 
     // 3. Convert non-system contents to input items
-    let input_items = contents_to_input_items(&non_system_contents);
+    let input_items = contents_to_input_items(&layout.contents);
     let input = InputParam::Items(input_items);
 
     // 4. Convert tools
@@ -358,6 +344,13 @@ pub fn build_create_response(
         .transpose()?;
 
     // 5. Forward temperature, top_p, max_output_tokens
+    if let Some(config) = config {
+        crate::generation_config::log_unsupported_fields(
+            "openai",
+            config,
+            &["temperature", "top_p", "max_output_tokens"],
+        );
+    }
     let temperature = config.and_then(|c| c.temperature);
     let top_p = config.and_then(|c| c.top_p);
     let max_output_tokens = config.and_then(|c| c.max_output_tokens).map(|v| v as u32);
@@ -553,6 +546,7 @@ pub fn from_response(response: &Response) -> LlmResponse {
         error_message: None,
         provider_metadata,
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -0,0 +1,99 @@
+//! Local token counting for OpenAI models.
+//!
+//! Requests are counted with the model's tiktoken encoding: `o200k_base` for
+//! GPT-4o, GPT-4.1, GPT-5, and the o-series, `cl100k_base` for GPT-4 and
+//! GPT-3.5. Models tiktoken does not know, such as Azure deployment names,
+//! use `o200k_base`.
+//!
+//! Message text is counted exactly, plus the chat format's fixed overhead per
+//! message. Tool declarations are counted as their JSON, while OpenAI renders
+//! them in its own format, so requests with many tools can be off by a few
+//! percent. Images and files are not counted.
+
+use adk_core::{LlmRequest, Part};
+use tiktoken_rs::CoreBPE;
+use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
+
+/// Tokens wrapping each message: `<|start|>{role}\n{content}<|end|>\n`, less the role.
+const TOKENS_PER_MESSAGE: usize = 3;
+
+/// Tokens priming the reply: `<|start|>assistant<|message|>`.
+const REPLY_PRIMING_TOKENS: usize = 3;
+
+/// The tiktoken encoding used by `model`.
+pub(crate) fn encoding_for(model: &str) -> &'static CoreBPE {
+    match get_tokenizer(model) {
+        Some(Tokenizer::Cl100kBase) => tiktoken_rs::cl100k_base_singleton(),
+        _ => tiktoken_rs::o200k_base_singleton(),
+    }
+}
+
+/// Counts the input tokens of `req` for `model`. See the [module docs](self).
+pub(crate) fn count_request_tokens(model: &str, req: &LlmRequest) -> usize {
+    let bpe = encoding_for(model);
+    let count = |text: &str| bpe.encode_ordinary(text).len();
+
+    let messages: usize = req
+        .contents
+        .iter()
+        .map(|content| {
+            TOKENS_PER_MESSAGE
+                + count(&content.role)
+                + content.parts.iter().map(|part| count(&part_text(part))).sum::<usize>()
+        })
+        .sum();
+    let tools: usize = req
+        .tools
+        .iter()
+        .map(|(name, declaration)| count(name) + count(&declaration.to_string()))
+        .sum();
+    messages + tools + REPLY_PRIMING_TOKENS
+}
+
+/// The text of `part` as the model reads it.
+fn part_text(part: &Part) -> String {
+    match part {
+        Part::Text { text } => text.clone(),
+        Part::Thinking { thinking, .. } => thinking.clone(),
+        Part::FunctionCall { name, args, .. } => format!("{name}{args}"),
+        Part::FunctionResponse { function_response, .. } => {
+            format!("{}{}", function_response.name, function_response.response)
+        }
+        Part::InlineData { .. }
+        | Part::FileData { .. }
+        | Part::ServerToolCall { .. }
+        | Part::ServerToolResponse { .. } => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adk_core::Content;
+
+    #[test]
+    fn test_encoding_follows_model_family() {
+        let hello = |model| encoding_for(model).encode_ordinary("Hello, world!").len();
+        assert_eq!(hello("gpt-4o-mini"), 4);
+        assert_eq!(hello("gpt-4"), 4);
+
+        let text = "日本語のテキストをトークンに分割します。";
+        assert!(
+            encoding_for("gpt-4o").encode_ordinary(text).len()
+                < encoding_for("gpt-4-turbo").encode_ordinary(text).len()
+        );
+    }
+
+    #[test]
+    fn test_request_count_adds_message_overhead() {
+        let req = LlmRequest::new(
+            "gpt-4o",
+            vec![
+                Content::new("system").with_text("You are a helpful assistant."),
+                Content::new("user").with_text("Hello, world!"),
+            ],
+        );
+        // 6 + 4 text tokens, 1 role token and 3 wrapping tokens per message, 3 to prime the reply
+        assert_eq!(count_request_tokens("gpt-4o", &req), 6 + 4 + 2 * (1 + 3) + 3);
+    }
+}
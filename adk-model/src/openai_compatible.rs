@@ -1,7 +1,11 @@
 //! Shared OpenAI-compatible provider implementation.
 
 use crate::openai::convert;
-use crate::retry::{RetryConfig, execute_with_retry, is_retryable_model_error};
+use crate::prompt_layout::PromptLayout;
+use crate::retry::{
+    RetryConfig, execute_stream_with_retry, execute_with_retry, is_retryable_model_error,
+    retry_after_from_headers,
+};
 use adk_core::{
     AdkError, Content, ErrorCategory, ErrorComponent, FinishReason, Llm, LlmRequest, LlmResponse,
     LlmResponseStream, Part, SchemaAdapter, SchemaCache, UsageMetadata,
@@ -13,7 +17,6 @@ use async_stream::try_stream;
 use async_trait::async_trait;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 /// Configuration for OpenAI-compatible providers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +41,13 @@ pub struct OpenAICompatibleConfig {
     pub reasoning_effort: Option<ReasoningEffort>,
     /// Whether to allow the model to call multiple tools in a single turn.
     pub parallel_tool_calls: bool,
+    /// Emulate tool calling over JSON output for endpoints without native
+    /// function calling. See [`JsonModeToolAdapter`](crate::JsonModeToolAdapter).
+    #[serde(default)]
+    pub json_mode_tools: bool,
+    /// Retry policy for transient failures such as rate limits.
+    #[serde(default)]
+    pub retry_config: RetryConfig,
 }
 
 impl OpenAICompatibleConfig {
@@ -52,6 +62,8 @@ impl OpenAICompatibleConfig {
             project_id: None,
             reasoning_effort: None,
             parallel_tool_calls: true,
+            json_mode_tools: false,
+            retry_config: RetryConfig::default(),
         }
     }
 
@@ -91,6 +103,21 @@ impl OpenAICompatibleConfig {
         self
     }
 
+    /// Emulate tool calling over JSON output instead of sending native `tools`.
+    ///
+    /// Use this for endpoints (often local models) that reject function
+    /// calling but support JSON responses.
+    pub fn with_json_mode_tools(mut self, enabled: bool) -> Self {
+        self.json_mode_tools = enabled;
+        self
+    }
+
+    /// Set the retry policy for transient failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
     // ── Provider presets ─────────────────────────────────────────
 
     /// Fireworks AI preset.
@@ -235,6 +262,7 @@ pub struct OpenAICompatible {
     reasoning_effort: Option<ReasoningEffort>,
     organization_id: Option<String>,
     parallel_tool_calls: bool,
+    json_mode_tools: bool,
 }
 
 impl OpenAICompatible {
@@ -248,10 +276,11 @@ impl OpenAICompatible {
             base_url,
             model: config.model,
             provider_name: config.provider_name,
-            retry_config: RetryConfig::default(),
+            retry_config: config.retry_config,
             reasoning_effort: config.reasoning_effort,
             organization_id: config.organization_id,
             parallel_tool_calls: config.parallel_tool_calls,
+            json_mode_tools: config.json_mode_tools,
         })
     }
 
@@ -286,7 +315,8 @@ pub(crate) fn build_request_json(
     adapter: &dyn SchemaAdapter,
     cache: &SchemaCache,
 ) -> Result<serde_json::Value, AdkError> {
-    let messages: Vec<_> = request.contents.iter().map(convert::content_to_message).collect();
+    let contents = PromptLayout::openai().normalize(&request.contents).contents;
+    let messages: Vec<_> = contents.iter().map(convert::content_to_message).collect();
 
     let mut request_builder = CreateChatCompletionRequestArgs::default();
     request_builder.model(model).messages(messages);
@@ -303,15 +333,40 @@ pub(crate) fn build_request_json(
     }
 
     if let Some(config) = &request.config {
+        crate::generation_config::log_unsupported_fields(
+            "openai",
+            config,
+            &[
+                "temperature",
+                "top_p",
+                "frequency_penalty",
+                "presence_penalty",
+                "max_output_tokens",
+                "seed",
+                "stop_sequences",
+            ],
+        );
         if let Some(temp) = config.temperature {
             request_builder.temperature(temp);
         }
         if let Some(top_p) = config.top_p {
             request_builder.top_p(top_p);
         }
+        if let Some(penalty) = config.frequency_penalty {
+            request_builder.frequency_penalty(penalty);
+        }
+        if let Some(penalty) = config.presence_penalty {
+            request_builder.presence_penalty(penalty);
+        }
         if let Some(max_tokens) = config.max_output_tokens {
             request_builder.max_completion_tokens(max_tokens as u32);
         }
+        if let Some(seed) = config.seed {
+            request_builder.seed(seed);
+        }
+        if !config.stop_sequences.is_empty() {
+            request_builder.stop(config.stop_sequences.clone());
+        }
 
         if let Some(schema) = &config.response_schema {
             let mut schema_with_strict = schema.clone();
@@ -382,32 +437,377 @@ async fn send_request(
 
     if !http_resp.status().is_success() {
         let status = http_resp.status();
-        let status_code = status.as_u16();
+        let retry_after =
+            retry_after_from_headers(|name| http_resp.headers().get(name)?.to_str().ok());
         let body = http_resp.text().await.unwrap_or_default();
-        let category = match status_code {
-            401 => ErrorCategory::Unauthorized,
-            403 => ErrorCategory::Forbidden,
-            404 => ErrorCategory::NotFound,
-            408 => ErrorCategory::Timeout,
-            429 => ErrorCategory::RateLimited,
-            503 | 529 => ErrorCategory::Unavailable,
-            _ if status_code >= 500 => ErrorCategory::Internal,
-            _ => ErrorCategory::InvalidInput,
-        };
-        return Err(AdkError::new(
+        let mut err = AdkError::from_http_status(
             ErrorComponent::Model,
-            category,
             "model.openai_compat.api_error",
+            status.as_u16(),
             format!("{provider_name} API error (HTTP {status}): {body}"),
         )
-        .with_upstream_status(status_code)
-        .with_provider(provider_name));
+        .with_provider(provider_name);
+        if let Some(delay) = retry_after {
+            err = err.with_retry_after(delay);
+        }
+        return Err(err);
     }
 
     Ok(http_resp)
 }
 
+/// Sends a streaming chat completion request and turns its SSE events into
+/// chunks. A failed request is yielded as the stream's first item.
+fn stream_chat_completion(
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    organization_id: Option<String>,
+    request_body: serde_json::Value,
+    provider_name: String,
+) -> LlmResponseStream {
+    let response_stream = try_stream! {
+        // Inject streaming fields into the pre-built request body.
+        let mut body = request_body;
+        if let Some(obj) = body.as_object_mut() {
+            obj.insert("stream".to_string(), serde_json::json!(true));
+            obj.insert(
+                "stream_options".to_string(),
+                serde_json::json!({"include_usage": true}),
+            );
+        }
+
+        let url = format!("{base_url}/chat/completions");
+
+        let response =
+            send_request(&http, &url, &api_key, &organization_id, &body, &provider_name)
+                .await?;
+
+        // Process SSE byte stream (following DeepSeekClient pattern).
+        let mut byte_stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut tool_calls = StreamedToolCalls::default();
+        let mut text_tool_buffer = crate::tool_call_parser::ToolCallBuffer::new();
+
+        while let Some(chunk_result) = byte_stream.next().await {
+            let chunk = chunk_result.map_err(|e| {
+                AdkError::model(format!("stream read error: {e}"))
+            })?;
+
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            // Process complete SSE lines.
+            while let Some(line_end) = buffer.find('\n') {
+                let line = buffer[..line_end].trim().to_string();
+                buffer = buffer[line_end + 1..].to_string();
+
+                if line.is_empty() || line == "data: [DONE]" {
+                    continue;
+                }
+
+                if let Some(data) = line.strip_prefix("data:").map(str::trim_start) {
+                    let chunk_json: serde_json::Value = match serde_json::from_str(data) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            tracing::warn!(
+                                "failed to parse SSE chunk: {e} - {data}"
+                            );
+                            continue;
+                        }
+                    };
+
+                    let choice = match chunk_json.get("choices").and_then(|c| c.get(0)) {
+                        Some(c) => c,
+                        None => continue,
+                    };
+                    // The finishing chunk of some providers has no delta.
+                    let empty_delta = serde_json::json!({});
+                    let delta = choice.get("delta").unwrap_or(&empty_delta);
+
+                    let finish_reason_str = choice
+                        .get("finish_reason")
+                        .and_then(|v| v.as_str())
+                        .map(String::from);
+
+                    if let Some(fragments) = delta.get("tool_calls").and_then(|v| v.as_array()) {
+                        tool_calls.push(fragments);
+                    }
+
+                    // Check for finish_reason → emit final response.
+                    if let Some(ref fr) = finish_reason_str {
+                        let finish_reason = Some(parse_finish_reason(fr));
+                        let usage_metadata = parse_usage_from_chunk(&chunk_json);
+
+                        // Emit accumulated tool calls if any.
+                        if !tool_calls.is_empty() {
+                            let mut parts = Vec::new();
+                            if let Some(text) = delta.get("content").and_then(|v| v.as_str())
+                                && !text.is_empty() {
+                                    parts.push(Part::Text { text: text.to_string() });
+                                }
+                            parts.extend(tool_calls.finish());
+
+                            yield LlmResponse {
+                                content: Some(Content {
+                                    role: "model".to_string(),
+                                    parts,
+                                }),
+                                usage_metadata,
+                                finish_reason,
+                                citation_metadata: None,
+                                partial: false,
+                                // Tool-call turns are not complete — tool
+                                // results must still be processed (issue #401).
+                                turn_complete: false,
+                                interrupted: false,
+                                error_code: None,
+                                error_message: None,
+                                provider_metadata: None,
+                                interaction_id: None,
+                                citations: Vec::new(),
+                            };
+                            continue;
+                        }
+
+                        // Final response without tool calls.
+                        let mut parts = Vec::new();
+                        if let Some(text) = delta.get("content").and_then(|v| v.as_str())
+                            && !text.is_empty() {
+                                parts.push(Part::Text { text: text.to_string() });
+                            }
+
+                        yield LlmResponse {
+                            content: if parts.is_empty() { None } else {
+                                Some(Content {
+                                    role: "model".to_string(),
+                                    parts,
+                                })
+                            },
+                            usage_metadata,
+                            finish_reason,
+                            citation_metadata: None,
+                            partial: false,
+                            turn_complete: true,
+                            interrupted: false,
+                            error_code: None,
+                            error_message: None,
+                            provider_metadata: None,
+                            interaction_id: None,
+                            citations: Vec::new(),
+                        };
+                        continue;
+                    }
+
+                    // Emit partial reasoning_content as Part::Thinking.
+                    // Fallback to "reasoning" field for OpenRouter, Kilo Gateway, SambaNova, Cerebras, Groq
+                    let reasoning = delta.get("reasoning_content")
+                        .or_else(|| delta.get("reasoning"))
+                        .and_then(|v| v.as_str());
+                    if let Some(reasoning) = reasoning
+                        && !reasoning.is_empty() {
+                            yield LlmResponse {
+                                content: Some(Content {
+                                    role: "model".to_string(),
+                                    parts: vec![Part::Thinking {
+                                        thinking: reasoning.to_string(),
+                                        signature: None,
+                                    }],
+                                }),
+                                usage_metadata: None,
+                                finish_reason: None,
+                                citation_metadata: None,
+                                partial: true,
+                                turn_complete: false,
+                                interrupted: false,
+                                error_code: None,
+                                error_message: None,
+                                provider_metadata: None,
+                                interaction_id: None,
+                                citations: Vec::new(),
+                            };
+                        }
+
+                    // Emit partial text content via tool call buffer.
+                    // The buffer detects <tool_call> tags split across chunks
+                    // and converts them to Part::FunctionCall.
+                    if let Some(text) = delta.get("content").and_then(|v| v.as_str())
+                        && !text.is_empty() {
+                            match text_tool_buffer.push(text) {
+                                crate::tool_call_parser::BufferAction::Emit(parts) => {
+                                    for part in parts {
+                                        let is_tool = matches!(part, Part::FunctionCall { .. });
+                                        yield LlmResponse {
+                                            content: Some(Content {
+                                                role: "model".to_string(),
+                                                parts: vec![part],
+                                            }),
+                                            usage_metadata: None,
+                                            finish_reason: None,
+                                            citation_metadata: None,
+                                            partial: !is_tool,
+                                            turn_complete: false,
+                                            interrupted: false,
+                                            error_code: None,
+                                            error_message: None,
+                                            provider_metadata: None,
+                                            interaction_id: None,
+                                            citations: Vec::new(),
+                                        };
+                                    }
+                                }
+                                crate::tool_call_parser::BufferAction::Buffering => {
+                                    // Still accumulating a potential tool call
+                                }
+                            }
+                        }
+                }
+            }
+        }
+
+        // Some providers end the stream without a finish_reason after the
+        // last tool call fragment.
+        if !tool_calls.is_empty() {
+            yield LlmResponse {
+                content: Some(Content { role: "model".to_string(), parts: tool_calls.finish() }),
+                usage_metadata: None,
+                finish_reason: Some(FinishReason::Stop),
+                citation_metadata: None,
+                partial: false,
+                turn_complete: false,
+                interrupted: false,
+                error_code: None,
+                error_message: None,
+                provider_metadata: None,
+                interaction_id: None,
+                citations: Vec::new(),
+            };
+        }
+
+        // Flush any remaining buffered content from the tool call buffer
+        for part in text_tool_buffer.flush() {
+            let is_tool = matches!(part, Part::FunctionCall { .. });
+            yield LlmResponse {
+                content: Some(Content {
+                    role: "model".to_string(),
+                    parts: vec![part],
+                }),
+                usage_metadata: None,
+                finish_reason: if is_tool { Some(adk_core::FinishReason::Stop) } else { None },
+                citation_metadata: None,
+                partial: !is_tool,
+                turn_complete: is_tool,
+                interrupted: false,
+                error_code: None,
+                error_message: None,
+                provider_metadata: None,
+                interaction_id: None,
+                citations: Vec::new(),
+            };
+        }
+    };
+    Box::pin(response_stream)
+}
+
+/// Tool calls of a streamed chat completion, assembled from the
+/// `delta.tool_calls` fragments spread over its chunks.
+///
+/// Fragments are matched to calls by `index`, so parallel calls may
+/// interleave. A fragment that carries a new `id` for an index already in use
+/// starts another call, since some OpenAI-compatible servers send every call
+/// with index 0.
+#[derive(Debug, Default)]
+struct StreamedToolCalls {
+    calls: Vec<StreamedToolCall>,
+}
+
+#[derive(Debug)]
+struct StreamedToolCall {
+    index: u64,
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl StreamedToolCalls {
+    /// Adds the fragments of one chunk's `delta.tool_calls`.
+    fn push(&mut self, fragments: &[serde_json::Value]) {
+        for fragment in fragments {
+            let index = fragment.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+            let id = fragment.get("id").and_then(|v| v.as_str()).filter(|id| !id.is_empty());
+            let existing = self.calls.iter().rposition(|call| call.index == index);
+            let position = match existing {
+                Some(position)
+                    if id.is_none_or(|id| {
+                        self.calls[position].id.is_empty() || self.calls[position].id == id
+                    }) =>
+                {
+                    position
+                }
+                _ => {
+                    self.calls.push(StreamedToolCall {
+                        index,
+                        id: String::new(),
+                        name: String::new(),
+                        arguments: String::new(),
+                    });
+                    self.calls.len() - 1
+                }
+            };
+
+            let call = &mut self.calls[position];
+            if let Some(id) = id {
+                call.id = id.to_string();
+            }
+            if let Some(function) = fragment.get("function") {
+                if let Some(name) = function.get("name").and_then(|v| v.as_str())
+                    && !name.is_empty()
+                {
+                    call.name = name.to_string();
+                }
+                if let Some(arguments) = function.get("arguments").and_then(|v| v.as_str()) {
+                    call.arguments.push_str(arguments);
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Takes the calls assembled so far as `FunctionCall` parts, in the order
+    /// they started.
+    fn finish(&mut self) -> Vec<Part> {
+        self.calls
+            .drain(..)
+            .map(|call| {
+                let args = if call.arguments.trim().is_empty() {
+                    serde_json::json!({})
+                } else {
+                    serde_json::from_str(&call.arguments).unwrap_or_else(|e| {
+                        tracing::warn!(
+                            tool = %call.name,
+                            error = %e,
+                            "streamed tool call arguments are not valid JSON"
+                        );
+                        serde_json::json!({})
+                    })
+                };
+                Part::FunctionCall {
+                    name: call.name,
+                    args,
+                    id: (!call.id.is_empty()).then_some(call.id),
+                    thought_signature: None,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Parse a finish_reason string into an ADK `FinishReason`.
+///
+/// `tool_calls` maps to `Stop` like the other providers' tool-use stop
+/// reasons; the function calls in the response tell the agent to continue.
 fn parse_finish_reason(fr: &str) -> FinishReason {
     match fr {
         "stop" => FinishReason::Stop,
@@ -474,6 +874,10 @@ impl Llm for OpenAICompatible {
         request: LlmRequest,
         stream: bool,
     ) -> Result<LlmResponseStream, AdkError> {
+        if self.json_mode_tools && !request.tools.is_empty() {
+            return crate::json_mode_tools::generate_with_json_tools(self, request).await;
+        }
+
         let model = self.model.clone();
         let provider_name = self.provider_name.clone();
         let http = self.http.clone();
@@ -500,276 +904,20 @@ impl Llm for OpenAICompatible {
 
         if stream {
             // ── Streaming path ──────────────────────────────────────
-            let response_stream = try_stream! {
-                // Inject streaming fields into the pre-built request body.
-                let mut body = request_body.clone();
-                if let Some(obj) = body.as_object_mut() {
-                    obj.insert("stream".to_string(), serde_json::json!(true));
-                    obj.insert(
-                        "stream_options".to_string(),
-                        serde_json::json!({"include_usage": true}),
-                    );
-                }
-
-                let url = format!("{base_url}/chat/completions");
-
-                // Retry covers only the initial HTTP request, not stream consumption.
-                let response = execute_with_retry(&retry_config, is_retryable_model_error, || {
-                    let http = http.clone();
-                    let url = url.clone();
-                    let api_key = api_key.clone();
-                    let organization_id = organization_id.clone();
-                    let body = body.clone();
-                    let provider_name = provider_name.clone();
-                    async move {
-                        send_request(&http, &url, &api_key, &organization_id, &body, &provider_name).await
-                    }
+            let response_stream =
+                execute_stream_with_retry(&retry_config, is_retryable_model_error, || {
+                    std::future::ready(Ok(stream_chat_completion(
+                        http.clone(),
+                        base_url.clone(),
+                        api_key.clone(),
+                        organization_id.clone(),
+                        request_body.clone(),
+                        provider_name.clone(),
+                    )))
                 })
                 .await?;
 
-                // Process SSE byte stream (following DeepSeekClient pattern).
-                let mut byte_stream = response.bytes_stream();
-                let mut buffer = String::new();
-                let mut tool_call_accumulators: HashMap<u32, (String, String, String)> =
-                    HashMap::new();
-                let mut text_tool_buffer = crate::tool_call_parser::ToolCallBuffer::new();
-
-                while let Some(chunk_result) = byte_stream.next().await {
-                    let chunk = chunk_result.map_err(|e| {
-                        AdkError::model(format!("stream read error: {e}"))
-                    })?;
-
-                    buffer.push_str(&String::from_utf8_lossy(&chunk));
-
-                    // Process complete SSE lines.
-                    while let Some(line_end) = buffer.find('\n') {
-                        let line = buffer[..line_end].trim().to_string();
-                        buffer = buffer[line_end + 1..].to_string();
-
-                        if line.is_empty() || line == "data: [DONE]" {
-                            continue;
-                        }
-
-                        if let Some(data) = line.strip_prefix("data: ") {
-                            let chunk_json: serde_json::Value = match serde_json::from_str(data) {
-                                Ok(v) => v,
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "failed to parse SSE chunk: {e} - {data}"
-                                    );
-                                    continue;
-                                }
-                            };
-
-                            let choice = match chunk_json.get("choices").and_then(|c| c.get(0)) {
-                                Some(c) => c,
-                                None => continue,
-                            };
-                            let delta = match choice.get("delta") {
-                                Some(d) => d,
-                                None => continue,
-                            };
-
-                            let finish_reason_str = choice
-                                .get("finish_reason")
-                                .and_then(|v| v.as_str())
-                                .map(String::from);
-
-                            // Accumulate tool calls by index.
-                            if let Some(tool_calls) = delta.get("tool_calls").and_then(|v| v.as_array()) {
-                                for tc in tool_calls {
-                                    let index = tc.get("index").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-                                    let entry = tool_call_accumulators
-                                        .entry(index)
-                                        .or_insert_with(|| {
-                                            let call_id = tc
-                                                .get("id")
-                                                .and_then(|v| v.as_str())
-                                                .unwrap_or("")
-                                                .to_string();
-                                            (call_id, String::new(), String::new())
-                                        });
-
-                                    if let Some(id) = tc.get("id").and_then(|v| v.as_str())
-                                        && !id.is_empty() {
-                                            entry.0 = id.to_string();
-                                        }
-
-                                    if let Some(func) = tc.get("function") {
-                                        if let Some(name) = func.get("name").and_then(|v| v.as_str()) {
-                                            entry.1 = name.to_string();
-                                        }
-                                        if let Some(args_chunk) =
-                                            func.get("arguments").and_then(|v| v.as_str())
-                                        {
-                                            entry.2.push_str(args_chunk);
-                                        }
-                                    }
-                                }
-                            }
-
-                            // Check for finish_reason → emit final response.
-                            if let Some(ref fr) = finish_reason_str {
-                                let finish_reason = Some(parse_finish_reason(fr));
-                                let usage_metadata = parse_usage_from_chunk(&chunk_json);
-
-                                // Emit accumulated tool calls if any.
-                                if !tool_call_accumulators.is_empty() {
-                                    let mut sorted_calls: Vec<_> =
-                                        tool_call_accumulators.drain().collect();
-                                    sorted_calls.sort_by_key(|(idx, _)| *idx);
-
-                                    let parts: Vec<Part> = sorted_calls
-                                        .into_iter()
-                                        .map(|(_, (id, name, args_str))| {
-                                            let args: serde_json::Value =
-                                                serde_json::from_str(&args_str)
-                                                    .unwrap_or(serde_json::json!({}));
-                                            Part::FunctionCall {
-                                                name,
-                                                args,
-                                                id: Some(id),
-                                                thought_signature: None,
-                                            }
-                                        })
-                                        .collect();
-
-                                    yield LlmResponse {
-                                        content: Some(Content {
-                                            role: "model".to_string(),
-                                            parts,
-                                        }),
-                                        usage_metadata,
-                                        finish_reason,
-                                        citation_metadata: None,
-                                        partial: false,
-                                        // Tool-call turns are not complete — tool
-                                        // results must still be processed (issue #401).
-                                        turn_complete: false,
-                                        interrupted: false,
-                                        error_code: None,
-                                        error_message: None,
-                                        provider_metadata: None,
-                                        interaction_id: None,
-                                    };
-                                    continue;
-                                }
-
-                                // Final response without tool calls.
-                                let mut parts = Vec::new();
-                                if let Some(text) = delta.get("content").and_then(|v| v.as_str())
-                                    && !text.is_empty() {
-                                        parts.push(Part::Text { text: text.to_string() });
-                                    }
-
-                                yield LlmResponse {
-                                    content: if parts.is_empty() { None } else {
-                                        Some(Content {
-                                            role: "model".to_string(),
-                                            parts,
-                                        })
-                                    },
-                                    usage_metadata,
-                                    finish_reason,
-                                    citation_metadata: None,
-                                    partial: false,
-                                    turn_complete: true,
-                                    interrupted: false,
-                                    error_code: None,
-                                    error_message: None,
-                                    provider_metadata: None,
-                                    interaction_id: None,
-                                };
-                                continue;
-                            }
-
-                            // Emit partial reasoning_content as Part::Thinking.
-                            // Fallback to "reasoning" field for OpenRouter, Kilo Gateway, SambaNova, Cerebras, Groq
-                            let reasoning = delta.get("reasoning_content")
-                                .or_else(|| delta.get("reasoning"))
-                                .and_then(|v| v.as_str());
-                            if let Some(reasoning) = reasoning
-                                && !reasoning.is_empty() {
-                                    yield LlmResponse {
-                                        content: Some(Content {
-                                            role: "model".to_string(),
-                                            parts: vec![Part::Thinking {
-                                                thinking: reasoning.to_string(),
-                                                signature: None,
-                                            }],
-                                        }),
-                                        usage_metadata: None,
-                                        finish_reason: None,
-                                        citation_metadata: None,
-                                        partial: true,
-                                        turn_complete: false,
-                                        interrupted: false,
-                                        error_code: None,
-                                        error_message: None,
-                                        provider_metadata: None,
-                                        interaction_id: None,
-                                    };
-                                }
-
-                            // Emit partial text content via tool call buffer.
-                            // The buffer detects <tool_call> tags split across chunks
-                            // and converts them to Part::FunctionCall.
-                            if let Some(text) = delta.get("content").and_then(|v| v.as_str())
-                                && !text.is_empty() {
-                                    match text_tool_buffer.push(text) {
-                                        crate::tool_call_parser::BufferAction::Emit(parts) => {
-                                            for part in parts {
-                                                let is_tool = matches!(part, Part::FunctionCall { .. });
-                                                yield LlmResponse {
-                                                    content: Some(Content {
-                                                        role: "model".to_string(),
-                                                        parts: vec![part],
-                                                    }),
-                                                    usage_metadata: None,
-                                                    finish_reason: None,
-                                                    citation_metadata: None,
-                                                    partial: !is_tool,
-                                                    turn_complete: false,
-                                                    interrupted: false,
-                                                    error_code: None,
-                                                    error_message: None,
-                                                    provider_metadata: None,
-                                                    interaction_id: None,
-                                                };
-                                            }
-                                        }
-                                        crate::tool_call_parser::BufferAction::Buffering => {
-                                            // Still accumulating a potential tool call
-                                        }
-                                    }
-                                }
-                        }
-                    }
-                }
-
-                // Flush any remaining buffered content from the tool call buffer
-                for part in text_tool_buffer.flush() {
-                    let is_tool = matches!(part, Part::FunctionCall { .. });
-                    yield LlmResponse {
-                        content: Some(Content {
-                            role: "model".to_string(),
-                            parts: vec![part],
-                        }),
-                        usage_metadata: None,
-                        finish_reason: if is_tool { Some(adk_core::FinishReason::Stop) } else { None },
-                        citation_metadata: None,
-                        partial: !is_tool,
-                        turn_complete: is_tool,
-                        interrupted: false,
-                        error_code: None,
-                        error_message: None,
-                        provider_metadata: None,
-                        interaction_id: None,
-                    };
-                }
-            };
-
-            Ok(crate::usage_tracking::with_usage_tracking(Box::pin(response_stream), usage_span))
+            Ok(crate::usage_tracking::with_usage_tracking(response_stream, usage_span))
         } else {
             // ── Non-streaming path (preserved identically) ──────────
             let response_stream = try_stream! {
@@ -865,10 +1013,144 @@ mod tests {
         assert_eq!(config.reasoning_effort, Some(ReasoningEffort::Low));
     }
 
+    #[test]
+    fn request_sampling_config_reaches_the_body() {
+        let mut request = LlmRequest::new("gpt-5", vec![Content::new("user").with_text("Hi")]);
+        request.config = Some(adk_core::GenerateContentConfig {
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            top_k: Some(40),
+            max_output_tokens: Some(256),
+            stop_sequences: vec!["END".to_string()],
+            ..Default::default()
+        });
+        let client = OpenAICompatible::new(OpenAICompatibleConfig::new("k", "gpt-5")).unwrap();
+
+        let body = build_request_json(
+            "gpt-5",
+            &request,
+            &None,
+            true,
+            client.schema_adapter(),
+            &SchemaCache::new(),
+        )
+        .unwrap();
+
+        assert_eq!(body["temperature"], serde_json::json!(0.2_f32));
+        assert_eq!(body["top_p"], serde_json::json!(0.9_f32));
+        assert_eq!(body["max_completion_tokens"], 256);
+        assert_eq!(body["stop"], serde_json::json!(["END"]));
+        // Chat Completions has no top_k; it is skipped rather than rejected.
+        assert!(body.get("top_k").is_none());
+    }
+
     #[test]
     fn gemini_preset_builds_client() {
         let config = OpenAICompatibleConfig::gemini("k", "gemini-3.5-flash");
         let client = OpenAICompatible::new(config).expect("client builds");
         assert_eq!(client.name(), "gemini-3.5-flash");
     }
+
+    /// The chunks GPT-4o streams for two parallel calls, with the second
+    /// call's fragments interleaved with the first's.
+    const PARALLEL_TOOL_CALL_CHUNKS: &[&str] = &[
+        r#"{"choices":[{"index":0,"delta":{"role":"assistant","content":null,"tool_calls":[{"index":0,"id":"call_weather","type":"function","function":{"name":"get_weather","arguments":""}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"ci"}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"id":"call_time","type":"function","function":{"name":"get_time","arguments":""}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"function":{"arguments":"{\"timezone\": "}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"ty\": \"Par"}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"function":{"arguments":"\"Europe/Paris\"}"}},{"index":0,"function":{"arguments":"is\"}"}}]},"finish_reason":null}]}"#,
+        r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+        r#"{"choices":[],"usage":{"prompt_tokens":80,"completion_tokens":42,"total_tokens":122}}"#,
+    ];
+
+    fn function_calls(parts: &[Part]) -> Vec<(String, serde_json::Value, Option<String>)> {
+        parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::FunctionCall { name, args, id, .. } => {
+                    Some((name.clone(), args.clone(), id.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn streamed_parallel_tool_calls_are_assembled_on_finish() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body: String = PARALLEL_TOOL_CALL_CHUNKS
+            .iter()
+            .map(|chunk| format!("data: {chunk}\n\n"))
+            .chain(["data: [DONE]\n\n".to_string()])
+            .collect();
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .mount(&server)
+            .await;
+        let client = OpenAICompatible::new(
+            OpenAICompatibleConfig::new("test-key", "gpt-4o").with_base_url(server.uri()),
+        )
+        .unwrap();
+
+        let responses: Vec<LlmResponse> = client
+            .generate_content(
+                LlmRequest::new("gpt-4o", vec![Content::new("user").with_text("Paris?")]),
+                true,
+            )
+            .await
+            .unwrap()
+            .map(|response| response.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(responses.len(), 1, "tool calls are only emitted once complete");
+        let response = &responses[0];
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert!(!response.partial && !response.turn_complete);
+        assert_eq!(
+            function_calls(&response.content.as_ref().unwrap().parts),
+            [
+                (
+                    "get_weather".to_string(),
+                    serde_json::json!({ "city": "Paris" }),
+                    Some("call_weather".to_string())
+                ),
+                (
+                    "get_time".to_string(),
+                    serde_json::json!({ "timezone": "Europe/Paris" }),
+                    Some("call_time".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn streamed_tool_calls_sharing_an_index_are_told_apart_by_id() {
+        let mut calls = StreamedToolCalls::default();
+        calls.push(&[serde_json::json!({
+            "index": 0, "id": "call_a", "function": { "name": "lookup", "arguments": "{\"q\":" }
+        })]);
+        calls.push(&[serde_json::json!({ "index": 0, "function": { "arguments": "1}" } })]);
+        calls.push(&[serde_json::json!({
+            "index": 0, "id": "call_b", "function": { "name": "ping", "arguments": "" }
+        })]);
+
+        assert_eq!(
+            function_calls(&calls.finish()),
+            [
+                ("lookup".to_string(), serde_json::json!({ "q": 1 }), Some("call_a".to_string())),
+                ("ping".to_string(), serde_json::json!({}), Some("call_b".to_string())),
+            ]
+        );
+        assert!(calls.is_empty());
+    }
 }
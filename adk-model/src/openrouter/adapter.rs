@@ -773,6 +773,7 @@ fn chat_response_to_llm_response(response: &OpenRouterChatResponse) -> LlmRespon
         provider_metadata: (!provider_metadata.is_empty())
             .then_some(Value::Object(provider_metadata)),
         interaction_id: None,
+        citations: Vec::new(),
     }
 }
 
@@ -856,6 +857,7 @@ fn responses_response_to_llm_response(
         provider_metadata: (!provider_metadata.is_empty())
             .then_some(Value::Object(provider_metadata)),
         interaction_id: None,
+        citations: Vec::new(),
     })
 }
 
@@ -989,6 +991,7 @@ fn llm_stream_response(
         error_message: None,
         provider_metadata,
         interaction_id: None,
+        citations: Vec::new(),
     })
 }
 
@@ -5,6 +5,7 @@ use super::chat::{
     OpenRouterChatRequest, OpenRouterChatToolCall, OpenRouterChatToolFunction, OpenRouterPlugin,
     OpenRouterReasoningReplay,
 };
+use crate::tool_result::{link_tool_call_ids, split_tool_results};
 use adk_core::{AdkError, Content, Part};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
@@ -16,7 +17,9 @@ const FILE_PARSER_PLUGIN_ID: &str = "file-parser";
 pub fn adk_contents_to_chat_messages(
     contents: &[Content],
 ) -> Result<Vec<OpenRouterChatMessage>, AdkError> {
-    contents.iter().map(adk_content_to_chat_message).collect()
+    let mut contents = contents.to_vec();
+    link_tool_call_ids(&mut contents);
+    split_tool_results(contents).iter().map(adk_content_to_chat_message).collect()
 }
 
 /// Ensure the `file-parser` plugin is present when chat content includes file inputs.
@@ -0,0 +1,384 @@
+//! Request validation before a provider is called.
+//!
+//! An oversized image or an empty prompt is otherwise only reported by the
+//! provider, late and in its own words. [`PreflightLlm`] wraps any model and
+//! checks each request against the provider's [`MediaLimits`] first:
+//!
+//! - the request must have some content,
+//! - image parts must use an image type the provider accepts, and
+//! - inline images must fit the provider's size and dimension limits.
+//!
+//! A request that fails is rejected with an [`ErrorCategory::InvalidInput`]
+//! error (code [`INVALID_REQUEST`]) naming the offending part. With
+//! [`PreflightLlm::with_downscale`], images over the limits are resized and
+//! re-encoded to fit instead.
+//!
+//! Only image types are checked against the provider: other media is
+//! forwarded as before, since providers that cannot take it natively turn it
+//! into a text attachment.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_model::ModelProvider;
+//! use adk_model::preflight::PreflightLlm;
+//! use std::sync::Arc;
+//!
+//! let model = Arc::new(
+//!     PreflightLlm::new(Arc::new(claude), ModelProvider::Anthropic).with_downscale(true),
+//! );
+//! let agent = LlmAgentBuilder::new("assistant").model(model).build()?;
+//! ```
+
+use crate::provider::ModelProvider;
+use adk_core::{
+    AdkError, ErrorCategory, ErrorComponent, Llm, LlmRequest, LlmResponseStream, Part, Result,
+};
+use async_trait::async_trait;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat, ImageReader};
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// Error code of requests rejected by validation.
+pub const INVALID_REQUEST: &str = "model.invalid_request";
+
+/// Downscaling shrinks an image by this factor per attempt until it fits.
+const DOWNSCALE_STEP: f64 = 0.75;
+
+/// Attempts made to fit an image under the byte limit before giving up.
+const MAX_DOWNSCALE_ATTEMPTS: usize = 8;
+
+const MB: usize = 1024 * 1024;
+
+/// What a provider accepts for images.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaLimits {
+    /// Provider named in error messages, if known.
+    pub provider: Option<ModelProvider>,
+    /// Largest inline image, in bytes.
+    pub max_image_bytes: Option<usize>,
+    /// Largest width or height of an inline image, in pixels.
+    pub max_image_dimension: Option<u32>,
+    /// Accepted image MIME types. Empty accepts every type.
+    pub image_mime_types: Vec<String>,
+}
+
+impl MediaLimits {
+    /// No limits: every image passes.
+    pub fn unlimited() -> Self {
+        Self {
+            provider: None,
+            max_image_bytes: None,
+            max_image_dimension: None,
+            image_mime_types: Vec::new(),
+        }
+    }
+
+    /// The documented image limits of `provider`.
+    pub fn for_provider(provider: ModelProvider) -> Self {
+        let (max_image_bytes, max_image_dimension, image_mime_types): (_, _, &[&str]) =
+            match provider {
+                ModelProvider::Gemini => (
+                    20 * MB,
+                    None,
+                    &["image/png", "image/jpeg", "image/webp", "image/heic", "image/heif"],
+                ),
+                ModelProvider::Openai => {
+                    (20 * MB, None, &["image/png", "image/jpeg", "image/webp", "image/gif"])
+                }
+                ModelProvider::Anthropic => {
+                    (5 * MB, Some(8000), &["image/jpeg", "image/png", "image/gif", "image/webp"])
+                }
+                ModelProvider::Groq => {
+                    (4 * MB, None, &["image/jpeg", "image/png", "image/webp", "image/gif"])
+                }
+                ModelProvider::Deepseek | ModelProvider::Ollama => {
+                    (20 * MB, None, &["image/png", "image/jpeg"])
+                }
+            };
+        Self {
+            provider: Some(provider),
+            max_image_bytes: Some(max_image_bytes),
+            max_image_dimension,
+            image_mime_types: image_mime_types.iter().map(|mime| mime.to_string()).collect(),
+        }
+    }
+
+    /// Set the largest inline image, in bytes.
+    #[must_use]
+    pub fn with_max_image_bytes(mut self, max: usize) -> Self {
+        self.max_image_bytes = Some(max);
+        self
+    }
+
+    /// Set the largest width or height of an inline image, in pixels.
+    #[must_use]
+    pub fn with_max_image_dimension(mut self, max: u32) -> Self {
+        self.max_image_dimension = Some(max);
+        self
+    }
+
+    /// Returns `true` if images of type `mime_type` are accepted.
+    pub fn accepts_image_type(&self, mime_type: &str) -> bool {
+        self.image_mime_types.is_empty()
+            || self.image_mime_types.iter().any(|accepted| accepted.eq_ignore_ascii_case(mime_type))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.provider.map_or("the provider", ModelProvider::as_str)
+    }
+}
+
+impl Default for MediaLimits {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// Checks `request` against `limits`, downscaling oversized inline images in
+/// place when `downscale` is set.
+///
+/// Returns an [`ErrorCategory::InvalidInput`] error with code
+/// [`INVALID_REQUEST`] for an empty request, an image type the provider does
+/// not accept, or an image over the limits that is not downscaled.
+pub fn validate_request(
+    request: &mut LlmRequest,
+    limits: &MediaLimits,
+    downscale: bool,
+) -> Result<()> {
+    if !request.contents.iter().flat_map(|content| &content.parts).any(has_content) {
+        return Err(invalid_request(limits, "request has no content to send to the model"));
+    }
+
+    for (content_index, content) in request.contents.iter_mut().enumerate() {
+        for (part_index, part) in content.parts.iter_mut().enumerate() {
+            let location = format!("content {content_index}, part {part_index}");
+            match part {
+                Part::InlineData { mime_type, data } if is_image(mime_type) => {
+                    check_image_type(limits, mime_type, &location)?;
+                    if let Some((resized, resized_type)) =
+                        fit_image(limits, mime_type, data, downscale, &location)?
+                    {
+                        *data = resized;
+                        *mime_type = resized_type;
+                    }
+                }
+                Part::FileData { mime_type, .. } if is_image(mime_type) => {
+                    check_image_type(limits, mime_type, &location)?;
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Wraps a model so every request is validated before it reaches the provider.
+pub struct PreflightLlm {
+    inner: Arc<dyn Llm>,
+    limits: MediaLimits,
+    downscale: bool,
+}
+
+impl PreflightLlm {
+    /// Validate requests to `inner` against the limits of `provider`.
+    pub fn new(inner: Arc<dyn Llm>, provider: ModelProvider) -> Self {
+        Self { inner, limits: MediaLimits::for_provider(provider), downscale: false }
+    }
+
+    /// Validate against `limits` instead of the provider's defaults.
+    #[must_use]
+    pub fn with_limits(mut self, limits: MediaLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Resize images over the limits instead of rejecting the request.
+    #[must_use]
+    pub fn with_downscale(mut self, downscale: bool) -> Self {
+        self.downscale = downscale;
+        self
+    }
+
+    /// Returns the limits requests are checked against.
+    pub fn limits(&self) -> &MediaLimits {
+        &self.limits
+    }
+}
+
+#[async_trait]
+impl Llm for PreflightLlm {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schema_adapter(&self) -> &dyn adk_core::SchemaAdapter {
+        self.inner.schema_adapter()
+    }
+
+    fn max_tools(&self) -> Option<usize> {
+        self.inner.max_tools()
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.inner.supports_response_schema()
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        self.inner.count_tokens(req).await
+    }
+
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<adk_core::TokenBreakdown> {
+        self.inner.count_tokens_breakdown(req).await
+    }
+
+    async fn generate_content(
+        &self,
+        mut request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        validate_request(&mut request, &self.limits, self.downscale)?;
+        self.inner.generate_content(request, stream).await
+    }
+}
+
+fn has_content(part: &Part) -> bool {
+    match part {
+        Part::Text { text } => !text.trim().is_empty(),
+        Part::Thinking { .. } => false,
+        _ => true,
+    }
+}
+
+fn is_image(mime_type: &str) -> bool {
+    mime_type.get(..6).is_some_and(|prefix| prefix.eq_ignore_ascii_case("image/"))
+}
+
+fn check_image_type(limits: &MediaLimits, mime_type: &str, location: &str) -> Result<()> {
+    if limits.accepts_image_type(mime_type) {
+        return Ok(());
+    }
+    Err(invalid_request(
+        limits,
+        format!(
+            "image type '{mime_type}' in {location} is not supported by {}; supported types: {}",
+            limits.provider_name(),
+            limits.image_mime_types.join(", ")
+        ),
+    ))
+}
+
+/// Returns the image re-encoded to fit `limits`, `None` if it already fits.
+fn fit_image(
+    limits: &MediaLimits,
+    mime_type: &str,
+    data: &[u8],
+    downscale: bool,
+    location: &str,
+) -> Result<Option<(Vec<u8>, String)>> {
+    let too_many_bytes = limits.max_image_bytes.is_some_and(|max| data.len() > max);
+    // Images that cannot be decoded are left for the provider to judge.
+    let dimensions = match limits.max_image_dimension {
+        Some(_) => read_dimensions(data),
+        None => None,
+    };
+    let too_large =
+        dimensions.zip(limits.max_image_dimension).is_some_and(|((w, h), max)| w.max(h) > max);
+    if !too_many_bytes && !too_large {
+        return Ok(None);
+    }
+
+    let problem = match dimensions {
+        Some((width, height)) if too_large => format!(
+            "image in {location} is {width}x{height} pixels; {} accepts at most {} pixels per side",
+            limits.provider_name(),
+            limits.max_image_dimension.unwrap_or_default()
+        ),
+        _ => format!(
+            "image in {location} is {}; {} accepts at most {}",
+            format_bytes(data.len()),
+            limits.provider_name(),
+            format_bytes(limits.max_image_bytes.unwrap_or_default())
+        ),
+    };
+    if !downscale {
+        return Err(invalid_request(
+            limits,
+            format!("{problem}. Send a smaller image or enable downscaling"),
+        ));
+    }
+
+    let image = decode(data).ok_or_else(|| {
+        invalid_request(limits, format!("{problem}, and it could not be decoded to downscale it"))
+    })?;
+    downscale_image(&image, mime_type, limits)
+        .map(Some)
+        .ok_or_else(|| invalid_request(limits, format!("{problem}, even after downscaling")))
+}
+
+/// Shrinks `image` until it fits `limits`, keeping its aspect ratio. JPEG
+/// stays JPEG; every other type is re-encoded as PNG.
+fn downscale_image(
+    image: &DynamicImage,
+    mime_type: &str,
+    limits: &MediaLimits,
+) -> Option<(Vec<u8>, String)> {
+    let (width, height) = image.dimensions();
+    let longest = f64::from(width.max(height));
+    let mut scale =
+        limits.max_image_dimension.map_or(1.0, |max| (f64::from(max) / longest).min(1.0));
+
+    for _ in 0..MAX_DOWNSCALE_ATTEMPTS {
+        let target = |side: u32| ((f64::from(side) * scale).floor() as u32).max(1);
+        let resized = if scale < 1.0 {
+            image.resize_exact(target(width), target(height), FilterType::Triangle)
+        } else {
+            image.clone()
+        };
+        let encoded = encode(&resized, mime_type)?;
+        if limits.max_image_bytes.is_none_or(|max| encoded.0.len() <= max) {
+            return Some(encoded);
+        }
+        scale *= DOWNSCALE_STEP;
+    }
+    None
+}
+
+fn read_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    ImageReader::new(Cursor::new(data)).with_guessed_format().ok()?.into_dimensions().ok()
+}
+
+fn decode(data: &[u8]) -> Option<DynamicImage> {
+    ImageReader::new(Cursor::new(data)).with_guessed_format().ok()?.decode().ok()
+}
+
+fn encode(image: &DynamicImage, mime_type: &str) -> Option<(Vec<u8>, String)> {
+    let mut bytes = Vec::new();
+    if mime_type.eq_ignore_ascii_case("image/jpeg") {
+        let rgb = DynamicImage::ImageRgb8(image.to_rgb8());
+        rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, 85)).ok()?;
+        Some((bytes, "image/jpeg".to_string()))
+    } else {
+        image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).ok()?;
+        Some((bytes, "image/png".to_string()))
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+fn invalid_request(limits: &MediaLimits, message: impl Into<String>) -> AdkError {
+    let error =
+        AdkError::new(ErrorComponent::Model, ErrorCategory::InvalidInput, INVALID_REQUEST, message);
+    match limits.provider {
+        Some(provider) => error.with_provider(provider.as_str()),
+        None => error,
+    }
+}
@@ -0,0 +1,228 @@
+//! Provider-specific placement of system instructions and multimodal parts.
+//!
+//! The same logical [`LlmRequest`](adk_core::LlmRequest) has to be laid out
+//! differently per provider:
+//!
+//! - Gemini, Anthropic, and the OpenAI Responses API take the system
+//!   instruction in a dedicated field; `system` messages in the conversation
+//!   are dropped or rejected.
+//! - Chat Completions style APIs (OpenAI, DeepSeek, Groq, Ollama) take it as a
+//!   message, and several models only honor a single system message placed
+//!   first.
+//! - Gemini and Claude answer image questions best when the media comes
+//!   before the text that refers to it.
+//! - Gemini matches tool results to calls by name, Anthropic and the
+//!   Responses API by id, and Chat Completions APIs by id with one `tool`
+//!   message per result.
+//!
+//! A [`PromptLayout`] captures these conventions and
+//! [`normalize`](PromptLayout::normalize) applies them: every `system` content
+//! is merged into one instruction, which is either returned separately or
+//! placed as the first message, media parts of user messages are moved
+//! ahead of their text when the provider prefers that, and tool results are
+//! linked to their calls (see [`crate::tool_result`]).
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::Content;
+//! use adk_model::prompt_layout::PromptLayout;
+//!
+//! let contents = vec![
+//!     Content::new("user").with_text("Hi"),
+//!     Content::new("system").with_text("Answer in French."),
+//! ];
+//!
+//! let layout = PromptLayout::openai().normalize(&contents);
+//! assert_eq!(layout.system_instruction, None);
+//! assert_eq!(layout.contents[0].role, "system");
+//!
+//! let layout = PromptLayout::gemini().normalize(&contents);
+//! assert_eq!(layout.system_instruction.as_deref(), Some("Answer in French."));
+//! assert_eq!(layout.contents.len(), 1);
+//! ```
+
+use crate::provider::ModelProvider;
+use crate::tool_result::{link_tool_call_ids, split_tool_results};
+use adk_core::{Content, Part};
+
+/// Where a provider expects the system instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemPlacement {
+    /// A dedicated request field, outside the conversation.
+    Field,
+    /// A single `system` message at the start of the conversation.
+    LeadingMessage,
+}
+
+/// How the parts of a user message are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartOrder {
+    /// Keep the order the parts were added in.
+    AsGiven,
+    /// Move inline and file media ahead of text, keeping the relative order
+    /// within each group.
+    MediaFirst,
+}
+
+/// How tool results are tied to the calls they answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolResultLayout {
+    /// Results are matched to calls by name and position; ids are passed
+    /// through as they are.
+    ByName,
+    /// Every result carries the id of its call.
+    ById,
+    /// Every result carries the id of its call and is sent as a message of
+    /// its own.
+    MessagePerResult,
+}
+
+/// The request layout a provider expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptLayout {
+    /// Where the merged system instruction goes.
+    pub system: SystemPlacement,
+    /// How user message parts are ordered.
+    pub part_order: PartOrder,
+    /// How tool results reference their calls.
+    pub tool_results: ToolResultLayout,
+}
+
+/// The contents of a request after applying a [`PromptLayout`].
+#[derive(Debug, Clone)]
+pub struct NormalizedContents {
+    /// The merged system instruction, for [`SystemPlacement::Field`] layouts.
+    /// Always `None` for [`SystemPlacement::LeadingMessage`].
+    pub system_instruction: Option<String>,
+    /// The conversation, without `system` contents except for the single
+    /// leading one of a [`SystemPlacement::LeadingMessage`] layout.
+    pub contents: Vec<Content>,
+}
+
+impl PromptLayout {
+    /// Gemini `generateContent`: `systemInstruction` field, media first,
+    /// results matched by name.
+    pub const fn gemini() -> Self {
+        Self {
+            system: SystemPlacement::Field,
+            part_order: PartOrder::MediaFirst,
+            tool_results: ToolResultLayout::ByName,
+        }
+    }
+
+    /// Anthropic Messages: top-level `system` field, media first, results
+    /// linked by id.
+    pub const fn anthropic() -> Self {
+        Self {
+            system: SystemPlacement::Field,
+            part_order: PartOrder::MediaFirst,
+            tool_results: ToolResultLayout::ById,
+        }
+    }
+
+    /// OpenAI Chat Completions and compatible APIs: one leading system
+    /// message, one `tool` message per result.
+    pub const fn openai() -> Self {
+        Self {
+            system: SystemPlacement::LeadingMessage,
+            part_order: PartOrder::AsGiven,
+            tool_results: ToolResultLayout::MessagePerResult,
+        }
+    }
+
+    /// OpenAI Responses API: `instructions` field, results linked by id.
+    pub const fn openai_responses() -> Self {
+        Self {
+            system: SystemPlacement::Field,
+            part_order: PartOrder::AsGiven,
+            tool_results: ToolResultLayout::ById,
+        }
+    }
+
+    /// DeepSeek: one leading system message.
+    pub const fn deepseek() -> Self {
+        Self::openai()
+    }
+
+    /// Groq: one leading system message.
+    pub const fn groq() -> Self {
+        Self::openai()
+    }
+
+    /// Mistral: one leading system message, one `tool` message per result.
+    pub const fn mistral() -> Self {
+        Self::openai()
+    }
+
+    /// Ollama: one leading system message, which most chat templates require.
+    pub const fn ollama() -> Self {
+        Self::openai()
+    }
+
+    /// The layout for `provider`.
+    pub const fn for_provider(provider: ModelProvider) -> Self {
+        match provider {
+            ModelProvider::Gemini => Self::gemini(),
+            ModelProvider::Openai => Self::openai(),
+            ModelProvider::Anthropic => Self::anthropic(),
+            ModelProvider::Deepseek => Self::deepseek(),
+            ModelProvider::Groq => Self::groq(),
+            ModelProvider::Ollama => Self::ollama(),
+        }
+    }
+
+    /// Apply this layout to `contents`.
+    ///
+    /// The text of every `system` content is merged, in order and separated
+    /// by newlines, into one instruction. System contents without text are
+    /// dropped. Unless results are matched by name, tool calls and results
+    /// are linked by id with [`link_tool_call_ids`].
+    pub fn normalize(&self, contents: &[Content]) -> NormalizedContents {
+        let mut fragments = Vec::new();
+        let mut conversation = Vec::with_capacity(contents.len());
+
+        for content in contents {
+            if content.role == "system" {
+                let text = content.parts.iter().filter_map(Part::text).collect::<Vec<_>>();
+                let text = text.join("\n");
+                if !text.trim().is_empty() {
+                    fragments.push(text);
+                }
+                continue;
+            }
+            let mut content = content.clone();
+            if self.part_order == PartOrder::MediaFirst && content.role == "user" {
+                // Stable sort: media keeps its order, text keeps its order.
+                content.parts.sort_by_key(|part| !is_media(part));
+            }
+            conversation.push(content);
+        }
+
+        match self.tool_results {
+            ToolResultLayout::ByName => {}
+            ToolResultLayout::ById => link_tool_call_ids(&mut conversation),
+            ToolResultLayout::MessagePerResult => {
+                link_tool_call_ids(&mut conversation);
+                conversation = split_tool_results(conversation);
+            }
+        }
+
+        let instruction = (!fragments.is_empty()).then(|| fragments.join("\n"));
+        match self.system {
+            SystemPlacement::Field => {
+                NormalizedContents { system_instruction: instruction, contents: conversation }
+            }
+            SystemPlacement::LeadingMessage => {
+                if let Some(instruction) = instruction {
+                    conversation.insert(0, Content::new("system").with_text(instruction));
+                }
+                NormalizedContents { system_instruction: None, contents: conversation }
+            }
+        }
+    }
+}
+
+fn is_media(part: &Part) -> bool {
+    matches!(part, Part::InlineData { .. } | Part::FileData { .. })
+}
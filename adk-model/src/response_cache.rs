@@ -0,0 +1,380 @@
+//! Caching of full model responses for repeated deterministic requests.
+//!
+//! Tests and repeated workflows often send the exact same request at
+//! temperature 0 and pay for the same answer every time. [`CachingLlm`] wraps
+//! any model and keeps the responses it returned, keyed by
+//! [`request_fingerprint`], so an identical request is answered from the
+//! cache without calling the model.
+//!
+//! Only requests with `temperature` set to `0` are cached, since anything
+//! else is expected to vary between calls; [`CachingLlm::with_force`] caches
+//! every request regardless. A response is stored only after its stream ends
+//! without an error, an error code, or an interruption.
+//!
+//! The cache is pluggable through [`ResponseCache`]; the default is an
+//! [`InMemoryResponseCache`] that evicts the least recently used entry and can
+//! expire entries after a TTL.
+//!
+//! Token counts from [`Llm::count_tokens`] and [`Llm::count_tokens_breakdown`]
+//! are cached too, whatever the temperature, since counting the same request
+//! twice gives the same answer. They are kept in process, separately from the
+//! response cache.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_model::response_cache::{CachingLlm, InMemoryResponseCache};
+//! use std::sync::Arc;
+//!
+//! let model = Arc::new(
+//!     CachingLlm::new(Arc::new(gemini))
+//!         .with_cache(Arc::new(InMemoryResponseCache::new(1_000))),
+//! );
+//! // ... run the agent ...
+//! println!("{:?}", model.stats());
+//! ```
+
+use adk_core::{
+    Clock, Content, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result, TokenBreakdown,
+    canonical_value, system_clock,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Number of entries kept by the cache [`CachingLlm::new`] creates.
+pub const DEFAULT_MAX_ENTRIES: usize = 256;
+
+/// Storage for cached model responses.
+///
+/// A cache entry holds every response of one call in the order the model
+/// yielded them, so streamed calls replay chunk by chunk. Implementations
+/// should treat storage failures as misses rather than errors.
+#[async_trait]
+pub trait ResponseCache: Send + Sync {
+    /// Returns the responses stored under `key`, if any.
+    async fn get(&self, key: &str) -> Option<Vec<LlmResponse>>;
+
+    /// Stores `responses` under `key`, replacing any previous entry.
+    async fn put(&self, key: String, responses: Vec<LlmResponse>);
+}
+
+/// In-process [`ResponseCache`] that evicts the least recently used entry.
+pub struct InMemoryResponseCache {
+    entries: Mutex<LruEntries<Vec<LlmResponse>>>,
+    ttl: Option<Duration>,
+    clock: Arc<dyn Clock>,
+}
+
+struct LruEntries<V> {
+    map: HashMap<String, (V, DateTime<Utc>)>,
+    order: VecDeque<String>,
+    max_entries: usize,
+}
+
+impl<V: Clone> LruEntries<V> {
+    fn new(max_entries: usize) -> Self {
+        Self { map: HashMap::new(), order: VecDeque::new(), max_entries }
+    }
+
+    /// Returns the entry under `key` and marks it most recently used.
+    fn get(&mut self, key: &str) -> Option<(V, DateTime<Utc>)> {
+        let entry = self.map.get(key)?.clone();
+        self.order.retain(|k| k != key);
+        self.order.push_back(key.to_string());
+        Some(entry)
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.map.remove(key);
+        self.order.retain(|k| k != key);
+    }
+
+    /// Stores `value` under `key`, evicting the least recently used entry when
+    /// full.
+    fn insert(&mut self, key: String, value: V, stored_at: DateTime<Utc>) {
+        if self.max_entries == 0 {
+            return;
+        }
+        if self.map.contains_key(&key) {
+            self.order.retain(|k| k != &key);
+        } else if self.map.len() >= self.max_entries
+            && let Some(evicted) = self.order.pop_front()
+        {
+            self.map.remove(&evicted);
+        }
+        self.order.push_back(key.clone());
+        self.map.insert(key, (value, stored_at));
+    }
+}
+
+impl InMemoryResponseCache {
+    /// Create a cache holding at most `max_entries` calls.
+    pub fn new(max_entries: usize) -> Self {
+        Self { entries: Mutex::new(LruEntries::new(max_entries)), ttl: None, clock: system_clock() }
+    }
+
+    /// Treat entries older than `ttl` as misses.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Read the time from `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the number of cached calls.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).map.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.map.clear();
+        entries.order.clear();
+    }
+}
+
+impl Default for InMemoryResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+#[async_trait]
+impl ResponseCache for InMemoryResponseCache {
+    async fn get(&self, key: &str) -> Option<Vec<LlmResponse>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (responses, stored_at) = entries.get(key)?;
+        if let Some(ttl) = self.ttl
+            && (self.clock.now() - stored_at).to_std().is_ok_and(|age| age >= ttl)
+        {
+            entries.remove(key);
+            return None;
+        }
+        Some(responses)
+    }
+
+    async fn put(&self, key: String, responses: Vec<LlmResponse>) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key, responses, self.clock.now());
+    }
+}
+
+/// Hit and miss counts of a [`CachingLlm`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Calls answered from the cache.
+    pub hits: u64,
+    /// Cacheable calls that went to the model.
+    pub misses: u64,
+    /// Calls that were not cacheable and went straight to the model.
+    pub bypassed: u64,
+}
+
+impl CacheStats {
+    /// Fraction of cacheable calls answered from the cache, or `0.0` before
+    /// the first one.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+/// Wraps a model so identical deterministic requests are answered from a cache.
+pub struct CachingLlm {
+    inner: Arc<dyn Llm>,
+    cache: Arc<dyn ResponseCache>,
+    force: bool,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypassed: AtomicU64,
+    token_counts: Mutex<LruEntries<usize>>,
+    token_breakdowns: Mutex<LruEntries<TokenBreakdown>>,
+}
+
+impl CachingLlm {
+    /// Wrap `inner` with an [`InMemoryResponseCache`] of
+    /// [`DEFAULT_MAX_ENTRIES`] entries.
+    pub fn new(inner: Arc<dyn Llm>) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(InMemoryResponseCache::default()),
+            force: false,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            bypassed: AtomicU64::new(0),
+            token_counts: Mutex::new(LruEntries::new(DEFAULT_MAX_ENTRIES)),
+            token_breakdowns: Mutex::new(LruEntries::new(DEFAULT_MAX_ENTRIES)),
+        }
+    }
+
+    /// Store responses in `cache` instead of the default in-memory cache.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cache every request, not only those with temperature 0.
+    #[must_use]
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Returns the hit and miss counts so far.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            bypassed: self.bypassed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns `true` if responses to `request` may be cached.
+    pub fn is_cacheable(&self, request: &LlmRequest) -> bool {
+        self.force || request.config.as_ref().and_then(|config| config.temperature) == Some(0.0)
+    }
+}
+
+#[async_trait]
+impl Llm for CachingLlm {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schema_adapter(&self) -> &dyn adk_core::SchemaAdapter {
+        self.inner.schema_adapter()
+    }
+
+    fn max_tools(&self) -> Option<usize> {
+        self.inner.max_tools()
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.inner.supports_response_schema()
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        let key = request_fingerprint(req);
+        if let Some((count, _)) = lock(&self.token_counts).get(&key) {
+            return Ok(count);
+        }
+        let count = self.inner.count_tokens(req).await?;
+        lock(&self.token_counts).insert(key, count, Utc::now());
+        Ok(count)
+    }
+
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<TokenBreakdown> {
+        let key = request_fingerprint(req);
+        if let Some((breakdown, _)) = lock(&self.token_breakdowns).get(&key) {
+            return Ok(breakdown);
+        }
+        let breakdown = self.inner.count_tokens_breakdown(req).await?;
+        lock(&self.token_breakdowns).insert(key, breakdown, Utc::now());
+        Ok(breakdown)
+    }
+
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        if !self.is_cacheable(&request) {
+            self.bypassed.fetch_add(1, Ordering::Relaxed);
+            return self.inner.generate_content(request, stream).await;
+        }
+
+        // Streamed and single-response calls yield different chunks.
+        let key = format!("{}:{}", request_fingerprint(&request), stream);
+        if let Some(responses) = self.cache.get(&key).await {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            adk_telemetry::debug!(model = %self.inner.name(), "response cache hit");
+            return Ok(Box::pin(futures::stream::iter(responses.into_iter().map(Ok))));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let responses = self.inner.generate_content(request, stream).await?;
+        Ok(record_stream(responses, self.cache.clone(), key))
+    }
+}
+
+fn lock<V>(entries: &Mutex<LruEntries<V>>) -> std::sync::MutexGuard<'_, LruEntries<V>> {
+    entries.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Pass `responses` through and store them under `key` once the stream ends
+/// cleanly.
+fn record_stream(
+    mut responses: LlmResponseStream,
+    cache: Arc<dyn ResponseCache>,
+    key: String,
+) -> LlmResponseStream {
+    Box::pin(async_stream::stream! {
+        let mut received = Vec::new();
+        let mut cacheable = true;
+        while let Some(item) = responses.next().await {
+            match &item {
+                Ok(response) if cacheable => {
+                    if response.error_code.is_some() || response.interrupted {
+                        cacheable = false;
+                        received.clear();
+                    } else {
+                        received.push(response.clone());
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    cacheable = false;
+                    received.clear();
+                }
+            }
+            yield item;
+        }
+        if cacheable && !received.is_empty() {
+            cache.put(key, received).await;
+        }
+    })
+}
+
+/// Returns the hex SHA-256 of everything in `request` that affects the
+/// response: the model, the canonical contents, the generation config, the
+/// tool declarations, and the previous response id.
+///
+/// Requests that differ only in JSON key order, number formatting, or tool
+/// call ids get the same fingerprint; see [`adk_core::fingerprint`].
+pub fn request_fingerprint(request: &LlmRequest) -> String {
+    let config = request
+        .config
+        .as_ref()
+        .and_then(|config| serde_json::to_value(config).ok())
+        .unwrap_or(Value::Null);
+    let tools: Map<String, Value> =
+        request.tools.iter().map(|(name, tool)| (name.clone(), tool.clone())).collect();
+    let canonical = canonical_value(&json!({
+        "model": request.model,
+        "contents": request.contents.iter().map(Content::canonical_value).collect::<Vec<_>>(),
+        "config": config,
+        "tools": tools,
+        "previous_response_id": request.previous_response_id,
+    }));
+    hex::encode(Sha256::digest(canonical.to_string().as_bytes()))
+}
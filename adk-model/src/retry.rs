@@ -1,8 +1,21 @@
-use adk_core::{AdkError, Result};
+use adk_core::{AdkError, ErrorCategory, LlmResponseStream, Result};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use std::{future::Future, time::Duration};
 
+/// Key in [`ErrorDetails::metadata`](adk_core::ErrorDetails::metadata) holding
+/// how many times a request was attempted before the error surfaced.
+pub const ATTEMPTS_METADATA_KEY: &str = "attempts";
+
 /// Configuration for automatic retry with exponential backoff.
-#[derive(Clone, Debug)]
+///
+/// Every provider client takes one, either on its config struct or through
+/// `with_retry_config`. Unary calls are retried as a whole; streaming calls are
+/// retried until the stream yields its first chunk. An error after that is
+/// returned as-is, with the number of attempts made recorded under
+/// [`ATTEMPTS_METADATA_KEY`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RetryConfig {
     /// Whether retries are enabled.
     pub enabled: bool,
@@ -14,6 +27,8 @@ pub struct RetryConfig {
     pub max_delay: Duration,
     /// Multiplier applied to the delay after each retry.
     pub backoff_multiplier: f32,
+    /// Which kinds of transient failure are retried.
+    pub retry_on: StatusClasses,
 }
 
 impl Default for RetryConfig {
@@ -24,6 +39,7 @@ impl Default for RetryConfig {
             initial_delay: Duration::from_millis(250),
             max_delay: Duration::from_secs(5),
             backoff_multiplier: 2.0,
+            retry_on: StatusClasses::ALL,
         }
     }
 }
@@ -62,6 +78,73 @@ impl RetryConfig {
         self.backoff_multiplier = backoff_multiplier;
         self
     }
+
+    /// Set which kinds of transient failure are retried.
+    #[must_use]
+    pub fn with_retry_on(mut self, retry_on: StatusClasses) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+}
+
+/// The kinds of transient failure a [`RetryConfig`] retries.
+///
+/// These narrow what the client would otherwise retry: an error outside the
+/// enabled classes is returned on the first attempt, and errors that are not
+/// transient, such as a 400, are never retried.
+///
+/// ```rust
+/// use adk_model::retry::{RetryConfig, StatusClasses};
+///
+/// // Wait out rate limits, but fail fast when the provider is down.
+/// let retry = RetryConfig::default()
+///     .with_retry_on(StatusClasses { server_error: false, ..StatusClasses::ALL });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusClasses {
+    /// HTTP 429 and other rate limit or quota errors.
+    pub rate_limited: bool,
+    /// HTTP 408 and requests that timed out.
+    pub timeout: bool,
+    /// HTTP 5xx responses, including 529 (overloaded).
+    pub server_error: bool,
+    /// Requests that failed before any response arrived, such as a refused or
+    /// reset connection.
+    pub connection: bool,
+}
+
+impl StatusClasses {
+    /// Every class of transient failure.
+    pub const ALL: Self =
+        Self { rate_limited: true, timeout: true, server_error: true, connection: true };
+
+    /// No failure at all; equivalent to disabling retries.
+    pub const NONE: Self =
+        Self { rate_limited: false, timeout: false, server_error: false, connection: false };
+
+    /// Returns `true` if `error` falls in one of the enabled classes.
+    #[must_use]
+    pub fn covers(&self, error: &AdkError) -> bool {
+        match error.upstream_status() {
+            Some(429) => self.rate_limited,
+            Some(408) => self.timeout,
+            Some(500..=599) => self.server_error,
+            Some(_) => false,
+            None => match error.category {
+                ErrorCategory::RateLimited => self.rate_limited,
+                ErrorCategory::Timeout => self.timeout,
+                ErrorCategory::Unavailable => self.connection,
+                _ => self.server_error,
+            },
+        }
+    }
+}
+
+impl Default for StatusClasses {
+    fn default() -> Self {
+        Self::ALL
+    }
 }
 
 /// Returns `true` if the HTTP status code indicates a transient error worth retrying.
@@ -70,6 +153,118 @@ pub fn is_retryable_status_code(status_code: u16) -> bool {
     matches!(status_code, 408 | 429 | 500 | 502 | 503 | 504 | 529)
 }
 
+/// Parses a `Retry-After` header value given in seconds.
+///
+/// HTTP-date values are not supported and yield `None`.
+///
+/// ```rust
+/// use adk_model::retry::parse_retry_after;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_retry_after("20"), Some(Duration::from_secs(20)));
+/// assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+/// ```
+#[must_use]
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let secs: f64 = value.trim().parse().ok()?;
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Parses an OpenAI-style rate limit reset value such as `6m0s`, `1.5s` or
+/// `250ms`. A bare number is read as seconds.
+///
+/// ```rust
+/// use adk_model::retry::parse_rate_limit_reset;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_rate_limit_reset("6m0s"), Some(Duration::from_secs(360)));
+/// assert_eq!(parse_rate_limit_reset("250ms"), Some(Duration::from_millis(250)));
+/// assert_eq!(parse_rate_limit_reset("soon"), None);
+/// ```
+#[must_use]
+pub fn parse_rate_limit_reset(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Some(delay) = parse_retry_after(value) {
+        return Some(delay);
+    }
+
+    let mut secs = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let unit_start = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let amount: f64 = rest[..unit_start].parse().ok()?;
+        let unit_len = rest[unit_start..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len() - unit_start);
+        let scale = match &rest[unit_start..unit_start + unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        secs += amount * scale;
+        rest = &rest[unit_start + unit_len..];
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Reads how long a provider asks callers to wait from the headers of a
+/// failed response. `header` looks up a header value by lowercase name.
+///
+/// `retry-after-ms` and `retry-after` are used when present. Otherwise, if an
+/// `x-ratelimit-remaining-requests` or `x-ratelimit-remaining-tokens` header
+/// (sent by OpenAI, Groq and other OpenAI-compatible APIs) reports an
+/// exhausted limit, the delay is the matching `x-ratelimit-reset-*` value.
+///
+/// ```rust
+/// use adk_model::retry::retry_after_from_headers;
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+///
+/// let headers = HashMap::from([
+///     ("x-ratelimit-remaining-requests", "12"),
+///     ("x-ratelimit-reset-requests", "2s"),
+///     ("x-ratelimit-remaining-tokens", "0"),
+///     ("x-ratelimit-reset-tokens", "1m30s"),
+/// ]);
+/// let delay = retry_after_from_headers(|name| headers.get(name).copied());
+/// assert_eq!(delay, Some(Duration::from_secs(90)));
+/// ```
+#[must_use]
+pub fn retry_after_from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Option<Duration> {
+    if let Some(millis) = header("retry-after-ms").and_then(|v| v.trim().parse::<f64>().ok()) {
+        return Duration::try_from_secs_f64(millis / 1000.0).ok();
+    }
+    if let Some(delay) = header("retry-after").and_then(parse_retry_after) {
+        return Some(delay);
+    }
+    ["requests", "tokens"]
+        .into_iter()
+        .filter(|limit| {
+            header(&format!("x-ratelimit-remaining-{limit}")).map(str::trim) == Some("0")
+        })
+        .filter_map(|limit| {
+            header(&format!("x-ratelimit-reset-{limit}")).and_then(parse_rate_limit_reset)
+        })
+        .max()
+}
+
+/// Returns how many times the request behind `error` was attempted, if it
+/// went through a retry loop.
+#[must_use]
+pub fn attempt_count(error: &AdkError) -> Option<u32> {
+    error.details.metadata.get(ATTEMPTS_METADATA_KEY)?.as_u64().map(|n| n as u32)
+}
+
+fn with_attempt_count(mut error: AdkError, attempts: u32) -> AdkError {
+    error.details.metadata.insert(ATTEMPTS_METADATA_KEY.to_string(), attempts.into());
+    error
+}
+
 /// Returns `true` if the error message contains patterns indicating a transient failure.
 #[must_use]
 pub fn is_retryable_error_message(message: &str) -> bool {
@@ -172,43 +367,129 @@ where
         return operation().await;
     }
 
-    let mut attempt: u32 = 0;
-    let mut delay = retry_config.initial_delay;
-
-    // If the server provided a retry-after hint, use it for the first retry delay.
-    let server_delay = server_hint.and_then(|h| h.retry_after);
-
+    let mut backoff = Backoff::new(retry_config, server_hint);
     loop {
         match operation().await {
             Ok(value) => return Ok(value),
-            Err(error) if attempt < retry_config.max_retries && classify_error(&error) => {
-                attempt += 1;
-
-                // Priority: 1) structured retry_after from AdkError, 2) server hint, 3) backoff
-                let error_retry_after = error.retry.retry_after();
-                let effective_delay = if let Some(d) = error_retry_after {
-                    d
-                } else if attempt == 1 {
-                    server_delay.unwrap_or(delay)
-                } else {
-                    delay
-                };
-
-                adk_telemetry::warn!(
-                    attempt = attempt,
-                    max_retries = retry_config.max_retries,
-                    delay_ms = effective_delay.as_millis(),
-                    error = %error,
-                    "Provider request failed with retryable error; retrying"
-                );
-                tokio::time::sleep(effective_delay).await;
-                delay = next_retry_delay(delay, retry_config);
+            Err(error) => {
+                if !backoff.retry(&error, &classify_error).await {
+                    return Err(with_attempt_count(error, backoff.attempts()));
+                }
             }
-            Err(error) => return Err(error),
         }
     }
 }
 
+/// Opens a response stream with retry on transient errors.
+///
+/// `open` is retried when it fails, and also when the stream it returns fails
+/// before producing its first chunk, so callers never see a partial answer
+/// from a failed attempt. Once a chunk has been yielded, later errors are
+/// passed through with the number of attempts made attached (see
+/// [`attempt_count`]). A final error from the first chunk is yielded as the
+/// stream's first item, while a final error from `open` is returned directly.
+pub async fn execute_stream_with_retry<Op, Fut, Classify>(
+    retry_config: &RetryConfig,
+    classify_error: Classify,
+    mut open: Op,
+) -> Result<LlmResponseStream>
+where
+    Op: FnMut() -> Fut,
+    Fut: Future<Output = Result<LlmResponseStream>>,
+    Classify: Fn(&AdkError) -> bool,
+{
+    if !retry_config.enabled {
+        return open().await;
+    }
+
+    let mut backoff = Backoff::new(retry_config, None);
+    loop {
+        let mut stream = match open().await {
+            Ok(stream) => stream,
+            Err(error) => {
+                if backoff.retry(&error, &classify_error).await {
+                    continue;
+                }
+                return Err(with_attempt_count(error, backoff.attempts()));
+            }
+        };
+        let first = match stream.next().await {
+            Some(Err(error)) => {
+                if backoff.retry(&error, &classify_error).await {
+                    continue;
+                }
+                Some(Err(with_attempt_count(error, backoff.attempts())))
+            }
+            first => first,
+        };
+
+        let attempts = backoff.attempts();
+        let rest =
+            stream.map(move |item| item.map_err(|error| with_attempt_count(error, attempts)));
+        return Ok(Box::pin(futures::stream::iter(first).chain(rest)));
+    }
+}
+
+/// Delay bookkeeping shared by the retry loops.
+struct Backoff<'a> {
+    config: &'a RetryConfig,
+    retries: u32,
+    delay: Duration,
+    server_delay: Option<Duration>,
+}
+
+impl<'a> Backoff<'a> {
+    fn new(config: &'a RetryConfig, server_hint: Option<&ServerRetryHint>) -> Self {
+        Self {
+            config,
+            retries: 0,
+            delay: config.initial_delay,
+            server_delay: server_hint.and_then(|hint| hint.retry_after),
+        }
+    }
+
+    /// Attempts made so far, including the one in flight.
+    fn attempts(&self) -> u32 {
+        self.retries + 1
+    }
+
+    /// Waits before the next attempt and returns `true` if `error` is worth
+    /// retrying and retries remain.
+    async fn retry(
+        &mut self,
+        error: &AdkError,
+        classify_error: &impl Fn(&AdkError) -> bool,
+    ) -> bool {
+        if self.retries >= self.config.max_retries
+            || !classify_error(error)
+            || !self.config.retry_on.covers(error)
+        {
+            return false;
+        }
+        self.retries += 1;
+
+        // Priority: 1) structured retry_after from AdkError, 2) server hint, 3) backoff
+        let effective_delay = if let Some(d) = error.retry.retry_after() {
+            d
+        } else if self.retries == 1 {
+            self.server_delay.unwrap_or(self.delay)
+        } else {
+            self.delay
+        };
+
+        adk_telemetry::warn!(
+            attempt = self.retries,
+            max_retries = self.config.max_retries,
+            delay_ms = effective_delay.as_millis(),
+            error = %error,
+            "Provider request failed with retryable error; retrying"
+        );
+        tokio::time::sleep(effective_delay).await;
+        self.delay = next_retry_delay(self.delay, self.config);
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,6 +610,99 @@ mod tests {
         assert_eq!(attempts.load(Ordering::SeqCst), 2);
     }
 
+    #[tokio::test]
+    async fn retry_on_excludes_classes_that_are_not_enabled() {
+        let retry_config = RetryConfig::default()
+            .with_max_retries(3)
+            .with_initial_delay(Duration::ZERO)
+            .with_retry_on(StatusClasses { rate_limited: false, ..StatusClasses::ALL });
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let error = execute_with_retry(&retry_config, is_retryable_model_error, || {
+            let attempts = Arc::clone(&attempts);
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(AdkError::from_http_status(
+                    adk_core::ErrorComponent::Model,
+                    "model.api_error",
+                    429,
+                    "slow down",
+                ))
+            }
+        })
+        .await
+        .expect_err("rate limits are not retried");
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(attempt_count(&error), Some(1));
+    }
+
+    fn chunk(text: &str) -> Result<adk_core::LlmResponse> {
+        Ok(adk_core::LlmResponse::new(adk_core::Content::new("model").with_text(text)))
+    }
+
+    #[tokio::test]
+    async fn stream_failing_before_its_first_chunk_is_reopened() {
+        let retry_config = RetryConfig::default().with_initial_delay(Duration::ZERO);
+        let opened = Arc::new(AtomicU32::new(0));
+
+        let stream = execute_stream_with_retry(&retry_config, is_retryable_model_error, || {
+            let attempt = opened.fetch_add(1, Ordering::SeqCst);
+            let items = if attempt == 0 {
+                vec![Err(AdkError::model("HTTP 503 unavailable"))]
+            } else {
+                vec![chunk("a"), chunk("b")]
+            };
+            std::future::ready(Ok(Box::pin(futures::stream::iter(items)) as LlmResponseStream))
+        })
+        .await
+        .unwrap();
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|item| item.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn stream_failing_after_a_chunk_reports_the_attempt_count() {
+        let retry_config = RetryConfig::default().with_initial_delay(Duration::ZERO);
+        let opened = Arc::new(AtomicU32::new(0));
+
+        let stream = execute_stream_with_retry(&retry_config, is_retryable_model_error, || {
+            let attempt = opened.fetch_add(1, Ordering::SeqCst);
+            let items = if attempt == 0 {
+                vec![Err(AdkError::model("HTTP 503 unavailable"))]
+            } else {
+                vec![chunk("a"), Err(AdkError::model("HTTP 503 connection reset"))]
+            };
+            std::future::ready(Ok(Box::pin(futures::stream::iter(items)) as LlmResponseStream))
+        })
+        .await
+        .unwrap();
+
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(opened.load(Ordering::SeqCst), 2, "mid-stream errors are not replayed");
+        let error = items[1].as_ref().expect_err("the mid-stream error is passed through");
+        assert_eq!(attempt_count(error), Some(2));
+    }
+
+    #[test]
+    fn rate_limit_headers_only_apply_to_an_exhausted_limit() {
+        let headers = std::collections::HashMap::from([
+            ("x-ratelimit-remaining-requests", "3"),
+            ("x-ratelimit-reset-requests", "20s"),
+        ]);
+        assert_eq!(retry_after_from_headers(|name| headers.get(name).copied()), None);
+
+        let headers =
+            std::collections::HashMap::from([("retry-after-ms", "1500"), ("retry-after", "30")]);
+        assert_eq!(
+            retry_after_from_headers(|name| headers.get(name).copied()),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
     /// Requirement 5.2: HTTP 529 (overloaded) is retried end-to-end.
     #[tokio::test]
     async fn status_529_is_retried_end_to_end() {
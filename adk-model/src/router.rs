@@ -0,0 +1,364 @@
+//! Per-request routing between several models.
+//!
+//! [`RouterLlm`] holds named routes (for example a cheap `fast` model and an
+//! expensive `smart` one) and asks a [`RoutingPolicy`] which to use for each
+//! request. Because it implements [`Llm`], it drops into `LlmAgent` like any
+//! other model.
+//!
+//! Policies are plain async closures returning a route name. Ready-made ones
+//! cover the common cases:
+//!
+//! - [`by_length`] — short prompts to one route, long prompts to another
+//! - [`by_keywords`] — route when the prompt mentions any keyword
+//! - [`classifier`] — ask a (cheap) model which route fits
+//!
+//! The chosen route is recorded on every response under
+//! `provider_metadata["router"]` and is available from
+//! [`RouterLlm::last_route`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_model::router::{RouterLlm, by_length};
+//! use std::sync::Arc;
+//!
+//! let model = Arc::new(
+//!     RouterLlm::new("router")
+//!         .with_route("fast", Arc::new(flash))
+//!         .with_route("smart", Arc::new(pro))
+//!         .with_policy(by_length(400, "fast", "smart")),
+//! );
+//! let agent = LlmAgentBuilder::new("assistant").model(model).build()?;
+//! ```
+
+use adk_core::{AdkError, Content, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Map, Value, json};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Decides which route handles a request, by name.
+///
+/// Returning an unknown route name or an error falls back to the router's
+/// default route.
+pub type RoutingPolicy =
+    Arc<dyn Fn(LlmRequest) -> Pin<Box<dyn Future<Output = Result<String>> + Send>> + Send + Sync>;
+
+/// Wrap a synchronous function as a [`RoutingPolicy`].
+pub fn policy_fn<F>(f: F) -> RoutingPolicy
+where
+    F: Fn(&LlmRequest) -> String + Send + Sync + 'static,
+{
+    Arc::new(move |request| {
+        let route = f(&request);
+        Box::pin(async move { Ok(route) })
+    })
+}
+
+/// Route prompts of at most `max_chars` characters to `short_route` and longer
+/// ones to `long_route`.
+///
+/// Only the text of the user message that started the invocation is
+/// measured, so tool-call round trips and prompts the agent adds within one
+/// turn stay on the same route.
+pub fn by_length(
+    max_chars: usize,
+    short_route: impl Into<String>,
+    long_route: impl Into<String>,
+) -> RoutingPolicy {
+    let short_route = short_route.into();
+    let long_route = long_route.into();
+    policy_fn(move |request| {
+        if user_text(request).chars().count() <= max_chars {
+            short_route.clone()
+        } else {
+            long_route.clone()
+        }
+    })
+}
+
+/// Route to `matched_route` when the user message contains any of
+/// `keywords` (case-insensitive), otherwise to `default_route`.
+pub fn by_keywords<I, S>(
+    keywords: I,
+    matched_route: impl Into<String>,
+    default_route: impl Into<String>,
+) -> RoutingPolicy
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let keywords: Vec<String> = keywords.into_iter().map(|k| k.into().to_lowercase()).collect();
+    let matched_route = matched_route.into();
+    let default_route = default_route.into();
+    policy_fn(move |request| {
+        let text = user_text(request).to_lowercase();
+        if keywords.iter().any(|keyword| text.contains(keyword.as_str())) {
+            matched_route.clone()
+        } else {
+            default_route.clone()
+        }
+    })
+}
+
+/// Ask `model` to pick a route for the user message.
+///
+/// `routes` pairs each route name with a short description of the requests it
+/// should handle. The model is expected to answer with one route name; any
+/// other answer falls back to the router's default route.
+pub fn classifier(model: Arc<dyn Llm>, routes: &[(&str, &str)]) -> RoutingPolicy {
+    let options: String =
+        routes.iter().map(|(name, description)| format!("- {name}: {description}\n")).collect();
+    let names: Vec<String> = routes.iter().map(|(name, _)| name.to_string()).collect();
+
+    Arc::new(move |request| {
+        let model = model.clone();
+        let names = names.clone();
+        let prompt = format!(
+            "Choose the option that best fits the user message. Reply with the option name only.\n\nOptions:\n{options}\nUser message:\n{}",
+            user_text(&request)
+        );
+        Box::pin(async move {
+            let request =
+                LlmRequest::new(model.name(), vec![Content::new("user").with_text(prompt)]);
+            let mut stream = model.generate_content(request, false).await?;
+            let mut answer = String::new();
+            while let Some(response) = stream.next().await {
+                if let Some(text) = response?.answer_text() {
+                    answer.push_str(&text);
+                }
+            }
+            let answer = answer.trim().trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+            Ok(names.iter().find(|name| name.to_lowercase() == answer).cloned().unwrap_or(answer))
+        })
+    })
+}
+
+/// A model that delegates each request to one of several named models.
+///
+/// The first route added is the default: it is used when no policy is set,
+/// or when the policy fails or names an unknown route.
+pub struct RouterLlm {
+    name: String,
+    routes: Vec<(String, Arc<dyn Llm>)>,
+    policy: Option<RoutingPolicy>,
+    last_route: Mutex<Option<String>>,
+}
+
+impl RouterLlm {
+    /// Create a router with no routes; add them with [`with_route`](Self::with_route).
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), routes: Vec::new(), policy: None, last_route: Mutex::new(None) }
+    }
+
+    /// Add a named route. Re-using a name replaces that route's model.
+    #[must_use]
+    pub fn with_route(mut self, name: impl Into<String>, model: Arc<dyn Llm>) -> Self {
+        let name = name.into();
+        match self.routes.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(route) => route.1 = model,
+            None => self.routes.push((name, model)),
+        }
+        self
+    }
+
+    /// Set the policy that picks a route for each request.
+    #[must_use]
+    pub fn with_policy(mut self, policy: RoutingPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Names of the configured routes, default first.
+    pub fn routes(&self) -> Vec<&str> {
+        self.routes.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// The route chosen for the most recent request, if any.
+    ///
+    /// With concurrent requests this is whichever finished routing last; read
+    /// `provider_metadata["router"]["route"]` on the response to know which
+    /// model produced it.
+    pub fn last_route(&self) -> Option<String> {
+        self.last_route.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Pick the route for `request` without calling the model.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no routes are configured.
+    pub async fn select(&self, request: &LlmRequest) -> Result<(String, Arc<dyn Llm>)> {
+        let (default_name, default_model) = self.routes.first().ok_or_else(|| {
+            AdkError::model(format!("router '{}' has no routes configured", self.name))
+        })?;
+        let Some(policy) = &self.policy else {
+            return Ok((default_name.clone(), default_model.clone()));
+        };
+
+        match policy(request.clone()).await {
+            Ok(chosen) => {
+                if let Some((name, model)) = self.routes.iter().find(|(name, _)| *name == chosen) {
+                    return Ok((name.clone(), model.clone()));
+                }
+                adk_telemetry::warn!(
+                    router = %self.name,
+                    route = %chosen,
+                    fallback = %default_name,
+                    "routing policy chose an unknown route"
+                );
+            }
+            Err(error) => {
+                adk_telemetry::warn!(
+                    router = %self.name,
+                    error = %error,
+                    fallback = %default_name,
+                    "routing policy failed"
+                );
+            }
+        }
+        Ok((default_name.clone(), default_model.clone()))
+    }
+
+    /// Select the route for `request`, point the request at its model, and
+    /// return the metadata recorded on its responses.
+    async fn route(&self, request: &mut LlmRequest) -> Result<(Value, Arc<dyn Llm>)> {
+        let (route, model) = self.select(request).await?;
+        adk_telemetry::debug!(router = %self.name, route = %route, model = %model.name(), "routed request");
+        *self.last_route.lock().unwrap_or_else(|e| e.into_inner()) = Some(route.clone());
+
+        request.model = model.name().to_string();
+        Ok((json!({ "route": route, "model": model.name() }), model))
+    }
+}
+
+#[async_trait]
+impl Llm for RouterLlm {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The default route's adapter, since the route is only known per request.
+    fn schema_adapter(&self) -> &dyn adk_core::SchemaAdapter {
+        match self.routes.first() {
+            Some((_, model)) => model.schema_adapter(),
+            None => &adk_core::GenericSchemaAdapter,
+        }
+    }
+
+    /// The smallest limit among the routes, since any of them may be chosen.
+    fn max_tools(&self) -> Option<usize> {
+        self.routes.iter().filter_map(|(_, model)| model.max_tools()).min()
+    }
+
+    /// `true` only if every route accepts a response schema.
+    fn supports_response_schema(&self) -> bool {
+        self.routes.iter().all(|(_, model)| model.supports_response_schema())
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        let (_, model) = self.select(req).await?;
+        let request = LlmRequest { model: model.name().to_string(), ..req.clone() };
+        model.count_tokens(&request).await
+    }
+
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<adk_core::TokenBreakdown> {
+        let (_, model) = self.select(req).await?;
+        let request = LlmRequest { model: model.name().to_string(), ..req.clone() };
+        model.count_tokens_breakdown(&request).await
+    }
+
+    /// Routes each request on its own and submits the requests sent to the
+    /// same route as one batch.
+    async fn generate_batch(
+        &self,
+        requests: Vec<LlmRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<LlmResponse>> {
+        let mut results: Vec<Option<Result<LlmResponse>>> = Vec::new();
+        results.resize_with(requests.len(), || None);
+        let mut batches: Vec<(Value, Arc<dyn Llm>, Vec<usize>, Vec<LlmRequest>)> = Vec::new();
+        for (index, mut request) in requests.into_iter().enumerate() {
+            match self.route(&mut request).await {
+                Ok((routed, model)) => {
+                    match batches.iter_mut().find(|(existing, ..)| *existing == routed) {
+                        Some((_, _, indices, batch)) => {
+                            indices.push(index);
+                            batch.push(request);
+                        }
+                        None => batches.push((routed, model, vec![index], vec![request])),
+                    }
+                }
+                Err(error) => results[index] = Some(Err(error)),
+            }
+        }
+
+        for (routed, model, indices, batch) in batches {
+            let responses = model.generate_batch(batch, concurrency).await;
+            for (index, response) in indices.into_iter().zip(responses) {
+                results[index] = Some(response.map(|mut response| {
+                    annotate(&mut response, routed.clone());
+                    response
+                }));
+            }
+        }
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| Err(AdkError::model("routed model returned no response")))
+            })
+            .collect()
+    }
+
+    async fn generate_content(
+        &self,
+        mut request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        let (routed, model) = self.route(&mut request).await?;
+        let responses = model.generate_content(request, stream).await?;
+        Ok(Box::pin(responses.map(move |response| {
+            response.map(|mut response| {
+                annotate(&mut response, routed.clone());
+                response
+            })
+        })))
+    }
+}
+
+/// Record the chosen route under `provider_metadata["router"]`.
+fn annotate(response: &mut LlmResponse, routed: Value) {
+    let metadata = response.provider_metadata.get_or_insert_with(|| Value::Object(Map::new()));
+    // Leave non-object metadata from the provider untouched.
+    if let Some(metadata) = metadata.as_object_mut() {
+        metadata.insert("router".to_string(), routed);
+    }
+}
+
+/// Text of the user message that started the invocation.
+///
+/// Agents record it with [`adk_core::with_invocation_user_content`], so
+/// continuation and schema-correction prompts added mid-turn do not change the
+/// route. Outside an agent this is the latest user message, skipping
+/// tool-result turns without text.
+fn user_text(request: &LlmRequest) -> String {
+    adk_core::invocation_user_content()
+        .map(|content| text_of(&content))
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| {
+            request
+                .contents
+                .iter()
+                .rev()
+                .filter(|content| content.role == "user")
+                .map(text_of)
+                .find(|text| !text.is_empty())
+                .unwrap_or_default()
+        })
+}
+
+fn text_of(content: &Content) -> String {
+    content.parts.iter().filter_map(|part| part.text()).collect()
+}
@@ -0,0 +1,226 @@
+//! Recovery for streaming responses whose connection drops mid-generation.
+//!
+//! Without recovery, an SSE connection that drops halfway through a long
+//! generation surfaces as an opaque error and the consumer loses track of what
+//! was already streamed. [`StreamRecoveryLlm`] wraps any model and, once at
+//! least one chunk has arrived, either:
+//!
+//! - ends the stream cleanly with a [`STREAM_INTERRUPTED`] marker response, so
+//!   the partial text already yielded is kept instead of being discarded
+//!   ([`StreamRecovery::SurfacePartial`], the default), or
+//! - re-issues the request with the partial text appended as a trailing model
+//!   turn and keeps streaming the continuation ([`StreamRecovery::Resume`]),
+//!   falling back to the marker when the attempts run out.
+//!
+//! Resumption relies on the provider continuing a trailing model message as a
+//! prefix (assistant prefill), which Anthropic, DeepSeek, and Ollama support.
+//! Do not enable it for providers that reject or ignore such a message.
+//!
+//! Errors raised before the first chunk are passed through unchanged so the
+//! provider's own retry logic keeps handling them.
+
+use adk_core::{
+    AdkError, Content, FinishReason, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// `error_code` of the marker response emitted when a stream is cut off.
+pub const STREAM_INTERRUPTED: &str = "STREAM_INTERRUPTED";
+
+/// What to do when a stream fails after some content was already received.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamRecovery {
+    /// Propagate the error as-is.
+    Fail,
+    /// End the stream with a [`STREAM_INTERRUPTED`] marker instead of an error.
+    #[default]
+    SurfacePartial,
+    /// Continue the generation from the partial text, up to `max_attempts`
+    /// times, then fall back to [`SurfacePartial`](Self::SurfacePartial).
+    Resume {
+        /// Maximum number of continuation requests per call.
+        max_attempts: u32,
+    },
+}
+
+/// Wraps a model so dropped streams keep the content received so far.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_model::stream_recovery::{StreamRecovery, StreamRecoveryLlm};
+/// use std::sync::Arc;
+///
+/// let model = Arc::new(
+///     StreamRecoveryLlm::new(Arc::new(anthropic))
+///         .with_recovery(StreamRecovery::Resume { max_attempts: 2 }),
+/// );
+/// ```
+pub struct StreamRecoveryLlm {
+    inner: Arc<dyn Llm>,
+    recovery: StreamRecovery,
+}
+
+impl StreamRecoveryLlm {
+    /// Wrap `inner` with [`StreamRecovery::SurfacePartial`].
+    pub fn new(inner: Arc<dyn Llm>) -> Self {
+        Self { inner, recovery: StreamRecovery::default() }
+    }
+
+    /// Set how interrupted streams are handled.
+    #[must_use]
+    pub fn with_recovery(mut self, recovery: StreamRecovery) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Returns the configured recovery behavior.
+    pub fn recovery(&self) -> StreamRecovery {
+        self.recovery
+    }
+}
+
+#[async_trait]
+impl Llm for StreamRecoveryLlm {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schema_adapter(&self) -> &dyn adk_core::SchemaAdapter {
+        self.inner.schema_adapter()
+    }
+
+    fn max_tools(&self) -> Option<usize> {
+        self.inner.max_tools()
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        self.inner.supports_response_schema()
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        self.inner.count_tokens(req).await
+    }
+
+    async fn count_tokens_breakdown(&self, req: &LlmRequest) -> Result<adk_core::TokenBreakdown> {
+        self.inner.count_tokens_breakdown(req).await
+    }
+
+    /// Batch calls do not stream, so they go straight to the wrapped model.
+    async fn generate_batch(
+        &self,
+        requests: Vec<LlmRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<LlmResponse>> {
+        self.inner.generate_batch(requests, concurrency).await
+    }
+
+    async fn generate_content(
+        &self,
+        request: LlmRequest,
+        stream: bool,
+    ) -> Result<LlmResponseStream> {
+        if self.recovery == StreamRecovery::Fail {
+            return self.inner.generate_content(request, stream).await;
+        }
+        let first = self.inner.generate_content(request.clone(), stream).await?;
+        Ok(recover_stream(self.inner.clone(), request, stream, first, self.recovery))
+    }
+}
+
+/// Returns `true` if `response` is the marker emitted for a cut-off stream.
+pub fn is_stream_interrupted(response: &LlmResponse) -> bool {
+    response.interrupted && response.error_code.as_deref() == Some(STREAM_INTERRUPTED)
+}
+
+fn recover_stream(
+    inner: Arc<dyn Llm>,
+    request: LlmRequest,
+    stream: bool,
+    first: LlmResponseStream,
+    recovery: StreamRecovery,
+) -> LlmResponseStream {
+    let max_attempts = match recovery {
+        StreamRecovery::Resume { max_attempts } => max_attempts,
+        _ => 0,
+    };
+
+    Box::pin(async_stream::stream! {
+        let mut current = first;
+        let mut received = String::new();
+        let mut saw_content = false;
+        let mut saw_function_call = false;
+        let mut attempts = 0;
+
+        'streaming: loop {
+            let mut error = loop {
+                match current.next().await {
+                    Some(Ok(chunk)) => {
+                        if let Some(content) = &chunk.content {
+                            saw_content = true;
+                            saw_function_call |= content.has_function_calls();
+                        }
+                        if let Some(text) = chunk.answer_text() {
+                            received.push_str(&text);
+                        }
+                        yield Ok(chunk);
+                    }
+                    Some(Err(error)) => break error,
+                    None => return,
+                }
+            };
+
+            if !saw_content {
+                yield Err(error);
+                return;
+            }
+
+            // A function call cannot be continued from a text prefix.
+            while attempts < max_attempts && !saw_function_call && !received.is_empty() {
+                attempts += 1;
+                adk_telemetry::warn!(
+                    attempt = attempts,
+                    max_attempts,
+                    received_chars = received.len(),
+                    error = %error,
+                    "stream interrupted, resuming from partial response"
+                );
+                match inner.generate_content(continuation_request(&request, &received), stream).await {
+                    Ok(next) => {
+                        current = next;
+                        continue 'streaming;
+                    }
+                    Err(resume_error) => error = resume_error,
+                }
+            }
+
+            adk_telemetry::warn!(
+                received_chars = received.len(),
+                error = %error,
+                "stream interrupted, keeping partial response"
+            );
+            yield Ok(interrupted_marker(&error));
+            return;
+        }
+    })
+}
+
+/// The original request with the text received so far as a trailing model turn.
+fn continuation_request(request: &LlmRequest, received: &str) -> LlmRequest {
+    let mut request = request.clone();
+    request.contents.push(Content::new("model").with_text(received));
+    request
+}
+
+fn interrupted_marker(error: &AdkError) -> LlmResponse {
+    LlmResponse {
+        finish_reason: Some(FinishReason::Other),
+        turn_complete: true,
+        interrupted: true,
+        error_code: Some(STREAM_INTERRUPTED.to_string()),
+        error_message: Some(error.to_string()),
+        ..Default::default()
+    }
+}
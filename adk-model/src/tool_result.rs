@@ -1,8 +1,160 @@
+//! Linking tool results to the calls they answer, for every provider.
+//!
+//! Providers represent tool results differently: Gemini sends
+//! `functionResponse` parts matched to calls by name and position, Anthropic
+//! and Bedrock send `tool_result` blocks that reference the call's id, and
+//! Chat Completions APIs expect one `tool` message per result carrying the
+//! call's `tool_call_id`. A session that switches providers mid-conversation
+//! carries calls without ids (from Gemini) or results whose ids no longer
+//! line up, which id-based providers reject.
+//!
+//! [`link_tool_call_ids`] brings any history into one canonical form before
+//! it is rendered: every call has an id and every result carries the id of
+//! the call it answers. [`split_tool_results`] additionally gives each result
+//! its own content for providers that take one result per message.
+//! [`PromptLayout::normalize`](crate::prompt_layout::PromptLayout::normalize)
+//! applies both according to the provider's
+//! [`ToolResultLayout`](crate::prompt_layout::ToolResultLayout).
+//!
+//! # Example
+//!
+//! ```rust
+//! use adk_core::{Content, FunctionResponseData, Part};
+//! use adk_model::tool_result::link_tool_call_ids;
+//! use serde_json::json;
+//!
+//! // A call and its result as Gemini returns them, without ids.
+//! let mut contents = vec![
+//!     Content {
+//!         role: "model".to_string(),
+//!         parts: vec![Part::FunctionCall {
+//!             name: "get_weather".to_string(),
+//!             args: json!({ "city": "Paris" }),
+//!             id: None,
+//!             thought_signature: None,
+//!         }],
+//!     },
+//!     Content {
+//!         role: "function".to_string(),
+//!         parts: vec![Part::FunctionResponse {
+//!             function_response: FunctionResponseData::new("get_weather", json!("sunny")),
+//!             id: None,
+//!         }],
+//!     },
+//! ];
+//!
+//! link_tool_call_ids(&mut contents);
+//! let Part::FunctionCall { id: Some(call_id), .. } = &contents[0].parts[0] else { panic!() };
+//! let Part::FunctionResponse { id: Some(result_id), .. } = &contents[1].parts[0] else { panic!() };
+//! assert_eq!(call_id, result_id);
+//! ```
+
+use adk_core::{Content, Part};
+use std::collections::HashSet;
+
+/// Give every function call an id and every function response the id of the
+/// call it answers.
+///
+/// Calls keep their ids; calls without one get `call_<n>`, skipping ids
+/// already in use. A response answers the pending call with the same id, or
+/// else the earliest pending call to the same function. Responses that match
+/// no call are left unchanged.
+pub fn link_tool_call_ids(contents: &mut [Content]) {
+    let mut taken: HashSet<String> = contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match part {
+            Part::FunctionCall { id: Some(id), .. } => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut next = 0;
+    // (call id, function name) of calls not yet answered, oldest first.
+    let mut pending: Vec<(String, String)> = Vec::new();
+
+    for part in contents.iter_mut().flat_map(|content| &mut content.parts) {
+        match part {
+            Part::FunctionCall { name, id, .. } => {
+                let id = id.get_or_insert_with(|| fresh_call_id(&mut next, &mut taken));
+                pending.push((id.clone(), name.clone()));
+            }
+            Part::FunctionResponse { function_response, id } => {
+                let position = id
+                    .as_ref()
+                    .and_then(|id| pending.iter().position(|(call_id, _)| call_id == id))
+                    .or_else(|| {
+                        pending.iter().position(|(_, name)| *name == function_response.name)
+                    });
+                if let Some(position) = position {
+                    *id = Some(pending.remove(position).0);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Split contents holding several function responses so that each response
+/// is in a content of its own, keeping the role and the order of all parts.
+///
+/// Other parts of such a content stay together between the responses.
+pub fn split_tool_results(contents: Vec<Content>) -> Vec<Content> {
+    let mut split = Vec::with_capacity(contents.len());
+    for content in contents {
+        let responses = content
+            .parts
+            .iter()
+            .filter(|part| matches!(part, Part::FunctionResponse { .. }))
+            .count();
+        if responses < 2 {
+            split.push(content);
+            continue;
+        }
+
+        let mut others = Vec::new();
+        for part in content.parts {
+            if matches!(part, Part::FunctionResponse { .. }) {
+                if !others.is_empty() {
+                    split.push(Content {
+                        role: content.role.clone(),
+                        parts: std::mem::take(&mut others),
+                    });
+                }
+                split.push(Content { role: content.role.clone(), parts: vec![part] });
+            } else {
+                others.push(part);
+            }
+        }
+        if !others.is_empty() {
+            split.push(Content { role: content.role, parts: others });
+        }
+    }
+    split
+}
+
+fn fresh_call_id(next: &mut usize, taken: &mut HashSet<String>) -> String {
+    loop {
+        let id = format!("call_{next}");
+        *next += 1;
+        if taken.insert(id.clone()) {
+            return id;
+        }
+    }
+}
+
 /// Serialize a tool result `Value` into a string suitable for model provider APIs.
 ///
 /// This avoids double-encoding: when the value is already a `String`, it is returned
 /// as-is. JSON objects and arrays are serialized to their JSON text representation.
 /// Primitive values (numbers, booleans, null) are converted via `to_string()`.
+#[cfg(any(
+    feature = "openai",
+    feature = "ollama",
+    feature = "deepseek",
+    feature = "groq",
+    feature = "bedrock",
+    feature = "azure-ai"
+))]
 pub(crate) fn serialize_tool_result(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::String(s) => s.clone(),
@@ -13,7 +165,17 @@ pub(crate) fn serialize_tool_result(value: &serde_json::Value) -> String {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(
+    test,
+    any(
+        feature = "openai",
+        feature = "ollama",
+        feature = "deepseek",
+        feature = "groq",
+        feature = "bedrock",
+        feature = "azure-ai"
+    )
+))]
 mod tests {
     use super::*;
     use serde_json::json;
@@ -0,0 +1,194 @@
+//! Prompt and response filtering shared across providers.
+
+use adk_core::{Content, FinishReason, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result};
+use adk_model::MockLlm;
+use adk_model::content_filter::{
+    BlocklistFilter, CONTENT_FILTERED, ContentFilterEvent, FilterDirection, FilteredLlm,
+    ModerationModelFilter, is_content_filtered,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Streams the given chunks and counts how often it is called.
+struct ChunkedModel {
+    chunks: Vec<&'static str>,
+    calls: AtomicUsize,
+}
+
+impl ChunkedModel {
+    fn new(chunks: Vec<&'static str>) -> Arc<Self> {
+        Arc::new(Self { chunks, calls: AtomicUsize::new(0) })
+    }
+}
+
+#[async_trait]
+impl Llm for ChunkedModel {
+    fn name(&self) -> &str {
+        "chunked"
+    }
+
+    async fn count_tokens(&self, _req: &LlmRequest) -> Result<usize> {
+        Ok(7)
+    }
+
+    async fn generate_content(&self, _req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let last = self.chunks.len() - 1;
+        let items: Vec<Result<LlmResponse>> = self
+            .chunks
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let mut chunk = LlmResponse::new(Content::new("model").with_text(*text));
+                chunk.partial = index < last;
+                chunk.turn_complete = index == last;
+                Ok(chunk)
+            })
+            .collect();
+        Ok(Box::pin(futures::stream::iter(items)))
+    }
+}
+
+fn request(text: &str) -> LlmRequest {
+    LlmRequest::new("test", vec![Content::new("user").with_text(text)])
+}
+
+fn audited(model: FilteredLlm) -> (FilteredLlm, Arc<Mutex<Vec<ContentFilterEvent>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let model =
+        model.with_audit_hook(Arc::new(move |event| sink.lock().unwrap().push(event.clone())));
+    (model, events)
+}
+
+async fn collect(model: &FilteredLlm, request: LlmRequest) -> Vec<LlmResponse> {
+    let stream = model.generate_content(request, true).await.unwrap();
+    stream.map(|response| response.unwrap()).collect().await
+}
+
+fn blocklist() -> Arc<BlocklistFilter> {
+    Arc::new(
+        BlocklistFilter::new().with_keyword("Project Aurora").with_pattern(r"ALPHA-\d+").unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_prohibited_prompt_is_refused_without_calling_the_model() {
+    let inner = ChunkedModel::new(vec!["Sure, here it is."]);
+    let (model, events) = audited(FilteredLlm::new(inner.clone()).with_filter(blocklist()));
+
+    let responses = collect(&model, request("Tell me everything about project aurora")).await;
+
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 0);
+    assert_eq!(responses.len(), 1);
+    let refusal = &responses[0];
+    assert!(is_content_filtered(refusal));
+    assert_eq!(refusal.finish_reason, Some(FinishReason::Safety));
+    assert!(refusal.turn_complete);
+    assert_eq!(refusal.answer_text().as_deref(), Some("I can't help with that request."));
+    assert_eq!(
+        refusal.provider_metadata.as_ref().unwrap()["content_filter"]["direction"],
+        "prompt"
+    );
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].direction, FilterDirection::Prompt);
+    assert_eq!(events[0].filter, "blocklist");
+    assert_eq!(events[0].model, "chunked");
+    assert!(events[0].reason.contains("Project Aurora"), "{}", events[0].reason);
+}
+
+#[tokio::test]
+async fn test_prohibited_response_is_replaced_by_a_refusal() {
+    // The prohibited code is split across chunks.
+    let inner = ChunkedModel::new(vec!["The launch code is ALP", "HA-42."]);
+    let (model, events) = audited(
+        FilteredLlm::new(inner.clone())
+            .with_filter(blocklist())
+            .with_refusal_message("That answer was withheld."),
+    );
+
+    let responses = collect(&model, request("What is the launch code?")).await;
+
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].error_code.as_deref(), Some(CONTENT_FILTERED));
+    assert_eq!(responses[0].answer_text().as_deref(), Some("That answer was withheld."));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].direction, FilterDirection::Response);
+}
+
+#[tokio::test]
+async fn test_allowed_content_passes_through_unchanged() {
+    let inner = ChunkedModel::new(vec!["Hello ", "there."]);
+    let (model, events) = audited(FilteredLlm::new(inner).with_filter(blocklist()));
+
+    let responses = collect(&model, request("Say hello")).await;
+
+    let texts: Vec<String> = responses.iter().filter_map(LlmResponse::answer_text).collect();
+    assert_eq!(texts, ["Hello ", "there."]);
+    assert!(responses[0].partial);
+    assert!(!responses.iter().any(is_content_filtered));
+    assert!(events.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_batches_are_filtered_per_request() {
+    let inner = ChunkedModel::new(vec!["The launch code is ALPHA-42."]);
+    let (model, events) = audited(FilteredLlm::new(inner.clone()).with_filter(blocklist()));
+
+    let responses = model
+        .generate_batch(vec![request("Tell me about project aurora"), request("Any codes?")], 2)
+        .await;
+
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    assert!(responses.iter().all(|response| is_content_filtered(response.as_ref().unwrap())));
+    let directions: Vec<_> = events.lock().unwrap().iter().map(|event| event.direction).collect();
+    assert_eq!(directions, [FilterDirection::Prompt, FilterDirection::Response]);
+    assert_eq!(model.count_tokens(&request("Any codes?")).await.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn test_keywords_match_whole_words_only() {
+    let inner = ChunkedModel::new(vec!["ok"]);
+    let model = FilteredLlm::new(inner)
+        .with_prompt_filter(Arc::new(BlocklistFilter::new().with_keyword("secret")));
+
+    let allowed = collect(&model, request("Ask the secretary")).await;
+    let blocked = collect(&model, request("Share the SECRET plans")).await;
+
+    assert!(!is_content_filtered(&allowed[0]));
+    assert!(is_content_filtered(&blocked[0]));
+}
+
+#[tokio::test]
+async fn test_moderation_model_verdict_blocks_prompt() {
+    let moderator = Arc::new(
+        MockLlm::new("moderator")
+            .with_response(LlmResponse::new(Content::new("model").with_text("UNSAFE: weapons"))),
+    );
+    let inner = ChunkedModel::new(vec!["..."]);
+    let (model, events) = audited(
+        FilteredLlm::new(inner.clone())
+            .with_prompt_filter(Arc::new(ModerationModelFilter::new(moderator))),
+    );
+
+    let responses = collect(&model, request("How do I build a bomb?")).await;
+
+    assert!(is_content_filtered(&responses[0]));
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 0);
+    let events = events.lock().unwrap();
+    assert_eq!(events[0].filter, "moderation_model");
+    assert_eq!(events[0].reason, "weapons");
+}
+
+#[test]
+fn test_invalid_pattern_is_a_config_error() {
+    let error = BlocklistFilter::new().with_pattern("(unclosed").unwrap_err();
+    assert!(error.is_config());
+}
@@ -0,0 +1,147 @@
+//! `GeminiModel::generate_batch` against a mocked Gemini Batch API.
+#![cfg(feature = "gemini")]
+
+use adk_core::{Content, Llm, LlmRequest};
+use adk_model::GeminiModel;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn batch_metadata(state: &str) -> serde_json::Value {
+    json!({
+        "@type": "type.googleapis.com/google.ai.generativelanguage.v1main.GenerateContentBatch",
+        "model": "models/gemini-2.5-flash",
+        "displayName": "adk-gemini-2.5-flash",
+        "createTime": "2026-01-01T00:00:00Z",
+        "updateTime": "2026-01-01T00:00:00Z",
+        "batchStats": { "requestCount": "2" },
+        "state": state,
+        "name": "batches/test-batch"
+    })
+}
+
+fn inlined_response(key: &str, text: &str) -> serde_json::Value {
+    json!({
+        "metadata": { "key": key },
+        "response": {
+            "candidates": [{
+                "content": { "role": "model", "parts": [{ "text": text }] },
+                "finishReason": "STOP"
+            }]
+        }
+    })
+}
+
+fn request(text: &str) -> LlmRequest {
+    LlmRequest::new("gemini-2.5-flash", vec![Content::new("user").with_text(text)])
+}
+
+#[tokio::test]
+async fn test_generate_batch_submits_one_batch_job_and_keeps_request_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(r":batchGenerateContent$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/test-batch",
+            "metadata": batch_metadata("BATCH_STATE_PENDING")
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // Results arrive out of order; the batch keys put them back in place.
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/test-batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/test-batch",
+            "metadata": batch_metadata("BATCH_STATE_SUCCEEDED"),
+            "done": true,
+            "response": {
+                "@type": "type.googleapis.com/google.ai.generativelanguage.v1main.GenerateContentBatchOutput",
+                "inlinedResponses": {
+                    "inlinedResponses": [
+                        inlined_response("1", "second answer"),
+                        inlined_response("0", "first answer")
+                    ]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(path_regex(r":generateContent$"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let model = GeminiModel::new_with_base_url(
+        "test-key",
+        "gemini-2.5-flash",
+        format!("{}/v1beta/", server.uri()),
+    )
+    .unwrap()
+    .with_batch_poll_interval(Duration::from_millis(10));
+
+    let results = model.generate_batch(vec![request("first"), request("second")], 4).await;
+
+    let texts: Vec<String> =
+        results.into_iter().map(|result| result.unwrap().answer_text().unwrap()).collect();
+    assert_eq!(texts, ["first answer", "second answer"]);
+
+    let submitted = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|request| request.url.path().ends_with(":batchGenerateContent"))
+        .unwrap();
+    let body: serde_json::Value = submitted.body_json().unwrap();
+    assert_eq!(body["batch"]["inputConfig"]["requests"]["requests"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_generate_batch_reports_failed_items_individually() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(r":batchGenerateContent$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/test-batch",
+            "metadata": batch_metadata("BATCH_STATE_PENDING")
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/test-batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/test-batch",
+            "metadata": batch_metadata("BATCH_STATE_SUCCEEDED"),
+            "done": true,
+            "response": {
+                "inlinedResponses": {
+                    "inlinedResponses": [
+                        inlined_response("0", "fine"),
+                        {
+                            "metadata": { "key": "1" },
+                            "error": { "code": 400, "message": "prompt too long" }
+                        }
+                    ]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let model = GeminiModel::new_with_base_url(
+        "test-key",
+        "gemini-2.5-flash",
+        format!("{}/v1beta/", server.uri()),
+    )
+    .unwrap()
+    .with_batch_poll_interval(Duration::from_millis(10));
+
+    let results = model.generate_batch(vec![request("ok"), request("too long")], 1).await;
+
+    assert_eq!(results[0].as_ref().unwrap().answer_text().unwrap(), "fine");
+    let error = results[1].as_ref().unwrap_err().to_string();
+    assert!(error.contains("prompt too long"), "unexpected error: {error}");
+}
@@ -0,0 +1,163 @@
+//! Tool calling emulated over JSON output.
+
+use adk_core::{Content, Llm, LlmRequest, LlmResponse, LlmResponseStream, Part, Result};
+use adk_model::JsonModeToolAdapter;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// A model without function calling: answers with canned text and records requests.
+struct JsonOnlyModel {
+    reply: String,
+    requests: Arc<Mutex<Vec<LlmRequest>>>,
+}
+
+#[async_trait]
+impl Llm for JsonOnlyModel {
+    fn name(&self) -> &str {
+        "json-only"
+    }
+
+    async fn generate_content(&self, req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.requests.lock().unwrap().push(req);
+        let response = LlmResponse::new(Content::new("model").with_text(&self.reply));
+        Ok(Box::pin(futures::stream::once(async { Ok(response) })))
+    }
+}
+
+fn weather_request() -> LlmRequest {
+    let mut request =
+        LlmRequest::new("local", vec![Content::new("user").with_text("Weather in Paris?")]);
+    request.tools.insert(
+        "get_weather".to_string(),
+        json!({
+            "description": "Current weather for a city",
+            "parameters": {
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"]
+            }
+        }),
+    );
+    request
+}
+
+async fn single_response(model: &dyn Llm, request: LlmRequest) -> LlmResponse {
+    let responses: Vec<_> = model.generate_content(request, true).await.unwrap().collect().await;
+    assert_eq!(responses.len(), 1);
+    responses.into_iter().next().unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_tool_selection_json_becomes_function_call() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let adapter = JsonModeToolAdapter::new(Arc::new(JsonOnlyModel {
+        reply: r#"{"tool_calls": [{"name": "get_weather", "arguments": {"city": "Paris"}}]}"#
+            .to_string(),
+        requests: requests.clone(),
+    }));
+
+    let response = single_response(&adapter, weather_request()).await;
+
+    let parts = response.content.unwrap().parts;
+    assert_eq!(parts.len(), 1);
+    match &parts[0] {
+        Part::FunctionCall { name, args, .. } => {
+            assert_eq!(name, "get_weather");
+            assert_eq!(args, &json!({ "city": "Paris" }));
+        }
+        other => panic!("expected a function call, got {other:?}"),
+    }
+    assert!(!response.turn_complete);
+
+    let sent = &requests.lock().unwrap()[0];
+    assert!(sent.tools.is_empty(), "native tools must not reach the endpoint");
+    let system = &sent.contents[0];
+    assert_eq!(system.role, "system");
+    assert!(system.parts[0].text().unwrap().contains("get_weather: Current weather for a city"));
+    assert_eq!(
+        sent.config.as_ref().unwrap().extensions["openai"]["response_format"],
+        json!({ "type": "json_object" })
+    );
+}
+
+#[tokio::test]
+async fn test_direct_response_json_becomes_text() {
+    let adapter = JsonModeToolAdapter::new(Arc::new(JsonOnlyModel {
+        reply: r#"{"response": "I need a city name first."}"#.to_string(),
+        requests: Arc::new(Mutex::new(Vec::new())),
+    }));
+
+    let response = single_response(&adapter, weather_request()).await;
+
+    let parts = response.content.unwrap().parts;
+    assert_eq!(parts[0].text(), Some("I need a city name first."));
+    assert!(response.turn_complete);
+}
+
+#[tokio::test]
+async fn test_requests_without_tools_pass_through() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let adapter = JsonModeToolAdapter::new(Arc::new(JsonOnlyModel {
+        reply: "plain answer".to_string(),
+        requests: requests.clone(),
+    }));
+
+    let request = LlmRequest::new("local", vec![Content::new("user").with_text("hi")]);
+    let response = single_response(&adapter, request).await;
+
+    assert_eq!(response.content.unwrap().parts[0].text(), Some("plain answer"));
+    let sent = &requests.lock().unwrap()[0];
+    assert_eq!(sent.contents.len(), 1);
+    assert!(sent.config.is_none());
+}
+
+#[cfg(feature = "openai")]
+mod openai_compatible {
+    use super::*;
+    use adk_model::openai_compatible::{OpenAICompatible, OpenAICompatibleConfig};
+    use adk_model::retry::RetryConfig;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_client_flag_sends_json_mode_instead_of_tools() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": "{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Paris\"}}"
+                    },
+                    "finish_reason": "stop"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = OpenAICompatibleConfig::new("test-key", "local-model")
+            .with_base_url(server.uri())
+            .with_json_mode_tools(true);
+        let client =
+            OpenAICompatible::new(config).unwrap().with_retry_config(RetryConfig::disabled());
+
+        let response = single_response(&client, weather_request()).await;
+
+        assert!(matches!(
+            &response.content.unwrap().parts[0],
+            Part::FunctionCall { name, .. } if name == "get_weather"
+        ));
+        let body: serde_json::Value =
+            server.received_requests().await.unwrap()[0].body_json().unwrap();
+        assert!(body.get("tools").is_none());
+        assert_eq!(body["response_format"], json!({ "type": "json_object" }));
+        assert_eq!(body["messages"][0]["role"], "system");
+    }
+}
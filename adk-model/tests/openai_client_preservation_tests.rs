@@ -25,6 +25,7 @@ mod openai_client_preservation {
             organization_id: None,
             project_id: None,
             reasoning_effort: None,
+            ..Default::default()
         };
         OpenAIClient::new(config)
             .expect("client creation should succeed")
@@ -40,6 +41,7 @@ mod openai_client_preservation {
             organization_id: None,
             project_id: None,
             reasoning_effort: None,
+            ..Default::default()
         };
         OpenAIClient::new(config)
             .expect("client creation should succeed")
@@ -25,6 +25,7 @@ mod openai_client_streaming_exploration {
             organization_id: None,
             project_id: None,
             reasoning_effort: None,
+            ..Default::default()
         };
         OpenAIClient::new(config)
             .expect("client creation should succeed")
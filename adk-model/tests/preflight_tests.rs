@@ -0,0 +1,132 @@
+//! Request validation by `PreflightLlm` before the provider is called.
+
+use adk_core::{
+    Content, ErrorCategory, Llm, LlmRequest, LlmResponse, LlmResponseStream, Part, Result,
+};
+use adk_model::ModelProvider;
+use adk_model::preflight::{INVALID_REQUEST, MediaLimits, PreflightLlm};
+use async_trait::async_trait;
+use image::{GenericImageView, ImageFormat, Rgb, RgbImage};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+/// Records the requests that reach the provider.
+#[derive(Default)]
+struct RecordingModel {
+    requests: Mutex<Vec<LlmRequest>>,
+}
+
+#[async_trait]
+impl Llm for RecordingModel {
+    fn name(&self) -> &str {
+        "recording-model"
+    }
+
+    async fn generate_content(&self, req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.requests.lock().unwrap().push(req);
+        let response = LlmResponse::new(Content::new("model").with_text("ok"));
+        Ok(Box::pin(futures::stream::once(async { Ok(response) })))
+    }
+}
+
+fn png(width: u32, height: u32, pixel: impl Fn(u32, u32) -> Rgb<u8>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::from_fn(width, height, pixel)
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+fn request_with(part: Part) -> LlmRequest {
+    let content = Content {
+        role: "user".to_string(),
+        parts: vec![Part::Text { text: "What is in this picture?".to_string() }, part],
+    };
+    LlmRequest::new("model", vec![content])
+}
+
+fn sent_image(model: &RecordingModel) -> (String, Vec<u8>) {
+    let requests = model.requests.lock().unwrap();
+    match &requests[0].contents[0].parts[1] {
+        Part::InlineData { mime_type, data } => (mime_type.clone(), data.clone()),
+        other => panic!("expected an image, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_image_is_downscaled_when_enabled_and_rejected_otherwise() {
+    // Wider than the 8000 pixels Anthropic accepts; one color, so few bytes.
+    let wide = png(9000, 300, |_, _| Rgb([40, 120, 200]));
+    let image = || Part::InlineData { mime_type: "image/png".to_string(), data: wide.clone() };
+
+    let inner = Arc::new(RecordingModel::default());
+    let model = PreflightLlm::new(inner.clone(), ModelProvider::Anthropic);
+    let error = model.generate_content(request_with(image()), false).await.err().unwrap();
+    assert_eq!(error.category, ErrorCategory::InvalidInput);
+    assert_eq!(error.code, INVALID_REQUEST);
+    assert!(error.message.contains("content 0, part 1 is 9000x300 pixels"), "{}", error.message);
+    assert!(inner.requests.lock().unwrap().is_empty());
+
+    let model = PreflightLlm::new(inner.clone(), ModelProvider::Anthropic).with_downscale(true);
+    let _ = model.generate_content(request_with(image()), false).await.unwrap();
+    let (mime_type, data) = sent_image(&inner);
+    assert_eq!(mime_type, "image/png");
+    let resized = image::load_from_memory(&data).unwrap();
+    assert_eq!(resized.dimensions(), (8000, 266));
+}
+
+#[tokio::test]
+async fn test_image_over_byte_limit_is_downscaled_to_fit() {
+    // Noise compresses poorly: about 190 KB as PNG.
+    let noisy = png(256, 256, |x, y| {
+        let v = x.wrapping_mul(2_654_435_761).wrapping_add(y.wrapping_mul(40_503)) as u8;
+        Rgb([v, v.wrapping_mul(7), v.wrapping_mul(13)])
+    });
+    let limits = MediaLimits::for_provider(ModelProvider::Openai).with_max_image_bytes(64 * 1024);
+    assert!(noisy.len() > 64 * 1024);
+
+    let inner = Arc::new(RecordingModel::default());
+    let model = PreflightLlm::new(inner.clone(), ModelProvider::Openai)
+        .with_limits(limits)
+        .with_downscale(true);
+    let part = Part::InlineData { mime_type: "image/png".to_string(), data: noisy };
+    let _ = model.generate_content(request_with(part), false).await.unwrap();
+
+    let (_, data) = sent_image(&inner);
+    assert!(data.len() <= 64 * 1024, "{} bytes", data.len());
+    let (width, height) = image::load_from_memory(&data).unwrap().dimensions();
+    assert!(width < 256 && width == height);
+}
+
+#[tokio::test]
+async fn test_unsupported_image_type_and_empty_requests_are_rejected() {
+    let inner = Arc::new(RecordingModel::default());
+    let model = PreflightLlm::new(inner.clone(), ModelProvider::Anthropic);
+
+    let bmp = Part::InlineData { mime_type: "image/bmp".to_string(), data: vec![0x42, 0x4d] };
+    let error = model.generate_content(request_with(bmp), false).await.err().unwrap();
+    assert_eq!(error.category, ErrorCategory::InvalidInput);
+    assert!(
+        error.message.contains("image type 'image/bmp' in content 0, part 1 is not supported"),
+        "{}",
+        error.message
+    );
+    assert!(error.message.contains("image/jpeg, image/png, image/gif, image/webp"));
+
+    let tiff = Part::FileData {
+        mime_type: "image/tiff".to_string(),
+        file_uri: "gs://bucket/scan.tiff".to_string(),
+    };
+    assert!(model.generate_content(request_with(tiff), false).await.is_err());
+
+    let blank = LlmRequest::new("model", vec![Content::new("user").with_text("   ")]);
+    let error = model.generate_content(blank, false).await.err().unwrap();
+    assert_eq!(error.code, INVALID_REQUEST);
+    assert!(error.message.contains("no content"), "{}", error.message);
+    assert!(inner.requests.lock().unwrap().is_empty());
+
+    // Non-image media is left for the provider to handle.
+    let pdf = Part::InlineData { mime_type: "application/pdf".to_string(), data: vec![1, 2, 3] };
+    let _ = model.generate_content(request_with(pdf), false).await.unwrap();
+    assert_eq!(inner.requests.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,138 @@
+//! System instruction placement and part order per provider.
+
+use adk_core::{Content, Part};
+use adk_model::ModelProvider;
+use adk_model::prompt_layout::{PartOrder, PromptLayout, SystemPlacement, ToolResultLayout};
+
+/// One logical request: two system fragments (one arriving mid-conversation,
+/// as `JsonModeToolAdapter` adds them) and a user message with text before an
+/// image.
+fn contents() -> Vec<Content> {
+    vec![
+        Content::new("system").with_text("You are a travel agent."),
+        Content::new("user")
+            .with_text("What city is this?")
+            .with_inline_data("image/png", vec![1, 2, 3]),
+        Content::new("model").with_text("Paris."),
+        Content::new("system").with_text("Answer in one word."),
+        Content::new("user").with_text("And the country?"),
+    ]
+}
+
+fn roles(contents: &[Content]) -> Vec<&str> {
+    contents.iter().map(|content| content.role.as_str()).collect()
+}
+
+fn is_image(part: &Part) -> bool {
+    matches!(part, Part::InlineData { .. })
+}
+
+#[test]
+fn test_field_providers_get_one_merged_instruction_outside_the_conversation() {
+    for provider in [ModelProvider::Gemini, ModelProvider::Anthropic] {
+        let normalized = PromptLayout::for_provider(provider).normalize(&contents());
+
+        assert_eq!(
+            normalized.system_instruction.as_deref(),
+            Some("You are a travel agent.\nAnswer in one word."),
+            "{provider}"
+        );
+        assert_eq!(roles(&normalized.contents), ["user", "model", "user"], "{provider}");
+        // Media goes first for these providers.
+        assert!(is_image(&normalized.contents[0].parts[0]), "{provider}");
+        assert_eq!(normalized.contents[0].parts[1].text(), Some("What city is this?"));
+    }
+}
+
+#[test]
+fn test_chat_completions_providers_get_one_leading_system_message() {
+    for provider in
+        [ModelProvider::Openai, ModelProvider::Deepseek, ModelProvider::Groq, ModelProvider::Ollama]
+    {
+        let normalized = PromptLayout::for_provider(provider).normalize(&contents());
+
+        assert_eq!(normalized.system_instruction, None, "{provider}");
+        assert_eq!(roles(&normalized.contents), ["system", "user", "model", "user"], "{provider}");
+        assert_eq!(
+            normalized.contents[0].parts[0].text(),
+            Some("You are a travel agent.\nAnswer in one word."),
+            "{provider}"
+        );
+        // Part order is left alone.
+        assert_eq!(normalized.contents[1].parts[0].text(), Some("What city is this?"));
+        assert!(is_image(&normalized.contents[1].parts[1]), "{provider}");
+    }
+}
+
+#[test]
+fn test_openai_responses_uses_instructions_field_without_reordering() {
+    let normalized = PromptLayout::openai_responses().normalize(&contents());
+
+    assert_eq!(
+        normalized.system_instruction.as_deref(),
+        Some("You are a travel agent.\nAnswer in one word.")
+    );
+    assert_eq!(roles(&normalized.contents), ["user", "model", "user"]);
+    assert!(is_image(&normalized.contents[0].parts[1]));
+}
+
+#[test]
+fn test_requests_without_system_content_are_unchanged() {
+    let contents = vec![Content::new("user").with_text("Hi"), Content::new("system")];
+    let layout = PromptLayout {
+        system: SystemPlacement::LeadingMessage,
+        part_order: PartOrder::AsGiven,
+        tool_results: ToolResultLayout::MessagePerResult,
+    };
+
+    let normalized = layout.normalize(&contents);
+
+    assert_eq!(normalized.system_instruction, None);
+    assert_eq!(roles(&normalized.contents), ["user"]);
+}
+
+#[cfg(feature = "gemini")]
+#[tokio::test]
+async fn test_gemini_sends_system_contents_as_system_instruction() {
+    use adk_core::{Llm, LlmRequest};
+    use adk_model::GeminiModel;
+    use futures::StreamExt;
+    use serde_json::json;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(r":generateContent$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": { "role": "model", "parts": [{ "text": "France." }] },
+                "finishReason": "STOP"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    let model = GeminiModel::new_with_base_url(
+        "test-key",
+        "gemini-2.5-flash",
+        format!("{}/v1beta/", server.uri()),
+    )
+    .unwrap();
+
+    let mut stream = model
+        .generate_content(LlmRequest::new("gemini-2.5-flash", contents()), false)
+        .await
+        .unwrap();
+    while stream.next().await.is_some() {}
+
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests[0].body_json().unwrap();
+    assert_eq!(
+        body["systemInstruction"]["parts"][0]["text"],
+        "You are a travel agent.\nAnswer in one word."
+    );
+    let roles: Vec<&str> =
+        body["contents"].as_array().unwrap().iter().map(|c| c["role"].as_str().unwrap()).collect();
+    assert_eq!(roles, ["user", "model", "user"]);
+    assert!(body["contents"][0]["parts"][0].get("inlineData").is_some());
+}
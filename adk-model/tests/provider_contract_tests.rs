@@ -16,6 +16,8 @@ use adk_model::deepseek::{DeepSeekClient, DeepSeekConfig};
 use adk_model::gemini::GeminiModel;
 #[cfg(feature = "groq")]
 use adk_model::groq::{GroqClient, GroqConfig};
+#[cfg(feature = "mistral")]
+use adk_model::mistral::{MistralClient, MistralConfig};
 #[cfg(feature = "ollama")]
 use adk_model::ollama::{OllamaConfig, OllamaModel};
 #[cfg(feature = "openai")]
@@ -553,6 +555,21 @@ fn mistral_cheapest_spec() -> ProviderSpec {
     }
 }
 
+#[cfg(feature = "mistral")]
+fn mistral_native_spec() -> ProviderSpec {
+    ProviderSpec {
+        name: "mistral-native",
+        model_env_candidates: &["MISTRAL_CHEAPEST_MODEL", "MISTRAL_MODEL"],
+        default_model: "mistral-small-latest",
+        required_envs: &["MISTRAL_API_KEY"],
+        supports_tools: true,
+        build_model: |model_name| {
+            let api_key = required_env("MISTRAL_API_KEY")?;
+            Ok(Box::new(MistralClient::new(MistralConfig::new(api_key, model_name))?))
+        },
+    }
+}
+
 #[cfg(feature = "openai")]
 fn perplexity_cheapest_spec() -> ProviderSpec {
     ProviderSpec {
@@ -610,6 +627,8 @@ provider_contract_tests!(fireworks_cheapest_provider, fireworks_cheapest_spec);
 provider_contract_tests!(together_cheapest_provider, together_cheapest_spec);
 #[cfg(feature = "openai")]
 provider_contract_tests!(mistral_cheapest_provider, mistral_cheapest_spec);
+#[cfg(feature = "mistral")]
+provider_contract_tests!(mistral_native_provider, mistral_native_spec);
 #[cfg(feature = "openai")]
 provider_contract_tests!(perplexity_cheapest_provider, perplexity_cheapest_spec);
 #[cfg(feature = "openai")]
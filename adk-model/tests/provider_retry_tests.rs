@@ -0,0 +1,384 @@
+//! Provider clients retry rate limits and server errors with backoff, for
+//! unary and streaming calls alike, honouring the delays servers ask for.
+
+#[cfg(any(feature = "gemini", feature = "openai", feature = "deepseek", feature = "groq"))]
+mod support {
+    use adk_core::{AdkError, Content, LlmRequest, LlmResponseStream, Part};
+    use adk_model::retry::RetryConfig;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    /// Retries twice without waiting.
+    pub fn fast_retry() -> RetryConfig {
+        RetryConfig::default()
+            .with_max_retries(2)
+            .with_initial_delay(Duration::ZERO)
+            .with_max_delay(Duration::ZERO)
+    }
+
+    pub fn request(model: &str) -> LlmRequest {
+        LlmRequest::new(model, vec![Content::new("user").with_text("Hello")])
+    }
+
+    /// An OpenAI-style chat completion answering `text`.
+    #[cfg(any(feature = "openai", feature = "deepseek", feature = "groq"))]
+    pub fn completion(text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "test-model",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": text },
+                "finish_reason": "stop"
+            }]
+        })
+    }
+
+    /// The text of every chunk, or the first error.
+    pub async fn collect_text(mut stream: LlmResponseStream) -> Result<String, AdkError> {
+        let mut text = String::new();
+        while let Some(item) = stream.next().await {
+            let response = item?;
+            for part in response.content.iter().flat_map(|content| &content.parts) {
+                if let Part::Text { text: chunk } = part {
+                    text.push_str(chunk);
+                }
+            }
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(feature = "gemini")]
+mod gemini {
+    use super::support::{collect_text, fast_retry, request};
+    use adk_core::Llm;
+    use adk_model::GeminiModel;
+    use adk_model::retry::attempt_count;
+    use serde_json::json;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn model(server: &MockServer) -> GeminiModel {
+        GeminiModel::new_with_base_url(
+            "test-key",
+            "gemini-2.5-flash",
+            format!("{}/v1beta/", server.uri()),
+        )
+        .unwrap()
+        .with_retry_config(fast_retry())
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_rate_limit_before_the_first_chunk() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":streamGenerateContent$"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(json!({
+                "error": { "code": 429, "message": "quota", "status": "RESOURCE_EXHAUSTED" }
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        let chunk = json!({
+            "candidates": [{
+                "content": { "role": "model", "parts": [{ "text": "Hi there" }] },
+                "finishReason": "STOP"
+            }]
+        });
+        Mock::given(method("POST"))
+            .and(path_regex(r":streamGenerateContent$"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(format!("data: {chunk}\r\n\r\n")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let stream = model(&server).generate_content(request("gemini-2.5-flash"), true).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+    }
+
+    #[tokio::test]
+    async fn test_persistent_server_error_gives_up_with_the_attempt_count() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(r":generateContent$"))
+            .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+                "error": { "code": 500, "message": "internal", "status": "INTERNAL" }
+            })))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let error = model(&server)
+            .generate_content(request("gemini-2.5-flash"), false)
+            .await
+            .err()
+            .expect("every attempt fails");
+
+        assert_eq!(error.upstream_status(), Some(500));
+        assert_eq!(attempt_count(&error), Some(3));
+    }
+}
+
+#[cfg(feature = "openai")]
+mod openai {
+    use super::support::{collect_text, completion, fast_retry, request};
+    use adk_core::Llm;
+    use adk_model::retry::{RetryConfig, StatusClasses, attempt_count};
+    use adk_model::{OpenAIClient, OpenAIConfig};
+    use std::time::{Duration, Instant};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer, retry_config: RetryConfig) -> OpenAIClient {
+        OpenAIClient::new(
+            OpenAIConfig::compatible("test-key", server.uri(), "gpt-4o")
+                .with_retry_config(retry_config),
+        )
+        .unwrap()
+    }
+
+    async fn rate_limit_once(server: &MockServer, response: ResponseTemplate) {
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(response)
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_rate_limit_then_streams_the_answer() {
+        let server = MockServer::start().await;
+        rate_limit_once(&server, ResponseTemplate::new(429).set_body_string("slow down")).await;
+        let sse = [
+            r#"data: {"choices":[{"index":0,"delta":{"content":"Hi "},"finish_reason":null}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{"content":"there"},"finish_reason":null}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+            "data: [DONE]",
+        ]
+        .join("\n\n");
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server, fast_retry()).generate_content(request("gpt-4o"), true).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+    }
+
+    #[tokio::test]
+    async fn test_unary_retries_rate_limit_then_returns_the_answer() {
+        let server = MockServer::start().await;
+        rate_limit_once(&server, ResponseTemplate::new(429)).await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Hi there")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server, fast_retry()).generate_content(request("gpt-4o"), false).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+    }
+
+    #[tokio::test]
+    async fn test_persistent_server_error_gives_up_with_the_attempt_count() {
+        for streaming in [false, true] {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/chat/completions"))
+                .respond_with(ResponseTemplate::new(500).set_body_string("oops"))
+                .expect(3)
+                .mount(&server)
+                .await;
+
+            let stream = client(&server, fast_retry())
+                .generate_content(request("gpt-4o"), streaming)
+                .await
+                .unwrap();
+            let error = collect_text(stream).await.expect_err("every attempt fails");
+
+            assert_eq!(error.upstream_status(), Some(500), "streaming: {streaming}");
+            assert_eq!(attempt_count(&error), Some(3), "streaming: {streaming}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_reset_header_overrides_the_backoff() {
+        let server = MockServer::start().await;
+        rate_limit_once(
+            &server,
+            ResponseTemplate::new(429)
+                .insert_header("x-ratelimit-remaining-requests", "0")
+                .insert_header("x-ratelimit-reset-requests", "20ms"),
+        )
+        .await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Hi there")))
+            .mount(&server)
+            .await;
+        let retry_config = fast_retry()
+            .with_initial_delay(Duration::from_secs(60))
+            .with_max_delay(Duration::from_secs(60));
+
+        let started = Instant::now();
+        let stream = client(&server, retry_config).generate_content(request("gpt-4o"), false).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_limits_which_failures_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let retry_config =
+            fast_retry().with_retry_on(StatusClasses { server_error: false, ..StatusClasses::ALL });
+
+        let stream = client(&server, retry_config).generate_content(request("gpt-4o"), true).await;
+        let error = collect_text(stream.unwrap()).await.expect_err("not retried");
+
+        assert_eq!(error.upstream_status(), Some(503));
+        assert_eq!(attempt_count(&error), Some(1));
+    }
+}
+
+#[cfg(feature = "deepseek")]
+mod deepseek {
+    use super::support::{collect_text, completion, fast_retry, request};
+    use adk_core::Llm;
+    use adk_model::retry::attempt_count;
+    use adk_model::{DeepSeekClient, DeepSeekConfig};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> DeepSeekClient {
+        DeepSeekClient::new(
+            DeepSeekConfig::new("test-key", "deepseek-chat")
+                .with_base_url(server.uri())
+                .with_retry_config(fast_retry()),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limit_then_returns_the_answer() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Hi there")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server).generate_content(request("deepseek-chat"), false).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+    }
+
+    #[tokio::test]
+    async fn test_persistent_server_error_gives_up_with_the_attempt_count() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server).generate_content(request("deepseek-chat"), true).await;
+        let error = collect_text(stream.unwrap()).await.expect_err("every attempt fails");
+
+        assert_eq!(error.upstream_status(), Some(500));
+        assert_eq!(attempt_count(&error), Some(3));
+    }
+}
+
+#[cfg(feature = "groq")]
+mod groq {
+    use super::support::{collect_text, completion, fast_retry, request};
+    use adk_core::Llm;
+    use adk_model::retry::attempt_count;
+    use adk_model::{GroqClient, GroqConfig};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> GroqClient {
+        GroqClient::new(
+            GroqConfig::llama8b("test-key")
+                .with_base_url(server.uri())
+                .with_retry_config(fast_retry()),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limit_then_returns_the_answer() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Hi there")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server).generate_content(request("llama-3.1-8b-instant"), false).await;
+
+        assert_eq!(collect_text(stream.unwrap()).await.unwrap(), "Hi there");
+    }
+
+    #[tokio::test]
+    async fn test_persistent_server_error_gives_up_with_the_attempt_count() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let stream = client(&server).generate_content(request("llama-3.1-8b-instant"), true).await;
+        let error = collect_text(stream.unwrap()).await.expect_err("every attempt fails");
+
+        assert_eq!(error.upstream_status(), Some(502));
+        assert_eq!(attempt_count(&error), Some(3));
+    }
+}
@@ -0,0 +1,178 @@
+//! Answering repeated deterministic requests from a response cache.
+
+use adk_core::{
+    AdkError, Content, GenerateContentConfig, Llm, LlmRequest, LlmResponse, LlmResponseStream,
+    MockClock, Result,
+};
+use adk_model::response_cache::{CacheStats, CachingLlm, InMemoryResponseCache, ResponseCache};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Answers with the call number, or fails when `fail` is set.
+struct CountingModel {
+    calls: AtomicUsize,
+    token_counts: AtomicUsize,
+    fail: bool,
+}
+
+impl CountingModel {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            calls: AtomicUsize::new(0),
+            token_counts: AtomicUsize::new(0),
+            fail: false,
+        })
+    }
+}
+
+#[async_trait]
+impl Llm for CountingModel {
+    fn name(&self) -> &str {
+        "counting"
+    }
+
+    async fn count_tokens(&self, req: &LlmRequest) -> Result<usize> {
+        self.token_counts.fetch_add(1, Ordering::SeqCst);
+        Ok(adk_core::estimate_request_tokens(req))
+    }
+
+    async fn generate_content(&self, _req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut first = LlmResponse::new(Content::new("model").with_text(format!("answer {call}")));
+        first.partial = true;
+        first.turn_complete = false;
+        let last = if self.fail {
+            Err(AdkError::model("connection reset"))
+        } else {
+            Ok(LlmResponse::new(Content::new("model").with_text(".")))
+        };
+        Ok(Box::pin(futures::stream::iter([Ok(first), last])))
+    }
+}
+
+fn request(temperature: Option<f32>) -> LlmRequest {
+    let config = GenerateContentConfig { temperature, ..Default::default() };
+    LlmRequest::new("counting", vec![Content::new("user").with_text("What is 2 + 2?")])
+        .with_config(config)
+}
+
+async fn text(model: &dyn Llm, request: LlmRequest) -> String {
+    let responses: Vec<_> = model.generate_content(request, true).await.unwrap().collect().await;
+    responses.iter().filter_map(|r| r.as_ref().ok()?.answer_text()).collect()
+}
+
+#[tokio::test]
+async fn test_repeated_request_hits_the_cache() {
+    let inner = CountingModel::new();
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let model = CachingLlm::new(inner.clone()).with_cache(cache.clone());
+
+    assert_eq!(text(&model, request(Some(0.0))).await, "answer 1.");
+    assert_eq!(text(&model, request(Some(0.0))).await, "answer 1.");
+
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(model.stats(), CacheStats { hits: 1, misses: 1, bypassed: 0 });
+    assert_eq!(model.stats().hit_rate(), 0.5);
+    assert_eq!(cache.len(), 1);
+
+    // A different question or config is a different entry.
+    let mut other = request(Some(0.0));
+    other.contents[0] = Content::new("user").with_text("What is 3 + 3?");
+    assert_eq!(text(&model, other).await, "answer 2.");
+    let mut other = request(Some(0.0));
+    other.tools.insert("calculator".to_string(), json!({ "name": "calculator" }));
+    assert_eq!(text(&model, other).await, "answer 3.");
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_only_deterministic_requests_are_cached() {
+    let inner = CountingModel::new();
+    let model = CachingLlm::new(inner.clone());
+
+    text(&model, request(None)).await;
+    text(&model, request(Some(0.7))).await;
+    text(&model, request(Some(0.7))).await;
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    assert_eq!(model.stats(), CacheStats { hits: 0, misses: 0, bypassed: 3 });
+
+    let forced = CachingLlm::new(inner.clone()).with_force(true);
+    assert_eq!(text(&forced, request(Some(0.7))).await, "answer 4.");
+    assert_eq!(text(&forced, request(Some(0.7))).await, "answer 4.");
+    assert_eq!(forced.stats().hits, 1);
+}
+
+#[tokio::test]
+async fn test_failed_streams_are_not_cached() {
+    let inner = Arc::new(CountingModel {
+        calls: AtomicUsize::new(0),
+        token_counts: AtomicUsize::new(0),
+        fail: true,
+    });
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let model = CachingLlm::new(inner.clone()).with_cache(cache.clone());
+
+    text(&model, request(Some(0.0))).await;
+    text(&model, request(Some(0.0))).await;
+
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn test_in_memory_cache_evicts_least_recently_used() {
+    let cache = InMemoryResponseCache::new(2);
+    let response = |text: &str| vec![LlmResponse::new(Content::new("model").with_text(text))];
+
+    cache.put("a".to_string(), response("a")).await;
+    cache.put("b".to_string(), response("b")).await;
+    assert!(cache.get("a").await.is_some());
+    cache.put("c".to_string(), response("c")).await;
+
+    assert!(cache.get("b").await.is_none());
+    assert!(cache.get("a").await.is_some());
+    assert!(cache.get("c").await.is_some());
+    assert_eq!(cache.len(), 2);
+}
+
+#[tokio::test]
+async fn test_in_memory_cache_expires_entries_after_ttl() {
+    let clock = MockClock::default();
+    let cache = InMemoryResponseCache::new(8)
+        .with_ttl(Duration::from_secs(60))
+        .with_clock(Arc::new(clock.clone()));
+    let response = vec![LlmResponse::new(Content::new("model").with_text("a"))];
+
+    cache.put("a".to_string(), response).await;
+    clock.advance(Duration::from_secs(59));
+    assert!(cache.get("a").await.is_some());
+
+    clock.advance(Duration::from_secs(1));
+    assert!(cache.get("a").await.is_none());
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn test_token_counts_are_cached_at_any_temperature() {
+    let inner = CountingModel::new();
+    let model = CachingLlm::new(inner.clone());
+    let mut with_tool = request(Some(0.9));
+    with_tool.tools.insert("calculator".to_string(), json!({ "name": "calculator" }));
+
+    let count = model.count_tokens(&with_tool).await.unwrap();
+    assert_eq!(model.count_tokens(&with_tool).await.unwrap(), count);
+    assert_eq!(inner.token_counts.load(Ordering::SeqCst), 1);
+
+    // The default breakdown counts the request with and without its tools.
+    let breakdown = model.count_tokens_breakdown(&with_tool).await.unwrap();
+    assert_eq!(model.count_tokens_breakdown(&with_tool).await.unwrap(), breakdown);
+    assert_eq!(inner.token_counts.load(Ordering::SeqCst), 3);
+    assert_eq!(breakdown.total, count);
+    assert!(breakdown.tools > 0);
+    assert_eq!(breakdown.system_instruction, 0);
+    assert_eq!(breakdown.contents + breakdown.tools, count);
+}
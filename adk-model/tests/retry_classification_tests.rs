@@ -0,0 +1,65 @@
+//! Upstream HTTP failures surface as structured, retry-aware `AdkError`s.
+
+#[cfg(feature = "openai")]
+mod openai_compatible {
+    use adk_core::{AdkError, Content, ErrorCategory, Llm, LlmRequest};
+    use adk_model::openai_compatible::{OpenAICompatible, OpenAICompatibleConfig};
+    use adk_model::retry::RetryConfig;
+    use futures::StreamExt;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn error_for(response: ResponseTemplate) -> AdkError {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(response)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = OpenAICompatibleConfig::new("test-key", "gpt-4o")
+            .with_provider_name("test")
+            .with_base_url(server.uri());
+        let client = OpenAICompatible::new(config)
+            .expect("client creation should succeed")
+            .with_retry_config(RetryConfig::disabled());
+        let request = LlmRequest::new("gpt-4o", vec![Content::new("user").with_text("Hello")]);
+
+        let mut stream = client.generate_content(request, false).await.expect("stream");
+        stream.next().await.expect("should yield an item").expect_err("should be an error")
+    }
+
+    #[tokio::test]
+    async fn rate_limit_carries_retry_after_header() {
+        let err = error_for(
+            ResponseTemplate::new(429).insert_header("retry-after", "7").set_body_string("slow"),
+        )
+        .await;
+
+        assert_eq!(err.category, ErrorCategory::RateLimited);
+        assert!(err.is_retryable());
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+        assert_eq!(err.upstream_status(), Some(429));
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retryable_without_a_hint() {
+        for status in [500, 502, 503] {
+            let err = error_for(ResponseTemplate::new(status).set_body_string("oops")).await;
+            assert!(err.is_retryable(), "{status} should be retryable");
+            assert_eq!(err.retry_after(), None);
+            assert_eq!(err.upstream_status(), Some(status));
+        }
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retryable() {
+        let err = error_for(ResponseTemplate::new(400).set_body_string("bad request")).await;
+
+        assert_eq!(err.category, ErrorCategory::InvalidInput);
+        assert!(!err.is_retryable());
+        assert_eq!(err.upstream_status(), Some(400));
+    }
+}
@@ -0,0 +1,184 @@
+//! Routing requests between models.
+
+use adk_core::{Content, Llm, LlmRequest, LlmResponse, LlmResponseStream, Part, Result};
+use adk_model::router::{RouterLlm, by_keywords, by_length, classifier, policy_fn};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Answers with a fixed reply and records the requests it receives.
+struct NamedModel {
+    name: &'static str,
+    reply: &'static str,
+    requests: Mutex<Vec<LlmRequest>>,
+}
+
+impl NamedModel {
+    fn new(name: &'static str, reply: &'static str) -> Arc<Self> {
+        Arc::new(Self { name, reply, requests: Mutex::new(Vec::new()) })
+    }
+
+    fn calls(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl Llm for NamedModel {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn count_tokens(&self, _req: &LlmRequest) -> Result<usize> {
+        Ok(self.reply.len())
+    }
+
+    async fn generate_content(&self, req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.requests.lock().unwrap().push(req);
+        let response = LlmResponse::new(Content::new("model").with_text(self.reply));
+        Ok(Box::pin(futures::stream::once(async { Ok(response) })))
+    }
+}
+
+fn prompt(text: &str) -> LlmRequest {
+    LlmRequest::new("router", vec![Content::new("user").with_text(text)])
+}
+
+async fn call(router: &RouterLlm, request: LlmRequest) -> LlmResponse {
+    let mut stream = router.generate_content(request, false).await.unwrap();
+    stream.next().await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_short_prompts_go_to_a_and_long_to_b() {
+    let model_a = NamedModel::new("model-a", "cheap answer");
+    let model_b = NamedModel::new("model-b", "careful answer");
+    let router = RouterLlm::new("router")
+        .with_route("a", model_a.clone())
+        .with_route("b", model_b.clone())
+        .with_policy(by_length(40, "a", "b"));
+
+    let short = call(&router, prompt("What is 2 + 2?")).await;
+    assert_eq!(router.last_route().as_deref(), Some("a"));
+    assert_eq!(short.answer_text().as_deref(), Some("cheap answer"));
+    assert_eq!(
+        short.provider_metadata.unwrap()["router"],
+        json!({ "route": "a", "model": "model-a" })
+    );
+
+    let long = call(
+        &router,
+        prompt("Compare the trade-offs of three consensus algorithms for a geo-distributed store."),
+    )
+    .await;
+    assert_eq!(router.last_route().as_deref(), Some("b"));
+    assert_eq!(long.answer_text().as_deref(), Some("careful answer"));
+
+    assert_eq!((model_a.calls(), model_b.calls()), (1, 1));
+    assert_eq!(model_b.requests.lock().unwrap()[0].model, "model-b");
+}
+
+#[tokio::test]
+async fn test_tool_results_keep_the_turns_route() {
+    let model_a = NamedModel::new("model-a", "a");
+    let model_b = NamedModel::new("model-b", "b");
+    let router = RouterLlm::new("router")
+        .with_route("a", model_a)
+        .with_route("b", model_b)
+        .with_policy(by_length(10, "a", "b"));
+
+    let mut request = prompt("Please look up the weather in Paris for me");
+    request.contents.push(Content {
+        role: "user".to_string(),
+        parts: vec![Part::FunctionResponse {
+            function_response: adk_core::FunctionResponseData::new("get_weather", json!("sunny")),
+            id: None,
+        }],
+    });
+    call(&router, request).await;
+
+    assert_eq!(router.last_route().as_deref(), Some("b"));
+}
+
+#[tokio::test]
+async fn test_unknown_route_and_failing_policy_use_default() {
+    let model_a = NamedModel::new("model-a", "a");
+    let router = RouterLlm::new("router")
+        .with_route("a", model_a.clone())
+        .with_policy(policy_fn(|_| "missing".to_string()));
+    call(&router, prompt("hi")).await;
+    assert_eq!(router.last_route().as_deref(), Some("a"));
+
+    let router =
+        RouterLlm::new("router").with_route("a", model_a.clone()).with_policy(Arc::new(|_| {
+            Box::pin(async { Err(adk_core::AdkError::model("classifier down")) })
+        }));
+    call(&router, prompt("hi")).await;
+    assert_eq!(model_a.calls(), 2);
+
+    let empty = RouterLlm::new("router");
+    assert!(empty.generate_content(prompt("hi"), false).await.is_err());
+}
+
+#[tokio::test]
+async fn test_keyword_and_classifier_policies() {
+    let router = RouterLlm::new("router")
+        .with_route("fast", NamedModel::new("fast", "f"))
+        .with_route("code", NamedModel::new("code", "c"))
+        .with_policy(by_keywords(["Rust", "stack trace"], "code", "fast"));
+    call(&router, prompt("Why does my rust build fail?")).await;
+    assert_eq!(router.last_route().as_deref(), Some("code"));
+
+    let judge = NamedModel::new("judge", " Smart.\n");
+    let router = RouterLlm::new("router")
+        .with_route("fast", NamedModel::new("fast", "f"))
+        .with_route("smart", NamedModel::new("smart", "s"))
+        .with_policy(classifier(
+            judge.clone(),
+            &[("fast", "greetings and simple facts"), ("smart", "multi-step reasoning")],
+        ));
+    call(&router, prompt("Plan a migration")).await;
+    assert_eq!(router.last_route().as_deref(), Some("smart"));
+    let judge_prompt =
+        judge.requests.lock().unwrap()[0].contents[0].parts[0].text().unwrap().to_string();
+    assert!(judge_prompt.contains("- smart: multi-step reasoning"));
+    assert!(judge_prompt.contains("Plan a migration"));
+}
+
+#[tokio::test]
+async fn test_agent_prompts_keep_the_invocations_route() {
+    let router = RouterLlm::new("router")
+        .with_route("a", NamedModel::new("model-a", "a"))
+        .with_route("b", NamedModel::new("model-b", "b"))
+        .with_policy(by_length(10, "a", "b"));
+    let question = Content::new("user").with_text("Summarize the quarterly report in detail");
+
+    let mut request = LlmRequest::new("router", vec![question.clone()]);
+    request.contents.push(Content::new("model").with_text("The report covers"));
+    request.contents.push(Content::new("user").with_text("Continue."));
+    adk_core::with_invocation_user_content(question, call(&router, request)).await;
+
+    assert_eq!(router.last_route().as_deref(), Some("b"));
+}
+
+#[tokio::test]
+async fn test_token_counts_and_batches_use_the_selected_route() {
+    let model_a = NamedModel::new("model-a", "cheap answer");
+    let model_b = NamedModel::new("model-b", "careful answer");
+    let router = RouterLlm::new("router")
+        .with_route("a", model_a.clone())
+        .with_route("b", model_b.clone())
+        .with_policy(by_length(20, "a", "b"));
+    let long = "Compare three consensus algorithms for a geo-distributed store.";
+
+    assert_eq!(router.count_tokens(&prompt("hi")).await.unwrap(), "cheap answer".len());
+    assert_eq!(router.count_tokens_breakdown(&prompt(long)).await.unwrap().total, 14);
+
+    let responses = router.generate_batch(vec![prompt("hi"), prompt(long), prompt("hey")], 2).await;
+    let answers: Vec<_> =
+        responses.into_iter().map(|response| response.unwrap().answer_text().unwrap()).collect();
+    assert_eq!(answers, ["cheap answer", "careful answer", "cheap answer"]);
+    assert_eq!((model_a.calls(), model_b.calls()), (2, 1));
+    assert_eq!(model_b.requests.lock().unwrap()[0].model, "model-b");
+}
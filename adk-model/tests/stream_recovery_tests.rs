@@ -0,0 +1,138 @@
+//! Recovery from streams that drop mid-generation.
+
+use adk_core::{AdkError, Content, Llm, LlmRequest, LlmResponse, LlmResponseStream, Result};
+use adk_model::stream_recovery::{
+    STREAM_INTERRUPTED, StreamRecovery, StreamRecoveryLlm, is_stream_interrupted,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+
+/// A transport that plays one scripted stream per call; `None` drops the connection.
+struct DroppingModel {
+    scripts: Mutex<Vec<Vec<Option<&'static str>>>>,
+    requests: Mutex<Vec<LlmRequest>>,
+}
+
+impl DroppingModel {
+    fn new(scripts: Vec<Vec<Option<&'static str>>>) -> Arc<Self> {
+        Arc::new(Self { scripts: Mutex::new(scripts), requests: Mutex::new(Vec::new()) })
+    }
+}
+
+#[async_trait]
+impl Llm for DroppingModel {
+    fn name(&self) -> &str {
+        "dropping"
+    }
+
+    fn supports_response_schema(&self) -> bool {
+        false
+    }
+
+    async fn count_tokens(&self, _req: &LlmRequest) -> Result<usize> {
+        Ok(42)
+    }
+
+    async fn generate_content(&self, req: LlmRequest, _stream: bool) -> Result<LlmResponseStream> {
+        self.requests.lock().unwrap().push(req);
+        let script = self.scripts.lock().unwrap().remove(0);
+        let items: Vec<Result<LlmResponse>> = script
+            .into_iter()
+            .map(|item| match item {
+                Some(text) => {
+                    let mut chunk = LlmResponse::new(Content::new("model").with_text(text));
+                    chunk.partial = true;
+                    chunk.turn_complete = false;
+                    chunk.finish_reason = None;
+                    Ok(chunk)
+                }
+                None => Err(AdkError::model("error decoding response body: connection reset")),
+            })
+            .collect();
+        Ok(Box::pin(futures::stream::iter(items)))
+    }
+}
+
+fn request() -> LlmRequest {
+    LlmRequest::new("test", vec![Content::new("user").with_text("Write a long story")])
+}
+
+async fn collect(model: &dyn Llm) -> Vec<Result<LlmResponse>> {
+    model.generate_content(request(), true).await.unwrap().collect().await
+}
+
+fn text_of(responses: &[Result<LlmResponse>]) -> String {
+    responses.iter().filter_map(|r| r.as_ref().ok()?.answer_text()).collect()
+}
+
+#[tokio::test]
+async fn test_dropped_stream_keeps_partial_content() {
+    let inner = DroppingModel::new(vec![vec![Some("Once upon "), Some("a time"), None]]);
+    let model = StreamRecoveryLlm::new(inner);
+
+    let responses = collect(&model).await;
+
+    assert!(responses.iter().all(Result::is_ok));
+    assert_eq!(text_of(&responses), "Once upon a time");
+    let marker = responses.last().unwrap().as_ref().unwrap();
+    assert!(is_stream_interrupted(marker));
+    assert_eq!(marker.error_code.as_deref(), Some(STREAM_INTERRUPTED));
+    assert!(marker.error_message.as_deref().unwrap().contains("connection reset"));
+    assert!(marker.content.is_none());
+}
+
+#[tokio::test]
+async fn test_resume_continues_from_partial_text() {
+    let inner = DroppingModel::new(vec![
+        vec![Some("Once upon "), None],
+        vec![Some("a time"), Some(" there was"), None],
+        vec![Some(" a fox.")],
+    ]);
+    let model = StreamRecoveryLlm::new(inner.clone())
+        .with_recovery(StreamRecovery::Resume { max_attempts: 2 });
+
+    let responses = collect(&model).await;
+
+    assert_eq!(text_of(&responses), "Once upon a time there was a fox.");
+    assert!(!responses.iter().any(|r| is_stream_interrupted(r.as_ref().unwrap())));
+    let requests = inner.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    let prefill = requests[2].contents.last().unwrap();
+    assert_eq!(prefill.role, "model");
+    assert_eq!(prefill.parts[0].text(), Some("Once upon a time there was"));
+}
+
+#[tokio::test]
+async fn test_resume_falls_back_to_marker_when_attempts_run_out() {
+    let inner = DroppingModel::new(vec![vec![Some("Once"), None], vec![Some(" upon"), None]]);
+    let model =
+        StreamRecoveryLlm::new(inner).with_recovery(StreamRecovery::Resume { max_attempts: 1 });
+
+    let responses = collect(&model).await;
+
+    assert_eq!(text_of(&responses), "Once upon");
+    assert!(is_stream_interrupted(responses.last().unwrap().as_ref().unwrap()));
+}
+
+#[tokio::test]
+async fn test_error_before_content_and_fail_mode_propagate() {
+    let inner = DroppingModel::new(vec![vec![None]]);
+    let responses = collect(&StreamRecoveryLlm::new(inner)).await;
+    assert!(responses[0].is_err());
+
+    let inner = DroppingModel::new(vec![vec![Some("Once"), None]]);
+    let model = StreamRecoveryLlm::new(inner).with_recovery(StreamRecovery::Fail);
+    let responses = collect(&model).await;
+    assert_eq!(text_of(&responses), "Once");
+    assert!(responses.last().unwrap().is_err());
+}
+
+#[tokio::test]
+async fn test_capabilities_come_from_the_wrapped_model() {
+    let model = StreamRecoveryLlm::new(DroppingModel::new(vec![]));
+
+    assert!(!model.supports_response_schema());
+    assert_eq!(model.count_tokens(&request()).await.unwrap(), 42);
+    assert_eq!(model.count_tokens_breakdown(&request()).await.unwrap().total, 42);
+}
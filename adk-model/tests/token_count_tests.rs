@@ -0,0 +1,97 @@
+//! `Llm::count_tokens` for each provider, checked against known token counts.
+
+use adk_core::{Content, Llm, LlmRequest, estimate_text_tokens};
+
+const PROSE: &str = "The quick brown fox jumps over the lazy dog. Large language models read text as tokens, which are usually whole words or pieces of words, so the same sentence can cost a different amount depending on the tokenizer.";
+const CODE: &str = "fn fibonacci(n: u64) -> u64 {\n    match n {\n        0 | 1 => n,\n        _ => fibonacci(n - 1) + fibonacci(n - 2),\n    }\n}\n";
+const JSON: &str = r#"{"city": "Lisbon", "forecast": [{"day": "Monday", "high_c": 24, "low_c": 16, "conditions": "sunny"}, {"day": "Tuesday", "high_c": 22, "low_c": 15, "conditions": "cloudy"}]}"#;
+const CJK: &str = "日本語のテキストをトークンに分割します。東京は日本の首都です。";
+
+/// Each sample with its token count under `cl100k_base`.
+const SAMPLES: [(&str, usize); 4] = [(PROSE, 42), (CODE, 43), (JSON, 66), (CJK, 30)];
+
+fn request(model: &str, text: &str) -> LlmRequest {
+    LlmRequest::new(model, vec![Content::new("user").with_text(text)])
+}
+
+#[test]
+fn test_estimate_is_within_tolerance_of_known_counts() {
+    for (text, known) in SAMPLES {
+        let estimate = estimate_text_tokens(text);
+        let error = estimate.abs_diff(known) as f64 / known as f64;
+        assert!(error <= 0.2, "estimated {estimate} tokens, expected about {known}: {text}");
+    }
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_openai_count_matches_tokenizer() {
+    use adk_model::openai::{OpenAIClient, OpenAIConfig};
+
+    let client = OpenAIClient::new(OpenAIConfig::new("test-key", "gpt-4")).unwrap();
+    for (text, known) in SAMPLES {
+        // One user message: 3 wrapping tokens and 1 role token, plus 3 priming the reply
+        assert_eq!(client.count_tokens(&request("gpt-4", text)).await.unwrap(), known + 7);
+    }
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_anthropic_count_uses_count_tokens_endpoint() {
+    use adk_model::anthropic::{AnthropicClient, AnthropicConfig};
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/count_tokens"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "input_tokens": 53 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = AnthropicClient::new(
+        AnthropicConfig::new("test-key", "claude-sonnet-4-6").with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let count = Llm::count_tokens(&client, &request("claude-sonnet-4-6", PROSE)).await.unwrap();
+
+    assert_eq!(count, 53);
+    let received = server.received_requests().await.unwrap();
+    let body: serde_json::Value = received[0].body_json().unwrap();
+    assert_eq!(body["model"], "claude-sonnet-4-6");
+    assert_eq!(body["messages"][0]["content"][0]["text"], PROSE);
+}
+
+#[cfg(feature = "gemini")]
+#[tokio::test]
+async fn test_gemini_count_uses_count_tokens_endpoint() {
+    use adk_model::GeminiModel;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-flash:countTokens"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "totalTokens": 41 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let model = GeminiModel::new_with_base_url(
+        "test-key",
+        "gemini-2.5-flash",
+        format!("{}/v1beta/", server.uri()),
+    )
+    .unwrap();
+
+    let count = model.count_tokens(&request("gemini-2.5-flash", PROSE)).await.unwrap();
+
+    assert_eq!(count, 41);
+    let received = server.received_requests().await.unwrap();
+    let body: serde_json::Value = received[0].body_json().unwrap();
+    let generate_request = &body["generateContentRequest"];
+    assert_eq!(generate_request["model"], "models/gemini-2.5-flash");
+    assert_eq!(generate_request["contents"][0]["parts"][0]["text"], PROSE);
+}
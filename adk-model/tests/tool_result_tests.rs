@@ -0,0 +1,286 @@
+//! Tool calls and results rendered for each provider with matching ids.
+
+use adk_core::{Content, FunctionResponseData, Part};
+use adk_model::prompt_layout::PromptLayout;
+use adk_model::tool_result::link_tool_call_ids;
+use serde_json::json;
+
+fn call(name: &str, id: Option<&str>) -> Part {
+    Part::FunctionCall {
+        name: name.to_string(),
+        args: json!({ "city": "Paris" }),
+        id: id.map(str::to_string),
+        thought_signature: None,
+    }
+}
+
+fn result(name: &str, id: Option<&str>, response: serde_json::Value) -> Part {
+    Part::FunctionResponse {
+        function_response: FunctionResponseData::new(name, response),
+        id: id.map(str::to_string),
+    }
+}
+
+/// A turn recorded from Gemini: two parallel calls to the same tool and one
+/// to another, none of them with ids, answered in one content.
+fn gemini_history() -> Vec<Content> {
+    vec![
+        Content::new("user").with_text("Weather and time in Paris and Rome?"),
+        Content {
+            role: "model".to_string(),
+            parts: vec![
+                call("get_weather", None),
+                call("get_weather", None),
+                call("get_time", None),
+            ],
+        },
+        Content {
+            role: "function".to_string(),
+            parts: vec![
+                result("get_weather", None, json!("sunny")),
+                result("get_time", None, json!("12:00")),
+                result("get_weather", None, json!("rainy")),
+            ],
+        },
+    ]
+}
+
+fn call_ids(contents: &[Content]) -> Vec<Option<String>> {
+    ids(contents, |part| match part {
+        Part::FunctionCall { id, .. } => Some(id.clone()),
+        _ => None,
+    })
+}
+
+fn result_ids(contents: &[Content]) -> Vec<Option<String>> {
+    ids(contents, |part| match part {
+        Part::FunctionResponse { id, .. } => Some(id.clone()),
+        _ => None,
+    })
+}
+
+fn ids(contents: &[Content], id: impl Fn(&Part) -> Option<Option<String>>) -> Vec<Option<String>> {
+    contents.iter().flat_map(|content| &content.parts).filter_map(id).collect()
+}
+
+fn some(ids: &[&str]) -> Vec<Option<String>> {
+    ids.iter().map(|id| Some(id.to_string())).collect()
+}
+
+#[test]
+fn test_results_take_the_id_of_the_call_they_answer() {
+    let mut contents = gemini_history();
+    link_tool_call_ids(&mut contents);
+
+    assert_eq!(call_ids(&contents), some(&["call_0", "call_1", "call_2"]));
+    // Same-name results answer the calls in order.
+    assert_eq!(result_ids(&contents), some(&["call_0", "call_2", "call_1"]));
+}
+
+#[test]
+fn test_existing_ids_are_kept_and_fresh_ids_do_not_collide() {
+    let mut contents = vec![
+        Content {
+            role: "model".to_string(),
+            parts: vec![call("a", Some("call_0")), call("b", None)],
+        },
+        Content {
+            role: "function".to_string(),
+            // Out of order, and one result with a stale id from another provider.
+            parts: vec![
+                result("b", Some("toolu_stale"), json!(2)),
+                result("a", Some("call_0"), json!(1)),
+            ],
+        },
+        Content { role: "function".to_string(), parts: vec![result("c", None, json!(3))] },
+    ];
+    link_tool_call_ids(&mut contents);
+
+    assert_eq!(call_ids(&contents), some(&["call_0", "call_1"]));
+    assert_eq!(
+        result_ids(&contents),
+        vec![Some("call_1".to_string()), Some("call_0".to_string()), None]
+    );
+}
+
+#[test]
+fn test_layouts() {
+    // Gemini matches results by name: the history is sent as recorded.
+    let gemini = PromptLayout::gemini().normalize(&gemini_history()).contents;
+    assert_eq!(call_ids(&gemini), vec![None, None, None]);
+    assert_eq!(gemini.len(), 3);
+
+    let anthropic = PromptLayout::anthropic().normalize(&gemini_history()).contents;
+    assert_eq!(result_ids(&anthropic), some(&["call_0", "call_2", "call_1"]));
+    assert_eq!(anthropic.len(), 3);
+
+    // Chat Completions: one message per result, in order.
+    let openai = PromptLayout::openai().normalize(&gemini_history()).contents;
+    assert_eq!(openai.len(), 5);
+    assert!(openai[2..].iter().all(|content| content.role == "function"));
+    assert!(openai[2..].iter().all(|content| content.parts.len() == 1));
+    assert_eq!(result_ids(&openai), some(&["call_0", "call_2", "call_1"]));
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_openai_tool_messages_reference_their_calls() {
+    use adk_core::{Llm, LlmRequest};
+    use adk_model::RetryConfig;
+    use adk_model::openai_compatible::{OpenAICompatible, OpenAICompatibleConfig};
+    use futures::StreamExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Sunny and rainy." },
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    let config = OpenAICompatibleConfig::new("test-key", "gpt-4o")
+        .with_provider_name("test")
+        .with_base_url(server.uri());
+    let model = OpenAICompatible::new(config).unwrap().with_retry_config(RetryConfig::disabled());
+
+    let mut stream =
+        model.generate_content(LlmRequest::new("gpt-4o", gemini_history()), false).await.unwrap();
+    while stream.next().await.is_some() {}
+
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests[0].body_json().unwrap();
+    let messages = body["messages"].as_array().unwrap();
+    let calls: Vec<_> =
+        messages[1]["tool_calls"].as_array().unwrap().iter().map(|c| c["id"].clone()).collect();
+    assert_eq!(calls, [json!("call_0"), json!("call_1"), json!("call_2")]);
+
+    let tool_messages: Vec<_> =
+        messages[2..].iter().map(|m| (m["role"].clone(), m["tool_call_id"].clone())).collect();
+    assert_eq!(
+        tool_messages,
+        [
+            (json!("tool"), json!("call_0")),
+            (json!("tool"), json!("call_2")),
+            (json!("tool"), json!("call_1")),
+        ]
+    );
+    assert_eq!(messages[3]["content"], "12:00");
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_anthropic_tool_results_reference_their_tool_use() {
+    use adk_core::{Llm, LlmRequest};
+    use adk_model::RetryConfig;
+    use adk_model::anthropic::{AnthropicClient, AnthropicConfig};
+    use futures::StreamExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "content": [{ "type": "text", "text": "Sunny and rainy." }],
+            "stop_reason": "end_turn",
+            "usage": { "input_tokens": 10, "output_tokens": 4 }
+        })))
+        .mount(&server)
+        .await;
+    let config = AnthropicConfig::new("test-key", "claude-sonnet-4-5").with_base_url(server.uri());
+    let model = AnthropicClient::new(config).unwrap().with_retry_config(RetryConfig::disabled());
+
+    let mut stream = model
+        .generate_content(LlmRequest::new("claude-sonnet-4-5", gemini_history()), false)
+        .await
+        .unwrap();
+    while stream.next().await.is_some() {}
+
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests[0].body_json().unwrap();
+    let messages = body["messages"].as_array().unwrap();
+    // The leading user text moves to `system`; the tool use comes first.
+    let tool_uses: Vec<_> =
+        messages[0]["content"].as_array().unwrap().iter().map(|b| b["id"].clone()).collect();
+    assert_eq!(tool_uses, [json!("call_0"), json!("call_1"), json!("call_2")]);
+
+    // All results in the single user message that follows the tool use.
+    assert_eq!(messages[1]["role"], "user");
+    let results: Vec<_> = messages[1]["content"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| (b["type"].clone(), b["tool_use_id"].clone()))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            (json!("tool_result"), json!("call_0")),
+            (json!("tool_result"), json!("call_2")),
+            (json!("tool_result"), json!("call_1")),
+        ]
+    );
+}
+
+#[cfg(feature = "gemini")]
+#[tokio::test]
+async fn test_gemini_function_responses_keep_openai_ids() {
+    use adk_core::{Llm, LlmRequest};
+    use adk_model::GeminiModel;
+    use futures::StreamExt;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path_regex(r":generateContent$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": { "role": "model", "parts": [{ "text": "Sunny." }] },
+                "finishReason": "STOP"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    let model = GeminiModel::new_with_base_url(
+        "test-key",
+        "gemini-2.5-flash",
+        format!("{}/v1beta/", server.uri()),
+    )
+    .unwrap();
+
+    // A turn recorded from OpenAI, continued on Gemini.
+    let contents = vec![
+        Content::new("user").with_text("Weather in Paris?"),
+        Content { role: "model".to_string(), parts: vec![call("get_weather", Some("call_abc"))] },
+        Content {
+            role: "function".to_string(),
+            parts: vec![result("get_weather", Some("call_abc"), json!({ "sky": "sunny" }))],
+        },
+    ];
+    let mut stream =
+        model.generate_content(LlmRequest::new("gemini-2.5-flash", contents), false).await.unwrap();
+    while stream.next().await.is_some() {}
+
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests[0].body_json().unwrap();
+    let call = &body["contents"][1]["parts"][0]["functionCall"];
+    let response = &body["contents"][2]["parts"][0]["functionResponse"];
+    assert_eq!(call["name"], "get_weather");
+    assert_eq!(response["name"], "get_weather");
+    assert_eq!(call["id"], "call_abc");
+    assert_eq!(response["id"], "call_abc");
+    assert_eq!(response["response"]["sky"], "sunny");
+}
@@ -72,6 +72,8 @@ mod enhanced_manager;
 mod enhanced_plugin;
 mod hook_result;
 mod manager;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod plugin;
 
 pub mod adapted_plugin;
@@ -85,4 +87,6 @@ pub use hook_result::{
     AfterModelCallResult, AfterToolCallResult, BeforeModelCallResult, BeforeToolCallResult,
 };
 pub use manager::{PluginManager, PluginManagerConfig};
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsPlugin;
 pub use plugin::{Plugin, PluginBuilder, PluginConfig};
@@ -0,0 +1,200 @@
+//! Metrics plugin
+//!
+//! [`MetricsPlugin`] records per-turn counters into an OpenTelemetry
+//! [`Meter`] from the agent, model, and tool callbacks, so agents get metrics
+//! without wiring OpenTelemetry into each callback by hand.
+//!
+//! | Counter | Incremented | Labels |
+//! |---------|-------------|--------|
+//! | `adk.agent.turns` | before each agent execution | `agent` |
+//! | `adk.model.calls` | before each model call | `agent`, `model` |
+//! | `adk.model.tokens` | after each model response with usage | `agent`, `model`, `token_type` (`input` / `output`) |
+//! | `adk.tool.calls` | before each tool execution | `agent`, `tool` |
+//! | `adk.errors` | on each model or tool error | `agent`, `source` (`model` / `tool`), plus `model` or `tool` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_plugin::{MetricsPlugin, PluginManager};
+//!
+//! let meter = opentelemetry::global::meter("my-app");
+//! let manager = PluginManager::new(vec![MetricsPlugin::new(&meter).build()]);
+//! ```
+
+use crate::plugin::{Plugin, PluginBuilder};
+use adk_core::{BeforeModelResult, CallbackContext};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Label used when a callback does not know the tool or model name.
+const UNKNOWN: &str = "unknown";
+
+/// The counters a [`MetricsPlugin`] writes to, plus the model each
+/// invocation last called so responses can be labeled with it.
+struct Instruments {
+    turns: Counter<u64>,
+    model_calls: Counter<u64>,
+    tokens: Counter<u64>,
+    tool_calls: Counter<u64>,
+    errors: Counter<u64>,
+    models: Mutex<HashMap<String, String>>,
+}
+
+impl Instruments {
+    fn model_for(&self, ctx: &dyn CallbackContext) -> String {
+        self.models
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(ctx.invocation_id())
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN.to_string())
+    }
+}
+
+/// Records turn, model call, token, tool call, and error counters into a
+/// [`Meter`].
+///
+/// Build it into a [`Plugin`] with [`build`](Self::build) and register it with a
+/// [`PluginManager`](crate::PluginManager) like any other plugin.
+pub struct MetricsPlugin {
+    name: String,
+    instruments: Arc<Instruments>,
+}
+
+impl MetricsPlugin {
+    /// Create the plugin's counters on `meter`.
+    pub fn new(meter: &Meter) -> Self {
+        let instruments = Instruments {
+            turns: meter
+                .u64_counter("adk.agent.turns")
+                .with_description("Agent executions")
+                .build(),
+            model_calls: meter
+                .u64_counter("adk.model.calls")
+                .with_description("Model calls")
+                .build(),
+            tokens: meter
+                .u64_counter("adk.model.tokens")
+                .with_description("Tokens reported by model responses")
+                .with_unit("{token}")
+                .build(),
+            tool_calls: meter
+                .u64_counter("adk.tool.calls")
+                .with_description("Tool executions")
+                .build(),
+            errors: meter
+                .u64_counter("adk.errors")
+                .with_description("Model and tool errors")
+                .build(),
+            models: Mutex::new(HashMap::new()),
+        };
+        Self { name: "metrics".to_string(), instruments: Arc::new(instruments) }
+    }
+
+    /// Set the plugin name. Defaults to `"metrics"`.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Build the [`Plugin`].
+    pub fn build(self) -> Plugin {
+        let on_turn = self.instruments.clone();
+        let on_turn_end = self.instruments.clone();
+        let on_model = self.instruments.clone();
+        let on_response = self.instruments.clone();
+        let on_model_error = self.instruments.clone();
+        let on_tool = self.instruments.clone();
+        let on_tool_error = self.instruments;
+
+        PluginBuilder::new(self.name)
+            .before_agent(Box::new(move |ctx| {
+                on_turn.turns.add(1, &[KeyValue::new("agent", ctx.agent_name().to_string())]);
+                Box::pin(async { Ok(None) })
+            }))
+            .after_agent(Box::new(move |ctx| {
+                on_turn_end
+                    .models
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(ctx.invocation_id());
+                Box::pin(async { Ok(None) })
+            }))
+            .before_model(Box::new(move |ctx, request| {
+                on_model.model_calls.add(
+                    1,
+                    &[
+                        KeyValue::new("agent", ctx.agent_name().to_string()),
+                        KeyValue::new("model", request.model.clone()),
+                    ],
+                );
+                on_model
+                    .models
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(ctx.invocation_id().to_string(), request.model.clone());
+                Box::pin(async move { Ok(BeforeModelResult::Continue(request)) })
+            }))
+            .after_model(Box::new(move |ctx, response| {
+                if let Some(usage) = &response.usage_metadata {
+                    let agent = KeyValue::new("agent", ctx.agent_name().to_string());
+                    let model = KeyValue::new("model", on_response.model_for(ctx.as_ref()));
+                    for (token_type, count) in [
+                        ("input", usage.prompt_token_count),
+                        ("output", usage.candidates_token_count),
+                    ] {
+                        on_response.tokens.add(
+                            u64::try_from(count).unwrap_or(0),
+                            &[
+                                agent.clone(),
+                                model.clone(),
+                                KeyValue::new("token_type", token_type),
+                            ],
+                        );
+                    }
+                }
+                Box::pin(async { Ok(None) })
+            }))
+            .on_model_error(Box::new(move |ctx, request, _error| {
+                on_model_error.errors.add(
+                    1,
+                    &[
+                        KeyValue::new("agent", ctx.agent_name().to_string()),
+                        KeyValue::new("source", "model"),
+                        KeyValue::new("model", request.model),
+                    ],
+                );
+                Box::pin(async { Ok(None) })
+            }))
+            .before_tool(Box::new(move |ctx| {
+                on_tool.tool_calls.add(
+                    1,
+                    &[
+                        KeyValue::new("agent", ctx.agent_name().to_string()),
+                        KeyValue::new("tool", ctx.tool_name().unwrap_or(UNKNOWN).to_string()),
+                    ],
+                );
+                Box::pin(async { Ok(None) })
+            }))
+            .on_tool_error(Box::new(move |ctx, tool, _args, _error| {
+                on_tool_error.errors.add(
+                    1,
+                    &[
+                        KeyValue::new("agent", ctx.agent_name().to_string()),
+                        KeyValue::new("source", "tool"),
+                        KeyValue::new("tool", tool.name().to_string()),
+                    ],
+                );
+                Box::pin(async { Ok(None) })
+            }))
+            .build()
+    }
+}
+
+impl From<MetricsPlugin> for Plugin {
+    fn from(plugin: MetricsPlugin) -> Self {
+        plugin.build()
+    }
+}
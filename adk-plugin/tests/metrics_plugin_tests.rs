@@ -0,0 +1,146 @@
+//! Counters recorded by `MetricsPlugin` through a `PluginManager`.
+
+use adk_core::{
+    BeforeModelResult, CallbackContext, Content, LlmRequest, LlmResponse, ReadonlyContext,
+    UsageMetadata, async_trait,
+};
+use adk_plugin::{MetricsPlugin, PluginManager};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::MeterProvider;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+use std::sync::{Arc, OnceLock};
+
+/// A callback context for the `support` agent, optionally inside a tool call.
+struct TestContext {
+    tool: Option<&'static str>,
+}
+
+impl ReadonlyContext for TestContext {
+    fn invocation_id(&self) -> &str {
+        "invocation-1"
+    }
+
+    fn agent_name(&self) -> &str {
+        "support"
+    }
+
+    fn user_id(&self) -> &str {
+        "user-1"
+    }
+
+    fn app_name(&self) -> &str {
+        "metrics-app"
+    }
+
+    fn session_id(&self) -> &str {
+        "session-1"
+    }
+
+    fn branch(&self) -> &str {
+        ""
+    }
+
+    fn user_content(&self) -> &Content {
+        static CONTENT: OnceLock<Content> = OnceLock::new();
+        CONTENT.get_or_init(|| Content::new("user").with_text("Where is order 42?"))
+    }
+}
+
+#[async_trait]
+impl CallbackContext for TestContext {
+    fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
+        None
+    }
+
+    fn tool_name(&self) -> Option<&str> {
+        self.tool
+    }
+}
+
+fn ctx(tool: Option<&'static str>) -> Arc<dyn CallbackContext> {
+    Arc::new(TestContext { tool })
+}
+
+/// The exported value of `counter` for the data point with exactly `labels`.
+fn counter_value(exporter: &InMemoryMetricExporter, counter: &str, labels: &[KeyValue]) -> u64 {
+    let metrics = exporter.get_finished_metrics().unwrap();
+    let resource = metrics.last().expect("metrics were exported");
+    let metric = resource
+        .scope_metrics()
+        .flat_map(|scope| scope.metrics())
+        .find(|metric| metric.name() == counter)
+        .unwrap_or_else(|| panic!("no {counter} metric"));
+    let AggregatedMetrics::U64(MetricData::Sum(sum)) = metric.data() else {
+        panic!("{counter} is not a u64 counter");
+    };
+    sum.data_points()
+        .find(|point| {
+            let mut attributes: Vec<_> = point.attributes().cloned().collect();
+            attributes.sort_by(|a, b| a.key.cmp(&b.key));
+            let mut expected = labels.to_vec();
+            expected.sort_by(|a, b| a.key.cmp(&b.key));
+            attributes == expected
+        })
+        .map(|point| point.value())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_counters_for_turn_with_one_model_call_and_two_tool_calls() {
+    let exporter = InMemoryMetricExporter::default();
+    let provider = SdkMeterProvider::builder()
+        .with_reader(PeriodicReader::builder(exporter.clone()).build())
+        .build();
+    let manager = PluginManager::new(vec![MetricsPlugin::new(&provider.meter("test")).build()]);
+
+    manager.run_before_agent(ctx(None)).await.unwrap();
+    let request = LlmRequest::new("gemini-2.5-flash", vec![]);
+    let result = manager.run_before_model(ctx(None), request).await.unwrap();
+    assert!(matches!(result, BeforeModelResult::Continue(_)));
+    let mut response = LlmResponse::new(Content::new("model").with_text("Checking."));
+    response.usage_metadata = Some(UsageMetadata {
+        prompt_token_count: 120,
+        candidates_token_count: 30,
+        total_token_count: 150,
+        ..Default::default()
+    });
+    assert!(manager.run_after_model(ctx(None), response).await.unwrap().is_none());
+    for tool in ["lookup_order", "track_shipment"] {
+        manager.run_before_tool(ctx(Some(tool))).await.unwrap();
+        manager.run_after_tool(ctx(Some(tool))).await.unwrap();
+    }
+    manager.run_after_agent(ctx(None)).await.unwrap();
+    provider.force_flush().unwrap();
+
+    let agent = KeyValue::new("agent", "support");
+    let model = KeyValue::new("model", "gemini-2.5-flash");
+    assert_eq!(counter_value(&exporter, "adk.agent.turns", std::slice::from_ref(&agent)), 1);
+    assert_eq!(counter_value(&exporter, "adk.model.calls", &[agent.clone(), model.clone()]), 1);
+    assert_eq!(
+        counter_value(
+            &exporter,
+            "adk.model.tokens",
+            &[agent.clone(), model.clone(), KeyValue::new("token_type", "input")]
+        ),
+        120
+    );
+    assert_eq!(
+        counter_value(
+            &exporter,
+            "adk.model.tokens",
+            &[agent.clone(), model, KeyValue::new("token_type", "output")]
+        ),
+        30
+    );
+    for tool in ["lookup_order", "track_shipment"] {
+        assert_eq!(
+            counter_value(
+                &exporter,
+                "adk.tool.calls",
+                &[agent.clone(), KeyValue::new("tool", tool)]
+            ),
+            1
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! LRU cache for query and chunk embeddings.
+//!
+//! An [`EmbeddingCache`] attached to a [`RagPipeline`](crate::RagPipeline)
+//! lets identical queries skip the embedding call. Entries are keyed by the
+//! provider's [`model_name`](crate::EmbeddingProvider::model_name) and the
+//! text with surrounding whitespace trimmed and inner whitespace runs collapsed
+//! to a single space. Case is preserved because embeddings are case-sensitive.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use adk_rag::{EmbeddingCache, RagPipeline};
+//!
+//! let pipeline = RagPipeline::builder()
+//!     .config(config)
+//!     .embedding_provider(Arc::new(embedder))
+//!     .vector_store(Arc::new(store))
+//!     .chunker(Arc::new(chunker))
+//!     .embedding_cache(
+//!         EmbeddingCache::new(1024).with_ttl(Duration::from_secs(600)).with_chunk_embeddings(true),
+//!     )
+//!     .build()?;
+//!
+//! pipeline.query("docs", "refund policy").await?;
+//! pipeline.query("docs", "refund   policy ").await?; // served from the cache
+//! assert_eq!(pipeline.embedding_cache_stats().unwrap().hits, 1);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type CacheKey = (String, String);
+
+/// Hit and miss counters for an [`EmbeddingCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingCacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to call the embedding provider.
+    pub misses: u64,
+    /// Entries currently cached.
+    pub entries: usize,
+}
+
+impl EmbeddingCacheStats {
+    /// Fraction of lookups answered from the cache, or `0.0` before any lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+/// A bounded LRU cache of embeddings with an optional time-to-live.
+///
+/// Cloning is cheap and clones share the same entries and counters, so one
+/// cache can serve several pipelines that use the same model.
+#[derive(Debug, Clone)]
+pub struct EmbeddingCache {
+    inner: Arc<Inner>,
+    ttl: Option<Duration>,
+    chunk_embeddings: bool,
+}
+
+#[derive(Debug)]
+struct Inner {
+    capacity: usize,
+    entries: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    map: HashMap<CacheKey, (Arc<Vec<f32>>, Instant)>,
+    order: VecDeque<CacheKey>,
+}
+
+impl EmbeddingCache {
+    /// Create a cache holding at most `capacity` embeddings.
+    ///
+    /// Only query embeddings are cached until
+    /// [`with_chunk_embeddings`](Self::with_chunk_embeddings) is enabled.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                entries: Mutex::new(Lru::default()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+            ttl: None,
+            chunk_embeddings: false,
+        }
+    }
+
+    /// Expire entries `ttl` after they were inserted.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Also cache chunk embeddings during ingestion, so chunks with identical
+    /// text are only embedded once.
+    pub fn with_chunk_embeddings(mut self, enabled: bool) -> Self {
+        self.chunk_embeddings = enabled;
+        self
+    }
+
+    /// Maximum number of cached embeddings.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Time-to-live for entries, if any.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Whether chunk embeddings are cached during ingestion.
+    pub fn caches_chunk_embeddings(&self) -> bool {
+        self.chunk_embeddings
+    }
+
+    /// Look up the embedding for `text` produced by `model`, counting a hit or miss.
+    pub fn get(&self, model: &str, text: &str) -> Option<Vec<f32>> {
+        let key = cache_key(model, text);
+        let found = self.lock().get(&key, self.ttl);
+        let counter = if found.is_some() { &self.inner.hits } else { &self.inner.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found.map(|embedding| embedding.as_ref().clone())
+    }
+
+    /// Store the embedding for `text` produced by `model`, evicting the least
+    /// recently used entry when full.
+    pub fn insert(&self, model: &str, text: &str, embedding: Vec<f32>) {
+        if self.inner.capacity == 0 {
+            return;
+        }
+        self.lock().insert(cache_key(model, text), Arc::new(embedding), self.inner.capacity);
+    }
+
+    /// Current hit and miss counters.
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.lock().map.len(),
+        }
+    }
+
+    /// Remove all entries. Counters are kept.
+    pub fn clear(&self) {
+        let mut lru = self.lock();
+        lru.map.clear();
+        lru.order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.inner.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Lru {
+    fn get(&mut self, key: &CacheKey, ttl: Option<Duration>) -> Option<Arc<Vec<f32>>> {
+        let (embedding, inserted_at) = self.map.get(key)?;
+        if ttl.is_some_and(|ttl| inserted_at.elapsed() > ttl) {
+            self.map.remove(key);
+            self.order.retain(|k| k != key);
+            return None;
+        }
+        let embedding = Arc::clone(embedding);
+        self.touch(key.clone());
+        Some(embedding)
+    }
+
+    fn insert(&mut self, key: CacheKey, embedding: Arc<Vec<f32>>, capacity: usize) {
+        if self.map.contains_key(&key) {
+            self.order.retain(|k| k != &key);
+        } else if self.map.len() >= capacity
+            && let Some(evicted) = self.order.pop_front()
+        {
+            self.map.remove(&evicted);
+        }
+        self.map.insert(key.clone(), (embedding, Instant::now()));
+        self.order.push_back(key);
+    }
+
+    fn touch(&mut self, key: CacheKey) {
+        self.order.retain(|k| k != &key);
+        self.order.push_back(key);
+    }
+}
+
+fn cache_key(model: &str, text: &str) -> CacheKey {
+    (model.to_string(), normalize(text))
+}
+
+/// Trim and collapse runs of whitespace to a single space.
+pub(crate) fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_lookup_and_stats() {
+        let cache = EmbeddingCache::new(4);
+        assert_eq!(cache.get("m", "hello world"), None);
+        cache.insert("m", "hello world", vec![1.0, 2.0]);
+
+        assert_eq!(cache.get("m", "  hello \n world "), Some(vec![1.0, 2.0]));
+        assert_eq!(cache.get("m", "Hello world"), None);
+        assert_eq!(cache.get("other-model", "hello world"), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 3, 1));
+        assert_eq!(stats.hit_rate(), 0.25);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = EmbeddingCache::new(2);
+        cache.insert("m", "a", vec![1.0]);
+        cache.insert("m", "b", vec![2.0]);
+        assert!(cache.get("m", "a").is_some());
+        cache.insert("m", "c", vec![3.0]);
+
+        assert!(cache.get("m", "a").is_some());
+        assert!(cache.get("m", "b").is_none());
+        assert!(cache.get("m", "c").is_some());
+    }
+
+    #[test]
+    fn test_expired_entries_miss() {
+        let cache = EmbeddingCache::new(2).with_ttl(Duration::ZERO);
+        cache.insert("m", "a", vec![1.0]);
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(cache.get("m", "a").is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -5,8 +5,10 @@
 //! - [`FixedSizeChunker`] — splits by character count with configurable overlap
 //! - [`RecursiveChunker`] — splits hierarchically by paragraphs, sentences, then words
 //! - [`MarkdownChunker`] — splits by markdown headers, preserving header context
+//!
+//! [`ParentChildChunker`] combines two of them for parent-document retrieval.
 
-use crate::document::{Chunk, Document};
+use crate::document::{Chunk, Document, PARENT_ID_KEY, PARENT_TEXT_KEY};
 
 /// MSRV-compatible replacement for `str::floor_char_boundary` (stable since 1.91.0).
 /// Returns the largest byte index `<= index` that is a valid char boundary.
@@ -402,6 +404,64 @@ impl Chunker for MarkdownChunker {
     }
 }
 
+/// Splits documents into large parent chunks, then each parent into small
+/// child chunks for search.
+///
+/// Only the children are returned, so only they are embedded and indexed.
+/// Each child carries its parent's ID in [`PARENT_ID_KEY`] and its text in
+/// [`PARENT_TEXT_KEY`]; with [`RagConfig::parent_document`](crate::RagConfig::parent_document)
+/// enabled, a query that matches a child returns the parent instead. Child
+/// IDs are `{parent_id}_{child_index}`, and parent IDs are those of the
+/// parent chunker.
+///
+/// Storing the parent text on every child trades index size for retrieval
+/// without a separate document store.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_rag::{ParentChildChunker, RecursiveChunker};
+///
+/// let chunker = ParentChildChunker::new(
+///     RecursiveChunker::new(2000, 200),
+///     RecursiveChunker::new(300, 50),
+/// );
+/// let children = chunker.chunk(&document);
+/// ```
+pub struct ParentChildChunker {
+    parent: Box<dyn Chunker>,
+    child: Box<dyn Chunker>,
+}
+
+impl ParentChildChunker {
+    /// Create a `ParentChildChunker` from the chunker that cuts parents and
+    /// the one that cuts each parent into children.
+    pub fn new(parent: impl Chunker + 'static, child: impl Chunker + 'static) -> Self {
+        Self { parent: Box::new(parent), child: Box::new(child) }
+    }
+}
+
+impl Chunker for ParentChildChunker {
+    fn chunk(&self, document: &Document) -> Vec<Chunk> {
+        let mut children = Vec::new();
+        for parent in self.parent.chunk(document) {
+            let parent_document = Document {
+                id: parent.id.clone(),
+                text: parent.text.clone(),
+                metadata: parent.metadata,
+                source_uri: None,
+            };
+            for mut child in self.child.chunk(&parent_document) {
+                child.metadata.insert(PARENT_ID_KEY.to_string(), parent.id.clone());
+                child.metadata.insert(PARENT_TEXT_KEY.to_string(), parent.text.clone());
+                child.document_id = document.id.clone();
+                children.push(child);
+            }
+        }
+        children
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,6 +477,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parent_child_chunks_link_children_to_their_parent() {
+        let chunker =
+            ParentChildChunker::new(FixedSizeChunker::new(20, 0), FixedSizeChunker::new(10, 0));
+        let children = chunker.chunk(&doc("aaaaaaaaaabbbbbbbbbbccccc"));
+
+        let ids: Vec<_> = children.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["test_0_0", "test_0_1", "test_1_0"]);
+        assert_eq!(children[1].text, "bbbbbbbbbb");
+        assert_eq!(children[1].metadata[PARENT_ID_KEY], "test_0");
+        assert_eq!(children[1].metadata[PARENT_TEXT_KEY], "aaaaaaaaaabbbbbbbbbb");
+        assert_eq!(children[2].metadata[PARENT_TEXT_KEY], "ccccc");
+        assert!(children.iter().all(|c| c.document_id == "test"));
+    }
+
     #[test]
     fn fixed_chunker_utf8_multibyte() {
         // Chinese characters are 3 bytes each in UTF-8.
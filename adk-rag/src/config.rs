@@ -1,8 +1,11 @@
 //! Configuration for the RAG pipeline.
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use crate::error::{RagError, Result};
+use crate::metric::DistanceMetric;
 
 /// Configuration parameters for the RAG pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -15,11 +18,37 @@ pub struct RagConfig {
     pub top_k: usize,
     /// Minimum similarity score for results (results below this are filtered out).
     pub similarity_threshold: f32,
+    /// Half-life for freshness weighting. When set, each result's score is
+    /// multiplied by `0.5^(age / half_life)` based on the chunk's `indexed_at`
+    /// metadata. `None` (the default) disables decay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recency_half_life: Option<Duration>,
+    /// Similarity metric the vector store must use. The pipeline rejects a
+    /// store configured with a different metric.
+    #[serde(default)]
+    pub metric: DistanceMetric,
+    /// Return the parent of each matched child chunk instead of the child.
+    ///
+    /// Use with [`ParentChildChunker`](crate::ParentChildChunker): small
+    /// children give precise matches, their parents give the model enough
+    /// context. Children of the same parent collapse into one result with
+    /// the best child's score, so a query may return fewer than `top_k`
+    /// results. Chunks without a parent are returned as they are.
+    #[serde(default)]
+    pub parent_document: bool,
 }
 
 impl Default for RagConfig {
     fn default() -> Self {
-        Self { chunk_size: 512, chunk_overlap: 100, top_k: 10, similarity_threshold: 0.0 }
+        Self {
+            chunk_size: 512,
+            chunk_overlap: 100,
+            top_k: 10,
+            similarity_threshold: 0.0,
+            recency_half_life: None,
+            metric: DistanceMetric::Cosine,
+            parent_document: false,
+        }
     }
 }
 
@@ -61,6 +90,31 @@ impl RagConfigBuilder {
         self
     }
 
+    /// Weight scores by freshness, halving a chunk's score every `half_life`
+    /// since it was indexed.
+    ///
+    /// Chunks without `indexed_at` metadata keep their original score.
+    pub fn recency_decay(mut self, half_life: Duration) -> Self {
+        self.config.recency_half_life = Some(half_life);
+        self
+    }
+
+    /// Set the similarity metric. Defaults to [`DistanceMetric::Cosine`].
+    ///
+    /// The pipeline's vector store must be constructed with the same metric.
+    pub fn metric(mut self, metric: DistanceMetric) -> Self {
+        self.config.metric = metric;
+        self
+    }
+
+    /// Return each matched child chunk's parent instead of the child.
+    ///
+    /// See [`RagConfig::parent_document`].
+    pub fn parent_document(mut self, enabled: bool) -> Self {
+        self.config.parent_document = enabled;
+        self
+    }
+
     /// Build the [`RagConfig`], validating that parameters are consistent.
     ///
     /// # Errors
@@ -68,6 +122,7 @@ impl RagConfigBuilder {
     /// Returns [`RagError::ConfigError`] if:
     /// - `chunk_overlap >= chunk_size`
     /// - `top_k == 0`
+    /// - the recency half-life is zero
     pub fn build(self) -> Result<RagConfig> {
         if self.config.chunk_overlap >= self.config.chunk_size {
             return Err(RagError::ConfigError(format!(
@@ -78,6 +133,11 @@ impl RagConfigBuilder {
         if self.config.top_k == 0 {
             return Err(RagError::ConfigError("top_k must be greater than zero".to_string()));
         }
+        if self.config.recency_half_life.is_some_and(|half_life| half_life.is_zero()) {
+            return Err(RagError::ConfigError(
+                "recency half-life must be greater than zero".to_string(),
+            ));
+        }
         Ok(self.config)
     }
 }
@@ -2,8 +2,40 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Metadata key holding the RFC 3339 time a chunk was indexed.
+///
+/// Used by [`RagConfig::recency_half_life`](crate::RagConfig::recency_half_life)
+/// to weight results by freshness.
+pub const INDEXED_AT_KEY: &str = "indexed_at";
+
+/// Metadata key holding the RFC 3339 time after which a chunk is no longer returned.
+pub const EXPIRES_AT_KEY: &str = "expires_at";
+
+/// Metadata key holding the tenant a chunk was ingested for.
+///
+/// Set by tenant-scoped ingestion, overriding any value from the document.
+pub const TENANT_ID_KEY: &str = "tenant_id";
+
+/// Metadata key holding the [`Document::source_uri`] a chunk was cut from.
+///
+/// Set at ingestion so retrieved chunks can be cited back to their source.
+pub const SOURCE_URI_KEY: &str = "source_uri";
+
+/// Metadata key holding the ID of the parent chunk a child chunk was cut from.
+///
+/// Set by [`ParentChildChunker`](crate::ParentChildChunker).
+pub const PARENT_ID_KEY: &str = "parent_id";
+
+/// Metadata key holding the full text of a child chunk's parent.
+///
+/// Set by [`ParentChildChunker`](crate::ParentChildChunker) and returned in
+/// place of the child when [`RagConfig::parent_document`](crate::RagConfig::parent_document)
+/// is enabled.
+pub const PARENT_TEXT_KEY: &str = "parent_text";
+
 /// A source document containing text content and metadata.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Document {
@@ -33,6 +65,44 @@ pub struct Chunk {
     pub document_id: String,
 }
 
+impl Chunk {
+    /// When this chunk was indexed, from the [`INDEXED_AT_KEY`] metadata entry.
+    pub fn indexed_at(&self) -> Option<DateTime<Utc>> {
+        self.timestamp(INDEXED_AT_KEY)
+    }
+
+    /// When this chunk expires, from the [`EXPIRES_AT_KEY`] metadata entry.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.timestamp(EXPIRES_AT_KEY)
+    }
+
+    /// Where this chunk's document came from, from the [`SOURCE_URI_KEY`] metadata entry.
+    pub fn source_uri(&self) -> Option<&str> {
+        self.metadata.get(SOURCE_URI_KEY).map(String::as_str)
+    }
+
+    /// The tenant this chunk was ingested for, from the [`TENANT_ID_KEY`] metadata entry.
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.metadata.get(TENANT_ID_KEY).map(String::as_str)
+    }
+
+    /// Returns `true` if the chunk has an expiry at or before `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// The ID of the parent chunk this chunk was cut from, from the
+    /// [`PARENT_ID_KEY`] metadata entry.
+    pub fn parent_id(&self) -> Option<&str> {
+        self.metadata.get(PARENT_ID_KEY).map(String::as_str)
+    }
+
+    fn timestamp(&self, key: &str) -> Option<DateTime<Utc>> {
+        let raw = self.metadata.get(key)?;
+        DateTime::parse_from_rfc3339(raw).ok().map(|t| t.with_timezone(&Utc))
+    }
+}
+
 /// A retrieved [`Chunk`] paired with a relevance score.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -40,4 +40,13 @@ pub trait EmbeddingProvider: Send + Sync {
 
     /// Return the dimensionality of embeddings produced by this provider.
     fn dimensions(&self) -> usize;
+
+    /// Return the model identifier used to key cached embeddings.
+    ///
+    /// Defaults to an empty string. Override it when one
+    /// [`EmbeddingCache`](crate::EmbeddingCache) may be shared by providers
+    /// that use different models.
+    fn model_name(&self) -> &str {
+        ""
+    }
 }
@@ -1,4 +1,4 @@
-//! In-memory vector store using cosine similarity.
+//! In-memory vector store with a configurable similarity metric.
 //!
 //! This module provides [`InMemoryVectorStore`], a zero-dependency vector store
 //! backed by a `HashMap` protected by a `tokio::sync::RwLock`. It is suitable
@@ -11,9 +11,11 @@ use tokio::sync::RwLock;
 
 use crate::document::{Chunk, SearchResult};
 use crate::error::{RagError, Result};
+use crate::metric::DistanceMetric;
 use crate::vectorstore::VectorStore;
 
-/// An in-memory vector store using cosine similarity for search.
+/// An in-memory vector store that searches by [`DistanceMetric`], cosine by
+/// default.
 ///
 /// Collections are stored as nested `HashMap`s: collection name → chunk ID → chunk.
 /// All operations are async-safe via `tokio::sync::RwLock`.
@@ -21,35 +23,30 @@ use crate::vectorstore::VectorStore;
 /// # Example
 ///
 /// ```rust,ignore
-/// use adk_rag::{InMemoryVectorStore, VectorStore};
+/// use adk_rag::{DistanceMetric, InMemoryVectorStore, VectorStore};
 ///
 /// let store = InMemoryVectorStore::new();
 /// store.create_collection("docs", 384).await?;
+///
+/// // For embeddings trained for maximum inner product search
+/// let store = InMemoryVectorStore::with_metric(DistanceMetric::DotProduct);
 /// ```
 #[derive(Debug, Default)]
 pub struct InMemoryVectorStore {
     collections: RwLock<HashMap<String, HashMap<String, Chunk>>>,
+    metric: DistanceMetric,
 }
 
 impl InMemoryVectorStore {
-    /// Create a new empty in-memory vector store.
+    /// Create a new empty in-memory vector store using cosine similarity.
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-/// Compute cosine similarity between two vectors.
-///
-/// Both vectors are L2-normalized before computing the dot product.
-/// Returns 0.0 if either vector has zero magnitude.
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if norm_a == 0.0 || norm_b == 0.0 {
-        return 0.0;
+    /// Create a new empty in-memory vector store using `metric`.
+    pub fn with_metric(metric: DistanceMetric) -> Self {
+        Self { metric, ..Self::default() }
     }
-    dot / (norm_a * norm_b)
 }
 
 #[async_trait]
@@ -105,7 +102,7 @@ impl VectorStore for InMemoryVectorStore {
         let mut scored: Vec<SearchResult> = store
             .values()
             .map(|chunk| {
-                let score = cosine_similarity(&chunk.embedding, embedding);
+                let score = self.metric.score(&chunk.embedding, embedding);
                 SearchResult { chunk: chunk.clone(), score }
             })
             .collect();
@@ -114,4 +111,8 @@ impl VectorStore for InMemoryVectorStore {
         scored.truncate(top_k);
         Ok(scored)
     }
+
+    fn metric(&self) -> DistanceMetric {
+        self.metric
+    }
 }
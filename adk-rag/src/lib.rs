@@ -18,20 +18,24 @@
 //! |--------------|------------------------------------------|
 //! | `gemini`     | `GeminiEmbeddingProvider` via adk-gemini  |
 //! | `openai`     | `OpenAIEmbeddingProvider` via reqwest     |
+//! | `mistral`    | `MistralEmbeddingProvider` via reqwest    |
 //! | `qdrant`     | `QdrantVectorStore` via qdrant-client     |
 //! | `lancedb`    | `LanceDBVectorStore` via lancedb          |
 //! | `pgvector`   | `PgVectorStore` via sqlx                  |
 //! | `surrealdb`  | `SurrealVectorStore` via surrealdb        |
 //! | `full`       | All of the above                          |
 
+pub mod cache;
 pub mod chunking;
 pub mod config;
 pub mod document;
 pub mod embedding;
 pub mod error;
 pub mod inmemory;
+pub mod metric;
 pub mod pipeline;
 pub mod reranker;
+pub mod tenant;
 pub mod tool;
 pub mod vectorstore;
 
@@ -39,6 +43,8 @@ pub mod vectorstore;
 pub mod gemini;
 #[cfg(feature = "lancedb")]
 pub mod lancedb;
+#[cfg(feature = "mistral")]
+pub mod mistral;
 #[cfg(feature = "openai")]
 pub mod openai;
 #[cfg(feature = "pgvector")]
@@ -48,14 +54,19 @@ pub mod qdrant;
 #[cfg(feature = "surrealdb")]
 pub mod surrealdb;
 
-pub use chunking::{Chunker, FixedSizeChunker, MarkdownChunker, RecursiveChunker};
+pub use cache::{EmbeddingCache, EmbeddingCacheStats};
+pub use chunking::{
+    Chunker, FixedSizeChunker, MarkdownChunker, ParentChildChunker, RecursiveChunker,
+};
 pub use config::{RagConfig, RagConfigBuilder};
 pub use document::{Chunk, Document, SearchResult};
 pub use embedding::EmbeddingProvider;
 pub use error::{RagError, Result};
 pub use inmemory::InMemoryVectorStore;
-pub use pipeline::{RagPipeline, RagPipelineBuilder};
+pub use metric::DistanceMetric;
+pub use pipeline::{RagPipeline, RagPipelineBuilder, TenantPipeline};
 pub use reranker::{NoOpReranker, Reranker};
+pub use tenant::TenantId;
 pub use tool::RagTool;
 pub use vectorstore::VectorStore;
 
@@ -63,6 +74,8 @@ pub use vectorstore::VectorStore;
 pub use gemini::GeminiEmbeddingProvider;
 #[cfg(feature = "lancedb")]
 pub use lancedb::LanceDBVectorStore;
+#[cfg(feature = "mistral")]
+pub use mistral::MistralEmbeddingProvider;
 #[cfg(feature = "openai")]
 pub use openai::OpenAIEmbeddingProvider;
 #[cfg(feature = "pgvector")]
@@ -0,0 +1,81 @@
+//! Similarity metrics for vector search.
+
+use serde::{Deserialize, Serialize};
+
+/// How a vector store compares a query embedding with stored embeddings.
+///
+/// Pick the metric the embedding model was trained for:
+///
+/// - [`Cosine`](Self::Cosine) compares direction only and suits most text
+///   embedding models (Gemini, OpenAI, sentence-transformers). For vectors
+///   that are already L2-normalized it ranks the same as dot product.
+/// - [`DotProduct`](Self::DotProduct) also rewards magnitude. Use it for models
+///   trained with an inner-product objective that emit unnormalized vectors,
+///   such as many retrieval models tuned for maximum inner product search.
+/// - [`Euclidean`](Self::Euclidean) ranks by straight-line distance. Use it for
+///   embeddings where absolute position matters, such as image or
+///   clustering-oriented models.
+///
+/// Every metric produces a score where higher means more similar, so
+/// [`RagConfig::similarity_threshold`](crate::RagConfig::similarity_threshold)
+/// keeps its meaning. Euclidean distance `d` is reported as `1 / (1 + d)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMetric {
+    /// Cosine similarity, in `[-1, 1]` (the default).
+    #[default]
+    Cosine,
+    /// Raw dot product, unbounded.
+    DotProduct,
+    /// Euclidean (L2) distance, reported as a score in `(0, 1]`.
+    Euclidean,
+}
+
+impl DistanceMetric {
+    /// Score how similar `a` is to `b` under this metric.
+    ///
+    /// Cosine similarity is 0.0 when either vector has zero magnitude.
+    pub fn score(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => {
+                let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    return 0.0;
+                }
+                dot(a, b) / (norm_a * norm_b)
+            }
+            Self::DotProduct => dot(a, b),
+            Self::Euclidean => {
+                let distance = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt();
+                Self::euclidean_score(distance)
+            }
+        }
+    }
+
+    /// Convert a Euclidean distance into a similarity score in `(0, 1]`.
+    pub(crate) fn euclidean_score(distance: f32) -> f32 {
+        1.0 / (1.0 + distance)
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_for_each_metric() {
+        let a = [3.0, 4.0];
+        let b = [3.0, 0.0];
+
+        assert!((DistanceMetric::Cosine.score(&a, &b) - 0.6).abs() < 1e-6);
+        assert_eq!(DistanceMetric::DotProduct.score(&a, &b), 9.0);
+        assert!((DistanceMetric::Euclidean.score(&a, &b) - 0.2).abs() < 1e-6);
+        assert_eq!(DistanceMetric::Euclidean.score(&a, &a), 1.0);
+        assert_eq!(DistanceMetric::Cosine.score(&a, &[0.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,241 @@
+//! Mistral embedding provider using the La Plateforme embeddings API.
+//!
+//! This module is only available when the `mistral` feature is enabled.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error};
+
+use crate::embedding::EmbeddingProvider;
+use crate::error::{RagError, Result};
+
+/// The default Mistral API base URL.
+const MISTRAL_API_BASE: &str = "https://api.mistral.ai/v1";
+
+/// The default model for Mistral embeddings.
+const DEFAULT_MODEL: &str = "mistral-embed";
+
+/// The dimensionality of `mistral-embed`.
+const DEFAULT_DIMENSIONS: usize = 1024;
+
+/// An [`EmbeddingProvider`] backed by the Mistral embeddings API.
+///
+/// Uses `reqwest` to call the `/v1/embeddings` endpoint directly.
+///
+/// # Configuration
+///
+/// - `model` – defaults to `mistral-embed`; `codestral-embed` suits code.
+/// - `dimensions` – optional output dimension, supported by `codestral-embed`.
+/// - `api_key` – from the constructor or the `MISTRAL_API_KEY` environment variable.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_rag::mistral::MistralEmbeddingProvider;
+///
+/// let provider = MistralEmbeddingProvider::from_env()?;
+/// let embedding = provider.embed("hello world").await?;
+/// ```
+pub struct MistralEmbeddingProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    dimensions: usize,
+    /// If set, passed to the API as `output_dimension`.
+    request_dimensions: Option<usize>,
+}
+
+impl MistralEmbeddingProvider {
+    /// Create a new provider with the given API key.
+    ///
+    /// Uses the default model (`mistral-embed`) and dimensions (1024).
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        let api_key = api_key.into();
+        if api_key.is_empty() {
+            return Err(RagError::EmbeddingError {
+                provider: "Mistral".into(),
+                message: "API key must not be empty".into(),
+            });
+        }
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_key,
+            base_url: MISTRAL_API_BASE.into(),
+            model: DEFAULT_MODEL.into(),
+            dimensions: DEFAULT_DIMENSIONS,
+            request_dimensions: None,
+        })
+    }
+
+    /// Create a new provider using the `MISTRAL_API_KEY` environment variable.
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("MISTRAL_API_KEY").map_err(|_| RagError::EmbeddingError {
+            provider: "Mistral".into(),
+            message: "MISTRAL_API_KEY environment variable not set".into(),
+        })?;
+        Self::new(api_key)
+    }
+
+    /// Set the model name (e.g. `codestral-embed`).
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set the output dimensions.
+    ///
+    /// When set, the API returns embeddings of this size. This also updates
+    /// the value returned by [`dimensions()`](EmbeddingProvider::dimensions).
+    pub fn with_dimensions(mut self, dims: usize) -> Self {
+        self.dimensions = dims;
+        self.request_dimensions = Some(dims);
+        self
+    }
+
+    /// Set a custom API base URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    fn request_body<'a>(&'a self, texts: &[&'a str]) -> EmbeddingRequest<'a> {
+        EmbeddingRequest {
+            model: &self.model,
+            input: texts.to_vec(),
+            output_dimension: self.request_dimensions,
+        }
+    }
+}
+
+// ── Mistral API request/response types ─────────────────────────────
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_dimension: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+// ── EmbeddingProvider implementation ───────────────────────────────
+
+#[async_trait]
+impl EmbeddingProvider for MistralEmbeddingProvider {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        debug!(provider = "Mistral", text_len = text.len(), "embedding single text");
+
+        let results = self.embed_batch(&[text]).await?;
+        results.into_iter().next().ok_or_else(|| RagError::EmbeddingError {
+            provider: "Mistral".into(),
+            message: "API returned empty response".into(),
+        })
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(
+            provider = "Mistral",
+            batch_size = texts.len(),
+            model = %self.model,
+            "embedding batch"
+        );
+
+        let url = format!("{}/embeddings", self.base_url.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .json(&self.request_body(texts))
+            .send()
+            .await
+            .map_err(|e| {
+                error!(provider = "Mistral", error = %e, "request failed");
+                RagError::EmbeddingError {
+                    provider: "Mistral".into(),
+                    message: format!("request failed: {e}"),
+                }
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let detail =
+                serde_json::from_str::<ErrorResponse>(&body).map(|e| e.message).unwrap_or(body);
+
+            error!(provider = "Mistral", %status, "API error");
+            return Err(RagError::EmbeddingError {
+                provider: "Mistral".into(),
+                message: format!("API returned {status}: {detail}"),
+            });
+        }
+
+        let mut embedding_response: EmbeddingResponse = response.json().await.map_err(|e| {
+            error!(provider = "Mistral", error = %e, "failed to parse response");
+            RagError::EmbeddingError {
+                provider: "Mistral".into(),
+                message: format!("failed to parse response: {e}"),
+            }
+        })?;
+
+        embedding_response.data.sort_by_key(|d| d.index);
+        Ok(embedding_response.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_body_uses_model_and_optional_dimension() {
+        let provider = MistralEmbeddingProvider::new("test-key").unwrap();
+        assert_eq!(provider.dimensions(), 1024);
+        assert_eq!(
+            serde_json::to_value(provider.request_body(&["a", "b"])).unwrap(),
+            json!({ "model": "mistral-embed", "input": ["a", "b"] })
+        );
+
+        let provider = provider.with_model("codestral-embed").with_dimensions(256);
+        assert_eq!(provider.dimensions(), 256);
+        assert_eq!(
+            serde_json::to_value(provider.request_body(&["fn main() {}"])).unwrap(),
+            json!({ "model": "codestral-embed", "input": ["fn main() {}"], "output_dimension": 256 })
+        );
+    }
+
+    #[test]
+    fn empty_api_key_is_rejected() {
+        assert!(MistralEmbeddingProvider::new("").is_err());
+    }
+}
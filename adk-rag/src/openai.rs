@@ -198,4 +198,8 @@ impl EmbeddingProvider for OpenAIEmbeddingProvider {
     fn dimensions(&self) -> usize {
         self.dimensions
     }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
 }
@@ -29,26 +29,49 @@ use tracing::debug;
 
 use crate::document::{Chunk, SearchResult};
 use crate::error::{RagError, Result};
+use crate::metric::DistanceMetric;
 use crate::vectorstore::VectorStore;
 
 /// A [`VectorStore`] backed by PostgreSQL with the pgvector extension.
 ///
 /// Each collection is stored as a separate table with columns:
 /// `id`, `text`, `embedding` (vector), `metadata` (jsonb), `document_id`.
+/// Searches use cosine distance unless another metric is set with
+/// [`with_metric`](Self::with_metric).
 pub struct PgVectorStore {
     pool: PgPool,
+    metric: DistanceMetric,
 }
 
 impl PgVectorStore {
     /// Create a new pgvector store by connecting to the given database URL.
     pub async fn new(database_url: &str) -> std::result::Result<Self, sqlx::Error> {
         let pool = PgPoolOptions::new().max_connections(5).connect(database_url).await?;
-        Ok(Self { pool })
+        Ok(Self { pool, metric: DistanceMetric::Cosine })
     }
 
     /// Create a new pgvector store from an existing connection pool.
     pub fn from_pool(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, metric: DistanceMetric::Cosine }
+    }
+
+    /// Use `metric` when searching.
+    pub fn with_metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// The pgvector distance operator for the metric and the SQL expression
+    /// that turns its result into a similarity score.
+    fn distance_sql(&self) -> (&'static str, &'static str) {
+        match self.metric {
+            // Cosine distance: 0 = identical
+            DistanceMetric::Cosine => ("<=>", "1 - (embedding <=> $1::vector)"),
+            // Negative inner product, so ascending order ranks the largest dot product first
+            DistanceMetric::DotProduct => ("<#>", "(embedding <#> $1::vector) * -1"),
+            // L2 distance, reported as 1 / (1 + distance)
+            DistanceMetric::Euclidean => ("<->", "1 / (1 + (embedding <-> $1::vector))"),
+        }
     }
 
     fn map_err(e: sqlx::Error) -> RagError {
@@ -174,13 +197,12 @@ impl VectorStore for PgVectorStore {
     ) -> Result<Vec<SearchResult>> {
         let table_name = Self::sanitize_table_name(collection)?;
 
-        // pgvector cosine distance operator: <=>
-        // Returns distance (0 = identical), so score = 1 - distance
+        let (operator, score) = self.distance_sql();
         let search_sql = format!(
             "SELECT id, text, metadata, document_id, \
-                    1 - (embedding <=> $1::vector) AS score \
+                    {score} AS score \
              FROM {table_name} \
-             ORDER BY embedding <=> $1::vector \
+             ORDER BY embedding {operator} $1::vector \
              LIMIT $2"
         );
 
@@ -220,4 +242,8 @@ impl VectorStore for PgVectorStore {
 
         Ok(results)
     }
+
+    fn metric(&self) -> DistanceMetric {
+        self.metric
+    }
 }
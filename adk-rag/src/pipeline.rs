@@ -20,17 +20,30 @@
 //! pipeline.ingest("docs", &document).await?;
 //! let results = pipeline.query("docs", "search query").await?;
 //! ```
+//!
+//! For a service shared by several tenants, build the pipeline with
+//! [`require_tenant`](RagPipelineBuilder::require_tenant) and go through
+//! [`RagPipeline::for_tenant`], so every ingest and query is confined to one
+//! tenant's collections.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tracing::{error, info};
+use chrono::{DateTime, Utc};
+use tracing::{debug, error, info};
 
+use crate::cache::{EmbeddingCache, EmbeddingCacheStats, normalize};
 use crate::chunking::Chunker;
 use crate::config::RagConfig;
-use crate::document::{Chunk, Document, SearchResult};
+use crate::document::{
+    Chunk, Document, INDEXED_AT_KEY, PARENT_ID_KEY, PARENT_TEXT_KEY, SOURCE_URI_KEY, SearchResult,
+    TENANT_ID_KEY,
+};
 use crate::embedding::EmbeddingProvider;
 use crate::error::{RagError, Result};
 use crate::reranker::Reranker;
+use crate::tenant::TenantId;
 use crate::vectorstore::VectorStore;
 
 /// The RAG pipeline orchestrator.
@@ -44,6 +57,8 @@ pub struct RagPipeline {
     vector_store: Arc<dyn VectorStore>,
     chunker: Arc<dyn Chunker>,
     reranker: Option<Arc<dyn Reranker>>,
+    embedding_cache: Option<EmbeddingCache>,
+    require_tenant: bool,
 }
 
 impl RagPipeline {
@@ -67,6 +82,26 @@ impl RagPipeline {
         &self.vector_store
     }
 
+    /// Return the embedding cache, if one is configured.
+    pub fn embedding_cache(&self) -> Option<&EmbeddingCache> {
+        self.embedding_cache.as_ref()
+    }
+
+    /// Return hit and miss counters of the embedding cache, if one is configured.
+    pub fn embedding_cache_stats(&self) -> Option<EmbeddingCacheStats> {
+        self.embedding_cache.as_ref().map(EmbeddingCache::stats)
+    }
+
+    /// Return `true` if operations must be scoped with [`for_tenant`](Self::for_tenant).
+    pub fn requires_tenant(&self) -> bool {
+        self.require_tenant
+    }
+
+    /// Return a handle whose operations are confined to `tenant`'s collections.
+    pub fn for_tenant(&self, tenant: TenantId) -> TenantPipeline<'_> {
+        TenantPipeline { pipeline: self, tenant }
+    }
+
     /// Create a named collection in the vector store.
     ///
     /// The collection is created with the dimensionality reported by the
@@ -74,25 +109,20 @@ impl RagPipeline {
     ///
     /// # Errors
     ///
-    /// Returns [`RagError::PipelineError`] if the vector store operation fails.
+    /// Returns [`RagError::ConfigError`] if the pipeline requires a tenant, or
+    /// [`RagError::PipelineError`] if the vector store operation fails.
     pub async fn create_collection(&self, name: &str) -> Result<()> {
-        let dimensions = self.embedding_provider.dimensions();
-        self.vector_store.create_collection(name, dimensions).await.map_err(|e| {
-            error!(collection = name, error = %e, "failed to create collection");
-            RagError::PipelineError(format!("failed to create collection '{name}': {e}"))
-        })
+        self.create_collection_in(None, name).await
     }
 
     /// Delete a named collection from the vector store.
     ///
     /// # Errors
     ///
-    /// Returns [`RagError::PipelineError`] if the vector store operation fails.
+    /// Returns [`RagError::ConfigError`] if the pipeline requires a tenant, or
+    /// [`RagError::PipelineError`] if the vector store operation fails.
     pub async fn delete_collection(&self, name: &str) -> Result<()> {
-        self.vector_store.delete_collection(name).await.map_err(|e| {
-            error!(collection = name, error = %e, "failed to delete collection");
-            RagError::PipelineError(format!("failed to delete collection '{name}': {e}"))
-        })
+        self.delete_collection_in(None, name).await
     }
 
     /// Ingest a single document: chunk → embed → store.
@@ -101,9 +131,83 @@ impl RagPipeline {
     ///
     /// # Errors
     ///
-    /// Returns [`RagError::PipelineError`] if embedding or storage fails,
-    /// including the document ID in the error message.
+    /// Returns [`RagError::ConfigError`] if the pipeline requires a tenant, or
+    /// [`RagError::PipelineError`] if embedding or storage fails, including
+    /// the document ID in the error message.
     pub async fn ingest(&self, collection: &str, document: &Document) -> Result<Vec<Chunk>> {
+        self.ingest_in(None, collection, document).await
+    }
+
+    /// Ingest multiple documents through the chunk → embed → store workflow.
+    ///
+    /// Returns all chunks that were stored across all documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::ConfigError`] if the pipeline requires a tenant, or
+    /// [`RagError::PipelineError`] on the first document that fails, including
+    /// the document ID in the error message.
+    pub async fn ingest_batch(
+        &self,
+        collection: &str,
+        documents: &[Document],
+    ) -> Result<Vec<Chunk>> {
+        self.ingest_batch_in(None, collection, documents).await
+    }
+
+    /// Query the pipeline: embed → search → rerank → decay → filter by threshold.
+    ///
+    /// Returns search results ordered by descending relevance score. Expired
+    /// chunks are dropped, scores are weighted by freshness when
+    /// [`RagConfig::recency_half_life`] is set, and results below the configured
+    /// `similarity_threshold` are filtered out. With
+    /// [`RagConfig::parent_document`], matched child chunks are replaced by
+    /// their parents, one result per parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::ConfigError`] if the pipeline requires a tenant, or
+    /// [`RagError::PipelineError`] if embedding or search fails.
+    pub async fn query(&self, collection: &str, query: &str) -> Result<Vec<SearchResult>> {
+        self.query_in(None, collection, query, self.config.top_k).await
+    }
+
+    /// Resolve the vector store collection for `collection`, scoped to `tenant`.
+    fn physical_collection(&self, tenant: Option<&TenantId>, collection: &str) -> Result<String> {
+        match tenant {
+            Some(tenant) => Ok(self.vector_store.tenant_collection(tenant, collection)),
+            None if self.require_tenant => Err(RagError::ConfigError(format!(
+                "this pipeline requires a tenant; use RagPipeline::for_tenant to access collection '{collection}'"
+            ))),
+            None => Ok(collection.to_string()),
+        }
+    }
+
+    async fn create_collection_in(&self, tenant: Option<&TenantId>, name: &str) -> Result<()> {
+        let physical = self.physical_collection(tenant, name)?;
+        let dimensions = self.embedding_provider.dimensions();
+        self.vector_store.create_collection(&physical, dimensions).await.map_err(|e| {
+            error!(collection = name, error = %e, "failed to create collection");
+            RagError::PipelineError(format!("failed to create collection '{name}': {e}"))
+        })
+    }
+
+    async fn delete_collection_in(&self, tenant: Option<&TenantId>, name: &str) -> Result<()> {
+        let physical = self.physical_collection(tenant, name)?;
+        self.vector_store.delete_collection(&physical).await.map_err(|e| {
+            error!(collection = name, error = %e, "failed to delete collection");
+            RagError::PipelineError(format!("failed to delete collection '{name}': {e}"))
+        })
+    }
+
+    async fn ingest_in(
+        &self,
+        tenant: Option<&TenantId>,
+        collection: &str,
+        document: &Document,
+    ) -> Result<Vec<Chunk>> {
+        let physical = self.physical_collection(tenant, collection)?;
+
         // 1. Chunk the document
         let mut chunks = self.chunker.chunk(document);
         if chunks.is_empty() {
@@ -115,18 +219,35 @@ impl RagPipeline {
         let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
 
         // 3. Generate embeddings
-        let embeddings = self.embedding_provider.embed_batch(&texts).await.map_err(|e| {
+        let embeddings = self.embed_chunks(&texts).await.map_err(|e| {
             error!(document.id = %document.id, error = %e, "embedding failed during ingestion");
             RagError::PipelineError(format!("embedding failed for document '{}': {e}", document.id))
         })?;
 
-        // 4. Attach embeddings to chunks
+        // 4. Attach embeddings to chunks, stamping the source document's URI,
+        //    the index time when decay is enabled and the owning tenant when scoped
+        let indexed_at = Utc::now().to_rfc3339();
         for (chunk, embedding) in chunks.iter_mut().zip(embeddings) {
             chunk.embedding = embedding;
+            if let Some(source_uri) = &document.source_uri {
+                chunk
+                    .metadata
+                    .entry(SOURCE_URI_KEY.to_string())
+                    .or_insert_with(|| source_uri.clone());
+            }
+            if self.config.recency_half_life.is_some() {
+                chunk
+                    .metadata
+                    .entry(INDEXED_AT_KEY.to_string())
+                    .or_insert_with(|| indexed_at.clone());
+            }
+            if let Some(tenant) = tenant {
+                chunk.metadata.insert(TENANT_ID_KEY.to_string(), tenant.to_string());
+            }
         }
 
         // 5. Upsert into vector store
-        self.vector_store.upsert(collection, &chunks).await.map_err(|e| {
+        self.vector_store.upsert(&physical, &chunks).await.map_err(|e| {
             error!(document.id = %document.id, error = %e, "upsert failed during ingestion");
             RagError::PipelineError(format!("upsert failed for document '{}': {e}", document.id))
         })?;
@@ -137,53 +258,53 @@ impl RagPipeline {
         Ok(chunks)
     }
 
-    /// Ingest multiple documents through the chunk → embed → store workflow.
-    ///
-    /// Returns all chunks that were stored across all documents.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`RagError::PipelineError`] on the first document that fails,
-    /// including the document ID in the error message.
-    pub async fn ingest_batch(
+    async fn ingest_batch_in(
         &self,
+        tenant: Option<&TenantId>,
         collection: &str,
         documents: &[Document],
     ) -> Result<Vec<Chunk>> {
         let mut all_chunks = Vec::new();
         for document in documents {
-            let chunks = self.ingest(collection, document).await?;
+            let chunks = self.ingest_in(tenant, collection, document).await?;
             all_chunks.extend(chunks);
         }
         Ok(all_chunks)
     }
 
-    /// Query the pipeline: embed → search → rerank → filter by threshold.
-    ///
-    /// Returns search results ordered by descending relevance score. Results
-    /// below the configured `similarity_threshold` are filtered out.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`RagError::PipelineError`] if embedding or search fails.
-    pub async fn query(&self, collection: &str, query: &str) -> Result<Vec<SearchResult>> {
+    /// Run a query returning at most `top_k` results before filtering.
+    pub(crate) async fn query_in(
+        &self,
+        tenant: Option<&TenantId>,
+        collection: &str,
+        query: &str,
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let physical = self.physical_collection(tenant, collection)?;
+
         // 1. Embed the query
-        let query_embedding = self.embedding_provider.embed(query).await.map_err(|e| {
+        let query_embedding = self.embed_query(query).await.map_err(|e| {
             error!(error = %e, "embedding failed during query");
             RagError::PipelineError(format!("query embedding failed: {e}"))
         })?;
 
         // 2. Search the vector store
-        let results = self
-            .vector_store
-            .search(collection, &query_embedding, self.config.top_k)
-            .await
-            .map_err(|e| {
+        let results =
+            self.vector_store.search(&physical, &query_embedding, top_k).await.map_err(|e| {
                 error!(collection, error = %e, "vector store search failed");
                 RagError::PipelineError(format!("search failed in collection '{collection}': {e}"))
             })?;
 
-        // 3. Rerank if a reranker is configured
+        // 3. Never return another tenant's chunks, even from a shared collection
+        let results = match tenant {
+            Some(tenant) => results
+                .into_iter()
+                .filter(|r| r.chunk.tenant_id() == Some(tenant.as_str()))
+                .collect(),
+            None => results,
+        };
+
+        // 4. Rerank if a reranker is configured
         let results = if let Some(reranker) = &self.reranker {
             reranker.rerank(query, results).await.map_err(|e| {
                 error!(error = %e, "reranking failed");
@@ -193,20 +314,200 @@ impl RagPipeline {
             results
         };
 
-        // 4. Filter by similarity threshold
+        // 5. Drop expired chunks and apply freshness weighting
+        let now = Utc::now();
+        let mut results: Vec<SearchResult> =
+            results.into_iter().filter(|r| !r.chunk.is_expired(now)).collect();
+        if let Some(half_life) = self.config.recency_half_life {
+            apply_recency_decay(&mut results, half_life, now);
+        }
+
+        // 6. Filter by similarity threshold
         let threshold = self.config.similarity_threshold;
         let filtered: Vec<SearchResult> =
             results.into_iter().filter(|r| r.score >= threshold).collect();
 
+        // 7. Swap matched children for their parents
+        let filtered =
+            if self.config.parent_document { resolve_parents(filtered) } else { filtered };
+
         info!(result_count = filtered.len(), "query completed");
 
         Ok(filtered)
     }
+
+    /// Embed a query, consulting the embedding cache first.
+    async fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        let Some(cache) = &self.embedding_cache else {
+            return self.embedding_provider.embed(query).await;
+        };
+        let model = self.embedding_provider.model_name();
+        if let Some(embedding) = cache.get(model, query) {
+            debug!("query embedding served from cache");
+            return Ok(embedding);
+        }
+        let embedding = self.embedding_provider.embed(query).await?;
+        cache.insert(model, query, embedding.clone());
+        Ok(embedding)
+    }
+
+    /// Embed chunk texts in one batch.
+    ///
+    /// When chunk caching is enabled, cached texts are reused and identical
+    /// texts are only sent to the provider once.
+    async fn embed_chunks(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let cache = match &self.embedding_cache {
+            Some(cache) if cache.caches_chunk_embeddings() => cache,
+            _ => return self.embedding_provider.embed_batch(texts).await,
+        };
+        let model = self.embedding_provider.model_name();
+
+        // Each text is either cached or points at its position in the deduplicated batch.
+        let mut slots: Vec<std::result::Result<Vec<f32>, usize>> = Vec::with_capacity(texts.len());
+        let mut pending: Vec<&str> = Vec::new();
+        let mut pending_by_key: HashMap<String, usize> = HashMap::new();
+        for text in texts {
+            match cache.get(model, text) {
+                Some(embedding) => slots.push(Ok(embedding)),
+                None => {
+                    let next = pending.len();
+                    let index = *pending_by_key.entry(normalize(text)).or_insert(next);
+                    if index == next {
+                        pending.push(text);
+                    }
+                    slots.push(Err(index));
+                }
+            }
+        }
+
+        let fresh = if pending.is_empty() {
+            Vec::new()
+        } else {
+            self.embedding_provider.embed_batch(&pending).await?
+        };
+        for (text, embedding) in pending.iter().zip(&fresh) {
+            cache.insert(model, text, embedding.clone());
+        }
+        debug!(chunks = texts.len(), embedded = pending.len(), "embedded chunks with cache");
+
+        Ok(slots
+            .into_iter()
+            .map(|slot| slot.unwrap_or_else(|index| fresh.get(index).cloned().unwrap_or_default()))
+            .collect())
+    }
+}
+
+/// Replace each child chunk with its parent, keeping the first (best-scored)
+/// result per parent and dropping later duplicates.
+fn resolve_parents(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut seen = std::collections::HashSet::new();
+    let mut resolved = Vec::with_capacity(results.len());
+    for SearchResult { chunk, score } in results {
+        let chunk = match (chunk.parent_id(), chunk.metadata.get(PARENT_TEXT_KEY)) {
+            (Some(parent_id), Some(parent_text)) => {
+                let mut metadata = chunk.metadata.clone();
+                metadata.remove(PARENT_ID_KEY);
+                metadata.remove(PARENT_TEXT_KEY);
+                Chunk {
+                    id: parent_id.to_string(),
+                    text: parent_text.clone(),
+                    embedding: Vec::new(),
+                    metadata,
+                    document_id: chunk.document_id,
+                }
+            }
+            _ => chunk,
+        };
+        if seen.insert(chunk.id.clone()) {
+            resolved.push(SearchResult { chunk, score });
+        }
+    }
+    resolved
+}
+
+/// Multiply each score by `0.5^(age / half_life)` and re-sort by descending score.
+fn apply_recency_decay(results: &mut [SearchResult], half_life: Duration, now: DateTime<Utc>) {
+    for result in results.iter_mut() {
+        if let Some(indexed_at) = result.chunk.indexed_at() {
+            let age = (now - indexed_at).to_std().unwrap_or_default();
+            let weight = 0.5f64.powf(age.as_secs_f64() / half_life.as_secs_f64());
+            result.score = (f64::from(result.score) * weight) as f32;
+        }
+    }
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+/// A [`RagPipeline`] scoped to one tenant.
+///
+/// Created by [`RagPipeline::for_tenant`]. Collections named here are the
+/// tenant's own: they map to separate vector store collections via
+/// [`VectorStore::tenant_collection`], ingested chunks are stamped with
+/// [`TENANT_ID_KEY`], and queries drop any result stamped with another tenant.
+pub struct TenantPipeline<'a> {
+    pipeline: &'a RagPipeline,
+    tenant: TenantId,
+}
+
+impl TenantPipeline<'_> {
+    /// Return the tenant this handle is scoped to.
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    /// Create one of the tenant's collections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::PipelineError`] if the vector store operation fails.
+    pub async fn create_collection(&self, name: &str) -> Result<()> {
+        self.pipeline.create_collection_in(Some(&self.tenant), name).await
+    }
+
+    /// Delete one of the tenant's collections and all its data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::PipelineError`] if the vector store operation fails.
+    pub async fn delete_collection(&self, name: &str) -> Result<()> {
+        self.pipeline.delete_collection_in(Some(&self.tenant), name).await
+    }
+
+    /// Ingest a document into one of the tenant's collections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::PipelineError`] if embedding or storage fails.
+    pub async fn ingest(&self, collection: &str, document: &Document) -> Result<Vec<Chunk>> {
+        self.pipeline.ingest_in(Some(&self.tenant), collection, document).await
+    }
+
+    /// Ingest several documents into one of the tenant's collections.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::PipelineError`] on the first document that fails.
+    pub async fn ingest_batch(
+        &self,
+        collection: &str,
+        documents: &[Document],
+    ) -> Result<Vec<Chunk>> {
+        self.pipeline.ingest_batch_in(Some(&self.tenant), collection, documents).await
+    }
+
+    /// Query one of the tenant's collections. Only the tenant's chunks are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::PipelineError`] if embedding or search fails.
+    pub async fn query(&self, collection: &str, query: &str) -> Result<Vec<SearchResult>> {
+        let top_k = self.pipeline.config.top_k;
+        self.pipeline.query_in(Some(&self.tenant), collection, query, top_k).await
+    }
 }
 
 /// Builder for constructing a [`RagPipeline`].
 ///
-/// All fields except `reranker` are required. Call [`build()`](RagPipelineBuilder::build)
+/// All fields except `reranker` and `embedding_cache` are required. Call [`build()`](RagPipelineBuilder::build)
 /// to validate and produce the pipeline.
 ///
 /// # Example
@@ -227,6 +528,8 @@ pub struct RagPipelineBuilder {
     vector_store: Option<Arc<dyn VectorStore>>,
     chunker: Option<Arc<dyn Chunker>>,
     reranker: Option<Arc<dyn Reranker>>,
+    embedding_cache: Option<EmbeddingCache>,
+    require_tenant: bool,
 }
 
 impl RagPipelineBuilder {
@@ -260,11 +563,31 @@ impl RagPipelineBuilder {
         self
     }
 
+    /// Set an optional cache so repeated queries (and, if enabled, repeated
+    /// chunk texts) skip the embedding provider.
+    pub fn embedding_cache(mut self, cache: EmbeddingCache) -> Self {
+        self.embedding_cache = Some(cache);
+        self
+    }
+
+    /// Reject operations that are not scoped to a tenant.
+    ///
+    /// When enabled, the unscoped [`RagPipeline`] methods return
+    /// [`RagError::ConfigError`] and all access goes through
+    /// [`RagPipeline::for_tenant`]. Use this for services shared by several
+    /// tenants so a missing tenant is an error rather than a shared collection.
+    pub fn require_tenant(mut self, require: bool) -> Self {
+        self.require_tenant = require;
+        self
+    }
+
     /// Build the [`RagPipeline`], validating that all required fields are set.
     ///
     /// # Errors
     ///
-    /// Returns [`RagError::ConfigError`] if any required field is missing.
+    /// Returns [`RagError::ConfigError`] if any required field is missing, the
+    /// embedding cache has zero capacity, or the vector store's metric differs
+    /// from [`RagConfig::metric`].
     pub fn build(self) -> Result<RagPipeline> {
         let config =
             self.config.ok_or_else(|| RagError::ConfigError("config is required".to_string()))?;
@@ -276,6 +599,18 @@ impl RagPipelineBuilder {
             .ok_or_else(|| RagError::ConfigError("vector_store is required".to_string()))?;
         let chunker =
             self.chunker.ok_or_else(|| RagError::ConfigError("chunker is required".to_string()))?;
+        if vector_store.metric() != config.metric {
+            return Err(RagError::ConfigError(format!(
+                "vector store uses {:?} similarity but the config expects {:?}",
+                vector_store.metric(),
+                config.metric
+            )));
+        }
+        if self.embedding_cache.as_ref().is_some_and(|cache| cache.capacity() == 0) {
+            return Err(RagError::ConfigError(
+                "embedding cache capacity must be greater than zero".to_string(),
+            ));
+        }
 
         Ok(RagPipeline {
             config,
@@ -283,6 +618,8 @@ impl RagPipelineBuilder {
             vector_store,
             chunker,
             reranker: self.reranker,
+            embedding_cache: self.embedding_cache,
+            require_tenant: self.require_tenant,
         })
     }
 }
@@ -28,21 +28,25 @@ use tracing::debug;
 
 use crate::document::{Chunk, SearchResult};
 use crate::error::{RagError, Result};
+use crate::metric::DistanceMetric;
 use crate::vectorstore::VectorStore;
 
 /// A [`VectorStore`] backed by [Qdrant](https://qdrant.tech/).
 ///
 /// Wraps a [`qdrant_client::Qdrant`] client and maps collections to Qdrant
-/// collections with cosine distance. Chunk metadata is stored as Qdrant payload.
+/// collections. Collections use cosine distance unless another metric is set
+/// with [`with_metric`](Self::with_metric). Chunk metadata is stored as Qdrant
+/// payload.
 pub struct QdrantVectorStore {
     client: Qdrant,
+    metric: DistanceMetric,
 }
 
 impl QdrantVectorStore {
     /// Create a new Qdrant vector store connecting to the given URL.
     pub fn new(url: &str) -> Result<Self> {
         let client = Qdrant::from_url(url).build().map_err(Self::map_err)?;
-        Ok(Self { client })
+        Ok(Self { client, metric: DistanceMetric::Cosine })
     }
 
     /// Create a new Qdrant vector store with default URL (`http://localhost:6334`).
@@ -52,7 +56,24 @@ impl QdrantVectorStore {
 
     /// Create a new Qdrant vector store from an existing client.
     pub fn from_client(client: Qdrant) -> Self {
-        Self { client }
+        Self { client, metric: DistanceMetric::Cosine }
+    }
+
+    /// Use `metric` for collections created by this store and for scoring.
+    ///
+    /// Qdrant fixes the metric when a collection is created, so existing
+    /// collections keep the metric they were created with.
+    pub fn with_metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    fn distance(&self) -> Distance {
+        match self.metric {
+            DistanceMetric::Cosine => Distance::Cosine,
+            DistanceMetric::DotProduct => Distance::Dot,
+            DistanceMetric::Euclidean => Distance::Euclid,
+        }
     }
 
     fn map_err(e: qdrant_client::QdrantError) -> RagError {
@@ -81,12 +102,12 @@ impl VectorStore for QdrantVectorStore {
         self.client
             .create_collection(
                 CreateCollectionBuilder::new(name)
-                    .vectors_config(VectorParamsBuilder::new(dimensions as u64, Distance::Cosine)),
+                    .vectors_config(VectorParamsBuilder::new(dimensions as u64, self.distance())),
             )
             .await
             .map_err(Self::map_err)?;
 
-        debug!(collection = name, dimensions, "created qdrant collection");
+        debug!(collection = name, dimensions, metric = ?self.metric, "created qdrant collection");
         Ok(())
     }
 
@@ -209,13 +230,23 @@ impl VectorStore for QdrantVectorStore {
                     })
                     .unwrap_or_default();
 
+                // Qdrant reports the raw distance for Euclid collections.
+                let score = match self.metric {
+                    DistanceMetric::Euclidean => DistanceMetric::euclidean_score(scored.score),
+                    DistanceMetric::Cosine | DistanceMetric::DotProduct => scored.score,
+                };
+
                 SearchResult {
                     chunk: Chunk { id, text, embedding: vec![], metadata, document_id },
-                    score: scored.score,
+                    score,
                 }
             })
             .collect();
 
         Ok(results)
     }
+
+    fn metric(&self) -> DistanceMetric {
+        self.metric
+    }
 }
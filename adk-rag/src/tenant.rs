@@ -0,0 +1,102 @@
+//! Tenant identifiers for multi-tenant pipelines.
+//!
+//! A [`TenantId`] scopes every pipeline operation to one tenant. Each
+//! [`VectorStore`](crate::VectorStore) maps a tenant's logical collection to
+//! an isolated physical collection via
+//! [`VectorStore::tenant_collection`](crate::VectorStore::tenant_collection),
+//! and the pipeline additionally stamps ingested chunks with the tenant and
+//! drops any query result stamped with another tenant.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use adk_rag::{RagPipeline, TenantId};
+//!
+//! let acme = pipeline.for_tenant(TenantId::new("acme")?);
+//! acme.create_collection("docs").await?;
+//! acme.ingest("docs", &document).await?;
+//! let results = acme.query("docs", "refund policy").await?;
+//! ```
+
+use std::fmt;
+
+use crate::error::{RagError, Result};
+
+/// Maximum length of a tenant id.
+const MAX_TENANT_ID_LEN: usize = 64;
+
+/// A validated tenant identifier.
+///
+/// Tenant ids are 1 to 64 ASCII letters, digits, or hyphens. Underscores are
+/// not allowed so that a tenant-scoped collection name such as
+/// `tenant_acme__docs` can never be produced by two different tenants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Validate and wrap a tenant id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RagError::ConfigError`] if the id is empty, longer than 64
+    /// characters, or contains anything other than ASCII letters, digits, or
+    /// hyphens.
+    pub fn new(id: impl Into<String>) -> Result<Self> {
+        let id = id.into();
+        if id.is_empty() || id.len() > MAX_TENANT_ID_LEN {
+            return Err(RagError::ConfigError(format!(
+                "tenant id must be 1 to {MAX_TENANT_ID_LEN} characters, got {}",
+                id.len()
+            )));
+        }
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(RagError::ConfigError(format!(
+                "tenant id '{id}' may only contain ASCII letters, digits, and hyphens"
+            )));
+        }
+        Ok(Self(id))
+    }
+
+    /// Return the tenant id as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The default physical collection name for this tenant's `collection`:
+    /// `tenant_{id}__{collection}`.
+    pub fn scoped_collection(&self, collection: &str) -> String {
+        format!("tenant_{}__{collection}", self.0)
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for TenantId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_id_validation() {
+        assert!(TenantId::new("acme-42").is_ok());
+        assert!(TenantId::new("").is_err());
+        assert!(TenantId::new("a_b").is_err());
+        assert!(TenantId::new("a/b").is_err());
+        assert!(TenantId::new("x".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_scoped_collection() {
+        let tenant = TenantId::new("acme").unwrap();
+        assert_eq!(tenant.scoped_collection("docs"), "tenant_acme__docs");
+    }
+}
@@ -18,21 +18,24 @@
 
 use std::sync::Arc;
 
-use adk_core::{AdkError, Tool, ToolContext};
+use adk_core::{AdkError, Citation, Tool, ToolContext};
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use tracing::{error, info};
 
 use crate::pipeline::RagPipeline;
+use crate::tenant::TenantId;
 
 /// A retrieval tool that wraps a [`RagPipeline`] for agentic use.
 ///
 /// Implements [`adk_core::Tool`] so it can be attached to any ADK agent.
 /// The tool accepts a required `query` string and optional `collection`
-/// and `top_k` parameters.
+/// and `top_k` parameters. A tool created with [`with_tenant`](Self::with_tenant)
+/// only searches that tenant's collections, whatever the agent asks for.
 pub struct RagTool {
     pipeline: Arc<RagPipeline>,
     default_collection: String,
+    tenant: Option<TenantId>,
 }
 
 impl RagTool {
@@ -41,7 +44,16 @@ impl RagTool {
     /// The `default_collection` is used when the agent does not specify
     /// a collection in the tool call arguments.
     pub fn new(pipeline: Arc<RagPipeline>, default_collection: impl Into<String>) -> Self {
-        Self { pipeline, default_collection: default_collection.into() }
+        Self { pipeline, default_collection: default_collection.into(), tenant: None }
+    }
+
+    /// Scope every search to `tenant`'s collections.
+    ///
+    /// Required when the pipeline was built with
+    /// [`require_tenant`](crate::RagPipelineBuilder::require_tenant).
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = Some(tenant);
+        self
     }
 }
 
@@ -76,7 +88,7 @@ impl Tool for RagTool {
         }))
     }
 
-    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+    async fn execute(&self, ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
         let query = args
             .get("query")
             .and_then(|v| v.as_str())
@@ -85,59 +97,49 @@ impl Tool for RagTool {
         let collection =
             args.get("collection").and_then(|v| v.as_str()).unwrap_or(&self.default_collection);
 
-        let top_k_override = args.get("top_k").and_then(|v| v.as_u64()).map(|v| v as usize);
+        let top_k = args
+            .get("top_k")
+            .and_then(|v| v.as_u64())
+            .map_or(self.pipeline.config().top_k, |v| v as usize);
 
-        info!(query, collection, top_k_override, "rag_search tool called");
+        let tenant = self.tenant.as_ref().map_or("", TenantId::as_str);
+        info!(query, collection, top_k, tenant, "rag_search tool called");
 
-        let results = if let Some(top_k) = top_k_override {
-            // Override top_k: embed query, search with custom top_k, rerank, filter
-            self.query_with_top_k(collection, query, top_k).await
-        } else {
-            self.pipeline.query(collection, query).await
-        };
+        let results = self.pipeline.query_in(self.tenant.as_ref(), collection, query, top_k).await;
 
         let results = results.map_err(|e| {
             error!(error = %e, "rag_search failed");
             AdkError::tool(format!("RAG search failed: {e}"))
         })?;
 
+        // Report each retrieved document as a source of the agent's answer
+        let mut citations: Vec<Citation> = Vec::new();
+        for result in &results {
+            let chunk = &result.chunk;
+            let citation = Citation {
+                text_span: None,
+                source_uri: chunk.source_uri().map(str::to_string),
+                title: Some(
+                    chunk
+                        .metadata
+                        .get("title")
+                        .cloned()
+                        .unwrap_or_else(|| chunk.document_id.clone()),
+                ),
+            };
+            if !citations.contains(&citation) {
+                citations.push(citation);
+            }
+        }
+        if !citations.is_empty() {
+            let mut actions = ctx.actions();
+            actions.citations.extend(citations);
+            ctx.set_actions(actions);
+        }
+
         serde_json::to_value(&results).map_err(|e| {
             error!(error = %e, "failed to serialize search results");
             AdkError::tool(format!("failed to serialize results: {e}"))
         })
     }
 }
-
-impl RagTool {
-    /// Query with a custom `top_k`, bypassing the pipeline's configured value.
-    async fn query_with_top_k(
-        &self,
-        collection: &str,
-        query: &str,
-        top_k: usize,
-    ) -> crate::error::Result<Vec<crate::document::SearchResult>> {
-        // 1. Embed the query
-        let query_embedding =
-            self.pipeline.embedding_provider().embed(query).await.map_err(|e| {
-                crate::error::RagError::PipelineError(format!("query embedding failed: {e}"))
-            })?;
-
-        // 2. Search with the overridden top_k
-        let results = self
-            .pipeline
-            .vector_store()
-            .search(collection, &query_embedding, top_k)
-            .await
-            .map_err(|e| {
-                crate::error::RagError::PipelineError(format!(
-                    "search failed in collection '{collection}': {e}"
-                ))
-            })?;
-
-        // 3. Filter by similarity threshold
-        let threshold = self.pipeline.config().similarity_threshold;
-        let filtered = results.into_iter().filter(|r| r.score >= threshold).collect();
-
-        Ok(filtered)
-    }
-}
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 
 use crate::document::{Chunk, SearchResult};
 use crate::error::Result;
+use crate::metric::DistanceMetric;
+use crate::tenant::TenantId;
 
 /// A storage backend for vector embeddings with similarity search.
 ///
@@ -43,4 +45,25 @@ pub trait VectorStore: Send + Sync {
         embedding: &[f32],
         top_k: usize,
     ) -> Result<Vec<SearchResult>>;
+
+    /// Name of the physical collection holding `tenant`'s `collection`.
+    ///
+    /// Tenant-scoped pipeline operations pass the returned name to every other
+    /// method, so each tenant gets its own collection (a Qdrant collection, a
+    /// pgvector table, or a separate in-memory map). The default is
+    /// [`TenantId::scoped_collection`]. Backends that partition a shared
+    /// collection instead may return `collection` unchanged; the pipeline also
+    /// stamps chunks with [`TENANT_ID_KEY`](crate::document::TENANT_ID_KEY)
+    /// and drops results stamped with another tenant.
+    fn tenant_collection(&self, tenant: &TenantId, collection: &str) -> String {
+        tenant.scoped_collection(collection)
+    }
+
+    /// Similarity metric used by [`search`](Self::search).
+    ///
+    /// The default is [`DistanceMetric::Cosine`]. Backends with a configurable
+    /// metric return the one they were constructed with.
+    fn metric(&self) -> DistanceMetric {
+        DistanceMetric::Cosine
+    }
 }
@@ -0,0 +1,94 @@
+//! Embedding cache integration with the pipeline.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use adk_rag::{
+    Document, EmbeddingCache, EmbeddingProvider, FixedSizeChunker, InMemoryVectorStore, RagConfig,
+    RagPipeline, Result,
+};
+use async_trait::async_trait;
+
+/// Counts every text sent to the provider.
+#[derive(Default)]
+struct CountingEmbedder {
+    embedded: AtomicUsize,
+}
+
+#[async_trait]
+impl EmbeddingProvider for CountingEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.embedded.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![1.0, text.len() as f32])
+    }
+
+    fn dimensions(&self) -> usize {
+        2
+    }
+}
+
+async fn pipeline(embedder: Arc<CountingEmbedder>, cache: EmbeddingCache) -> RagPipeline {
+    let pipeline = RagPipeline::builder()
+        .config(RagConfig::default())
+        .embedding_provider(embedder)
+        .vector_store(Arc::new(InMemoryVectorStore::new()))
+        .chunker(Arc::new(FixedSizeChunker::new(5, 0)))
+        .embedding_cache(cache)
+        .build()
+        .unwrap();
+    pipeline.create_collection("kb").await.unwrap();
+    pipeline
+}
+
+#[tokio::test]
+async fn test_repeated_query_hits_cache() {
+    let embedder = Arc::new(CountingEmbedder::default());
+    let pipeline = pipeline(embedder.clone(), EmbeddingCache::new(16)).await;
+
+    pipeline.query("kb", "refund policy").await.unwrap();
+    pipeline.query("kb", "  refund   policy\n").await.unwrap();
+
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 1);
+    let stats = pipeline.embedding_cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+}
+
+#[tokio::test]
+async fn test_identical_chunks_embedded_once() {
+    let embedder = Arc::new(CountingEmbedder::default());
+    let cache = EmbeddingCache::new(16).with_chunk_embeddings(true);
+    let pipeline = pipeline(embedder.clone(), cache).await;
+    let document = Document {
+        id: "doc".to_string(),
+        text: "abcdeabcdeabcdevwxyz".to_string(),
+        metadata: HashMap::new(),
+        source_uri: None,
+    };
+
+    let chunks = pipeline.ingest("kb", &document).await.unwrap();
+    assert_eq!(chunks.len(), 4);
+    assert!(chunks.iter().all(|chunk| chunk.embedding.len() == 2));
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 2);
+
+    // Re-ingesting reuses every cached chunk embedding.
+    pipeline.ingest("kb", &document).await.unwrap();
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_chunks_not_cached_by_default() {
+    let embedder = Arc::new(CountingEmbedder::default());
+    let pipeline = pipeline(embedder.clone(), EmbeddingCache::new(16)).await;
+    let document = Document {
+        id: "doc".to_string(),
+        text: "abcdeabcde".to_string(),
+        metadata: HashMap::new(),
+        source_uri: None,
+    };
+
+    pipeline.ingest("kb", &document).await.unwrap();
+
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 2);
+    assert_eq!(pipeline.embedding_cache_stats().unwrap().entries, 0);
+}
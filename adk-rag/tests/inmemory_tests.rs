@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 
+use adk_rag::DistanceMetric;
 use adk_rag::document::Chunk;
 use adk_rag::inmemory::InMemoryVectorStore;
 use adk_rag::vectorstore::VectorStore;
@@ -92,3 +93,55 @@ mod prop_inmemory_search_ordering {
         }
     }
 }
+
+fn chunk(id: &str, embedding: Vec<f32>) -> Chunk {
+    Chunk {
+        id: id.to_string(),
+        text: id.to_string(),
+        embedding,
+        metadata: HashMap::new(),
+        document_id: "doc_1".to_string(),
+    }
+}
+
+async fn ranking(metric: DistanceMetric) -> Vec<String> {
+    let store = InMemoryVectorStore::with_metric(metric);
+    store.create_collection("test", 2).await.unwrap();
+    store
+        .upsert(
+            "test",
+            &[
+                // Points the same way as the query but is short.
+                chunk("aligned", vec![1.0, 0.0]),
+                // Off-angle but much longer.
+                chunk("long", vec![3.0, 3.0]),
+                // Off-angle and closest to the query point.
+                chunk("near", vec![0.8, 0.5]),
+            ],
+        )
+        .await
+        .unwrap();
+    let results = store.search("test", &[1.0, 0.0], 3).await.unwrap();
+    results.into_iter().map(|result| result.chunk.id).collect()
+}
+
+#[tokio::test]
+async fn test_metric_changes_ranking_for_unnormalized_vectors() {
+    assert_eq!(ranking(DistanceMetric::Cosine).await, ["aligned", "near", "long"]);
+    assert_eq!(ranking(DistanceMetric::DotProduct).await, ["long", "aligned", "near"]);
+    assert_eq!(ranking(DistanceMetric::Euclidean).await, ["aligned", "near", "long"]);
+}
+
+#[tokio::test]
+async fn test_euclidean_scores_are_bounded() {
+    let store = InMemoryVectorStore::with_metric(DistanceMetric::Euclidean);
+    store.create_collection("test", 2).await.unwrap();
+    store
+        .upsert("test", &[chunk("same", vec![1.0, 0.0]), chunk("far", vec![1.0, 4.0])])
+        .await
+        .unwrap();
+
+    let results = store.search("test", &[1.0, 0.0], 2).await.unwrap();
+    assert_eq!(results[0].score, 1.0);
+    assert!((results[1].score - 0.2).abs() < 1e-6);
+}
@@ -0,0 +1,82 @@
+//! Parent-document retrieval: search small children, return their parents.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use adk_rag::{
+    Document, EmbeddingProvider, InMemoryVectorStore, ParentChildChunker, RagConfig, RagPipeline,
+    RecursiveChunker, Result,
+};
+use async_trait::async_trait;
+
+/// Embeds text by which topics it mentions.
+struct TopicEmbedder;
+
+#[async_trait]
+impl EmbeddingProvider for TopicEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let text = text.to_lowercase();
+        let mentions = |topic: &str| if text.contains(topic) { 1.0 } else { 0.0 };
+        Ok(vec![mentions("refund"), mentions("shipping"), 0.1])
+    }
+
+    fn dimensions(&self) -> usize {
+        3
+    }
+}
+
+const REFUNDS: &str = "Our refund window is 30 days. Refunds go back to the original card.\n\n";
+const SHIPPING: &str = "Shipping takes 5 days. Shipping is free over $50.";
+
+async fn pipeline(parent_document: bool) -> RagPipeline {
+    let config = RagConfig::builder()
+        .similarity_threshold(0.5)
+        .parent_document(parent_document)
+        .build()
+        .unwrap();
+    let pipeline = RagPipeline::builder()
+        .config(config)
+        .embedding_provider(Arc::new(TopicEmbedder))
+        .vector_store(Arc::new(InMemoryVectorStore::new()))
+        .chunker(Arc::new(ParentChildChunker::new(
+            RecursiveChunker::new(80, 0),
+            RecursiveChunker::new(40, 0),
+        )))
+        .build()
+        .unwrap();
+
+    pipeline.create_collection("kb").await.unwrap();
+    let document = Document {
+        id: "policy".to_string(),
+        text: format!("{REFUNDS}{SHIPPING}"),
+        metadata: HashMap::new(),
+        source_uri: None,
+    };
+    let children = pipeline.ingest("kb", &document).await.unwrap();
+    assert_eq!(children.len(), 4);
+    pipeline
+}
+
+#[tokio::test]
+async fn test_child_match_returns_the_parents_fuller_text_once() {
+    let results = pipeline(true).await.query("kb", "refund window").await.unwrap();
+
+    // Both refund children matched; they share one parent.
+    assert_eq!(results.len(), 1);
+    let parent = &results[0].chunk;
+    assert_eq!(parent.id, "policy_0");
+    assert_eq!(parent.text, REFUNDS);
+    assert_eq!(parent.document_id, "policy");
+    assert!(parent.parent_id().is_none());
+    assert!(results[0].score > 0.9);
+}
+
+#[tokio::test]
+async fn test_children_are_returned_when_parent_resolution_is_off() {
+    let results = pipeline(false).await.query("kb", "refund window").await.unwrap();
+
+    let texts: Vec<_> = results.iter().map(|r| r.chunk.text.as_str()).collect();
+    assert_eq!(texts.len(), 2);
+    assert!(texts.iter().all(|text| text.len() <= 40 && text.to_lowercase().contains("refund")));
+    assert!(results.iter().all(|r| r.chunk.parent_id() == Some("policy_0")));
+}
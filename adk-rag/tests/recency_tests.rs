@@ -0,0 +1,90 @@
+//! Freshness weighting and expiry at query time.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use adk_rag::document::{EXPIRES_AT_KEY, INDEXED_AT_KEY};
+use adk_rag::{
+    Chunk, EmbeddingProvider, FixedSizeChunker, InMemoryVectorStore, RagConfig, RagPipeline,
+    Result, VectorStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+/// Embeds every query to the same unit vector.
+struct FixedEmbedder;
+
+#[async_trait]
+impl EmbeddingProvider for FixedEmbedder {
+    async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+        Ok(vec![1.0, 0.0])
+    }
+
+    fn dimensions(&self) -> usize {
+        2
+    }
+}
+
+/// A chunk whose cosine similarity to the query is `similarity`, indexed `age_days` ago.
+fn chunk(id: &str, similarity: f32, age_days: i64) -> Chunk {
+    let indexed_at = Utc::now() - chrono::Duration::days(age_days);
+    Chunk {
+        id: id.to_string(),
+        text: id.to_string(),
+        embedding: vec![similarity, (1.0 - similarity * similarity).sqrt()],
+        metadata: HashMap::from([(INDEXED_AT_KEY.to_string(), indexed_at.to_rfc3339())]),
+        document_id: id.to_string(),
+    }
+}
+
+async fn pipeline(config: RagConfig, chunks: &[Chunk]) -> RagPipeline {
+    let store = Arc::new(InMemoryVectorStore::new());
+    store.create_collection("kb", 2).await.unwrap();
+    store.upsert("kb", chunks).await.unwrap();
+
+    RagPipeline::builder()
+        .config(config)
+        .embedding_provider(Arc::new(FixedEmbedder))
+        .vector_store(store)
+        .chunker(Arc::new(FixedSizeChunker::new(512, 100)))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_newer_chunk_outranks_older_under_decay() {
+    let chunks = [chunk("stale", 0.95, 60), chunk("fresh", 0.80, 1)];
+
+    let plain = pipeline(RagConfig::default(), &chunks).await;
+    let ids: Vec<_> =
+        plain.query("kb", "q").await.unwrap().into_iter().map(|r| r.chunk.id).collect();
+    assert_eq!(ids, ["stale", "fresh"]);
+
+    let config =
+        RagConfig::builder().recency_decay(Duration::from_secs(7 * 24 * 3600)).build().unwrap();
+    let decayed = pipeline(config, &chunks).await;
+    let results = decayed.query("kb", "q").await.unwrap();
+    let ids: Vec<_> = results.iter().map(|r| r.chunk.id.as_str()).collect();
+    assert_eq!(ids, ["fresh", "stale"]);
+    assert!(results[1].score < 0.01);
+}
+
+#[tokio::test]
+async fn test_expired_chunks_are_not_returned() {
+    let mut expired = chunk("expired", 0.99, 0);
+    expired
+        .metadata
+        .insert(EXPIRES_AT_KEY.to_string(), (Utc::now() - chrono::Duration::hours(1)).to_rfc3339());
+    let chunks = [expired, chunk("current", 0.5, 0)];
+
+    let pipeline = pipeline(RagConfig::default(), &chunks).await;
+    let ids: Vec<_> =
+        pipeline.query("kb", "q").await.unwrap().into_iter().map(|r| r.chunk.id).collect();
+    assert_eq!(ids, ["current"]);
+}
+
+#[test]
+fn test_zero_half_life_is_rejected() {
+    assert!(RagConfig::builder().recency_decay(Duration::ZERO).build().is_err());
+}
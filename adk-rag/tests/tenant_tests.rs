@@ -0,0 +1,158 @@
+//! Tenant isolation on ingest and query.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use adk_rag::document::TENANT_ID_KEY;
+use adk_rag::{
+    Chunk, Document, EmbeddingProvider, FixedSizeChunker, InMemoryVectorStore, RagConfig, RagError,
+    RagPipeline, Result, SearchResult, TenantId, VectorStore,
+};
+use async_trait::async_trait;
+
+/// Embeds every text to the same vector, so every chunk matches every query.
+struct FlatEmbedder;
+
+#[async_trait]
+impl EmbeddingProvider for FlatEmbedder {
+    async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+        Ok(vec![1.0, 0.0])
+    }
+
+    fn dimensions(&self) -> usize {
+        2
+    }
+}
+
+/// Keeps every tenant in one shared collection, like a tenant-column backend.
+#[derive(Default)]
+struct SharedStore(InMemoryVectorStore);
+
+#[async_trait]
+impl VectorStore for SharedStore {
+    async fn create_collection(&self, name: &str, dimensions: usize) -> Result<()> {
+        self.0.create_collection(name, dimensions).await
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        self.0.delete_collection(name).await
+    }
+
+    async fn upsert(&self, collection: &str, chunks: &[Chunk]) -> Result<()> {
+        self.0.upsert(collection, chunks).await
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str]) -> Result<()> {
+        self.0.delete(collection, ids).await
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        embedding: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        self.0.search(collection, embedding, top_k).await
+    }
+
+    fn tenant_collection(&self, _tenant: &TenantId, collection: &str) -> String {
+        collection.to_string()
+    }
+}
+
+fn pipeline(store: Arc<dyn VectorStore>) -> RagPipeline {
+    RagPipeline::builder()
+        .config(RagConfig::default())
+        .embedding_provider(Arc::new(FlatEmbedder))
+        .vector_store(store)
+        .chunker(Arc::new(FixedSizeChunker::new(512, 0)))
+        .require_tenant(true)
+        .build()
+        .unwrap()
+}
+
+fn document(id: &str, text: &str) -> Document {
+    Document {
+        id: id.to_string(),
+        text: text.to_string(),
+        metadata: HashMap::new(),
+        source_uri: None,
+    }
+}
+
+fn document_ids(results: &[SearchResult]) -> Vec<&str> {
+    let mut ids: Vec<&str> = results.iter().map(|r| r.chunk.document_id.as_str()).collect();
+    ids.sort_unstable();
+    ids
+}
+
+async fn ingest_two_tenants(pipeline: &RagPipeline) {
+    let acme = pipeline.for_tenant(TenantId::new("acme").unwrap());
+    let globex = pipeline.for_tenant(TenantId::new("globex").unwrap());
+    for tenant in [&acme, &globex] {
+        tenant.create_collection("kb").await.unwrap();
+    }
+    acme.ingest_batch(
+        "kb",
+        &[document("acme-refunds", "Refunds take 5 days"), document("acme-hours", "Open 9-5")],
+    )
+    .await
+    .unwrap();
+
+    // A document cannot claim another tenant through its metadata.
+    let mut spoofed = document("globex-refunds", "Refunds take 30 days");
+    spoofed.metadata.insert(TENANT_ID_KEY.to_string(), "acme".to_string());
+    let chunks = globex.ingest("kb", &spoofed).await.unwrap();
+    assert!(chunks.iter().all(|chunk| chunk.tenant_id() == Some("globex")));
+}
+
+#[tokio::test]
+async fn test_queries_never_cross_tenants() {
+    let store = Arc::new(InMemoryVectorStore::new());
+    let pipeline = pipeline(store.clone());
+    ingest_two_tenants(&pipeline).await;
+
+    let acme = pipeline.for_tenant(TenantId::new("acme").unwrap());
+    let globex = pipeline.for_tenant(TenantId::new("globex").unwrap());
+    assert_eq!(
+        document_ids(&acme.query("kb", "refunds").await.unwrap()),
+        ["acme-hours", "acme-refunds"]
+    );
+    assert_eq!(document_ids(&globex.query("kb", "refunds").await.unwrap()), ["globex-refunds"]);
+
+    // Each tenant has its own collection in the store.
+    let acme_only = store.search("tenant_acme__kb", &[1.0, 0.0], 10).await.unwrap();
+    assert_eq!(document_ids(&acme_only), ["acme-hours", "acme-refunds"]);
+
+    // A tenant that never created the collection gets an error, not other data.
+    let initech = pipeline.for_tenant(TenantId::new("initech").unwrap());
+    assert!(initech.query("kb", "refunds").await.is_err());
+}
+
+#[tokio::test]
+async fn test_shared_collection_is_filtered_by_tenant() {
+    let pipeline = pipeline(Arc::new(SharedStore::default()));
+    ingest_two_tenants(&pipeline).await;
+
+    let acme = pipeline.for_tenant(TenantId::new("acme").unwrap());
+    let globex = pipeline.for_tenant(TenantId::new("globex").unwrap());
+    assert_eq!(
+        document_ids(&acme.query("kb", "refunds").await.unwrap()),
+        ["acme-hours", "acme-refunds"]
+    );
+    assert_eq!(document_ids(&globex.query("kb", "refunds").await.unwrap()), ["globex-refunds"]);
+}
+
+#[tokio::test]
+async fn test_unscoped_operations_rejected_when_tenant_required() {
+    let pipeline = pipeline(Arc::new(InMemoryVectorStore::new()));
+    ingest_two_tenants(&pipeline).await;
+
+    assert!(pipeline.requires_tenant());
+    assert!(matches!(pipeline.create_collection("kb").await, Err(RagError::ConfigError(_))));
+    assert!(matches!(
+        pipeline.ingest("kb", &document("stray", "no tenant")).await,
+        Err(RagError::ConfigError(_))
+    ));
+    assert!(matches!(pipeline.query("kb", "refunds").await, Err(RagError::ConfigError(_))));
+}
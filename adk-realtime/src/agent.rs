@@ -1102,6 +1102,14 @@ impl CallbackContext for RealtimeToolContext {
     fn artifacts(&self) -> Option<Arc<dyn adk_core::Artifacts>> {
         self.parent_ctx.artifacts()
     }
+
+    fn memory(&self) -> Option<Arc<dyn adk_core::Memory>> {
+        self.parent_ctx.memory()
+    }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.parent_ctx.blackboard()
+    }
 }
 
 #[async_trait]
@@ -1125,4 +1133,14 @@ impl ToolContext for RealtimeToolContext {
             Ok(vec![])
         }
     }
+
+    fn get_state(&self, key: &str) -> Option<serde_json::Value> {
+        self.actions
+            .lock()
+            .unwrap()
+            .state_delta
+            .get(key)
+            .cloned()
+            .or_else(|| self.parent_ctx.session().state().get(key))
+    }
 }
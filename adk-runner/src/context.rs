@@ -1,6 +1,6 @@
 use adk_core::{
     AdkIdentity, Agent, AppName, Artifacts, CallbackContext, Content, Event, ExecutionIdentity,
-    InvocationContext as InvocationContextTrait, InvocationId, Memory, ReadonlyContext,
+    InvocationContext as InvocationContextTrait, InvocationId, Memory, Part, ReadonlyContext,
     RequestContext, RunConfig, SecretService, SessionId, UserId,
 };
 use adk_session::Session as AdkSession;
@@ -106,10 +106,25 @@ impl MutableSession {
     /// already done.
     ///
     /// When `agent_name` is `None`, all events are included (backward-compatible).
+    ///
+    /// Consecutive tool results are merged into one content, as the model
+    /// receives them within an invocation.
     pub fn conversation_history_for_agent_impl(
         &self,
         agent_name: Option<&str>,
     ) -> Vec<adk_core::Content> {
+        self.history_where(&|event| match agent_name {
+            Some(name) => event.author == "user" || event.author == name,
+            None => true,
+        })
+    }
+
+    /// Build conversation history from the events accepted by `keep`.
+    ///
+    /// An event carrying only function responses is kept exactly when the
+    /// event holding the matching call was, so a rejected call never leaves
+    /// its response behind and a kept call never loses it.
+    fn history_where(&self, keep: &dyn Fn(&Event) -> bool) -> Vec<adk_core::Content> {
         let Ok(events) = self.events.read() else {
             tracing::error!("events RwLock poisoned in conversation_history — returning empty");
             return Vec::new();
@@ -128,6 +143,7 @@ impl MutableSession {
             }
         }
 
+        let mut call_decisions: HashMap<String, bool> = HashMap::new();
         for event in events.iter() {
             // Skip the compaction event itself
             if event.actions.compaction.is_some() {
@@ -141,15 +157,21 @@ impl MutableSession {
                 continue;
             }
 
-            // When filtering for a specific agent, skip events from other agents.
-            // Keep: user messages and the agent's own events.
-            // Skip: other agents' events entirely (model-role, function calls,
-            // and function/tool responses). This prevents the sub-agent from
-            // seeing orphaned function responses without their preceding calls.
-            if let Some(name) = agent_name
-                && event.author != "user"
-                && event.author != name
-            {
+            // Function responses follow their call; everything else asks `keep`.
+            // When filtering for a specific agent this skips other agents'
+            // events entirely, so the sub-agent never sees orphaned responses.
+            let kept = match response_call_decision(event, &call_decisions) {
+                Some(kept) => kept,
+                None => keep(event),
+            };
+            if let Some(content) = &event.llm_response.content {
+                for part in &content.parts {
+                    if let Part::FunctionCall { name, id, .. } = part {
+                        call_decisions.insert(id.clone().unwrap_or_else(|| name.clone()), kept);
+                    }
+                }
+            }
+            if !kept {
                 continue;
             }
 
@@ -165,8 +187,30 @@ impl MutableSession {
             }
         }
 
-        history
+        // Each tool result is its own event; the model expects the results of
+        // one turn together.
+        adk_core::batch_function_responses(history)
+    }
+}
+
+/// Returns the decision recorded for the call an event responds to, if the
+/// event carries only function responses and one of their calls was seen.
+fn response_call_decision(event: &Event, call_decisions: &HashMap<String, bool>) -> Option<bool> {
+    let content = event.llm_response.content.as_ref()?;
+    if content.parts.is_empty() {
+        return None;
+    }
+    let mut decision = None;
+    for part in &content.parts {
+        let Part::FunctionResponse { function_response, id } = part else {
+            return None;
+        };
+        let key = id.as_deref().unwrap_or(&function_response.name);
+        if let Some(&kept) = call_decisions.get(key) {
+            decision = Some(decision.unwrap_or(false) || kept);
+        }
     }
+    decision
 }
 
 impl adk_core::Session for MutableSession {
@@ -193,6 +237,13 @@ impl adk_core::Session for MutableSession {
     fn conversation_history_for_agent(&self, agent_name: &str) -> Vec<adk_core::Content> {
         self.conversation_history_for_agent_impl(Some(agent_name))
     }
+
+    fn conversation_history_filtered(
+        &self,
+        keep: &dyn Fn(&Event) -> bool,
+    ) -> Vec<adk_core::Content> {
+        self.history_where(keep)
+    }
 }
 
 impl adk_core::State for MutableSession {
@@ -248,6 +299,10 @@ pub struct InvocationContext {
     request_context: Option<RequestContext>,
     /// Optional shared state for parallel agent coordination.
     shared_state: Option<Arc<adk_core::SharedState>>,
+    /// Run-scoped scratch space shared by every agent and tool in the run.
+    blackboard: Option<Arc<adk_core::Blackboard>>,
+    /// Run-wide record of the dialogue shared across agents.
+    conversation_buffer: Option<Arc<adk_core::ConversationBuffer>>,
     /// Optional secret service for retrieving secrets at runtime.
     /// When present, `get_secret()` delegates to this service.
     secret_service: Option<Arc<dyn SecretService>>,
@@ -255,7 +310,7 @@ pub struct InvocationContext {
     ///
     /// When present, `is_cancelled()` reflects this token, letting agents and
     /// tools detect external cancellation (`Runner::interrupt()` or
-    /// `RunConfig::cancellation_token`) during long-running work.
+    /// `Runner::run_cancellable`) during long-running work.
     cancellation_token: Option<tokio_util::sync::CancellationToken>,
 }
 
@@ -287,6 +342,8 @@ impl InvocationContext {
             session: Arc::new(MutableSession::new(session)),
             request_context: None,
             shared_state: None,
+            blackboard: None,
+            conversation_buffer: None,
             secret_service: None,
             cancellation_token: None,
         })
@@ -344,6 +401,8 @@ impl InvocationContext {
             session,
             request_context: None,
             shared_state: None,
+            blackboard: None,
+            conversation_buffer: None,
             secret_service: None,
             cancellation_token: None,
         })
@@ -414,6 +473,24 @@ impl InvocationContext {
         self
     }
 
+    /// Set the run-scoped blackboard.
+    ///
+    /// The [`Runner`](crate::Runner) shares one blackboard across the initial,
+    /// refreshed, and transfer contexts of a run and clears it when the run ends.
+    pub fn with_blackboard(mut self, blackboard: Arc<adk_core::Blackboard>) -> Self {
+        self.blackboard = Some(blackboard);
+        self
+    }
+
+    /// Set the run-wide conversation buffer.
+    ///
+    /// The [`Runner`](crate::Runner) shares one buffer across every context in a
+    /// run and records each persisted event into it.
+    pub fn with_conversation_buffer(mut self, buffer: Arc<adk_core::ConversationBuffer>) -> Self {
+        self.conversation_buffer = Some(buffer);
+        self
+    }
+
     /// Set the secret service for runtime secret retrieval.
     ///
     /// When configured, tools can call `ctx.get_secret("name")` to retrieve
@@ -483,9 +560,17 @@ impl CallbackContext for InvocationContext {
         self.artifacts.clone()
     }
 
+    fn memory(&self) -> Option<Arc<dyn Memory>> {
+        self.memory.clone()
+    }
+
     fn shared_state(&self) -> Option<Arc<adk_core::SharedState>> {
         self.shared_state.clone()
     }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.blackboard.clone()
+    }
 }
 
 #[async_trait]
@@ -494,10 +579,6 @@ impl InvocationContextTrait for InvocationContext {
         self.agent.clone()
     }
 
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        self.memory.clone()
-    }
-
     fn session(&self) -> &dyn adk_core::Session {
         self.session.as_ref()
     }
@@ -520,6 +601,10 @@ impl InvocationContextTrait for InvocationContext {
             .is_some_and(tokio_util::sync::CancellationToken::is_cancelled)
     }
 
+    fn conversation_buffer(&self) -> Option<Arc<adk_core::ConversationBuffer>> {
+        self.conversation_buffer.clone()
+    }
+
     fn user_scopes(&self) -> Vec<String> {
         self.request_context.as_ref().map_or_else(Vec::new, |rc| rc.scopes.clone())
     }
@@ -38,12 +38,19 @@ pub struct Launcher {
     app_name: Option<String>,
     session_service: Option<Arc<dyn SessionService>>,
     memory_service: Option<Arc<dyn Memory>>,
+    show_thinking: bool,
 }
 
 impl Launcher {
     /// Create a new launcher with the given agent.
     pub fn new(agent: Arc<dyn Agent>) -> Self {
-        Self { agent, app_name: None, session_service: None, memory_service: None }
+        Self {
+            agent,
+            app_name: None,
+            session_service: None,
+            memory_service: None,
+            show_thinking: true,
+        }
     }
 
     /// Set a custom application name (defaults to agent name).
@@ -64,6 +71,15 @@ impl Launcher {
         self
     }
 
+    /// Print the model's thinking parts as `[thinking]` lines (default `true`).
+    ///
+    /// Thinking is always kept in the session either way, so providers that
+    /// need their reasoning signatures back still receive them.
+    pub fn with_show_thinking(mut self, show: bool) -> Self {
+        self.show_thinking = show;
+        self
+    }
+
     /// Run the interactive console loop.
     ///
     /// Reads lines from stdin, sends them to the agent, and prints streaming
@@ -71,6 +87,7 @@ impl Launcher {
     pub async fn run(self) -> Result<()> {
         let app_name = self.app_name.unwrap_or_else(|| self.agent.name().to_string());
         let agent = self.agent;
+        let show_thinking = self.show_thinking;
 
         let session_service: Arc<dyn SessionService> =
             self.session_service.unwrap_or_else(|| Arc::new(InMemorySessionService::new()));
@@ -129,6 +146,7 @@ impl Launcher {
                 cache_capable: None,
                 request_context: None,
                 cancellation_token: None,
+                loop_break_threshold: None,
                 intra_compaction_config: None,
                 intra_compaction_summarizer: None,
                 #[cfg(feature = "context-compaction")]
@@ -149,7 +167,7 @@ impl Launcher {
                                         print!("{text}");
                                         io::stdout().flush().ok();
                                     }
-                                    Part::Thinking { thinking, .. } => {
+                                    Part::Thinking { thinking, .. } if show_thinking => {
                                         print!("\n[thinking] {thinking}");
                                         io::stdout().flush().ok();
                                     }
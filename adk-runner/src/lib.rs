@@ -63,6 +63,9 @@ pub use context::{InvocationContext, MutableSession};
 pub use launcher::Launcher;
 pub use runner::{Runner, RunnerConfig};
 
+// Re-export the token returned by `Runner::run_cancellable`
+pub use tokio_util::sync::CancellationToken;
+
 // Re-export RequestContext for convenience
 pub use adk_core::RequestContext;
 
@@ -59,6 +59,12 @@ pub struct RunnerConfig {
     pub request_context: Option<adk_core::RequestContext>,
     /// Optional cooperative cancellation token for externally managed runs.
     pub cancellation_token: Option<CancellationToken>,
+    /// How many times an agent may repeat the same tool call (same tool and
+    /// arguments) within one invocation before the loop is broken.
+    ///
+    /// Overrides [`RunConfig::loop_break_threshold`] when set. See that field
+    /// for how the loop is broken.
+    pub loop_break_threshold: Option<usize>,
     /// Optional intra-invocation compaction configuration.
     /// When set, the runner estimates token count before each agent run
     /// and triggers mid-invocation summarization when the threshold is exceeded.
@@ -109,6 +115,52 @@ pub struct Runner {
     active_sessions: Arc<std::sync::Mutex<std::collections::HashMap<String, CancellationToken>>>,
 }
 
+/// Awaits the next event of an agent's stream.
+///
+/// Returns `None` once `deadline` passes without an event; otherwise the
+/// stream's next item.
+async fn next_before_deadline(
+    stream: &mut EventStream,
+    deadline: Option<tokio::time::Instant>,
+) -> Option<Option<Result<adk_core::Event>>> {
+    use futures::StreamExt;
+
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, stream.next()).await.ok(),
+        None => Some(stream.next().await),
+    }
+}
+
+/// Fills in the author, invocation and branch of an event its agent left
+/// blank, so every event the runner records can be placed on the session's
+/// timeline and attributed to the agent that ran.
+fn stamp_event(event: &mut adk_core::Event, ctx: &dyn adk_core::ReadonlyContext) {
+    if event.author.is_empty() {
+        event.author = ctx.agent_name().to_string();
+    }
+    if event.invocation_id.is_empty() {
+        event.invocation_id = ctx.invocation_id().to_string();
+    }
+    if event.branch.is_empty() {
+        event.branch = ctx.branch().to_string();
+    }
+}
+
+/// The error that ends a run's stream once it is cancelled.
+fn cancelled_error() -> adk_core::AdkError {
+    adk_core::AdkError::cancelled(
+        adk_core::ErrorComponent::Agent,
+        "runner.cancelled",
+        "invocation was cancelled",
+    )
+}
+
+/// Whether the turn's deadline has passed. Agents stop themselves at the
+/// deadline too, so a stream that ends after it was cut short.
+fn deadline_passed(deadline: Option<tokio::time::Instant>) -> bool {
+    deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline)
+}
+
 impl Runner {
     /// Create a typestate builder for constructing a `Runner`.
     ///
@@ -137,7 +189,13 @@ impl Runner {
     ///
     /// Prefer [`Runner::builder()`] for a compile-time-safe construction API.
     pub fn new(config: RunnerConfig) -> Result<Self> {
-        let run_config = config.run_config.unwrap_or_default();
+        let mut run_config = config.run_config.unwrap_or_default();
+        if config.loop_break_threshold.is_some() {
+            run_config.loop_break_threshold = config.loop_break_threshold;
+        }
+        for sub_agent in config.agent.sub_agents() {
+            Self::warn_nested_global_instructions(sub_agent);
+        }
 
         // When a cache-capable model is provided but no explicit cache config,
         // use the default ContextCacheConfig to enable caching automatically.
@@ -230,6 +288,52 @@ impl Runner {
         session_id: SessionId,
         user_content: Content,
     ) -> Result<EventStream> {
+        self.run_with_config(user_id, session_id, user_content, self.run_config.clone()).await
+    }
+
+    /// Like [`run`](Self::run), but with a [`RunConfig`] for this call only.
+    ///
+    /// Use it to vary one request on a shared runner and agent, e.g. a
+    /// higher temperature through
+    /// [`RunConfig::generate_content_config`] or fewer tools through
+    /// [`RunConfig::tool_allowlist`]. The runner's own config is unchanged.
+    pub async fn run_with_config(
+        &self,
+        user_id: UserId,
+        session_id: SessionId,
+        user_content: Content,
+        run_config: RunConfig,
+    ) -> Result<EventStream> {
+        let (stream, _cancel) =
+            self.run_cancellable(user_id, session_id, user_content, run_config).await?;
+        Ok(stream)
+    }
+
+    /// Like [`run_with_config`](Self::run_with_config), but also returns a
+    /// token that cancels this invocation.
+    ///
+    /// Cancelling stops the agent, its model stream, and any tool still
+    /// running; their in-flight work is dropped, so the state changes of an
+    /// interrupted tool are never persisted. The stream then ends with an
+    /// [`ErrorCategory::Cancelled`](adk_core::ErrorCategory::Cancelled) error.
+    /// Events yielded before the cancellation stay in the session.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (mut stream, cancel) = runner
+    ///     .run_cancellable(user_id, session_id, content, RunConfig::default())
+    ///     .await?;
+    /// // e.g. from a "stop" button or a request timeout
+    /// cancel.cancel();
+    /// ```
+    pub async fn run_cancellable(
+        &self,
+        user_id: UserId,
+        session_id: SessionId,
+        user_content: Content,
+        mut run_config: RunConfig,
+    ) -> Result<(EventStream, CancellationToken)> {
         let app_name = self.app_name.clone();
         let typed_app_name = AppName::try_from(app_name.clone())?;
         let session_service = self.session_service.clone();
@@ -241,7 +345,14 @@ impl Runner {
         let plugin_manager = self.plugin_manager.clone();
         #[cfg(feature = "skills")]
         let skill_injector = self.skill_injector.clone();
-        let mut run_config = self.run_config.clone();
+        // Fix the turn's deadline now so the root agent, its tools, and every
+        // delegate share one budget.
+        if let Some(deadline) = run_config.deadline {
+            run_config.deadline_at = Some(std::time::Instant::now() + deadline);
+        }
+        // Only the root agent's global instruction applies, to every agent in
+        // the tree; an empty one keeps sub-agents from using their own.
+        run_config.global_instruction = Some(root_agent.global_instruction().unwrap_or_default());
         let compaction_config = self.compaction_config.clone();
         let context_cache_config = self.context_cache_config.clone();
         let cache_capable = self.cache_capable.clone();
@@ -303,8 +414,44 @@ impl Runner {
                 session_id: session_id_str,
             };
 
+            // One blackboard per run, shared by every context built below and
+            // cleared on exit so values never leak into the next run.
+            struct BlackboardCleanup(Arc<adk_core::Blackboard>);
+            impl Drop for BlackboardCleanup {
+                fn drop(&mut self) {
+                    self.0.clear();
+                }
+            }
+            let blackboard = Arc::new(adk_core::Blackboard::new());
+            let _blackboard_cleanup = BlackboardCleanup(blackboard.clone());
+            // One conversation buffer per run, so agents that opt into shared
+            // history see turns taken by every other agent.
+            let conversation_buffer = Arc::new(adk_core::ConversationBuffer::new());
+
             // Use the effective token (combines global + per-session)
             let cancellation_token = effective_token;
+
+            // Contexts get a child token that also fires at the turn's
+            // deadline, so agents and tools polling `is_cancelled` stop with
+            // the turn. The runner itself keeps racing the parent token, which
+            // ends the run silently, and handles the deadline separately.
+            let turn_deadline = run_config.deadline_at.map(tokio::time::Instant::from_std);
+            let (context_token, _deadline_guard) = match (cancellation_token.as_ref(), turn_deadline) {
+                (Some(token), Some(deadline)) => {
+                    let child = token.child_token();
+                    let timer = child.clone();
+                    tokio::spawn(async move {
+                        tokio::select! {
+                            _ = tokio::time::sleep_until(deadline) => timer.cancel(),
+                            _ = timer.cancelled() => {}
+                        }
+                    });
+                    (Some(child.clone()), Some(child.drop_guard()))
+                }
+                _ => (cancellation_token.clone(), None),
+            };
+            // Set when the turn deadline stops an agent: (agent, invocation id)
+            let mut timed_out_turn: Option<(String, String)> = None;
             // Get or create session
             let session = match session_service
                 .get(adk_session::GetRequest {
@@ -323,6 +470,10 @@ impl Runner {
                 }
             };
 
+            for event in session.events().all() {
+                conversation_buffer.record(&event);
+            }
+
             // Find which agent should handle this request
             let agent_to_run = Self::find_agent_to_run(&root_agent, session.as_ref());
 
@@ -399,9 +550,11 @@ impl Runner {
             }
 
             // Expose cooperative cancellation to the agent/tools.
-            if let Some(token) = cancellation_token.as_ref() {
+            if let Some(token) = context_token.as_ref() {
                 invocation_ctx = invocation_ctx.with_cancellation_token(token.clone());
             }
+            invocation_ctx = invocation_ctx.with_blackboard(blackboard.clone());
+            invocation_ctx = invocation_ctx.with_conversation_buffer(conversation_buffer.clone());
 
             let mut ctx = Arc::new(invocation_ctx);
 
@@ -417,6 +570,7 @@ impl Runner {
                         early_event.llm_response.content = Some(content);
 
                         ctx.mutable_session().append_event(early_event.clone());
+                        conversation_buffer.record(&early_event);
                         if let Err(e) = session_service.append_event(ctx.session_id(), early_event.clone()).await {
                             yield Err(e);
                             return;
@@ -477,9 +631,11 @@ impl Runner {
                         if let Some(rc) = request_context.clone() {
                             refreshed_ctx = refreshed_ctx.with_request_context(rc);
                         }
-                        if let Some(token) = cancellation_token.as_ref() {
+                        if let Some(token) = context_token.as_ref() {
                             refreshed_ctx = refreshed_ctx.with_cancellation_token(token.clone());
                         }
+                        refreshed_ctx = refreshed_ctx.with_blackboard(blackboard.clone());
+                        refreshed_ctx = refreshed_ctx.with_conversation_buffer(conversation_buffer.clone());
                         ctx = Arc::new(refreshed_ctx);
                     }
                     Ok(None) => {}
@@ -501,6 +657,7 @@ impl Runner {
             // Also add to mutable session for immediate visibility
             // Note: adk_session::Event is a re-export of adk_core::Event, so we can use it directly
             ctx.mutable_session().append_event(user_event.clone());
+            conversation_buffer.record(&user_event);
 
             if let Err(e) = session_service.append_event(ctx.session_id(), user_event).await {
                 #[cfg(feature = "plugins")]
@@ -601,9 +758,11 @@ impl Runner {
                     if let Some(rc) = request_context.clone() {
                         refreshed_ctx = refreshed_ctx.with_request_context(rc);
                     }
-                    if let Some(token) = cancellation_token.as_ref() {
+                    if let Some(token) = context_token.as_ref() {
                         refreshed_ctx = refreshed_ctx.with_cancellation_token(token.clone());
                     }
+                    refreshed_ctx = refreshed_ctx.with_blackboard(blackboard.clone());
+                    refreshed_ctx = refreshed_ctx.with_conversation_buffer(conversation_buffer.clone());
                     ctx = Arc::new(refreshed_ctx);
                 }
             }
@@ -663,6 +822,7 @@ impl Runner {
                 "gen_ai.conversation.id" = ctx.session_id(),
                 "adk.app_name" = ctx.app_name(),
                 "adk.user_id" = ctx.user_id(),
+                "adk.request_id" = run_config.request_id.as_deref().unwrap_or_default(),
                 "agent.name" = %agent_to_run.name(),
                 "adk.skills.selected_name" = %selected_skill_name,
                 "adk.skills.selected_id" = %selected_skill_id
@@ -717,15 +877,18 @@ impl Runner {
             };
 
             // Stream events and check for transfers
-            use futures::StreamExt;
             let mut transfer_target: Option<String> = None;
+            let mut transfer_source = String::new();
+            let mut transfer_handoff: Option<adk_core::Handoff> = None;
+
+            let mut timed_out = false;
 
             while let Some(result) = {
-                // Race the next event against cancellation so an in-flight
-                // await (LLM streaming, tool I/O) is interrupted promptly
-                // rather than only at poll boundaries. Dropping the stream on
-                // cancellation releases the underlying provider connection.
-                match cancellation_token.as_ref() {
+                // Race the next event against cancellation and the turn's
+                // deadline so an in-flight await (LLM streaming, tool I/O) is
+                // interrupted promptly rather than only at poll boundaries.
+                // Dropping the stream releases the provider connection.
+                let next = match cancellation_token.as_ref() {
                     Some(token) => {
                         tokio::select! {
                             biased;
@@ -735,18 +898,22 @@ impl Runner {
                                 if let Some(manager) = plugin_manager.as_ref() {
                                     manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
                                 }
+                                yield Err(cancelled_error());
                                 return;
                             }
-                            result = agent_stream.next() => result,
+                            next = next_before_deadline(&mut agent_stream, turn_deadline) => next,
                         }
                     }
-                    None => agent_stream.next().await,
-                }
+                    None => next_before_deadline(&mut agent_stream, turn_deadline).await,
+                };
+                next.unwrap_or_else(|| {
+                    timed_out = true;
+                    None
+                })
             } {
                 match result {
-                    Ok(event) => {
-                        #[cfg(feature = "plugins")]
-                        let mut event = event;
+                    Ok(mut event) => {
+                        stamp_event(&mut event, ctx.as_ref());
 
                         #[cfg(feature = "plugins")]
                         if let Some(manager) = plugin_manager.as_ref() {
@@ -769,9 +936,19 @@ impl Runner {
                             }
                         }
 
+                        // Lifecycle events and sub-agent output forwarded by a
+                        // tool only report progress; they never touch session
+                        // state or history.
+                        if event.is_lifecycle() || event.sub_agent_call_id().is_some() {
+                            yield Ok(event);
+                            continue;
+                        }
+
                         // Check for transfer action
                         if let Some(target) = &event.actions.transfer_to_agent {
                             transfer_target = Some(target.clone());
+                            transfer_source = event.author.clone();
+                            transfer_handoff = event.actions.handoff.clone();
                         }
 
                         // CRITICAL: Apply state_delta to the mutable session immediately.
@@ -785,6 +962,7 @@ impl Runner {
 
                         // Also add the event to the mutable session's event list
                         ctx.mutable_session().append_event(event.clone());
+                        conversation_buffer.record(&event);
 
                         // Append event to session service (persistent storage)
                         // Skip partial streaming chunks — only persist the final
@@ -814,6 +992,13 @@ impl Runner {
                 }
             }
 
+            if timed_out || deadline_passed(turn_deadline) {
+                drop(agent_stream);
+                adk_core::InvocationContext::end_invocation(ctx.as_ref());
+                timed_out_turn = Some((agent_to_run.name().to_string(), ctx.invocation_id().to_string()));
+                transfer_target = None;
+            }
+
             // ===== TRANSFER LOOP =====
             // Support multi-hop transfers with a max-depth guard.
             // When an agent emits transfer_to_agent, the runner resolves the
@@ -833,9 +1018,32 @@ impl Runner {
                         target = %target_name,
                         "max transfer depth exceeded, stopping transfer chain"
                     );
+                    // Record the stopped transfer so the session shows why the
+                    // run ended with the requesting agent.
+                    let limit = adk_core::TransferLimit { target: target_name, limit: max_depth };
+                    let event = adk_core::Event::transfer_stopped(
+                        ctx.invocation_id(),
+                        &transfer_source,
+                        limit,
+                    );
+                    ctx.mutable_session().append_event(event.clone());
+                    conversation_buffer.record(&event);
+                    if let Err(e) = session_service.append_event(ctx.session_id(), event.clone()).await {
+                        #[cfg(feature = "plugins")]
+                        if let Some(manager) = plugin_manager.as_ref() {
+                            manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
+                        }
+                        yield Err(e);
+                        return;
+                    }
+                    yield Ok(event);
                     break;
                 }
 
+                // The agent that requested this hop; control returns to it if
+                // the target times out.
+                let delegating_agent = transfer_source.clone();
+
                 let target_agent = match Self::find_agent(&root_agent, &target_name) {
                     Some(a) => a,
                     None => {
@@ -858,6 +1066,7 @@ impl Runner {
                 targets.extend(peer_names);
                 transfer_run_config.transfer_targets = targets;
                 transfer_run_config.parent_agent = parent_name;
+                transfer_run_config.handoff = transfer_handoff.take();
 
                 // For transfers, we reuse the same mutable session to preserve state
                 let transfer_invocation_id = format!("inv-{}", uuid::Uuid::new_v4());
@@ -894,12 +1103,25 @@ impl Runner {
                 if let Some(rc) = request_context.clone() {
                     transfer_ctx = transfer_ctx.with_request_context(rc);
                 }
-                if let Some(token) = cancellation_token.as_ref() {
+                if let Some(token) = context_token.as_ref() {
                     transfer_ctx = transfer_ctx.with_cancellation_token(token.clone());
                 }
+                transfer_ctx = transfer_ctx.with_blackboard(blackboard.clone());
+                transfer_ctx = transfer_ctx.with_conversation_buffer(conversation_buffer.clone());
 
                 let transfer_ctx = Arc::new(transfer_ctx);
 
+                if run_config.lifecycle_events {
+                    yield Ok(adk_core::Event::lifecycle(
+                        &transfer_invocation_id,
+                        &transfer_source,
+                        adk_core::LifecycleEvent::AgentDelegated {
+                            from: transfer_source.clone(),
+                            to: target_name.clone(),
+                        },
+                    ));
+                }
+
                 // Run the transferred agent
                 let mut transfer_stream = match target_agent.run(transfer_ctx.clone()).await {
                     Ok(s) => s,
@@ -913,11 +1135,21 @@ impl Runner {
                     }
                 };
 
+                // The delegate gets its own timeout, but never past the turn's.
+                let deadline = match (
+                    run_config.transfer_timeout.map(|timeout| tokio::time::Instant::now() + timeout),
+                    turn_deadline,
+                ) {
+                    (Some(transfer), Some(turn)) => Some(transfer.min(turn)),
+                    (transfer, turn) => transfer.or(turn),
+                };
+                let mut timed_out = false;
+
                 // Stream events from the transferred agent, capturing any further transfer
                 while let Some(result) = {
                     // Race the next event against cancellation for prompt
                     // mid-await interruption of the transferred agent.
-                    match cancellation_token.as_ref() {
+                    let next = match cancellation_token.as_ref() {
                         Some(token) => {
                             tokio::select! {
                                 biased;
@@ -927,18 +1159,22 @@ impl Runner {
                                     if let Some(manager) = plugin_manager.as_ref() {
                                         manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
                                     }
+                                    yield Err(cancelled_error());
                                     return;
                                 }
-                                result = transfer_stream.next() => result,
+                                next = next_before_deadline(&mut transfer_stream, deadline) => next,
                             }
                         }
-                        None => transfer_stream.next().await,
-                    }
+                        None => next_before_deadline(&mut transfer_stream, deadline).await,
+                    };
+                    next.unwrap_or_else(|| {
+                        timed_out = true;
+                        None
+                    })
                 } {
                     match result {
-                        Ok(event) => {
-                            #[cfg(feature = "plugins")]
-                            let mut event = event;
+                        Ok(mut event) => {
+                            stamp_event(&mut event, transfer_ctx.as_ref());
                             #[cfg(feature = "plugins")]
                             if let Some(manager) = plugin_manager.as_ref() {
                                 match manager
@@ -960,9 +1196,16 @@ impl Runner {
                                 }
                             }
 
+                            if event.is_lifecycle() || event.sub_agent_call_id().is_some() {
+                                yield Ok(event);
+                                continue;
+                            }
+
                             // Capture further transfer requests
                             if let Some(target) = &event.actions.transfer_to_agent {
                                 current_transfer_target = Some(target.clone());
+                                transfer_source = event.author.clone();
+                                transfer_handoff = event.actions.handoff.clone();
                             }
 
                             // Apply state delta for transferred agent too
@@ -972,6 +1215,7 @@ impl Runner {
 
                             // Add to mutable session
                             transfer_ctx.mutable_session().append_event(event.clone());
+                            conversation_buffer.record(&event);
 
                             if !event.llm_response.partial
                                 && let Err(e) = session_service.append_event(ctx.session_id(), event.clone()).await {
@@ -994,6 +1238,84 @@ impl Runner {
                         }
                     }
                 }
+
+                if deadline_passed(turn_deadline) {
+                    drop(transfer_stream);
+                    adk_core::InvocationContext::end_invocation(transfer_ctx.as_ref());
+                    timed_out_turn = Some((target_name, transfer_invocation_id));
+                    break;
+                }
+
+                if timed_out {
+                    // Dropping the stream cancels the delegate, including any
+                    // model request it is still waiting on.
+                    drop(transfer_stream);
+                    adk_core::InvocationContext::end_invocation(transfer_ctx.as_ref());
+                    let timeout = adk_core::DelegationTimeout::new(
+                        target_name.clone(),
+                        run_config.transfer_timeout.unwrap_or_default(),
+                    );
+                    tracing::warn!(
+                        target = %target_name,
+                        timeout_ms = timeout.timeout_ms,
+                        "transferred agent timed out, returning control to {delegating_agent}"
+                    );
+                    if delegating_agent.is_empty() {
+                        break;
+                    }
+
+                    let event = adk_core::Event::delegation_timed_out(
+                        &transfer_invocation_id,
+                        &delegating_agent,
+                        timeout,
+                    );
+                    transfer_ctx.mutable_session().append_event(event.clone());
+                    conversation_buffer.record(&event);
+                    if let Err(e) = session_service.append_event(ctx.session_id(), event.clone()).await {
+                        #[cfg(feature = "plugins")]
+                        if let Some(manager) = plugin_manager.as_ref() {
+                            manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
+                        }
+                        yield Err(e);
+                        return;
+                    }
+                    yield Ok(event);
+
+                    // Hand control back so the delegating agent can answer
+                    // without the delegate.
+                    current_transfer_target = Some(delegating_agent);
+                    transfer_source = target_name;
+                }
+            }
+
+            // ===== TURN DEADLINE =====
+            // The events produced so far stay in the session as a partial
+            // result; a timeout event closes the turn.
+            if let Some((agent, invocation_id)) = timed_out_turn {
+                let timeout = adk_core::TurnTimeout::new(agent.clone(), run_config.deadline.unwrap_or_default());
+                tracing::warn!(
+                    agent = %agent,
+                    deadline_ms = timeout.deadline_ms,
+                    "turn deadline passed, stopping the run"
+                );
+                let event = adk_core::Event::turn_timed_out(&invocation_id, &agent, timeout);
+                ctx.mutable_session().append_event(event.clone());
+                conversation_buffer.record(&event);
+                if let Err(e) = session_service.append_event(ctx.session_id(), event.clone()).await {
+                    #[cfg(feature = "plugins")]
+                    if let Some(manager) = plugin_manager.as_ref() {
+                        manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
+                    }
+                    yield Err(e);
+                    return;
+                }
+                yield Ok(event);
+
+                #[cfg(feature = "plugins")]
+                if let Some(manager) = plugin_manager.as_ref() {
+                    manager.run_after_run(ctx.clone() as Arc<dyn adk_core::InvocationContext>).await;
+                }
+                return;
             }
 
             // ===== CONTEXT COMPACTION =====
@@ -1070,7 +1392,7 @@ impl Runner {
             }
         };
 
-        Ok(Box::pin(s))
+        Ok((Box::pin(s), session_token))
     }
 
     /// Convenience method that accepts string arguments.
@@ -1206,6 +1528,20 @@ impl Runner {
         None
     }
 
+    /// Warn about agents below the root that set a global instruction, which
+    /// the runner ignores in favor of the root agent's.
+    fn warn_nested_global_instructions(agent: &Arc<dyn Agent>) {
+        if agent.global_instruction().is_some() {
+            tracing::warn!(
+                agent.name = %agent.name(),
+                "global instruction set on a sub-agent is ignored; only the root agent's applies"
+            );
+        }
+        for sub_agent in agent.sub_agents() {
+            Self::warn_nested_global_instructions(sub_agent);
+        }
+    }
+
     /// Compute the parent name and peer names for a given agent in the tree.
     /// Returns `(parent_name, peer_names)`.
     ///
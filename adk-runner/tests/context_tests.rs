@@ -821,3 +821,112 @@ fn conversation_history_for_agent_double_transfer_sees_own_prior_events() {
     assert_eq!(filtered[2].role, "function"); // own tool response
     assert_eq!(filtered[3].role, "model"); // own transfer-back text
 }
+
+#[derive(Default)]
+struct VersionedArtifacts {
+    versions: std::sync::Mutex<HashMap<String, Vec<Part>>>,
+}
+
+#[async_trait]
+impl adk_core::Artifacts for VersionedArtifacts {
+    async fn save(&self, name: &str, data: &Part) -> adk_core::Result<i64> {
+        let mut versions = self.versions.lock().unwrap();
+        let entry = versions.entry(name.to_string()).or_default();
+        entry.push(data.clone());
+        Ok(entry.len() as i64)
+    }
+
+    async fn load(&self, name: &str) -> adk_core::Result<Part> {
+        self.versions
+            .lock()
+            .unwrap()
+            .get(name)
+            .and_then(|v| v.last().cloned())
+            .ok_or_else(|| adk_core::AdkError::artifact(format!("artifact not found: {name}")))
+    }
+
+    async fn list(&self) -> adk_core::Result<Vec<String>> {
+        Ok(self.versions.lock().unwrap().keys().cloned().collect())
+    }
+}
+
+struct EchoMemory;
+
+#[async_trait]
+impl adk_core::Memory for EchoMemory {
+    async fn search(&self, query: &str) -> adk_core::Result<Vec<adk_core::MemoryEntry>> {
+        Ok(vec![adk_core::MemoryEntry {
+            content: Content::new("user").with_text(query),
+            author: "user".to_string(),
+        }])
+    }
+}
+
+fn callback_test_context() -> InvocationContext {
+    InvocationContext::new(
+        "inv-cb".to_string(),
+        Arc::new(MockAgent { name: "test_agent".to_string() }),
+        "user-456".to_string(),
+        "test-app".to_string(),
+        "session-789".to_string(),
+        Content::new("user"),
+        Arc::new(MockSessionWithState::new()),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_callback_context_saves_artifacts_through_shared_store() {
+    let artifacts = Arc::new(VersionedArtifacts::default());
+    let ctx: Arc<dyn CallbackContext> =
+        Arc::new(callback_test_context().with_artifacts(artifacts.clone()));
+
+    // A tool already saved the first version through the same store.
+    let first = adk_core::Artifacts::save(
+        artifacts.as_ref(),
+        "reply.txt",
+        &Part::Text { text: "v1".to_string() },
+    )
+    .await
+    .unwrap();
+    assert_eq!(first, 1);
+
+    let second =
+        ctx.save_artifact("reply.txt", &Part::Text { text: "v2".to_string() }).await.unwrap();
+    assert_eq!(second, 2);
+
+    let latest = ctx.load_artifact("reply.txt").await.unwrap();
+    assert_eq!(latest.text(), Some("v2"));
+}
+
+#[tokio::test]
+async fn test_callback_context_searches_memory() {
+    let ctx: Arc<dyn CallbackContext> =
+        Arc::new(callback_test_context().with_memory(Arc::new(EchoMemory)));
+
+    assert!(ctx.memory().is_some());
+    let entries = ctx.search_memory("favourite colour").await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].author, "user");
+}
+
+#[tokio::test]
+async fn test_callback_context_without_services_returns_unsupported() {
+    let ctx: Arc<dyn CallbackContext> = Arc::new(callback_test_context());
+
+    assert!(ctx.artifacts().is_none());
+    assert!(ctx.memory().is_none());
+
+    let err =
+        ctx.save_artifact("reply.txt", &Part::Text { text: "v1".to_string() }).await.unwrap_err();
+    assert!(err.is_artifact());
+    assert_eq!(err.category, adk_core::ErrorCategory::Unsupported);
+    assert_eq!(err.code, "artifact.not_configured");
+
+    let err = ctx.load_artifact("reply.txt").await.unwrap_err();
+    assert_eq!(err.code, "artifact.not_configured");
+
+    let err = ctx.search_memory("anything").await.unwrap_err();
+    assert!(err.is_memory());
+    assert_eq!(err.code, "memory.not_configured");
+}
@@ -712,6 +712,41 @@ async fn test_runner_interrupt_stops_agent_stream() {
     }
 }
 
+#[tokio::test]
+async fn test_runner_interrupt_ends_stream_with_cancelled_error() {
+    let agent = Arc::new(CancellableLoopAgent { max_ticks: 1000 });
+    let runner = Runner::builder()
+        .app_name("test_app")
+        .agent(agent as Arc<dyn Agent>)
+        .session_service(Arc::new(MockSessionService) as Arc<dyn SessionService>)
+        .build()
+        .unwrap();
+
+    let session_id = "interrupted-session";
+    let mut stream = runner
+        .run(
+            UserId::new("user123").unwrap(),
+            SessionId::new(session_id).unwrap(),
+            Content::new("user").with_text("go"),
+        )
+        .await
+        .unwrap();
+    stream.next().await.unwrap().unwrap();
+    assert!(runner.interrupt(session_id));
+
+    let mut items = Vec::new();
+    while let Some(item) =
+        tokio::time::timeout(std::time::Duration::from_secs(2), stream.next()).await.unwrap()
+    {
+        items.push(item);
+    }
+    let (last, rest) = items.split_last().expect("the stream should yield a terminal item");
+    assert!(rest.iter().all(Result::is_ok));
+    let error = last.as_ref().unwrap_err();
+    assert!(error.is_cancelled(), "{error}");
+    assert!(!runner.interrupt(session_id), "the interrupted run should be unregistered");
+}
+
 #[tokio::test]
 async fn test_runner_runs_to_completion_without_interrupt() {
     // Control case: without interrupt, the same agent runs to completion.
@@ -739,3 +774,56 @@ async fn test_runner_runs_to_completion_without_interrupt() {
     }
     assert_eq!(count, 3, "agent should emit exactly max_ticks events when not interrupted");
 }
+
+// Agent whose events leave the invocation id blank.
+struct UnstampedAgent;
+
+#[async_trait]
+impl Agent for UnstampedAgent {
+    fn name(&self) -> &str {
+        "unstamped"
+    }
+
+    fn description(&self) -> &str {
+        "Emits an event without an invocation id"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    async fn run(&self, _ctx: Arc<dyn InvocationContext>) -> Result<EventStream> {
+        let mut event = Event::new("");
+        event.author = "unstamped".to_string();
+        event.llm_response.content = Some(Content::new("model").with_text("hi"));
+        Ok(Box::pin(futures::stream::once(async { Ok(event) })))
+    }
+}
+
+#[tokio::test]
+async fn test_runner_stamps_invocation_id_on_events() {
+    let runner = Runner::builder()
+        .app_name("test_app")
+        .agent(Arc::new(UnstampedAgent) as Arc<dyn Agent>)
+        .session_service(Arc::new(MockSessionService) as Arc<dyn SessionService>)
+        .build()
+        .unwrap();
+
+    let before = chrono::Utc::now();
+    let events: Vec<Event> = runner
+        .run(
+            UserId::new("user123").unwrap(),
+            SessionId::new("stamp-session").unwrap(),
+            Content::new("user").with_text("go"),
+        )
+        .await
+        .unwrap()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 1);
+    assert!(events[0].invocation_id.starts_with("inv-"), "{}", events[0].invocation_id);
+    assert!(events[0].branch.is_empty());
+    assert!(events[0].timestamp >= before);
+}
@@ -7,13 +7,26 @@ pub fn build_agent_skills(agent: &dyn Agent) -> Vec<AgentSkill> {
     skills
 }
 
+const TEXT_MODE: &str = "text/plain";
+const JSON_MODE: &str = "application/json";
+
+/// Describes the agent itself. An agent that declares an input or output
+/// schema exchanges JSON on that side, and the skill carries the schema.
 fn build_primary_skills(agent: &dyn Agent) -> Vec<AgentSkill> {
-    vec![AgentSkill::new(
+    let info = agent.info();
+    let mut skill = AgentSkill::new(
         agent.name().to_string(),
         agent.name().to_string(),
         agent.description().to_string(),
         vec!["agent".to_string()],
-    )]
+    );
+    if let Some(schema) = info.input_schema {
+        skill = skill.with_input_modes(vec![JSON_MODE.to_string()]).with_input_schema(schema);
+    }
+    if let Some(schema) = info.output_schema {
+        skill = skill.with_output_modes(vec![JSON_MODE.to_string()]).with_output_schema(schema);
+    }
+    vec![skill]
 }
 
 fn build_sub_agent_skills(agent: &dyn Agent) -> Vec<AgentSkill> {
@@ -46,17 +59,11 @@ fn build_sub_agent_skills(agent: &dyn Agent) -> Vec<AgentSkill> {
     // Recursively add sub-agent skills
     for sub in sub_agents {
         let sub_skills = build_primary_skills(sub.as_ref());
-        for skill in sub_skills {
-            skills.push(AgentSkill::new(
-                format!("{}_{}", sub.name(), skill.id),
-                format!("{}: {}", sub.name(), skill.name),
-                skill.description,
-                {
-                    let mut tags = vec![format!("sub_agent:{}", sub.name())];
-                    tags.extend(skill.tags);
-                    tags
-                },
-            ));
+        for mut skill in sub_skills {
+            skill.id = format!("{}_{}", sub.name(), skill.id);
+            skill.name = format!("{}: {}", sub.name(), skill.name);
+            skill.tags.insert(0, format!("sub_agent:{}", sub.name()));
+            skills.push(skill);
         }
     }
 
@@ -75,6 +82,8 @@ pub fn build_agent_card(agent: &dyn Agent, base_url: &str) -> AgentCard {
             state_transition_history: true,
             extensions: None,
         })
+        .default_input_modes(vec![TEXT_MODE.to_string()])
+        .default_output_modes(vec![TEXT_MODE.to_string()])
         .skills(build_agent_skills(agent))
         .build()
         .expect("build_agent_card: agent name, description, and url must be non-empty")
@@ -2,6 +2,7 @@ use crate::a2a::{
     AgentCard, JsonRpcRequest, JsonRpcResponse, Message, MessageSendParams,
     TaskArtifactUpdateEvent, TaskStatusUpdateEvent, UpdateEvent,
 };
+use crate::correlation::Correlation;
 use adk_core::Result;
 use futures::stream::Stream;
 use serde_json::Value;
@@ -11,12 +12,23 @@ use std::pin::Pin;
 pub struct A2aClient {
     http_client: reqwest::Client,
     agent_card: AgentCard,
+    correlation: Correlation,
 }
 
 impl A2aClient {
     /// Create a new A2A client from an agent card
     pub fn new(agent_card: AgentCard) -> Self {
-        Self { http_client: reqwest::Client::new(), agent_card }
+        Self {
+            http_client: reqwest::Client::new(),
+            agent_card,
+            correlation: Correlation::default(),
+        }
+    }
+
+    /// Forward the given request id and trace context on every request
+    pub fn with_correlation(mut self, correlation: Correlation) -> Self {
+        self.correlation = correlation;
+        self
     }
 
     /// Resolve an agent card from a URL (fetch from /.well-known/agent.json)
@@ -70,6 +82,7 @@ impl A2aClient {
         let response = self
             .http_client
             .post(&self.agent_card.url)
+            .headers(self.correlation.to_headers())
             .json(&request)
             .send()
             .await
@@ -110,6 +123,7 @@ impl A2aClient {
         let response = self
             .http_client
             .post(&stream_url)
+            .headers(self.correlation.to_headers())
             .json(&request)
             .send()
             .await
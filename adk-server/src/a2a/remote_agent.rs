@@ -1,9 +1,11 @@
 use crate::a2a::{
     A2aClient, Part as A2aPart, Role, TaskArtifactUpdateEvent, TaskStatusUpdateEvent, UpdateEvent,
 };
+use crate::correlation::Correlation;
 use adk_core::{Agent, Content, Event, EventStream, InvocationContext, Part, Result};
 use async_trait::async_trait;
 use std::sync::Arc;
+use tracing::Instrument;
 
 /// Configuration for a remote A2A agent
 #[derive(Clone)]
@@ -58,25 +60,46 @@ impl Agent for RemoteA2aAgent {
         // Get user content from context
         let user_content = get_user_content_from_context(ctx.as_ref());
 
+        // Forward the caller's request id so both sides of the hop share it
+        let correlation = Correlation::from_context(ctx.as_ref());
+        let span = tracing::info_span!(
+            "a2a.remote_call",
+            agent.name = %agent_name,
+            a2a.url = %url,
+            request.id = correlation.request_id.as_deref().unwrap_or_default()
+        );
+
         let stream = async_stream::stream! {
             // Create A2A client
-            let client = match A2aClient::from_url(&url).await {
-                Ok(c) => c,
+            let client = match A2aClient::from_url(&url).instrument(span.clone()).await {
+                Ok(c) => c.with_correlation(correlation),
                 Err(e) => {
                     yield Ok(create_error_event(&invocation_id, &agent_name, &e.to_string()));
                     return;
                 }
             };
 
+            // Check the request against what the card advertises before sending
+            let card = client.agent_card();
+            if config_streaming == Some(true) && !card.capabilities.streaming {
+                let msg = format!("agent '{}' does not support streaming", card.name);
+                yield Ok(create_error_event(&invocation_id, &agent_name, &msg));
+                return;
+            }
+            if let Err(msg) = card.validate_request(user_content.as_deref().unwrap_or_default()) {
+                yield Ok(create_error_event(&invocation_id, &agent_name, &msg));
+                return;
+            }
+
             // Determine if we should use streaming
-            let use_streaming = config_streaming.unwrap_or(client.agent_card().capabilities.streaming);
+            let use_streaming = config_streaming.unwrap_or(card.capabilities.streaming);
 
             // Build message from user content
             let message = build_a2a_message(user_content);
 
             if use_streaming {
                 // Send streaming message
-                match client.send_streaming_message(message).await {
+                match client.send_streaming_message(message).instrument(span.clone()).await {
                     Ok(mut event_stream) => {
                         use futures::StreamExt;
                         while let Some(result) = event_stream.next().await {
@@ -99,7 +122,7 @@ impl Agent for RemoteA2aAgent {
                 }
             } else {
                 // Send non-streaming message
-                match client.send_message(message).await {
+                match client.send_message(message).instrument(span).await {
                     Ok(rpc_response) => {
                         if let Some(result) = rpc_response.result {
                             match serde_json::from_value::<crate::a2a::Task>(result) {
@@ -184,11 +184,53 @@ pub struct AgentSkill {
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub examples: Option<Vec<String>>,
+    /// Media types the skill accepts, overriding the card's `defaultInputModes`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "inputModes")]
+    pub input_modes: Option<Vec<String>>,
+    /// Media types the skill produces, overriding the card's `defaultOutputModes`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "outputModes")]
+    pub output_modes: Option<Vec<String>>,
+    /// JSON Schema a structured (`application/json`) request must match.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "inputSchema")]
+    pub input_schema: Option<Value>,
+    /// JSON Schema of the skill's structured response.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "outputSchema")]
+    pub output_schema: Option<Value>,
 }
 
 impl AgentSkill {
     pub fn new(id: String, name: String, description: String, tags: Vec<String>) -> Self {
-        Self { id, name, description, tags, examples: None }
+        Self {
+            id,
+            name,
+            description,
+            tags,
+            examples: None,
+            input_modes: None,
+            output_modes: None,
+            input_schema: None,
+            output_schema: None,
+        }
+    }
+
+    pub fn with_input_modes(mut self, modes: Vec<String>) -> Self {
+        self.input_modes = Some(modes);
+        self
+    }
+
+    pub fn with_output_modes(mut self, modes: Vec<String>) -> Self {
+        self.output_modes = Some(modes);
+        self
+    }
+
+    pub fn with_input_schema(mut self, schema: Value) -> Self {
+        self.input_schema = Some(schema);
+        self
+    }
+
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.output_schema = Some(schema);
+        self
     }
 }
 
@@ -214,6 +256,12 @@ pub struct AgentCard {
     pub protocol_version: String,
     #[serde(default)]
     pub capabilities: AgentCapabilities,
+    /// Media types every skill accepts unless it lists its own `inputModes`.
+    #[serde(default, rename = "defaultInputModes", skip_serializing_if = "Vec::is_empty")]
+    pub default_input_modes: Vec<String>,
+    /// Media types every skill produces unless it lists its own `outputModes`.
+    #[serde(default, rename = "defaultOutputModes", skip_serializing_if = "Vec::is_empty")]
+    pub default_output_modes: Vec<String>,
     #[serde(default)]
     pub skills: Vec<AgentSkill>,
 }
@@ -222,6 +270,63 @@ impl AgentCard {
     pub fn builder() -> AgentCardBuilder {
         AgentCardBuilder::default()
     }
+
+    /// The skill describing the agent itself: the one whose id is the card's
+    /// name, or the first skill.
+    pub fn primary_skill(&self) -> Option<&AgentSkill> {
+        self.skills.iter().find(|skill| skill.id == self.name).or_else(|| self.skills.first())
+    }
+
+    /// Media types `skill` accepts, falling back to the card defaults.
+    pub fn input_modes<'a>(&'a self, skill: Option<&'a AgentSkill>) -> &'a [String] {
+        skill.and_then(|s| s.input_modes.as_deref()).unwrap_or(&self.default_input_modes)
+    }
+
+    /// Checks an outgoing text request against what the card advertises,
+    /// before it is sent.
+    ///
+    /// When the primary skill declares an `inputSchema`, the text must be a
+    /// JSON document matching it; otherwise the request is plain text. Either
+    /// way its media type must be one the skill accepts. A card that lists no
+    /// input modes accepts anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first mismatch.
+    pub fn validate_request(&self, text: &str) -> std::result::Result<(), String> {
+        let skill = self.primary_skill();
+        let schema = skill.and_then(|s| s.input_schema.as_ref());
+        let mode = if schema.is_some() { "application/json" } else { "text/plain" };
+
+        let accepted = self.input_modes(skill);
+        if !accepted.is_empty() && !accepted.iter().any(|m| m == mode) {
+            return Err(format!(
+                "agent '{}' does not accept {mode} input (accepts: {})",
+                self.name,
+                accepted.join(", ")
+            ));
+        }
+
+        if let Some(schema) = schema {
+            let value: Value = serde_json::from_str(text).map_err(|e| {
+                format!(
+                    "agent '{}' expects a JSON request matching its input schema: {e}",
+                    self.name
+                )
+            })?;
+            let validator = jsonschema::validator_for(schema).map_err(|e| {
+                format!("agent '{}' advertises an invalid input schema: {e}", self.name)
+            })?;
+            if let Some(error) = validator.iter_errors(&value).next() {
+                return Err(format!(
+                    "request does not match the input schema of agent '{}': {error}",
+                    self.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -231,6 +336,8 @@ pub struct AgentCardBuilder {
     url: Option<String>,
     version: Option<String>,
     capabilities: Option<AgentCapabilities>,
+    default_input_modes: Vec<String>,
+    default_output_modes: Vec<String>,
     skills: Vec<AgentSkill>,
 }
 
@@ -260,6 +367,16 @@ impl AgentCardBuilder {
         self
     }
 
+    pub fn default_input_modes(mut self, modes: Vec<String>) -> Self {
+        self.default_input_modes = modes;
+        self
+    }
+
+    pub fn default_output_modes(mut self, modes: Vec<String>) -> Self {
+        self.default_output_modes = modes;
+        self
+    }
+
     pub fn skills(mut self, skills: Vec<AgentSkill>) -> Self {
         self.skills = skills;
         self
@@ -293,6 +410,8 @@ impl AgentCardBuilder {
                 state_transition_history: false,
                 extensions: None,
             }),
+            default_input_modes: self.default_input_modes,
+            default_output_modes: self.default_output_modes,
             skills: self.skills,
         })
     }
@@ -351,6 +351,8 @@ impl RequestHandler {
                         }
                     }
                 }
+                // A cancelled run just stops; the canceller owns the task state
+                Err(e) if e.is_cancelled() => break,
                 Err(e) => {
                     return Err(A2aError::Internal { message: format!("agent error: {e}") });
                 }
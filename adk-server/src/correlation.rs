@@ -0,0 +1,112 @@
+//! Request correlation across REST and A2A hops.
+//!
+//! Every request handled by the server carries an `X-Request-Id`: the caller's
+//! id when it sends a valid one, otherwise a freshly generated UUID. The id (and
+//! any W3C `traceparent`) is recorded on the request span, stored in the run's
+//! [`RunConfig`] so agents can read it via
+//! [`InvocationContext::request_id`], and forwarded by [`RemoteA2aAgent`] on
+//! outbound calls so both sides of a delegation share one id.
+//!
+//! [`RemoteA2aAgent`]: crate::a2a::RemoteA2aAgent
+
+use adk_core::{InvocationContext, RunConfig};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+
+/// Header carrying the request correlation id.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// W3C trace context header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Correlation identifiers carried by a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Correlation {
+    /// The `X-Request-Id` value.
+    pub request_id: Option<String>,
+    /// The W3C `traceparent` value.
+    pub traceparent: Option<String>,
+}
+
+impl Correlation {
+    /// Read correlation headers from an incoming request, ignoring malformed values.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        Self {
+            request_id: header(REQUEST_ID_HEADER)
+                .filter(|v| is_valid_request_id(v))
+                .map(String::from),
+            traceparent: header(TRACEPARENT_HEADER)
+                .filter(|v| is_valid_traceparent(v))
+                .map(String::from),
+        }
+    }
+
+    /// Read the correlation of the run an invocation belongs to.
+    pub fn from_context(ctx: &dyn InvocationContext) -> Self {
+        let run_config = ctx.run_config();
+        Self {
+            request_id: run_config.request_id.clone(),
+            traceparent: run_config.traceparent.clone(),
+        }
+    }
+
+    /// Record these identifiers on a run configuration.
+    pub fn apply_to(&self, run_config: &mut RunConfig) {
+        run_config.request_id.clone_from(&self.request_id);
+        run_config.traceparent.clone_from(&self.traceparent);
+    }
+
+    /// Headers to send on an outbound request.
+    pub fn to_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in
+            [(REQUEST_ID_HEADER, &self.request_id), (TRACEPARENT_HEADER, &self.traceparent)]
+        {
+            if let Some(value) = value
+                && let Ok(value) = HeaderValue::from_str(value)
+            {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+        headers
+    }
+}
+
+/// Whether a caller-supplied request id is acceptable (a UUID of bounded length).
+pub(crate) fn is_valid_request_id(raw: &str) -> bool {
+    raw.len() <= 128 && uuid::Uuid::parse_str(raw).is_ok()
+}
+
+/// Whether a value is a well-formed version-00 W3C `traceparent`.
+fn is_valid_traceparent(raw: &str) -> bool {
+    let fields: Vec<&str> = raw.split('-').collect();
+    let hex = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_hexdigit());
+    matches!(fields.as_slice(), [version, trace_id, span_id, flags]
+        if *version == "00" && hex(trace_id, 32) && hex(span_id, 16) && hex(flags, 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_from_headers_drops_malformed_values() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("not-a-uuid"));
+        headers.insert(TRACEPARENT_HEADER, HeaderValue::from_static("00-bad"));
+
+        assert_eq!(Correlation::from_headers(&headers), Correlation::default());
+    }
+
+    #[test]
+    fn test_headers_round_trip() {
+        let correlation = Correlation {
+            request_id: Some("0b7c6f3e-4d6f-4c1b-9a53-6f1f1c1e2a10".to_string()),
+            traceparent: Some(TRACEPARENT.to_string()),
+        };
+
+        assert_eq!(Correlation::from_headers(&correlation.to_headers()), correlation);
+    }
+}
@@ -0,0 +1,297 @@
+//! # Human-in-the-Loop Graph Runs
+//!
+//! REST endpoints that run a [`CompiledGraph`] on a thread, stream its events
+//! to the client, and pause when a node interrupts for human input.
+//!
+//! This module is gated behind the `graph` feature flag.
+//!
+//! - `POST /graph/{thread}/run` — Run the graph, streaming events as SSE
+//! - `POST /graph/{thread}/resume` — Resume a paused run with the client's values
+//! - `GET /graph/{thread}` — Latest state and pending interrupt of a thread
+//!
+//! The graph must be compiled with a checkpointer. When a run pauses, the
+//! interrupt is stored in the thread's latest checkpoint, so a client that
+//! lost the stream can reconnect, read the interrupt with `GET
+//! /graph/{thread}`, and resume.
+//!
+//! ## Events
+//!
+//! Each SSE event carries one JSON object with a `type` field: the graph's
+//! [`StreamEvent`]s, followed by exactly one of
+//!
+//! - `{"type": "interrupted", "interrupt": {...}}` — the run paused; the
+//!   [`PendingInterrupt`] includes the data the node attached, typically the
+//!   shape of the value it expects on resume
+//! - `{"type": "done", ...}` — the run completed
+//! - `{"type": "error", "error": "..."}` — the run failed
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use adk_graph::prelude::*;
+//! use adk_server::graph::graph_router;
+//! use std::sync::Arc;
+//!
+//! let graph = StateGraph::with_channels(&["draft", "approved"])
+//!     // ... nodes and edges ...
+//!     .compile()?
+//!     .with_checkpointer(MemoryCheckpointer::new());
+//!
+//! let app = axum::Router::new().merge(graph_router(Arc::new(graph)));
+//! ```
+
+use adk_graph::{Checkpoint, CompiledGraph, ExecutionConfig, Interrupt, StreamEvent, StreamMode};
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{get, post},
+};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// ---------------------------------------------------------------------------
+// Data Types
+// ---------------------------------------------------------------------------
+
+/// Request body for `POST /graph/{thread}/run`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunGraphRequest {
+    /// Initial values for the graph's channels.
+    #[serde(default)]
+    pub input: HashMap<String, Value>,
+}
+
+/// Request body for `POST /graph/{thread}/resume`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResumeGraphRequest {
+    /// Values merged into the paused state before the run continues, for
+    /// example `{"approved": true}`.
+    #[serde(default)]
+    pub values: HashMap<String, Value>,
+}
+
+/// An interrupt a thread is waiting on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingInterrupt {
+    /// Thread the run belongs to.
+    pub thread_id: String,
+    /// Checkpoint the run will resume from.
+    pub checkpoint_id: String,
+    /// Super-step at which the run paused.
+    pub step: usize,
+    /// Node that interrupted, or that the interrupt was configured on.
+    pub node: Option<String>,
+    /// Message for the human.
+    pub message: String,
+    /// Data attached by the node, such as the expected resume value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl PendingInterrupt {
+    /// The interrupt recorded in `checkpoint`, if the run paused there.
+    pub fn from_checkpoint(checkpoint: &Checkpoint) -> Option<Self> {
+        let interrupt = checkpoint.pending_interrupt()?;
+        let (node, message, data) = match interrupt {
+            Interrupt::Before(ref node) | Interrupt::After(ref node) => {
+                (Some(node.clone()), interrupt.to_string(), None)
+            }
+            Interrupt::Dynamic { message, data } => {
+                (checkpoint.pending_nodes.first().cloned(), message, data)
+            }
+        };
+        Some(Self {
+            thread_id: checkpoint.thread_id.clone(),
+            checkpoint_id: checkpoint.checkpoint_id.clone(),
+            step: checkpoint.step,
+            node,
+            message,
+            data,
+        })
+    }
+}
+
+/// Response body for `GET /graph/{thread}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphThreadResponse {
+    /// Thread identifier.
+    pub thread_id: String,
+    /// State at the latest checkpoint.
+    pub state: HashMap<String, Value>,
+    /// Super-step of the latest checkpoint.
+    pub step: usize,
+    /// The interrupt the thread is waiting on, if any.
+    pub interrupt: Option<PendingInterrupt>,
+}
+
+// ---------------------------------------------------------------------------
+// Shared Application State for Axum Handlers
+// ---------------------------------------------------------------------------
+
+/// Shared state for graph run endpoints.
+#[derive(Clone)]
+pub struct GraphState {
+    pub graph: Arc<CompiledGraph>,
+    pub stream_mode: StreamMode,
+}
+
+impl GraphState {
+    /// Serve `graph`, streaming the full state after each step.
+    pub fn new(graph: Arc<CompiledGraph>) -> Self {
+        Self { graph, stream_mode: StreamMode::Values }
+    }
+
+    /// Stream events in `mode` instead of [`StreamMode::Values`].
+    #[must_use]
+    pub fn with_stream_mode(mut self, mode: StreamMode) -> Self {
+        self.stream_mode = mode;
+        self
+    }
+
+    /// Latest checkpoint of `thread_id`.
+    async fn latest_checkpoint(&self, thread_id: &str) -> Result<Option<Checkpoint>, Response> {
+        let Some(checkpointer) = self.graph.checkpointer() else {
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "graph was compiled without a checkpointer",
+            ));
+        };
+        checkpointer
+            .load(thread_id)
+            .await
+            .map_err(|e| error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()))
+    }
+}
+
+impl std::fmt::Debug for GraphState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphState").field("stream_mode", &self.stream_mode).finish_non_exhaustive()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// REST Endpoint Handlers
+// ---------------------------------------------------------------------------
+
+/// POST /graph/{thread}/run — Run the graph, streaming events until it
+/// completes or pauses.
+async fn run_graph(
+    State(state): State<GraphState>,
+    Path(thread_id): Path<String>,
+    Json(request): Json<RunGraphRequest>,
+) -> Response {
+    match state.latest_checkpoint(&thread_id).await {
+        Ok(Some(checkpoint)) if checkpoint.pending_interrupt().is_some() => error_response(
+            StatusCode::CONFLICT,
+            "thread is waiting on an interrupt; use /resume to continue it",
+        ),
+        Ok(_) => stream_run(state, thread_id, request.input),
+        Err(response) => response,
+    }
+}
+
+/// POST /graph/{thread}/resume — Continue a paused run with the client's values.
+async fn resume_graph(
+    State(state): State<GraphState>,
+    Path(thread_id): Path<String>,
+    Json(request): Json<ResumeGraphRequest>,
+) -> Response {
+    match state.latest_checkpoint(&thread_id).await {
+        Ok(Some(checkpoint)) if checkpoint.pending_interrupt().is_some() => {
+            stream_run(state, thread_id, request.values)
+        }
+        Ok(Some(_)) => {
+            error_response(StatusCode::CONFLICT, "thread is not waiting on an interrupt")
+        }
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "thread not found"),
+        Err(response) => response,
+    }
+}
+
+/// GET /graph/{thread} — Latest state and pending interrupt of a thread.
+async fn get_thread(State(state): State<GraphState>, Path(thread_id): Path<String>) -> Response {
+    match state.latest_checkpoint(&thread_id).await {
+        Ok(Some(checkpoint)) => {
+            let response = GraphThreadResponse {
+                interrupt: PendingInterrupt::from_checkpoint(&checkpoint),
+                thread_id: checkpoint.thread_id,
+                state: checkpoint.state,
+                step: checkpoint.step,
+            };
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "thread not found"),
+        Err(response) => response,
+    }
+}
+
+/// Stream a run of the graph on `thread_id` as SSE.
+///
+/// The graph resumes from the thread's latest checkpoint, if any, with
+/// `input` merged on top.
+fn stream_run(state: GraphState, thread_id: String, input: HashMap<String, Value>) -> Response {
+    let stream = async_stream::stream! {
+        let events = state.graph.stream(input, ExecutionConfig::new(&thread_id), state.stream_mode);
+        let mut events = std::pin::pin!(events);
+
+        while let Some(event) = events.next().await {
+            let payload = match event {
+                Ok(event @ StreamEvent::Interrupted { .. }) => {
+                    // The executor saved the interrupt before emitting the event.
+                    match state.latest_checkpoint(&thread_id).await {
+                        Ok(Some(checkpoint)) => match PendingInterrupt::from_checkpoint(&checkpoint) {
+                            Some(pending) => json!({ "type": "interrupted", "interrupt": pending }),
+                            None => event_payload(&event),
+                        },
+                        _ => event_payload(&event),
+                    }
+                }
+                Ok(event) => event_payload(&event),
+                Err(e) => json!({ "type": "error", "error": e.to_string() }),
+            };
+            yield Ok::<_, std::convert::Infallible>(Event::default().data(payload.to_string()));
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default()).into_response()
+}
+
+fn event_payload(event: &StreamEvent) -> Value {
+    serde_json::to_value(event)
+        .unwrap_or_else(|e| json!({ "type": "error", "error": e.to_string() }))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+/// Create the graph run router for `graph`.
+///
+/// Mounts the following routes:
+/// - `POST /graph/{thread}/run` — Run the graph, streaming events as SSE
+/// - `POST /graph/{thread}/resume` — Resume a paused run
+/// - `GET /graph/{thread}` — Latest state and pending interrupt of a thread
+pub fn graph_router(graph: Arc<CompiledGraph>) -> Router {
+    graph_router_with_state(GraphState::new(graph))
+}
+
+/// Create the graph run router with a pre-configured state.
+pub fn graph_router_with_state(state: GraphState) -> Router {
+    Router::new()
+        .route("/graph/{thread}", get(get_thread))
+        .route("/graph/{thread}/run", post(run_graph))
+        .route("/graph/{thread}/resume", post(resume_graph))
+        .with_state(state)
+}
@@ -62,6 +62,7 @@
 pub mod a2a;
 pub mod auth_bridge;
 pub mod config;
+pub mod correlation;
 pub mod rest;
 pub mod ui_protocol;
 pub mod ui_types;
@@ -92,6 +93,16 @@ pub use background::{
     cron_jobs_router, cron_jobs_router_with_state, start_cron_scheduler, validate_cron_expression,
 };
 
+#[cfg(feature = "graph")]
+pub mod graph;
+
+// Graph run/resume re-exports
+#[cfg(feature = "graph")]
+pub use graph::{
+    GraphState, GraphThreadResponse, PendingInterrupt, ResumeGraphRequest, RunGraphRequest,
+    graph_router, graph_router_with_state,
+};
+
 pub use a2a::{
     A2aClient, Executor, ExecutorConfig, RemoteA2aAgent, RemoteA2aAgentBuilder, RemoteA2aConfig,
     build_agent_card, build_agent_skills,
@@ -100,6 +111,7 @@ pub use a2a::{
 pub use a2a::{A2aServer, A2aServerApp, A2aServerBuilder};
 pub use auth_bridge::{RequestContext, RequestContextError, RequestContextExtractor};
 pub use config::{SecurityConfig, ServerConfig};
+pub use correlation::Correlation;
 pub use rest::{
     A2aController, RuntimeController, ServerBuilder, SessionController, ShutdownHandle, create_app,
     create_app_with_a2a, shutdown_signal,
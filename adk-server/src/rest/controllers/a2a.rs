@@ -4,10 +4,11 @@ use crate::a2a::{
     MessageSendParams, Task, TaskState, TaskStatus, TaskStatusUpdateEvent, TasksCancelParams,
     TasksGetParams, UpdateEvent, build_agent_card, jsonrpc,
 };
+use crate::correlation::Correlation;
 use adk_runner::{Runner, RunnerConfig};
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{
         IntoResponse, Json,
         sse::{Event, Sse},
@@ -18,6 +19,7 @@ use serde_json::Value;
 use std::{collections::HashMap, convert::Infallible, sync::Arc, time::Duration};
 use tokio::sync::{Mutex, Notify, RwLock, mpsc, oneshot};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 /// In-memory task storage
 #[derive(Default)]
@@ -84,11 +86,15 @@ fn build_runner_config(
     controller: &A2aController,
     root_agent: Arc<dyn adk_core::Agent>,
     cancellation_token: Option<CancellationToken>,
+    correlation: &Correlation,
 ) -> Arc<RunnerConfig> {
+    let mut run_config = adk_core::RunConfig::default();
+    correlation.apply_to(&mut run_config);
     let mut builder = Runner::builder()
         .app_name(root_agent.name())
         .agent(root_agent)
-        .session_service(controller.config.session_service.clone());
+        .session_service(controller.config.session_service.clone())
+        .run_config(run_config);
     if let Some(ref artifact_service) = controller.config.artifact_service {
         builder = builder.artifact_service(artifact_service.clone());
     }
@@ -169,6 +175,7 @@ async fn start_task(
     task_id: String,
     message: Message,
     stream_updates: bool,
+    correlation: &Correlation,
 ) -> (oneshot::Receiver<adk_core::Result<Task>>, Option<mpsc::Receiver<StreamTaskMessage>>) {
     let token = CancellationToken::new();
     let completion = Arc::new(Notify::new());
@@ -183,7 +190,12 @@ async fn start_task(
     let root_agent = controller.config.agent_loader.root_agent();
     let executor = Executor::new(ExecutorConfig {
         app_name: root_agent.name().to_string(),
-        runner_config: build_runner_config(controller, root_agent, Some(token.clone())),
+        runner_config: build_runner_config(
+            controller,
+            root_agent,
+            Some(token.clone()),
+            correlation,
+        ),
         cancellation_token: Some(token.clone()),
         #[cfg(feature = "a2a-interceptors")]
         interceptor_chain: controller.config.interceptor_chain.clone(),
@@ -195,51 +207,60 @@ async fn start_task(
     let context_id_for_task = context_id.clone();
     let stream_tx_for_task = stream_tx.clone();
 
-    let join_handle = tokio::spawn(async move {
-        let result = executor.execute(&context_id_for_task, &task_id_for_task, &message).await;
-
-        match result {
-            Ok(events) => {
-                if let Some(sender) = stream_tx_for_task {
-                    for event in &events {
-                        if sender
-                            .send(StreamTaskMessage::Update(Box::new(event.clone())))
-                            .await
-                            .is_err()
-                        {
-                            break;
+    let task_span = tracing::info_span!(
+        "a2a.task",
+        task.id = %task_id,
+        request.id = correlation.request_id.as_deref().unwrap_or_default()
+    );
+    let join_handle = tokio::spawn(
+        async move {
+            let result = executor.execute(&context_id_for_task, &task_id_for_task, &message).await;
+
+            match result {
+                Ok(events) => {
+                    if let Some(sender) = stream_tx_for_task {
+                        for event in &events {
+                            if sender
+                                .send(StreamTaskMessage::Update(Box::new(event.clone())))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
                         }
                     }
-                }
 
-                let task = build_task_from_events(&task_id_for_task, &context_id_for_task, &events);
-                controller_clone.task_store.store(task.clone()).await;
-                let _ = task_tx.send(Ok(task));
-            }
-            Err(error) => {
-                if let Some(sender) = stream_tx_for_task {
-                    let _ = sender
-                        .send(StreamTaskMessage::Error(sanitize_internal_error(
-                            &controller_clone.config,
-                            &error,
-                        )))
+                    let task =
+                        build_task_from_events(&task_id_for_task, &context_id_for_task, &events);
+                    controller_clone.task_store.store(task.clone()).await;
+                    let _ = task_tx.send(Ok(task));
+                }
+                Err(error) => {
+                    if let Some(sender) = stream_tx_for_task {
+                        let _ = sender
+                            .send(StreamTaskMessage::Error(sanitize_internal_error(
+                                &controller_clone.config,
+                                &error,
+                            )))
+                            .await;
+                    }
+                    controller_clone
+                        .task_store
+                        .store(build_failed_task(
+                            &task_id_for_task,
+                            &context_id_for_task,
+                            error.to_string(),
+                        ))
                         .await;
+                    let _ = task_tx.send(Err(error));
                 }
-                controller_clone
-                    .task_store
-                    .store(build_failed_task(
-                        &task_id_for_task,
-                        &context_id_for_task,
-                        error.to_string(),
-                    ))
-                    .await;
-                let _ = task_tx.send(Err(error));
             }
-        }
 
-        controller_clone.active_tasks.lock().await.remove(&task_id_for_task);
-        completion_clone.notify_waiters();
-    });
+            controller_clone.active_tasks.lock().await.remove(&task_id_for_task);
+            completion_clone.notify_waiters();
+        }
+        .instrument(task_span),
+    );
 
     controller.active_tasks.lock().await.insert(
         task_id,
@@ -257,6 +278,7 @@ pub async fn get_agent_card(State(controller): State<A2aController>) -> impl Int
 /// POST /a2a - JSON-RPC endpoint for A2A protocol
 pub async fn handle_jsonrpc(
     State(controller): State<A2aController>,
+    headers: HeaderMap,
     Json(request): Json<JsonRpcRequest>,
 ) -> impl IntoResponse {
     if request.jsonrpc != "2.0" {
@@ -268,7 +290,8 @@ pub async fn handle_jsonrpc(
 
     match request.method.as_str() {
         jsonrpc::methods::MESSAGE_SEND => {
-            handle_message_send(&controller, request.params, request.id).await
+            let correlation = Correlation::from_headers(&headers);
+            handle_message_send(&controller, request.params, request.id, &correlation).await
         }
         jsonrpc::methods::TASKS_GET => {
             handle_tasks_get(&controller, request.params, request.id).await
@@ -286,6 +309,7 @@ pub async fn handle_jsonrpc(
 /// POST /a2a/stream - SSE streaming endpoint for A2A protocol
 pub async fn handle_jsonrpc_stream(
     State(controller): State<A2aController>,
+    headers: HeaderMap,
     Json(request): Json<JsonRpcRequest>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<JsonRpcResponse>)>
 {
@@ -333,7 +357,8 @@ pub async fn handle_jsonrpc_stream(
     };
 
     let request_id = request.id.clone();
-    let stream = create_message_stream(controller, params, request_id);
+    let correlation = Correlation::from_headers(&headers);
+    let stream = create_message_stream(controller, params, request_id, correlation);
 
     Ok(Sse::new(stream).keep_alive(
         axum::response::sse::KeepAlive::new().interval(Duration::from_secs(15)).text("ping"),
@@ -344,6 +369,7 @@ fn create_message_stream(
     controller: A2aController,
     params: MessageSendParams,
     request_id: Option<Value>,
+    correlation: Correlation,
 ) -> impl Stream<Item = Result<Event, Infallible>> {
     async_stream::stream! {
         let context_id = params
@@ -363,6 +389,7 @@ fn create_message_stream(
             task_id.clone(),
             params.message.clone(),
             true,
+            &correlation,
         )
         .await;
 
@@ -414,6 +441,7 @@ async fn handle_message_send(
     controller: &A2aController,
     params: Option<Value>,
     id: Option<Value>,
+    correlation: &Correlation,
 ) -> Json<JsonRpcResponse> {
     let params: MessageSendParams = match params {
         Some(p) => match serde_json::from_value(p) {
@@ -438,8 +466,15 @@ async fn handle_message_send(
     let task_id =
         params.message.task_id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-    let (task_rx, _) =
-        start_task(controller, context_id.clone(), task_id.clone(), params.message, false).await;
+    let (task_rx, _) = start_task(
+        controller,
+        context_id.clone(),
+        task_id.clone(),
+        params.message,
+        false,
+        correlation,
+    )
+    .await;
 
     match task_rx.await {
         Ok(Ok(task)) => {
@@ -1184,10 +1184,13 @@ pub async fn run_sse(
             .map_err(adk_err_to_runtime)?;
 
         // Create runner
+        let mut run_config = adk_core::RunConfig::default();
+        crate::correlation::Correlation::from_headers(&headers).apply_to(&mut run_config);
         let mut runner_builder = adk_runner::Runner::builder()
             .app_name(app_name.clone())
             .agent(agent)
-            .session_service(controller.config.session_service.clone());
+            .session_service(controller.config.session_service.clone())
+            .run_config(run_config);
         if let Some(ref artifact_service) = controller.config.artifact_service {
             runner_builder = runner_builder.artifact_service(artifact_service.clone());
         }
@@ -1375,11 +1378,13 @@ pub async fn run_sse_compat(
     let streaming_mode =
         if req.streaming { adk_core::StreamingMode::SSE } else { adk_core::StreamingMode::None };
 
+    let mut run_config = adk_core::RunConfig::builder().streaming_mode(streaming_mode).build();
+    crate::correlation::Correlation::from_headers(&headers).apply_to(&mut run_config);
     let mut runner_builder = adk_runner::Runner::builder()
         .app_name(app_name)
         .agent(agent)
         .session_service(controller.config.session_service.clone())
-        .run_config(adk_core::RunConfig::builder().streaming_mode(streaming_mode).build());
+        .run_config(run_config);
     if let Some(ref artifact_service) = controller.config.artifact_service {
         runner_builder = runner_builder.artifact_service(artifact_service.clone());
     }
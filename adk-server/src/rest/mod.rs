@@ -9,6 +9,7 @@ pub use controllers::{
 use crate::{
     ServerConfig,
     auth_bridge::{RequestContext, RequestContextError, RequestContextExtractor},
+    correlation::{REQUEST_ID_HEADER, TRACEPARENT_HEADER, is_valid_request_id},
     web_ui,
 };
 use axum::{
@@ -31,13 +32,14 @@ use tower_http::{
     trace::TraceLayer,
 };
 
-const REQUEST_ID_HEADER: &str = "x-request-id";
-
 #[derive(Clone)]
 struct HealthController {
     session_service: Arc<dyn adk_session::SessionService>,
     artifact_service: Option<Arc<dyn adk_artifact::ArtifactService>>,
     memory_service: Option<Arc<dyn adk_core::Memory>>,
+    agent_loader: Arc<dyn adk_core::AgentLoader>,
+    /// The first successful warm-up, reused so readiness probes don't ping the model every time.
+    warm_report: Arc<tokio::sync::Mutex<Option<adk_core::WarmupReport>>>,
 }
 
 impl HealthController {
@@ -46,6 +48,8 @@ impl HealthController {
             session_service: config.session_service.clone(),
             artifact_service: config.artifact_service.clone(),
             memory_service: config.memory_service.clone(),
+            agent_loader: config.agent_loader.clone(),
+            warm_report: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 }
@@ -104,6 +108,7 @@ fn build_cors_layer(config: &ServerConfig) -> CorsLayer {
             header::CONTENT_TYPE,
             header::AUTHORIZATION,
             HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(TRACEPARENT_HEADER),
             HeaderName::from_static("x-adk-ui-protocol"),
             HeaderName::from_static("x-adk-ui-transport"),
         ]);
@@ -122,13 +127,8 @@ fn build_cors_layer(config: &ServerConfig) -> CorsLayer {
 }
 
 fn validate_request_id(headers: &HeaderMap) -> Option<String> {
-    let value = headers.get(REQUEST_ID_HEADER)?;
-    let raw = value.to_str().ok()?;
-    if raw.len() > 128 {
-        return None;
-    }
-    uuid::Uuid::parse_str(raw).ok()?;
-    Some(raw.to_string())
+    let raw = headers.get(REQUEST_ID_HEADER)?.to_str().ok()?;
+    is_valid_request_id(raw).then(|| raw.to_string())
 }
 
 async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Response {
@@ -136,6 +136,10 @@ async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Respon
         validate_request_id(request.headers()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
     request.extensions_mut().insert(RequestId(request_id.clone()));
+    // Normalize the header so handlers forward the effective id, not a rejected one
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
 
     let mut response = next.run(request).await;
     if let Ok(value) = HeaderValue::from_str(&request_id) {
@@ -220,6 +224,30 @@ async fn health_check(State(controller): State<HealthController>) -> impl IntoRe
     )
 }
 
+/// Readiness probe: warms up the root agent and reports per-component status.
+///
+/// Returns `503` until a warm-up succeeds; after that the successful report is
+/// served without re-checking.
+async fn readiness_check(State(controller): State<HealthController>) -> Response {
+    let mut warm_report = controller.warm_report.lock().await;
+    if let Some(report) = warm_report.as_ref() {
+        return (StatusCode::OK, Json(report.clone())).into_response();
+    }
+
+    match controller.agent_loader.root_agent().warm_up().await {
+        Ok(report) if report.is_ready() => {
+            *warm_report = Some(report.clone());
+            (StatusCode::OK, Json(report)).into_response()
+        }
+        Ok(report) => (StatusCode::SERVICE_UNAVAILABLE, Json(report)).into_response(),
+        Err(error) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "error": error.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
 /// Create the server application with optional A2A support
 pub fn create_app(config: ServerConfig) -> Router {
     create_app_with_a2a(config, None)
@@ -339,8 +367,10 @@ pub fn create_app_with_a2a(config: ServerConfig, a2a_base_url: Option<&str>) ->
         }
     });
 
-    let health_router =
-        Router::new().route("/health", get(health_check)).with_state(health_controller);
+    let health_router = Router::new()
+        .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
+        .with_state(health_controller);
 
     let ui_api_router = Router::new()
         .route("/apps", get(controllers::apps::list_apps))
@@ -651,8 +681,10 @@ impl ServerBuilder {
             }
         });
 
-        let health_router =
-            Router::new().route("/health", get(health_check)).with_state(health_controller);
+        let health_router = Router::new()
+            .route("/health", get(health_check))
+            .route("/ready", get(readiness_check))
+            .with_state(health_controller);
 
         let ui_api_router = Router::new()
             .route("/apps", get(controllers::apps::list_apps))
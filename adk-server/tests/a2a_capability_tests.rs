@@ -0,0 +1,174 @@
+//! Structured skills on the agent card, and a remote agent checking requests
+//! against them before sending.
+
+use adk_core::{
+    Agent, AgentInfo, Content, EventStream, InvocationContext, Result as AdkResult, SessionId,
+    UserId,
+};
+use adk_runner::Runner;
+use adk_server::a2a::{RemoteA2aAgent, build_agent_card};
+use adk_server::{ServerConfig, create_app_with_a2a};
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn order_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "sku": { "type": "string" },
+            "quantity": { "type": "integer", "minimum": 1 }
+        },
+        "required": ["sku", "quantity"]
+    })
+}
+
+/// Takes structured orders and records each request it serves.
+struct OrderAgent {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Agent for OrderAgent {
+    fn name(&self) -> &str {
+        "orders"
+    }
+
+    fn description(&self) -> &str {
+        "Places orders"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    fn info(&self) -> AgentInfo {
+        AgentInfo::new(self.name(), self.description())
+            .with_input_schema(order_schema())
+            .with_output_schema(json!({ "type": "object" }))
+    }
+
+    async fn run(&self, ctx: Arc<dyn InvocationContext>) -> AdkResult<EventStream> {
+        let text = ctx.user_content().parts.iter().find_map(|p| p.text().map(String::from));
+        self.seen.lock().unwrap().push(text.unwrap_or_default());
+
+        let mut event = adk_core::Event::new(ctx.invocation_id());
+        event.author = "orders".to_string();
+        event.llm_response.content = Some(Content::new("model").with_text(r#"{"placed":true}"#));
+        event.llm_response.turn_complete = true;
+        Ok(Box::pin(futures::stream::once(async { Ok(event) })))
+    }
+}
+
+struct SingleAgentLoader {
+    agent: Arc<dyn Agent>,
+}
+
+#[async_trait]
+impl adk_core::AgentLoader for SingleAgentLoader {
+    fn root_agent(&self) -> Arc<dyn Agent> {
+        self.agent.clone()
+    }
+
+    async fn load_agent(&self, _name: &str) -> AdkResult<Arc<dyn Agent>> {
+        Ok(self.agent.clone())
+    }
+
+    fn list_agents(&self) -> Vec<String> {
+        vec![self.agent.name().to_string()]
+    }
+}
+
+/// Serve the order agent over A2A on a random port and return its base URL.
+async fn start_downstream(seen: Arc<Mutex<Vec<String>>>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let config = ServerConfig::new(
+        Arc::new(SingleAgentLoader { agent: Arc::new(OrderAgent { seen }) }),
+        Arc::new(InMemorySessionService::new()),
+    );
+    let app = create_app_with_a2a(config, Some(&base_url));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    base_url
+}
+
+/// Send `text` through a remote agent and return the error messages it yields.
+async fn call_through_remote_agent(base_url: &str, text: &str) -> Vec<String> {
+    let session_service = Arc::new(InMemorySessionService::new());
+    session_service
+        .create(CreateRequest {
+            app_name: "upstream".to_string(),
+            user_id: "user".to_string(),
+            session_id: Some("session".to_string()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    let remote = RemoteA2aAgent::builder("remote").agent_url(base_url).build().unwrap();
+    let runner = Runner::builder()
+        .app_name("upstream")
+        .agent(Arc::new(remote))
+        .session_service(session_service)
+        .build()
+        .unwrap();
+
+    let mut events = runner
+        .run(
+            UserId::try_from("user").unwrap(),
+            SessionId::try_from("session").unwrap(),
+            Content::new("user").with_text(text),
+        )
+        .await
+        .unwrap();
+    let mut errors = Vec::new();
+    while let Some(event) = events.next().await {
+        if let Some(error) = event.unwrap().llm_response.error_message {
+            errors.push(error);
+        }
+    }
+    errors
+}
+
+#[test]
+fn test_card_advertises_structured_skill() {
+    let agent = OrderAgent { seen: Arc::default() };
+    let card = build_agent_card(&agent, "http://localhost:8080");
+
+    assert_eq!(card.default_input_modes, ["text/plain"]);
+    let skill = card.primary_skill().unwrap();
+    assert_eq!(skill.input_modes.as_deref(), Some(&["application/json".to_string()][..]));
+    assert_eq!(skill.input_schema, Some(order_schema()));
+    assert_eq!(skill.output_schema, Some(json!({ "type": "object" })));
+
+    let wire = serde_json::to_value(&card).unwrap();
+    assert_eq!(wire["defaultInputModes"], json!(["text/plain"]));
+    assert_eq!(wire["skills"][0]["inputSchema"], order_schema());
+    assert_eq!(wire["skills"][0]["outputModes"], json!(["application/json"]));
+
+    assert!(card.validate_request(r#"{"sku":"A-1","quantity":2}"#).is_ok());
+    assert!(card.validate_request("two of A-1 please").is_err());
+}
+
+#[tokio::test]
+async fn test_remote_agent_rejects_request_violating_input_schema() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let base_url = start_downstream(seen.clone()).await;
+
+    let errors = call_through_remote_agent(&base_url, r#"{"sku":"A-1","quantity":0}"#).await;
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("does not match the input schema"), "{}", errors[0]);
+    // The request never reached the remote agent.
+    assert!(seen.lock().unwrap().is_empty());
+
+    let errors = call_through_remote_agent(&base_url, r#"{"sku":"A-1","quantity":2}"#).await;
+
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(*seen.lock().unwrap(), [r#"{"sku":"A-1","quantity":2}"#]);
+}
@@ -0,0 +1,135 @@
+//! Request id propagation across an A2A hop.
+
+use adk_core::{
+    Agent, Content, EventStream, InvocationContext, Result as AdkResult, RunConfig, SessionId,
+    UserId,
+};
+use adk_runner::Runner;
+use adk_server::a2a::RemoteA2aAgent;
+use adk_server::{ServerConfig, create_app_with_a2a};
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const REQUEST_ID: &str = "6f1c2d9e-8a4b-4c3d-9e2f-1a2b3c4d5e6f";
+
+/// Records the request id of every invocation it serves.
+struct RecordingAgent {
+    seen: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+#[async_trait]
+impl Agent for RecordingAgent {
+    fn name(&self) -> &str {
+        "downstream"
+    }
+
+    fn description(&self) -> &str {
+        "Records request ids"
+    }
+
+    fn sub_agents(&self) -> &[Arc<dyn Agent>] {
+        &[]
+    }
+
+    async fn run(&self, ctx: Arc<dyn InvocationContext>) -> AdkResult<EventStream> {
+        self.seen.lock().unwrap().push(ctx.request_id().map(String::from));
+
+        let mut event = adk_core::Event::new(ctx.invocation_id());
+        event.author = "downstream".to_string();
+        event.llm_response.content = Some(Content::new("model").with_text("ok"));
+        event.llm_response.turn_complete = true;
+        Ok(Box::pin(futures::stream::once(async { Ok(event) })))
+    }
+}
+
+struct SingleAgentLoader {
+    agent: Arc<dyn Agent>,
+}
+
+#[async_trait]
+impl adk_core::AgentLoader for SingleAgentLoader {
+    fn root_agent(&self) -> Arc<dyn Agent> {
+        self.agent.clone()
+    }
+
+    async fn load_agent(&self, _name: &str) -> AdkResult<Arc<dyn Agent>> {
+        Ok(self.agent.clone())
+    }
+
+    fn list_agents(&self) -> Vec<String> {
+        vec![self.agent.name().to_string()]
+    }
+}
+
+/// Serve the recording agent over A2A on a random port and return its base URL.
+async fn start_downstream(seen: Arc<Mutex<Vec<Option<String>>>>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let config = ServerConfig::new(
+        Arc::new(SingleAgentLoader { agent: Arc::new(RecordingAgent { seen }) }),
+        Arc::new(InMemorySessionService::new()),
+    );
+    let app = create_app_with_a2a(config, Some(&base_url));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    base_url
+}
+
+async fn call_through_remote_agent(base_url: &str, streaming: bool) {
+    let session_service = Arc::new(InMemorySessionService::new());
+    session_service
+        .create(CreateRequest {
+            app_name: "upstream".to_string(),
+            user_id: "user".to_string(),
+            session_id: Some("session".to_string()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    let remote =
+        RemoteA2aAgent::builder("remote").agent_url(base_url).streaming(streaming).build().unwrap();
+    let runner = Runner::builder()
+        .app_name("upstream")
+        .agent(Arc::new(remote))
+        .session_service(session_service)
+        .run_config(RunConfig::builder().request_id(REQUEST_ID).build())
+        .build()
+        .unwrap();
+
+    let mut events = runner
+        .run(
+            UserId::try_from("user").unwrap(),
+            SessionId::try_from("session").unwrap(),
+            Content::new("user").with_text("hello"),
+        )
+        .await
+        .unwrap();
+    while let Some(event) = events.next().await {
+        event.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_request_id_reaches_downstream_agent() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let base_url = start_downstream(seen.clone()).await;
+
+    call_through_remote_agent(&base_url, false).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec![Some(REQUEST_ID.to_string())]);
+}
+
+#[tokio::test]
+async fn test_request_id_reaches_downstream_agent_when_streaming() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let base_url = start_downstream(seen.clone()).await;
+
+    call_through_remote_agent(&base_url, true).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec![Some(REQUEST_ID.to_string())]);
+}
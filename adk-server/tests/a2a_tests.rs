@@ -269,3 +269,22 @@ async fn test_a2a_tasks_cancel() {
     assert!(json["result"].is_object());
     assert_eq!(json["result"]["status"]["state"], "canceled");
 }
+
+#[test]
+fn test_a2a_artifact_keeps_sub_agent_author() {
+    use adk_core::{Content, Event, SUB_AGENT_CALL_ID_KEY};
+    use adk_server::a2a::{processor::EventProcessor, to_invocation_meta};
+
+    let mut event = Event::new("inv-1");
+    event.author = "researcher".to_string();
+    event.llm_response.content = Some(Content::new("model").with_text("Found "));
+    event.llm_response.partial = true;
+    event.provider_metadata.insert(SUB_AGENT_CALL_ID_KEY.to_string(), "call-1".to_string());
+
+    let meta = to_invocation_meta("app", "ctx-1", Some("user"));
+    let mut processor = EventProcessor::new("ctx-1".to_string(), "task-1".to_string(), meta);
+    let update = processor.process(&event).unwrap().expect("artifact update");
+
+    let metadata = update.artifact.metadata.unwrap();
+    assert_eq!(metadata["adk_author"], "researcher");
+}
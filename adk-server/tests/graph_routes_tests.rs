@@ -0,0 +1,118 @@
+//! Run, pause, and resume an interrupting graph over the graph routes.
+#![cfg(feature = "graph")]
+
+use adk_graph::prelude::*;
+use adk_server::graph::{GraphState, graph_router_with_state};
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// `draft` writes a draft; `review` pauses for approval until `approved` is set.
+fn approval_graph(checkpointer: Arc<dyn Checkpointer>) -> Arc<CompiledGraph> {
+    let graph = StateGraph::with_channels(&["topic", "draft", "approved", "status"])
+        .add_node_fn("draft", |ctx| async move {
+            let topic = ctx.get("topic").and_then(Value::as_str).unwrap_or("nothing").to_string();
+            Ok(NodeOutput::new().with_update("draft", json!(format!("A post about {topic}"))))
+        })
+        .add_node_fn("review", |ctx| async move {
+            if ctx.get("approved").and_then(Value::as_bool) == Some(true) {
+                return Ok(NodeOutput::new().with_update("status", json!("published")));
+            }
+            Ok(NodeOutput::interrupt_with_data(
+                "Approve the draft?",
+                json!({ "approved": { "type": "boolean" } }),
+            ))
+        })
+        .add_edge(START, "draft")
+        .add_edge("draft", "review")
+        .add_edge("review", END)
+        .compile()
+        .unwrap()
+        .with_checkpointer_arc(checkpointer);
+    Arc::new(graph)
+}
+
+async fn post(app: Router, uri: &str, body: Value) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+/// The JSON payloads of an SSE body.
+fn sse_events(body: &str) -> Vec<Value> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_run_pauses_and_resume_survives_reconnect() {
+    let checkpointer: Arc<dyn Checkpointer> = Arc::new(MemoryCheckpointer::new());
+    let app = graph_router_with_state(GraphState::new(approval_graph(checkpointer.clone())));
+
+    let (status, body) =
+        post(app.clone(), "/graph/post-1/run", json!({ "input": { "topic": "rust" } })).await;
+    assert_eq!(status, StatusCode::OK);
+    let events = sse_events(&body);
+    let last = events.last().unwrap();
+    assert_eq!(last["type"], "interrupted");
+    assert_eq!(last["interrupt"]["node"], "review");
+    assert_eq!(last["interrupt"]["message"], "Approve the draft?");
+    assert_eq!(last["interrupt"]["data"], json!({ "approved": { "type": "boolean" } }));
+
+    // Running again while paused is rejected.
+    let (status, _) = post(app.clone(), "/graph/post-1/run", json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // A new server over the same checkpointer still knows about the interrupt.
+    let app = graph_router_with_state(GraphState::new(approval_graph(checkpointer)));
+    let (status, thread) = get(app.clone(), "/graph/post-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(thread["interrupt"]["message"], "Approve the draft?");
+    assert_eq!(thread["state"]["draft"], "A post about rust");
+
+    let (status, body) =
+        post(app.clone(), "/graph/post-1/resume", json!({ "values": { "approved": true } })).await;
+    assert_eq!(status, StatusCode::OK);
+    let events = sse_events(&body);
+    let last = events.last().unwrap();
+    assert_eq!(last["type"], "done");
+    assert_eq!(last["state"]["status"], "published");
+    assert_eq!(last["state"]["draft"], "A post about rust");
+
+    let (_, thread) = get(app.clone(), "/graph/post-1").await;
+    assert!(thread["interrupt"].is_null());
+    let (status, _) = post(app, "/graph/post-1/resume", json!({ "values": {} })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_resume_unknown_thread_is_not_found() {
+    let app = graph_router_with_state(GraphState::new(approval_graph(Arc::new(
+        MemoryCheckpointer::new(),
+    ))));
+
+    let (status, _) = post(app, "/graph/missing/resume", json!({ "values": {} })).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
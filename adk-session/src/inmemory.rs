@@ -2,8 +2,8 @@ use crate::{
     AppendEventRequest, CreateRequest, DeleteRequest, Event, Events, GetRequest, KEY_PREFIX_TEMP,
     ListRequest, Session, SessionService, State, state_utils,
 };
-use adk_core::Result;
 use adk_core::identity::{AdkIdentity, AppName, SessionId, UserId};
+use adk_core::{Clock, Result, system_clock};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
@@ -28,6 +28,7 @@ pub struct InMemorySessionService {
     sessions: Arc<RwLock<HashMap<AdkIdentity, SessionData>>>,
     app_state: Arc<RwLock<HashMap<String, StateMap>>>,
     user_state: Arc<RwLock<HashMap<String, HashMap<String, StateMap>>>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl InMemorySessionService {
@@ -37,9 +38,19 @@ impl InMemorySessionService {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             app_state: Arc::new(RwLock::new(HashMap::new())),
             user_state: Arc::new(RwLock::new(HashMap::new())),
+            clock: system_clock(),
         }
     }
 
+    /// Stamp sessions with the time from `clock` instead of the system clock.
+    ///
+    /// Event timestamps are set by whoever creates the event and are kept
+    /// as-is.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     fn extract_state_deltas(delta: &HashMap<String, Value>) -> (StateMap, StateMap, StateMap) {
         state_utils::extract_state_deltas(delta)
     }
@@ -71,7 +82,7 @@ impl InMemorySessionService {
 
         data.events.clear();
         data.state = HashMap::new();
-        data.updated_at = Utc::now();
+        data.updated_at = self.clock.now();
 
         let app_name = data.identity.app_name.as_ref().to_string();
         let user_id = data.identity.user_id.as_ref().to_string();
@@ -132,11 +143,12 @@ impl SessionService for InMemorySessionService {
 
         let merged_state = Self::merge_states(&app_state_clone, &user_state_clone, &session_state);
 
+        let now = self.clock.now();
         let data = SessionData {
             identity: identity.clone(),
             events: Vec::new(),
             state: merged_state.clone(),
-            updated_at: Utc::now(),
+            updated_at: now,
         };
 
         let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
@@ -147,7 +159,7 @@ impl SessionService for InMemorySessionService {
             identity,
             state: merged_state,
             events: Vec::new(),
-            updated_at: Utc::now(),
+            updated_at: now,
         }))
     }
 
@@ -392,7 +404,8 @@ impl SessionService for InMemorySessionService {
 
         // Update the stored session state with rebuilt session-level state
         data.state = rebuilt_session_state.clone();
-        data.updated_at = data.events.last().map(|e| e.timestamp).unwrap_or(Utc::now());
+        data.updated_at =
+            data.events.last().map(|e| e.timestamp).unwrap_or_else(|| self.clock.now());
 
         let identity = data.identity.clone();
         let events = data.events.clone();
@@ -41,6 +41,7 @@ pub mod event;
 pub mod inmemory;
 /// Schema migration utilities for database-backed session stores.
 pub mod migration;
+pub mod profile;
 /// Session service trait and request/response types.
 pub mod service;
 /// The [`Session`] trait and state key prefix constants.
@@ -80,10 +81,13 @@ pub mod vertex;
 
 pub use event::{Event, EventActions, Events};
 pub use inmemory::InMemorySessionService;
+pub use profile::{
+    FileUserProfileService, InMemoryUserProfileService, ProfileSessionService, UserProfileService,
+};
 pub use service::{
     AppendEventRequest, CreateRequest, DeleteRequest, GetRequest, ListRequest, SessionService,
 };
-pub use session::{KEY_PREFIX_APP, KEY_PREFIX_TEMP, KEY_PREFIX_USER, Session};
+pub use session::{KEY_PREFIX_APP, KEY_PREFIX_TEMP, KEY_PREFIX_USER, Session, StateChange};
 pub use state::{ReadonlyState, State};
 pub use state_utils::{extract_state_deltas, merge_states};
 
@@ -0,0 +1,268 @@
+//! User profiles that outlive any single session.
+//!
+//! Every backend keeps `user:`-prefixed state per app and user, but it lives
+//! alongside the sessions and goes away with them (or with the process, for
+//! [`InMemorySessionService`](crate::InMemorySessionService)). A
+//! [`UserProfileService`] stores that state on its own, and
+//! [`ProfileSessionService`] wraps any [`SessionService`] to keep the two in
+//! sync:
+//!
+//! - [`create`](SessionService::create) loads the user's profile into the new
+//!   session as `user:` keys. Keys passed in the request win over stored ones.
+//! - [`append_event`](SessionService::append_event) writes the event's `user:`
+//!   state changes back to the profile.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use adk_session::{FileUserProfileService, InMemorySessionService, ProfileSessionService};
+//! use std::sync::Arc;
+//!
+//! let profiles = Arc::new(FileUserProfileService::new("./profiles")?);
+//! let sessions = ProfileSessionService::new(InMemorySessionService::new(), profiles);
+//! # Ok::<(), adk_core::AdkError>(())
+//! ```
+
+use crate::service::{
+    AppendEventRequest, CreateRequest, DeleteRequest, GetRequest, ListRequest, SessionService,
+};
+use crate::session::{KEY_PREFIX_USER, Session};
+use crate::{Event, state_utils};
+use adk_core::{AdkError, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+type StateMap = HashMap<String, Value>;
+
+/// Durable storage for a user's `user:` state, independent of sessions.
+///
+/// Keys are stored without the `user:` prefix.
+#[async_trait]
+pub trait UserProfileService: Send + Sync {
+    /// Load the profile of `user_id` in `app_name`. Unknown users have an empty profile.
+    async fn load(&self, app_name: &str, user_id: &str) -> Result<HashMap<String, Value>>;
+
+    /// Merge `delta` into the profile of `user_id` in `app_name`.
+    async fn update(
+        &self,
+        app_name: &str,
+        user_id: &str,
+        delta: HashMap<String, Value>,
+    ) -> Result<()>;
+}
+
+/// In-memory user profiles, shared by every session service holding the same instance.
+#[derive(Default)]
+pub struct InMemoryUserProfileService {
+    profiles: RwLock<HashMap<(String, String), StateMap>>,
+}
+
+impl InMemoryUserProfileService {
+    /// Creates an empty profile store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserProfileService for InMemoryUserProfileService {
+    async fn load(&self, app_name: &str, user_id: &str) -> Result<HashMap<String, Value>> {
+        let profiles = self.profiles.read().unwrap_or_else(|e| e.into_inner());
+        Ok(profiles.get(&(app_name.to_string(), user_id.to_string())).cloned().unwrap_or_default())
+    }
+
+    async fn update(
+        &self,
+        app_name: &str,
+        user_id: &str,
+        delta: HashMap<String, Value>,
+    ) -> Result<()> {
+        let mut profiles = self.profiles.write().unwrap_or_else(|e| e.into_inner());
+        profiles.entry((app_name.to_string(), user_id.to_string())).or_default().extend(delta);
+        Ok(())
+    }
+}
+
+/// User profiles stored as JSON files, one per user at `<base_dir>/<app_name>/<user_id>.json`.
+pub struct FileUserProfileService {
+    base_dir: PathBuf,
+    /// Serializes read-modify-write cycles on profile files.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl FileUserProfileService {
+    /// Create a profile store rooted at `base_dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(base_dir: impl Into<PathBuf>) -> Result<Self> {
+        let base_dir = base_dir.into();
+        std::fs::create_dir_all(&base_dir)
+            .map_err(|e| AdkError::session(format!("failed to create profile directory: {e}")))?;
+        Ok(Self { base_dir, write_lock: tokio::sync::Mutex::new(()) })
+    }
+
+    fn profile_path(&self, app_name: &str, user_id: &str) -> Result<PathBuf> {
+        for (component, field) in [(app_name, "app_name"), (user_id, "user_id")] {
+            if component.is_empty()
+                || component.contains('/')
+                || component.contains('\\')
+                || component.contains("..")
+            {
+                return Err(AdkError::session(format!(
+                    "invalid profile {field} '{component}': path separators and traversal patterns are not allowed"
+                )));
+            }
+        }
+        Ok(self.base_dir.join(app_name).join(format!("{user_id}.json")))
+    }
+
+    async fn read(&self, app_name: &str, user_id: &str) -> Result<StateMap> {
+        let path = self.profile_path(app_name, user_id)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| AdkError::session(format!("corrupt profile {}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(StateMap::new()),
+            Err(e) => Err(AdkError::session(format!("failed to read profile: {e}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl UserProfileService for FileUserProfileService {
+    async fn load(&self, app_name: &str, user_id: &str) -> Result<HashMap<String, Value>> {
+        self.read(app_name, user_id).await
+    }
+
+    async fn update(
+        &self,
+        app_name: &str,
+        user_id: &str,
+        delta: HashMap<String, Value>,
+    ) -> Result<()> {
+        let _guard = self.write_lock.lock().await;
+        let mut profile = self.read(app_name, user_id).await?;
+        profile.extend(delta);
+
+        let path = self.profile_path(app_name, user_id)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(|e| {
+                AdkError::session(format!("failed to create profile directory: {e}"))
+            })?;
+        }
+        let json = serde_json::to_vec_pretty(&profile)
+            .map_err(|e| AdkError::session(format!("failed to serialize profile: {e}")))?;
+        tokio::fs::write(&path, json)
+            .await
+            .map_err(|e| AdkError::session(format!("failed to write profile: {e}")))
+    }
+}
+
+/// Wraps a [`SessionService`] so `user:` state is loaded from and saved to a
+/// [`UserProfileService`]. See the [module docs](self).
+pub struct ProfileSessionService<S: SessionService> {
+    inner: S,
+    profiles: Arc<dyn UserProfileService>,
+    /// `(app_name, user_id)` of sessions seen through this wrapper, for
+    /// [`append_event`](SessionService::append_event), which only gets a session ID.
+    owners: RwLock<HashMap<String, (String, String)>>,
+}
+
+impl<S: SessionService> ProfileSessionService<S> {
+    /// Wrap `inner`, keeping user state in `profiles`.
+    pub fn new(inner: S, profiles: Arc<dyn UserProfileService>) -> Self {
+        Self { inner, profiles, owners: RwLock::new(HashMap::new()) }
+    }
+
+    fn remember(&self, session: &dyn Session) {
+        let mut owners = self.owners.write().unwrap_or_else(|e| e.into_inner());
+        owners.insert(
+            session.id().to_string(),
+            (session.app_name().to_string(), session.user_id().to_string()),
+        );
+    }
+
+    async fn save_user_delta(&self, app_name: &str, user_id: &str, event: &Event) -> Result<()> {
+        let (_, user_delta, _) = state_utils::extract_state_deltas(&event.actions.state_delta);
+        if user_delta.is_empty() {
+            return Ok(());
+        }
+        self.profiles.update(app_name, user_id, user_delta).await
+    }
+}
+
+#[async_trait]
+impl<S: SessionService> SessionService for ProfileSessionService<S> {
+    async fn create(&self, mut req: CreateRequest) -> Result<Box<dyn Session>> {
+        let profile = self.profiles.load(&req.app_name, &req.user_id).await?;
+        let (_, requested, _) = state_utils::extract_state_deltas(&req.state);
+        for (key, value) in profile {
+            req.state.entry(format!("{KEY_PREFIX_USER}{key}")).or_insert(value);
+        }
+        if !requested.is_empty() {
+            self.profiles.update(&req.app_name, &req.user_id, requested).await?;
+        }
+
+        let session = self.inner.create(req).await?;
+        self.remember(session.as_ref());
+        Ok(session)
+    }
+
+    async fn get(&self, req: GetRequest) -> Result<Box<dyn Session>> {
+        let session = self.inner.get(req).await?;
+        self.remember(session.as_ref());
+        Ok(session)
+    }
+
+    async fn list(&self, req: ListRequest) -> Result<Vec<Box<dyn Session>>> {
+        self.inner.list(req).await
+    }
+
+    async fn delete(&self, req: DeleteRequest) -> Result<()> {
+        self.owners.write().unwrap_or_else(|e| e.into_inner()).remove(&req.session_id);
+        self.inner.delete(req).await
+    }
+
+    async fn append_event(&self, session_id: &str, event: Event) -> Result<()> {
+        let owner = self.owners.read().unwrap_or_else(|e| e.into_inner()).get(session_id).cloned();
+        self.inner.append_event(session_id, event.clone()).await?;
+        match owner {
+            Some((app_name, user_id)) => self.save_user_delta(&app_name, &user_id, &event).await,
+            None => {
+                tracing::debug!(
+                    session_id,
+                    "session not loaded through ProfileSessionService, user state not saved to profile"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    async fn append_event_for_identity(&self, req: AppendEventRequest) -> Result<()> {
+        let app_name = req.identity.app_name.as_ref().to_string();
+        let user_id = req.identity.user_id.as_ref().to_string();
+        let event = req.event.clone();
+        self.inner.append_event_for_identity(req).await?;
+        self.save_user_delta(&app_name, &user_id, &event).await
+    }
+
+    async fn delete_all_sessions(&self, app_name: &str, user_id: &str) -> Result<()> {
+        self.inner.delete_all_sessions(app_name, user_id).await
+    }
+
+    async fn rewind(&self, session_id: &str, target_event_id: &str) -> Result<Box<dyn Session>> {
+        self.inner.rewind(session_id, target_event_id).await
+    }
+
+    async fn rewind_steps(&self, session_id: &str, steps: usize) -> Result<Box<dyn Session>> {
+        self.inner.rewind_steps(session_id, steps).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
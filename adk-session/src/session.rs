@@ -2,6 +2,21 @@ use crate::{Events, State};
 use adk_core::Result;
 use adk_core::identity::{AdkIdentity, AppName, SessionId, UserId};
 use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// One change to a state key, reconstructed from an event's `state_delta`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    /// The event whose delta made the change.
+    pub event_id: String,
+    /// When that event was created.
+    pub timestamp: DateTime<Utc>,
+    /// The value before the change, or `None` if the key was unset.
+    pub old: Option<Value>,
+    /// The value written by the event.
+    pub new: Value,
+}
 
 /// Trait representing a conversation session with state and event history.
 pub trait Session: Send + Sync {
@@ -76,6 +91,48 @@ pub trait Session: Send + Sync {
             session_id: self.try_session_id()?,
         })
     }
+
+    /// Returns every change to `key`, oldest first, by replaying the
+    /// `state_delta` of each event in the order it was appended.
+    ///
+    /// Only writes made through this session's events are visible. State
+    /// passed to [`SessionService::create`](crate::SessionService::create)
+    /// and `app:`/`user:` keys written by other sessions of the same app or
+    /// user are not recorded here, so the first change's `old` is `None`.
+    fn state_history(&self, key: &str) -> Vec<StateChange> {
+        let events = self.events();
+        let mut current: Option<Value> = None;
+        let mut history = Vec::new();
+        for event in (0..events.len()).filter_map(|index| events.at(index)) {
+            if let Some(new) = event.actions.state_delta.get(key) {
+                history.push(StateChange {
+                    event_id: event.id.clone(),
+                    timestamp: event.timestamp,
+                    old: current.replace(new.clone()),
+                    new: new.clone(),
+                });
+            }
+        }
+        history
+    }
+
+    /// Recomputes state by applying each event's `state_delta` in the order
+    /// the events were appended, later writes winning.
+    ///
+    /// Use this to recover when the materialized [`state()`](Self::state) is
+    /// suspected to be corrupt. The same caveats as
+    /// [`state_history()`](Self::state_history) apply: only writes recorded
+    /// in this session's events are included.
+    fn rebuild_state(&self) -> HashMap<String, Value> {
+        let events = self.events();
+        let mut state = HashMap::new();
+        for event in (0..events.len()).filter_map(|index| events.at(index)) {
+            state.extend(
+                event.actions.state_delta.iter().map(|(key, value)| (key.clone(), value.clone())),
+            );
+        }
+        state
+    }
 }
 
 /// Key prefix for application-scoped state entries.
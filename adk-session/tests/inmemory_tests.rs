@@ -1,6 +1,8 @@
+use adk_core::{Clock, MockClock};
 use adk_session::*;
 use serde_json::json;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_create_session() {
@@ -174,3 +176,77 @@ async fn test_delete_session() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_state_history_and_rebuild() {
+    let service = InMemorySessionService::new();
+    service
+        .create(CreateRequest {
+            app_name: "test_app".to_string(),
+            user_id: "user1".to_string(),
+            session_id: Some("session1".to_string()),
+            state: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    let writes = [
+        ("e1", vec![("status", json!("draft")), ("user:lang", json!("en"))]),
+        ("e2", vec![("status", json!("review")), ("temp:scratch", json!(1))]),
+        ("e3", vec![("reviewer", json!("kim"))]),
+        ("e4", vec![("status", json!("published"))]),
+    ];
+    let start = chrono::Utc::now();
+    for (offset, (id, delta)) in writes.into_iter().enumerate() {
+        let mut event = Event::new("inv1");
+        event.id = id.to_string();
+        event.timestamp = start + chrono::Duration::seconds(offset as i64);
+        event.actions.state_delta =
+            delta.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+        service.append_event("session1", event).await.unwrap();
+    }
+
+    let session = service
+        .get(GetRequest {
+            app_name: "test_app".to_string(),
+            user_id: "user1".to_string(),
+            session_id: "session1".to_string(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await
+        .unwrap();
+
+    let history = session.state_history("status");
+    let summary: Vec<_> = history
+        .iter()
+        .map(|change| (change.event_id.as_str(), change.old.clone(), change.new.clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("e1", None, json!("draft")),
+            ("e2", Some(json!("draft")), json!("review")),
+            ("e4", Some(json!("review")), json!("published")),
+        ]
+    );
+    assert_eq!(history[2].timestamp, start + chrono::Duration::seconds(3));
+    assert!(session.state_history("temp:scratch").is_empty());
+
+    assert_eq!(session.rebuild_state(), session.state().all());
+}
+
+#[tokio::test]
+async fn test_session_timestamps_use_injected_clock() {
+    let clock = MockClock::default();
+    let service = InMemorySessionService::new().with_clock(Arc::new(clock.clone()));
+
+    let req = CreateRequest {
+        app_name: "test_app".to_string(),
+        user_id: "user1".to_string(),
+        session_id: Some("session1".to_string()),
+        state: HashMap::new(),
+    };
+    let session = service.create(req).await.unwrap();
+    assert_eq!(session.last_update_time(), clock.now());
+}
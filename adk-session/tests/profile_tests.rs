@@ -0,0 +1,63 @@
+use adk_session::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn create_request(session_id: &str, state: HashMap<String, serde_json::Value>) -> CreateRequest {
+    CreateRequest {
+        app_name: "travel-app".to_string(),
+        user_id: "alice".to_string(),
+        session_id: Some(session_id.to_string()),
+        state,
+    }
+}
+
+#[tokio::test]
+async fn test_user_preference_follows_user_to_new_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let profiles: Arc<dyn UserProfileService> =
+        Arc::new(FileUserProfileService::new(dir.path()).unwrap());
+
+    let first = ProfileSessionService::new(InMemorySessionService::new(), profiles.clone());
+    first.create(create_request("session-1", HashMap::new())).await.unwrap();
+    let mut event = Event::new("inv-1");
+    event.actions.state_delta = HashMap::from([
+        ("user:seat".to_string(), json!("aisle")),
+        ("draft_itinerary".to_string(), json!("Lisbon")),
+    ]);
+    first.append_event("session-1", event).await.unwrap();
+
+    // A new service over fresh storage, as after a restart
+    let second = ProfileSessionService::new(
+        InMemorySessionService::new(),
+        Arc::new(FileUserProfileService::new(dir.path()).unwrap()),
+    );
+    let session = second.create(create_request("session-2", HashMap::new())).await.unwrap();
+
+    assert_eq!(session.state().get("user:seat"), Some(json!("aisle")));
+    assert_eq!(session.state().get("draft_itinerary"), None);
+}
+
+#[tokio::test]
+async fn test_requested_user_state_overrides_and_updates_profile() {
+    let profiles = Arc::new(InMemoryUserProfileService::new());
+    profiles
+        .update("travel-app", "alice", HashMap::from([("seat".to_string(), json!("aisle"))]))
+        .await
+        .unwrap();
+    let sessions = ProfileSessionService::new(InMemorySessionService::new(), profiles.clone());
+
+    let session = sessions
+        .create(create_request(
+            "session-1",
+            HashMap::from([("user:seat".to_string(), json!("window"))]),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(session.state().get("user:seat"), Some(json!("window")));
+    assert_eq!(
+        profiles.load("travel-app", "alice").await.unwrap(),
+        HashMap::from([("seat".to_string(), json!("window"))])
+    );
+}
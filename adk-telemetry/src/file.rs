@@ -0,0 +1,397 @@
+//! Span and metric export to rotating NDJSON files — no collector required.
+//!
+//! [`FileTelemetrySink`] appends one JSON object per line to a local file,
+//! so traces can be inspected with `jq`, `grep`, or loaded into a notebook
+//! without running an OTLP collector. Each line has a `type` of `"span"` or
+//! `"metric"`:
+//!
+//! ```json
+//! {"type":"span","service":"my-agent","name":"call_llm","trace_id":"inv-1","span_id":"evt-1","start_time_unix_nanos":1,"end_time_unix_nanos":2,"duration_ms":0.0,"attributes":{...}}
+//! {"type":"metric","service":"my-agent","name":"gen_ai.usage.input_tokens","value":42.0,"timestamp_unix_nanos":2,"attributes":{"span_name":"call_llm","trace_id":"inv-1"}}
+//! ```
+//!
+//! Numeric `gen_ai.usage.*` span attributes are also written as metric lines;
+//! custom values can be added with [`FileTelemetrySink::record_metric`].
+//!
+//! Like the SQLite exporter, writes happen on a dedicated thread so the traced
+//! code path only pays for a channel send. When the active file grows past
+//! [`FileRotation::max_bytes`] or gets older than [`FileRotation::max_age`],
+//! it is renamed to `<path>.1` (shifting older files to `.2`, `.3`, …) and a
+//! fresh file is started; at most [`FileRotation::max_files`] rotated files
+//! are kept.
+//!
+//! ```no_run
+//! use adk_telemetry::{FileRotation, init_with_file};
+//!
+//! let sink = init_with_file("my-agent", "traces.ndjson", FileRotation::by_size(10 * 1024 * 1024))
+//!     .expect("failed to initialize telemetry");
+//! // ... run your agent ...
+//! sink.flush().ok();
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+
+use crate::init::TelemetryError;
+use crate::span_exporter::SpanSink;
+
+/// How long the writer waits for more records before flushing its buffer.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+/// How long [`FileTelemetrySink::flush`] waits for the writer to acknowledge.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Span attribute prefix whose numeric values are also exported as metrics.
+const USAGE_PREFIX: &str = "gen_ai.usage.";
+
+/// When the active NDJSON file is rotated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRotation {
+    /// Rotate once the file would grow past this many bytes.
+    pub max_bytes: Option<u64>,
+    /// Rotate once the file has been written to for this long.
+    pub max_age: Option<Duration>,
+    /// Number of rotated files to keep (`<path>.1` is the newest).
+    pub max_files: usize,
+}
+
+impl Default for FileRotation {
+    fn default() -> Self {
+        Self { max_bytes: None, max_age: None, max_files: 5 }
+    }
+}
+
+impl FileRotation {
+    /// Never rotate; the file grows without bound.
+    pub fn never() -> Self {
+        Self::default()
+    }
+
+    /// Rotate when the file would exceed `max_bytes`.
+    pub fn by_size(max_bytes: u64) -> Self {
+        Self { max_bytes: Some(max_bytes), ..Self::default() }
+    }
+
+    /// Rotate every `max_age`.
+    pub fn by_age(max_age: Duration) -> Self {
+        Self { max_age: Some(max_age), ..Self::default() }
+    }
+
+    /// Also rotate every `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Keep at most `max_files` rotated files.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+}
+
+enum WriterMsg {
+    Line(String),
+    Flush(Sender<()>),
+    Shutdown,
+}
+
+/// [`SpanSink`] that appends spans and metrics as NDJSON to a rotating file.
+///
+/// Create with [`FileTelemetrySink::new`] (or the
+/// [`init_with_file`](crate::init_with_file) convenience helper) and hand it
+/// to [`AdkSpanLayer`](crate::span_exporter::AdkSpanLayer).
+///
+/// By default only the agent-loop spans are written (`agent.execute`,
+/// `call_llm`, `send_data`, `execute_tool*`). Call
+/// [`record_all_spans`](Self::record_all_spans) to write every span the
+/// active `EnvFilter` lets through.
+pub struct FileTelemetrySink {
+    tx: Sender<WriterMsg>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+    service_name: String,
+    record_all: bool,
+    path: PathBuf,
+}
+
+impl FileTelemetrySink {
+    /// Open (or create) the file at `path` for appending and start the writer thread.
+    pub fn new(
+        service_name: &str,
+        path: impl AsRef<Path>,
+        rotation: FileRotation,
+    ) -> Result<Self, TelemetryError> {
+        let path = path.as_ref().to_path_buf();
+        let writer = RotatingWriter::open(path.clone(), rotation)?;
+
+        let (tx, rx) = channel::<WriterMsg>();
+        let handle = std::thread::Builder::new()
+            .name("adk-telemetry-file".into())
+            .spawn(move || writer_loop(writer, rx))
+            .map_err(|e| TelemetryError::Init(format!("failed to spawn file writer: {e}")))?;
+
+        Ok(Self {
+            tx,
+            writer: Mutex::new(Some(handle)),
+            service_name: service_name.to_string(),
+            record_all: false,
+            path,
+        })
+    }
+
+    /// Write every span enabled by the subscriber's filter instead of only
+    /// the agent-loop allowlist.
+    pub fn record_all_spans(mut self, record_all: bool) -> Self {
+        self.record_all = record_all;
+        self
+    }
+
+    /// Path of the active file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a metric line with the current timestamp.
+    pub fn record_metric(&self, name: &str, value: f64, attributes: HashMap<String, String>) {
+        self.send(metric_line(&self.service_name, name, value, unix_nanos_now(), &attributes));
+    }
+
+    /// Block until every record handed to the sink so far is written to disk.
+    pub fn flush(&self) -> Result<(), TelemetryError> {
+        let (ack_tx, ack_rx) = channel();
+        self.tx
+            .send(WriterMsg::Flush(ack_tx))
+            .map_err(|_| TelemetryError::Init("file writer thread is gone".into()))?;
+        ack_rx
+            .recv_timeout(FLUSH_TIMEOUT)
+            .map_err(|_| TelemetryError::Init("file flush timed out".into()))
+    }
+
+    /// Flush pending records and stop the writer thread.
+    ///
+    /// Called automatically on drop; explicit shutdown is useful when the
+    /// sink is held alive by a global subscriber.
+    pub fn shutdown(&self) {
+        let _ = self.tx.send(WriterMsg::Shutdown);
+        if let Some(handle) = self.writer.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            let _ = handle.join();
+        }
+    }
+
+    fn send(&self, line: String) {
+        // Unbounded channel: never blocks the traced thread. If the writer
+        // died, dropping the record is the only safe option.
+        let _ = self.tx.send(WriterMsg::Line(line));
+    }
+}
+
+impl Drop for FileTelemetrySink {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl std::fmt::Debug for FileTelemetrySink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileTelemetrySink")
+            .field("service_name", &self.service_name)
+            .field("path", &self.path)
+            .field("record_all", &self.record_all)
+            .finish()
+    }
+}
+
+impl SpanSink for FileTelemetrySink {
+    fn export_span(&self, span_name: &str, attributes: HashMap<String, String>) {
+        if !self.record_all && !is_agent_loop_span(span_name) {
+            return;
+        }
+
+        let start = attr_nanos(&attributes, "start_time");
+        let end = attr_nanos(&attributes, "end_time");
+        let trace_id = attributes.get("trace_id").cloned().unwrap_or_default();
+
+        let mut metric_attributes = HashMap::from([
+            ("span_name".to_string(), span_name.to_string()),
+            ("trace_id".to_string(), trace_id.clone()),
+        ]);
+        if let Some(session_id) = attributes.get("gcp.vertex.agent.session_id") {
+            metric_attributes.insert("session_id".to_string(), session_id.clone());
+        }
+        for (key, value) in &attributes {
+            if key.starts_with(USAGE_PREFIX)
+                && let Ok(value) = value.parse::<f64>()
+            {
+                self.send(metric_line(&self.service_name, key, value, end, &metric_attributes));
+            }
+        }
+
+        let line = json!({
+            "type": "span",
+            "service": self.service_name,
+            "name": span_name,
+            "trace_id": trace_id,
+            "span_id": attributes.get("span_id").cloned().unwrap_or_default(),
+            "start_time_unix_nanos": start,
+            "end_time_unix_nanos": end,
+            "duration_ms": end.saturating_sub(start) as f64 / 1_000_000.0,
+            "attributes": attributes,
+        });
+        self.send(line.to_string());
+    }
+}
+
+fn is_agent_loop_span(span_name: &str) -> bool {
+    span_name == "agent.execute"
+        || span_name == "call_llm"
+        || span_name == "send_data"
+        || span_name.starts_with("execute_tool")
+}
+
+fn metric_line(
+    service: &str,
+    name: &str,
+    value: f64,
+    timestamp: u64,
+    attributes: &HashMap<String, String>,
+) -> String {
+    let line: Value = json!({
+        "type": "metric",
+        "service": service,
+        "name": name,
+        "value": value,
+        "timestamp_unix_nanos": timestamp,
+        "attributes": attributes,
+    });
+    line.to_string()
+}
+
+fn attr_nanos(attributes: &HashMap<String, String>, key: &str) -> u64 {
+    attributes.get(key).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0)
+}
+
+fn unix_nanos_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64
+}
+
+/// The active file plus the bookkeeping needed to decide when to rotate it.
+struct RotatingWriter {
+    path: PathBuf,
+    rotation: FileRotation,
+    file: BufWriter<File>,
+    size: u64,
+    opened_at: Instant,
+}
+
+impl RotatingWriter {
+    fn open(path: PathBuf, rotation: FileRotation) -> Result<Self, TelemetryError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                TelemetryError::Init(format!("failed to create {}: {e}", parent.display()))
+            })?;
+        }
+        let (file, size) = open_append(&path)?;
+        Ok(Self { path, rotation, file, size, opened_at: Instant::now() })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.should_rotate(len) {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn should_rotate(&self, incoming: u64) -> bool {
+        // Never rotate an empty file, even if a single line exceeds the limit.
+        self.size > 0
+            && (self.rotation.max_bytes.is_some_and(|max| self.size + incoming > max)
+                || self.rotation.max_age.is_some_and(|max| self.opened_at.elapsed() >= max))
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        let max_files = self.rotation.max_files;
+        if max_files == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            let _ = std::fs::remove_file(rotated_path(&self.path, max_files));
+            for index in (1..max_files).rev() {
+                let from = rotated_path(&self.path, index);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+
+        let (file, size) = open_append(&self.path).map_err(std::io::Error::other)?;
+        self.file = file;
+        self.size = size;
+        self.opened_at = Instant::now();
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<(BufWriter<File>, u64), TelemetryError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| TelemetryError::Init(format!("failed to open {}: {e}", path.display())))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    Ok((BufWriter::new(file), size))
+}
+
+/// `<path>.<index>`, e.g. `traces.ndjson.1`.
+pub(crate) fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+fn writer_loop(mut writer: RotatingWriter, rx: Receiver<WriterMsg>) {
+    loop {
+        let result = match rx.recv_timeout(FLUSH_INTERVAL) {
+            Ok(WriterMsg::Line(line)) => writer.write_line(&line),
+            Ok(WriterMsg::Flush(ack)) => {
+                let result = writer.file.flush();
+                let _ = ack.send(());
+                result
+            }
+            Ok(WriterMsg::Shutdown) | Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                let _ = writer.file.flush();
+                break;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => writer.file.flush(),
+        };
+
+        if let Err(e) = result {
+            // Events (not spans) can't recurse into the span layer, so logging
+            // from the writer thread is safe.
+            tracing::warn!("adk-telemetry file writer failed: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_path_appends_index() {
+        assert_eq!(
+            rotated_path(Path::new("logs/traces.ndjson"), 2),
+            PathBuf::from("logs/traces.ndjson.2")
+        );
+    }
+}
@@ -344,3 +344,58 @@ pub fn init_with_sqlite(
 
     Ok(exporter)
 }
+
+/// Initialize telemetry with span and metric export to a rotating NDJSON file —
+/// lightweight local observability with no OTLP collector.
+///
+/// Records are appended to `path` (created if needed) by a background writer
+/// thread and the file is rotated according to `rotation`. See
+/// [`FileTelemetrySink`](crate::file::FileTelemetrySink) for the line format.
+///
+/// Returns the sink so callers can [`flush`](crate::file::FileTelemetrySink::flush)
+/// before exiting or [`record_metric`](crate::file::FileTelemetrySink::record_metric)
+/// custom values.
+///
+/// # Example
+/// ```no_run
+/// use adk_telemetry::{FileRotation, init_with_file};
+///
+/// let sink = init_with_file("my-agent", "traces.ndjson", FileRotation::by_size(10 * 1024 * 1024))
+///     .expect("Failed to initialize telemetry");
+/// // ... run the agent ...
+/// sink.flush().ok();
+/// ```
+#[cfg(feature = "file")]
+pub fn init_with_file(
+    service_name: &str,
+    path: impl AsRef<std::path::Path>,
+    rotation: crate::file::FileRotation,
+) -> Result<Arc<crate::file::FileTelemetrySink>, TelemetryError> {
+    // Open the file (and surface I/O errors) before the irreversible global
+    // subscriber installation.
+    let sink = Arc::new(crate::file::FileTelemetrySink::new(service_name, path, rotation)?);
+    let sink_clone = sink.clone();
+
+    INIT.call_once(|| {
+        let filter = EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new("info"))
+            .unwrap_or_else(|_| EnvFilter::new("info"));
+
+        let adk_layer = AdkSpanLayer::new(sink_clone);
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_line_number(true),
+            )
+            .with(adk_layer)
+            .init();
+
+        tracing::info!(service.name = service_name, "telemetry initialized with file sink");
+    });
+
+    Ok(sink)
+}
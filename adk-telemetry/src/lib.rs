@@ -31,6 +31,10 @@ pub mod init;
 pub mod span_exporter;
 pub mod spans;
 
+// Span and metric export to rotating NDJSON files (feature-gated)
+#[cfg(feature = "file")]
+pub mod file;
+
 // Direct span export to a local SQLite file (feature-gated)
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
@@ -57,6 +61,10 @@ pub use spans::*;
 pub use span_exporter::*;
 
 // Re-export init functions and error type
+#[cfg(feature = "file")]
+pub use file::{FileRotation, FileTelemetrySink};
+#[cfg(feature = "file")]
+pub use init::init_with_file;
 #[cfg(feature = "sqlite")]
 pub use init::init_with_sqlite;
 pub use init::{TelemetryError, init_telemetry, init_with_adk_exporter, shutdown_telemetry};
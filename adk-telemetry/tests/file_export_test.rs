@@ -0,0 +1,118 @@
+//! End-to-end tests for the NDJSON file sink: spans emitted through a real
+//! tracing subscriber must be written as JSON lines and the file must rotate.
+#![cfg(feature = "file")]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use adk_telemetry::file::{FileRotation, FileTelemetrySink};
+use adk_telemetry::span_exporter::AdkSpanLayer;
+use serde_json::Value;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join("adk-telemetry-file-tests")
+        .join(format!("{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("traces.ndjson")
+}
+
+fn read_lines(path: &Path) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn rotated(path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{index}", path.display()))
+}
+
+fn emit_llm_call(invocation_id: &str) {
+    let parent = tracing::info_span!(
+        "agent.execute",
+        "gcp.vertex.agent.event_id" = format!("evt-{invocation_id}-agent"),
+        "gcp.vertex.agent.invocation_id" = invocation_id,
+        "gcp.vertex.agent.session_id" = "session-1"
+    );
+    let _parent_guard = parent.enter();
+
+    let llm = tracing::info_span!(
+        "call_llm",
+        "gcp.vertex.agent.event_id" = format!("evt-{invocation_id}-llm"),
+        "gen_ai.usage.input_tokens" = 42u64
+    );
+    drop(llm.entered());
+
+    // Not on the agent-loop allowlist — must NOT be written by default.
+    let noise = tracing::info_span!("internal.bookkeeping");
+    drop(noise.entered());
+}
+
+#[test]
+fn spans_and_usage_metrics_are_written_as_ndjson() {
+    let path = temp_file("spans");
+    let sink =
+        Arc::new(FileTelemetrySink::new("test-agent", &path, FileRotation::never()).unwrap());
+    let subscriber = tracing_subscriber::registry().with(AdkSpanLayer::new(sink.clone()));
+
+    tracing::subscriber::with_default(subscriber, || emit_llm_call("inv-1"));
+    sink.record_metric("custom.score", 0.5, HashMap::new());
+    sink.flush().unwrap();
+
+    let lines = read_lines(&path);
+    let spans: Vec<_> = lines.iter().filter(|l| l["type"] == "span").collect();
+    let names: Vec<_> = spans.iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["call_llm", "agent.execute"]);
+
+    let llm = spans[0];
+    assert_eq!(llm["service"], "test-agent");
+    assert_eq!(llm["trace_id"], "inv-1");
+    assert_eq!(llm["span_id"], "evt-inv-1-llm");
+    assert_eq!(llm["attributes"]["gcp.vertex.agent.session_id"], "session-1");
+    assert!(llm["end_time_unix_nanos"].as_u64() >= llm["start_time_unix_nanos"].as_u64());
+
+    let metrics: Vec<_> = lines.iter().filter(|l| l["type"] == "metric").collect();
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics[0]["name"], "gen_ai.usage.input_tokens");
+    assert_eq!(metrics[0]["value"], 42.0);
+    assert_eq!(metrics[0]["attributes"]["span_name"], "call_llm");
+    assert_eq!(metrics[1]["name"], "custom.score");
+}
+
+#[test]
+fn file_rotates_after_max_bytes() {
+    let path = temp_file("rotation");
+    let rotation = FileRotation::by_size(600).with_max_files(2);
+    let sink = Arc::new(FileTelemetrySink::new("test-agent", &path, rotation).unwrap());
+    let subscriber = tracing_subscriber::registry().with(AdkSpanLayer::new(sink.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..10 {
+            emit_llm_call(&format!("inv-{i}"));
+        }
+    });
+    sink.flush().unwrap();
+
+    // Every file respects the size limit, and only `max_files` rotated files are kept.
+    for file in [path.clone(), rotated(&path, 1), rotated(&path, 2)] {
+        let size = std::fs::metadata(&file).unwrap().len();
+        assert!(size <= 600, "{} is {size} bytes", file.display());
+        assert!(!read_lines(&file).is_empty());
+    }
+    assert!(!rotated(&path, 3).exists());
+
+    // The newest records are in the active file; the oldest were dropped.
+    assert_eq!(read_lines(&path).last().unwrap()["trace_id"], "inv-9");
+    let kept: Vec<_> = [rotated(&path, 2), rotated(&path, 1), path.clone()]
+        .iter()
+        .flat_map(|file| read_lines(file))
+        .filter(|line| line["type"] == "span")
+        .map(|line| line["trace_id"].as_str().unwrap().to_string())
+        .collect();
+    assert!(!kept.contains(&"inv-0".to_string()), "{kept:?}");
+}
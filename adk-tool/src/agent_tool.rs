@@ -28,8 +28,8 @@
 //! ```
 
 use adk_core::{
-    Agent, Artifacts, CallbackContext, Content, Event, InvocationContext, Memory, Part,
-    ReadonlyContext, Result, RunConfig, Session, State, Tool, ToolContext,
+    Agent, Artifacts, CallbackContext, CollectedResponse, Content, DelegationTimeout, Event,
+    InvocationContext, ReadonlyContext, Result, RunConfig, Session, State, Tool, ToolContext,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -77,6 +77,11 @@ impl Default for AgentToolConfig {
 /// When the parent LLM generates a function call targeting this tool,
 /// the framework executes the wrapped agent, captures its final response,
 /// and returns it as the tool's result.
+///
+/// While the agent runs, its events are forwarded to the caller through
+/// [`ToolContext::forward_sub_agent_event`] when the caller takes them (see
+/// [`ToolContext::sub_agent_streaming_mode`]), so its output streams live
+/// under its own name as `author`.
 pub struct AgentTool {
     agent: Arc<dyn Agent>,
     config: AgentToolConfig,
@@ -106,6 +111,10 @@ impl AgentTool {
     }
 
     /// Set timeout for sub-agent execution.
+    ///
+    /// When it elapses the sub-agent is cancelled and the tool returns a
+    /// [`DelegationTimeout`] result (`"error_type": "timeout"`) so the calling
+    /// agent can answer without it.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.config.timeout = Some(timeout);
         self
@@ -166,37 +175,20 @@ impl AgentTool {
     }
 
     /// Extract the final response text from agent events.
-    fn extract_response(events: &[Event]) -> Value {
-        // Collect all text responses from final events
-        let mut responses = Vec::new();
-
-        for event in events.iter().rev() {
-            if event.is_final_response() {
-                if let Some(content) = &event.llm_response.content {
-                    for part in &content.parts {
-                        if let Part::Text { text } = part {
-                            responses.push(text.clone());
-                        }
-                    }
-                }
-                break; // Only get the last final response
-            }
-        }
-
-        if responses.is_empty() {
-            // Try to get any text from the last event
-            if let Some(last_event) = events.last()
-                && let Some(content) = &last_event.llm_response.content
-            {
-                for part in &content.parts {
-                    if let Part::Text { text } = part {
-                        return json!({ "response": text });
-                    }
-                }
-            }
-            json!({ "response": "No response from agent" })
+    fn extract_response(events: Vec<Event>) -> Value {
+        let last_text = events.last().and_then(|event| {
+            event.llm_response.content.as_ref()?.parts.iter().find_map(|part| part.text())
+        });
+        let last_text = last_text.map(str::to_string);
+
+        let collected = CollectedResponse::from_events(events);
+        if !collected.text.is_empty() {
+            json!({ "response": collected.text })
+        } else if let Some(text) = last_text {
+            // No final response; fall back to any text from the last event
+            json!({ "response": text })
         } else {
-            json!({ "response": responses.join("\n") })
+            json!({ "response": "No response from agent" })
         }
     }
 }
@@ -241,12 +233,16 @@ impl Tool for AgentTool {
         // Create user content for the sub-agent
         let user_content = Content::new("user").with_text(&request_text);
 
+        // Stream the sub-agent in the caller's mode when it forwards our events.
+        let streaming_mode = ctx.sub_agent_streaming_mode();
+
         // Create an isolated context for the sub-agent
         let sub_ctx = Arc::new(AgentToolInvocationContext::new(
             ctx.clone(),
             self.agent.clone(),
             user_content.clone(),
             self.config.forward_artifacts,
+            streaming_mode.unwrap_or(adk_core::StreamingMode::None),
         ));
 
         // Execute the sub-agent
@@ -260,10 +256,16 @@ impl Tool for AgentTool {
 
             while let Some(result) = event_stream.next().await {
                 match result {
-                    Ok(event) => {
+                    Ok(mut event) => {
+                        if event.author.is_empty() {
+                            event.author = self.agent.name().to_string();
+                        }
                         // Merge state deltas
                         state_delta.extend(event.actions.state_delta.clone());
                         artifact_delta.extend(event.actions.artifact_delta.clone());
+                        if streaming_mode.is_some() {
+                            ctx.forward_sub_agent_event(event.clone()).await;
+                        }
                         events.push(event);
                     }
                     Err(e) => {
@@ -276,15 +278,28 @@ impl Tool for AgentTool {
             Ok((events, state_delta, artifact_delta))
         };
 
-        // Apply timeout if configured
-        let result = if let Some(timeout_duration) = self.config.timeout {
+        // Apply timeout if configured, never running past the turn's deadline
+        let time_left =
+            ctx.deadline().map(|at| at.saturating_duration_since(std::time::Instant::now()));
+        let timeout = match (self.config.timeout, time_left) {
+            (Some(timeout), Some(left)) => Some(timeout.min(left)),
+            (timeout, left) => timeout.or(left),
+        };
+        let result = if let Some(timeout_duration) = timeout {
             match tokio::time::timeout(timeout_duration, execution).await {
                 Ok(r) => r,
                 Err(_) => {
-                    return Ok(json!({
-                        "error": "Agent execution timed out",
-                        "agent": self.agent.name()
-                    }));
+                    // The sub-agent's stream was dropped with `execution`;
+                    // mark its context ended for anything still holding it.
+                    sub_ctx.end_invocation();
+                    adk_telemetry::warn!(
+                        "Agent tool {} timed out after {:?}",
+                        self.agent.name(),
+                        timeout_duration
+                    );
+                    return Ok(
+                        DelegationTimeout::new(self.agent.name(), timeout_duration).to_value()
+                    );
                 }
             }
         } else {
@@ -301,15 +316,15 @@ impl Tool for AgentTool {
                     ctx.set_actions(parent_actions);
                 }
 
-                // Extract and return the response
-                let response = Self::extract_response(&events);
-
                 adk_telemetry::debug!(
                     "Agent tool {} completed with {} events",
                     self.agent.name(),
                     events.len()
                 );
 
+                // Extract and return the response
+                let response = Self::extract_response(events);
+
                 Ok(response)
             }
             Err(e) => Ok(json!({
@@ -329,6 +344,7 @@ struct AgentToolInvocationContext {
     ended: Arc<AtomicBool>,
     forward_artifacts: bool,
     session: Arc<AgentToolSession>,
+    run_config: RunConfig,
 }
 
 impl AgentToolInvocationContext {
@@ -337,8 +353,15 @@ impl AgentToolInvocationContext {
         agent: Arc<dyn Agent>,
         user_content: Content,
         forward_artifacts: bool,
+        streaming_mode: adk_core::StreamingMode,
     ) -> Self {
         let invocation_id = format!("agent-tool-{}", uuid::Uuid::new_v4());
+        // The sub-agent streams only when its events are forwarded to the
+        // caller; otherwise responses are fully accumulated before being
+        // returned. extract_response merges streamed chunks either way. The
+        // sub-agent shares the caller's turn deadline.
+        let mut run_config = RunConfig::builder().streaming_mode(streaming_mode).build();
+        run_config.deadline_at = parent_ctx.deadline();
         Self {
             parent_ctx,
             agent,
@@ -347,6 +370,7 @@ impl AgentToolInvocationContext {
             ended: Arc::new(AtomicBool::new(false)),
             forward_artifacts,
             session: Arc::new(AgentToolSession::new()),
+            run_config,
         }
     }
 }
@@ -388,6 +412,10 @@ impl CallbackContext for AgentToolInvocationContext {
     fn artifacts(&self) -> Option<Arc<dyn Artifacts>> {
         if self.forward_artifacts { self.parent_ctx.artifacts() } else { None }
     }
+
+    fn blackboard(&self) -> Option<Arc<adk_core::Blackboard>> {
+        self.parent_ctx.blackboard()
+    }
 }
 
 #[async_trait]
@@ -396,24 +424,12 @@ impl InvocationContext for AgentToolInvocationContext {
         self.agent.clone()
     }
 
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        // Sub-agents don't have direct memory access in this implementation
-        // Could be extended to forward memory if needed
-        None
-    }
-
     fn session(&self) -> &dyn Session {
         self.session.as_ref()
     }
 
     fn run_config(&self) -> &RunConfig {
-        // Use None streaming mode for sub-agent so responses are fully accumulated
-        // before being returned. SSE mode yields partial chunks which makes
-        // extract_response unable to capture the complete text.
-        static AGENT_TOOL_CONFIG: std::sync::OnceLock<RunConfig> = std::sync::OnceLock::new();
-        AGENT_TOOL_CONFIG.get_or_init(|| {
-            RunConfig::builder().streaming_mode(adk_core::StreamingMode::None).build()
-        })
+        &self.run_config
     }
 
     fn end_invocation(&self) {
@@ -423,6 +439,10 @@ impl InvocationContext for AgentToolInvocationContext {
     fn ended(&self) -> bool {
         self.ended.load(std::sync::atomic::Ordering::SeqCst)
     }
+
+    fn is_cancelled(&self) -> bool {
+        self.parent_ctx.is_cancelled()
+    }
 }
 
 // Minimal session for sub-agent execution
@@ -584,7 +604,7 @@ mod tests {
         event.llm_response.content = Some(Content::new("model").with_text("The answer is 4"));
 
         let events = vec![event];
-        let response = AgentTool::extract_response(&events);
+        let response = AgentTool::extract_response(events);
 
         assert_eq!(response["response"], "The answer is 4");
     }
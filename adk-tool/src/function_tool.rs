@@ -1,4 +1,4 @@
-use adk_core::{Result, Tool, ToolContext};
+use adk_core::{AdkError, ErrorComponent, Result, Tool, ToolContext};
 use async_trait::async_trait;
 use schemars::{
     JsonSchema,
@@ -178,6 +178,13 @@ impl Tool for FunctionTool {
     )]
     async fn execute(&self, ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value> {
         adk_telemetry::debug!("Executing tool");
+        if ctx.is_cancelled() {
+            return Err(AdkError::cancelled(
+                ErrorComponent::Tool,
+                "tool.cancelled",
+                format!("tool '{}' was not run: invocation cancelled", self.name),
+            ));
+        }
         (self.handler)(ctx, args).await
     }
 }
@@ -8,6 +8,7 @@
 //! This crate provides the tool infrastructure for ADK agents:
 //!
 //! - [`FunctionTool`] - Create tools from async Rust functions
+//! - [`SessionScopedTool`] - Per-session tool state with a cleanup hook
 //! - [`AgentTool`] - Use agents as callable tools for composition
 //! - [`GoogleSearchTool`] - Web search via Gemini's grounding
 //! - `McpToolset` - MCP tools, resources, prompts, completion, elicitation,
@@ -124,7 +125,7 @@ pub use mcp::{
     ResourceNotificationHandler, ResourceTemplate,
 };
 pub use simple_context::SimpleToolContext;
-pub use stateful_tool::StatefulTool;
+pub use stateful_tool::{SessionScopedTool, StatefulTool, stateful_tool};
 pub use toolset::{
     BasicToolset, FilteredToolset, MergedToolset, PrefixedToolset, string_predicate,
 };
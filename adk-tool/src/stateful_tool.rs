@@ -6,9 +6,10 @@ use schemars::{
 };
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 type AsyncStatefulHandler<S> = Box<
     dyn Fn(
@@ -20,6 +21,11 @@ type AsyncStatefulHandler<S> = Box<
         + Sync,
 >;
 
+type StateFactory<S> = Box<dyn Fn(&str) -> S + Send + Sync>;
+
+type AsyncCleanup<S> =
+    Box<dyn Fn(String, Arc<S>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
 /// A generic tool wrapper that manages shared state for stateful closures.
 ///
 /// `StatefulTool<S>` accepts an `Arc<S>` and a handler closure that receives
@@ -218,6 +224,265 @@ impl<S: Send + Sync + 'static> Tool for StatefulTool<S> {
     }
 }
 
+/// A tool whose state is isolated per session.
+///
+/// Tools are shared `Arc`s across every session an agent serves, so state held
+/// by a [`StatefulTool`] is visible to all of them. `SessionScopedTool<S>`
+/// instead keeps one `Arc<S>` per session id, so concurrent sessions never
+/// share mutable state (a shopping cart, a scratchpad connection).
+///
+/// # Lifecycle
+///
+/// 1. The first call in a session runs the factory with that session's id to
+///    create its state.
+/// 2. Later calls in the same session reuse that state.
+/// 3. [`end_session`](Self::end_session) drops the session's state and runs the
+///    cleanup hook, if one is set. Call it when the session is deleted or
+///    closed. States still held when the tool is dropped are released without
+///    running the hook.
+///
+/// Calls made without a session (an empty session id, as with a default
+/// [`SimpleToolContext`](crate::SimpleToolContext)) share a single state.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adk_tool::stateful_tool;
+/// use serde_json::json;
+/// use tokio::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Cart { items: Mutex<Vec<String>> }
+///
+/// let cart_tool = Arc::new(
+///     stateful_tool("add_to_cart", "Add an item to the cart", |_session_id| Cart::default(),
+///         |cart, _ctx, args| async move {
+///             let mut items = cart.items.lock().await;
+///             items.push(args["item"].as_str().unwrap_or_default().to_string());
+///             Ok(json!({ "items": *items }))
+///         })
+///     .with_cleanup(|session_id, _cart| async move {
+///         tracing::info!(session_id, "cart discarded");
+///     }),
+/// );
+///
+/// // When the session is deleted:
+/// cart_tool.end_session("session-123").await;
+/// ```
+pub struct SessionScopedTool<S: Send + Sync + 'static> {
+    name: String,
+    description: String,
+    factory: StateFactory<S>,
+    handler: AsyncStatefulHandler<S>,
+    cleanup: Option<AsyncCleanup<S>>,
+    states: Mutex<HashMap<String, Arc<S>>>,
+    long_running: bool,
+    read_only: bool,
+    concurrency_safe: bool,
+    parameters_schema: Option<Value>,
+    response_schema: Option<Value>,
+    scopes: Vec<&'static str>,
+}
+
+impl<S: Send + Sync + 'static> SessionScopedTool<S> {
+    /// Create a new session-scoped tool.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Tool name exposed to the LLM
+    /// * `description` - Human-readable description of what the tool does
+    /// * `factory` - Creates the state for a session, given its id
+    /// * `handler` - Async closure receiving `(Arc<S>, Arc<dyn ToolContext>, Value)`
+    pub fn new<Fac, F, Fut>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        factory: Fac,
+        handler: F,
+    ) -> Self
+    where
+        Fac: Fn(&str) -> S + Send + Sync + 'static,
+        F: Fn(Arc<S>, Arc<dyn ToolContext>, Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            factory: Box::new(factory),
+            handler: Box::new(move |s, ctx, args| Box::pin(handler(s, ctx, args))),
+            cleanup: None,
+            states: Mutex::new(HashMap::new()),
+            long_running: false,
+            read_only: false,
+            concurrency_safe: false,
+            parameters_schema: None,
+            response_schema: None,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Run `cleanup` with a session's id and state when the session ends.
+    pub fn with_cleanup<C, Fut>(mut self, cleanup: C) -> Self
+    where
+        C: Fn(String, Arc<S>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.cleanup =
+            Some(Box::new(move |session_id, state| Box::pin(cleanup(session_id, state))));
+        self
+    }
+
+    /// Mark this tool as long-running (prevents duplicate invocations).
+    pub fn with_long_running(mut self, long_running: bool) -> Self {
+        self.long_running = long_running;
+        self
+    }
+
+    /// Mark this tool as read-only (safe for parallel dispatch).
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Mark this tool as concurrency-safe (can run in parallel with other tools).
+    pub fn with_concurrency_safe(mut self, concurrency_safe: bool) -> Self {
+        self.concurrency_safe = concurrency_safe;
+        self
+    }
+
+    /// Derive the parameters JSON Schema from a type implementing `JsonSchema`.
+    pub fn with_parameters_schema<T>(mut self) -> Self
+    where
+        T: JsonSchema + Serialize,
+    {
+        self.parameters_schema = Some(generate_schema::<T>());
+        self
+    }
+
+    /// Derive the response JSON Schema from a type implementing `JsonSchema`.
+    pub fn with_response_schema<T>(mut self) -> Self
+    where
+        T: JsonSchema + Serialize,
+    {
+        self.response_schema = Some(generate_schema::<T>());
+        self
+    }
+
+    /// Declare the scopes required to execute this tool.
+    pub fn with_scopes(mut self, scopes: &[&'static str]) -> Self {
+        self.scopes = scopes.to_vec();
+        self
+    }
+
+    /// The state of a session, if the tool has been called in it.
+    pub fn session_state(&self, session_id: &str) -> Option<Arc<S>> {
+        self.states.lock().unwrap_or_else(|e| e.into_inner()).get(session_id).cloned()
+    }
+
+    /// Number of sessions currently holding state.
+    pub fn active_sessions(&self) -> usize {
+        self.states.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Drop a session's state and run the cleanup hook.
+    ///
+    /// Returns `false` if the session had no state.
+    pub async fn end_session(&self, session_id: &str) -> bool {
+        let removed = self.states.lock().unwrap_or_else(|e| e.into_inner()).remove(session_id);
+        let Some(state) = removed else {
+            return false;
+        };
+        if let Some(cleanup) = &self.cleanup {
+            cleanup(session_id.to_string(), state).await;
+        }
+        true
+    }
+
+    fn state_for(&self, session_id: &str) -> Arc<S> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        states
+            .entry(session_id.to_string())
+            .or_insert_with(|| Arc::new((self.factory)(session_id)))
+            .clone()
+    }
+}
+
+/// Create a [`SessionScopedTool`] whose state is built per session by `factory`.
+pub fn stateful_tool<S, Fac, F, Fut>(
+    name: impl Into<String>,
+    description: impl Into<String>,
+    factory: Fac,
+    handler: F,
+) -> SessionScopedTool<S>
+where
+    S: Send + Sync + 'static,
+    Fac: Fn(&str) -> S + Send + Sync + 'static,
+    F: Fn(Arc<S>, Arc<dyn ToolContext>, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Value>> + Send + 'static,
+{
+    SessionScopedTool::new(name, description, factory, handler)
+}
+
+#[async_trait]
+impl<S: Send + Sync + 'static> Tool for SessionScopedTool<S> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn enhanced_description(&self) -> String {
+        if self.long_running {
+            if self.description.is_empty() {
+                LONG_RUNNING_NOTE.to_string()
+            } else {
+                format!("{}\n\n{}", self.description, LONG_RUNNING_NOTE)
+            }
+        } else {
+            self.description.clone()
+        }
+    }
+
+    fn is_long_running(&self) -> bool {
+        self.long_running
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn is_concurrency_safe(&self) -> bool {
+        self.concurrency_safe
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        self.parameters_schema.clone()
+    }
+
+    fn response_schema(&self) -> Option<Value> {
+        self.response_schema.clone()
+    }
+
+    fn required_scopes(&self) -> &[&str] {
+        &self.scopes
+    }
+
+    #[adk_telemetry::instrument(
+        skip(self, ctx, args),
+        fields(
+            tool.name = %self.name,
+            session.id = %ctx.session_id(),
+            function_call.id = %ctx.function_call_id()
+        )
+    )]
+    async fn execute(&self, ctx: Arc<dyn ToolContext>, args: Value) -> Result<Value> {
+        adk_telemetry::debug!("Executing session-scoped tool");
+        let state = self.state_for(ctx.session_id());
+        (self.handler)(state, ctx, args).await
+    }
+}
+
 fn generate_schema<T>() -> Value
 where
     T: JsonSchema + Serialize,
@@ -0,0 +1,78 @@
+use adk_core::{Tool, ToolContext};
+use adk_tool::{SimpleToolContext, stateful_tool};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Cart {
+    items: tokio::sync::Mutex<Vec<String>>,
+}
+
+fn ctx(session_id: &str) -> Arc<dyn ToolContext> {
+    Arc::new(SimpleToolContext::new("shop").with_session_id(session_id))
+}
+
+#[tokio::test]
+async fn test_sessions_accumulate_independent_state() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let counter = created.clone();
+    let tool = stateful_tool(
+        "add_to_cart",
+        "Add an item to the cart",
+        move |_session_id| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Cart::default()
+        },
+        |cart, _ctx, args| async move {
+            let mut items = cart.items.lock().await;
+            items.push(args["item"].as_str().unwrap().to_string());
+            Ok(json!({ "items": *items }))
+        },
+    );
+
+    tool.execute(ctx("alice"), json!({ "item": "apple" })).await.unwrap();
+    tool.execute(ctx("bob"), json!({ "item": "bread" })).await.unwrap();
+    let alice = tool.execute(ctx("alice"), json!({ "item": "avocado" })).await.unwrap();
+    let bob = tool.execute(ctx("bob"), json!({ "item": "butter" })).await.unwrap();
+
+    assert_eq!(alice, json!({ "items": ["apple", "avocado"] }));
+    assert_eq!(bob, json!({ "items": ["bread", "butter"] }));
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+    assert_eq!(tool.active_sessions(), 2);
+}
+
+#[tokio::test]
+async fn test_end_session_runs_cleanup_and_resets_state() {
+    let cleaned = Arc::new(Mutex::new(Vec::new()));
+    let log = cleaned.clone();
+    let tool = stateful_tool(
+        "add_to_cart",
+        "Add an item to the cart",
+        |_session_id| Cart::default(),
+        |cart, _ctx, args| async move {
+            let mut items = cart.items.lock().await;
+            items.push(args["item"].as_str().unwrap().to_string());
+            Ok(json!({ "count": items.len() }))
+        },
+    )
+    .with_cleanup(move |session_id, cart| {
+        let log = log.clone();
+        async move {
+            let count = cart.items.lock().await.len();
+            log.lock().unwrap().push((session_id, count));
+        }
+    });
+
+    tool.execute(ctx("alice"), json!({ "item": "apple" })).await.unwrap();
+    tool.execute(ctx("alice"), json!({ "item": "avocado" })).await.unwrap();
+
+    assert!(tool.end_session("alice").await);
+    assert!(!tool.end_session("alice").await);
+    assert_eq!(*cleaned.lock().unwrap(), vec![("alice".to_string(), 2)]);
+    assert!(tool.session_state("alice").is_none());
+
+    // A new call after the session ended starts from fresh state
+    let result = tool.execute(ctx("alice"), json!({ "item": "apricot" })).await.unwrap();
+    assert_eq!(result, json!({ "count": 1 }));
+}
@@ -106,6 +106,10 @@ async fn main() -> anyhow::Result<()> {
     if std::env::var("BEDROCK_PROMPT_CACHING").as_deref() != Ok("1") {
         config = config.without_prompt_caching();
     }
+    // A VPC interface endpoint keeps traffic off the public internet.
+    if let Ok(endpoint_url) = std::env::var("BEDROCK_ENDPOINT_URL") {
+        config = config.with_endpoint_url(endpoint_url);
+    }
     let model = Arc::new(BedrockClient::new(config).await?);
 
     let agent = Arc::new(
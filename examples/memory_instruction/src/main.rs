@@ -0,0 +1,144 @@
+//! # Memory Instruction Example
+//!
+//! A travel assistant whose instruction is written fresh before every model
+//! call. The instruction provider searches the memory service with the user's
+//! message and injects the three most recent matching memories, together with
+//! today's date, into the system prompt.
+//!
+//! ## What This Shows
+//!
+//! - `LlmAgentBuilder::instruction_fn` with an async closure that captures a
+//!   `MemoryService` and queries it at request time
+//! - Session state templating applied to the provider's output (`{user_name}`)
+//! - Provider errors, such as a failed memory search, failing the call
+//!
+//! ## Run
+//!
+//! ```bash
+//! export GOOGLE_API_KEY=your-key-here
+//! cargo run --manifest-path examples/memory_instruction/Cargo.toml
+//! ```
+
+use adk_agent::LlmAgentBuilder;
+use adk_core::{Agent, Content, Part, SessionId, UserId};
+use adk_memory::{InMemoryMemoryService, MemoryEntry, MemoryService, SearchRequest};
+use adk_model::GeminiModel;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, InMemorySessionService, SessionService};
+use chrono::{Duration, Utc};
+use futures::StreamExt;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const APP_NAME: &str = "memory-instruction-example";
+const MODEL_NAME: &str = "gemini-3-pro-preview";
+const RECENT_MEMORIES: usize = 3;
+const QUESTION: &str = "Suggest a trip for my next long weekend.";
+
+/// Things the user told the assistant in earlier sessions, newest last.
+const MEMORIES: &[(i64, &str)] = &[
+    (400, "Took a ski trip to Chamonix and did not enjoy the cold."),
+    (120, "Prefers a trip reachable by train over flying."),
+    (30, "Is learning Portuguese and wants a trip to practise it."),
+    (5, "Has a budget of 600 EUR for the next trip."),
+];
+
+async fn seed_memory(memory: &InMemoryMemoryService) -> anyhow::Result<()> {
+    for (days_ago, text) in MEMORIES {
+        let entry = MemoryEntry {
+            content: Content::new("user").with_text(*text),
+            author: "user".into(),
+            timestamp: Utc::now() - Duration::days(*days_ago),
+        };
+        memory.add_entry(APP_NAME, "user", entry).await?;
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    let api_key = std::env::var("GOOGLE_API_KEY")
+        .map_err(|_| anyhow::anyhow!("set GOOGLE_API_KEY (see .env.example)"))?;
+    let model_name = std::env::var("GEMINI_MODEL").unwrap_or_else(|_| MODEL_NAME.to_string());
+    let model = Arc::new(GeminiModel::new(api_key, model_name)?);
+
+    let memory = Arc::new(InMemoryMemoryService::new());
+    seed_memory(&memory).await?;
+
+    let agent = LlmAgentBuilder::new("travel_assistant")
+        .description("Plans trips around what it remembers about the user")
+        .model(model)
+        .instruction_fn(move |ctx| {
+            let memory = memory.clone();
+            async move {
+                let query = ctx.user_content().parts.iter().filter_map(Part::text).collect();
+                let mut memories = memory
+                    .search(SearchRequest {
+                        query,
+                        user_id: ctx.user_id().to_string(),
+                        app_name: ctx.app_name().to_string(),
+                        limit: None,
+                        min_score: None,
+                        project_id: None,
+                    })
+                    .await?
+                    .memories;
+                memories.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+                let notes = memories
+                    .iter()
+                    .take(RECENT_MEMORIES)
+                    .map(|entry| {
+                        let text: String =
+                            entry.content.parts.iter().filter_map(Part::text).collect();
+                        format!("- {} ({})", text, entry.timestamp.format("%Y-%m-%d"))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let instruction = format!(
+                    "You are a travel assistant for {{user_name}}. Today is {}.\n\n\
+                     What you remember about them, most recent first:\n{notes}",
+                    Utc::now().format("%A %Y-%m-%d"),
+                );
+                println!("── instruction ──\n{instruction}\n");
+                Ok(instruction)
+            }
+        })
+        .build()?;
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: APP_NAME.into(),
+            user_id: "user".into(),
+            session_id: Some("trip".into()),
+            state: HashMap::from([("user_name".to_string(), json!("Sam"))]),
+        })
+        .await?;
+    let runner = Runner::builder()
+        .app_name(APP_NAME)
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions)
+        .build()?;
+
+    println!("Question: {QUESTION}\n");
+    let mut events = runner
+        .run(
+            UserId::new("user")?,
+            SessionId::new("trip")?,
+            Content::new("user").with_text(QUESTION),
+        )
+        .await?;
+    while let Some(event) = events.next().await {
+        let event = event?;
+        if let Some(content) = &event.llm_response.content {
+            for text in content.parts.iter().filter_map(Part::text) {
+                print!("{text}");
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
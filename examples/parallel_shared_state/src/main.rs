@@ -9,8 +9,7 @@
 
 use adk_agent::ParallelAgent;
 use adk_core::{
-    Agent, CallbackContext, Content, Event, EventStream, InvocationContext, Memory,
-    ReadonlyContext, Result, RunConfig, Session,
+    Agent, CallbackContext, Content, Event, EventStream, InvocationContext, ReadonlyContext, Result, RunConfig, Session,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -91,9 +90,6 @@ impl InvocationContext for MockContext {
     fn agent(&self) -> Arc<dyn Agent> {
         unimplemented!()
     }
-    fn memory(&self) -> Option<Arc<dyn Memory>> {
-        None
-    }
     fn session(&self) -> &dyn Session {
         &self.session
     }
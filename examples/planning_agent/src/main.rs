@@ -0,0 +1,127 @@
+//! # Planning Agent Example
+//!
+//! A research agent that plans before it searches. `PlanReActPlanner` asks the
+//! model to write a plan, carry it out with Google Search and a note-taking
+//! tool, revise the plan when a step fails, and finish with a tagged final
+//! answer.
+//!
+//! ## What This Shows
+//!
+//! - `LlmAgentBuilder::planner` with `PlanReActPlanner::new().max_steps(..)`
+//! - Plans surfaced as their own events (`Event::plan`), apart from the answer
+//! - A failing tool step prompting a revised plan
+//! - The final answer, without planning text, stored under `output_key`
+//!
+//! ## Run
+//!
+//! ```bash
+//! export GOOGLE_API_KEY=your-key-here
+//! cargo run --manifest-path examples/planning_agent/Cargo.toml
+//! ```
+
+use adk_agent::{LlmAgentBuilder, PlanReActPlanner};
+use adk_core::{AdkError, Agent, Content, Part, SessionId, UserId};
+use adk_model::GeminiModel;
+use adk_runner::Runner;
+use adk_session::{CreateRequest, GetRequest, InMemorySessionService, SessionService};
+use adk_tool::{FunctionTool, GoogleSearchTool};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const APP_NAME: &str = "planning-agent-example";
+const MODEL_NAME: &str = "gemini-3-pro-preview";
+const QUESTION: &str = "What changed in the most recent stable Rust release, and which \
+                        change matters most for async code? Save a note for each source you use.";
+
+/// Records a note about a source. Notes without a URL fail, so the agent has
+/// something to re-plan around.
+fn save_note_tool() -> FunctionTool {
+    FunctionTool::new(
+        "save_note",
+        "Save a research note. Arguments: {\"url\": string, \"summary\": string}",
+        |_ctx, args: Value| async move {
+            let url = args.get("url").and_then(Value::as_str).unwrap_or_default();
+            if !url.starts_with("http") {
+                return Err(AdkError::tool("a note needs the source URL"));
+            }
+            Ok(json!({ "saved": url }))
+        },
+    )
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    let api_key = std::env::var("GOOGLE_API_KEY")
+        .map_err(|_| anyhow::anyhow!("set GOOGLE_API_KEY (see .env.example)"))?;
+    let model_name = std::env::var("GEMINI_MODEL").unwrap_or_else(|_| MODEL_NAME.to_string());
+    let model = Arc::new(GeminiModel::new(api_key, model_name)?);
+
+    let agent = LlmAgentBuilder::new("researcher")
+        .description("Researches questions on the web")
+        .instruction("You are a careful researcher. Cite the sources you rely on.")
+        .model(model)
+        .tool(Arc::new(GoogleSearchTool::new()))
+        .tool(Arc::new(save_note_tool()))
+        .planner(Arc::new(PlanReActPlanner::new().max_steps(6)))
+        .output_key("answer")
+        .build()?;
+
+    let sessions = Arc::new(InMemorySessionService::new());
+    sessions
+        .create(CreateRequest {
+            app_name: APP_NAME.into(),
+            user_id: "user".into(),
+            session_id: Some("research".into()),
+            state: HashMap::new(),
+        })
+        .await?;
+    let runner = Runner::builder()
+        .app_name(APP_NAME)
+        .agent(Arc::new(agent) as Arc<dyn Agent>)
+        .session_service(sessions.clone())
+        .build()?;
+
+    println!("Question: {QUESTION}\n");
+    let mut events = runner
+        .run(
+            UserId::new("user")?,
+            SessionId::new("research")?,
+            Content::new("user").with_text(QUESTION),
+        )
+        .await?;
+    while let Some(event) = events.next().await {
+        let event = event?;
+        if let Some(plan) = event.plan() {
+            println!("── plan ──\n{plan}\n");
+            continue;
+        }
+        let Some(content) = event.llm_response.content.as_ref() else { continue };
+        for part in &content.parts {
+            match part {
+                Part::FunctionCall { name, args, .. } => println!("→ {name} {args}"),
+                Part::FunctionResponse { function_response, .. } => {
+                    println!("← {} {}", function_response.name, function_response.response)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let session = sessions
+        .get(GetRequest {
+            app_name: APP_NAME.into(),
+            user_id: "user".into(),
+            session_id: "research".into(),
+            num_recent_events: None,
+            after: None,
+        })
+        .await?;
+    match session.state().get("answer") {
+        Some(Value::String(answer)) => println!("\n── answer ──\n{answer}"),
+        _ => println!("\nThe agent finished without an answer."),
+    }
+    Ok(())
+}
@@ -128,6 +128,7 @@ fn load_eval_set(dir: &str) -> anyhow::Result<EvalSet> {
         description: "Test cases for evaluating and optimizing agent instructions".to_string(),
         test_files: vec![],
         eval_cases,
+        needs_review: false,
     })
 }
 